@@ -56,6 +56,15 @@ check_permission_failure() {
   assert_not_contains "/abc.txt"
 }
 
+@test "deploy --check-certification verifies all assets" {
+  install_asset assetscanister
+  dd if=/dev/urandom of=src/e2e_project_frontend/assets/large-asset.bin bs=1000000 count=6
+  dfx_start
+  assert_command dfx deploy --check-certification
+  assert_contains "Checking the certification of assets."
+  assert_contains "All assets are certified."
+}
+
 @test "batch id persists through upgrade" {
   install_asset assetscanister
   dfx_start
@@ -861,6 +870,25 @@ check_permission_failure() {
   assert_match '/asset2.bin 1/1'
 }
 
+@test "uploads with configured parallelism and removes the resume file" {
+  install_asset assetscanister
+  dd if=/dev/urandom of=src/e2e_project_frontend/assets/large.bin bs=1000000 count=6
+  jq '.canisters.e2e_project_frontend.upload_parallelism=1' dfx.json | sponge dfx.json
+
+  dfx_start
+  assert_command dfx deploy
+  assert_match '/large.bin 4/4'
+  assert_file_not_exists .dfx/local/canisters/e2e_project_frontend/upload-resume.json
+
+  # a resume file for a batch that no longer exists starts a new batch
+  dd if=/dev/urandom of=src/e2e_project_frontend/assets/large.bin bs=1000000 count=6
+  echo "{\"canister_id\":\"$(dfx canister id e2e_project_frontend)\",\"batch_id\":1234,\"chunks\":{}}" >.dfx/local/canisters/e2e_project_frontend/upload-resume.json
+  assert_command dfx deploy
+  assert_contains "Starting batch."
+  assert_match '/large.bin 4/4'
+  assert_file_not_exists .dfx/local/canisters/e2e_project_frontend/upload-resume.json
+}
+
 @test "unsets asset encodings that are removed from project" {
   install_asset assetscanister
 
@@ -934,6 +962,28 @@ check_permission_failure() {
   assert_command_fail dfx canister call --query e2e_project_frontend retrieve '("C")'
 }
 
+@test "can store the bytes of a file passed with --arg-file-as-blob" {
+  install_asset assetscanister
+
+  dfx_start
+  dfx canister create --all
+  dfx build
+  dfx canister install e2e_project_frontend
+
+  printf 'binary\x00\xffcontent' > payload.bin
+  assert_command dfx canister call --update e2e_project_frontend store '(record{key="/payload.bin"; content_type="application/octet-stream"; content_encoding="identity"; content=${payload}})' --arg-file-as-blob payload=payload.bin
+  assert_command dfx canister call --query e2e_project_frontend retrieve '("/payload.bin")' --output idl
+  # shellcheck disable=SC2154
+  assert_eq '(blob "binary\00\ffcontent")' "$stdout"
+
+  assert_command_fail dfx canister call --update e2e_project_frontend store '(record{key="/payload.bin"; content_type="application/octet-stream"; content_encoding="identity"; content=blob ""})' --arg-file-as-blob payload=payload.bin
+  assert_contains 'The argument has no placeholder ${payload}'
+
+  head -c 2100000 /dev/zero > large.bin
+  assert_command_fail dfx canister call --update e2e_project_frontend store '(record{key="/large.bin"; content_type="application/octet-stream"; content_encoding="identity"; content=${large}})' --arg-file-as-blob large=large.bin
+  assert_contains "more than the 2000000 bytes that fit in a call"
+}
+
 @test "asset canister supports http requests" {
   install_asset assetscanister
 
@@ -1779,3 +1829,25 @@ WARN: {
   assert_command dfx canister call e2e_project_frontend list_permitted '(record { permission = variant { ManagePermissions }; })'
   assert_match "$(dfx identity get-principal)"
 }
+
+@test "set permissions through the asset-permissions init preset" {
+  dfx_start
+  dfx deploy
+
+  dfx identity new alice --storage-mode plaintext
+  ALICE="$(dfx --identity alice identity get-principal)"
+
+  assert_command dfx canister install e2e_project_frontend --upgrade-unchanged --mode upgrade \
+    --init-preset asset-permissions \
+    --preset-arg "prepare=${ALICE}" \
+    --preset-arg "commit=$(dfx identity get-principal),aaaaa-aa" \
+    --preset-arg "manage-permissions=$(dfx identity get-principal)"
+  assert_command dfx canister call e2e_project_frontend list_permitted '(record { permission = variant { Prepare }; })'
+  assert_match "${ALICE}"
+  assert_command dfx canister call e2e_project_frontend list_permitted '(record { permission = variant { Commit }; })'
+  assert_match "$(dfx identity get-principal)"
+  assert_match '"aaaaa-aa"'
+
+  assert_command_fail dfx canister install e2e_project_frontend --mode upgrade --init-preset asset-permissions --preset-arg "owner=aaaaa-aa"
+  assert_contains "The asset-permissions preset has no parameter 'owner'"
+}
@@ -241,6 +241,24 @@ teardown() {
   assert_command diff main.did installed.did
 }
 
+@test "custom canister build commands can use placeholders" {
+  install_asset custom_canister
+  install_asset wasm/identity
+  dfx_start
+  dfx canister create --all
+  jq '.canisters.custom.build="echo BUILT ${canister_id:custom2} ON ${network} FOR ${env:BUILD_USER} IN ${HOME}"' dfx.json | sponge dfx.json
+
+  BUILD_USER=alice assert_command dfx build custom
+  assert_contains "BUILT $(dfx canister id custom2) ON local FOR alice IN \${HOME}"
+
+  assert_command_fail dfx build custom
+  assert_contains "refers to the environment variable 'BUILD_USER'"
+
+  jq '.canisters.custom.build="echo ${canister_id:missing}"' dfx.json | sponge dfx.json
+  assert_command_fail dfx build custom
+  assert_contains "a canister with the name 'missing' was not found in the current project"
+}
+
 @test "upgrade check writes .old.did under .dfx" {
   install_asset custom_canister
   install_asset wasm/identity
@@ -248,7 +266,7 @@ teardown() {
   dfx_start
   dfx deploy
 
-  echo yes | dfx deploy --mode=reinstall custom
+  echo custom | dfx deploy --mode=reinstall custom
 
   # dfx intentionally leaves this file after creating it for comparison,
   # so that the developer can look at the differences too.
@@ -271,6 +289,36 @@ teardown() {
   assert_match CUSTOM_CANISTER2_BUILD_DONE
 }
 
+@test "custom canister build commands run in the build_container" {
+  install_asset custom_canister
+  install_asset wasm/identity
+  dfx_start
+  dfx canister create custom
+
+  # stands in for docker: prints its arguments and runs the command on the host
+  mkdir bin
+  cat >bin/docker <<'EOF'
+#!/usr/bin/env bash
+echo "DOCKER $*"
+while [ $# -gt 0 ]; do
+  case "$1" in
+    run|--rm) shift ;;
+    --volume|--workdir|--env|--user) shift 2 ;;
+    *) break ;;
+  esac
+done
+shift
+exec "$@"
+EOF
+  chmod +x bin/docker
+  jq '.canisters.custom.build_container.image="example/builder:1.0"' dfx.json | sponge dfx.json
+
+  PATH="$(pwd)/bin:$PATH" assert_command dfx build custom
+  assert_match "DOCKER run --rm --volume .* example/builder:1.0 echo CUSTOM_CANISTER_BUILD_DONE"
+  assert_contains "--env CANISTER_ID=$(dfx canister id custom)"
+  assert_match "CUSTOM_CANISTER_BUILD_DONE"
+}
+
 @test "build succeeds with network parameter" {
   dfx_start
   dfx canister create --all --network local
@@ -320,3 +368,59 @@ teardown() {
   assert_command ls .dfx/actuallylocal/canisters/e2e_project_backend/
   assert_command ls .dfx/actuallylocal/canisters/e2e_project_backend/e2e_project_backend.wasm
 }
+
+@test "test-coverage profile builds Motoko canisters in debug mode" {
+  dfx_start
+  dfx canister create --all
+  assert_command dfx build e2e_project_backend --profile test-coverage
+  assert_contains "Coverage instrumentation is not supported for Motoko canisters"
+
+  assert_command_fail dfx build e2e_project_backend --profile coverage
+  assert_contains "Must be one of debug, release or test-coverage."
+}
+
+@test "canister coverage fails for canisters that are not instrumented" {
+  dfx_start
+  dfx deploy e2e_project_backend
+  assert_command_fail dfx canister coverage e2e_project_backend
+  assert_contains "Failed to collect the coverage of canister"
+  assert_command_fail dfx canister coverage --all
+  assert_contains "No canister reported its coverage."
+}
+
+@test "builds canisters that miss the build cache and warns when they cannot be uploaded" {
+  mkdir www
+  start_webserver --directory www
+
+  jq '.build_cache.url="http://localhost:'"$E2E_WEB_SERVER_PORT"'/cache"' dfx.json | sponge dfx.json
+  jq '.canisters.e2e_project_backend.build_inputs=["src/e2e_project_backend"]' dfx.json | sponge dfx.json
+
+  assert_command dfx build --check e2e_project_backend
+  assert_contains "Failed to upload canister e2e_project_backend to the build cache"
+  assert_file_exists .dfx/local/canisters/e2e_project_backend/e2e_project_backend.wasm
+
+  jq '.build_cache.read_only=true' dfx.json | sponge dfx.json
+  assert_command dfx build --check e2e_project_backend
+  assert_not_contains "build cache"
+}
+
+@test "copies canister artifacts to the output directory" {
+  jq '.defaults.build.output_dir="artifacts"' dfx.json | sponge dfx.json
+
+  assert_command dfx build --check e2e_project_backend
+  assert_file_exists artifacts/e2e_project_backend/e2e_project_backend.wasm
+  assert_file_exists artifacts/e2e_project_backend/e2e_project_backend.did
+  assert_command jq -r '.wasm_sha256' artifacts/e2e_project_backend/metadata.json
+  assert_eq "$(sha256sum artifacts/e2e_project_backend/e2e_project_backend.wasm | cut -d' ' -f1)"
+  assert_command jq -r '.layout_version' artifacts/e2e_project_backend/metadata.json
+  assert_eq "1"
+
+  jq '.canisters.e2e_project_backend.output_dir="backend-artifacts"' dfx.json | sponge dfx.json
+  assert_command dfx build --check e2e_project_backend
+  assert_file_exists backend-artifacts/e2e_project_backend.wasm
+  assert_file_exists backend-artifacts/metadata.json
+
+  assert_command dfx build --check e2e_project_backend --output-dir release
+  assert_file_exists release/e2e_project_backend/e2e_project_backend.wasm
+  assert_file_exists release/e2e_project_backend/metadata.json
+}
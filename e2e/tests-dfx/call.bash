@@ -55,6 +55,56 @@ teardown() {
   assert_match '("Hello, Names are difficult!")'
 }
 
+@test "call subcommand accepts canister aliases from dfx.json" {
+  install_asset greet
+  dfx_start
+  dfx deploy hello_backend
+  CANISTER_ID=$(dfx canister id hello_backend)
+
+  jq '.aliases.greeter="'"$CANISTER_ID"'"' dfx.json | sponge dfx.json
+  assert_command dfx canister call greeter greet '("Alias")'
+  assert_match '("Hello, Alias!")'
+  assert_command dfx canister status greeter
+  assert_contains "Status: Running"
+
+  # aliases by network are only defined on their networks
+  jq '.aliases.greeter={"ic": "'"$CANISTER_ID"'"}' dfx.json | sponge dfx.json
+  assert_command_fail dfx canister call greeter greet '("Alias")'
+  assert_contains "Cannot find canister id"
+
+  # canisters of the project take precedence
+  jq '.aliases.hello_backend="ryjl3-tyaaa-aaaaa-aaaba-cai"' dfx.json | sponge dfx.json
+  assert_command dfx canister id hello_backend
+  assert_eq "$CANISTER_ID"
+
+  # an alias does not stand in for a canister of the project that was not created yet
+  jq '.aliases.hello_frontend="'"$CANISTER_ID"'"' dfx.json | sponge dfx.json
+  assert_command dfx canister create hello_frontend
+  assert_not_contains "already created"
+  assert_command dfx canister id hello_frontend
+  assert_neq "$CANISTER_ID"
+}
+
+@test "call fails when the response exceeds its latency or size budget" {
+  install_asset greet
+  dfx_start
+  dfx canister create --all
+  dfx build
+  dfx canister install hello_backend
+
+  assert_command_fail dfx canister call hello_backend greet '("budget")' --max-response-bytes 1
+  assert_contains '("Hello, budget!")'
+  assert_contains "which exceeds the maximum of 1 bytes"
+
+  assert_command_fail dfx canister call hello_backend greet '("budget")' --max-latency 1ns
+  assert_contains "exceeds the maximum latency"
+
+  assert_command dfx canister call hello_backend greet '("budget")' --max-latency 1m --max-response-bytes 1000
+  assert_match '("Hello, budget!")'
+
+  assert_command_fail dfx canister call hello_backend greet '("budget")' --async --max-latency 1m
+}
+
 @test "call subcommand accepts raw argument" {
   install_asset greet
   dfx_start
@@ -92,6 +142,21 @@ teardown() {
   rm "$TMP_NAME_FILE"
 }
 
+@test "call subcommand splices Candid from files into the argument" {
+  install_asset greet
+  dfx_start
+  dfx canister create --all
+  dfx build
+  dfx canister install hello_backend
+  printf '"spliced"' > name.did
+  assert_command dfx canister call hello_backend greet '(${name})' --arg-file-as-candid name=name.did
+  assert_match '("Hello, spliced!")'
+  assert_command dfx canister call hello_backend greet --arg-file-as-candid name=name.did
+  assert_match '("Hello, spliced!")'
+  assert_command_fail dfx canister call hello_backend greet --arg-file-as-candid name=name.did --arg-file-as-candid other=name.did
+  assert_contains 'Specify the argument'
+}
+
 @test "call random value (pattern)" {
   install_asset greet
   dfx_start
@@ -151,3 +216,66 @@ teardown() {
     assert_match '("Hello, you!")'
   )
 }
+
+@test "benchmark measures the cycles of update calls" {
+  install_asset counter
+  dfx_start
+  dfx deploy
+
+  assert_command dfx canister benchmark hello_backend inc -n 5
+  assert_contains "Measuring 5 calls of inc"
+  assert_contains "Cycles:  mean"
+  assert_contains "Instructions (estimated): mean"
+  assert_contains "Latency: mean"
+  assert_command dfx canister call hello_backend read
+  assert_eq "(6 : nat)"
+
+  assert_command dfx canister benchmark hello_backend write '(42)' -n 2 --warmup 0
+  assert_command dfx canister call hello_backend read
+  assert_eq "(42 : nat)"
+
+  assert_command dfx canister benchmark hello_backend inc -n 2 --warmup 0 --wallet "$(dfx identity get-wallet)"
+  assert_contains "Instructions (estimated): mean"
+  assert_command dfx canister call hello_backend read
+  assert_eq "(44 : nat)"
+
+  assert_command_fail dfx canister benchmark hello_backend read
+  assert_contains "read is a query method"
+}
+
+@test "call estimates the cycles of update calls" {
+  install_asset counter
+  dfx_start
+  dfx deploy
+
+  assert_command dfx canister call hello_backend inc --estimate-cycles
+  assert_contains "Estimated cost of the call in cycles, on a 13-node subnet:"
+  assert_contains "Charged to hello_backend:"
+  assert_match "Ingress message reception \(9 bytes\) +1218000"
+  assert_match "Update execution +5000000"
+  assert_match "Total +6218000, plus 4 per 10 instructions executed"
+  assert_command dfx canister call hello_backend read
+  assert_eq "(1 : nat)"
+
+  assert_command dfx canister call hello_backend inc --estimate-cycles --wallet "$(dfx identity get-wallet)"
+  assert_contains "Charged to the wallet $(dfx identity get-wallet):"
+  assert_match "Call to hello_backend \(9 bytes\) +269000"
+
+  assert_command dfx canister call hello_backend read --estimate-cycles
+  assert_contains "Query calls do not cost cycles."
+}
+
+@test "call a query method as an update or an update method as a query" {
+  install_asset greet
+  dfx_start
+  dfx deploy hello_backend
+
+  assert_command dfx canister call hello_backend greet '("Certified")' --update
+  assert_eq '("Hello, Certified!")'
+
+  assert_command dfx canister call hello_backend greet '("Uncertified")' --query
+  assert_eq '("Hello, Uncertified!")'
+
+  assert_command_fail dfx canister call hello_backend greet '("Both")' --query --update
+  assert_contains "cannot be used with"
+}
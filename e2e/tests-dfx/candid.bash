@@ -0,0 +1,68 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  cat > service.did <<'DID'
+type Profile = record { name : text; age : nat8 };
+service : {
+  greet : (text) -> (text);
+  set_profile : (Profile) -> ();
+}
+DID
+}
+
+teardown() {
+  standard_teardown
+}
+
+@test "dfx candid check accepts a valid file" {
+  assert_command dfx candid check service.did
+}
+
+@test "dfx candid check rejects an invalid file" {
+  echo 'service : { greet : (undefined_type) -> () }' > invalid.did
+  assert_command_fail dfx candid check invalid.did
+}
+
+@test "dfx candid check detects incompatible upgrades" {
+  cat > upgrade.did <<'DID'
+type Profile = record { name : text; age : nat8 };
+service : {
+  greet : (text) -> (text);
+  set_profile : (Profile) -> ();
+  get_profile : () -> (Profile) query;
+}
+DID
+  assert_command dfx candid check upgrade.did --previous service.did
+
+  echo 'service : { greet : (nat) -> (text) }' > breaking.did
+  assert_command_fail dfx candid check breaking.did --previous service.did
+  assert_match "not a backwards-compatible upgrade"
+}
+
+@test "dfx candid bind generates bindings" {
+  assert_command dfx candid bind service.did --target mo
+  assert_match "public type Profile"
+  assert_command dfx candid bind service.did --target ts
+  assert_match "export interface _SERVICE"
+  assert_command dfx candid bind service.did --target js --outfile service.js
+  assert_file_exists service.js
+}
+
+@test "dfx candid random generates method arguments" {
+  assert_command dfx candid random --types service.did --method set_profile --seed 42
+  assert_match "record"
+  first="$stdout"
+  assert_command dfx candid random --types service.did --method set_profile --seed 42
+  assert_eq "$first" "$stdout"
+
+  assert_command_fail dfx candid random --types service.did --method missing
+}
+
+@test "dfx candid random generates values for a tuple of types" {
+  assert_command dfx candid random --args '(nat, opt text)' --output raw
+  assert_match "^4449444c"
+}
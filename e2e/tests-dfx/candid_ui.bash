@@ -16,6 +16,27 @@ teardown() {
 
 @test "Candid UI" {
   dfx_start
+  ID=a4gq6-oaaaa-aaaab-qaa4q-cai
+  PORT=$(get_webserver_port)
+  assert_command curl http://localhost:"$PORT"/?canisterId="$ID"
+  assert_match "Candid UI"
+}
+
+@test "canister open prints the Candid UI url" {
+  dfx_start
+  dfx deploy
+  PORT=$(get_webserver_port)
+  BACKEND_ID=$(dfx canister id hello_backend)
+  assert_command dfx canister open hello_backend --print
+  assert_eq "http://a4gq6-oaaaa-aaaab-qaa4q-cai.localhost:${PORT}/?id=${BACKEND_ID}"
+}
+
+@test "Candid UI can be disabled" {
+  define_project_network
+  jq '.networks.local.candid_ui.enabled=false' dfx.json | sponge dfx.json
+  dfx_start
+  assert_command_fail dfx canister info a4gq6-oaaaa-aaaab-qaa4q-cai
+
   dfx deploy
   ID=$(dfx canister id __Candid_UI)
   PORT=$(get_webserver_port)
@@ -0,0 +1,31 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "canister export-state and import-state require a stopped canister" {
+  dfx_start
+  dfx deploy hello_backend
+
+  assert_command_fail dfx canister export-state hello_backend state
+  assert_contains "Canister hello_backend is not stopped"
+  assert_file_not_exists state
+
+  mkdir state
+  assert_command_fail dfx canister export-state hello_backend state
+  assert_contains "state already exists"
+
+  assert_command_fail dfx canister import-state hello_backend state --yes
+  assert_contains "metadata.json"
+}
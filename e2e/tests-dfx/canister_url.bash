@@ -0,0 +1,60 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new_assets hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "canister url prints the local urls" {
+  dfx_start
+  dfx deploy
+  port=$(get_webserver_port)
+  frontend_id=$(dfx canister id hello_frontend)
+  backend_id=$(dfx canister id hello_backend)
+
+  assert_command dfx canister url hello_frontend
+  assert_contains "Frontend: http://${frontend_id}.localhost:${port}/"
+  assert_contains "Raw: http://${frontend_id}.raw.localhost:${port}/"
+  assert_not_contains "Candid UI"
+
+  assert_command dfx canister url hello_backend --candid
+  assert_match "^http://.*\.localhost:${port}/\?id=${backend_id}$"
+
+  assert_command_fail dfx canister url hello_backend --frontend
+  assert_contains "Canister hello_backend has no frontend."
+}
+
+@test "canister url prints the custom domains on mainnet" {
+  echo "{}" | jq '.hello_frontend.ic = "bd3sg-teaaa-aaaaa-qaaba-cai"' >canister_ids.json
+  dfx domains add example.com --canister hello_frontend
+
+  assert_command dfx canister url hello_frontend --network ic
+  assert_contains "Frontend: https://bd3sg-teaaa-aaaaa-qaaba-cai.icp0.io/"
+  assert_contains "Raw: https://bd3sg-teaaa-aaaaa-qaaba-cai.raw.icp0.io/"
+  assert_contains "Custom domain: https://example.com/"
+
+  assert_command dfx canister url bd3sg-teaaa-aaaaa-qaaba-cai --network ic --frontend
+  assert_eq "https://bd3sg-teaaa-aaaaa-qaaba-cai.icp0.io/"
+}
+
+@test "canister url prints the custom domains of the local network" {
+  create_networks_json
+  jq '.local.proxy.custom_domains["app.localhost"] = "bd3sg-teaaa-aaaaa-qaaba-cai"' "$E2E_NETWORKS_JSON" | sponge "$E2E_NETWORKS_JSON"
+  dfx_start
+  port=$(get_webserver_port)
+
+  assert_command dfx canister url bd3sg-teaaa-aaaaa-qaaba-cai
+  assert_contains "Custom domain: http://app.localhost:${port}/"
+
+  assert_command dfx canister url aaaaa-aa
+  assert_not_contains "Custom domain"
+}
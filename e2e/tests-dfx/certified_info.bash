@@ -36,4 +36,52 @@ Module hash: None"
   assert_match \
 "Controllers: ($WALLET_ID $SELF_ID|$SELF_ID $WALLET_ID)
 Module hash: $(HASH)"
+  assert_contains "Candid interface: available"
+  assert_contains "Balance: "
+}
+
+@test "canister info works for canisters outside of a project" {
+  dfx_start
+  dfx canister create hello_backend
+  dfx canister install hello_backend
+  CANISTER_ID="$(dfx canister id hello_backend)"
+
+  dfx identity new --storage-mode plaintext alice
+  cd ..
+  assert_command dfx canister info "$CANISTER_ID" --identity alice
+  assert_contains "Module hash: 0x"
+  assert_contains "Candid interface: available"
+  assert_not_contains "Balance: "
+}
+
+@test "canister watch-metadata reads the state of a canister when it starts" {
+  dfx_start
+  dfx deploy hello_backend
+
+  # The command only exits when timeout stops it, which it does with status 124.
+  assert_command_fail timeout 5s dfx canister watch-metadata hello_backend --interval 1s
+  assert_eq 124 "$status"
+  assert_contains "Watching canister $(dfx canister id hello_backend) every 1s."
+  assert_not_contains "Failed to read the state"
+}
+
+@test "canister watch-metadata reports a change of the module hash" {
+  dfx_start
+  dfx canister create hello_backend
+  dfx build hello_backend
+  CANISTER_ID="$(dfx canister id hello_backend)"
+
+  dfx canister watch-metadata hello_backend --interval 1s --until-change >changes.json 2>watch.log &
+  WATCH_PID=$!
+  sleep 3
+  dfx canister install hello_backend
+  wait "$WATCH_PID"
+
+  assert_command jq -r 'select(.field == "module_hash") | .previous, .current' changes.json
+  assert_match "null
+0x[0-9a-f]{64}"
+  assert_command jq -r .canister_id changes.json
+  assert_contains "$CANISTER_ID"
+  assert_command cat watch.log
+  assert_contains "Watching canister $CANISTER_ID every 1s."
 }
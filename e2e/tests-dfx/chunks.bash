@@ -0,0 +1,35 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "canister chunks list and clear an empty chunk store" {
+  dfx_start
+  dfx deploy hello_backend
+
+  assert_command dfx canister chunks list hello_backend
+  assert_match "hello_backend \(.*\): 0 chunks"
+
+  assert_command dfx canister chunks list hello_backend --json
+  assert_command jq -r '.[0].chunks' <<<"$stdout"
+  assert_eq "0"
+
+  assert_command dfx canister chunks clear hello_backend
+  assert_match "Clearing the chunk store of canister hello_backend"
+}
+
+@test "canister chunks requires a canister or --all" {
+  assert_command_fail dfx canister chunks list
+  assert_command_fail dfx canister chunks clear
+}
@@ -0,0 +1,66 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  standard_teardown
+}
+
+@test "dfx config get prints values of dfx.json" {
+  assert_command dfx config get canisters.hello_backend.type
+  assert_eq '"motoko"'
+
+  assert_command dfx config get /canisters/hello_backend/type
+  assert_eq '"motoko"'
+
+  assert_command_fail dfx config get canisters.hello_backend.nothing_here
+  assert_contains "There is no value at 'canisters.hello_backend.nothing_here' in dfx.json."
+}
+
+@test "dfx config set changes dfx.json" {
+  assert_command dfx config set canisters.hello_backend.initialization_values.compute_allocation 50
+  assert_command jq -r .canisters.hello_backend.initialization_values.compute_allocation dfx.json
+  assert_eq "50"
+
+  assert_command dfx config set canisters.hello_backend.main src/hello_backend/app.mo
+  assert_command jq -r .canisters.hello_backend.main dfx.json
+  assert_eq "src/hello_backend/app.mo"
+
+  assert_command dfx config set --string /canisters/hello_backend/args 5
+  assert_command jq .canisters.hello_backend.args dfx.json
+  assert_eq '"5"'
+
+  assert_command dfx config unset canisters.hello_backend.initialization_values
+  assert_command jq .canisters.hello_backend.initialization_values dfx.json
+  assert_eq "null"
+}
+
+@test "dfx config set and unset keep the order of keys in dfx.json" {
+  jq -c 'keys_unsorted' dfx.json >keys.before
+  jq -c '.canisters | keys_unsorted' dfx.json >canisters.before
+
+  assert_command dfx config set canisters.hello_backend.initialization_values.compute_allocation 50
+  assert_command dfx config unset canisters.hello_backend.initialization_values
+
+  assert_command jq -c 'keys_unsorted' dfx.json
+  assert_eq "$(cat keys.before)"
+  assert_command jq -c '.canisters | keys_unsorted' dfx.json
+  assert_eq "$(cat canisters.before)"
+}
+
+@test "dfx config set does not save an invalid dfx.json" {
+  cp dfx.json dfx.json.before
+
+  assert_command_fail dfx config set canisters.hello_backend.initialization_values.compute_allocation lots
+  assert_contains "would make dfx.json invalid"
+  assert_command_fail dfx config set canisters.hello_backend.type.name motoko
+  assert_contains "is neither an object nor an array"
+
+  assert_command diff dfx.json dfx.json.before
+}
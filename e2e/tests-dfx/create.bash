@@ -35,6 +35,22 @@ teardown() {
   assert_command dfx canister create --all
 }
 
+@test "create --all creates the other canisters when one fails, and reports it" {
+  dfx_start
+  # nojwb-ieaaa-aaaaa-aaaaa-cai is the canister ID of (u64::MAX / 2 + 1), which the local subnet does not host
+  jq '.canisters.e2e_project_backend.specified_id="nojwb-ieaaa-aaaaa-aaaaa-cai"' dfx.json | sponge dfx.json
+  assert_command_fail dfx canister create --all --concurrency 2
+  assert_contains "Failed to create 1 of 2 canister(s): e2e_project_backend."
+  assert_contains "Canisters that succeeded: e2e_project_frontend"
+  assert_command dfx canister id e2e_project_frontend
+  assert_command_fail dfx canister id e2e_project_backend
+}
+
+@test "create --concurrency requires --all" {
+  assert_command_fail dfx canister create e2e_project_backend --concurrency 2
+  assert_contains "--all"
+}
+
 @test "create succeeds with --specified-id" {
   dfx_start
   assert_command dfx canister create e2e_project_backend --specified-id n5n4y-3aaaa-aaaaa-p777q-cai
@@ -109,6 +125,14 @@ teardown() {
   assert_match "Controllers: $PRINCIPAL"
 }
 
+@test "create with --no-wallet warns that a subnet selection is ignored on a local network" {
+  dfx_start
+  SUBNET_ID="5kdm2-62fc6-fwnja-hutkz-ycsnm-4z33i-woh43-4cenu-ev7mi-gii6t-4ae"
+  assert_command dfx canister create e2e_project_backend --no-wallet --subnet "$SUBNET_ID"
+  assert_contains "Ignoring the subnet selection"
+  assert_command dfx canister id e2e_project_backend
+}
+
 @test "build fails without create" {
   dfx_start
   assert_command_fail dfx build
@@ -0,0 +1,52 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "create-fixture creates a project with the requested canisters" {
+  assert_command dfx internal create-fixture --directory fixture-root --name proj \
+    --canister backend:custom --canister frontend:assets:backend
+  assert_command jq -r .project_dir <<<"$stdout"
+  assert_match "fixture-root/proj"
+  assert_file_exists fixture-root/proj/src/backend/backend.wasm
+  assert_file_exists fixture-root/proj/src/frontend/assets/index.html
+  assert_command jq -r '.canisters.frontend.dependencies[0]' fixture-root/proj/dfx.json
+  assert_eq "backend"
+}
+
+@test "create-fixture identities are deterministic" {
+  assert_command dfx internal create-fixture --directory first --identity alice
+  first_alice=$(jq -r .identities.alice <<<"$stdout")
+  assert_command dfx internal create-fixture --directory second --identity alice --identity bob
+  assert_command jq -r .identities.alice <<<"$stdout"
+  assert_eq "$first_alice"
+
+  cd second/fixture
+  export DFX_CONFIG_ROOT="$E2E_TEMP_DIR/working-dir/second/config-root"
+  assert_command dfx identity whoami
+  assert_eq "alice"
+  assert_command dfx identity get-principal
+  assert_eq "$first_alice"
+}
+
+@test "create-fixture preconfigures the local network" {
+  assert_command dfx internal create-fixture --directory fixture-root --canister backend:custom
+  cd fixture-root/fixture
+  export DFX_CONFIG_ROOT="$E2E_TEMP_DIR/working-dir/fixture-root/config-root"
+  dfx_start
+  assert_command dfx deploy backend
+}
+
+@test "create-fixture rejects unknown dependencies" {
+  assert_command_fail dfx internal create-fixture --canister frontend:assets:backend
+  assert_match "Canister 'frontend' depends on unknown canister 'backend'."
+}
@@ -452,6 +452,28 @@ current_time_nanoseconds() {
   assert_eq "2399799800000 cycles."
 }
 
+@test "top-up falls back to the cycles wallet" {
+  dfx_new
+  add_cycles_ledger_canisters_to_project
+  install_cycles_ledger_canisters
+
+  deploy_cycles_ledger
+
+  assert_command dfx deploy
+  assert_command dfx identity get-wallet --identity alice
+
+  assert_command dfx cycles balance --precise --identity alice
+  assert_eq "0 cycles."
+  assert_command dfx canister status e2e_project_backend
+  assert_contains "Balance: 3_100_000_000_000 Cycles"
+
+  assert_command dfx cycles top-up e2e_project_backend 100000 --identity alice --yes
+  assert_contains "Deposited 100000 cycles from cycles wallet"
+
+  assert_command dfx canister status e2e_project_backend
+  assert_contains "Balance: 3_100_000_100_000 Cycles"
+}
+
 @test "howto" {
   # This is the equivalent of https://www.notion.so/dfinityorg/How-to-install-and-test-the-cycles-ledger-521c9f3c410f4a438514a03e35464299
   ALICE=$(dfx identity get-principal --identity alice)
@@ -564,7 +586,7 @@ current_time_nanoseconds() {
   assert_command dfx cycles balance --precise
   assert_eq "11399800000000 cycles."
   # reset and forget about canister. If --created-at-time is a valid idempotency key we should end up with the same canister id
-  dfx canister uninstall-code e2e_project_backend
+  dfx canister uninstall-code e2e_project_backend --yes
   rm .dfx/local/canister_ids.json
   assert_command dfx deploy e2e_project_backend --with-cycles 1T --created-at-time "$t" -vv
   assert_command dfx canister id e2e_project_backend
@@ -589,6 +611,34 @@ current_time_nanoseconds() {
   assert_eq "9399600000000 cycles."
 }
 
+@test "wallet-less deployment with the cycles ledger" {
+  dfx_new temporary
+  add_cycles_ledger_canisters_to_project
+  install_cycles_ledger_canisters
+
+  ALICE=$(dfx identity get-principal --identity alice)
+
+  assert_command deploy_cycles_ledger
+  assert_command dfx canister call depositor deposit "(record {to = record{owner = principal \"$ALICE\";};cycles = 13_400_000_000_000;})" --identity cycle-giver
+
+  cd ..
+  dfx_new
+  jq '.defaults.cycles_ledger.enabled=true' dfx.json | sponge dfx.json
+  dfx identity use alice
+
+  assert_command dfx deploy e2e_project_backend --with-cycles 1T
+  assert_command dfx canister info e2e_project_backend
+  assert_match "Controllers: $ALICE
+Module hash"
+  assert_command dfx cycles balance --precise
+  assert_eq "12399900000000 cycles."
+
+  assert_command dfx canister deposit-cycles 100000 e2e_project_backend
+  assert_contains "from the cycles ledger"
+  assert_command dfx cycles balance --precise
+  assert_eq "12399799900000 cycles."
+}
+
 @test "canister deletion" {
   skip "can't be properly tested with feature flag turned off (CYCLES_LEDGER_ENABLED). TODO(SDK-1331): re-enable this test"
   dfx_new temporary
@@ -748,3 +798,21 @@ current_time_nanoseconds() {
   # shellcheck disable=SC2154
   assert_contains "Transfer sent at block height 12"
 }
+
+@test "cycles fabricate" {
+  ALICE=$(dfx identity get-principal --identity alice)
+  ALICE_SUBACCT1="000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+
+  deploy_cycles_ledger
+
+  assert_command dfx cycles fabricate 5T --identity alice
+  assert_contains "Fabricated 5000000000000 cycles into the account of $ALICE."
+  assert_command dfx cycles balance --identity alice --precise
+  assert_eq "5000000000000 cycles."
+
+  assert_command dfx cycles fabricate 1T --to "$ALICE" --to-subaccount "$ALICE_SUBACCT1" --identity bob
+  assert_command dfx cycles balance --identity alice --subaccount "$ALICE_SUBACCT1" --precise
+  assert_eq "1000000000000 cycles."
+
+  assert_command_fail dfx cycles fabricate 1T --network ic
+}
@@ -51,3 +51,28 @@ teardown() {
   assert_command_fail dfx canister status "$id"
   assert_contains "Canister $id not found"
 }
+
+@test "delete --dry-run reports the withdrawal without deleting" {
+  dfx_start
+  dfx deploy e2e_project_backend
+  dfx canister stop e2e_project_backend
+  assert_command dfx canister delete e2e_project_backend --dry-run
+  assert_contains "has a balance of"
+  assert_contains "cycles would be withdrawn to canister $(dfx identity get-wallet)"
+  assert_contains "Dry run: canister e2e_project_backend was not deleted."
+  assert_command dfx canister info e2e_project_backend
+
+  assert_command dfx canister delete e2e_project_backend --dry-run --no-withdrawal
+  assert_contains "Deleting the canister would burn its balance."
+}
+
+@test "delete can withdraw cycles to another canister of the project" {
+  dfx_start
+  dfx deploy
+  dfx canister stop e2e_project_backend
+  before=$(dfx canister status e2e_project_frontend 2>&1 | grep "Balance:" | tr -dc '0-9')
+  assert_command dfx canister delete e2e_project_backend --withdraw-cycles-to-canister e2e_project_frontend
+  assert_contains "Successfully withdrew"
+  after=$(dfx canister status e2e_project_frontend 2>&1 | grep "Balance:" | tr -dc '0-9')
+  (( after > before ))
+}
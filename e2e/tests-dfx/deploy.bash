@@ -109,6 +109,27 @@ teardown() {
   assert_match "Hello, dfx!"
 }
 
+@test "deploy --only-dependencies deploys the dependencies but not the canister" {
+  dfx_start
+  assert_command dfx deploy hello_frontend --only-dependencies
+  assert_command dfx canister call hello_backend greet '("dfx")'
+  assert_match "Hello, dfx!"
+  assert_command_fail dfx canister id hello_frontend
+
+  assert_command_fail dfx deploy --only-dependencies
+}
+
+@test "deploy --exclude skips installing the excluded canisters" {
+  dfx_start
+  assert_command dfx deploy --exclude hello_frontend
+  assert_command dfx canister call hello_backend greet '("dfx")'
+  assert_match "Hello, dfx!"
+  assert_command_fail dfx canister id hello_frontend
+
+  assert_command_fail dfx deploy --exclude no_such_canister
+  assert_contains "Cannot exclude canister no_such_canister: it is not defined in dfx.json."
+}
+
 @test "deploy succeeds if init_arg is defined in dfx.json" {
   install_asset deploy_deps
   dfx_start
@@ -125,6 +146,39 @@ teardown() {
   assert_match "Hello, icp!"
 }
 
+@test "deploy uses the init_arg for the network" {
+  install_asset deploy_deps
+  dfx_start
+  jq '.canisters.dependency.init_arg={"local": "(\"local\")", "ic": "(\"ic\")"}' dfx.json | sponge dfx.json
+  assert_command dfx deploy dependency
+  assert_command dfx canister call dependency greet
+  assert_match "Hello, local!"
+
+  # init_arg for other networks are checked against the init type as well
+  jq '.canisters.dependency.init_arg.ic="(42)"' dfx.json | sponge dfx.json
+  assert_command_fail dfx deploy dependency
+  assert_contains "The init_arg for network 'ic' does not match the init type"
+}
+
+@test "deploy uses init_arg from an environment overlay" {
+  install_asset deploy_deps
+  dfx_start
+  jq '.canisters.dependency.init_arg="(\"dfx\")"' dfx.json | sponge dfx.json
+  jq '.envs.staging.canisters.dependency.init_arg="(\"staging\")"' dfx.json | sponge dfx.json
+  echo '{"canisters": {"dependency": {"init_arg": "(\"production\")"}}}' > dfx.production.json
+
+  assert_command dfx deploy dependency --env staging
+  assert_command dfx canister call dependency greet
+  assert_match "Hello, staging!"
+
+  assert_command dfx deploy dependency --mode reinstall --yes --env production
+  assert_command dfx canister call dependency greet
+  assert_match "Hello, production!"
+
+  assert_command_fail dfx deploy dependency --env unknown
+  assert_contains "Unknown environment 'unknown'"
+}
+
 @test "reinstalling a single Motoko canister with imported dependency works" {
   install_asset import_canister
   dfx_start
@@ -181,7 +235,7 @@ teardown() {
   dfx_start
   assert_command dfx deploy
   BUILD_HASH="0x$(sha256sum .dfx/local/canisters/hello_backend/hello_backend.wasm.gz | cut -d " " -f 1)"
-  ONCHAIN_HASH="$(dfx canister info hello_backend | tail -n 1 | cut -d " " -f 3)"
+  ONCHAIN_HASH="$(dfx canister info hello_backend | grep "Module hash" | cut -d " " -f 3)"
   assert_eq "$BUILD_HASH" "$ONCHAIN_HASH"
 }
 
@@ -234,3 +288,80 @@ teardown() {
   assert_command dfx canister call fake-cmc last_create_canister_args
   assert_contains 'subnet_type = opt "custom_subnet_type"'
 }
+
+@test "deploy reports progress as JSON events" {
+  dfx_start
+  assert_command dfx deploy hello_backend --log-format json
+  # shellcheck disable=SC2154
+  events="$(echo "$stderr" | grep '^{')"
+
+  assert_command jq -r 'select(.event == "step" and .step == "deploy") | .status' <<<"$events"
+  assert_eq "started
+finished"
+  assert_command jq -r 'select(.event == "step" and .step == "build" and .canister == "hello_backend") | .status' <<<"$events"
+  assert_eq "started
+finished"
+  assert_command jq -r 'select(.event == "step" and .step == "install") | .canister + " " + .status' <<<"$events"
+  assert_eq "hello_backend started
+hello_backend finished"
+
+  # the events are included even when the messages are not
+  assert_command dfx deploy hello_backend --log-format json -qq
+  assert_contains '"event":"step"' "$stderr"
+  assert_not_contains "Building canisters..." "$stderr"
+}
+
+@test "DFX_LOG sets the logging level regardless of the verbosity" {
+  dfx_start
+  assert_command dfx deploy hello_backend
+  assert_contains "Building canisters..." "$stderr"
+
+  DFX_LOG=off assert_command dfx deploy hello_backend
+  assert_not_contains "Building canisters..." "$stderr"
+
+  DFX_LOG=info assert_command dfx deploy hello_backend -qq
+  assert_contains "Building canisters..." "$stderr"
+
+  DFX_LOG=dfx=loud assert_command dfx deploy hello_backend
+  assert_contains "Invalid log level 'loud' in DFX_LOG. Ignoring DFX_LOG." "$stderr"
+  assert_contains "Building canisters..." "$stderr"
+}
+
+@test "deploy --plan shows the changes without deploying" {
+  dfx_start
+
+  assert_command dfx deploy --plan
+  assert_contains "Deploy plan for network 'local':"
+  assert_contains "hello_backend: create, then install"
+  assert_contains "hello_frontend: create, then install"
+  assert_command_fail dfx canister id hello_backend
+
+  dfx deploy
+  backend_id=$(dfx canister id hello_backend)
+
+  assert_command dfx deploy --plan
+  assert_contains "hello_backend (${backend_id}): unchanged 0x"
+  assert_contains "assets are synchronized"
+
+  install_asset greet
+  jq '.canisters.hello_backend.initialization_values.freezing_threshold = "14days"' dfx.json | sponge dfx.json
+  assert_command dfx deploy --plan
+  assert_contains "hello_backend (${backend_id}): upgrade 0x"
+  assert_contains "freezing_threshold: 2_592_000 -> 1_209_600"
+
+  dfx deploy
+  assert_command dfx canister call hello_backend greet '("plan")'
+  assert_eq '("Hello, plan!")'
+}
+
+@test "deploy reports the canisters that were installed before a failure" {
+  dfx_start
+  jq '.canisters.hello_frontend.init_arg="("' dfx.json | sponge dfx.json
+  assert_command_fail dfx deploy
+  assert_contains "Failed to install canister hello_frontend. Already installed: hello_backend."
+  assert_contains "Run the deploy again to resume"
+
+  jq 'del(.canisters.hello_frontend.init_arg)' dfx.json | sponge dfx.json
+  assert_command dfx deploy
+  assert_match "Module hash.*is already installed"
+}
@@ -59,6 +59,25 @@ cleanup_onchain() {
   cd .. || exit
 }
 
+@test "dfx deps graph prints the canister dependencies" {
+  dfx_new_frontend hello
+
+  assert_command dfx deps graph
+  assert_contains '"hello_backend" [label="hello_backend (motoko)"];'
+  assert_contains '"hello_frontend" -> "hello_backend";'
+
+  assert_command dfx deps graph --format json
+  assert_eq "$(echo "$stdout" | jq -c '.edges')" '[{"from":"hello_frontend","to":"hello_backend"}]'
+
+  assert_command dfx deps graph --format mermaid
+  assert_contains "graph TD"
+  assert_contains ' --> '
+
+  jq '.canisters.hello_backend.dependencies=["hello_frontend"]' dfx.json | sponge dfx.json
+  assert_command_fail dfx deps graph
+  assert_contains "Circular canister dependencies: hello_backend -> hello_frontend -> hello_backend"
+}
+
 @test "dfx build can write required metadata for pullable" {
   dfx_start
 
@@ -132,7 +151,7 @@ Failed to download from url: http://example.com/c.wasm."
   cd ../onchain
   dfx build c
   dfx canister install c --mode=reinstall --yes # reinstall the correct canister c
-  dfx canister uninstall-code a
+  dfx canister uninstall-code a --yes
 
   cd ../app
   assert_command_fail dfx deps pull --network local
@@ -78,3 +78,38 @@ teardown() {
   # and also installed the cache itself
   test -f "$(dfx cache show)"/dfx
 }
+
+@test "cache export and import round-trip" {
+  assert_command dfx cache export "$E2E_TEMP_DIR/cache.tar.gz"
+  # shellcheck disable=SC2154
+  sha256="$stdout"
+
+  use_test_specific_cache_root
+  test ! -e "$(dfx cache show)"/moc
+
+  assert_command_fail dfx cache import "$E2E_TEMP_DIR/cache.tar.gz" --sha256 0000
+  assert_contains "but 0000 was expected"
+
+  assert_command dfx cache import "$E2E_TEMP_DIR/cache.tar.gz" --sha256 "$sha256"
+  assert_contains "Imported version"
+  test -x "$(dfx cache show)"/moc
+
+  assert_command_fail dfx cache import "$E2E_TEMP_DIR/cache.tar.gz"
+  assert_contains "is already in the cache"
+  assert_command dfx cache import "$E2E_TEMP_DIR/cache.tar.gz" --force
+}
+
+@test "cache import rejects files that do not match the manifest" {
+  assert_command dfx cache export "$E2E_TEMP_DIR/cache.tar.gz"
+  mkdir "$E2E_TEMP_DIR/unpacked"
+  tar -xzf "$E2E_TEMP_DIR/cache.tar.gz" -C "$E2E_TEMP_DIR/unpacked"
+  version="$(jq -r .version "$E2E_TEMP_DIR/unpacked/manifest.json")"
+  chmod u+w "$E2E_TEMP_DIR/unpacked/$version/moc"
+  echo "garbage" >>"$E2E_TEMP_DIR/unpacked/$version/moc"
+  tar -czf "$E2E_TEMP_DIR/tampered.tar.gz" -C "$E2E_TEMP_DIR/unpacked" .
+
+  use_test_specific_cache_root
+  assert_command_fail dfx cache import "$E2E_TEMP_DIR/tampered.tar.gz"
+  assert_contains "The SHA-256 of moc does not match the manifest."
+  test ! -e "$(dfx cache show)"
+}
@@ -0,0 +1,66 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+}
+
+teardown() {
+  standard_teardown
+}
+
+@test "nothing is recorded unless enabled" {
+  assert_command dfx identity get-principal
+  assert_file_not_exists "$DFX_CONFIG_ROOT/.config/dfx/telemetry/events.jsonl"
+}
+
+@test "records invocations without their arguments" {
+  assert_command dfx diagnostics enable
+  assert_contains "Nothing is sent anywhere."
+
+  assert_command dfx identity get-principal
+  assert_command_fail dfx identity use some-secret-name
+
+  events="$DFX_CONFIG_ROOT/.config/dfx/telemetry/events.jsonl"
+  assert_command jq -r 'select(.command == "identity get-principal") | .success' "$events"
+  assert_eq "true"
+  assert_command jq -r 'select(.command == "identity use") | .success' "$events"
+  assert_eq "false"
+  assert_command_fail grep "some-secret-name" "$events"
+
+  assert_command dfx diagnostics disable --clear
+  assert_file_not_exists "$events"
+  assert_command dfx identity get-principal
+  assert_file_not_exists "$events"
+}
+
+@test "DFX_TELEMETRY overrides the setting" {
+  export DFX_TELEMETRY=local
+  assert_command dfx identity get-principal
+  assert_file_exists "$DFX_CONFIG_ROOT/.config/dfx/telemetry/events.jsonl"
+
+  unset DFX_TELEMETRY
+  assert_command dfx diagnostics enable
+  rm "$DFX_CONFIG_ROOT/.config/dfx/telemetry/events.jsonl"
+  export DFX_TELEMETRY=off
+  assert_command dfx identity get-principal
+  assert_file_not_exists "$DFX_CONFIG_ROOT/.config/dfx/telemetry/events.jsonl"
+}
+
+@test "report bundles the events, and the project config only when asked to" {
+  dfx_new hello
+  assert_command dfx diagnostics enable
+  assert_command dfx identity get-principal
+
+  assert_command dfx diagnostics report --output report.tar.gz
+  assert_contains "It has not been sent anywhere"
+  assert_command tar -tzf report.tar.gz
+  assert_contains "report.json"
+  assert_contains "events.jsonl"
+  assert_not_contains "dfx.json"
+
+  assert_command dfx diagnostics report --output report.tar.gz --include-dfx-json
+  assert_command tar -tzf report.tar.gz
+  assert_contains "dfx.json"
+}
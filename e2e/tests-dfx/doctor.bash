@@ -0,0 +1,49 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "doctor reports a local network that is not running" {
+  dfx_new hello
+  assert_command dfx doctor
+  assert_contains "Identity: 'default'"
+  assert_contains "is available"
+  assert_contains "The local network is not running"
+  assert_contains "Fix: Run \`dfx start --background\`."
+}
+
+@test "doctor accepts the bind address of the running network" {
+  dfx_new hello
+  dfx_start
+  assert_command dfx doctor
+  assert_contains "is used by the running local network"
+  assert_contains "'local' is reachable at"
+}
+
+@test "doctor reports a bind address used by another program" {
+  dfx_new hello
+  python3 -c 'import socket, time; s = socket.socket(); s.bind(("127.0.0.1", 0)); s.listen(); print(s.getsockname()[1], flush=True); time.sleep(60)' >"$E2E_TEMP_DIR/port" &
+  server_pid=$!
+  while [ ! -s "$E2E_TEMP_DIR/port" ]; do sleep 0.1; done
+  port=$(cat "$E2E_TEMP_DIR/port")
+  jq ".networks.local.bind=\"127.0.0.1:$port\"" dfx.json | sponge dfx.json
+
+  assert_command_fail dfx doctor
+  assert_contains "127.0.0.1:$port is not available"
+  assert_contains "dfx doctor found 1 problem(s)."
+  kill "$server_pid"
+}
+
+@test "doctor reports an identity that does not exist" {
+  assert_command_fail dfx doctor --identity does-not-exist
+  assert_contains "[ error ] Identity:"
+}
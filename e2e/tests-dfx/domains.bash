@@ -0,0 +1,64 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new_assets hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "domains add writes the ic-domains asset" {
+  assert_command dfx domains add example.com --canister hello_frontend
+  assert_match "does not exist yet on network 'ic'"
+  assert_match "does not mention the .well-known directory"
+  assert_command cat src/hello_frontend/assets/.well-known/ic-domains
+  assert_eq "example.com"
+
+  # adding a domain twice does not duplicate it
+  assert_command dfx domains add www.example.com --canister hello_frontend
+  assert_command dfx domains add example.com --canister hello_frontend
+  assert_command cat src/hello_frontend/assets/.well-known/ic-domains
+  assert_eq "example.com
+www.example.com"
+}
+
+@test "domains add --assets-dir creates an asset configuration" {
+  mkdir public
+  assert_command dfx domains add example.com --canister hello_frontend --assets-dir public
+  assert_not_match "does not mention the .well-known directory"
+  assert_command cat public/.well-known/ic-domains
+  assert_eq "example.com"
+  assert_command jq -r '.[0].match' public/.ic-assets.json
+  assert_eq ".well-known"
+}
+
+@test "domains add shows the DNS records to create" {
+  dfx_start
+  assert_command dfx deploy hello_frontend
+  CANISTER_ID="$(dfx canister id hello_frontend)"
+  assert_command dfx domains add example.com --canister hello_frontend --network local
+  assert_match "_canister-id.example.com +TXT +$CANISTER_ID"
+  assert_match "example.com +CNAME +example.com.icp1.io"
+  assert_match "_acme-challenge.example.com +CNAME +_acme-challenge.example.com.icp2.io"
+}
+
+@test "domains add rejects invalid domains and canisters" {
+  assert_command_fail dfx domains add https://example.com --canister hello_frontend
+  assert_match "not a valid domain name"
+  assert_command_fail dfx domains add example.com --canister hello_backend
+  assert_match "is not an asset canister"
+}
+
+@test "domains status requires a registration" {
+  assert_command_fail dfx domains status
+  assert_match "No domains have been registered from this project"
+  assert_command_fail dfx domains status example.com
+  assert_match "has not been registered from this project"
+}
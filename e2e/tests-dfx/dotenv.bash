@@ -28,7 +28,7 @@ teardown() {
   ( cd subdir/build-single && dfx build e2e_project_frontend )
   ( cd subdir/build-all && dfx build --all )
   ( cd subdir/canister-install-single && dfx canister install e2e_project_backend )
-  dfx canister uninstall-code e2e_project_backend
+  dfx canister uninstall-code e2e_project_backend --yes
   ( cd subdir/canister-install-all && dfx canister install --all )
   rm -rf .dfx
   ( cd subdir/deploy-single && dfx deploy e2e_project_backend)
@@ -19,7 +19,7 @@ teardown() {
   PORT=$(get_webserver_port)
 
   assert_command dfx deploy
-  CANDID_UI_ID=$(dfx canister id __Candid_UI)
+  CANDID_UI_ID=a4gq6-oaaaa-aaaab-qaa4q-cai
   APP_ID=$(dfx canister id e2e_project_backend)
   ASSETS_ID=$(dfx canister id e2e_project_frontend)
   assert_match "e2e_project_backend: http://127.0.0.1:$PORT/\?canisterId=$CANDID_UI_ID&id=$APP_ID"
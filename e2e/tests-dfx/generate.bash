@@ -148,3 +148,97 @@ teardown() {
   assert_command dfx generate --help
   assert_not_contains "--network"
 }
+
+@test "dfx generate creates Rust, Python and Kotlin bindings" {
+  dfx_new hello
+  jq '.canisters.hello_backend.declarations.bindings=["rs", "py", "kt"]' dfx.json | sponge dfx.json
+
+  dfx_start
+  dfx canister create --all
+  dfx build
+
+  assert_command dfx generate hello_backend
+
+  assert_file_not_exists "src/declarations/hello_backend/hello_backend.did"
+  assert_file_not_exists "src/declarations/hello_backend/index.js"
+  assert_command cat "src/declarations/hello_backend/hello_backend.rs"
+  assert_match "pub struct Service\(pub Principal\);"
+  assert_command cat "src/declarations/hello_backend/hello_backend.py"
+  assert_match "'greet': Types.Func\(\[Types.Text\], \[Types.Text\], \['query'\]\)"
+  assert_command cat "src/declarations/hello_backend/hello_backend.kt"
+  assert_match "interface HelloBackend"
+  assert_match "suspend fun greet\(arg0: String\): String"
+}
+
+@test "dfx generate writes the canister ids of the network to env files" {
+  dfx_new hello
+  jq '.canisters.hello_backend.declarations.env_file = "src/frontend/.env"' dfx.json | sponge dfx.json
+  mkdir -p src/frontend
+  echo "VITE_TITLE='Hello'" >src/frontend/.env
+  dfx_start
+  dfx canister create --all
+  backend_id=$(dfx canister id hello_backend)
+
+  assert_command dfx generate hello_backend
+  assert_file_exists src/frontend/.env.local
+  assert_command cat src/frontend/.env
+  assert_contains "VITE_TITLE='Hello'"
+  assert_contains "DFX_NETWORK='local'"
+  assert_contains "CANISTER_ID_HELLO_BACKEND='${backend_id}'"
+
+  echo '{"hello_backend": {"ic": "bd3sg-teaaa-aaaaa-qaaba-cai"}}' >canister_ids.json
+  assert_command dfx generate hello_backend --network ic
+  assert_command cat src/frontend/.env.ic
+  assert_contains "DFX_NETWORK='ic'"
+  assert_contains "CANISTER_ID_HELLO_BACKEND='bd3sg-teaaa-aaaaa-qaaba-cai'"
+  assert_command cat src/frontend/.env.local
+  assert_contains "CANISTER_ID_HELLO_BACKEND='${backend_id}'"
+}
+
+@test "dfx generate of one canister keeps the ids of the other canisters in env files" {
+  dfx_new hello
+  jq '.canisters.hello_backend.declarations.env_file = "src/frontend/.env"' dfx.json | sponge dfx.json
+  dfx_start
+  dfx canister create --all
+  backend_id=$(dfx canister id hello_backend)
+  frontend_id=$(dfx canister id hello_frontend)
+
+  assert_command dfx generate
+  assert_command cat src/frontend/.env
+  assert_contains "CANISTER_ID_HELLO_FRONTEND='${frontend_id}'"
+
+  assert_command dfx generate hello_backend
+  assert_command cat src/frontend/.env
+  assert_contains "CANISTER_ID_HELLO_BACKEND='${backend_id}'"
+  assert_contains "CANISTER_ID_HELLO_FRONTEND='${frontend_id}'"
+  assert_command cat src/frontend/.env.local
+  assert_contains "CANISTER_ID_HELLO_FRONTEND='${frontend_id}'"
+}
+
+@test "dfx generate docs renders the interface with its comments" {
+  dfx_new_rust hello
+  cat > src/hello_backend/hello_backend.did <<'DID'
+// Greets its callers.
+service : {
+  // Returns a greeting for the name.
+  "greet": (text) -> (text) query;
+}
+DID
+
+  assert_command dfx generate docs hello_backend
+  assert_command cat docs/canisters/hello_backend.md
+  assert_contains "Greets its callers."
+  assert_contains "greet : (text) -> (text) query;"
+  assert_contains "Returns a greeting for the name."
+  assert_file_not_exists "src/declarations/hello_backend/hello_backend.did"
+
+  assert_command dfx generate docs --format html --output api
+  assert_command cat api/hello_backend.html
+  assert_contains "<h1>hello_backend</h1>"
+
+  jq '.canisters.hello_backend.declarations.bindings=["did", "md"]' dfx.json | sponge dfx.json
+  assert_command dfx generate hello_backend
+  assert_file_exists "src/declarations/hello_backend/hello_backend.did"
+  assert_command cat "src/declarations/hello_backend/hello_backend.md"
+  assert_contains "Returns a greeting for the name."
+}
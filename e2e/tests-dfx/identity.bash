@@ -38,6 +38,53 @@ teardown() {
   assert_command dfx identity new i_den@ti-ty --storage-mode plaintext
 }
 
+@test "project default identity and network" {
+  dfx identity new --storage-mode plaintext alice
+  jq '.defaults.identity="alice"' dfx.json | sponge dfx.json
+
+  assert_command dfx identity whoami
+  assert_eq "alice"
+
+  assert_command dfx identity whoami --identity default
+  assert_eq "default"
+
+  # outside of the project, the global default identity is used
+  (
+    cd ..
+    assert_command dfx identity whoami
+    assert_eq "default"
+  )
+
+  jq '.defaults.network="no-such-network"' dfx.json | sponge dfx.json
+  assert_command_fail dfx canister id e2e_project_backend
+  assert_contains "Using the project's default network 'no-such-network'"
+  assert_contains "no-such-network"
+}
+
+@test "project default network does not apply to dfx start and dfx stop" {
+  jq '.defaults.network="ic"' dfx.json | sponge dfx.json
+
+  dfx_start
+  assert_command dfx ping local
+  assert_command dfx stop
+  assert_command_fail dfx ping local
+}
+
+@test "identity set-allowed-networks restricts the networks an identity can be used on" {
+  dfx identity new --storage-mode plaintext prod
+  assert_command dfx identity set-allowed-networks prod ic
+  assert_contains "Identity 'prod' can only be used on: ic."
+
+  assert_command_fail dfx canister status e2e_project_backend --identity prod
+  assert_contains "Identity 'prod' is not allowed on network 'local'. It can only be used on: ic."
+
+  assert_command dfx identity set-allowed-networks prod --any
+  assert_command dfx identity get-principal --identity prod
+
+  assert_command_fail dfx identity set-allowed-networks no-such-identity ic
+  assert_contains "Identity must exist"
+}
+
 @test "identity get-principal: the get-principal is the same as sender id" {
   install_asset identity
   dfx_start
@@ -82,6 +129,29 @@ teardown() {
   fi
 }
 
+@test "identity get-principal --all shows the principals of all identities" {
+  assert_command dfx identity new --storage-mode plaintext alice
+  assert_command dfx identity new --storage-mode plaintext bob
+  ALICE_PRINCIPAL="$(dfx identity get-principal --identity alice)"
+  BOB_PRINCIPAL="$(dfx identity get-principal --identity bob)"
+
+  assert_command dfx identity get-principal --all
+  assert_contains "alice $ALICE_PRINCIPAL"
+  assert_contains "bob $BOB_PRINCIPAL"
+  assert_not_contains "anonymous"
+
+  assert_command dfx identity get-principal --all --json
+  assert_eq "$ALICE_PRINCIPAL" "$(echo "$stdout" | jq -r .alice)"
+  assert_eq "$BOB_PRINCIPAL" "$(echo "$stdout" | jq -r .bob)"
+
+  assert_command dfx identity get-principal --all --candid
+  assert_contains "principal \"$ALICE_PRINCIPAL\"; principal \"$BOB_PRINCIPAL\""
+  assert_match "^vec \{ .* \}$"
+
+  assert_command dfx identity get-principal --candid --identity alice
+  assert_eq "vec { principal \"$ALICE_PRINCIPAL\" }" "$stdout"
+}
+
 @test "calls and query receive the same principal from dfx" {
   install_asset identity
   dfx_start
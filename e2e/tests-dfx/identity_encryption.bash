@@ -60,3 +60,45 @@ EOF
   assert_command "${BATS_TEST_DIRNAME}/../assets/expect_scripts/init_alice_with_storage_mode_pwprotected.exp"
   assert_command dfx identity remove alice
 }
+
+@test "unlocked identity with a password is used without a prompt until it is locked" {
+  unset DFX_CI_MOCK_KEYRING_LOCATION
+  assert_command "${BATS_TEST_DIRNAME}/../assets/expect_scripts/init_alice_with_storage_mode_pwprotected.exp"
+  export DFX_CI_MOCK_KEYRING_LOCATION="$MOCK_KEYRING_LOCATION"
+
+  assert_command "${BATS_TEST_DIRNAME}/../assets/expect_scripts/unlock_identity_with_password.exp"
+  assert_command cat "$MOCK_KEYRING_LOCATION"
+  assert_match "internet_computer_identity_session_"
+  assert_not_contains "testpassword"
+
+  assert_command dfx identity get-principal --identity alice
+  assert_not_contains "Please enter the passphrase"
+
+  assert_command dfx identity lock alice
+  assert_contains "Identity 'alice' is locked."
+  assert_command_fail dfx identity get-principal --identity alice
+
+  assert_command_fail dfx identity unlock default
+  assert_contains "Identity 'default' is not password-protected."
+}
+
+@test "identity migrate finds identities encrypted with outdated key derivation parameters" {
+  assert_command "${BATS_TEST_DIRNAME}/../assets/expect_scripts/init_alice_with_storage_mode_pwprotected.exp"
+  # older versions of dfx always derive the key with these parameters
+  assert_command jq -c .encryption.key_derivation "$DFX_CONFIG_ROOT/.config/dfx/identity/alice/identity.json"
+  assert_eq '{"memory_kib":64000,"iterations":3,"parallelism":1}'
+  assert_command dfx identity migrate --storage-mode password-protected --dry-run
+  assert_not_contains "alice"
+  # a deliberately password-protected identity is not moved to the keyring
+  assert_command dfx identity migrate --storage-mode keyring --dry-run
+  assert_not_contains "alice"
+
+  # identities created by older versions of dfx do not record their key derivation parameters, and use the current ones
+  jq 'del(.encryption.key_derivation)' "$DFX_CONFIG_ROOT/.config/dfx/identity/alice/identity.json" | sponge "$DFX_CONFIG_ROOT/.config/dfx/identity/alice/identity.json"
+  assert_command dfx identity migrate --storage-mode password-protected --dry-run
+  assert_not_contains "alice"
+
+  jq '.encryption.key_derivation = {"memory_kib": 65536, "iterations": 4, "parallelism": 1}' "$DFX_CONFIG_ROOT/.config/dfx/identity/alice/identity.json" | sponge "$DFX_CONFIG_ROOT/.config/dfx/identity/alice/identity.json"
+  assert_command dfx identity migrate --storage-mode password-protected --dry-run
+  assert_contains "alice: password-protected (encrypt again with current key derivation parameters)"
+}
@@ -14,6 +14,26 @@ teardown() {
   standard_teardown
 }
 
+@test "install checks the wasm hash against allowed_wasm_hashes" {
+  install_asset custom_canister
+  install_asset wasm/identity
+  dfx_start
+  dfx canister create custom
+  dfx build custom
+  jq '.canisters.custom.allowed_wasm_hashes=["0000000000000000000000000000000000000000000000000000000000000000"]' dfx.json | sponge dfx.json
+
+  assert_command_fail dfx canister install custom
+  assert_contains "which is not in its allowed_wasm_hashes in dfx.json"
+
+  assert_command dfx canister install custom --allow-unverified
+  assert_contains "which is not in its allowed_wasm_hashes"
+
+  HASH="$(sha256sum .dfx/local/canisters/custom/custom.wasm | cut -d " " -f 1)"
+  jq '.canisters.custom.allowed_wasm_hashes=["'"$HASH"'"]' dfx.json | sponge dfx.json
+  assert_command dfx canister install custom --mode reinstall --yes
+  assert_not_contains "allowed_wasm_hashes"
+}
+
 @test "canister install --upgrade-unchanged upgrades even if the .wasm did not change" {
   dfx_start
   dfx canister create --all
@@ -148,7 +168,7 @@ teardown() {
   assert_command dfx build
   assert_command dfx canister install --all
   BUILD_HASH="0x$(sha256sum .dfx/local/canisters/e2e_project_backend/e2e_project_backend.wasm.gz | cut -d " " -f 1)"
-  ONCHAIN_HASH="$(dfx canister info e2e_project_backend | tail -n 1 | cut -d " " -f 3)"
+  ONCHAIN_HASH="$(dfx canister info e2e_project_backend | grep "Module hash" | cut -d " " -f 3)"
   assert_eq "$BUILD_HASH" "$ONCHAIN_HASH"
 }
 
@@ -237,6 +257,24 @@ teardown() {
   assert_contains "The canister must be built before install. Please run \`dfx build\`."
 }
 
+@test "install explains a missing init argument" {
+  install_asset deploy_deps
+  dfx_start
+
+  dfx canister create dependency
+  dfx build dependency
+  # without a terminal, dfx cannot prompt for the argument
+  assert_command_fail dfx canister install dependency
+  assert_contains "This canister requires an initialization argument of type (text)."
+  assert_contains "Specify it with --argument, or with init_arg in dfx.json."
+
+  # --yes never prompts
+  assert_command_fail dfx canister install dependency --yes
+  assert_contains "This canister requires an initialization argument of type (text)."
+
+  assert_command dfx canister install dependency --argument '("icp")'
+}
+
 @test "install succeeds if init_arg is defined in dfx.json" {
   install_asset deploy_deps
   dfx_start
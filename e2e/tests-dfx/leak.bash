@@ -21,6 +21,6 @@ teardown() {
   dfx deploy
   for _ in {1..50}
   do
-  echo yes | dfx canister install --mode=reinstall custom
+  echo custom | dfx canister install --mode=reinstall custom
   done
 }
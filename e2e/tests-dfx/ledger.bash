@@ -126,6 +126,40 @@ current_time_nanoseconds() {
   assert_command dfx ledger balance --identity alice
   assert_match "999999999.99990000 ICP"
 }
+
+@test "ledger fabricate requires the minting identity" {
+  dfx identity use alice
+  assert_command_fail dfx ledger fabricate 10 --to "$(dfx identity get-principal --identity bob)"
+  assert_contains "The minting account of the ledger belongs to"
+  assert_command dfx ledger balance --identity bob
+  assert_eq "1000000000.00000000 ICP"
+
+  assert_command_fail dfx ledger fabricate 10 --to 22ca7edac648b814e81d7946e8bacea99280e07c5f51a04ba7a38009d8ad8e89 --to-subaccount 0000000000000000000000000000000000000000000000000000000000000001
+  assert_contains "--to-subaccount requires a principal"
+}
+
+@test "ledger balance of other accounts and subaccount scan" {
+  subacct=0000000000000000000000000000000000000000000000000000000000000002
+  bob_principal=$(dfx identity get-principal --identity bob)
+  bob_subacct=$(dfx ledger account-id --identity bob --subaccount "$subacct")
+
+  dfx identity use alice
+  assert_command dfx ledger transfer --amount 10 --memo 1 "$bob_subacct"
+  assert_match "Transfer sent at block height"
+
+  assert_command dfx ledger balance "$bob_principal" --subaccount "$subacct"
+  assert_eq "10.00000000 ICP"
+  assert_command dfx ledger balance "$bob_subacct"
+  assert_eq "10.00000000 ICP"
+  assert_command_fail dfx ledger balance "$bob_subacct" --subaccount "$subacct"
+  assert_contains "The subaccounts of an AccountIdentifier cannot be derived."
+
+  assert_command dfx ledger balance "$bob_principal" --scan-subaccounts 4
+  assert_eq "0000000000000000000000000000000000000000000000000000000000000000 1000000000.00000000 ICP
+$subacct 10.00000000 ICP" "$stdout"
+
+  assert_command_fail dfx ledger balance "$bob_principal" --scan-subaccounts 4 --subaccount "$subacct"
+}
 tc_to_num() {
   if [[ $1 =~ T ]]; then
     echo "${1%%[^0-9]*}000000000000"
@@ -144,6 +178,8 @@ tc_to_num() {
 
   assert_command dfx ledger top-up "$wallet" --icp 5
   assert_match "Canister was topped up with 617283500000000 cycles"
+  # shellcheck disable=SC2154
+  assert_contains "Converting 5.00000000 ICP into about 617.284T cycles (617283500000000 cycles)." "$stderr"
   balance_now=$(tc_to_num "$(dfx wallet balance)")
 
   (( balance_now - balance > 600000000000000 ))
@@ -192,6 +228,20 @@ tc_to_num() {
   assert_contains "Canister was topped up with 617283500000000 cycles"
 }
 
+@test "cycles rate" {
+  assert_command dfx cycles rate
+  assert_contains "Conversion rate: 1 ICP <> 123.4567 XDR"
+  assert_contains "1 ICP <> 123.457 TC (trillion cycles), 1 XDR <> 1.000 TC."
+
+  assert_command dfx cycles rate --icp 5
+  assert_contains "5.00000000 ICP <> 617.284 TC (617283500000000 cycles)."
+
+  assert_command dfx cycles rate --cycles 123.4567T
+  assert_contains "123.457 TC (123456700000000 cycles) <> 1.00000000 ICP."
+
+  assert_command_fail dfx cycles rate --icp 1 --cycles 1T
+}
+
 @test "ledger create-canister" {
   dfx identity use alice
   assert_command dfx ledger create-canister --amount=100 --subnet-type "type1" "$(dfx identity get-principal)"
@@ -205,6 +255,9 @@ tc_to_num() {
   assert_match "Refunded at block height"
   assert_match "with message: Subnet $SUBNET_ID does not exist"
 
+  assert_command_fail dfx ledger create-canister --amount=100 --next-to "not_a_canister" "$(dfx identity get-principal)"
+  assert_not_contains "Transfer sent at block height"
+
   # Transaction Deduplication
   t=$(current_time_nanoseconds)
 
@@ -220,6 +273,10 @@ tc_to_num() {
   assert_match "Using transfer at block height $block_height" "$stdout"
   # shellcheck disable=SC2154
   assert_match "Canister created with id: $created_canister_id" "$stdout"
+  # shellcheck disable=SC2154
+  assert_contains "Conversion rate: 1 ICP <>" "$stderr"
+  # shellcheck disable=SC2154
+  assert_contains "Converting 100.00000000 ICP into about" "$stderr"
 
   assert_command dfx ledger create-canister --amount=100 --created-at-time $((t+1)) "$(dfx identity get-principal)"
   # shellcheck disable=SC2154
@@ -247,6 +304,37 @@ tc_to_num() {
 
 }
 
+@test "ledger notify scan" {
+  dfx identity use alice
+  wallet=$(dfx identity get-wallet)
+
+  assert_command dfx ledger create-canister --amount=100 "$(dfx identity get-principal)"
+  # shellcheck disable=SC2154
+  create_block=$(echo "$stdout" | sed '1q' | sed 's/Transfer sent at block height //')
+  # shellcheck disable=SC2154
+  created_canister_id=$(echo "$stdout" | sed '3q;d' | sed 's/Canister created with id: //' | tr -d '"')
+
+  assert_command dfx ledger top-up "$wallet" --icp 5
+  # shellcheck disable=SC2154
+  top_up_block=$(echo "$stdout" | sed '1q' | sed 's/Transfer sent at block height //')
+
+  assert_command dfx ledger notify scan
+  assert_contains "Block $create_block: dfx ledger notify create-canister $create_block $(dfx identity get-principal)"
+  assert_contains "Block $top_up_block: top-up of an unknown canister"
+
+  assert_command dfx ledger notify scan --canister "$wallet"
+  assert_contains "Block $top_up_block: dfx ledger notify top-up $top_up_block $wallet"
+
+  # notifying again returns the result of the first notification
+  assert_command dfx ledger notify scan --canister "$wallet" --notify
+  assert_contains "Block $create_block: canister $created_canister_id created."
+  assert_contains "Block $top_up_block: canister $wallet topped up with"
+
+  dfx identity use bob
+  assert_command dfx ledger notify scan
+  assert_not_contains "Block $create_block"
+}
+
 @test "ledger show-subnet-types" {
   install_asset cmc
 
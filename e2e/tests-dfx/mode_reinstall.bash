@@ -37,7 +37,7 @@ teardown() {
 
   # if the pipe is alone with assert_command, $stdout, $stderr etc will not be available,
   # so all the assert_match calls will fail.  http://mywiki.wooledge.org/BashFAQ/024
-  echo yes | (
+  echo hello_backend | (
     assert_command dfx canister install --mode=reinstall hello_backend
 
     assert_match "YOU WILL LOSE ALL DATA IN THE CANISTER"
@@ -72,7 +72,7 @@ teardown() {
 
   # if the pipe is alone with assert_command, $stdout, $stderr etc will not be available,
   # so all the assert_match calls will fail.  http://mywiki.wooledge.org/BashFAQ/024
-  echo yes | (
+  echo hello_backend | (
     assert_command dfx deploy --mode=reinstall hello_backend
 
     assert_match "YOU WILL LOSE ALL DATA IN THE CANISTER"
@@ -115,7 +115,7 @@ teardown() {
 
   # if the pipe is alone with assert_command, $stdout, $stderr etc will not be available,
   # so all the assert_match calls will fail.  http://mywiki.wooledge.org/BashFAQ/024
-  echo "yes" | (
+  echo "hello_frontend" | (
     assert_command dfx deploy --mode=reinstall hello_frontend
 
     assert_match "You are about to reinstall the hello_frontend canister."
@@ -129,34 +129,46 @@ teardown() {
   assert_eq "(2 : nat)"
 }
 
-@test "confirmation dialogue accepts multiple forms of 'yes'" {
+@test "reinstall confirmation requires typing the canister name" {
   dfx_start
   dfx deploy
 
   # if the pipe is alone with assert_command, $stdout, $stderr etc will not be available,
   # so all the assert_match calls will fail.  http://mywiki.wooledge.org/BashFAQ/024
   echo yes | (
-    assert_command dfx deploy --mode=reinstall hello_backend
+    assert_command_fail dfx deploy --mode=reinstall hello_backend
 
-    assert_match "YOU WILL LOSE ALL DATA IN THE CANISTER"
-    assert_match "Reinstalling code for canister hello_backend"
+    assert_match "Type 'hello_backend' to proceed"
+    assert_match "Module hash: 0x"
+    assert_match "Memory size: "
+    assert_match "Snapshot: none"
+    assert_match "User declined consent."
   )
-  echo y | (
+  echo hello_backend | (
     assert_command dfx deploy --mode=reinstall hello_backend
 
     assert_match "YOU WILL LOSE ALL DATA IN THE CANISTER"
     assert_match "Reinstalling code for canister hello_backend"
   )
-  echo YES | (
-    assert_command dfx deploy --mode=reinstall hello_backend
+}
 
-    assert_match "YOU WILL LOSE ALL DATA IN THE CANISTER"
-    assert_match "Reinstalling code for canister hello_backend"
+@test "uninstall-code requires typing the canister name" {
+  dfx_start
+  dfx deploy
+
+  echo no | (
+    assert_command_fail dfx canister uninstall-code hello_backend
+
+    assert_match "You are about to uninstall the code of the hello_backend canister"
+    assert_match "Module hash: 0x"
+    assert_match "Refusing to uninstall code without approval"
   )
-  echo YeS | (
-    assert_command dfx deploy --mode=reinstall hello_backend
+  assert_command dfx canister info hello_backend
+  assert_not_contains "Module hash: None"
 
-    assert_match "YOU WILL LOSE ALL DATA IN THE CANISTER"
-    assert_match "Reinstalling code for canister hello_backend"
+  echo hello_backend | (
+    assert_command dfx canister uninstall-code hello_backend
   )
+  assert_command dfx canister info hello_backend
+  assert_contains "Module hash: None"
 }
@@ -111,3 +111,50 @@ teardown() {
   assert_not_contains "identity is not stored securely"
   assert_eq "No problems found"
 }
+
+@test "network list and describe show the effective configuration of networks" {
+  create_networks_json
+  echo "{}" | jq '.shared_net.providers=["https://shared.example.com"] | .local.replica.subnet_type="system"' >"$E2E_NETWORKS_JSON"
+  jq '.networks.staging.providers=["https://staging.example.com"] | .networks.staging.type="persistent"' dfx.json | sponge dfx.json
+
+  assert_command dfx network list
+  assert_match "ic +persistent +https://icp0.io"
+  assert_match "local \* +ephemeral, local \(shared\)"
+  assert_match "shared_net +ephemeral +https://shared.example.com/? +\($E2E_NETWORKS_JSON\)"
+  assert_match "staging +persistent +https://staging.example.com/? +\(.*dfx.json\)"
+
+  assert_command dfx network list --json
+  assert_eq "$(echo "$stdout" | jq -r '.[] | select(.is_default) | .name')" "local"
+
+  assert_command dfx network describe
+  assert_contains "Name: local"
+  assert_contains "Replica subnet type: system"
+
+  assert_command dfx network describe --network staging --json
+  assert_eq "$(echo "$stdout" | jq -r .type)" "persistent"
+  assert_eq "$(echo "$stdout" | jq -r .local)" "null"
+
+  jq '.defaults.network="staging"' dfx.json | sponge dfx.json
+  assert_command dfx network describe
+  assert_contains "Name: staging"
+  assert_contains "Default: yes"
+}
+
+@test "requests fail over to the next provider of a network" {
+  dfx_start
+  dfx deploy e2e_project_backend
+  CANISTER_ID="$(dfx canister id e2e_project_backend)"
+  webserver_port=$(get_webserver_port)
+  jq '.networks.multi.providers=["http://127.0.0.1:1", "http://127.0.0.1:'"$webserver_port"'"]' dfx.json | sponge dfx.json
+
+  assert_command dfx network ping --network multi --all-providers
+  assert_match "127.0.0.1:1/?: unreachable"
+  assert_match "127.0.0.1:$webserver_port/?: healthy"
+
+  assert_command_fail dfx network ping --network multi
+  assert_contains "No provider of network 'multi' could be reached."
+
+  assert_command dfx canister call --network multi "$CANISTER_ID" greet '("failover")'
+  assert_contains "Hello, failover!"
+  assert_contains "failed"
+}
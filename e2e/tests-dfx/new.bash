@@ -70,6 +70,15 @@ teardown() {
   done
 }
 
+@test "dfx new --extras ci adds a CI workflow" {
+  assert_command dfx new e2e_project --type rust --frontend react --extras ci
+  assert_file_exists e2e_project/.github/workflows/ci.yml
+  assert_command grep "dfx-version: \"$(dfx --version | cut -d' ' -f2)\"" e2e_project/.github/workflows/ci.yml
+  assert_command jq -r '.dependencies["@dfinity/agent"]' e2e_project/src/e2e_project_frontend/package.json
+  assert_not_contains "{js_agent_version}"
+  assert_match '^\^[0-9]+\.[0-9]+'
+}
+
 @test "backend templates" {
   for backend in motoko rust kybra azle; do
     assert_command dfx new e2e_$backend --type $backend --no-frontend
@@ -83,3 +92,46 @@ teardown() {
   assert_file_exists e2e_project/src/e2e_project_frontend/src/setupTests.js
   assert_command jq .canisters.internet_identity e2e_project/dfx.json
 }
+
+@test "dfx new --template with a built-in template" {
+  assert_command dfx new --template rust --dry-run e2e_project
+  assert_match "e2e_project/src/e2e_project_backend/Cargo.toml"
+  assert_command_fail dfx new --template rust --no-frontend e2e_project
+  assert_match "cannot be used with"
+  assert_command_fail dfx new --template rust --extras ci e2e_project
+  assert_match "cannot be used with"
+}
+
+@test "dfx new --template with a git template" {
+  mkdir template
+  (
+    cd template
+    git init --quiet
+    echo '{"canisters": {"__project_name___backend": {"type": "custom", "wasm": "x.wasm", "candid": "x.did"}}}' | sed 's/__project_name__/{project_name}/' > dfx.json
+    mkdir -p src/__project_name__
+    echo "hello {project_name}" > src/__project_name__/README.md
+    echo '{"post_generate": ["echo generated {project_name} > post_generate.txt"]}' > dfx-template.json
+    git add . && git -c user.name=e2e -c user.email=e2e@example.com commit --quiet -m "template"
+    git tag v1
+  )
+
+  assert_command dfx new --template "$(pwd)/template" --template-rev v1 e2e_project
+  assert_command cat e2e_project/src/e2e_project/README.md
+  assert_eq "hello e2e_project"
+  assert_command jq -r '.canisters | keys[0]' e2e_project/dfx.json
+  assert_eq "e2e_project_backend"
+  assert_file_not_exists e2e_project/dfx-template.json
+  # the commands of the template only run with consent
+  assert_contains "Skipped the commands of the template"
+  assert_contains "echo generated e2e_project > post_generate.txt"
+  assert_file_not_exists e2e_project/post_generate.txt
+
+  assert_command dfx new --template "$(pwd)/template" --run-template-scripts e2e_project2
+  assert_command cat e2e_project2/post_generate.txt
+  assert_eq "generated e2e_project2"
+}
+
+@test "dfx new --template with an unknown template" {
+  assert_command_fail dfx new --template not-a-template e2e_project
+  assert_match "Unknown template 'not-a-template'"
+}
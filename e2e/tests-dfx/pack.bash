@@ -0,0 +1,88 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "pack requires the canisters to be built" {
+  dfx_start
+  dfx canister create --all
+
+  assert_command_fail dfx pack
+  assert_contains "Canister hello_backend has not been built for network 'local'."
+}
+
+@test "apply deploys a packed project without its source" {
+  install_asset greet
+  dfx_start
+  dfx canister create --all
+  dfx build
+  backend_id=$(dfx canister id hello_backend)
+
+  assert_command dfx pack --output "$E2E_TEMP_DIR/bundle.tar.gz"
+  assert_contains "Packed 1 canister(s) for network 'local'"
+  tar -tzf "$E2E_TEMP_DIR/bundle.tar.gz" >contents.txt
+  assert_command cat contents.txt
+  assert_contains "bundle.json"
+  assert_contains "canisters/hello_backend/hello_backend.wasm"
+
+  cd "$E2E_TEMP_DIR"
+  assert_command_fail dfx apply bundle.tar.gz --sha256 0000
+  assert_contains "but 0000 was expected"
+
+  assert_command_fail dfx apply bundle.tar.gz --network ic
+  assert_contains "The bundle was packed for network 'local', and cannot be deployed to network 'ic'."
+
+  assert_command dfx apply bundle.tar.gz
+  assert_contains "Installing code for canister hello_backend, with canister ID ${backend_id}"
+
+  assert_command dfx canister call "$backend_id" greet '("bundle")'
+  assert_eq '("Hello, bundle!")'
+
+  assert_command dfx apply bundle.tar.gz
+  assert_contains "is already installed in canister hello_backend"
+}
+
+@test "apply fails when a canister of the bundle does not exist" {
+  dfx_start
+  dfx canister create --all
+  dfx build
+  backend_id=$(dfx canister id hello_backend)
+  dfx pack --output "$E2E_TEMP_DIR/bundle.tar.gz"
+  dfx canister stop hello_backend
+  dfx canister delete hello_backend --no-withdrawal -y
+
+  cd "$E2E_TEMP_DIR"
+  assert_command_fail dfx apply bundle.tar.gz
+  assert_contains "Canister(s) hello_backend (${backend_id}) do not exist on network 'local'."
+}
+
+@test "apply installs the code with a wallet" {
+  install_asset greet
+  dfx_start
+  dfx canister create --all
+  dfx build
+  backend_id=$(dfx canister id hello_backend)
+  wallet_id=$(dfx identity get-wallet)
+  dfx canister update-settings hello_backend --add-controller "$wallet_id"
+  dfx pack --output "$E2E_TEMP_DIR/bundle.tar.gz"
+
+  cd "$E2E_TEMP_DIR"
+  assert_command_fail dfx apply bundle.tar.gz --wallet not-a-principal
+  assert_contains "Failed to determine call sender"
+
+  assert_command dfx apply bundle.tar.gz --wallet "$wallet_id"
+  assert_contains "Installing code for canister hello_backend, with canister ID ${backend_id}"
+  assert_command dfx canister call "$backend_id" greet '("wallet")'
+  assert_eq '("Hello, wallet!")'
+}
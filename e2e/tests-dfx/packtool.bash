@@ -73,3 +73,42 @@ teardown() {
   assert_match 'sh.*command-that-fails.bash'
   assert_match 'exit (code|status): 3'
 }
+
+@test "build resolves packages with the configured package manager and caches them" {
+  install_asset packtool
+  # stands in for mops: records its invocations and prints the package sources
+  mkdir bin
+  cat >bin/mops <<'EOF'
+#!/usr/bin/env bash
+echo "mops $*" >>mops-calls.txt
+echo --package describe ./vessel/describe/v1.0.1/src --package rate ./vessel/rate/v1.0.0/src
+EOF
+  chmod +x bin/mops
+  echo '[dependencies]' >mops.toml
+  jq '.defaults.build.packages="mops"' dfx.json | sponge dfx.json
+
+  dfx_start
+  dfx canister create --all
+  PATH="$(pwd)/bin:$PATH" assert_command dfx build e2e_project_backend
+  assert_contains "Resolving Motoko packages with mops..."
+  assert_eq "mops sources" "$(cat mops-calls.txt)"
+
+  PATH="$(pwd)/bin:$PATH" assert_command dfx build e2e_project_backend
+  assert_not_contains "Resolving Motoko packages"
+  assert_eq "1" "$(wc -l <mops-calls.txt | tr -d ' ')"
+
+  echo '# changed' >>mops.toml
+  PATH="$(pwd)/bin:$PATH" assert_command dfx build e2e_project_backend
+  assert_contains "Resolving Motoko packages with mops..."
+  assert_eq "2" "$(wc -l <mops-calls.txt | tr -d ' ')"
+}
+
+@test "build fails if the manifest of the package manager is missing" {
+  install_asset packtool
+  jq '.defaults.build.packages="vessel"' dfx.json | sponge dfx.json
+
+  dfx_start
+  dfx canister create --all
+  assert_command_fail dfx build
+  assert_contains "The Motoko package manager is vessel, but there is no vessel.dhall"
+}
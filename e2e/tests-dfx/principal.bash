@@ -0,0 +1,61 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+}
+
+teardown() {
+  standard_teardown
+}
+
+@test "principal account-id and icrc1-account" {
+  assert_command dfx principal account-id 2vxsx-fae
+  assert_eq "1c7a48ba6a562aa9eaa2481a9049cdf0433b9738c992d698c31d8abf89cadc79"
+
+  assert_command dfx principal icrc1-account 2vxsx-fae
+  assert_eq "2vxsx-fae"
+
+  assert_command dfx principal icrc1-account 2vxsx-fae --subaccount 0000000000000000000000000000000000000000000000000000000000000001
+  assert_match "^2vxsx-fae-[a-z2-7]{7}\.1$"
+  account="$output"
+
+  assert_command dfx principal inspect "$account"
+  assert_contains "Owner: 2vxsx-fae"
+  assert_contains "Kind: anonymous"
+  assert_contains "Subaccount: 0000000000000000000000000000000000000000000000000000000000000001"
+  assert_command dfx principal account-id 2vxsx-fae --subaccount 0000000000000000000000000000000000000000000000000000000000000001
+  account_id="$output"
+  assert_command dfx principal inspect "$account"
+  assert_contains "Account identifier: ${account_id}"
+}
+
+@test "principal inspect" {
+  assert_command dfx principal inspect aaaaa-aa
+  assert_contains "Kind: management canister"
+
+  assert_command dfx principal inspect rrkah-fqaaa-aaaaa-aaaaq-cai
+  assert_contains "Kind: canister"
+
+  assert_command dfx principal inspect 1c7a48ba6a562aa9eaa2481a9049cdf0433b9738c992d698c31d8abf89cadc79
+  assert_contains "The checksum is valid."
+
+  assert_command_fail dfx principal inspect 0c7a48ba6a562aa9eaa2481a9049cdf0433b9738c992d698c31d8abf89cadc79
+  assert_contains "is not a principal, an ICRC-1 account or an account identifier"
+}
+
+@test "principal neuron-account, top-up-account and random" {
+  assert_command dfx principal neuron-account 2vxsx-fae --memo 1
+  assert_match "^[0-9a-f]{64}$"
+  assert_command dfx principal neuron-account 2vxsx-fae --memo 1 --subaccount
+  assert_match "^[0-9a-f]{64}$"
+
+  assert_command dfx principal top-up-account rrkah-fqaaa-aaaaa-aaaaq-cai
+  assert_match "^[0-9a-f]{64}$"
+
+  assert_command dfx principal random --count 3
+  assert_eq 3 "$(echo "$output" | wc -l | tr -d ' ')"
+  assert_command dfx principal inspect "$(echo "$output" | head -n 1)"
+  assert_contains "Kind: self-authenticating (user)"
+}
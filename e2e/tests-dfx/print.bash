@@ -25,3 +25,30 @@ teardown() {
   run tail -2 stderr.txt
   assert_match "Hello, World! from DFINITY"
 }
+
+@test "dfx start prefixes canister output with the canister name" {
+  install_asset print
+  dfx_start 2>stderr.txt
+  dfx deploy
+  dfx canister call e2e_project hello
+  sleep 2
+  run tail -2 stderr.txt
+  assert_match "\[e2e_project\] Hello, World! from DFINITY"
+}
+
+@test "dfx start --filter only shows the output of the given canisters" {
+  install_asset print
+  dfx_start --host 127.0.0.1:0 --filter some_other_canister 2>stderr.txt
+  dfx deploy
+  dfx canister call e2e_project hello
+  sleep 2
+  assert_command cat stderr.txt
+  assert_not_contains "Hello, World! from DFINITY"
+
+  dfx stop
+  dfx_start --host 127.0.0.1:0 --filter e2e_project 2>stderr.txt
+  dfx canister call e2e_project hello
+  sleep 2
+  assert_command cat stderr.txt
+  assert_contains "[e2e_project] Hello, World! from DFINITY"
+}
@@ -0,0 +1,37 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  if [ -f proxy.pid ]; then
+    kill "$(cat proxy.pid)" || true
+  fi
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "proxy serves the dev server and the api of the network" {
+  dfx_start
+  dfx canister create --all
+  mkdir dev-server
+  echo "dev server page" >dev-server/index.html
+  BACKEND_ID=$(dfx canister id hello_backend)
+
+  dfx proxy --dev-server http://127.0.0.1:5174 --bind 127.0.0.1:5175 -- \
+    sh -c 'echo "$CANISTER_ID_HELLO_BACKEND" >canister-id.txt && cd dev-server && exec python3 -m http.server 5174 --bind 127.0.0.1' &
+  echo $! >proxy.pid
+  timeout 30 sh -c 'until curl --silent --fail http://127.0.0.1:5175/ >/dev/null; do sleep 1; done'
+
+  assert_command curl --silent --fail http://127.0.0.1:5175/
+  assert_eq "dev server page"
+  assert_command curl --silent --fail --output /dev/null http://127.0.0.1:5175/api/v2/status
+  assert_command cat canister-id.txt
+  assert_eq "$BACKEND_ID"
+}
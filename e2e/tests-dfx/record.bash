@@ -0,0 +1,68 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "record and replay calls" {
+  install_asset counter
+  dfx_start
+  dfx deploy
+
+  assert_command dfx record start --output scenario.json
+  assert_command_fail dfx record start
+  assert_contains "A recording is already in progress."
+  dfx canister call hello_backend inc_read
+  dfx canister call hello_backend write '(41)'
+  dfx canister call hello_backend read
+  assert_command dfx record stop
+  assert_contains "Recorded 3 call(s)"
+  assert_command jq -r '.calls[1].method' scenario.json
+  assert_eq "write"
+
+  # the counter is back in its initial state after a reinstall
+  dfx deploy hello_backend --mode reinstall --yes
+  assert_command dfx replay scenario.json
+  assert_contains "Replayed 3 call(s)."
+
+  # without a reinstall, inc_read returns a different value
+  assert_command_fail dfx replay scenario.json
+  assert_contains "The response differs from the recorded one."
+  assert_contains "1 of 3 response(s) differ from the recorded ones."
+  assert_command dfx replay scenario.json --no-check
+
+  assert_command_fail dfx record stop
+  assert_contains "No recording is in progress."
+}
+
+@test "replay reuses the deployed canisters, and dfx test replays against a fresh deployment" {
+  install_asset counter
+  dfx_start
+  dfx deploy
+  backend_id=$(dfx canister id hello_backend)
+
+  dfx record start --output scenario.json
+  dfx canister call hello_backend inc_read
+  dfx canister call hello_backend inc_read
+  dfx record stop
+
+  # the canister keeps its state, so the counter does not start over
+  assert_command_fail dfx replay scenario.json
+  assert_contains "against the canisters deployed on network 'local', in their current state"
+  assert_contains "2 of 2 response(s) differ from the recorded ones."
+  assert_command dfx canister id hello_backend
+  assert_eq "$backend_id"
+
+  assert_command dfx test -- dfx replay scenario.json
+  assert_contains "Replayed 2 call(s)."
+}
@@ -285,6 +285,10 @@ teardown() {
 
   assert_command dfx deploy --network actuallylocal -vv
   assert_match "Not building canister 'remote'"
+  assert_contains "Fetching the Candid interface of remote canister remote (${REMOTE_CANISTER_ID})."
+  assert_file_exists .dfx/actuallylocal/remote_candid/remote.did
+  assert_command cat .dfx/actuallylocal/remote_candid/remote.did
+  assert_contains "which_am_i"
   assert_command dfx canister call basic read_remote --network actuallylocal
   assert_eq '("this is data in the remote canister")'
 
@@ -317,4 +321,46 @@ teardown() {
 
   assert_command dfx deploy --network actuallylocal -vv
   assert_match "CANISTER_ID_remote: qoctq-giaaa-aaaaa-aaaea-cai"
+  # the remote canister does not exist, so its interface cannot be fetched
+  assert_contains "Failed to fetch the Candid interface of remote canister remote (qoctq-giaaa-aaaaa-aaaea-cai)."
+}
+
+@test "remote canisters without a candid file use the interface from their metadata" {
+  install_asset remote/actual
+  dfx_start
+  setup_actuallylocal_shared_network
+
+  dfx identity new --storage-mode plaintext alice
+  assert_command dfx deploy --network actuallylocal --identity alice
+  REMOTE_CANISTER_ID=$(jq -r .remote.actuallylocal canister_ids.json)
+  rm canister_ids.json
+
+  install_asset remote/basic
+  setup_actuallylocal_shared_network
+  setup_local_shared_network
+  jq ".canisters.remote.remote.id.actuallylocal=\"$REMOTE_CANISTER_ID\"" dfx.json | sponge dfx.json
+
+  assert_command dfx deploy --network actuallylocal
+  assert_contains "Fetching the Candid interface of remote canister remote (${REMOTE_CANISTER_ID})."
+  assert_file_exists .dfx/actuallylocal/remote_candid/remote.did
+  assert_command dfx canister call basic read_remote --network actuallylocal
+  assert_eq '("")'
+
+  # the cached interface is used until it is deleted
+  assert_command dfx deploy --network actuallylocal
+  assert_not_contains "Fetching the Candid interface"
+  rm .dfx/actuallylocal/remote_candid/remote.did
+  assert_command dfx generate remote --network actuallylocal
+  assert_contains "Fetching the Candid interface of remote canister remote (${REMOTE_CANISTER_ID})."
+  assert_file_exists src/declarations/remote/remote.did
+  assert_command cat src/declarations/remote/remote.did
+  assert_contains "which_am_i"
+
+  # a canister that declares its candid file uses it instead
+  cp .dfx/actuallylocal/remote_candid/remote.did remote.did
+  rm -r .dfx/actuallylocal/remote_candid
+  jq 'del(.canisters.remote.main) | .canisters.remote += {"type": "custom", "candid": "remote.did", "wasm": "remote.wasm", "build": []}' dfx.json | sponge dfx.json
+  assert_command dfx generate remote --network actuallylocal
+  assert_not_contains "Fetching the Candid interface"
+  assert_file_not_exists .dfx/actuallylocal/remote_candid/remote.did
 }
@@ -29,3 +29,79 @@ teardown() {
   assert_eq '4449444c0001710b48656c6c6f2c20426f6221'
 
 }
+
+@test "dfx request tracks async calls" {
+  install_asset greet
+  dfx_start
+  dfx canister create --all
+  dfx build
+  dfx canister install hello_backend
+
+  assert_command dfx canister call --async hello_backend greet Bob
+  # shellcheck disable=SC2154
+  request_id="$stdout"
+
+  assert_command dfx request list
+  assert_contains "$request_id"
+  assert_contains "greet"
+
+  assert_command dfx request status "$request_id" --wait --poll-interval 100ms
+  assert_eq '("Hello, Bob!")'
+
+  # The request is forgotten once its reply was shown.
+  assert_command dfx request list
+  assert_not_contains "$request_id"
+  assert_command_fail dfx request retry "$request_id"
+  assert_contains "is not tracked"
+
+  assert_command dfx canister call --async hello_backend greet Alice
+  assert_command dfx request forget --all
+  assert_command dfx request list
+  assert_eq ""
+}
+
+@test "dfx request retry only resends requests whose status was unknown after they expired" {
+  install_asset greet
+  dfx_start
+  dfx canister create --all
+  dfx build
+  dfx canister install hello_backend
+
+  assert_command dfx canister call --async hello_backend greet Bob
+  request_id="$stdout"
+  # A request that the IC does not know, sent long ago, without a status check after it expired.
+  unknown_id="0x$(printf '%064d' 0)"
+  jq --arg old "$request_id" --arg new "$unknown_id" \
+    '.requests[$new] = (.requests[$old] | .sent_at = 0 | .unknown_checked_at = null) | del(.requests[$old])' \
+    .dfx/local/pending_requests.json | sponge .dfx/local/pending_requests.json
+
+  assert_command_fail dfx request retry "$unknown_id"
+  assert_contains "was not checked within 5m after it was sent"
+
+  # A check before the expiry does not show that the request was never received.
+  jq --arg id "$unknown_id" '.requests[$id].unknown_checked_at = 1' \
+    .dfx/local/pending_requests.json | sponge .dfx/local/pending_requests.json
+  assert_command_fail dfx request retry "$unknown_id"
+  assert_contains "was not checked within 5m after it was sent"
+
+  jq --arg id "$unknown_id" '.requests[$id].unknown_checked_at = 270' \
+    .dfx/local/pending_requests.json | sponge .dfx/local/pending_requests.json
+  assert_command dfx request retry "$unknown_id"
+  assert_command dfx request status "$stdout" --wait
+  assert_eq '("Hello, Bob!")'
+
+  assert_command dfx canister call --async hello_backend greet Alice
+  assert_command_fail dfx request retry "$stdout"
+  assert_contains "was received by the IC"
+}
+
+@test "dfx canister call --poll-interval" {
+  install_asset greet
+  dfx_start
+  dfx canister create --all
+  dfx build
+  dfx canister install hello_backend
+
+  assert_command dfx canister call --update hello_backend greet Bob --poll-interval 100ms
+  assert_eq '("Hello, Bob!")'
+}
@@ -42,6 +42,38 @@ teardown() {
   assert_match '(9 : nat)'
 }
 
+@test "rust canister build generates bindings for its dependencies" {
+  dfx_new_rust rust_deps
+  install_asset rust_deps
+  dfx_start
+  assert_command dfx build --check rust_deps
+
+  bindings=.dfx/local/canisters/rust_deps/bindings/multiply_deps.rs
+  assert_file_exists "$bindings"
+  assert_command cat "$bindings"
+  assert_contains "pub mod multiply_deps {"
+  assert_contains "pub struct Service(pub Principal);"
+  assert_contains "pub async fn mul(&self"
+}
+
+@test "rust canister builds with a profile of dfx.json" {
+  dfx_new_rust hello
+  jq '.profiles.debug_symbols={"cargo_profile":"dev","shrink":false}' dfx.json | sponge dfx.json
+
+  dfx_start
+  dfx canister create --all
+  assert_command dfx build hello_backend --profile debug_symbols -vvv
+  assert_contains "--profile dev"
+  assert_not_contains "Shrinking WASM"
+  assert_file_exists target/wasm32-unknown-unknown/debug/hello_backend.wasm
+
+  assert_command dfx build hello_backend
+  assert_contains "--profile release"
+
+  assert_command_fail dfx build hello_backend --profile symbols
+  assert_contains "Unknown build profile 'symbols'"
+}
+
 @test "rust canister can have nonstandard target dir location" {
   dfx_new_rust
   # We used to set CARGO_TARGET_DIR="$(echo -ne '\x81')"
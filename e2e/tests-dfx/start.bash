@@ -42,7 +42,7 @@ teardown() {
   cd ..
   assert_command dfx canister status "$CANISTER_ID"
   assert_contains "Module hash: 0x"
-  assert_command dfx canister uninstall-code "$CANISTER_ID"
+  assert_command dfx canister uninstall-code "$CANISTER_ID" --yes
   assert_contains "Uninstalling code for canister $CANISTER_ID"
   assert_command dfx canister status "$CANISTER_ID"
   assert_contains "Module hash: None"
@@ -403,6 +403,45 @@ teardown() {
   assert_match "Hello, World! from DFINITY"
 }
 
+@test "dfx logs shows the output of the replica" {
+  dfx_new
+  install_asset print
+  dfx_start
+  assert_command dfx deploy
+  assert_command dfx canister call e2e_project hello
+  sleep 2
+  assert_file_exists "$E2E_NETWORK_DATA_DIRECTORY/logs/replica.log"
+  assert_command dfx logs replica
+  assert_contains "Hello, World! from DFINITY"
+  assert_command dfx logs replica --lines 1
+  assert_eq 1 "$(echo "$output" | wc -l | tr -d ' ')"
+
+  assert_command_fail dfx logs btc-adapter
+  assert_contains "There is no output of this process yet"
+}
+
+@test "dfx start runs the scheduled tasks of the local network" {
+  dfx_new
+  define_project_network
+  jq '.networks.local.tasks=[{"name":"greeting","interval":"1s","command":["echo","hello from a task"]}]' dfx.json | sponge dfx.json
+
+  dfx_start
+  sleep 3
+  assert_file_exists "$E2E_NETWORK_DATA_DIRECTORY/logs/tasks.log"
+  assert_command dfx logs tasks
+  assert_contains "[greeting] started"
+  assert_contains "hello from a task"
+}
+
+@test "dfx start rejects a scheduled task without a command" {
+  dfx_new
+  define_project_network
+  jq '.networks.local.tasks=[{"name":"nothing","interval":"1s"}]' dfx.json | sponge dfx.json
+
+  assert_command_fail dfx start --background
+  assert_contains "Task 'nothing' must specify either 'dfx' arguments or a non-empty 'command', but not both."
+}
+
 @test "modifying networks.json requires --clean on restart" {
   dfx_start
   dfx stop
@@ -0,0 +1,26 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "dfx subnet of only works on mainnet" {
+  dfx_start
+  dfx deploy hello_backend
+  assert_command_fail dfx subnet of hello_backend --network local
+  assert_contains "The subnet of a canister can only be looked up on mainnet."
+}
+
+@test "dfx subnet info rejects an invalid subnet id" {
+  assert_command_fail dfx subnet info not-a-principal
+}
@@ -0,0 +1,45 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "test deploys to an isolated network, runs the command and stops the network" {
+  assert_command dfx test -- sh -c 'dfx canister call "$CANISTER_ID_HELLO_BACKEND" greet "(\"test\")" >greeting.txt'
+  assert_contains "Starting an isolated local network"
+  assert_contains "Stopping the isolated local network"
+  assert_eq '("Hello, test!")' "$(cat greeting.txt)"
+
+  assert_command_fail dfx ping local
+  assert_command_fail dfx canister id hello_backend
+}
+
+@test "test leaves a running local network and its canisters alone" {
+  dfx_start
+  dfx deploy hello_backend
+  canister_id="$(dfx canister id hello_backend)"
+
+  assert_command dfx test -- true
+
+  assert_command dfx ping local
+  assert_command dfx canister id hello_backend
+  assert_eq "$canister_id"
+  assert_command dfx canister call hello_backend greet '("again")'
+  assert_eq '("Hello, again!")'
+}
+
+@test "test fails if the command fails" {
+  assert_command_fail dfx test -- false
+  assert_contains "The test command failed"
+  assert_contains "Stopping the isolated local network"
+}
@@ -0,0 +1,53 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  standard_teardown
+}
+
+@test "a project that pins a dfx version that is not installed is refused" {
+  use_test_specific_cache_root
+  jq '.dfx="0.1.0"' dfx.json | sponge dfx.json
+
+  assert_command_fail dfx cache show
+  assert_contains 'This project uses dfx 0.1.0, because the "dfx" field of dfx.json pins it'
+  assert_contains "dfx toolchain install 0.1.0"
+
+  assert_command_fail env DFX_VERSION=0.2.0 dfx cache show
+  assert_contains "This project uses dfx 0.2.0, because DFX_VERSION is set"
+}
+
+@test "commands are forwarded to the pinned dfx version" {
+  use_test_specific_cache_root
+  mkdir -p "$DFX_CACHE_ROOT/.cache/dfinity/versions/0.1.0"
+  cat > "$DFX_CACHE_ROOT/.cache/dfinity/versions/0.1.0/dfx" <<'SCRIPT'
+#!/usr/bin/env bash
+echo "forwarded: $*"
+SCRIPT
+  chmod +x "$DFX_CACHE_ROOT/.cache/dfinity/versions/0.1.0/dfx"
+  jq '.dfx="0.1.0"' dfx.json | sponge dfx.json
+
+  assert_command dfx cache show
+  assert_eq "forwarded: cache show" "$stdout"
+  assert_contains "The version of DFX used (0.1.0) is different than the version being run" "$stderr"
+}
+
+@test "toolchain use pins the running version" {
+  use_test_specific_cache_root
+  VERSION="$(dfx --version | cut -d' ' -f2)"
+  jq '.dfx="0.1.0"' dfx.json | sponge dfx.json
+
+  assert_command dfx toolchain use "$VERSION"
+  assert_contains "The project now uses dfx $VERSION."
+  assert_command jq -r .dfx dfx.json
+  assert_eq "$VERSION"
+  assert_command dfx cache show
+  assert_not_contains "0.1.0"
+}
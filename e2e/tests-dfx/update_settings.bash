@@ -25,6 +25,33 @@ teardown() {
     assert_contains "Reserved Cycles Limit: 650_000 Cycles"
 }
 
+@test "status reports the reserved cycles headroom and the idle burn rate" {
+  dfx_start
+  assert_command dfx deploy hello_backend
+  assert_command dfx canister update-settings hello_backend --reserved-cycles-limit 650000
+  assert_command dfx canister status hello_backend
+  assert_contains "Reserved: 0 Cycles"
+  assert_contains "Reserved Cycles Limit: 650_000 Cycles (650_000 Cycles left)"
+  assert_contains "Idle Cycles Burned Per Day:"
+  assert_contains "Runway:"
+}
+
+@test "status shows the wasm memory limit and warns about memory usage" {
+  dfx_start
+  assert_command dfx deploy hello_backend
+  assert_command dfx canister status hello_backend
+  assert_contains "Wasm Memory Limit: Not Set"
+  assert_not_contains "of the Wasm memory that a 32-bit canister can use"
+
+  assert_command dfx canister update-settings hello_backend --wasm-memory-limit 100MiB
+  assert_command dfx canister status hello_backend --memory-warning-threshold 1
+  assert_contains "Wasm Memory Limit: 104857600 Bytes"
+  assert_contains "of its Wasm memory limit (100.00 MiB)"
+
+  assert_command_fail dfx canister status hello_backend --memory-warning-threshold 101
+  assert_command_fail dfx canister status hello_backend --interval 1s
+}
+
 @test "set freezing threshold" {
   dfx_start
   assert_command dfx deploy hello_backend
@@ -42,6 +69,55 @@ teardown() {
   assert_match "Freezing threshold: 100_000_000_000"
 }
 
+@test "freeze and unfreeze a canister" {
+  dfx_start
+  assert_command dfx deploy hello_backend
+  assert_command dfx canister status hello_backend
+  assert_contains "Freezing threshold: 2_592_000"
+
+  assert_command dfx canister freeze hello_backend
+  assert_contains "Run \`dfx canister unfreeze hello_backend\` to restore its freezing threshold of 2592000 seconds."
+  assert_command_fail dfx canister call hello_backend greet '("alice")'
+  assert_command_fail dfx canister freeze hello_backend
+  assert_contains "already frozen"
+
+  assert_command dfx canister start hello_backend
+  assert_contains "still rejects calls"
+
+  assert_command dfx canister unfreeze hello_backend
+  assert_command dfx canister status hello_backend
+  assert_contains "Freezing threshold: 2_592_000"
+  assert_command dfx canister call hello_backend greet '("alice")'
+  assert_command_fail dfx canister unfreeze hello_backend
+  assert_contains "was not frozen"
+}
+
+@test "stop --wait waits until the canister has stopped" {
+  dfx_start
+  assert_command dfx deploy hello_backend
+  assert_command dfx canister stop hello_backend --wait
+  assert_contains "Canister hello_backend is stopped."
+  assert_command dfx canister status hello_backend
+  assert_contains "Status: Stopped"
+}
+
+@test "deploy applies initialization values to existing canisters" {
+  dfx_start
+  assert_command dfx deploy hello_backend
+  assert_command dfx canister status hello_backend
+  assert_contains "Freezing threshold: 2_592_000"
+
+  jq '.canisters.hello_backend.initialization_values.freezing_threshold="14days"' dfx.json | sponge dfx.json
+  assert_command dfx deploy hello_backend
+  assert_contains "Updating settings of hello_backend"
+  assert_command dfx canister status hello_backend
+  assert_contains "Freezing threshold: 1_209_600"
+
+  # settings that already match are not sent again
+  assert_command dfx deploy hello_backend
+  assert_not_contains "Updating settings of hello_backend"
+}
+
 @test "set controller" {
   # Create two identities
   assert_command dfx identity new --storage-mode plaintext alice
@@ -63,16 +139,16 @@ teardown() {
   assert_match "Set controller of \"hello_backend\" to: bob"
 
   # Bob is controller, Alice cannot reinstall
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall
 
   # Bob can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob
 
   assert_command dfx identity use bob
   # Set controller using canister id and principal
   assert_command dfx canister update-settings "$ID" --set-controller "${ALICE_PRINCIPAL}" --yes
   assert_match "Set controller of \"${ID}\" to: ${ALICE_PRINCIPAL}"
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall
 
   # Set controller using combination of name/id and identity/principal
   assert_command dfx canister update-settings hello_backend --set-controller "${BOB_PRINCIPAL}" --identity alice --yes
@@ -116,16 +192,16 @@ teardown() {
   assert_match "Set controller of \"hello_backend\" to: ${BOB_WALLET}"
 
   # Bob is controller, Alice cannot reinstall
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${ALICE_WALLET}"
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${ALICE_WALLET}"
 
   # Bob can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
 
   assert_command dfx identity use bob
   # Set controller using canister id and principal
   assert_command dfx canister update-settings "${ID}" --set-controller "${ALICE_WALLET}" --wallet "${BOB_WALLET}" --yes
   assert_match "Set controller of \"${ID}\" to: ${ALICE_WALLET}"
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${BOB_WALLET}"
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${BOB_WALLET}"
 
   # Set controller using combination of name/id and identity/principal
   assert_command dfx canister update-settings hello_backend --set-controller "${BOB_WALLET}" --identity alice --wallet "${ALICE_WALLET}" --yes
@@ -171,16 +247,16 @@ teardown() {
   assert_match "Set controller of \"hello_backend\" to: ${BOB_WALLET}"
 
   # Bob is controller, Alice cannot reinstall
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${ALICE_WALLET}"
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${ALICE_WALLET}"
 
   # Bob can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
 
   assert_command dfx identity use bob
   # Set controller using canister id and principal
   assert_command dfx canister update-settings "${ID}" --set-controller "${ALICE_WALLET}" --wallet "${BOB_WALLET}" --yes
   assert_match "Set controller of \"${ID}\" to: ${ALICE_WALLET}"
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${BOB_WALLET}"
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall --wallet "${BOB_WALLET}"
 
   # Set controller using combination of name/id and identity/principal
   assert_command dfx canister update-settings hello_backend --set-controller "${BOB_WALLET}" --identity alice --wallet "${ALICE_WALLET}" --yes
@@ -226,16 +302,16 @@ teardown() {
   assert_match "Set controller of \"hello_backend\" to: bob"
 
   # Bob is controller, Alice cannot reinstall
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall
 
   # Bob can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob
 
   assert_command dfx identity use bob
   # Set controller using canister id and principal
   assert_command dfx canister update-settings "$ID" --set-controller "${ALICE_PRINCIPAL}" --yes
   assert_match "Set controller of \"${ID}\" to: ${ALICE_PRINCIPAL}"
-  echo "yes" | assert_command_fail dfx canister install hello_backend -m reinstall
+  echo "hello_backend" | assert_command_fail dfx canister install hello_backend -m reinstall
 
   # Set controller using combination of name/id and identity/principal
   assert_command dfx canister update-settings hello_backend --set-controller "${BOB_PRINCIPAL}" --identity alice --yes
@@ -282,8 +358,8 @@ teardown() {
   assert_match "Set controllers of \"hello_backend\" to: $PRINCIPALS_SORTED"
 
   # Both can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity alice
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity alice
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob
 
   assert_command dfx canister info hello_backend
   assert_match "Controllers: ${PRINCIPALS_SORTED}"
@@ -311,8 +387,8 @@ teardown() {
   assert_match "Set controllers of \"hello_backend\" to: ${WALLETS_SORTED}"
 
   # Both can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity alice --wallet "${ALICE_WALLET}"
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity alice --wallet "${ALICE_WALLET}"
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
 
   assert_command dfx canister info hello_backend
   assert_match "Controllers: ${WALLETS_SORTED}"
@@ -342,8 +418,8 @@ teardown() {
   assert_match "Set controllers of \"hello_backend\" to: $WALLETS_SORTED"
 
   # Both can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity alice --wallet "${ALICE_WALLET}"
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity alice --wallet "${ALICE_WALLET}"
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob --wallet "${BOB_WALLET}"
 
   assert_command dfx canister info hello_backend
   assert_match "Controllers: ${WALLETS_SORTED}"
@@ -374,8 +450,8 @@ teardown() {
   assert_match "Set controllers of \"hello_backend\" to: $PRINCIPALS_SORTED"
 
   # Both can reinstall
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity alice
-  echo "yes" | assert_command dfx canister install hello_backend -m reinstall --identity bob
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity alice
+  echo "hello_backend" | assert_command dfx canister install hello_backend -m reinstall --identity bob
 
   assert_command dfx canister info hello_backend
   assert_match "Controllers: ${PRINCIPALS_SORTED}"
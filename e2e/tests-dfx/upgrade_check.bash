@@ -46,7 +46,7 @@ teardown() {
   dfx canister call hello_backend inc '()'
   jq '.canisters.hello_backend.main="v2_bad.mo"' dfx.json | sponge dfx.json
   dfx build
-  echo yes | dfx canister install hello_backend --mode=reinstall
+  echo hello_backend | dfx canister install hello_backend --mode=reinstall
   assert_command dfx canister call hello_backend read '()'
   assert_match "(0 : nat)"
 }
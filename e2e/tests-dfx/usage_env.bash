@@ -47,3 +47,17 @@ teardown() {
     rm -rf hello
   )
 }
+
+@test "dfx state dir env var replaces the .dfx directory of the project" {
+  dfx_new hello
+  export DFX_STATE_DIR="$E2E_TEMP_DIR/state"
+
+  assert_command dfx build --check
+  assert_command ls "$DFX_STATE_DIR/local/canisters/hello_backend"
+  assert_directory_not_exists .dfx
+
+  unset DFX_STATE_DIR
+  assert_command dfx build --check --state-dir "$E2E_TEMP_DIR/other-state"
+  assert_command ls "$E2E_TEMP_DIR/other-state/local/canisters/hello_backend"
+  assert_directory_not_exists .dfx
+}
@@ -0,0 +1,38 @@
+#!/usr/bin/env bats
+
+load ../utils/_
+
+setup() {
+  standard_setup
+
+  dfx_new hello
+}
+
+teardown() {
+  dfx_stop
+
+  standard_teardown
+}
+
+@test "canister verify compares the local build with the deployed module" {
+  dfx_start
+  assert_command dfx deploy hello_backend
+
+  assert_command dfx canister verify hello_backend
+  assert_contains "Verified: the module deployed in canister hello_backend"
+  assert_contains "Module hash: 0x"
+
+  # a change to the source code produces a different module
+  sed -i.bak 's/Hello, /Hi, /' src/hello_backend/main.mo
+  assert_command_fail dfx canister verify hello_backend
+  assert_contains "Verification failed: the module deployed in canister hello_backend"
+  assert_contains "Deployed module hash: 0x"
+}
+
+@test "canister verify fails if no module is installed" {
+  dfx_start
+  assert_command dfx canister create hello_backend
+
+  assert_command_fail dfx canister verify hello_backend
+  assert_contains "No module is installed in canister hello_backend"
+}
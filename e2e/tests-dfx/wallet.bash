@@ -34,6 +34,24 @@ teardown() {
     assert_contains "Deposited 42 cycles"
 }
 
+@test "wallet balance --all-networks reports the wallets of all identities" {
+    dfx_start
+
+    dfx identity new --storage-mode plaintext alice
+    assert_command dfx wallet balance --all-networks
+    assert_contains "No wallets are configured."
+
+    DEFAULT_WALLET="$(dfx identity get-wallet)"
+    ALICE_WALLET="$(dfx identity get-wallet --identity alice)"
+
+    assert_command dfx wallet balance --all-networks
+    assert_contains "local default $DEFAULT_WALLET: "
+    assert_contains "local alice $ALICE_WALLET: "
+
+    assert_command_fail dfx wallet --network local balance --all-networks
+    assert_contains "--all-networks cannot be combined with a network selection."
+}
+
 @test "DFX_WALLET_WASM environment variable overrides wallet module wasm at installation" {
   dfx_new hello
   dfx_start
@@ -248,3 +266,58 @@ teardown() {
   # balance may be 109.??? TC if cycles accounting is done, or 110.000 TC if not
   assert_match "109\.|110\."
 }
+
+@test "rotating the key of an identity moves control of its wallet to the new principal" {
+  dfx_start
+  assert_command dfx identity new alice --storage-mode plaintext
+  assert_command dfx identity use alice
+  assert_command dfx identity get-wallet
+  OLD_PRINCIPAL="$(dfx identity get-principal)"
+
+  assert_command "${BATS_TEST_DIRNAME}/../assets/expect_scripts/rotate_key_of_identity.exp" alice
+  NEW_PRINCIPAL="$(dfx identity get-principal)"
+  assert_neq "$OLD_PRINCIPAL" "$NEW_PRINCIPAL"
+
+  assert_command dfx wallet controllers
+  assert_match "$NEW_PRINCIPAL"
+  assert_not_match "$OLD_PRINCIPAL"
+  assert_command dfx wallet custodians
+  assert_not_match "$OLD_PRINCIPAL"
+}
+
+@test "removing yourself from the wallet asks for confirmation" {
+  dfx_start
+  assert_command dfx identity get-wallet
+  PRINCIPAL="$(dfx identity get-principal)"
+
+  echo "no" | assert_command_fail dfx wallet remove-controller "$PRINCIPAL"
+  echo "no" | assert_command_fail dfx wallet deauthorize "$PRINCIPAL"
+  assert_command dfx wallet controllers
+  assert_match "$PRINCIPAL"
+  assert_command dfx wallet custodians
+  assert_match "$PRINCIPAL"
+}
+
+@test "wallet transfer-ownership hands the wallet over to another identity" {
+  dfx_start
+  assert_command dfx identity new alice --storage-mode plaintext
+  assert_command dfx identity new bob --storage-mode plaintext
+  ALICE_PRINCIPAL="$(dfx identity get-principal --identity alice)"
+  BOB_PRINCIPAL="$(dfx identity get-principal --identity bob)"
+  WALLET_ID="$(dfx identity get-wallet --identity alice)"
+
+  echo "no" | assert_command_fail dfx wallet transfer-ownership "$BOB_PRINCIPAL" --identity alice
+
+  assert_command dfx wallet transfer-ownership "$BOB_PRINCIPAL" --identity alice --yes
+  assert_contains "Transferred the wallet $WALLET_ID to $BOB_PRINCIPAL."
+
+  assert_command dfx identity set-wallet "$WALLET_ID" --identity bob
+  assert_command dfx wallet controllers --identity bob
+  assert_match "$BOB_PRINCIPAL"
+  assert_not_match "$ALICE_PRINCIPAL"
+  assert_command dfx wallet custodians --identity bob
+  assert_not_match "$ALICE_PRINCIPAL"
+  assert_command dfx canister info "$WALLET_ID"
+  assert_contains "$BOB_PRINCIPAL"
+  assert_not_contains "$ALICE_PRINCIPAL"
+}
@@ -1,6 +1,7 @@
 use crate::asset::config::AssetConfig;
 use crate::asset::content::Content;
 use crate::asset::content_encoder::ContentEncoder;
+use crate::batch_upload::resume::ResumeState;
 use crate::batch_upload::semaphores::Semaphores;
 use crate::canister_api::methods::chunk::create_chunk;
 use crate::canister_api::types::asset::AssetDetails;
@@ -9,15 +10,17 @@ use crate::error::CreateEncodingError;
 use crate::error::CreateEncodingError::EncodeContentFailed;
 use crate::error::CreateProjectAssetError;
 use candid::Nat;
+use dfx_core::error::structured_file::StructuredFileError;
 use futures::future::try_join_all;
 use futures::TryFutureExt;
 use ic_utils::Canister;
 use mime::Mime;
+use sha2::{Digest, Sha256};
 use slog::{debug, info, Logger};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 const CONTENT_ENCODING_IDENTITY: &str = "identity";
 
@@ -51,6 +54,8 @@ pub(crate) struct ChunkUploader<'agent> {
     batch_id: Nat,
     chunks: Arc<AtomicUsize>,
     bytes: Arc<AtomicUsize>,
+    resumed_chunks: Arc<AtomicUsize>,
+    resume: Option<Mutex<ResumeState>>,
 }
 impl<'agent> ChunkUploader<'agent> {
     pub(crate) fn new(canister: Canister<'agent>, batch_id: Nat) -> Self {
@@ -59,17 +64,50 @@ impl<'agent> ChunkUploader<'agent> {
             batch_id,
             chunks: Arc::new(AtomicUsize::new(0)),
             bytes: Arc::new(AtomicUsize::new(0)),
+            resumed_chunks: Arc::new(AtomicUsize::new(0)),
+            resume: None,
         }
     }
 
+    /// Records uploaded chunks, and reuses chunks that a previous attempt already uploaded to the batch.
+    pub(crate) fn with_resume_state(mut self, resume: ResumeState) -> Self {
+        self.resume = Some(Mutex::new(resume));
+        self
+    }
+
     pub(crate) async fn create_chunk(
         &self,
         contents: &[u8],
         semaphores: &Semaphores,
     ) -> Result<Nat, CreateChunkError> {
+        let Some(resume) = &self.resume else {
+            self.chunks.fetch_add(1, Ordering::SeqCst);
+            self.bytes.fetch_add(contents.len(), Ordering::SeqCst);
+            return create_chunk(&self.canister, &self.batch_id, contents, semaphores).await;
+        };
+
+        let sha256 = hex::encode(Sha256::digest(contents));
+        if let Some(chunk_id) = resume.lock().unwrap().take(&sha256) {
+            self.resumed_chunks.fetch_add(1, Ordering::SeqCst);
+            return Ok(chunk_id);
+        }
         self.chunks.fetch_add(1, Ordering::SeqCst);
         self.bytes.fetch_add(contents.len(), Ordering::SeqCst);
-        create_chunk(&self.canister, &self.batch_id, contents, semaphores).await
+        let chunk_id = create_chunk(&self.canister, &self.batch_id, contents, semaphores).await?;
+        resume
+            .lock()
+            .unwrap()
+            .record(sha256, &chunk_id)
+            .map_err(CreateChunkError::SaveResumeFile)?;
+        Ok(chunk_id)
+    }
+
+    /// Writes out the chunks uploaded so far, so that a later attempt can reuse them.
+    pub(crate) fn save_resume_state(&self) -> Result<(), StructuredFileError> {
+        match &self.resume {
+            Some(resume) => resume.lock().unwrap().save(),
+            None => Ok(()),
+        }
     }
 
     pub(crate) fn bytes(&self) -> usize {
@@ -78,6 +116,9 @@ impl<'agent> ChunkUploader<'agent> {
     pub(crate) fn chunks(&self) -> usize {
         self.chunks.load(Ordering::SeqCst)
     }
+    pub(crate) fn resumed_chunks(&self) -> usize {
+        self.resumed_chunks.load(Ordering::SeqCst)
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -283,10 +324,9 @@ pub(crate) async fn make_project_assets(
     chunk_upload_target: Option<&ChunkUploader<'_>>,
     asset_descriptors: Vec<AssetDescriptor>,
     canister_assets: &HashMap<String, AssetDetails>,
+    semaphores: &Semaphores,
     logger: &Logger,
 ) -> Result<HashMap<String, ProjectAsset>, CreateProjectAssetError> {
-    let semaphores = Semaphores::new();
-
     let project_asset_futures: Vec<_> = asset_descriptors
         .iter()
         .map(|loc| {
@@ -294,7 +334,7 @@ pub(crate) async fn make_project_assets(
                 chunk_upload_target,
                 loc.clone(),
                 canister_assets,
-                &semaphores,
+                semaphores,
                 logger,
             )
         })
@@ -0,0 +1,101 @@
+use candid::{Nat, Principal};
+use dfx_core::error::structured_file::StructuredFileError;
+use dfx_core::json::{load_json_file, save_json_file};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+// How often, at most, the resume file is rewritten while chunks are being uploaded.
+const SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The chunks that a sync uploaded to a batch, as recorded in the resume file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ResumeFile {
+    canister_id: String,
+    pub(crate) batch_id: u64,
+    // chunk ids, by the hex-encoded sha256 of the chunk contents
+    chunks: HashMap<String, Vec<u64>>,
+}
+
+impl ResumeFile {
+    /// Loads the record of a previous, interrupted sync to the same canister, if there is one.
+    pub(crate) fn load(path: &Path, canister_id: &Principal) -> Option<ResumeFile> {
+        if !path.exists() {
+            return None;
+        }
+        load_json_file::<ResumeFile>(path)
+            .ok()
+            .filter(|file| file.canister_id == canister_id.to_text())
+    }
+
+    pub(crate) fn remove(path: &Path) {
+        if path.exists() {
+            let _ = dfx_core::fs::remove_file(path);
+        }
+    }
+}
+
+/// Tracks the chunks uploaded to a batch, reusing those that a previous attempt already
+/// uploaded to the same batch.
+pub(crate) struct ResumeState {
+    path: PathBuf,
+    file: ResumeFile,
+    // Chunks uploaded by the previous attempt that this attempt has not used yet.
+    // A chunk is consumed when its content is set, so each chunk id can only be used once.
+    available: HashMap<String, Vec<u64>>,
+    last_saved: Instant,
+}
+
+impl ResumeState {
+    pub(crate) fn new(
+        path: &Path,
+        canister_id: &Principal,
+        batch_id: &Nat,
+        previous: Option<ResumeFile>,
+    ) -> Option<Self> {
+        let batch_id = u64::try_from(&batch_id.0).ok()?;
+        let chunks = previous
+            .filter(|previous| previous.batch_id == batch_id)
+            .map(|previous| previous.chunks)
+            .unwrap_or_default();
+        Some(Self {
+            path: path.to_path_buf(),
+            available: chunks.clone(),
+            file: ResumeFile {
+                canister_id: canister_id.to_text(),
+                batch_id,
+                chunks,
+            },
+            last_saved: Instant::now(),
+        })
+    }
+
+    /// Takes a chunk with the given contents that was already uploaded to the batch.
+    pub(crate) fn take(&mut self, sha256: &str) -> Option<Nat> {
+        self.available
+            .get_mut(sha256)
+            .and_then(|ids| ids.pop())
+            .map(Nat::from)
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        sha256: String,
+        chunk_id: &Nat,
+    ) -> Result<(), StructuredFileError> {
+        if let Ok(chunk_id) = u64::try_from(&chunk_id.0) {
+            self.file.chunks.entry(sha256).or_default().push(chunk_id);
+        }
+        if self.last_saved.elapsed() >= SAVE_INTERVAL {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn save(&mut self) -> Result<(), StructuredFileError> {
+        save_json_file(&self.path, &self.file)?;
+        self.last_saved = Instant::now();
+        Ok(())
+    }
+}
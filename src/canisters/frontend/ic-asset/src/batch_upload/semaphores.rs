@@ -3,14 +3,11 @@ use futures_intrusive::sync::SharedSemaphore;
 // Maximum MB of file data to load at once.  More memory may be used, due to encodings.
 const MAX_SIMULTANEOUS_LOADED_MB: usize = 50;
 
-// How many simultaneous chunks being created at once
-const MAX_SIMULTANEOUS_CREATE_CHUNK: usize = 12;
+// How many simultaneous chunks being created at once, per simultaneous call
+const CREATE_CHUNK_PER_CALL: usize = 3;
 
-// How many simultaneous Agent.call() to create_chunk
-const MAX_SIMULTANEOUS_CREATE_CHUNK_CALLS: usize = 4;
-
-// How many simultaneous Agent.wait() on create_chunk result
-const MAX_SIMULTANEOUS_CREATE_CHUNK_WAITS: usize = 4;
+// How many simultaneous Agent.call() to create_chunk, and Agent.wait() on create_chunk result
+const DEFAULT_PARALLELISM: usize = 4;
 
 pub(crate) struct Semaphores {
     // The "file" semaphore limits how much file data to load at once.  A given loaded file's data
@@ -35,13 +32,19 @@ pub(crate) struct Semaphores {
 
 impl Semaphores {
     pub fn new() -> Semaphores {
+        Self::with_parallelism(DEFAULT_PARALLELISM)
+    }
+
+    pub fn with_parallelism(parallelism: usize) -> Semaphores {
+        let parallelism = parallelism.max(1);
+
         let file = SharedSemaphore::new(true, MAX_SIMULTANEOUS_LOADED_MB);
 
-        let create_chunk = SharedSemaphore::new(true, MAX_SIMULTANEOUS_CREATE_CHUNK);
+        let create_chunk = SharedSemaphore::new(true, parallelism * CREATE_CHUNK_PER_CALL);
 
-        let create_chunk_call = SharedSemaphore::new(true, MAX_SIMULTANEOUS_CREATE_CHUNK_CALLS);
+        let create_chunk_call = SharedSemaphore::new(true, parallelism);
 
-        let create_chunk_wait = SharedSemaphore::new(true, MAX_SIMULTANEOUS_CREATE_CHUNK_WAITS);
+        let create_chunk_wait = SharedSemaphore::new(true, parallelism);
 
         Semaphores {
             file,
@@ -0,0 +1,33 @@
+use crate::canister_api::methods::method_names::{HTTP_REQUEST, HTTP_REQUEST_STREAMING_CALLBACK};
+use crate::canister_api::types::http::{
+    HttpRequest, HttpResponse, StreamingCallbackHttpResponse, StreamingCallbackToken,
+};
+use ic_agent::AgentError;
+use ic_utils::call::SyncCall;
+use ic_utils::Canister;
+
+pub(crate) async fn http_request(
+    canister: &Canister<'_>,
+    request: HttpRequest,
+) -> Result<HttpResponse, AgentError> {
+    let (response,): (HttpResponse,) = canister
+        .query(HTTP_REQUEST)
+        .with_arg(request)
+        .build()
+        .call()
+        .await?;
+    Ok(response)
+}
+
+pub(crate) async fn http_request_streaming_callback(
+    canister: &Canister<'_>,
+    token: StreamingCallbackToken,
+) -> Result<Option<StreamingCallbackHttpResponse>, AgentError> {
+    let (response,): (Option<StreamingCallbackHttpResponse>,) = canister
+        .query(HTTP_REQUEST_STREAMING_CALLBACK)
+        .with_arg(token)
+        .build()
+        .call()
+        .await?;
+    Ok(response)
+}
@@ -4,5 +4,7 @@ pub(crate) const COMPUTE_EVIDENCE: &str = "compute_evidence";
 pub(crate) const CREATE_BATCH: &str = "create_batch";
 pub(crate) const CREATE_CHUNK: &str = "create_chunk";
 pub(crate) const GET_ASSET_PROPERTIES: &str = "get_asset_properties";
+pub(crate) const HTTP_REQUEST: &str = "http_request";
+pub(crate) const HTTP_REQUEST_STREAMING_CALLBACK: &str = "http_request_streaming_callback";
 pub(crate) const LIST: &str = "list";
 pub(crate) const PROPOSE_COMMIT_BATCH: &str = "propose_commit_batch";
@@ -0,0 +1,47 @@
+use candid::{CandidType, Func, Nat};
+use serde::Deserialize;
+use serde_bytes::ByteBuf;
+
+/// An HTTP request, as passed to the `http_request` method.
+#[derive(CandidType, Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: ByteBuf,
+    pub certificate_version: Option<u16>,
+}
+
+/// An HTTP response, as returned by the `http_request` method.
+#[derive(CandidType, Debug, Deserialize)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: ByteBuf,
+    pub streaming_strategy: Option<StreamingStrategy>,
+}
+
+/// Identifies the next chunk of a streamed response body.
+#[derive(CandidType, Clone, Debug, Deserialize)]
+pub struct StreamingCallbackToken {
+    pub key: String,
+    pub content_encoding: String,
+    pub index: Nat,
+    pub sha256: Option<ByteBuf>,
+}
+
+/// How to retrieve the remainder of a response body.
+#[derive(CandidType, Debug, Deserialize)]
+pub enum StreamingStrategy {
+    Callback {
+        callback: Func,
+        token: StreamingCallbackToken,
+    },
+}
+
+/// A chunk of a streamed response body.
+#[derive(CandidType, Debug, Deserialize)]
+pub struct StreamingCallbackHttpResponse {
+    pub body: ByteBuf,
+    pub token: Option<StreamingCallbackToken>,
+}
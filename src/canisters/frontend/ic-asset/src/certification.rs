@@ -0,0 +1,118 @@
+use crate::canister_api::methods::http::{http_request, http_request_streaming_callback};
+use crate::canister_api::methods::list::list_assets;
+use crate::canister_api::types::http::{HttpRequest, HttpResponse, StreamingStrategy};
+use crate::error::CheckCertificationError;
+use crate::error::CheckCertificationError::{
+    CertificationFailed, HttpRequestFailed, ListAssetsFailed,
+};
+use ic_utils::Canister;
+use serde_bytes::ByteBuf;
+use slog::{info, warn, Logger};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The certification version that assets are checked against.
+const CERTIFICATE_VERSION: u16 = 2;
+
+/// How far the time of a certificate may deviate from the local clock.
+const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000; // 5 minutes
+
+/// Checks that every encoding of every asset in the asset canister is served with a valid
+/// certificate, using response verification v2 as done by HTTP gateways and service workers.
+pub async fn check_certification(
+    canister: &Canister<'_>,
+    logger: &Logger,
+) -> Result<(), CheckCertificationError> {
+    info!(logger, "Checking the certification of assets.");
+    let assets = list_assets(canister).await.map_err(ListAssetsFailed)?;
+    let root_key = canister.agent_().read_root_key();
+
+    let mut keys: Vec<_> = assets.keys().collect();
+    keys.sort();
+    let mut failures = 0;
+    for key in keys {
+        for encoding in &assets[key].encodings {
+            let content_encoding = &encoding.content_encoding;
+            let headers = vec![("Accept-Encoding".to_string(), content_encoding.clone())];
+            let response = fetch(canister, key, headers.clone()).await?;
+            let request = ic_http_certification::http::HttpRequest {
+                method: "GET".to_string(),
+                url: key.clone(),
+                headers,
+                body: Vec::new().into(),
+            };
+            let response = ic_http_certification::http::HttpResponse {
+                status_code: response.status_code,
+                headers: response.headers,
+                body: response.body.into_vec().into(),
+                upgrade: None,
+            };
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards.")
+                .as_nanos();
+            let result = ic_response_verification::verify_request_response_pair(
+                request,
+                response,
+                canister.canister_id_().as_slice(),
+                now,
+                MAX_CERT_TIME_OFFSET_NS,
+                &root_key,
+                CERTIFICATE_VERSION as u8,
+            );
+            match result {
+                Ok(info) if info.response.is_some() => {}
+                Ok(_) => {
+                    warn!(logger, "{} ({}): not certified", key, content_encoding);
+                    failures += 1;
+                }
+                Err(err) => {
+                    warn!(logger, "{} ({}): {}", key, content_encoding, err);
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(CertificationFailed(failures));
+    }
+    info!(logger, "All assets are certified.");
+    Ok(())
+}
+
+/// Requests an asset, following the streaming callback to retrieve the whole body.
+async fn fetch(
+    canister: &Canister<'_>,
+    key: &str,
+    headers: Vec<(String, String)>,
+) -> Result<HttpResponse, CheckCertificationError> {
+    let request = HttpRequest {
+        method: "GET".to_string(),
+        url: key.to_string(),
+        headers,
+        body: ByteBuf::new(),
+        certificate_version: Some(CERTIFICATE_VERSION),
+    };
+    let mut response = http_request(canister, request)
+        .await
+        .map_err(|e| HttpRequestFailed(key.to_string(), e))?;
+    let mut body = response.body.into_vec();
+    let mut token = match response.streaming_strategy.take() {
+        Some(StreamingStrategy::Callback { token, .. }) => Some(token),
+        None => None,
+    };
+    while let Some(next) = token {
+        let chunk = http_request_streaming_callback(canister, next)
+            .await
+            .map_err(|e| HttpRequestFailed(key.to_string(), e))?;
+        match chunk {
+            Some(chunk) => {
+                body.extend_from_slice(&chunk.body);
+                token = chunk.token;
+            }
+            None => token = None,
+        }
+    }
+    response.body = ByteBuf::from(body);
+    Ok(response)
+}
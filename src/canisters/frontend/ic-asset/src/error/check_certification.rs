@@ -0,0 +1,18 @@
+use ic_agent::AgentError;
+use thiserror::Error;
+
+/// Errors related to checking the certification of the assets in an asset canister.
+#[derive(Error, Debug)]
+pub enum CheckCertificationError {
+    /// One or more assets are not served with a valid certificate.
+    #[error("{0} asset(s) failed the certification check")]
+    CertificationFailed(usize),
+
+    /// Failed when calling http_request or http_request_streaming_callback.
+    #[error("Failed to request asset {0}: {1}")]
+    HttpRequestFailed(String, AgentError),
+
+    /// Failed when calling the list method.
+    #[error("List assets failed: {0}")]
+    ListAssetsFailed(AgentError),
+}
@@ -1,3 +1,4 @@
+use dfx_core::error::structured_file::StructuredFileError;
 use ic_agent::AgentError;
 use thiserror::Error;
 
@@ -11,4 +12,8 @@ pub enum CreateChunkError {
     /// Failed to decode the create chunk response.
     #[error("Failed to decode create chunk response: {0}")]
     DecodeCreateChunkResponse(candid::Error),
+
+    /// Failed to record the uploaded chunk in the resume file.
+    #[error("Failed to save resume file: {0}")]
+    SaveResumeFile(StructuredFileError),
 }
@@ -1,5 +1,6 @@
 //! Error types
 
+mod check_certification;
 mod compatibility;
 mod compute_evidence;
 mod create_chunk;
@@ -17,6 +18,7 @@ mod sync;
 mod upload;
 mod upload_content;
 
+pub use check_certification::CheckCertificationError;
 pub use compatibility::CompatibilityError;
 pub use compute_evidence::ComputeEvidenceError;
 pub use create_chunk::CreateChunkError;
@@ -1,6 +1,7 @@
 use crate::error::create_project_asset::CreateProjectAssetError;
 use crate::error::gather_asset_descriptors::GatherAssetDescriptorsError;
 use crate::error::get_asset_properties::GetAssetPropertiesError;
+use dfx_core::error::structured_file::StructuredFileError;
 use ic_agent::AgentError;
 use thiserror::Error;
 
@@ -26,4 +27,8 @@ pub enum UploadContentError {
     /// Failed when calling the list method.
     #[error("Failed to list assets: {0}")]
     ListAssetsFailed(AgentError),
+
+    /// Failed to record the uploaded chunks in the resume file.
+    #[error("Failed to save resume file: {0}")]
+    SaveResumeFileFailed(StructuredFileError),
 }
@@ -3,6 +3,7 @@ use crate::asset::content_encoder::ContentEncoder::Gzip;
 use crate::batch_upload::operations::assemble_batch_operations;
 use crate::batch_upload::operations::AssetDeletionReason::Obsolete;
 use crate::batch_upload::plumbing::{make_project_assets, ProjectAsset};
+use crate::batch_upload::semaphores::Semaphores;
 use crate::canister_api::methods::asset_properties::get_assets_properties;
 use crate::canister_api::methods::list::list_assets;
 use crate::canister_api::types::asset::SetAssetPropertiesArguments;
@@ -55,8 +56,14 @@ pub async fn compute_evidence(
         logger,
         "Computing evidence for batch operations for assets in the project.",
     );
-    let project_assets =
-        make_project_assets(None, asset_descriptors, &canister_assets, logger).await?;
+    let project_assets = make_project_assets(
+        None,
+        asset_descriptors,
+        &canister_assets,
+        &Semaphores::new(),
+        logger,
+    )
+    .await?;
 
     let mut operations = assemble_batch_operations(
         &project_assets,
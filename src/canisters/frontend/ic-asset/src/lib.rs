@@ -34,12 +34,16 @@
 mod asset;
 mod batch_upload;
 mod canister_api;
+mod certification;
 pub mod error;
 mod evidence;
 mod sync;
 mod upload;
 
+pub use certification::check_certification;
 pub use evidence::compute_evidence;
 pub use sync::prepare_sync_for_proposal;
 pub use sync::sync;
+pub use sync::sync_with_options;
+pub use sync::SyncOptions;
 pub use upload::upload;
@@ -3,12 +3,15 @@ use crate::asset::config::{
 };
 use crate::batch_upload::operations::BATCH_UPLOAD_API_VERSION;
 use crate::batch_upload::plumbing::ChunkUploader;
+use crate::batch_upload::resume::{ResumeFile, ResumeState};
+use crate::batch_upload::semaphores::Semaphores;
 use crate::batch_upload::{
     self,
     operations::AssetDeletionReason,
     plumbing::{make_project_assets, AssetDescriptor},
 };
 use crate::canister_api::methods::batch::{compute_evidence, propose_commit_batch};
+use crate::canister_api::methods::chunk::create_chunk;
 use crate::canister_api::methods::{
     api_version::api_version,
     asset_properties::get_assets_properties,
@@ -29,19 +32,33 @@ use crate::error::PrepareSyncForProposalError;
 use crate::error::SyncError;
 use crate::error::SyncError::CommitBatchFailed;
 use crate::error::UploadContentError;
-use crate::error::UploadContentError::{CreateBatchFailed, ListAssetsFailed};
+use crate::error::UploadContentError::{CreateBatchFailed, ListAssetsFailed, SaveResumeFileFailed};
 use candid::Nat;
 use ic_agent::AgentError;
 use ic_utils::Canister;
 use slog::{debug, info, trace, warn, Logger};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+/// Options that control how [`sync_with_options`] uploads content.
+#[derive(Clone, Debug, Default)]
+pub struct SyncOptions {
+    /// The maximum number of simultaneous create_chunk calls.  Defaults to 4.
+    pub parallelism: Option<usize>,
+
+    /// A file that records the chunks uploaded so far.  If a sync is interrupted, the next sync
+    /// with the same file continues the same batch and only uploads the chunks that are missing,
+    /// as long as the canister has not expired the batch yet.  The file is removed once the batch
+    /// is committed.
+    pub resume_file: Option<PathBuf>,
+}
+
 /// Sets the contents of the asset canister to the contents of a directory, including deleting old assets.
 pub async fn upload_content_and_assemble_sync_operations(
     canister: &Canister<'_>,
     dirs: &[&Path],
+    options: &SyncOptions,
     logger: &Logger,
 ) -> Result<CommitBatchArguments, UploadContentError> {
     let asset_descriptors = gather_asset_descriptors(dirs, logger)?;
@@ -53,25 +70,58 @@ pub async fn upload_content_and_assemble_sync_operations(
     );
     let canister_asset_properties = get_assets_properties(canister, &canister_assets).await?;
 
-    info!(logger, "Starting batch.");
+    let semaphores = options
+        .parallelism
+        .map_or_else(Semaphores::new, Semaphores::with_parallelism);
 
-    let batch_id = create_batch(canister).await.map_err(CreateBatchFailed)?;
+    let previous = options
+        .resume_file
+        .as_deref()
+        .and_then(|path| ResumeFile::load(path, canister.canister_id()));
+
+    let batch_id = match resume_batch(canister, previous.as_ref(), &semaphores).await {
+        Some(batch_id) => {
+            info!(logger, "Resuming batch {}.", batch_id);
+            batch_id
+        }
+        None => {
+            info!(logger, "Starting batch.");
+            create_batch(canister).await.map_err(CreateBatchFailed)?
+        }
+    };
 
     info!(
         logger,
         "Staging contents of new and changed assets in batch {}:", batch_id
     );
 
-    let chunk_uploader = ChunkUploader::new(canister.clone(), batch_id.clone());
+    let mut chunk_uploader = ChunkUploader::new(canister.clone(), batch_id.clone());
+    if let Some(path) = &options.resume_file {
+        if let Some(resume) = ResumeState::new(path, canister.canister_id(), &batch_id, previous) {
+            chunk_uploader = chunk_uploader.with_resume_state(resume);
+        }
+    }
 
     let project_assets = make_project_assets(
         Some(&chunk_uploader),
         asset_descriptors,
         &canister_assets,
+        &semaphores,
         logger,
     )
     .await?;
 
+    chunk_uploader
+        .save_resume_state()
+        .map_err(SaveResumeFileFailed)?;
+    if chunk_uploader.resumed_chunks() > 0 {
+        info!(
+            logger,
+            "Reused {} chunks uploaded by an earlier attempt.",
+            chunk_uploader.resumed_chunks()
+        );
+    }
+
     let commit_batch_args = batch_upload::operations::assemble_commit_batch_arguments(
         project_assets,
         canister_assets,
@@ -99,14 +149,38 @@ pub async fn upload_content_and_assemble_sync_operations(
     Ok(commit_batch_args)
 }
 
+/// Returns the batch of an interrupted sync, if the canister still has it.
+/// The canister expires batches that see no new chunks for a while.
+async fn resume_batch(
+    canister: &Canister<'_>,
+    previous: Option<&ResumeFile>,
+    semaphores: &Semaphores,
+) -> Option<Nat> {
+    let batch_id = Nat::from(previous?.batch_id);
+    create_chunk(canister, &batch_id, &[], semaphores)
+        .await
+        .ok()?;
+    Some(batch_id)
+}
+
 /// Sets the contents of the asset canister to the contents of a directory, including deleting old assets.
 pub async fn sync(
     canister: &Canister<'_>,
     dirs: &[&Path],
     logger: &Logger,
+) -> Result<(), SyncError> {
+    sync_with_options(canister, dirs, &SyncOptions::default(), logger).await
+}
+
+/// Like [`sync`], with control over upload parallelism and resuming interrupted uploads.
+pub async fn sync_with_options(
+    canister: &Canister<'_>,
+    dirs: &[&Path],
+    options: &SyncOptions,
+    logger: &Logger,
 ) -> Result<(), SyncError> {
     let commit_batch_args =
-        upload_content_and_assemble_sync_operations(canister, dirs, logger).await?;
+        upload_content_and_assemble_sync_operations(canister, dirs, options, logger).await?;
     let canister_api_version = api_version(canister).await;
     debug!(logger, "Canister API version: {canister_api_version}. ic-asset API version: {BATCH_UPLOAD_API_VERSION}");
     info!(logger, "Committing batch.");
@@ -117,7 +191,12 @@ pub async fn sync(
             commit_batch(canister, commit_batch_args_v0).await
         }
         BATCH_UPLOAD_API_VERSION.. => commit_in_stages(canister, commit_batch_args, logger).await,
-    }.map_err(CommitBatchFailed)
+    }.map_err(CommitBatchFailed)?;
+
+    if let Some(path) = &options.resume_file {
+        ResumeFile::remove(path);
+    }
+    Ok(())
 }
 
 async fn commit_in_stages(
@@ -180,7 +259,13 @@ pub async fn prepare_sync_for_proposal(
     dirs: &[&Path],
     logger: &Logger,
 ) -> Result<(), PrepareSyncForProposalError> {
-    let arg = upload_content_and_assemble_sync_operations(canister, dirs, logger).await?;
+    let arg = upload_content_and_assemble_sync_operations(
+        canister,
+        dirs,
+        &SyncOptions::default(),
+        logger,
+    )
+    .await?;
     let arg = sort_batch_operations(arg);
     let batch_id = arg.batch_id.clone();
 
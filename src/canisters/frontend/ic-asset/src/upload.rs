@@ -4,6 +4,7 @@ use crate::batch_upload::{
     self,
     operations::AssetDeletionReason,
     plumbing::{make_project_assets, AssetDescriptor, ChunkUploader},
+    semaphores::Semaphores,
 };
 use crate::canister_api::methods::{
     api_version::api_version,
@@ -48,6 +49,7 @@ pub async fn upload(
         Some(&chunk_upload_target),
         asset_descriptors,
         &canister_assets,
+        &Semaphores::new(),
         logger,
     )
     .await?;
@@ -9,6 +9,13 @@ pub(crate) async fn sync(
     logger: &Logger,
 ) -> anyhow::Result<()> {
     let dirs: Vec<&Path> = o.directory.iter().map(|d| d.as_path()).collect();
-    ic_asset::sync(canister, &dirs, logger).await?;
+    let options = ic_asset::SyncOptions {
+        parallelism: o.parallelism,
+        resume_file: o.resume_file.clone(),
+    };
+    ic_asset::sync_with_options(canister, &dirs, &options, logger).await?;
+    if o.check_certification {
+        ic_asset::check_certification(canister, logger).await?;
+    }
     Ok(())
 }
@@ -65,6 +65,19 @@ struct SyncOpts {
 
     /// The directories to synchronize
     directory: Vec<PathBuf>,
+
+    /// After synchronizing, check that all assets are served with a valid certificate.
+    #[arg(long)]
+    check_certification: bool,
+
+    /// The maximum number of chunks to upload at once.
+    #[arg(long)]
+    parallelism: Option<usize>,
+
+    /// Record uploaded chunks in this file. If the sync is interrupted, running it again
+    /// with the same file resumes the upload instead of starting over.
+    #[arg(long)]
+    resume_file: Option<PathBuf>,
 }
 
 #[derive(Parser)]
@@ -0,0 +1,45 @@
+//! Calls a query method without arguments on many canisters, with the network and identity dfx would use.
+//!
+//! cargo run -p dfx-core --example bulk_query -- <method> <canister id>...
+//!
+//! Set DFX_NETWORK to use another network than the local one.
+use candid::Principal;
+use dfx_core::canister::bulk_query::{connect, BulkQuery, QueryRequest};
+use futures::StreamExt;
+use slog::Logger;
+
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let method = args
+        .next()
+        .expect("usage: bulk_query <method> <canister id>...");
+    let canister_ids: Vec<Principal> = args
+        .map(|id| Principal::from_text(&id).expect("invalid canister id"))
+        .collect();
+
+    let logger = Logger::root(slog::Discard, slog::o!());
+    let network = std::env::var("DFX_NETWORK").ok();
+    let (agent, network) = connect(network, None, &logger).await.unwrap();
+    eprintln!(
+        "Querying {} canisters on {}",
+        canister_ids.len(),
+        network.name
+    );
+
+    let bulk_query = BulkQuery::new(&agent)
+        .with_concurrency(32)
+        .with_rate_limit(100);
+    let requests = canister_ids
+        .iter()
+        .map(|id| QueryRequest::new(*id, method.clone(), candid::Encode!().unwrap()));
+    let mut results = bulk_query.stream(requests);
+    let mut canister_ids = canister_ids.iter();
+    while let Some(result) = results.next().await {
+        let canister_id = canister_ids.next().unwrap();
+        match result {
+            Ok(response) => println!("{}: {} bytes", canister_id, response.len()),
+            Err(err) => println!("{}: {}", canister_id, err),
+        }
+    }
+}
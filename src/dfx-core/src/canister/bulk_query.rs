@@ -0,0 +1,207 @@
+//! Making many query calls with one agent, for tools that read a lot of canister state.
+//!
+//! The agent keeps its HTTP connections alive, so the queries reuse them. [`BulkQuery`] keeps a
+//! bounded number of queries in flight and can limit how many are started per second,
+//! so that a boundary node does not start rejecting them.
+use crate::config::model::dfinity::{Config, NetworksConfig};
+use crate::config::model::network_descriptor::NetworkDescriptor;
+use crate::error::bulk_query::ConnectError;
+use crate::identity::IdentityManager;
+use crate::network::provider::{
+    create_network_descriptor, network_or_project_default, LocalBindDetermination,
+};
+use crate::network::root_key::fetch_root_key_when_local;
+use crate::util::expiry_duration;
+use candid::Principal;
+use futures::{Stream, StreamExt};
+use ic_agent::agent::http_transport::ReqwestTransport;
+use ic_agent::{Agent, AgentError};
+use slog::Logger;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The number of queries in flight if not set with [`BulkQuery::with_concurrency`].
+pub const DEFAULT_CONCURRENCY: usize = 16;
+
+/// Creates an agent the way dfx does: for the network and identity selected
+/// in the same way as with `--network` and `--identity`, from the current directory.
+/// Fetches the root key if the network is not the IC mainnet.
+pub async fn connect(
+    network: Option<String>,
+    identity: Option<String>,
+    logger: &Logger,
+) -> Result<(Agent, NetworkDescriptor), ConnectError> {
+    let project_config = Config::from_current_dir(None)?.map(Arc::new);
+    let shared_config = Arc::new(NetworksConfig::new()?);
+    let network = network_or_project_default(project_config.as_deref(), network, logger);
+    let network = create_network_descriptor(
+        project_config,
+        shared_config,
+        network,
+        Some(logger.clone()),
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+    let identity =
+        IdentityManager::new(logger, &identity)?.instantiate_selected_identity(logger)?;
+    let agent = Agent::builder()
+        .with_transport(
+            ReqwestTransport::create(network.first_provider()?)
+                .map_err(ConnectError::CreateAgent)?,
+        )
+        .with_boxed_identity(identity)
+        .with_ingress_expiry(Some(expiry_duration()))
+        .build()
+        .map_err(ConnectError::CreateAgent)?;
+    fetch_root_key_when_local(&agent, &network).await?;
+    Ok((agent, network))
+}
+
+/// A query call: the method and its Candid-encoded argument.
+#[derive(Clone, Debug)]
+pub struct QueryRequest {
+    pub canister_id: Principal,
+    pub method: String,
+    pub arg: Vec<u8>,
+}
+
+impl QueryRequest {
+    pub fn new(canister_id: Principal, method: impl Into<String>, arg: Vec<u8>) -> Self {
+        Self {
+            canister_id,
+            method: method.into(),
+            arg,
+        }
+    }
+}
+
+pub struct BulkQuery<'a> {
+    agent: &'a Agent,
+    concurrency: usize,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl<'a> BulkQuery<'a> {
+    pub fn new(agent: &'a Agent) -> Self {
+        Self {
+            agent,
+            concurrency: DEFAULT_CONCURRENCY,
+            rate_limiter: None,
+        }
+    }
+
+    /// Sets the maximum number of queries in flight.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Starts at most `per_second` queries per second.
+    pub fn with_rate_limit(mut self, per_second: u32) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(per_second));
+        self
+    }
+
+    /// Makes one query call, waiting for the rate limit first.
+    pub async fn query(&self, request: QueryRequest) -> Result<Vec<u8>, AgentError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait().await;
+        }
+        self.agent
+            .query(&request.canister_id, request.method)
+            .with_arg(request.arg)
+            .call()
+            .await
+    }
+
+    /// Makes the query calls, yielding the results in the order of the requests.
+    /// Requests are only taken from the iterator when there is room for them,
+    /// so it can be lazy and longer than what fits in memory.
+    pub fn stream<I>(&'a self, requests: I) -> impl Stream<Item = Result<Vec<u8>, AgentError>> + 'a
+    where
+        I: IntoIterator<Item = QueryRequest>,
+        I::IntoIter: 'a,
+    {
+        futures::stream::iter(requests)
+            .map(move |request| self.query(request))
+            .buffered(self.concurrency)
+    }
+
+    /// Makes the query calls and returns the results in the order of the requests.
+    /// A failed call does not stop the others.
+    pub async fn run(
+        &self,
+        requests: impl IntoIterator<Item = QueryRequest>,
+    ) -> Vec<Result<Vec<u8>, AgentError>> {
+        futures::stream::iter(requests)
+            .map(|request| self.query(request))
+            .buffered(self.concurrency)
+            .collect()
+            .await
+    }
+
+    /// Calls a paginated query method until there are no more pages.
+    /// `next_arg` gets the response to the last call, and returns the argument
+    /// for the next page or None if it was the last one.
+    pub async fn paginate(
+        &self,
+        canister_id: Principal,
+        method: &str,
+        first_arg: Vec<u8>,
+        mut next_arg: impl FnMut(&[u8]) -> Option<Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>, AgentError> {
+        let mut pages = vec![];
+        let mut arg = Some(first_arg);
+        while let Some(current) = arg {
+            let page = self
+                .query(QueryRequest::new(canister_id, method, current))
+                .await?;
+            arg = next_arg(&page);
+            pages.push(page);
+        }
+        Ok(pages)
+    }
+}
+
+/// Spaces the start of calls evenly, without allowing bursts.
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / per_second.max(1),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn wait(&self) {
+        let delay = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = (*next_slot).max(now);
+            *next_slot = slot + self.interval;
+            slot - now
+        };
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rate_limiter_spaces_calls() {
+        let rate_limiter = RateLimiter::new(20);
+        let start = Instant::now();
+        for _ in 0..5 {
+            rate_limiter.wait().await;
+        }
+        // The first call starts right away, the other four 50ms apart.
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -1,18 +1,27 @@
 use crate::{
-    cli::ask_for_consent,
-    error::canister::{CanisterBuilderError, CanisterInstallError},
+    cli::ask_for_typed_consent,
+    error::{
+        canister::{CanisterBuilderError, CanisterInstallError},
+        cli::UserConsent,
+    },
     identity::CallSender,
 };
-use candid::Principal;
+use candid::{CandidType, Principal};
 use ic_agent::Agent;
 use ic_utils::{
+    call::AsyncCall,
     interfaces::{
-        management_canister::builders::{CanisterInstall, InstallMode},
+        management_canister::{
+            builders::{CanisterInstall, InstallMode},
+            StatusCallResult,
+        },
         ManagementCanister, WalletCanister,
     },
     Argument,
 };
 
+pub mod bulk_query;
+
 pub async fn build_wallet_canister(
     id: Principal,
     agent: &Agent,
@@ -36,6 +45,104 @@ pub fn install_mode_to_prompt(mode: &InstallMode) -> &'static str {
     }
 }
 
+/// An action that destroys the state of a canister.
+#[derive(Clone, Copy, Debug)]
+pub enum DestructiveAction {
+    Reinstall,
+    UninstallCode,
+}
+
+/// Warns about the state that the action destroys, and asks the user to confirm by typing
+/// the name of the canister, or its id if it has no name.
+pub async fn ask_to_destroy_state(
+    agent: &Agent,
+    canister_id: Principal,
+    canister_name: Option<&str>,
+    action: DestructiveAction,
+    call_sender: &CallSender,
+) -> Result<(), UserConsent> {
+    let (target, what) = match canister_name {
+        Some(name) => (name.to_string(), format!("the {name} canister")),
+        None => (canister_id.to_text(), format!("the canister {canister_id}")),
+    };
+    let (verb, effect) = match action {
+        DestructiveAction::Reinstall => ("reinstall", "OVERWRITE all the data and code"),
+        DestructiveAction::UninstallCode => {
+            ("uninstall the code of", "REMOVE all the data and code")
+        }
+    };
+    let details = describe_state(agent, canister_id, call_sender).await;
+    let msg = format!(
+        r#"You are about to {verb} {what}
+This will {effect} in the canister.
+
+{details}
+
+YOU WILL LOSE ALL DATA IN THE CANISTER.
+"#
+    );
+    ask_for_typed_consent(&msg, &target)
+}
+
+/// Describes the installed module and its memory, as far as the caller is allowed to see them.
+async fn describe_state(agent: &Agent, canister_id: Principal, call_sender: &CallSender) -> String {
+    let (module_hash, memory_size) =
+        match read_canister_status(agent, canister_id, call_sender).await {
+            Some(status) => (status.module_hash, Some(status.memory_size)),
+            // Only controllers can read the status, but anyone can read the module hash.
+            None => (
+                agent
+                    .read_state_canister_info(canister_id, "module_hash")
+                    .await
+                    .ok(),
+                None,
+            ),
+        };
+    let module_hash = module_hash.map_or_else(
+        || "none installed".to_string(),
+        |hash| format!("0x{}", hex::encode(hash)),
+    );
+    let memory_size =
+        memory_size.map_or_else(|| "unknown".to_string(), |size| format!("{size} bytes"));
+    format!(
+        "  Module hash: {module_hash}\n  Memory size: {memory_size}\n  Snapshot: none. The state cannot be recovered afterwards."
+    )
+}
+
+async fn read_canister_status(
+    agent: &Agent,
+    canister_id: Principal,
+    call_sender: &CallSender,
+) -> Option<StatusCallResult> {
+    let mgr = ManagementCanister::create(agent);
+    let (status,) = match call_sender {
+        CallSender::SelectedId => mgr
+            .canister_status(&canister_id)
+            .call_and_wait()
+            .await
+            .ok()?,
+        CallSender::Wallet(wallet_id) => {
+            #[derive(CandidType)]
+            struct In {
+                canister_id: Principal,
+            }
+            let wallet = build_wallet_canister(*wallet_id, agent).await.ok()?;
+            let out: (StatusCallResult,) = wallet
+                .call(
+                    *mgr.canister_id_(),
+                    "canister_status",
+                    Argument::from_candid((In { canister_id },)),
+                    0,
+                )
+                .call_and_wait()
+                .await
+                .ok()?;
+            out
+        }
+    };
+    Some(status)
+}
+
 pub async fn install_canister_wasm(
     agent: &Agent,
     canister_id: Principal,
@@ -48,17 +155,15 @@ pub async fn install_canister_wasm(
 ) -> Result<(), CanisterInstallError> {
     let mgr = ManagementCanister::create(agent);
     if !skip_consent && mode == InstallMode::Reinstall {
-        let msg = if let Some(name) = canister_name {
-            format!("You are about to reinstall the {name} canister")
-        } else {
-            format!("You are about to reinstall the canister {canister_id}")
-        } + r#"
-This will OVERWRITE all the data and code in the canister.
-
-YOU WILL LOSE ALL DATA IN THE CANISTER.
-
-"#;
-        ask_for_consent(&msg).map_err(CanisterInstallError::UserConsent)?;
+        ask_to_destroy_state(
+            agent,
+            canister_id,
+            canister_name,
+            DestructiveAction::Reinstall,
+            call_sender,
+        )
+        .await
+        .map_err(CanisterInstallError::UserConsent)?;
     }
 
     match call_sender {
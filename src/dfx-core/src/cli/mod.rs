@@ -15,3 +15,19 @@ pub fn ask_for_consent(message: &str) -> Result<(), UserConsent> {
     }
     Ok(())
 }
+
+/// Like [`ask_for_consent`], for actions that destroy data: the user has to type
+/// `confirmation` rather than answer yes.
+pub fn ask_for_typed_consent(message: &str, confirmation: &str) -> Result<(), UserConsent> {
+    eprintln!("WARNING!");
+    eprintln!("{}", message);
+    eprintln!("Type '{}' to proceed:", confirmation);
+    let mut input_string = String::new();
+    stdin()
+        .read_line(&mut input_string)
+        .map_err(UserConsent::ReadError)?;
+    if input_string.trim() != confirmation {
+        return Err(UserConsent::Declined);
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::config::directories::get_state_dir_override;
 #[cfg(windows)]
 use crate::config::directories::project_dirs;
 use crate::error::cache::CacheError;
@@ -16,17 +17,22 @@ pub trait Cache {
 
 pub fn get_cache_root() -> Result<PathBuf, CacheError> {
     let cache_root = std::env::var_os("DFX_CACHE_ROOT");
+    let state_dir = get_state_dir_override().filter(|_| cache_root.is_none());
     // dirs-next is not used for *nix to preserve existing paths
     #[cfg(not(windows))]
-    let p = {
-        let home = get_user_home()?;
-        let root = cache_root.unwrap_or(home);
-        PathBuf::from(root).join(".cache").join("dfinity")
+    let p = match state_dir {
+        Some(state_dir) => state_dir.join("cache"),
+        None => {
+            let home = get_user_home()?;
+            let root = cache_root.unwrap_or(home);
+            PathBuf::from(root).join(".cache").join("dfinity")
+        }
     };
     #[cfg(windows)]
-    let p = match cache_root {
-        Some(var) => PathBuf::from(var),
-        None => project_dirs()?.cache_dir().to_owned(),
+    let p = match (cache_root, state_dir) {
+        (_, Some(state_dir)) => state_dir.join("cache"),
+        (Some(var), None) => PathBuf::from(var),
+        (None, None) => project_dirs()?.cache_dir().to_owned(),
     };
     if p.exists() && !p.is_dir() {
         return Err(CacheError::FindCacheDirectoryFailed(p));
@@ -18,7 +18,22 @@ pub fn project_dirs() -> Result<&'static ProjectDirs, GetUserHomeError> {
     DIRS.as_ref().ok_or(NoHomeInEnvironment())
 }
 
+/// The directory set with `DFX_STATE_DIR` (or `--state-dir`), made absolute.
+/// It takes the place of the `.dfx` directory of the project, and also holds the data of shared
+/// networks and, unless `DFX_CACHE_ROOT` is set, the cache, so that dfx writes nothing into
+/// the project or the home directory.
+pub fn get_state_dir_override() -> Option<PathBuf> {
+    let dir = PathBuf::from(std::env::var_os("DFX_STATE_DIR").filter(|dir| !dir.is_empty())?);
+    Some(match std::env::current_dir() {
+        Ok(cwd) => cwd.join(dir),
+        Err(_) => dir,
+    })
+}
+
 pub fn get_shared_network_data_directory(network: &str) -> Result<PathBuf, ConfigError> {
+    if let Some(state_dir) = get_state_dir_override() {
+        return Ok(state_dir.join("shared").join("network").join(network));
+    }
     let project_dirs = project_dirs().map_err(DetermineSharedNetworkDirectoryFailed)?;
     Ok(project_dirs.data_local_dir().join("network").join(network))
 }
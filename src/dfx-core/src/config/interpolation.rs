@@ -0,0 +1,112 @@
+//! `${...}` placeholders in the string values of dfx.json.
+//!
+//! - `${env:VAR}` is replaced by the environment variable `VAR` when dfx.json is loaded.
+//! - `${network}` is replaced by the name of the network when the configuration of a canister is loaded.
+//! - `${canister_id:name}` is replaced by the id of the canister `name` when building.
+//!
+//! Any other `${...}`, e.g. a parameter expansion in a build command, is left as is.
+use serde_json::Value;
+
+/// Replaces the placeholders in a string.
+/// `resolve` is called with the kind and the optional argument of every placeholder,
+/// e.g. `("env", Some("HOME"))` for `${env:HOME}`, and returns None to leave the placeholder as is.
+pub fn interpolate<E>(
+    value: &str,
+    mut resolve: impl FnMut(&str, Option<&str>) -> Result<Option<String>, E>,
+) -> Result<String, E> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let placeholder = &rest[start..start + 2 + len + 1];
+        let inner = &placeholder[2..placeholder.len() - 1];
+        let (kind, arg) = match inner.split_once(':') {
+            Some((kind, arg)) => (kind, Some(arg)),
+            None => (inner, None),
+        };
+        result.push_str(&rest[..start]);
+        match resolve(kind, arg)? {
+            Some(replacement) => result.push_str(&replacement),
+            None => result.push_str(placeholder),
+        }
+        rest = &rest[start + placeholder.len()..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Replaces the `${env:VAR}` placeholders in all strings of a json value.
+/// Returns the name of the first variable that is not set as the error.
+pub fn interpolate_env_vars(value: &mut Value) -> Result<(), String> {
+    match value {
+        Value::String(s) => {
+            *s = interpolate(s, |kind, arg| match (kind, arg) {
+                ("env", Some(var)) => std::env::var(var).map(Some).map_err(|_| var.to_string()),
+                _ => Ok(None),
+            })?;
+        }
+        Value::Array(values) => {
+            for value in values {
+                interpolate_env_vars(value)?;
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                interpolate_env_vars(value)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replaces `${network}` in a string.
+pub fn interpolate_network(value: &str, network: &str) -> String {
+    interpolate(value, |kind, arg| {
+        Ok::<_, std::convert::Infallible>(match (kind, arg) {
+            ("network", None) => Some(network.to_string()),
+            _ => None,
+        })
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_known_placeholders_only() {
+        let result = interpolate::<()>(
+            "build ${canister_id:backend} --out ${HOME}/${network}.wasm ${",
+            |kind, arg| {
+                Ok(match (kind, arg) {
+                    ("canister_id", Some(name)) => Some(format!("<{}>", name)),
+                    ("network", None) => Some("ic".to_string()),
+                    _ => None,
+                })
+            },
+        )
+        .unwrap();
+        assert_eq!(result, "build <backend> --out ${HOME}/ic.wasm ${");
+    }
+
+    #[test]
+    fn replaces_env_vars_in_json() {
+        std::env::set_var("DFX_INTERPOLATION_TEST", "value");
+        let mut json = serde_json::json!({
+            "canisters": { "a": { "args": "--flag=${env:DFX_INTERPOLATION_TEST}", "build": ["${network}"] } }
+        });
+        interpolate_env_vars(&mut json).unwrap();
+        assert_eq!(json["canisters"]["a"]["args"], "--flag=value");
+        assert_eq!(json["canisters"]["a"]["build"][0], "${network}");
+
+        let mut json = serde_json::json!({ "args": "${env:DFX_INTERPOLATION_TEST_UNSET}" });
+        assert_eq!(
+            interpolate_env_vars(&mut json).unwrap_err(),
+            "DFX_INTERPOLATION_TEST_UNSET"
+        );
+    }
+}
@@ -1,3 +1,4 @@
 pub mod cache;
 pub mod directories;
+pub mod interpolation;
 pub mod model;
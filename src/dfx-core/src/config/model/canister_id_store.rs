@@ -99,6 +99,9 @@ pub struct CanisterIdStore {
 
     // ids of pull dependencies in dfx.json, never written to canister_ids.json
     pull_ids: BTreeMap<CanisterName, CanisterId>,
+
+    // ids of the aliases in dfx.json on this network, never written to canister_ids.json
+    alias_ids: BTreeMap<CanisterName, CanisterId>,
 }
 
 impl CanisterIdStore {
@@ -147,6 +150,7 @@ impl CanisterIdStore {
             _ => None,
         };
         let remote_ids = get_remote_ids(config.clone());
+        let alias_ids = get_alias_ids(config.as_deref(), &network_descriptor.name);
         let pull_ids = if let Some(config) = config {
             config.get_config().get_pull_canisters()?
         } else {
@@ -168,6 +172,7 @@ impl CanisterIdStore {
             acquisition_timestamps,
             remote_ids,
             pull_ids,
+            alias_ids,
         };
 
         if let NetworkTypeDescriptor::Playground {
@@ -250,6 +255,13 @@ impl CanisterIdStore {
             .or_else(|| self.find_in(canister_name, &self.ids))
             .or_else(|| self.pull_ids.get(canister_name).copied())
     }
+
+    /// Whether the name is an alias from dfx.json rather than a canister of the project,
+    /// in which case there is no canister configuration for it.
+    pub fn is_alias(&self, canister_name: &str) -> bool {
+        self.alias_ids.contains_key(canister_name) && self.find(canister_name).is_none()
+    }
+
     pub fn get_name_id_map(&self) -> BTreeMap<String, String> {
         let mut ids: BTreeMap<_, _> = self
             .ids
@@ -309,6 +321,16 @@ impl CanisterIdStore {
         })
     }
 
+    /// Like `get`, but also accepts the aliases from dfx.json, for canisters that a command only addresses.
+    /// Canisters that are created, built or installed are always looked up with `get`.
+    pub fn get_including_aliases(
+        &self,
+        canister_name: &str,
+    ) -> Result<CanisterId, CanisterIdStoreError> {
+        self.get(canister_name)
+            .or_else(|err| self.alias_ids.get(canister_name).copied().ok_or(err))
+    }
+
     pub fn add(
         &mut self,
         canister_name: &str,
@@ -401,6 +423,18 @@ impl CanisterIdStore {
     }
 }
 
+fn get_alias_ids(config: Option<&Config>, network: &str) -> BTreeMap<CanisterName, CanisterId> {
+    config
+        .and_then(|config| config.get_config().aliases.as_ref())
+        .map(|aliases| {
+            aliases
+                .iter()
+                .filter_map(|(name, alias)| Some((name.clone(), alias.get_id(network)?)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn get_remote_ids(config: Option<Arc<Config>>) -> Option<CanisterIds> {
     let config = config?;
     let config = config.get_config();
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 #![allow(clippy::should_implement_trait)] // for from_str.  why now?
-use crate::config::directories::get_user_dfx_config_dir;
+use crate::config::directories::{get_state_dir_override, get_user_dfx_config_dir};
+use crate::config::interpolation::interpolate_env_vars;
 use crate::config::model::bitcoin_adapter::BitcoinAdapterLogLevel;
 use crate::config::model::canister_http_adapter::HttpAdapterLogLevel;
 use crate::error::config::GetOutputEnvFileError;
@@ -13,15 +14,17 @@ use crate::error::dfx_config::GetPullCanistersError::PullCanistersSameId;
 use crate::error::dfx_config::GetRemoteCanisterIdError::GetRemoteCanisterIdFailed;
 use crate::error::dfx_config::GetReservedCyclesLimitError::GetReservedCyclesLimitFailed;
 use crate::error::dfx_config::GetSpecifiedIdError::GetSpecifiedIdFailed;
+use crate::error::dfx_config::GetWasmMemoryLimitError::GetWasmMemoryLimitFailed;
 use crate::error::dfx_config::{
-    AddDependenciesError, GetCanisterConfigError, GetCanisterNamesWithDependenciesError,
-    GetComputeAllocationError, GetFreezingThresholdError, GetMemoryAllocationError,
-    GetPullCanistersError, GetRemoteCanisterIdError, GetReservedCyclesLimitError,
-    GetSpecifiedIdError,
+    AddDependenciesError, ConfigValueError, GetCanisterConfigError,
+    GetCanisterNamesWithDependenciesError, GetComputeAllocationError, GetFreezingThresholdError,
+    GetMemoryAllocationError, GetPullCanistersError, GetRemoteCanisterIdError,
+    GetReservedCyclesLimitError, GetSpecifiedIdError, GetWasmMemoryLimitError,
 };
 use crate::error::load_dfx_config::LoadDfxConfigError;
 use crate::error::load_dfx_config::LoadDfxConfigError::{
-    DetermineCurrentWorkingDirFailed, LoadFromFileFailed, ResolveConfigPathFailed,
+    DetermineCurrentWorkingDirFailed, EnvironmentWithoutProject, InvalidEnvironmentName,
+    LoadFromFileFailed, ResolveConfigPathFailed, UndefinedEnvironmentVariable, UnknownEnvironment,
 };
 use crate::error::load_networks_config::LoadNetworksConfigError;
 use crate::error::load_networks_config::LoadNetworksConfigError::{
@@ -35,8 +38,8 @@ use crate::error::structured_file::StructuredFileError;
 use crate::error::structured_file::StructuredFileError::{
     DeserializeJsonFileFailed, ReadJsonFileFailed,
 };
-use crate::json::save_json_file;
 use crate::json::structure::{PossiblyStr, SerdeVec};
+use crate::json::{load_json_file, save_json_file};
 use byte_unit::Byte;
 use candid::Principal;
 use schemars::JsonSchema;
@@ -58,14 +61,20 @@ const EMPTY_CONFIG_DEFAULTS: ConfigDefaults = ConfigDefaults {
     bitcoin: None,
     bootstrap: None,
     build: None,
+    candid_ui: None,
     canister_http: None,
+    cycles_ledger: None,
+    identity: None,
+    network: None,
     proxy: None,
     replica: None,
 };
 
 const EMPTY_CONFIG_DEFAULTS_BUILD: ConfigDefaultsBuild = ConfigDefaultsBuild {
     packtool: None,
+    packages: None,
     args: None,
+    output_dir: None,
 };
 
 /// # Remote Canister Configuration
@@ -76,6 +85,7 @@ const EMPTY_CONFIG_DEFAULTS_BUILD: ConfigDefaultsBuild = ConfigDefaultsBuild {
 pub struct ConfigCanistersCanisterRemote {
     /// # Remote Candid File
     /// On networks where this canister is marked 'remote', this candid file is used instead of the one declared in the canister settings.
+    /// If it is not set, Motoko canisters fetch the interface from the `candid:service` metadata of the remote canister, and cache it under `.dfx/<network>/remote_candid`, while other canisters use the one declared in the canister settings.
     pub candid: Option<PathBuf>,
 
     /// # Network to Remote ID Mapping
@@ -192,6 +202,54 @@ pub struct Pullable {
     pub init_arg: Option<String>,
 }
 
+/// # Build Container
+/// A Docker image to build the canister in, instead of with the toolchains installed on the host.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BuildContainer {
+    /// # Image
+    /// The Docker image that contains the toolchain of the canister, such as `cargo` or `moc`.
+    /// Pin it by digest (`image@sha256:...`) so that the build environment cannot change.
+    pub image: String,
+}
+
+/// # Build Cache
+/// A remote cache of built canisters, so that CI and teammates reuse builds instead of compiling them again.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigBuildCache {
+    /// # URL
+    /// The base URL of the cache. Builds are downloaded from `<url>/<key>.tar.gz` with GET, and uploaded there with PUT.
+    /// If the DFX_BUILD_CACHE_TOKEN environment variable is set, it is sent as a bearer token.
+    pub url: String,
+
+    /// # Read Only
+    /// Only restores builds from the cache, without uploading new ones, e.g. for builds on developer machines.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// # Canister Alias
+/// A name for a canister outside of the project, e.g. a well-known canister on mainnet.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ConfigCanisterAlias {
+    /// The canister id on every network.
+    #[schemars(with = "String")]
+    Id(Principal),
+
+    /// Canister ids by network name. The alias is only defined on these networks.
+    #[schemars(with = "BTreeMap<String, String>")]
+    ByNetwork(BTreeMap<String, Principal>),
+}
+
+impl ConfigCanisterAlias {
+    pub fn get_id(&self, network: &str) -> Option<Principal> {
+        match self {
+            ConfigCanisterAlias::Id(id) => Some(*id),
+            ConfigCanisterAlias::ByNetwork(ids) => ids.get(network).copied(),
+        }
+    }
+}
+
 pub const DEFAULT_SHARED_LOCAL_BIND: &str = "127.0.0.1:4943"; // hex for "IC"
 pub const DEFAULT_PROJECT_LOCAL_BIND: &str = "127.0.0.1:8000";
 pub const DEFAULT_IC_GATEWAY: &str = "https://icp0.io";
@@ -283,8 +341,66 @@ pub struct ConfigCanistersCanister {
 
     /// # Init Arg
     /// The Candid initialization argument for installing the canister.
+    /// Either a single argument for every network, or an object with an argument per network name.
     /// If the `--argument` or `--argument-file` argument is also provided, this `init_arg` field will be ignored.
-    pub init_arg: Option<String>,
+    #[serde(alias = "init_args")]
+    pub init_arg: Option<CanisterInitArg>,
+
+    /// # Allowed Wasm Hashes
+    /// SHA-256 hashes (in hex) of the wasm modules that may be installed in this canister.
+    /// If set, dfx refuses to install any other module unless `--allow-unverified` is passed.
+    /// Meant for canisters whose wasm is not built in the project, such as custom canisters with a wasm URL and pull dependencies.
+    pub allowed_wasm_hashes: Option<Vec<String>>,
+
+    /// # Build Container
+    /// Runs the compiler of Rust and Motoko canisters, and the build commands of custom canisters, in a Docker container.
+    /// The project directory is mounted at the same path in the container.
+    /// `dfx build` and `dfx canister verify` both build the canister in it.
+    #[serde(default)]
+    pub build_container: Option<BuildContainer>,
+
+    /// # Build Inputs
+    /// The files and directories, relative to the project root, whose contents determine the build of the canister.
+    /// With a `build_cache`, the canister is restored from the cache instead of built when they have not changed.
+    /// Canisters without build inputs are always built.
+    #[serde(default)]
+    pub build_inputs: Vec<PathBuf>,
+
+    /// # Output Directory
+    /// After a build, the artifacts of the canister are copied to this directory, instead of to `<output_dir>/<canister name>`
+    /// with the `output_dir` of the build defaults. Relative to the project root.
+    pub output_dir: Option<PathBuf>,
+}
+
+/// # Canister Init Arg
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum CanisterInitArg {
+    /// # Init Arg For All Networks
+    Any(String),
+    /// # Init Arg Per Network
+    /// Networks that are not listed install the canister without an init_arg from dfx.json.
+    PerNetwork(BTreeMap<String, String>),
+}
+
+impl CanisterInitArg {
+    pub fn for_network(&self, network: &str) -> Option<&str> {
+        match self {
+            Self::Any(arg) => Some(arg),
+            Self::PerNetwork(args) => args.get(network).map(String::as_str),
+        }
+    }
+
+    /// All arguments, with the name of the network they are for, if they are network-specific.
+    pub fn all(&self) -> Vec<(Option<&str>, &str)> {
+        match self {
+            Self::Any(arg) => vec![(None, arg)],
+            Self::PerNetwork(args) => args
+                .iter()
+                .map(|(network, arg)| (Some(network.as_str()), arg.as_str()))
+                .collect(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, JsonSchema)]
@@ -316,6 +432,10 @@ pub enum CanisterTypeProperties {
         /// # NPM workspace
         /// The workspace in package.json that this canister is in, if it is not in the root workspace.
         workspace: Option<String>,
+
+        /// # Upload Parallelism
+        /// The maximum number of chunks uploaded to the canister at once. Defaults to 4.
+        upload_parallelism: Option<usize>,
     },
     /// # Custom-Specific Properties
     Custom {
@@ -391,6 +511,15 @@ pub struct InitializationValues {
     /// A setting of 0 means that the canister will trap if it tries to allocate new storage while the subnet's memory usage exceeds 450 GiB.
     #[schemars(with = "Option<u128>")]
     pub reserved_cycles_limit: Option<u128>,
+
+    /// # Wasm Memory Limit
+    /// Specifies a soft limit (in bytes) on the Wasm memory usage of the canister.
+    ///
+    /// Update calls, timers, heartbeats, installs and post-upgrades fail if the Wasm memory usage exceeds this limit.
+    /// The main purpose of this setting is to protect against the case when the canister reaches the hard 4GiB limit.
+    /// Must be a number of bytes between 0 and 2^48 (i.e 256TB), inclusively.
+    #[schemars(with = "Option<u64>")]
+    pub wasm_memory_limit: Option<Byte>,
 }
 
 /// # Declarations Configuration
@@ -405,7 +534,8 @@ pub struct CanisterDeclarationsConfig {
 
     /// # Languages to generate
     /// A list of languages to generate type declarations.
-    /// Supported options are 'js', 'ts', 'did', 'mo'.
+    /// Supported options are 'js', 'ts', 'did', 'mo', 'rs', 'py', 'kt',
+    /// and 'md' and 'html' for documentation of the interface.
     /// Default is ['js', 'ts', 'did'].
     pub bindings: Option<Vec<String>>,
 
@@ -419,6 +549,12 @@ pub struct CanisterDeclarationsConfig {
     /// Default is false
     #[serde(default)]
     pub node_compatibility: bool,
+
+    /// # Environment File
+    /// A dotenv file to write `DFX_NETWORK` and a `CANISTER_ID_<NAME>` variable for each canister to,
+    /// when declarations are generated. A copy for the network is written next to it, like '.env.ic'.
+    /// Variables outside of the section that dfx manages are kept.
+    pub env_file: Option<PathBuf>,
 }
 
 /// # Bitcoin Adapter Configuration
@@ -463,6 +599,22 @@ impl Default for ConfigDefaultsBitcoin {
     }
 }
 
+/// # Candid UI Configuration
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigDefaultsCandidUi {
+    /// # Enable Candid UI
+    /// Installs the Candid UI canister that comes with dfx when the local network starts,
+    /// and replaces it if it was installed by another version of dfx.
+    #[serde(default = "default_as_true")]
+    pub enabled: bool,
+}
+
+impl Default for ConfigDefaultsCandidUi {
+    fn default() -> Self {
+        ConfigDefaultsCandidUi { enabled: true }
+    }
+}
+
 /// # HTTP Adapter Configuration
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ConfigDefaultsCanisterHttp {
@@ -485,6 +637,16 @@ impl Default for ConfigDefaultsCanisterHttp {
     }
 }
 
+/// # Cycles Ledger Configuration
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigDefaultsCyclesLedger {
+    /// # Use the Cycles Ledger Instead of a Cycles Wallet
+    /// Creates canisters and deposits cycles from the cycles ledger account of the selected identity,
+    /// instead of provisioning and using a cycles wallet.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
 fn default_as_true() -> bool {
     // sigh https://github.com/serde-rs/serde/issues/368
     true
@@ -534,8 +696,54 @@ pub struct ConfigDefaultsBuild {
     /// Main command to run the packtool.
     pub packtool: Option<String>,
 
+    /// # Motoko Package Manager
+    /// The package manager that provides the packages of Motoko canisters: "mops" or "vessel".
+    /// dfx asks it for the package sources and caches them until its manifest changes.
+    /// If this is set, packtool is ignored.
+    #[serde(default)]
+    pub packages: Option<MotokoPackageManager>,
+
     /// Arguments for packtool.
     pub args: Option<String>,
+
+    /// # Output Directory
+    /// After a build, the artifacts of each canister are copied to `<output_dir>/<canister name>`,
+    /// in a layout that does not change across dfx versions: `<canister name>.wasm` (or `.wasm.gz`),
+    /// `<canister name>.did` and `metadata.json`. Relative to the project root.
+    pub output_dir: Option<PathBuf>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MotokoPackageManager {
+    /// https://mops.one, configured in mops.toml.
+    Mops,
+
+    /// https://github.com/dfinity/vessel, configured in vessel.dhall and package-set.dhall.
+    Vessel,
+}
+
+impl MotokoPackageManager {
+    pub fn command(&self) -> &'static str {
+        match self {
+            Self::Mops => "mops",
+            Self::Vessel => "vessel",
+        }
+    }
+
+    /// The files that determine which packages are used.
+    pub fn manifest_files(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mops => &["mops.toml", "mops.lock"],
+            Self::Vessel => &["vessel.dhall", "package-set.dhall"],
+        }
+    }
+}
+
+impl fmt::Display for MotokoPackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.command())
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -589,6 +797,13 @@ pub struct ConfigDefaultsReplica {
 pub struct ConfigDefaultsProxy {
     /// A list of domains that can be served. These are used for canister resolution [default: localhost]
     pub domain: SerdeVec<String>,
+
+    /// # Custom Domains
+    /// Domains that serve a single canister, like a custom domain on mainnet, mapped to the id of the canister.
+    /// Requests to them are routed to the canister without a canister id in the host name or the query.
+    #[serde(default)]
+    #[schemars(with = "BTreeMap<String, String>")]
+    pub custom_domains: BTreeMap<String, Principal>,
 }
 
 // Schemars doesn't add the enum value's docstrings. Therefore the explanations have to be up here.
@@ -650,6 +865,50 @@ pub struct PlaygroundConfig {
     pub timeout_seconds: u64,
 }
 
+/// # Network Connection Configuration
+/// How dfx sends requests to a network.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigNetworkConnection {
+    /// # HTTP Timeout
+    /// Timeout for each HTTP request to the network.
+    /// Strings parsable by humantime, e.g. "30s" or "2min".
+    #[serde(default, with = "humantime_serde")]
+    #[schemars(with = "Option<String>")]
+    pub http_timeout: Option<Duration>,
+
+    /// # Retries
+    /// How many times a request that failed because of a connection error or timeout is sent again.
+    /// Defaults to 0.
+    pub retry: Option<u32>,
+
+    /// # Backoff
+    /// How long to wait before sending a request again, doubled for each further attempt.
+    /// Strings parsable by humantime, e.g. "500ms".
+    /// Defaults to 500ms.
+    #[serde(default, with = "humantime_serde")]
+    #[schemars(with = "Option<String>")]
+    pub backoff: Option<Duration>,
+
+    /// # HTTP Proxy
+    /// The URL of the proxy to send requests through, e.g. "http://proxy.example.com:3128".
+    /// Without it, the HTTPS_PROXY, HTTP_PROXY and NO_PROXY environment variables are used.
+    pub http_proxy: Option<String>,
+
+    /// # CA Bundle
+    /// Path to a PEM file with certificates to trust in addition to the built-in root certificates,
+    /// e.g. the certificate of a proxy that intercepts TLS.
+    /// Relative to the file that defines the network.
+    pub ca_bundle: Option<PathBuf>,
+}
+
+impl ConfigNetworkConnection {
+    /// Resolves `ca_bundle` relative to the directory of the file that defines the network.
+    pub fn with_base_dir(mut self, dir: &Path) -> Self {
+        self.ca_bundle = self.ca_bundle.map(|path| dir.join(path));
+        self
+    }
+}
+
 /// # Custom Network Configuration
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ConfigNetworkProvider {
@@ -660,6 +919,9 @@ pub struct ConfigNetworkProvider {
     #[serde(default = "NetworkType::persistent")]
     pub r#type: NetworkType,
     pub playground: Option<PlaygroundConfig>,
+
+    #[serde(flatten)]
+    pub connection: ConfigNetworkConnection,
 }
 
 /// # Local Replica Configuration
@@ -676,10 +938,45 @@ pub struct ConfigLocalProvider {
 
     pub bitcoin: Option<ConfigDefaultsBitcoin>,
     pub bootstrap: Option<ConfigDefaultsBootstrap>,
+    pub candid_ui: Option<ConfigDefaultsCandidUi>,
     pub canister_http: Option<ConfigDefaultsCanisterHttp>,
     pub replica: Option<ConfigDefaultsReplica>,
     pub playground: Option<PlaygroundConfig>,
     pub proxy: Option<ConfigDefaultsProxy>,
+
+    /// # Scheduled Tasks
+    /// Commands that `dfx start` runs periodically while the network is running,
+    /// e.g. to call a canister method every 30 seconds.
+    #[serde(default)]
+    pub tasks: Vec<ConfigLocalTask>,
+
+    #[serde(flatten)]
+    pub connection: ConfigNetworkConnection,
+}
+
+/// # Scheduled Task
+/// Either a dfx command or another program, run every `interval`.
+/// Its output is written to the tasks log of the network, shown by `dfx logs tasks`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigLocalTask {
+    /// # Name
+    /// Identifies the task in the output of `dfx start` and in the tasks log.
+    pub name: String,
+
+    /// # Interval
+    /// How often to run the task. The first run is one interval after the network is ready.
+    /// Strings parsable by humantime, e.g. "30s" or "1min".
+    #[serde(with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub interval: Duration,
+
+    /// # dfx Arguments
+    /// Runs dfx with these arguments, e.g. ["canister", "call", "backend", "tick"].
+    pub dfx: Option<Vec<String>>,
+
+    /// # Command
+    /// Runs this program with these arguments, e.g. ["bitcoin-cli", "-regtest", "generatetoaddress", "1", "<address>"].
+    pub command: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
@@ -695,6 +992,44 @@ pub enum Profile {
     Debug,
     // release is for production
     Release,
+    // test coverage instruments canisters to collect code coverage
+    TestCoverage,
+}
+
+/// # Build Profile
+/// A named build profile, selected with `--profile <name>` of `dfx build` and `dfx deploy`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigBuildProfile {
+    /// # Base Profile
+    /// The built-in profile that this profile extends: "Debug", "Release" or "TestCoverage".
+    /// It decides e.g. whether Motoko canisters are built in release mode. Defaults to "Debug".
+    pub inherits: Option<Profile>,
+
+    /// # Cargo Profile
+    /// The Cargo profile that Rust canisters are built with, e.g. "dev" to keep debug symbols. Defaults to "release".
+    pub cargo_profile: Option<String>,
+
+    /// # Cargo Features
+    /// Features to enable when building Rust canisters.
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// # Cargo Arguments
+    /// Additional arguments for `cargo build` when building Rust canisters.
+    #[serde(default)]
+    pub cargo_args: Vec<String>,
+
+    /// # Motoko Compiler Arguments
+    /// Additional arguments for the Motoko compiler, after the `args` of the canister.
+    pub moc_args: Option<String>,
+
+    /// # Optimize Canister WASM
+    /// Overrides the `optimize` setting of the canisters built with this profile.
+    pub optimize: Option<WasmOptLevel>,
+
+    /// # Shrink Canister WASM
+    /// Overrides the `shrink` setting of the canisters built with this profile.
+    pub shrink: Option<bool>,
 }
 
 /// Defaults to use on dfx start.
@@ -703,7 +1038,18 @@ pub struct ConfigDefaults {
     pub bitcoin: Option<ConfigDefaultsBitcoin>,
     pub bootstrap: Option<ConfigDefaultsBootstrap>,
     pub build: Option<ConfigDefaultsBuild>,
+    pub candid_ui: Option<ConfigDefaultsCandidUi>,
     pub canister_http: Option<ConfigDefaultsCanisterHttp>,
+    pub cycles_ledger: Option<ConfigDefaultsCyclesLedger>,
+
+    /// # Default Identity
+    /// The identity to use in this project, unless one is selected with `--identity`.
+    pub identity: Option<String>,
+
+    /// # Default Network
+    /// The network to use in this project, unless one is selected with `--network`, `--ic` or `--playground`.
+    pub network: Option<String>,
+
     pub proxy: Option<ConfigDefaultsProxy>,
     pub replica: Option<ConfigDefaultsReplica>,
 }
@@ -713,6 +1059,12 @@ pub struct ConfigDefaults {
 pub struct ConfigInterface {
     pub profile: Option<Profile>,
 
+    /// # Build Profiles
+    /// Named build profiles, selected with `--profile <name>` of `dfx build` and `dfx deploy`.
+    /// The built-in profiles debug, release and test-coverage cannot be redefined.
+    #[serde(default, deserialize_with = "deserialize_build_profiles")]
+    pub profiles: Option<BTreeMap<String, ConfigBuildProfile>>,
+
     /// Used to keep track of dfx.json versions.
     pub version: Option<u32>,
 
@@ -732,6 +1084,21 @@ pub struct ConfigInterface {
 
     /// If set, environment variables will be output to this file (without overwriting any user-defined variables, if the file already exists).
     pub output_env_file: Option<PathBuf>,
+
+    /// # Environments
+    /// Overlays that are merged over this configuration when selected with `--env <name>`.
+    /// A `dfx.<name>.json` file next to dfx.json is merged over it as well.
+    pub envs: Option<BTreeMap<String, Value>>,
+
+    /// # Build Cache
+    /// A remote cache of built canisters, shared e.g. between CI and teammates.
+    /// Only canisters with `build_inputs` are cached.
+    pub build_cache: Option<ConfigBuildCache>,
+
+    /// # Canister Aliases
+    /// Names for canisters outside of the project, which commands that address an existing canister accept instead of its name or id.
+    /// Canisters of the project take precedence over aliases with the same name.
+    pub aliases: Option<BTreeMap<String, ConfigCanisterAlias>>,
 }
 
 pub type TopLevelConfigNetworks = BTreeMap<String, ConfigNetwork>;
@@ -760,6 +1127,9 @@ impl ConfigDefaultsBuild {
             _ => None,
         }
     }
+    pub fn get_packages(&self) -> Option<MotokoPackageManager> {
+        self.packages
+    }
     pub fn get_args(&self) -> Option<String> {
         match &self.args {
             Some(v) if !v.is_empty() => self.args.to_owned(),
@@ -900,6 +1270,17 @@ impl ConfigInterface {
             .reserved_cycles_limit)
     }
 
+    pub fn get_wasm_memory_limit(
+        &self,
+        canister_name: &str,
+    ) -> Result<Option<Byte>, GetWasmMemoryLimitError> {
+        Ok(self
+            .get_canister_config(canister_name)
+            .map_err(|e| GetWasmMemoryLimitFailed(canister_name.to_string(), e))?
+            .initialization_values
+            .wasm_memory_limit)
+    }
+
     fn get_canister_config(
         &self,
         canister_name: &str,
@@ -971,6 +1352,26 @@ fn add_dependencies(
     Ok(())
 }
 
+/// Merges `overlay` into `target` like a JSON merge patch (RFC 7396).
+fn merge_json(target: &mut Value, overlay: Value) {
+    match overlay {
+        Value::Object(overlay) => {
+            if !target.is_object() {
+                *target = Value::Object(serde_json::Map::new());
+            }
+            let target = target.as_object_mut().unwrap();
+            for (key, value) in overlay {
+                if value.is_null() {
+                    target.remove(&key);
+                } else {
+                    merge_json(target.entry(key).or_insert(Value::Null), value);
+                }
+            }
+        }
+        overlay => *target = overlay,
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Config {
     path: PathBuf,
@@ -999,20 +1400,94 @@ impl Config {
         Ok(None)
     }
 
-    fn from_file(path: &Path) -> Result<Config, StructuredFileError> {
-        let content = crate::fs::read(path).map_err(ReadJsonFileFailed)?;
-        Config::from_slice(path.to_path_buf(), &content)
+    fn from_file(path: &Path) -> Result<Config, LoadDfxConfigError> {
+        let json: Value = load_json_file(path).map_err(LoadFromFileFailed)?;
+        Config::from_json(path, json.clone(), json, path)
+    }
+
+    /// Creates the configuration from the content of dfx.json and the value to deserialize it from,
+    /// which is the content of dfx.json with the overlay of an environment merged over it, if any.
+    /// `${env:VAR}` placeholders are replaced in the deserialized value only.
+    fn from_json(
+        path: &Path,
+        json: Value,
+        mut value: Value,
+        source: &Path,
+    ) -> Result<Config, LoadDfxConfigError> {
+        interpolate_env_vars(&mut value)
+            .map_err(|var| UndefinedEnvironmentVariable(var, source.to_path_buf()))?;
+        let config = serde_json::from_value(value).map_err(|e| {
+            LoadFromFileFailed(DeserializeJsonFileFailed(Box::new(source.to_path_buf()), e))
+        })?;
+        Ok(Config {
+            path: path.to_path_buf(),
+            json,
+            config,
+        })
+    }
+
+    /// Loads dfx.json and merges the overlay of an environment over it:
+    /// first the `envs.<name>` section of dfx.json, then the content of `dfx.<name>.json`.
+    /// Objects are merged recursively, a `null` removes a field, and any other value replaces the previous one.
+    ///
+    /// `get_json` still returns the content of dfx.json only, so that saving the configuration does not persist the overlay.
+    fn from_file_with_overlay(path: &Path, overlay: &str) -> Result<Config, LoadDfxConfigError> {
+        if overlay.is_empty()
+            || !overlay
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(InvalidEnvironmentName(overlay.to_string()));
+        }
+        let json: Value = load_json_file(path).map_err(LoadFromFileFailed)?;
+
+        let mut merged = json.clone();
+        let mut found = false;
+        if let Some(section) = json.get("envs").and_then(|envs| envs.get(overlay)) {
+            merge_json(&mut merged, section.clone());
+            found = true;
+        }
+        let overlay_path = path.with_file_name(format!("dfx.{}.json", overlay));
+        if overlay_path.is_file() {
+            let overlay_json: Value = load_json_file(&overlay_path).map_err(LoadFromFileFailed)?;
+            merge_json(&mut merged, overlay_json);
+            found = true;
+        }
+        if !found {
+            return Err(UnknownEnvironment(overlay.to_string(), overlay_path));
+        }
+
+        let source = if overlay_path.is_file() {
+            overlay_path
+        } else {
+            path.to_path_buf()
+        };
+        Config::from_json(path, json, merged, &source)
     }
 
     pub fn from_dir(working_dir: &Path) -> Result<Option<Config>, LoadDfxConfigError> {
+        Config::from_dir_with_overlay(working_dir, None)
+    }
+
+    /// Like `from_dir`, merging the overlay of the given environment over dfx.json.
+    pub fn from_dir_with_overlay(
+        working_dir: &Path,
+        overlay: Option<&str>,
+    ) -> Result<Option<Config>, LoadDfxConfigError> {
         let path = Config::resolve_config_path(working_dir)?;
-        path.map(|path| Config::from_file(&path))
-            .transpose()
-            .map_err(LoadFromFileFailed)
+        match (path, overlay) {
+            (Some(path), Some(overlay)) => Config::from_file_with_overlay(&path, overlay).map(Some),
+            (Some(path), None) => Config::from_file(&path).map(Some),
+            (None, Some(overlay)) => Err(EnvironmentWithoutProject(overlay.to_string())),
+            (None, None) => Ok(None),
+        }
     }
 
-    pub fn from_current_dir() -> Result<Option<Config>, LoadDfxConfigError> {
-        Config::from_dir(&std::env::current_dir().map_err(DetermineCurrentWorkingDirFailed)?)
+    pub fn from_current_dir(overlay: Option<&str>) -> Result<Option<Config>, LoadDfxConfigError> {
+        Config::from_dir_with_overlay(
+            &std::env::current_dir().map_err(DetermineCurrentWorkingDirFailed)?,
+            overlay,
+        )
     }
 
     fn from_slice(path: PathBuf, content: &[u8]) -> Result<Config, StructuredFileError> {
@@ -1040,8 +1515,9 @@ impl Config {
     pub fn get_path(&self) -> &PathBuf {
         &self.path
     }
+    /// The `.dfx` directory of the project, or the directory set with `DFX_STATE_DIR`.
     pub fn get_temp_path(&self) -> PathBuf {
-        self.get_path().parent().unwrap().join(".dfx")
+        get_state_dir_override().unwrap_or_else(|| self.get_path().parent().unwrap().join(".dfx"))
     }
     pub fn get_json(&self) -> &Value {
         &self.json
@@ -1091,11 +1567,154 @@ impl Config {
             .transpose()
     }
 
+    /// Returns the value at the given path of dfx.json, if there is one.
+    /// The path is either a JSON pointer (`/canisters/app/type`) or a list of keys separated by dots (`canisters.app.type`).
+    pub fn get_json_value(&self, path: &str) -> Result<Option<&Value>, ConfigValueError> {
+        let segments = parse_config_path(path)?;
+        let mut current = &self.json;
+        for segment in &segments {
+            let next = match current {
+                Value::Object(map) => map.get(segment),
+                Value::Array(array) => segment.parse::<usize>().ok().and_then(|i| array.get(i)),
+                _ => None,
+            };
+            match next {
+                Some(next) => current = next,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(current))
+    }
+
+    /// Sets the value at the given path of dfx.json, creating any missing objects along the way,
+    /// or removes it if `value` is `None`.
+    /// The configuration is only changed if the result is still a valid dfx.json.
+    pub fn set_json_value(
+        &mut self,
+        path: &str,
+        value: Option<Value>,
+    ) -> Result<(), ConfigValueError> {
+        let segments = parse_config_path(path)?;
+        let (last, parents) = segments
+            .split_last()
+            .ok_or_else(|| ConfigValueError::InvalidPath(path.to_string()))?;
+
+        let mut json = self.json.clone();
+        let mut current = &mut json;
+        for (i, segment) in parents.iter().enumerate() {
+            let container = segments[..=i].join(".");
+            current = match current {
+                Value::Object(map) => map
+                    .entry(segment.clone())
+                    .or_insert_with(|| Value::Object(serde_json::Map::new())),
+                Value::Array(array) => {
+                    let len = array.len();
+                    segment
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| array.get_mut(index))
+                        .ok_or_else(|| {
+                            ConfigValueError::InvalidIndex(path.to_string(), segment.clone(), len)
+                        })?
+                }
+                _ => return Err(ConfigValueError::NotAContainer(path.to_string(), container)),
+            };
+        }
+        match (current, value) {
+            (Value::Object(map), Some(value)) => {
+                map.insert(last.clone(), value);
+            }
+            (Value::Object(map), None) => {
+                map.shift_remove(last);
+            }
+            (Value::Array(array), value) => {
+                let len = array.len();
+                let index = match last.as_str() {
+                    "-" => len,
+                    index => index
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|index| *index <= len)
+                        .ok_or_else(|| {
+                            ConfigValueError::InvalidIndex(path.to_string(), last.clone(), len)
+                        })?,
+                };
+                match value {
+                    Some(value) if index == len => array.push(value),
+                    Some(value) => array[index] = value,
+                    None if index < len => {
+                        array.remove(index);
+                    }
+                    None => {
+                        return Err(ConfigValueError::InvalidIndex(
+                            path.to_string(),
+                            last.clone(),
+                            len,
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(ConfigValueError::NotAContainer(
+                    path.to_string(),
+                    parents.join("."),
+                ))
+            }
+        }
+
+        let mut value = json.clone();
+        interpolate_env_vars(&mut value).map_err(ConfigValueError::UndefinedEnvironmentVariable)?;
+        let config = serde_json::from_value(value)
+            .map_err(|e| ConfigValueError::InvalidConfig(path.to_string(), e))?;
+        self.json = json;
+        self.config = config;
+        Ok(())
+    }
+
     pub fn save(&self) -> Result<(), StructuredFileError> {
         save_json_file(&self.path, &self.json)
     }
 }
 
+/// Splits a path to a value of dfx.json into its keys.
+/// A path that starts with `/` is a JSON pointer, any other path is a list of keys separated by dots.
+fn parse_config_path(path: &str) -> Result<Vec<String>, ConfigValueError> {
+    let segments: Vec<String> = if let Some(pointer) = path.strip_prefix('/') {
+        pointer
+            .split('/')
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect()
+    } else {
+        path.split('.').map(str::to_string).collect()
+    };
+    if path.is_empty() || segments.iter().any(|segment| segment.is_empty()) {
+        return Err(ConfigValueError::InvalidPath(path.to_string()));
+    }
+    Ok(segments)
+}
+
+/// The profiles that `--profile` selects without them being in the `profiles` of dfx.json.
+const BUILT_IN_PROFILES: [&str; 3] = ["debug", "release", "test-coverage"];
+
+fn deserialize_build_profiles<'de, D>(
+    deserializer: D,
+) -> Result<Option<BTreeMap<String, ConfigBuildProfile>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let profiles = Option::<BTreeMap<String, ConfigBuildProfile>>::deserialize(deserializer)?;
+    if let Some(name) = profiles
+        .iter()
+        .flat_map(BTreeMap::keys)
+        .find(|name| BUILT_IN_PROFILES.contains(&name.as_str()))
+    {
+        return Err(D::Error::custom(format!(
+            "build profile '{name}' cannot be redefined, because debug, release and test-coverage are built-in profiles"
+        )));
+    }
+    Ok(profiles)
+}
+
 // grumble grumble https://github.com/serde-rs/serde/issues/2231
 impl<'de> Deserialize<'de> for CanisterTypeProperties {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -1126,6 +1745,7 @@ impl<'de> Visitor<'de> for PropertiesVisitor {
         let mut r#type = None;
         let mut id = None;
         let mut workspace = None;
+        let mut upload_parallelism = None;
         while let Some(key) = map.next_key::<String>()? {
             match &*key {
                 "package" => package = Some(map.next_value()?),
@@ -1136,6 +1756,7 @@ impl<'de> Visitor<'de> for PropertiesVisitor {
                 "type" => r#type = Some(map.next_value::<String>()?),
                 "id" => id = Some(map.next_value()?),
                 "workspace" => workspace = Some(map.next_value()?),
+                "upload_parallelism" => upload_parallelism = Some(map.next_value()?),
                 _ => continue,
             }
         }
@@ -1149,6 +1770,7 @@ impl<'de> Visitor<'de> for PropertiesVisitor {
                 source: source.ok_or_else(|| missing_field("source"))?,
                 build: build.unwrap_or_default(),
                 workspace,
+                upload_parallelism,
             },
             Some("custom") => CanisterTypeProperties::Custom {
                 build: build.unwrap_or_default(),
@@ -1223,6 +1845,174 @@ impl NetworksConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn gets_and_sets_json_values() {
+        let mut config = Config::from_str(
+            r#"{
+              "canisters": {
+                "app": { "type": "custom", "candid": "app.did", "wasm": "app.wasm" }
+              }
+            }"#,
+        )
+        .unwrap();
+
+        config
+            .set_json_value(
+                "canisters.app.initialization_values.compute_allocation",
+                Some(serde_json::json!(50)),
+            )
+            .unwrap();
+        assert_eq!(
+            config
+                .get_json_value("/canisters/app/initialization_values/compute_allocation")
+                .unwrap(),
+            Some(&serde_json::json!(50))
+        );
+        assert_eq!(
+            config.get_config().get_compute_allocation("app").unwrap(),
+            Some(50)
+        );
+
+        // The value must match the type of the field.
+        assert!(matches!(
+            config.set_json_value("canisters.app.type", Some(serde_json::json!(3))),
+            Err(ConfigValueError::InvalidConfig(..))
+        ));
+        assert!(matches!(
+            config.set_json_value("canisters.app.wasm.name", Some(serde_json::json!("x"))),
+            Err(ConfigValueError::NotAContainer(..))
+        ));
+        assert!(matches!(
+            config.set_json_value("canisters..app", None),
+            Err(ConfigValueError::InvalidPath(..))
+        ));
+        assert_eq!(
+            config.get_json_value("canisters.app.type").unwrap(),
+            Some(&serde_json::json!("custom"))
+        );
+
+        config
+            .set_json_value("canisters.app.initialization_values", None)
+            .unwrap();
+        assert_eq!(
+            config
+                .get_json_value("canisters.app.initialization_values")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn set_json_value_preserves_key_order() {
+        let mut config = Config::from_str(
+            r#"{
+              "version": 1,
+              "output_env_file": ".env",
+              "canisters": {
+                "frontend": { "type": "assets", "source": ["dist"] },
+                "backend": { "type": "motoko", "main": "main.mo" }
+              },
+              "dfx": "0.15.0"
+            }"#,
+        )
+        .unwrap();
+        let keys = |value: Option<&Value>| {
+            value
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        config
+            .set_json_value(
+                "canisters.backend.args",
+                Some(serde_json::json!("--release")),
+            )
+            .unwrap();
+        config
+            .set_json_value("canisters.frontend.type", Some(serde_json::json!("assets")))
+            .unwrap();
+        config.set_json_value("output_env_file", None).unwrap();
+        assert_eq!(
+            keys(Some(config.get_json())),
+            ["version", "canisters", "dfx"]
+        );
+        assert_eq!(
+            keys(config.get_json_value("canisters").unwrap()),
+            ["frontend", "backend"]
+        );
+        assert_eq!(
+            keys(config.get_json_value("canisters.backend").unwrap()),
+            ["type", "main", "args"]
+        );
+    }
+
+    #[test]
+    fn rejects_built_in_profile_names() {
+        let config =
+            Config::from_str(r#"{ "profiles": { "fast": { "shrink": false } } }"#).unwrap();
+        assert!(config
+            .get_config()
+            .profiles
+            .as_ref()
+            .unwrap()
+            .contains_key("fast"));
+
+        let err = Config::from_str(r#"{ "profiles": { "release": { "shrink": false } } }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("build profile 'release' cannot be redefined"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn merges_environment_overlays() {
+        let root_dir = tempfile::tempdir().unwrap();
+        let config_path = root_dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(
+            &config_path,
+            r#"{
+              "canisters": {
+                "app": { "type": "custom", "candid": "app.did", "wasm": "app.wasm", "args": "--base" },
+                "debug": { "type": "custom", "candid": "debug.did", "wasm": "debug.wasm" }
+              },
+              "envs": {
+                "staging": { "canisters": { "app": { "args": "--staging" } } }
+              }
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root_dir.path().join("dfx.staging.json"),
+            r#"{ "canisters": { "debug": null }, "networks": { "staging": { "providers": ["https://staging.example.com"] } } }"#,
+        )
+        .unwrap();
+
+        let config = Config::from_dir_with_overlay(root_dir.path(), Some("staging"))
+            .unwrap()
+            .unwrap();
+        let canisters = config.get_config().canisters.as_ref().unwrap();
+        assert_eq!(canisters.keys().collect::<Vec<_>>(), vec!["app"]);
+        assert_eq!(canisters["app"].args.as_deref(), Some("--staging"));
+        assert!(config.get_config().get_network("staging").is_some());
+        // The overlay is not part of the json that would be saved.
+        assert!(config.get_json()["canisters"].get("debug").is_some());
+
+        assert!(matches!(
+            Config::from_dir_with_overlay(root_dir.path(), Some("production")),
+            Err(UnknownEnvironment(..))
+        ));
+        assert!(matches!(
+            Config::from_dir_with_overlay(root_dir.path(), Some("../staging")),
+            Err(InvalidEnvironmentName(..))
+        ));
+    }
+
     #[test]
     fn find_dfinity_config_current_path() {
         let root_dir = tempfile::tempdir().unwrap();
@@ -1365,10 +2155,86 @@ mod tests {
                 providers: vec![String::from("https://1.2.3.4:5000")],
                 r#type: NetworkType::Ephemeral,
                 playground: None,
+                connection: ConfigNetworkConnection::default(),
             })
         );
     }
 
+    #[test]
+    fn network_connection_settings() {
+        let config = Config::from_str(
+            r#"{
+            "networks": {
+                "staging": {
+                    "providers": [ "https://1.2.3.4:5000" ],
+                    "http_timeout": "30s",
+                    "retry": 3,
+                    "backoff": "250ms",
+                    "http_proxy": "http://proxy.example.com:3128",
+                    "ca_bundle": "certs/proxy.pem"
+                },
+                "local": {
+                    "bind": "127.0.0.1:8000",
+                    "retry": 1
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+
+        let network = config.get_config().get_network("staging").unwrap();
+        if let ConfigNetwork::ConfigNetworkProvider(network_provider) = network {
+            assert_eq!(
+                network_provider.connection,
+                ConfigNetworkConnection {
+                    http_timeout: Some(Duration::from_secs(30)),
+                    retry: Some(3),
+                    backoff: Some(Duration::from_millis(250)),
+                    http_proxy: Some("http://proxy.example.com:3128".to_string()),
+                    ca_bundle: Some(PathBuf::from("certs/proxy.pem")),
+                }
+            );
+        } else {
+            panic!("not a network provider");
+        }
+
+        let network = config.get_config().get_network("local").unwrap();
+        if let ConfigNetwork::ConfigLocalProvider(local_network) = network {
+            assert_eq!(local_network.connection.retry, Some(1));
+            assert_eq!(local_network.connection.http_timeout, None);
+        } else {
+            panic!("not a local provider");
+        }
+    }
+
+    #[test]
+    fn init_arg_per_network() {
+        let config = Config::from_str(
+            r#"{
+              "canisters": {
+                "single": {
+                  "init_arg": "(1 : nat)"
+                },
+                "per_network": {
+                  "init_arg": {
+                    "local": "(2 : nat)",
+                    "ic": "(3 : nat)"
+                  }
+                }
+              }
+        }"#,
+        )
+        .unwrap();
+
+        let canisters = config.get_config().canisters.as_ref().unwrap();
+        let single = canisters["single"].init_arg.as_ref().unwrap();
+        assert_eq!(single.for_network("ic"), Some("(1 : nat)"));
+        let per_network = canisters["per_network"].init_arg.as_ref().unwrap();
+        assert_eq!(per_network.for_network("local"), Some("(2 : nat)"));
+        assert_eq!(per_network.for_network("ic"), Some("(3 : nat)"));
+        assert_eq!(per_network.for_network("staging"), None);
+    }
+
     #[test]
     fn get_correct_initialization_values() {
         let config = Config::from_str(
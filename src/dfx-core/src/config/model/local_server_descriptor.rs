@@ -1,9 +1,9 @@
 use crate::config::model::bitcoin_adapter;
 use crate::config::model::canister_http_adapter::HttpAdapterLogLevel;
 use crate::config::model::dfinity::{
-    to_socket_addr, ConfigDefaultsBitcoin, ConfigDefaultsCanisterHttp, ConfigDefaultsProxy,
-    ConfigDefaultsReplica, ReplicaLogLevel, ReplicaSubnetType, DEFAULT_PROJECT_LOCAL_BIND,
-    DEFAULT_SHARED_LOCAL_BIND,
+    to_socket_addr, ConfigDefaultsBitcoin, ConfigDefaultsCandidUi, ConfigDefaultsCanisterHttp,
+    ConfigDefaultsProxy, ConfigDefaultsReplica, ConfigLocalTask, ReplicaLogLevel,
+    ReplicaSubnetType, DEFAULT_PROJECT_LOCAL_BIND, DEFAULT_SHARED_LOCAL_BIND,
 };
 use crate::error::network_config::{
     NetworkConfigError, NetworkConfigError::ParseBindAddressFailed,
@@ -31,9 +31,11 @@ pub struct LocalServerDescriptor {
     pub bind_address: SocketAddr,
 
     pub bitcoin: ConfigDefaultsBitcoin,
+    pub candid_ui: ConfigDefaultsCandidUi,
     pub canister_http: ConfigDefaultsCanisterHttp,
     pub proxy: ConfigDefaultsProxy,
     pub replica: ConfigDefaultsReplica,
+    pub tasks: Vec<ConfigLocalTask>,
 
     pub scope: LocalNetworkScopeDescriptor,
 
@@ -53,9 +55,11 @@ impl LocalServerDescriptor {
         data_directory: PathBuf,
         bind: String,
         bitcoin: ConfigDefaultsBitcoin,
+        candid_ui: ConfigDefaultsCandidUi,
         canister_http: ConfigDefaultsCanisterHttp,
         proxy: ConfigDefaultsProxy,
         replica: ConfigDefaultsReplica,
+        tasks: Vec<ConfigLocalTask>,
         scope: LocalNetworkScopeDescriptor,
         legacy_pid_path: Option<PathBuf>,
     ) -> Result<Self, NetworkConfigError> {
@@ -64,9 +68,11 @@ impl LocalServerDescriptor {
             data_directory,
             bind_address,
             bitcoin,
+            candid_ui,
             canister_http,
             proxy,
             replica,
+            tasks,
             scope,
             legacy_pid_path,
         })
@@ -166,6 +172,31 @@ impl LocalServerDescriptor {
     pub fn effective_config_path(&self) -> PathBuf {
         self.data_directory.join("replica-effective-config.json")
     }
+
+    /// The directory holding the output of the processes started by `dfx start`.
+    pub fn logs_dir(&self) -> PathBuf {
+        self.data_directory.join("logs")
+    }
+
+    /// This file contains the output of the replica process
+    pub fn replica_log_path(&self) -> PathBuf {
+        self.logs_dir().join("replica.log")
+    }
+
+    /// This file contains the output of the ic-btc-adapter process
+    pub fn btc_adapter_log_path(&self) -> PathBuf {
+        self.logs_dir().join("ic-btc-adapter.log")
+    }
+
+    /// This file contains the output of the ic-https-outcalls-adapter process
+    pub fn canister_http_adapter_log_path(&self) -> PathBuf {
+        self.logs_dir().join("ic-https-outcalls-adapter.log")
+    }
+
+    /// This file contains the output of the scheduled tasks of the network
+    pub fn tasks_log_path(&self) -> PathBuf {
+        self.logs_dir().join("tasks.log")
+    }
 }
 
 impl LocalServerDescriptor {
@@ -203,6 +234,7 @@ impl LocalServerDescriptor {
     pub fn with_proxy_domains(self, domains: Vec<String>) -> LocalServerDescriptor {
         let proxy = ConfigDefaultsProxy {
             domain: SerdeVec::Many(domains),
+            ..self.proxy
         };
         Self { proxy, ..self }
     }
@@ -242,6 +274,12 @@ impl LocalServerDescriptor {
             debug!(log, "  bitcoin: disabled");
         }
 
+        if self.candid_ui.enabled {
+            debug!(log, "  candid ui: enabled");
+        } else {
+            debug!(log, "  candid ui: disabled (default: enabled)");
+        }
+
         if self.canister_http.enabled {
             debug!(log, "  canister http: enabled");
             let diffs: String = if self.canister_http.log_level != HttpAdapterLogLevel::default() {
@@ -257,6 +295,10 @@ impl LocalServerDescriptor {
             debug!(log, "  canister http: disabled (default: enabled)");
         }
 
+        for (domain, canister_id) in &self.proxy.custom_domains {
+            debug!(log, "  custom domain: {} -> {}", domain, canister_id);
+        }
+
         debug!(log, "  replica:");
         if let Some(port) = self.replica.port {
             debug!(log, "    port: {}", port);
@@ -280,6 +322,10 @@ impl LocalServerDescriptor {
         };
         debug!(log, "    log level: {:?}{}", log_level, diffs);
 
+        for task in &self.tasks {
+            debug!(log, "  task: {} every {:?}", task.name, task.interval);
+        }
+
         debug!(log, "  data directory: {}", self.data_directory.display());
         let scope = match self.scope {
             LocalNetworkScopeDescriptor::Project => "project",
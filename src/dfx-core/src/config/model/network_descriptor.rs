@@ -1,5 +1,6 @@
 use crate::config::model::dfinity::{
-    NetworkType, PlaygroundConfig, DEFAULT_IC_GATEWAY, DEFAULT_IC_GATEWAY_TRAILING_SLASH,
+    ConfigNetworkConnection, NetworkType, PlaygroundConfig, DEFAULT_IC_GATEWAY,
+    DEFAULT_IC_GATEWAY_TRAILING_SLASH,
 };
 use crate::config::model::local_server_descriptor::LocalServerDescriptor;
 use crate::error::network_config::NetworkConfigError;
@@ -34,6 +35,8 @@ pub struct NetworkDescriptor {
     pub r#type: NetworkTypeDescriptor,
     pub is_ic: bool,
     pub local_server_descriptor: Option<LocalServerDescriptor>,
+    /// The timeout and retries for requests to the network.
+    pub connection: ConfigNetworkConnection,
 }
 
 impl NetworkTypeDescriptor {
@@ -72,6 +75,7 @@ impl NetworkDescriptor {
             r#type: NetworkTypeDescriptor::Persistent,
             is_ic: true,
             local_server_descriptor: None,
+            connection: ConfigNetworkConnection::default(),
         }
     }
 
@@ -122,6 +126,7 @@ impl NetworkDescriptor {
             },
             is_ic: true,
             local_server_descriptor: None,
+            connection: ConfigNetworkConnection::default(),
         }
     }
 
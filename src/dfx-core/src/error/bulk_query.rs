@@ -0,0 +1,31 @@
+use crate::error::identity::instantiate_identity_from_name::InstantiateIdentityFromNameError;
+use crate::error::identity::new_identity_manager::NewIdentityManagerError;
+use crate::error::load_dfx_config::LoadDfxConfigError;
+use crate::error::load_networks_config::LoadNetworksConfigError;
+use crate::error::network_config::NetworkConfigError;
+use crate::error::root_key::FetchRootKeyError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConnectError {
+    #[error("Failed to load dfx.json: {0}")]
+    LoadDfxConfig(#[from] LoadDfxConfigError),
+
+    #[error("Failed to load networks.json: {0}")]
+    LoadNetworksConfig(#[from] LoadNetworksConfigError),
+
+    #[error("Failed to determine the network: {0}")]
+    NetworkConfig(#[from] NetworkConfigError),
+
+    #[error("Failed to create the identity manager: {0}")]
+    NewIdentityManager(#[from] NewIdentityManagerError),
+
+    #[error("Failed to instantiate the identity: {0}")]
+    InstantiateIdentity(#[from] InstantiateIdentityFromNameError),
+
+    #[error("Failed to create the agent: {0}")]
+    CreateAgent(ic_agent::AgentError),
+
+    #[error("Failed to fetch the root key: {0}")]
+    FetchRootKey(#[from] FetchRootKeyError),
+}
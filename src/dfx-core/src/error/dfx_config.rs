@@ -52,6 +52,12 @@ pub enum GetMemoryAllocationError {
     GetMemoryAllocationFailed(String, GetCanisterConfigError),
 }
 
+#[derive(Error, Debug)]
+pub enum GetWasmMemoryLimitError {
+    #[error("Failed to get Wasm memory limit for canister '{0}': {1}")]
+    GetWasmMemoryLimitFailed(String, GetCanisterConfigError),
+}
+
 #[derive(Error, Debug)]
 pub enum GetPullCanistersError {
     #[error("Pull dependencies '{0}' and '{1}' have the same canister ID: {2}")]
@@ -69,3 +75,21 @@ pub enum GetSpecifiedIdError {
     #[error("Failed to get specified_id for canister '{0}': {1}")]
     GetSpecifiedIdFailed(String, GetCanisterConfigError),
 }
+
+#[derive(Error, Debug)]
+pub enum ConfigValueError {
+    #[error("'{0}' is not a valid path.")]
+    InvalidPath(String),
+
+    #[error("Cannot set '{0}': '{1}' is neither an object nor an array.")]
+    NotAContainer(String, String),
+
+    #[error("Cannot set '{0}': '{1}' is not a valid index for an array of length {2}.")]
+    InvalidIndex(String, String, usize),
+
+    #[error("Environment variable '{0}' is not defined.")]
+    UndefinedEnvironmentVariable(String),
+
+    #[error("Setting '{0}' would make dfx.json invalid: {1}")]
+    InvalidConfig(String, serde_json::Error),
+}
@@ -11,6 +11,9 @@ pub enum EncryptionError {
     #[error("Failed to hash password: {0}")]
     HashPasswordFailed(argon2::password_hash::Error),
 
+    #[error("Invalid key derivation parameters: {0}")]
+    InvalidKeyDerivationParameters(argon2::Error),
+
     #[error("Failed to generate nonce: {0}")]
     NonceGenerationFailed(ring::error::Unspecified),
 
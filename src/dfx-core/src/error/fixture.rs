@@ -0,0 +1,35 @@
+use crate::error::fs::FsError;
+use crate::error::identity::convert_mnemonic_to_key::ConvertMnemonicToKeyError;
+use crate::error::structured_file::StructuredFileError;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CreateFixtureError {
+    #[error("Failed to create temporary directory in {0}: {1}")]
+    CreateTemporaryDirectoryFailed(PathBuf, std::io::Error),
+
+    #[error("Failed to derive the key of identity '{0}': {1}")]
+    DeriveIdentityKeyFailed(String, ConvertMnemonicToKeyError),
+
+    #[error("Canister '{0}' is defined more than once.")]
+    DuplicateCanister(String),
+
+    #[error("Failed to get the principal of identity '{0}': {1}")]
+    GetIdentityPrincipalFailed(String, String),
+
+    #[error("Canister '{0}' depends on unknown canister '{1}'.")]
+    UnknownDependency(String, String),
+
+    #[error(transparent)]
+    Fs(#[from] FsError),
+
+    #[error(transparent)]
+    StructuredFile(#[from] StructuredFileError),
+}
+
+#[derive(Error, Debug)]
+pub enum FixtureCanisterTypeError {
+    #[error("Unknown fixture canister type: {0}. Supported types are motoko, assets and custom.")]
+    UnknownCanisterType(String),
+}
@@ -2,6 +2,15 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ConvertMnemonicToKeyError {
+    #[error("Convert secret key to sec1 Pem failed: {0}")]
+    ConvertSecretKeyToSec1PemFailed(Box<sec1::Error>),
+
+    #[error("Failed to derive Ed25519 key: {0}")]
+    DeriveEd25519KeyFailed(String),
+
     #[error("Failed to derive extended secret key from path: {0}")]
     DeriveExtendedKeyFromPathFailed(bip32::Error),
+
+    #[error("Failed to encode Ed25519 key as Pem: {0}")]
+    EncodeEd25519PemFailed(String),
 }
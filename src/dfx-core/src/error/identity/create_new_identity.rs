@@ -1,6 +1,7 @@
 use crate::error::fs::FsError;
 use crate::error::identity::convert_mnemonic_to_key::ConvertMnemonicToKeyError;
 use crate::error::identity::create_identity_config::CreateIdentityConfigError;
+use crate::error::identity::delegation_chain::DelegationChainError;
 use crate::error::identity::generate_key::GenerateKeyError;
 use crate::error::identity::load_pem_from_file::LoadPemFromFileError;
 use crate::error::identity::remove_identity::RemoveIdentityError;
@@ -21,9 +22,6 @@ pub enum CreateNewIdentityError {
     #[error("Failed to create identity config: {0}")]
     ConvertMnemonicToKeyFailed(ConvertMnemonicToKeyError),
 
-    #[error("Convert secret key to sec1 Pem failed: {0}")]
-    ConvertSecretKeyToSec1PemFailed(Box<sec1::Error>),
-
     #[error("Failed to create identity config: {0}")]
     CreateIdentityConfigFailed(CreateIdentityConfigError),
 
@@ -33,12 +31,18 @@ pub enum CreateNewIdentityError {
     #[error("Failed to create temporary identity directory: {0}")]
     CreateTemporaryIdentityDirectoryFailed(FsError),
 
+    #[error("The session key is not the key that the delegation chain delegates to.")]
+    DelegationSessionKeyMismatch(),
+
     #[error("Failed to generate key: {0}")]
     GenerateKeyFailed(GenerateKeyError),
 
     #[error("Identity already exists.")]
     IdentityAlreadyExists(),
 
+    #[error("Invalid delegation chain: {0}")]
+    InvalidDelegationChain(DelegationChainError),
+
     #[error("Failed to load pem file: {0}")]
     LoadPemFromFileFailed(LoadPemFromFileError),
 
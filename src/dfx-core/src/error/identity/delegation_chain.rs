@@ -0,0 +1,20 @@
+use candid::types::principal::PrincipalError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DelegationChainError {
+    #[error("The delegation chain has expired at {0}. Import a new delegation.")]
+    DelegationExpired(String),
+
+    #[error("The delegation chain has no delegations.")]
+    EmptyDelegationChain(),
+
+    #[error("Invalid expiration '{0}': {1}")]
+    InvalidExpiration(String, std::num::ParseIntError),
+
+    #[error("Invalid hex in field '{0}': {1}")]
+    InvalidHex(String, hex::FromHexError),
+
+    #[error("Invalid target canister '{0}': {1}")]
+    InvalidTarget(String, PrincipalError),
+}
@@ -0,0 +1,19 @@
+use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError;
+use crate::error::identity::require_identity_exists::RequireIdentityExistsError;
+use crate::error::keyring::KeyringError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LockIdentityError {
+    #[error("Failed to delete the session key from the keyring: {0}")]
+    DeleteSessionKeyFailed(KeyringError),
+
+    #[error("Failed to get identity config: {0}")]
+    GetIdentityConfigFailed(GetIdentityConfigOrDefaultError),
+
+    #[error("The specified identity does not exist: {0}")]
+    IdentityDoesNotExist(RequireIdentityExistsError),
+
+    #[error("Identity '{0}' is not password-protected.")]
+    NotPasswordProtected(String),
+}
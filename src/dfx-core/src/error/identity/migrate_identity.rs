@@ -0,0 +1,46 @@
+use crate::error::encryption::EncryptionError;
+use crate::error::fs::FsError;
+use crate::error::identity::create_identity_config::CreateIdentityConfigError;
+use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError;
+use crate::error::identity::load_pem::LoadPemError;
+use crate::error::identity::replace_key_file::ReplaceKeyFileError;
+use crate::error::identity::save_identity_configuration::SaveIdentityConfigurationError;
+use crate::error::identity::save_pem::SavePemError;
+use crate::error::keyring::KeyringError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MigrateIdentityError {
+    #[error("Identities cannot be migrated to plaintext storage.")]
+    CannotMigrateToPlaintext(),
+
+    #[error("Failed to create the identity configuration for the new storage mode: {0}")]
+    CreateIdentityConfigFailed(CreateIdentityConfigError),
+
+    #[error("Failed to generate a fresh encryption configuration: {0}")]
+    GenerateFreshEncryptionConfigurationFailed(EncryptionError),
+
+    #[error("Failed to get identity config: {0}")]
+    GetIdentityConfigFailed(GetIdentityConfigOrDefaultError),
+
+    #[error("Failed to list identities: {0}")]
+    GetIdentityNamesFailed(FsError),
+
+    #[error("Failed to load pem: {0}")]
+    LoadPemFailed(LoadPemError),
+
+    #[error("Failed to replace the previous key: {0}")]
+    ReplaceKeyFailed(ReplaceKeyFileError),
+
+    #[error("Failed to remove the previously stored key: {0}")]
+    RemovePreviousKeyFailed(FsError),
+
+    #[error("Failed to remove the previously stored key from the keyring: {0}")]
+    RemovePreviousKeyFromKeyringFailed(KeyringError),
+
+    #[error("Failed to save identity configuration: {0}")]
+    SaveIdentityConfigurationFailed(SaveIdentityConfigurationError),
+
+    #[error("Failed to save pem: {0}")]
+    SavePemFailed(SavePemError),
+}
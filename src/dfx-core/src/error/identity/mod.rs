@@ -2,6 +2,7 @@ pub mod call_sender_from_wallet;
 pub mod convert_mnemonic_to_key;
 pub mod create_identity_config;
 pub mod create_new_identity;
+pub mod delegation_chain;
 pub mod export_identity;
 pub mod generate_key;
 pub mod get_identity_config_or_default;
@@ -12,17 +13,26 @@ pub mod load_identity;
 pub mod load_pem;
 pub mod load_pem_from_file;
 pub mod load_pem_identity;
+pub mod lock_identity;
 pub mod map_wallets_to_renamed_identity;
+pub mod migrate_identity;
 pub mod new_hardware_identity;
 pub mod new_identity;
 pub mod new_identity_manager;
 pub mod remove_identity;
 pub mod rename_identity;
 pub mod rename_wallet_global_config_key;
+pub mod replace_key_file;
 pub mod require_identity_exists;
+pub mod require_network_allowed;
+pub mod rotate_key;
 pub mod save_identity_configuration;
 pub mod save_pem;
+pub mod set_allowed_networks;
+pub mod sign_message;
+pub mod unlock_identity;
 pub mod use_identity_by_name;
 pub mod validate_pem_file;
+pub mod verify_message;
 pub mod write_default_identity;
 pub mod write_pem_to_file;
@@ -1,3 +1,4 @@
+use crate::error::identity::delegation_chain::DelegationChainError;
 use crate::error::identity::load_pem::LoadPemError;
 use crate::error::identity::load_pem_identity::LoadPemIdentityError;
 use crate::error::identity::new_hardware_identity::NewHardwareIdentityError;
@@ -5,6 +6,9 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum NewIdentityError {
+    #[error("Failed to load delegation chain: {0}")]
+    LoadDelegationChainFailed(DelegationChainError),
+
     #[error("Failed to load PEM: {0}")]
     LoadPemFailed(LoadPemError),
 
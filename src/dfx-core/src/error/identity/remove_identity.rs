@@ -15,6 +15,9 @@ pub enum RemoveIdentityError {
     #[error("Failed to display linked wallets: {0}")]
     DisplayLinkedWalletsFailed(WalletConfigError),
 
+    #[error("If you want to remove an identity and the backups of its previous keys, please use the --drop-key-backups flag.")]
+    DropKeyBackupsFlagRequiredToRemoveIdentityWithKeyBackups(),
+
     #[error("If you want to remove an identity with configured wallets, please use the --drop-wallets flag.")]
     DropWalletsFlagRequiredToRemoveIdentityWithWallets(),
 
@@ -0,0 +1,19 @@
+use crate::error::fs::FsError;
+use crate::error::identity::save_identity_configuration::SaveIdentityConfigurationError;
+use crate::error::identity::write_pem_to_file::WritePemToFileError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReplaceKeyFileError {
+    #[error("Failed to save identity configuration: {0}")]
+    SaveIdentityConfigurationFailed(SaveIdentityConfigurationError),
+
+    #[error("Failed to replace the configuration of the previous key: {0}")]
+    SwapConfigurationFailed(FsError),
+
+    #[error("Failed to replace the previous key: {0}")]
+    SwapKeyFailed(FsError),
+
+    #[error("Failed to write the new key: {0}")]
+    WriteNewKeyFailed(WritePemToFileError),
+}
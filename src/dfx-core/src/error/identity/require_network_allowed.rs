@@ -0,0 +1,15 @@
+use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RequireNetworkAllowedError {
+    #[error("Failed to get identity config: {0}")]
+    GetIdentityConfigOrDefaultFailed(GetIdentityConfigOrDefaultError),
+
+    #[error("Identity '{identity}' is not allowed on network '{network}'. It can only be used on: {allowed}. Change this with `dfx identity set-allowed-networks`.")]
+    NetworkNotAllowed {
+        identity: String,
+        network: String,
+        allowed: String,
+    },
+}
@@ -0,0 +1,58 @@
+use crate::error::encryption::EncryptionError;
+use crate::error::identity::generate_key::GenerateKeyError;
+use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError;
+use crate::error::identity::load_pem::LoadPemError;
+use crate::error::identity::load_pem_identity::LoadPemIdentityError;
+use crate::error::identity::replace_key_file::ReplaceKeyFileError;
+use crate::error::identity::require_identity_exists::RequireIdentityExistsError;
+use crate::error::identity::save_identity_configuration::SaveIdentityConfigurationError;
+use crate::error::identity::save_pem::SavePemError;
+use crate::error::identity::write_pem_to_file::WritePemToFileError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RotateKeyError {
+    #[error("Failed to back up the previous key: {0}")]
+    BackupKeyFailed(WritePemToFileError),
+
+    #[error("Cannot rotate the key of the anonymous identity.")]
+    CannotRotateAnonymousIdentity(),
+
+    #[error(
+        "Cannot rotate the session key of a delegated identity. Import a new delegation instead."
+    )]
+    CannotRotateDelegatedIdentity(),
+
+    #[error("Cannot rotate the key of a hardware identity. Generate a new key on the HSM and create a new identity for it instead.")]
+    CannotRotateHardwareIdentity(),
+
+    #[error("Failed to generate a fresh encryption configuration: {0}")]
+    GenerateFreshEncryptionConfigurationFailed(EncryptionError),
+
+    #[error("Failed to generate key: {0}")]
+    GenerateKeyFailed(GenerateKeyError),
+
+    #[error("Failed to get identity config: {0}")]
+    GetIdentityConfigFailed(GetIdentityConfigOrDefaultError),
+
+    #[error("Failed to get the principal of the new key: {0}")]
+    GetNewPrincipalFailed(String),
+
+    #[error("Identity does not exist: {0}")]
+    IdentityDoesNotExist(RequireIdentityExistsError),
+
+    #[error("Failed to load the new key: {0}")]
+    LoadNewKeyFailed(LoadPemIdentityError),
+
+    #[error("Failed to load pem: {0}")]
+    LoadPemFailed(LoadPemError),
+
+    #[error("Failed to save the configuration of the key backup: {0}")]
+    SaveBackupConfigurationFailed(SaveIdentityConfigurationError),
+
+    #[error("Failed to save pem: {0}")]
+    SavePemFailed(SavePemError),
+
+    #[error("Failed to replace the previous key: {0}")]
+    ReplaceKeyFailed(ReplaceKeyFileError),
+}
@@ -0,0 +1,16 @@
+use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError;
+use crate::error::identity::require_identity_exists::RequireIdentityExistsError;
+use crate::error::identity::save_identity_configuration::SaveIdentityConfigurationError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SetAllowedNetworksError {
+    #[error("Failed to get identity config: {0}")]
+    GetIdentityConfigOrDefaultFailed(GetIdentityConfigOrDefaultError),
+
+    #[error("Identity must exist: {0}")]
+    RequireIdentityExistsFailed(RequireIdentityExistsError),
+
+    #[error("Failed to save identity configuration: {0}")]
+    SaveIdentityConfigurationFailed(SaveIdentityConfigurationError),
+}
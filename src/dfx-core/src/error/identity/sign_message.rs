@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SignMessageError {
+    #[error("Signing messages with a delegated identity is not supported.")]
+    DelegatedIdentityNotSupported(),
+
+    #[error("The anonymous identity cannot sign messages.")]
+    NoPublicKey(),
+
+    #[error("Failed to get the principal of the identity: {0}")]
+    GetPrincipalFailed(String),
+
+    #[error("Failed to sign the message: {0}")]
+    SignFailed(String),
+}
@@ -0,0 +1,27 @@
+use crate::error::encryption::EncryptionError;
+use crate::error::fs::FsError;
+use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError;
+use crate::error::identity::require_identity_exists::RequireIdentityExistsError;
+use crate::error::keyring::KeyringError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum UnlockIdentityError {
+    #[error("Failed to decrypt pem file: {0}")]
+    DecryptPemFailed(EncryptionError),
+
+    #[error("Failed to get identity config: {0}")]
+    GetIdentityConfigFailed(GetIdentityConfigOrDefaultError),
+
+    #[error("The specified identity does not exist: {0}")]
+    IdentityDoesNotExist(RequireIdentityExistsError),
+
+    #[error("Identity '{0}' is not password-protected.")]
+    NotPasswordProtected(String),
+
+    #[error("Failed to read pem file: {0}")]
+    ReadPemFileFailed(FsError),
+
+    #[error("Failed to store the session key in the keyring: {0}")]
+    WriteSessionKeyFailed(KeyringError),
+}
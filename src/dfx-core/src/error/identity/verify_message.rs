@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum VerifyMessageError {
+    #[error("Invalid hex in field '{0}': {1}")]
+    InvalidHex(String, hex::FromHexError),
+
+    #[error("Invalid public key: {0}")]
+    InvalidPublicKey(String),
+
+    #[error("The signature is not valid for the message.")]
+    InvalidSignature(),
+
+    #[error("The principal {0} does not belong to the public key, which is the key of {1}.")]
+    PrincipalMismatch(String, String),
+
+    #[error("Unsupported key type. Only Ed25519, secp256k1, and prime256v1 keys are supported.")]
+    UnsupportedKeyType(),
+}
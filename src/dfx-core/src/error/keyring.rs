@@ -6,6 +6,9 @@ pub enum KeyringError {
     #[error("Failed to decode pem from keyring: {0}")]
     DecodePemFailed(hex::FromHexError),
 
+    #[error("The keyring is disabled with DFX_KEYRING_BACKEND=none.")]
+    Disabled(),
+
     #[error("Failed to delete password from keyring: {0}")]
     DeletePasswordFailed(keyring::Error),
 
@@ -26,4 +29,7 @@ pub enum KeyringError {
 
     #[error("Failed to set password for keyring: {0}")]
     SetPasswordFailed(keyring::Error),
+
+    #[error("Unknown keyring backend '{0}' in DFX_KEYRING_BACKEND. Expected 'system' or 'none'.")]
+    UnknownBackend(String),
 }
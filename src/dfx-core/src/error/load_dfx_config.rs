@@ -1,5 +1,6 @@
 use crate::error::fs::FsError;
 use crate::error::structured_file::StructuredFileError;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -12,4 +13,20 @@ pub enum LoadDfxConfigError {
 
     #[error("Failed to determine current working dir: {0}")]
     DetermineCurrentWorkingDirFailed(std::io::Error),
+
+    #[error("Cannot use environment '{0}' outside of a project.")]
+    EnvironmentWithoutProject(String),
+
+    #[error("Invalid environment name '{0}'. Only letters, digits, '-' and '_' are allowed.")]
+    InvalidEnvironmentName(String),
+
+    #[error(
+        "Unknown environment '{0}': dfx.json has no 'envs.{0}' section and {1} does not exist."
+    )]
+    UnknownEnvironment(String, PathBuf),
+
+    #[error(
+        "{1} refers to the environment variable '{0}' with '${{env:{0}}}', but it is not set."
+    )]
+    UndefinedEnvironmentVariable(String, PathBuf),
 }
@@ -1,4 +1,5 @@
 pub mod archive;
+pub mod bulk_query;
 pub mod cache;
 pub mod canister;
 pub mod canister_id_store;
@@ -7,6 +8,7 @@ pub mod config;
 pub mod dfx_config;
 pub mod encryption;
 pub mod extension;
+pub mod fixture;
 pub mod fs;
 pub mod get_current_exe;
 pub mod get_user_home;
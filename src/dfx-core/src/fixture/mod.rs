@@ -0,0 +1,395 @@
+//! Throwaway dfx projects for integration tests of extensions and other tooling.
+//!
+//! A fixture consists of a project directory with the requested canisters, and a config root
+//! with deterministic identities. Commands run against the fixture must set `DFX_CONFIG_ROOT`
+//! to the config root, which [`ProjectFixture::command`] takes care of.
+use crate::error::fixture::CreateFixtureError::{
+    CreateTemporaryDirectoryFailed, DeriveIdentityKeyFailed, DuplicateCanister,
+    GetIdentityPrincipalFailed, UnknownDependency,
+};
+use crate::error::fixture::{CreateFixtureError, FixtureCanisterTypeError};
+use crate::fs::composite::ensure_parent_dir_exists;
+use crate::identity::identity_file_locations::IdentityFileLocations;
+use crate::identity::identity_manager::{mnemonic_to_pem, IdentityConfiguration, KeyType};
+use crate::identity::IDENTITY_JSON;
+use crate::json::save_json_file;
+use bip39::{Language, Mnemonic};
+use candid::Principal;
+use ic_agent::identity::Secp256k1Identity;
+use ic_agent::Identity;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use tempfile::TempDir;
+
+pub const DEFAULT_FIXTURE_IDENTITY: &str = "default";
+
+/// The smallest valid wasm module: just the magic number and version.
+const EMPTY_WASM_MODULE: &[u8] = b"\0asm\x01\0\0\0";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixtureCanisterType {
+    /// A Motoko canister with a `greet` query method.
+    Motoko,
+    /// An asset canister with an `index.html`.
+    Assets,
+    /// A custom canister with an empty wasm module and interface, which builds without any toolchain.
+    Custom,
+}
+
+impl FromStr for FixtureCanisterType {
+    type Err = FixtureCanisterTypeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "motoko" => Ok(FixtureCanisterType::Motoko),
+            "assets" => Ok(FixtureCanisterType::Assets),
+            "custom" => Ok(FixtureCanisterType::Custom),
+            other => Err(FixtureCanisterTypeError::UnknownCanisterType(
+                other.to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FixtureCanister {
+    pub name: String,
+    pub canister_type: FixtureCanisterType,
+    pub dependencies: Vec<String>,
+}
+
+impl FixtureCanister {
+    pub fn new(name: &str, canister_type: FixtureCanisterType) -> Self {
+        Self {
+            name: name.to_string(),
+            canister_type,
+            dependencies: vec![],
+        }
+    }
+
+    pub fn with_dependency(mut self, dependency: &str) -> Self {
+        self.dependencies.push(dependency.to_string());
+        self
+    }
+}
+
+/// Describes a fixture to create with [`ProjectFixtureBuilder::create`].
+pub struct ProjectFixtureBuilder {
+    project_name: String,
+    canisters: Vec<FixtureCanister>,
+    identities: Vec<String>,
+    local_network_port: u16,
+    location: Option<PathBuf>,
+}
+
+impl ProjectFixtureBuilder {
+    pub fn new(project_name: &str) -> Self {
+        Self {
+            project_name: project_name.to_string(),
+            canisters: vec![],
+            identities: vec![],
+            local_network_port: 0,
+            location: None,
+        }
+    }
+
+    pub fn canister(mut self, canister: FixtureCanister) -> Self {
+        self.canisters.push(canister);
+        self
+    }
+
+    /// Adds an identity with a key derived from its name. The first identity added becomes the default identity.
+    /// An identity named `default` is always created.
+    pub fn identity(mut self, name: &str) -> Self {
+        if !self.identities.iter().any(|identity| identity == name) {
+            self.identities.push(name.to_string());
+        }
+        self
+    }
+
+    /// The port the project's local network binds to. By default, a free port is chosen when the network starts.
+    pub fn local_network_port(mut self, port: u16) -> Self {
+        self.local_network_port = port;
+        self
+    }
+
+    /// Creates the fixture in this directory instead of a temporary directory that is removed on drop.
+    pub fn location(mut self, location: &Path) -> Self {
+        self.location = Some(location.to_path_buf());
+        self
+    }
+
+    pub fn create(self) -> Result<ProjectFixture, CreateFixtureError> {
+        for (index, canister) in self.canisters.iter().enumerate() {
+            if self.canisters[..index]
+                .iter()
+                .any(|other| other.name == canister.name)
+            {
+                return Err(DuplicateCanister(canister.name.clone()));
+            }
+            for dependency in &canister.dependencies {
+                if !self.canisters.iter().any(|other| &other.name == dependency) {
+                    return Err(UnknownDependency(canister.name.clone(), dependency.clone()));
+                }
+            }
+        }
+
+        let (root, temp_dir) = match &self.location {
+            Some(location) => {
+                crate::fs::create_dir_all(location)?;
+                (location.clone(), None)
+            }
+            None => {
+                let parent = std::env::temp_dir();
+                let temp_dir = tempfile::Builder::new()
+                    .prefix("dfx-fixture-")
+                    .tempdir_in(&parent)
+                    .map_err(|e| CreateTemporaryDirectoryFailed(parent, e))?;
+                (temp_dir.path().to_path_buf(), Some(temp_dir))
+            }
+        };
+
+        let project_dir = root.join(&self.project_name);
+        self.write_project(&project_dir)?;
+
+        let config_root = root.join("config-root");
+        let mut identities = self.identities.clone();
+        if !identities
+            .iter()
+            .any(|name| name == DEFAULT_FIXTURE_IDENTITY)
+        {
+            identities.push(DEFAULT_FIXTURE_IDENTITY.to_string());
+        }
+        let identities = write_identities(&config_root, &identities)?;
+
+        Ok(ProjectFixture {
+            project_dir,
+            config_root,
+            identities,
+            temp_dir,
+        })
+    }
+
+    fn write_project(&self, project_dir: &Path) -> Result<(), CreateFixtureError> {
+        let mut canisters = Map::new();
+        for canister in &self.canisters {
+            let src_dir = project_dir.join("src").join(&canister.name);
+            let mut definition = match canister.canister_type {
+                FixtureCanisterType::Motoko => {
+                    write_file(
+                        &src_dir.join("main.mo"),
+                        "actor {\n  public query func greet(name : Text) : async Text {\n    return \"Hello, \" # name # \"!\";\n  };\n};\n",
+                    )?;
+                    json!({
+                        "type": "motoko",
+                        "main": format!("src/{}/main.mo", canister.name),
+                    })
+                }
+                FixtureCanisterType::Assets => {
+                    write_file(
+                        &src_dir.join("assets").join("index.html"),
+                        format!("<html><body>{}</body></html>\n", canister.name),
+                    )?;
+                    json!({
+                        "type": "assets",
+                        "source": [format!("src/{}/assets", canister.name)],
+                    })
+                }
+                FixtureCanisterType::Custom => {
+                    write_file(
+                        &src_dir.join(format!("{}.did", canister.name)),
+                        "service : {}\n",
+                    )?;
+                    write_file(
+                        &src_dir.join(format!("{}.wasm", canister.name)),
+                        EMPTY_WASM_MODULE,
+                    )?;
+                    json!({
+                        "type": "custom",
+                        "candid": format!("src/{0}/{0}.did", canister.name),
+                        "wasm": format!("src/{0}/{0}.wasm", canister.name),
+                    })
+                }
+            };
+            if !canister.dependencies.is_empty() {
+                definition["dependencies"] = json!(canister.dependencies);
+            }
+            canisters.insert(canister.name.clone(), definition);
+        }
+
+        let dfx_json = json!({
+            "version": 1,
+            "canisters": Value::Object(canisters),
+            "networks": {
+                "local": {
+                    "bind": format!("127.0.0.1:{}", self.local_network_port),
+                    "type": "ephemeral",
+                },
+            },
+        });
+        crate::fs::create_dir_all(project_dir)?;
+        save_json_file(&project_dir.join("dfx.json"), &dfx_json)?;
+        Ok(())
+    }
+}
+
+/// A project created by [`ProjectFixtureBuilder`].
+/// If it was created in a temporary directory, the directory is removed when the fixture is dropped.
+pub struct ProjectFixture {
+    project_dir: PathBuf,
+    config_root: PathBuf,
+    identities: BTreeMap<String, Principal>,
+    temp_dir: Option<TempDir>,
+}
+
+impl ProjectFixture {
+    pub fn project_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    /// The value for `DFX_CONFIG_ROOT` when running commands against the fixture.
+    pub fn config_root(&self) -> &Path {
+        &self.config_root
+    }
+
+    /// The principals of the fixture's identities, which are the same every time a fixture is created.
+    pub fn identities(&self) -> &BTreeMap<String, Principal> {
+        &self.identities
+    }
+
+    /// Prepares a command that runs in the project directory with the fixture's identities.
+    pub fn command(&self, program: impl AsRef<std::ffi::OsStr>) -> Command {
+        let mut command = Command::new(program);
+        command
+            .current_dir(&self.project_dir)
+            .env("DFX_CONFIG_ROOT", &self.config_root);
+        command
+    }
+
+    /// Keeps a temporary fixture on disk after the fixture is dropped.
+    pub fn keep(mut self) -> Self {
+        if let Some(temp_dir) = self.temp_dir.take() {
+            let _ = temp_dir.into_path();
+        }
+        self
+    }
+}
+
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), CreateFixtureError> {
+    ensure_parent_dir_exists(path)?;
+    crate::fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Writes plaintext identities whose keys are derived from their names, and selects the first one as default.
+fn write_identities(
+    config_root: &Path,
+    names: &[String],
+) -> Result<BTreeMap<String, Principal>, CreateFixtureError> {
+    let config_dir = dfx_config_dir(config_root);
+    let locations = IdentityFileLocations::new(config_dir.join("identity"));
+    let mut principals = BTreeMap::new();
+    for name in names {
+        let pem = deterministic_pem(name)?;
+        write_file(&locations.get_plaintext_identity_pem_path(name), &pem)?;
+        save_json_file(
+            &locations.get_identity_dir_path(name).join(IDENTITY_JSON),
+            &IdentityConfiguration::default(),
+        )?;
+        let principal = Secp256k1Identity::from_pem(pem.as_slice())
+            .map_err(|e| GetIdentityPrincipalFailed(name.clone(), e.to_string()))?
+            .sender()
+            .map_err(|e| GetIdentityPrincipalFailed(name.clone(), e))?;
+        principals.insert(name.clone(), principal);
+    }
+    save_json_file(
+        &config_dir.join("identity.json"),
+        &json!({ "default": names[0] }),
+    )?;
+    Ok(principals)
+}
+
+fn deterministic_pem(name: &str) -> Result<Vec<u8>, CreateFixtureError> {
+    let entropy = ring::digest::digest(
+        &ring::digest::SHA256,
+        format!("dfx fixture identity {name}").as_bytes(),
+    );
+    // 32 bytes of entropy always make a valid 24-word mnemonic.
+    let mnemonic = Mnemonic::from_entropy(entropy.as_ref(), Language::English).unwrap();
+    mnemonic_to_pem(&mnemonic, KeyType::Secp256k1)
+        .map_err(|e| DeriveIdentityKeyFailed(name.to_string(), e))
+}
+
+/// Mirrors how `get_user_dfx_config_dir` interprets `DFX_CONFIG_ROOT`.
+fn dfx_config_dir(config_root: &Path) -> PathBuf {
+    #[cfg(not(windows))]
+    let dir = config_root.join(".config").join("dfx");
+    #[cfg(windows)]
+    let dir = config_root.to_path_buf();
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_deterministic_identities() {
+        let first = ProjectFixtureBuilder::new("first")
+            .identity("alice")
+            .create()
+            .unwrap();
+        let second = ProjectFixtureBuilder::new("second")
+            .identity("alice")
+            .identity("bob")
+            .create()
+            .unwrap();
+        assert_eq!(first.identities()["alice"], second.identities()["alice"]);
+        assert_eq!(
+            first.identities()["default"],
+            second.identities()["default"]
+        );
+        assert_ne!(second.identities()["alice"], second.identities()["bob"]);
+        assert!(dfx_config_dir(first.config_root())
+            .join("identity/alice/identity.pem")
+            .exists());
+    }
+
+    #[test]
+    fn writes_canister_layout() {
+        let fixture = ProjectFixtureBuilder::new("project")
+            .canister(FixtureCanister::new("backend", FixtureCanisterType::Custom))
+            .canister(
+                FixtureCanister::new("frontend", FixtureCanisterType::Assets)
+                    .with_dependency("backend"),
+            )
+            .local_network_port(8123)
+            .create()
+            .unwrap();
+        let dfx_json: Value =
+            crate::json::load_json_file(&fixture.project_dir().join("dfx.json")).unwrap();
+        assert_eq!(
+            dfx_json["canisters"]["frontend"]["dependencies"],
+            json!(["backend"])
+        );
+        assert_eq!(dfx_json["networks"]["local"]["bind"], "127.0.0.1:8123");
+        assert!(fixture
+            .project_dir()
+            .join("src/backend/backend.wasm")
+            .exists());
+    }
+
+    #[test]
+    fn rejects_unknown_dependencies() {
+        let result = ProjectFixtureBuilder::new("project")
+            .canister(
+                FixtureCanister::new("frontend", FixtureCanisterType::Assets)
+                    .with_dependency("backend"),
+            )
+            .create();
+        assert!(matches!(result, Err(UnknownDependency(_, _))));
+    }
+}
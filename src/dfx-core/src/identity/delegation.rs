@@ -0,0 +1,177 @@
+//! Delegation chains, which let an identity make calls as the principal of another key,
+//! e.g. the principal that Internet Identity gives a user for a frontend.
+use crate::error::identity::delegation_chain::DelegationChainError;
+use crate::error::identity::delegation_chain::DelegationChainError::{
+    DelegationExpired, EmptyDelegationChain, InvalidExpiration, InvalidHex, InvalidTarget,
+};
+use candid::Principal;
+use humantime_serde::re::humantime;
+use ic_agent::identity::{Delegation, SignedDelegation};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A delegation chain in the JSON format of `DelegationChain.toJSON()` of the `@dfinity/identity` package,
+/// with hex-encoded keys, signatures, and expiration.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationChain {
+    pub delegations: Vec<JsonSignedDelegation>,
+
+    /// The DER-encoded public key that delegates. The principal of the identity is derived from it.
+    pub public_key: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonSignedDelegation {
+    pub delegation: JsonDelegation,
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonDelegation {
+    pub pubkey: String,
+
+    /// In nanoseconds since the Unix epoch.
+    pub expiration: String,
+
+    /// The canisters the delegation is restricted to, as hex-encoded principals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Vec<String>>,
+}
+
+impl DelegationChain {
+    pub fn public_key(&self) -> Result<Vec<u8>, DelegationChainError> {
+        decode_hex("publicKey", &self.public_key)
+    }
+
+    /// The principal that calls are made with.
+    pub fn principal(&self) -> Result<Principal, DelegationChainError> {
+        Ok(Principal::self_authenticating(self.public_key()?))
+    }
+
+    /// The public key that the last delegation delegates to, whose private key signs the calls.
+    pub fn session_public_key(&self) -> Result<Vec<u8>, DelegationChainError> {
+        let last = self.delegations.last().ok_or(EmptyDelegationChain())?;
+        decode_hex("pubkey", &last.delegation.pubkey)
+    }
+
+    /// When the first of the delegations expires.
+    pub fn expiration(&self) -> Result<SystemTime, DelegationChainError> {
+        let mut expiration = None;
+        for signed in &self.delegations {
+            let nanos = parse_expiration(&signed.delegation.expiration)?;
+            expiration = Some(expiration.map_or(nanos, |e: u64| e.min(nanos)));
+        }
+        let nanos = expiration.ok_or(EmptyDelegationChain())?;
+        Ok(UNIX_EPOCH + Duration::from_nanos(nanos))
+    }
+
+    /// Fails if the delegation chain has expired.
+    pub fn ensure_not_expired(&self) -> Result<(), DelegationChainError> {
+        let expiration = self.expiration()?;
+        if expiration <= SystemTime::now() {
+            return Err(DelegationExpired(
+                humantime::format_rfc3339_seconds(expiration).to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn to_signed_delegations(&self) -> Result<Vec<SignedDelegation>, DelegationChainError> {
+        self.delegations
+            .iter()
+            .map(|signed| {
+                let targets = match &signed.delegation.targets {
+                    Some(targets) => Some(
+                        targets
+                            .iter()
+                            .map(|target| {
+                                let bytes = decode_hex("targets", target)?;
+                                Principal::try_from_slice(&bytes)
+                                    .map_err(|e| InvalidTarget(target.clone(), e))
+                            })
+                            .collect::<Result<Vec<_>, _>>()?,
+                    ),
+                    None => None,
+                };
+                Ok(SignedDelegation {
+                    delegation: Delegation {
+                        pubkey: decode_hex("pubkey", &signed.delegation.pubkey)?,
+                        expiration: parse_expiration(&signed.delegation.expiration)?,
+                        targets,
+                    },
+                    signature: decode_hex("signature", &signed.signature)?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, DelegationChainError> {
+    hex::decode(value).map_err(|e| InvalidHex(field.to_string(), e))
+}
+
+fn parse_expiration(value: &str) -> Result<u64, DelegationChainError> {
+    u64::from_str_radix(value, 16).map_err(|e| InvalidExpiration(value.to_string(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(expirations: &[&str]) -> DelegationChain {
+        DelegationChain {
+            delegations: expirations
+                .iter()
+                .map(|expiration| JsonSignedDelegation {
+                    delegation: JsonDelegation {
+                        pubkey: "abcd".to_string(),
+                        expiration: expiration.to_string(),
+                        targets: None,
+                    },
+                    signature: "00".to_string(),
+                })
+                .collect(),
+            public_key: "0102".to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_the_json_format() {
+        let chain: DelegationChain = serde_json::from_str(
+            r#"{"delegations":[{"delegation":{"pubkey":"abcd","expiration":"17e2b6bd0bd8f200","targets":["00000000000000010101"]},"signature":"0102"}],"publicKey":"0304"}"#,
+        )
+        .unwrap();
+        let delegations = chain.to_signed_delegations().unwrap();
+        assert_eq!(delegations[0].delegation.pubkey, vec![0xab, 0xcd]);
+        assert_eq!(delegations[0].delegation.expiration, 0x17e2b6bd0bd8f200);
+        assert_eq!(
+            delegations[0].delegation.targets,
+            Some(vec![
+                Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap()
+            ])
+        );
+        assert_eq!(chain.session_public_key().unwrap(), vec![0xab, 0xcd]);
+    }
+
+    #[test]
+    fn expires_with_the_first_delegation() {
+        let chain = chain(&["f", "a"]);
+        assert_eq!(
+            chain.expiration().unwrap(),
+            UNIX_EPOCH + Duration::from_nanos(10)
+        );
+        assert!(matches!(
+            chain.ensure_not_expired(),
+            Err(DelegationExpired(_))
+        ));
+        assert!(matches!(
+            DelegationChain {
+                delegations: vec![],
+                public_key: String::new()
+            }
+            .expiration(),
+            Err(EmptyDelegationChain())
+        ));
+    }
+}
@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 
 pub const IDENTITY_PEM: &str = "identity.pem";
 pub const IDENTITY_PEM_ENCRYPTED: &str = "identity.pem.encrypted";
+pub const KEY_BACKUPS_DIR: &str = "key-backups";
 
 #[derive(Clone, Debug)]
 pub(crate) struct IdentityFileLocations {
@@ -42,6 +43,12 @@ impl IdentityFileLocations {
             .join(IDENTITY_PEM_ENCRYPTED)
     }
 
+    /// Determines the directory that holds backups of the identity's previous keys.
+    pub fn get_key_backups_dir_path(&self, identity_name: &str) -> PathBuf {
+        self.get_identity_dir_path(identity_name)
+            .join(KEY_BACKUPS_DIR)
+    }
+
     pub fn get_identity_dir_path(&self, identity: &str) -> PathBuf {
         self.root_dir.join(identity)
     }
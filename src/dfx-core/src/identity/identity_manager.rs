@@ -5,20 +5,21 @@ use crate::error::encryption::EncryptionError;
 use crate::error::encryption::EncryptionError::{NonceGenerationFailed, SaltGenerationFailed};
 use crate::error::fs::FsError;
 use crate::error::identity::convert_mnemonic_to_key::ConvertMnemonicToKeyError;
-use crate::error::identity::convert_mnemonic_to_key::ConvertMnemonicToKeyError::DeriveExtendedKeyFromPathFailed;
+use crate::error::identity::convert_mnemonic_to_key::ConvertMnemonicToKeyError::{
+    ConvertSecretKeyToSec1PemFailed, DeriveEd25519KeyFailed, DeriveExtendedKeyFromPathFailed,
+    EncodeEd25519PemFailed,
+};
 use crate::error::identity::create_identity_config::CreateIdentityConfigError;
 use crate::error::identity::create_identity_config::CreateIdentityConfigError::GenerateFreshEncryptionConfigurationFailed;
 use crate::error::identity::create_new_identity::CreateNewIdentityError;
 use crate::error::identity::create_new_identity::CreateNewIdentityError::{
-    CleanupPreviousCreationAttemptsFailed, ConvertSecretKeyToSec1PemFailed,
-    CreateMnemonicFromPhraseFailed, CreateTemporaryIdentityDirectoryFailed,
-    RenameTemporaryIdentityDirectoryFailed, SwitchBackToIdentityFailed,
-    SwitchToAnonymousIdentityFailed,
+    CleanupPreviousCreationAttemptsFailed, CreateMnemonicFromPhraseFailed,
+    CreateTemporaryIdentityDirectoryFailed, RenameTemporaryIdentityDirectoryFailed,
+    SwitchBackToIdentityFailed, SwitchToAnonymousIdentityFailed,
 };
 use crate::error::identity::export_identity::ExportIdentityError;
 use crate::error::identity::export_identity::ExportIdentityError::TranslatePemContentToTextFailed;
 use crate::error::identity::generate_key::GenerateKeyError;
-use crate::error::identity::generate_key::GenerateKeyError::GenerateFreshSecp256k1KeyFailed;
 use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError;
 use crate::error::identity::get_identity_config_or_default::GetIdentityConfigOrDefaultError::LoadIdentityConfigurationFailed;
 use crate::error::identity::get_legacy_credentials_pem_path::GetLegacyCredentialsPemPathError;
@@ -33,21 +34,38 @@ use crate::error::identity::instantiate_identity_from_name::InstantiateIdentityF
     GetIdentityPrincipalFailed, LoadIdentityFailed,
 };
 use crate::error::identity::load_identity::LoadIdentityError;
+use crate::error::identity::lock_identity::LockIdentityError;
+use crate::error::identity::migrate_identity::MigrateIdentityError;
+use crate::error::identity::migrate_identity::MigrateIdentityError::{
+    GetIdentityNamesFailed, RemovePreviousKeyFailed, RemovePreviousKeyFromKeyringFailed,
+};
 use crate::error::identity::new_identity_manager::NewIdentityManagerError;
 use crate::error::identity::new_identity_manager::NewIdentityManagerError::LoadIdentityManagerConfigurationFailed;
 use crate::error::identity::remove_identity::RemoveIdentityError;
 use crate::error::identity::remove_identity::RemoveIdentityError::{
-    DisplayLinkedWalletsFailed, DropWalletsFlagRequiredToRemoveIdentityWithWallets,
-    RemoveIdentityDirectoryFailed, RemoveIdentityFileFailed,
+    DisplayLinkedWalletsFailed, DropKeyBackupsFlagRequiredToRemoveIdentityWithKeyBackups,
+    DropWalletsFlagRequiredToRemoveIdentityWithWallets, RemoveIdentityDirectoryFailed,
+    RemoveIdentityFileFailed,
 };
 use crate::error::identity::rename_identity::RenameIdentityError;
 use crate::error::identity::rename_identity::RenameIdentityError::{
     GetIdentityConfigFailed, LoadPemFailed, MapWalletsToRenamedIdentityFailed,
     RenameIdentityDirectoryFailed, SavePemFailed, SwitchDefaultIdentitySettingsFailed,
 };
+use crate::error::identity::replace_key_file::ReplaceKeyFileError;
+use crate::error::identity::replace_key_file::ReplaceKeyFileError::{
+    SwapConfigurationFailed, SwapKeyFailed, WriteNewKeyFailed,
+};
 use crate::error::identity::require_identity_exists::RequireIdentityExistsError;
+use crate::error::identity::require_network_allowed::RequireNetworkAllowedError;
+use crate::error::identity::rotate_key::RotateKeyError;
+use crate::error::identity::rotate_key::RotateKeyError::{
+    BackupKeyFailed, GetNewPrincipalFailed, LoadNewKeyFailed, SaveBackupConfigurationFailed,
+};
 use crate::error::identity::save_identity_configuration::SaveIdentityConfigurationError;
 use crate::error::identity::save_identity_configuration::SaveIdentityConfigurationError::EnsureIdentityConfigurationDirExistsFailed;
+use crate::error::identity::set_allowed_networks::SetAllowedNetworksError;
+use crate::error::identity::unlock_identity::UnlockIdentityError;
 use crate::error::identity::use_identity_by_name::UseIdentityByNameError;
 use crate::error::identity::use_identity_by_name::UseIdentityByNameError::WriteDefaultIdentityFailed;
 use crate::error::identity::write_default_identity::WriteDefaultIdentityError;
@@ -55,8 +73,12 @@ use crate::error::identity::write_default_identity::WriteDefaultIdentityError::S
 use crate::error::structured_file::StructuredFileError;
 use crate::foundation::get_user_home;
 use crate::fs::composite::ensure_parent_dir_exists;
-use crate::identity::identity_file_locations::{IdentityFileLocations, IDENTITY_PEM};
+use crate::identity::delegation::DelegationChain;
+use crate::identity::identity_file_locations::{
+    IdentityFileLocations, IDENTITY_PEM, IDENTITY_PEM_ENCRYPTED,
+};
 use crate::identity::identity_manager::IdentityStorageModeError::UnknownStorageMode;
+use crate::identity::identity_manager::KeyTypeError::UnknownKeyType;
 use crate::identity::{
     pem_safekeeping, pem_utils, Identity as DfxIdentity, ANONYMOUS_IDENTITY_NAME, IDENTITY_JSON,
     TEMP_IDENTITY_PREFIX,
@@ -67,14 +89,17 @@ use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use candid::Principal;
 use k256::pkcs8::LineEnding;
 use k256::SecretKey;
-use ring::{rand, rand::SecureRandom};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use ring::{hmac, rand, rand::SecureRandom};
 use sec1::EncodeEcPrivateKey;
 use serde::{Deserialize, Serialize};
-use slog::{debug, trace, Logger};
+use slog::{debug, trace, warn, Logger};
 use std::boxed::Box;
 use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::SystemTime;
 use thiserror::Error;
 
 const DEFAULT_IDENTITY_NAME: &str = "default";
@@ -83,6 +108,11 @@ const DEFAULT_IDENTITY_NAME: &str = "default";
 struct Configuration {
     #[serde(default = "default_identity")]
     pub default: String,
+
+    /// Whether identities stored in a legacy format were already looked for.
+    /// This is done once, so that users upgrading from older versions learn about `dfx identity migrate`.
+    #[serde(default)]
+    pub legacy_identities_checked: bool,
 }
 
 fn default_identity() -> String {
@@ -98,6 +128,29 @@ pub struct IdentityConfiguration {
 
     /// If the identity's PEM file is stored in the system's keyring, this field contains the identity's name WITHOUT the common prefix.
     pub keyring_identity_suffix: Option<String>,
+
+    /// If set, the identity can only be used to talk to these networks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_networks: Option<Vec<String>>,
+
+    /// If set, the identity's key is a session key, and calls are made as the principal that delegated to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegation: Option<DelegationChain>,
+}
+
+impl IdentityConfiguration {
+    /// The storage mode of the identity's key, or None for hardware identities.
+    pub fn storage_mode(&self) -> Option<IdentityStorageMode> {
+        if self.hsm.is_some() {
+            None
+        } else if self.keyring_identity_suffix.is_some() {
+            Some(IdentityStorageMode::Keyring)
+        } else if self.encryption.is_some() {
+            Some(IdentityStorageMode::PasswordProtected)
+        } else {
+            Some(IdentityStorageMode::Plaintext)
+        }
+    }
 }
 
 /// The information necessary to de- and encrypt (except the password) the identity's .pem file
@@ -108,6 +161,34 @@ pub struct EncryptionConfiguration {
 
     /// 96 bit Nonce used to decrypt the file
     pub file_nonce: Vec<u8>,
+
+    /// Parameters used for deriving the key from the password.
+    /// Absent for files that were encrypted before the parameters were recorded, which use the legacy ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_derivation: Option<KeyDerivationParameters>,
+}
+
+/// The cost parameters of the Argon2id key derivation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyDerivationParameters {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl KeyDerivationParameters {
+    /// The parameters of files that do not record any.
+    pub const LEGACY: Self = Self {
+        memory_kib: 64000,
+        iterations: 3,
+        parallelism: 1,
+    };
+
+    /// The parameters that new files are encrypted with.
+    /// Older versions of dfx ignore the recorded parameters and always use the legacy ones,
+    /// so changing these requires a new identity configuration format that older versions reject.
+    pub const CURRENT: Self = Self::LEGACY;
 }
 
 impl EncryptionConfiguration {
@@ -125,8 +206,19 @@ impl EncryptionConfiguration {
         Ok(Self {
             pw_salt,
             file_nonce,
+            key_derivation: Some(KeyDerivationParameters::CURRENT),
         })
     }
+
+    pub fn key_derivation_parameters(&self) -> KeyDerivationParameters {
+        self.key_derivation
+            .unwrap_or(KeyDerivationParameters::LEGACY)
+    }
+
+    /// Whether the file was encrypted with other parameters than new files are, and should be encrypted again.
+    pub fn has_outdated_parameters(&self) -> bool {
+        self.key_derivation_parameters() != KeyDerivationParameters::CURRENT
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -177,9 +269,51 @@ impl Default for IdentityStorageMode {
     }
 }
 
+impl Display for IdentityStorageMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdentityStorageMode::Keyring => write!(f, "keyring"),
+            IdentityStorageMode::PasswordProtected => write!(f, "password-protected"),
+            IdentityStorageMode::Plaintext => write!(f, "plaintext"),
+        }
+    }
+}
+
+/// The elliptic curve of a generated or seed-phrase-derived key.
+#[derive(Clone, Debug, Serialize, Deserialize, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    Secp256k1,
+    Ed25519,
+}
+
+#[derive(Error, Debug)]
+pub enum KeyTypeError {
+    #[error("Unknown key type: {0}")]
+    UnknownKeyType(String),
+}
+
+impl FromStr for KeyType {
+    type Err = KeyTypeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "secp256k1" => Ok(KeyType::Secp256k1),
+            "ed25519" => Ok(KeyType::Ed25519),
+            other => Err(UnknownKeyType(other.to_string())),
+        }
+    }
+}
+
+impl Default for KeyType {
+    fn default() -> Self {
+        Self::Secp256k1
+    }
+}
+
 pub enum IdentityCreationParameters {
     Pem {
         mode: IdentityStorageMode,
+        key_type: KeyType,
     },
     PemFile {
         src_pem_file: PathBuf,
@@ -188,10 +322,31 @@ pub enum IdentityCreationParameters {
     SeedPhrase {
         mnemonic: String,
         mode: IdentityStorageMode,
+        key_type: KeyType,
     },
     Hardware {
         hsm: HardwareIdentityConfiguration,
     },
+    Delegation {
+        session_key_pem: Vec<u8>,
+        chain: DelegationChain,
+        mode: IdentityStorageMode,
+    },
+}
+
+/// An identity whose key is not stored in the preferred storage mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdentityMigration {
+    pub name: String,
+    pub from: IdentityStorageMode,
+    pub to: IdentityStorageMode,
+}
+
+/// A freshly generated key that is about to replace the key of an identity.
+pub struct KeyRotation {
+    pub pem: Vec<u8>,
+    pub mnemonic: Mnemonic,
+    pub principal: Principal,
 }
 
 #[derive(Clone, Debug)]
@@ -213,7 +368,7 @@ impl IdentityManager {
         let identity_root_path = config_dfx_dir_path.join("identity");
         let identity_json_path = config_dfx_dir_path.join("identity.json");
 
-        let configuration = if identity_json_path.exists() {
+        let mut configuration = if identity_json_path.exists() {
             load_configuration(&identity_json_path)
                 .map_err(LoadIdentityManagerConfigurationFailed)?
         } else {
@@ -226,6 +381,9 @@ impl IdentityManager {
             .unwrap_or_else(|| configuration.default.clone());
         let file_locations = IdentityFileLocations::new(identity_root_path);
 
+        let check_legacy_identities = !configuration.legacy_identities_checked;
+        configuration.legacy_identities_checked = true;
+
         let mgr = IdentityManager {
             identity_json_path,
             file_locations,
@@ -234,6 +392,10 @@ impl IdentityManager {
             selected_identity_principal: None,
         };
 
+        if check_legacy_identities {
+            mgr.report_legacy_identities(logger);
+        }
+
         if let Some(identity) = identity_override {
             mgr.require_identity_exists(logger, identity)
                 .map_err(NewIdentityManagerError::OverrideIdentityMustExist)?;
@@ -246,6 +408,11 @@ impl IdentityManager {
         self.selected_identity_principal
     }
 
+    /// Records the principal of an identity that was instantiated earlier, when it is used again.
+    pub fn set_selected_identity_principal(&mut self, principal: Principal) {
+        self.selected_identity_principal = Some(principal);
+    }
+
     /// Create an Identity instance for use with an Agent
     pub fn instantiate_selected_identity(
         &mut self,
@@ -312,54 +479,13 @@ impl IdentityManager {
                     self.use_identity_named(log, ANONYMOUS_IDENTITY_NAME)
                         .map_err(SwitchToAnonymousIdentityFailed)?;
                 }
-                self.remove(log, name, true, None)
+                self.remove(log, name, true, true, None)
                     .map_err(CreateNewIdentityError::RemoveIdentityFailed)?;
             } else {
                 return Err(CreateNewIdentityError::IdentityAlreadyExists());
             }
         }
 
-        fn create_identity_config(
-            log: &Logger,
-            mode: IdentityStorageMode,
-            name: &str,
-            hardware_config: Option<HardwareIdentityConfiguration>,
-        ) -> Result<IdentityConfiguration, CreateIdentityConfigError> {
-            if let Some(hsm) = hardware_config {
-                Ok(IdentityConfiguration {
-                    hsm: Some(hsm),
-                    ..Default::default()
-                })
-            } else {
-                match mode {
-                    IdentityStorageMode::Keyring => {
-                        if keyring_mock::keyring_available(log) {
-                            Ok(IdentityConfiguration {
-                                keyring_identity_suffix: Some(String::from(name)),
-                                ..Default::default()
-                            })
-                        } else {
-                            Ok(IdentityConfiguration {
-                                encryption: Some(
-                                    EncryptionConfiguration::new()
-                                        .map_err(GenerateFreshEncryptionConfigurationFailed)?,
-                                ),
-                                ..Default::default()
-                            })
-                        }
-                    }
-                    IdentityStorageMode::PasswordProtected => Ok(IdentityConfiguration {
-                        encryption: Some(
-                            EncryptionConfiguration::new()
-                                .map_err(GenerateFreshEncryptionConfigurationFailed)?,
-                        ),
-                        ..Default::default()
-                    }),
-                    IdentityStorageMode::Plaintext => Ok(IdentityConfiguration::default()),
-                }
-            }
-        }
-
         // Use a temporary directory to prepare all identity parts in so that we don't end up with broken parts if the
         // creation process fails half-way through.
         let temp_identity_name = format!("{}{}", TEMP_IDENTITY_PREFIX, name);
@@ -372,9 +498,9 @@ impl IdentityManager {
 
         let identity_config;
         match parameters {
-            IdentityCreationParameters::Pem { mode } => {
+            IdentityCreationParameters::Pem { mode, key_type } => {
                 let (pem_content, mnemonic) =
-                    generate_key().map_err(CreateNewIdentityError::GenerateKeyFailed)?;
+                    generate_key(key_type).map_err(CreateNewIdentityError::GenerateKeyFailed)?;
                 identity_config = create_identity_config(log, mode, name, None)
                     .map_err(CreateNewIdentityError::CreateIdentityConfigFailed)?;
                 pem_safekeeping::save_pem(
@@ -410,23 +536,57 @@ impl IdentityManager {
                 crate::fs::create_dir_all(&temp_identity_dir)
                     .map_err(CreateTemporaryIdentityDirectoryFailed)?;
             }
-            IdentityCreationParameters::SeedPhrase { mnemonic, mode } => {
+            IdentityCreationParameters::SeedPhrase {
+                mnemonic,
+                mode,
+                key_type,
+            } => {
                 identity_config = create_identity_config(log, mode, name, None)
                     .map_err(CreateNewIdentityError::CreateIdentityConfigFailed)?;
                 let mnemonic = Mnemonic::from_phrase(&mnemonic, Language::English)
                     .map_err(|e| CreateMnemonicFromPhraseFailed(format!("{}", e)))?;
-                let key = mnemonic_to_key(&mnemonic)
+                let pem_content = mnemonic_to_pem(&mnemonic, key_type)
                     .map_err(CreateNewIdentityError::ConvertMnemonicToKeyFailed)?;
-                let pem = key
-                    .to_sec1_pem(k256::pkcs8::LineEnding::CRLF)
-                    .map_err(|e| ConvertSecretKeyToSec1PemFailed(Box::new(e)))?;
-                let pem_content = pem.as_bytes();
                 pem_safekeeping::save_pem(
                     log,
                     self.file_locations(),
                     &temp_identity_name,
                     &identity_config,
-                    pem_content,
+                    pem_content.as_slice(),
+                )
+                .map_err(CreateNewIdentityError::SavePemFailed)?;
+            }
+            IdentityCreationParameters::Delegation {
+                session_key_pem,
+                chain,
+                mode,
+            } => {
+                let session_public_key = pem_utils::pem_public_key(&session_key_pem)
+                    .map_err(CreateNewIdentityError::ValidatePemFileFailed)?;
+                if chain
+                    .session_public_key()
+                    .map_err(CreateNewIdentityError::InvalidDelegationChain)?
+                    != session_public_key
+                {
+                    return Err(CreateNewIdentityError::DelegationSessionKeyMismatch());
+                }
+                chain
+                    .ensure_not_expired()
+                    .map_err(CreateNewIdentityError::InvalidDelegationChain)?;
+                chain
+                    .to_signed_delegations()
+                    .map_err(CreateNewIdentityError::InvalidDelegationChain)?;
+                identity_config = IdentityConfiguration {
+                    delegation: Some(chain),
+                    ..create_identity_config(log, mode, name, None)
+                        .map_err(CreateNewIdentityError::CreateIdentityConfigFailed)?
+                };
+                pem_safekeeping::save_pem(
+                    log,
+                    self.file_locations(),
+                    &temp_identity_name,
+                    &identity_config,
+                    session_key_pem.as_slice(),
                 )
                 .map_err(CreateNewIdentityError::SavePemFailed)?;
             }
@@ -493,12 +653,15 @@ impl IdentityManager {
     /// Remove a named identity.
     /// Removing the selected identity is not allowed.
     /// Removing an identity that is connected to non-ephemeral wallets is only allowed if drop_wallets is true.
-    /// If display_linked_wallets_to contains a logger, this will log all the wallets the identity is connected to.
+    /// Removing an identity with backups of its previous keys is only allowed if drop_key_backups is true.
+    /// If display_linked_wallets_to contains a logger, this will log all the wallets the identity is connected to,
+    /// and the key backups that are removed with it.
     pub fn remove(
         &self,
         log: &Logger,
         name: &str,
         drop_wallets: bool,
+        drop_key_backups: bool,
         display_linked_wallets_to: Option<&Logger>,
     ) -> Result<(), RemoveIdentityError> {
         self.require_identity_exists(log, name)
@@ -512,6 +675,20 @@ impl IdentityManager {
             return Err(RemoveIdentityError::CannotDeleteDefaultIdentity());
         }
 
+        let key_backups_dir = self.file_locations.get_key_backups_dir_path(name);
+        if key_backups_dir.exists() {
+            if let Some(logger) = display_linked_wallets_to {
+                info!(
+                    logger,
+                    "This identity has backups of its previous keys in {}.",
+                    key_backups_dir.display()
+                );
+            }
+            if !drop_key_backups {
+                return Err(DropKeyBackupsFlagRequiredToRemoveIdentityWithKeyBackups());
+            }
+        }
+
         let wallet_config_file = self.get_persistent_wallet_config_file(name);
         if wallet_config_file.exists() {
             if let Some(logger) = display_linked_wallets_to {
@@ -535,6 +712,10 @@ impl IdentityManager {
         remove_identity_file(&self.file_locations.get_plaintext_identity_pem_path(name))?;
         remove_identity_file(&self.file_locations.get_encrypted_identity_pem_path(name))?;
 
+        if key_backups_dir.exists() {
+            crate::fs::remove_dir_all(&key_backups_dir).map_err(RemoveIdentityDirectoryFailed)?;
+        }
+
         let dir = self.get_identity_dir_path(name);
         if dir.exists() {
             crate::fs::remove_dir(&dir).map_err(RemoveIdentityDirectoryFailed)?;
@@ -599,6 +780,310 @@ impl IdentityManager {
         }
     }
 
+    /// Generates a new key for an identity without storing it.
+    /// The identity keeps using its current key until the rotation is completed with `rotate_key`.
+    pub fn prepare_key_rotation(
+        &self,
+        log: &Logger,
+        name: &str,
+        key_type: KeyType,
+    ) -> Result<KeyRotation, RotateKeyError> {
+        if name == ANONYMOUS_IDENTITY_NAME {
+            return Err(RotateKeyError::CannotRotateAnonymousIdentity());
+        }
+        self.require_identity_exists(log, name)
+            .map_err(RotateKeyError::IdentityDoesNotExist)?;
+        let config = self
+            .get_identity_config_or_default(name)
+            .map_err(RotateKeyError::GetIdentityConfigFailed)?;
+        if config.hsm.is_some() {
+            return Err(RotateKeyError::CannotRotateHardwareIdentity());
+        }
+        if config.delegation.is_some() {
+            return Err(RotateKeyError::CannotRotateDelegatedIdentity());
+        }
+
+        let (pem, mnemonic) = generate_key(key_type).map_err(RotateKeyError::GenerateKeyFailed)?;
+        let principal = {
+            use ic_agent::Identity;
+            let identity = match key_type {
+                KeyType::Secp256k1 => DfxIdentity::secp256k1(name, &pem, false),
+                KeyType::Ed25519 => DfxIdentity::basic(name, &pem, false),
+            }
+            .map_err(LoadNewKeyFailed)?;
+            identity.sender().map_err(GetNewPrincipalFailed)?
+        };
+        Ok(KeyRotation {
+            pem,
+            mnemonic,
+            principal,
+        })
+    }
+
+    /// Replaces the key of an identity with the one generated by `prepare_key_rotation`.
+    /// The previous key is first backed up, encrypted with a passphrase the user is prompted for.
+    /// The backup directory has the same layout as an identity directory.
+    ///
+    /// Returns the path of the backup directory.
+    pub fn rotate_key(
+        &self,
+        log: &Logger,
+        name: &str,
+        rotation: &KeyRotation,
+    ) -> Result<PathBuf, RotateKeyError> {
+        let config = self
+            .get_identity_config_or_default(name)
+            .map_err(RotateKeyError::GetIdentityConfigFailed)?;
+        if config.hsm.is_some() {
+            return Err(RotateKeyError::CannotRotateHardwareIdentity());
+        }
+        if config.delegation.is_some() {
+            return Err(RotateKeyError::CannotRotateDelegatedIdentity());
+        }
+        let (previous_pem, _) = pem_safekeeping::load_pem(log, &self.file_locations, name, &config)
+            .map_err(RotateKeyError::LoadPemFailed)?;
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let backup_dir = self
+            .file_locations
+            .get_key_backups_dir_path(name)
+            .join(timestamp.to_string());
+        let backup_config = IdentityConfiguration {
+            encryption: Some(
+                EncryptionConfiguration::new()
+                    .map_err(RotateKeyError::GenerateFreshEncryptionConfigurationFailed)?,
+            ),
+            ..Default::default()
+        };
+        debug!(log, "Backing up previous key to {}.", backup_dir.display());
+        pem_safekeeping::write_pem_to_file(
+            &backup_dir.join(IDENTITY_PEM_ENCRYPTED),
+            Some(&backup_config),
+            &previous_pem,
+        )
+        .map_err(BackupKeyFailed)?;
+        save_identity_configuration(log, &backup_dir.join(IDENTITY_JSON), &backup_config)
+            .map_err(SaveBackupConfigurationFailed)?;
+
+        if config.keyring_identity_suffix.is_some() {
+            pem_safekeeping::save_pem(log, &self.file_locations, name, &config, &rotation.pem)
+                .map_err(RotateKeyError::SavePemFailed)?;
+        } else {
+            // Never reuse the nonce of the previous key.
+            let new_config = if config.encryption.is_some() {
+                IdentityConfiguration {
+                    encryption: Some(
+                        EncryptionConfiguration::new()
+                            .map_err(RotateKeyError::GenerateFreshEncryptionConfigurationFailed)?,
+                    ),
+                    ..config.clone()
+                }
+            } else {
+                config.clone()
+            };
+            self.replace_key_file(log, name, &config, &new_config, &rotation.pem)
+                .map_err(RotateKeyError::ReplaceKeyFailed)?;
+        }
+
+        Ok(backup_dir)
+    }
+
+    /// Replaces the key file of an identity and its configuration.
+    /// Both are written next to the old ones first, so that a failure cannot leave the identity
+    /// with a key that its configuration does not decrypt.
+    fn replace_key_file(
+        &self,
+        log: &Logger,
+        name: &str,
+        previous_config: &IdentityConfiguration,
+        new_config: &IdentityConfiguration,
+        pem: &[u8],
+    ) -> Result<(), ReplaceKeyFileError> {
+        let identity_dir = self.get_identity_dir_path(name);
+        let temp_path = |path: &Path| {
+            identity_dir.join(format!(
+                "{}{}",
+                TEMP_IDENTITY_PREFIX,
+                path.file_name().unwrap().to_string_lossy()
+            ))
+        };
+        let pem_path = self.file_locations.get_identity_pem_path(name, new_config);
+        let temp_pem_path = temp_path(&pem_path);
+        let json_path = self.get_identity_json_path(name);
+        let temp_json_path = temp_path(&json_path);
+        remove_identity_file(&temp_pem_path).ok();
+        remove_identity_file(&temp_json_path).ok();
+        pem_safekeeping::write_pem_to_file(&temp_pem_path, Some(new_config), pem)
+            .map_err(WriteNewKeyFailed)?;
+        if new_config.encryption.is_some() {
+            if let Err(err) = save_identity_configuration(log, &temp_json_path, new_config) {
+                remove_identity_file(&temp_pem_path).ok();
+                return Err(ReplaceKeyFileError::SaveIdentityConfigurationFailed(err));
+            }
+            if let Err(err) = crate::fs::rename(&temp_json_path, &json_path) {
+                remove_identity_file(&temp_pem_path).ok();
+                remove_identity_file(&temp_json_path).ok();
+                return Err(SwapConfigurationFailed(err));
+            }
+        }
+        if let Err(err) = crate::fs::rename(&temp_pem_path, &pem_path) {
+            // Put back the configuration that decrypts the previous key.
+            if new_config.encryption.is_some() {
+                save_identity_configuration(log, &json_path, previous_config).ok();
+            }
+            remove_identity_file(&temp_pem_path).ok();
+            return Err(SwapKeyFailed(err));
+        }
+        Ok(())
+    }
+
+    /// Lists the identities whose keys are stored in plaintext, to move them to the preferred storage mode,
+    /// and the password-protected identities whose key derivation parameters are outdated, to encrypt them again.
+    ///
+    /// If the keyring is preferred but not available, password protection is preferred instead.
+    /// Identities that are password-protected with current parameters, stored in the keyring,
+    /// or stored on hardware were chosen deliberately and are never listed.
+    pub fn find_identities_to_migrate(
+        &self,
+        log: &Logger,
+        preferred_mode: IdentityStorageMode,
+    ) -> Result<Vec<IdentityMigration>, MigrateIdentityError> {
+        let target = match preferred_mode {
+            IdentityStorageMode::Plaintext => {
+                return Err(MigrateIdentityError::CannotMigrateToPlaintext())
+            }
+            IdentityStorageMode::Keyring if !keyring_mock::keyring_available(log) => {
+                IdentityStorageMode::PasswordProtected
+            }
+            mode => mode,
+        };
+
+        let mut migrations = vec![];
+        for name in self
+            .get_identity_names(log)
+            .map_err(GetIdentityNamesFailed)?
+        {
+            if name == ANONYMOUS_IDENTITY_NAME {
+                continue;
+            }
+            let config = self
+                .get_identity_config_or_default(&name)
+                .map_err(MigrateIdentityError::GetIdentityConfigFailed)?;
+            let Some(from) = config.storage_mode() else {
+                continue;
+            };
+            let to = match from {
+                IdentityStorageMode::Plaintext => target,
+                IdentityStorageMode::PasswordProtected
+                    if config
+                        .encryption
+                        .as_ref()
+                        .map_or(false, EncryptionConfiguration::has_outdated_parameters) =>
+                {
+                    IdentityStorageMode::PasswordProtected
+                }
+                _ => continue,
+            };
+            migrations.push(IdentityMigration { name, from, to });
+        }
+        Ok(migrations)
+    }
+
+    /// Moves the key of an identity to another storage mode, or encrypts it again with the current
+    /// key derivation parameters if it stays password-protected.
+    /// The key is stored in its new location before it is removed from the previous one.
+    pub fn migrate_identity(
+        &self,
+        log: &Logger,
+        migration: &IdentityMigration,
+    ) -> Result<(), MigrateIdentityError> {
+        let name = migration.name.as_str();
+        let config = self
+            .get_identity_config_or_default(name)
+            .map_err(MigrateIdentityError::GetIdentityConfigFailed)?;
+        let (pem, _) = pem_safekeeping::load_pem(log, &self.file_locations, name, &config)
+            .map_err(MigrateIdentityError::LoadPemFailed)?;
+
+        if let Some(encryption) = config
+            .encryption
+            .as_ref()
+            .filter(|_| migration.to == IdentityStorageMode::PasswordProtected)
+        {
+            debug!(log, "Encrypting key of identity '{name}' again.");
+            let new_encryption = EncryptionConfiguration::new()
+                .map_err(MigrateIdentityError::GenerateFreshEncryptionConfigurationFailed)?;
+            let new_config = IdentityConfiguration {
+                encryption: Some(new_encryption),
+                ..config.clone()
+            };
+            self.replace_key_file(log, name, &config, &new_config, &pem)
+                .map_err(MigrateIdentityError::ReplaceKeyFailed)?;
+            // The key of an unlocked identity was derived from the previous parameters.
+            pem_safekeeping::lock_pem_file(encryption).ok();
+            return Ok(());
+        }
+
+        let new_config = IdentityConfiguration {
+            allowed_networks: config.allowed_networks.clone(),
+            delegation: config.delegation.clone(),
+            ..create_identity_config(log, migration.to, name, None)
+                .map_err(MigrateIdentityError::CreateIdentityConfigFailed)?
+        };
+        if new_config.keyring_identity_suffix.is_none() {
+            // A leftover file from an earlier migration would be read-only.
+            let new_pem_path = self.file_locations.get_identity_pem_path(name, &new_config);
+            if new_pem_path.exists() {
+                crate::fs::remove_file(&new_pem_path).map_err(RemovePreviousKeyFailed)?;
+            }
+        }
+        debug!(
+            log,
+            "Moving key of identity '{name}' from {} to {} storage.", migration.from, migration.to
+        );
+        pem_safekeeping::save_pem(log, &self.file_locations, name, &new_config, &pem)
+            .map_err(MigrateIdentityError::SavePemFailed)?;
+        save_identity_configuration(log, &self.get_identity_json_path(name), &new_config)
+            .map_err(MigrateIdentityError::SaveIdentityConfigurationFailed)?;
+
+        if let Some(suffix) = &config.keyring_identity_suffix {
+            keyring_mock::delete_pem_from_keyring(suffix)
+                .map_err(RemovePreviousKeyFromKeyringFailed)?;
+        } else {
+            crate::fs::remove_file(&self.file_locations.get_identity_pem_path(name, &config))
+                .map_err(RemovePreviousKeyFailed)?;
+        }
+        Ok(())
+    }
+
+    /// Tells the user about identities with plaintext keys, which `dfx identity migrate` can move to the keyring.
+    /// Failing to look for them does not keep dfx from working.
+    fn report_legacy_identities(&self, log: &Logger) {
+        let plaintext_identities = self
+            .get_identity_names(log)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|name| name != ANONYMOUS_IDENTITY_NAME)
+            .filter(|name| {
+                self.get_identity_config_or_default(name)
+                    .map(|config| config.storage_mode() == Some(IdentityStorageMode::Plaintext))
+                    .unwrap_or(false)
+            })
+            .collect::<Vec<_>>();
+        if !plaintext_identities.is_empty() {
+            warn!(
+                log,
+                "The following identities store their keys in plaintext: {}. Run 'dfx identity migrate --dry-run' to see how they can be stored more securely.",
+                plaintext_identities.join(", ")
+            );
+        }
+        if let Err(err) = save_configuration(&self.identity_json_path, &self.configuration) {
+            debug!(log, "Failed to save identity manager configuration: {err}");
+        }
+    }
+
     /// Select an identity by name to use by default
     pub fn use_identity_named(
         &mut self,
@@ -616,6 +1101,7 @@ impl IdentityManager {
     fn write_default_identity(&self, name: &str) -> Result<(), WriteDefaultIdentityError> {
         let config = Configuration {
             default: String::from(name),
+            ..self.configuration.clone()
         };
         save_configuration(&self.identity_json_path, &config)
             .map_err(SaveIdentityManagerConfigurationFailed)?;
@@ -682,6 +1168,7 @@ impl IdentityManager {
                         encryption: None,
                         keyring_identity_suffix: None,
                         hsm: None,
+                        ..
                     } = config
                     {
                         let sender = self.load_identity(name, log).ok()?.sender().ok()?;
@@ -701,6 +1188,85 @@ impl IdentityManager {
         res
     }
 
+    /// Fails if the identity's configuration does not allow it to be used on the network.
+    pub fn require_network_allowed(
+        &self,
+        identity: &str,
+        network: &str,
+    ) -> Result<(), RequireNetworkAllowedError> {
+        if identity == ANONYMOUS_IDENTITY_NAME {
+            return Ok(());
+        }
+        let config = self
+            .get_identity_config_or_default(identity)
+            .map_err(RequireNetworkAllowedError::GetIdentityConfigOrDefaultFailed)?;
+        match config.allowed_networks {
+            Some(allowed) if !allowed.iter().any(|n| n == network) => {
+                Err(RequireNetworkAllowedError::NetworkNotAllowed {
+                    identity: identity.to_string(),
+                    network: network.to_string(),
+                    allowed: allowed.join(", "),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Restricts the networks the identity can be used on. `None` lifts the restriction.
+    pub fn set_allowed_networks(
+        &self,
+        log: &Logger,
+        identity: &str,
+        networks: Option<Vec<String>>,
+    ) -> Result<(), SetAllowedNetworksError> {
+        self.require_identity_exists(log, identity)
+            .map_err(SetAllowedNetworksError::RequireIdentityExistsFailed)?;
+        let config = self
+            .get_identity_config_or_default(identity)
+            .map_err(SetAllowedNetworksError::GetIdentityConfigOrDefaultFailed)?;
+        let config = IdentityConfiguration {
+            allowed_networks: networks,
+            ..config
+        };
+        save_identity_configuration(log, &self.get_identity_json_path(identity), &config)
+            .map_err(SetAllowedNetworksError::SaveIdentityConfigurationFailed)
+    }
+
+    /// Lets a password-protected identity be used without a password prompt until `expires_at`.
+    pub fn unlock(
+        &self,
+        log: &Logger,
+        identity: &str,
+        expires_at: SystemTime,
+    ) -> Result<(), UnlockIdentityError> {
+        self.require_identity_exists(log, identity)
+            .map_err(UnlockIdentityError::IdentityDoesNotExist)?;
+        let config = self
+            .get_identity_config_or_default(identity)
+            .map_err(UnlockIdentityError::GetIdentityConfigFailed)?;
+        let encryption = config
+            .encryption
+            .as_ref()
+            .ok_or_else(|| UnlockIdentityError::NotPasswordProtected(identity.to_string()))?;
+        let pem_path = self.file_locations.get_identity_pem_path(identity, &config);
+        pem_safekeeping::unlock_pem_file(&pem_path, encryption, expires_at)
+    }
+
+    /// Requires the password of a password-protected identity again, before its unlock expires.
+    pub fn lock(&self, log: &Logger, identity: &str) -> Result<(), LockIdentityError> {
+        self.require_identity_exists(log, identity)
+            .map_err(LockIdentityError::IdentityDoesNotExist)?;
+        let config = self
+            .get_identity_config_or_default(identity)
+            .map_err(LockIdentityError::GetIdentityConfigFailed)?;
+        let encryption = config
+            .encryption
+            .as_ref()
+            .ok_or_else(|| LockIdentityError::NotPasswordProtected(identity.to_string()))?;
+        pem_safekeeping::lock_pem_file(encryption)
+            .map_err(LockIdentityError::DeleteSessionKeyFailed)
+    }
+
     pub fn get_identity_config_or_default(
         &self,
         identity: &str,
@@ -764,7 +1330,7 @@ To create a more secure identity, create and use an identity that is protected b
                 "  - generating new key at {}",
                 identity_pem_path.display()
             );
-            let (key, mnemonic) = generate_key().map_err(GenerateKeyFailed)?;
+            let (key, mnemonic) = generate_key(KeyType::default()).map_err(GenerateKeyFailed)?;
             pem_safekeeping::write_pem_to_file(&identity_pem_path, None, key.as_slice())
                 .map_err(WritePemToFileFailed)?;
             eprintln!("Your seed phrase: {}\nThis can be used to reconstruct your key in case of emergency, so write it down in a safe place.", mnemonic.phrase());
@@ -779,6 +1345,7 @@ To create a more secure identity, create and use an identity that is protected b
 
     let config = Configuration {
         default: String::from(DEFAULT_IDENTITY_NAME),
+        legacy_identities_checked: true,
     };
     save_configuration(identity_json_path, &config)
         .map_err(InitializeIdentityManagerError::SaveConfigurationFailed)?;
@@ -787,6 +1354,47 @@ To create a more secure identity, create and use an identity that is protected b
     Ok(config)
 }
 
+fn create_identity_config(
+    log: &Logger,
+    mode: IdentityStorageMode,
+    name: &str,
+    hardware_config: Option<HardwareIdentityConfiguration>,
+) -> Result<IdentityConfiguration, CreateIdentityConfigError> {
+    if let Some(hsm) = hardware_config {
+        Ok(IdentityConfiguration {
+            hsm: Some(hsm),
+            ..Default::default()
+        })
+    } else {
+        match mode {
+            IdentityStorageMode::Keyring => {
+                if keyring_mock::keyring_available(log) {
+                    Ok(IdentityConfiguration {
+                        keyring_identity_suffix: Some(String::from(name)),
+                        ..Default::default()
+                    })
+                } else {
+                    Ok(IdentityConfiguration {
+                        encryption: Some(
+                            EncryptionConfiguration::new()
+                                .map_err(GenerateFreshEncryptionConfigurationFailed)?,
+                        ),
+                        ..Default::default()
+                    })
+                }
+            }
+            IdentityStorageMode::PasswordProtected => Ok(IdentityConfiguration {
+                encryption: Some(
+                    EncryptionConfiguration::new()
+                        .map_err(GenerateFreshEncryptionConfigurationFailed)?,
+                ),
+                ..Default::default()
+            }),
+            IdentityStorageMode::Plaintext => Ok(IdentityConfiguration::default()),
+        }
+    }
+}
+
 fn get_legacy_creds_pem_path() -> Result<Option<PathBuf>, GetLegacyCredentialsPemPathError> {
     if cfg!(windows) {
         // No legacy path on Windows - there was no Windows support when paths were changed
@@ -834,14 +1442,71 @@ fn remove_identity_file(file: &Path) -> Result<(), RemoveIdentityError> {
 }
 
 /// Generates a new secp256k1 key.
-pub(super) fn generate_key() -> Result<(Vec<u8>, Mnemonic), GenerateKeyError> {
+pub(super) fn generate_key(key_type: KeyType) -> Result<(Vec<u8>, Mnemonic), GenerateKeyError> {
     let mnemonic = Mnemonic::new(MnemonicType::for_key_size(256).unwrap(), Language::English);
-    let secret =
-        mnemonic_to_key(&mnemonic).map_err(GenerateKeyError::ConvertMnemonicToKeyFailed)?;
-    let pem = secret
-        .to_sec1_pem(LineEnding::CRLF)
-        .map_err(|e| GenerateFreshSecp256k1KeyFailed(Box::new(e)))?;
-    Ok((pem.as_bytes().to_vec(), mnemonic))
+    let pem = mnemonic_to_pem(&mnemonic, key_type)
+        .map_err(GenerateKeyError::ConvertMnemonicToKeyFailed)?;
+    Ok((pem, mnemonic))
+}
+
+/// Derives the key of the given type from a seed phrase and returns it PEM-encoded:
+/// SEC1 for secp256k1 keys, PKCS#8 v2 for Ed25519 keys.
+pub fn mnemonic_to_pem(
+    mnemonic: &Mnemonic,
+    key_type: KeyType,
+) -> Result<Vec<u8>, ConvertMnemonicToKeyError> {
+    match key_type {
+        KeyType::Secp256k1 => {
+            let pem = mnemonic_to_key(mnemonic)?
+                .to_sec1_pem(LineEnding::CRLF)
+                .map_err(|e| ConvertSecretKeyToSec1PemFailed(Box::new(e)))?;
+            Ok(pem.as_bytes().to_vec())
+        }
+        KeyType::Ed25519 => ed25519_seed_to_pem(&mnemonic_to_ed25519_seed(mnemonic)),
+    }
+}
+
+/// Returns the Ed25519 private key with the given seed as a PEM-encoded PKCS#8 v2 document.
+pub fn ed25519_seed_to_pem(seed: &[u8; 32]) -> Result<Vec<u8>, ConvertMnemonicToKeyError> {
+    let key_pair = Ed25519KeyPair::from_seed_unchecked(seed)
+        .map_err(|e| DeriveEd25519KeyFailed(e.to_string()))?;
+    // The same PKCS#8 v2 document ring generates, which BasicIdentity can load.
+    const PKCS8_V2_PREFIX: [u8; 16] = [
+        0x30, 0x53, 0x02, 0x01, 0x01, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04,
+        0x20,
+    ];
+    const PKCS8_V2_PUBLIC_KEY_PREFIX: [u8; 5] = [0xa1, 0x23, 0x03, 0x21, 0x00];
+    let mut der = PKCS8_V2_PREFIX.to_vec();
+    der.extend_from_slice(seed);
+    der.extend_from_slice(&PKCS8_V2_PUBLIC_KEY_PREFIX);
+    der.extend_from_slice(key_pair.public_key().as_ref());
+    let pem = k256::pkcs8::der::pem::encode_string("PRIVATE KEY", LineEnding::LF, &der)
+        .map_err(|e| EncodeEd25519PemFailed(e.to_string()))?;
+    Ok(pem.into_bytes())
+}
+
+/// Derives an Ed25519 private key following SLIP-0010, which only supports hardened derivation,
+/// along the hardened equivalent of the secp256k1 derivation path: m/44'/223'/0'/0'/0'.
+fn mnemonic_to_ed25519_seed(mnemonic: &Mnemonic) -> [u8; 32] {
+    fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+        let tag = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA512, key), data);
+        let mut out = [0; 64];
+        out.copy_from_slice(tag.as_ref());
+        out
+    }
+
+    let seed = Seed::new(mnemonic, "");
+    let mut node = hmac_sha512(b"ed25519 seed", seed.as_bytes());
+    for index in [44u32, 223, 0, 0, 0] {
+        let mut data = Vec::with_capacity(37);
+        data.push(0);
+        data.extend_from_slice(&node[..32]);
+        data.extend_from_slice(&(index | 0x8000_0000).to_be_bytes());
+        node = hmac_sha512(&node[32..], &data);
+    }
+    let mut key = [0; 32];
+    key.copy_from_slice(&node[..32]);
+    key
 }
 
 pub fn mnemonic_to_key(mnemonic: &Mnemonic) -> Result<SecretKey, ConvertMnemonicToKeyError> {
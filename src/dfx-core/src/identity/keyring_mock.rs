@@ -1,94 +1,167 @@
 use super::TEMP_IDENTITY_PREFIX;
 use crate::error::keyring::KeyringError;
 use crate::error::keyring::KeyringError::{
-    DecodePemFailed, DeletePasswordFailed, GetPasswordFailed, LoadMockKeyringFailed,
-    MockKeyNotFound, MockUnavailable, SaveMockKeyringFailed, SetPasswordFailed,
+    DecodePemFailed, DeletePasswordFailed, Disabled, GetPasswordFailed, LoadMockKeyringFailed,
+    MockKeyNotFound, MockUnavailable, SaveMockKeyringFailed, SetPasswordFailed, UnknownBackend,
 };
 use crate::json::{load_json_file, save_json_file};
 use keyring;
 use serde::{Deserialize, Serialize};
-use slog::{trace, Logger};
+use slog::{trace, warn, Logger};
 use std::{collections::HashMap, path::PathBuf};
 
 pub const KEYRING_SERVICE_NAME: &str = "internet_computer_identities";
 pub const KEYRING_IDENTITY_PREFIX: &str = "internet_computer_identity_";
+/// Prefix of the entries that keep password-protected identities unlocked.
+pub const KEYRING_SESSION_PREFIX: &str = "internet_computer_identity_session_";
 pub const USE_KEYRING_MOCK_ENV_VAR: &str = "DFX_CI_MOCK_KEYRING_LOCATION";
+/// Selects the keyring backend: `system` (the default) or `none`.
+pub const KEYRING_BACKEND_ENV_VAR: &str = "DFX_KEYRING_BACKEND";
 fn keyring_identity_name_from_suffix(suffix: &str) -> String {
     format!("{}{}", KEYRING_IDENTITY_PREFIX, suffix)
 }
 
-enum KeyringMockMode {
-    /// Use system keyring
-    NoMock,
-    /// Simulate keyring where access is granted
-    MockAvailable,
-    /// Simulate keyring where access is rejected
-    MockReject,
+/// Where identities with the keyring storage mode keep their hex-encoded PEM files.
+trait KeyringBackend {
+    fn get(&self, entry_name: &str) -> Result<String, KeyringError>;
+    fn set(&self, entry_name: &str, value: &str) -> Result<(), KeyringError>;
+    /// Deleting an entry that does not exist succeeds.
+    fn delete(&self, entry_name: &str) -> Result<(), KeyringError>;
+    fn available(&self, log: &Logger) -> bool;
 }
 
-impl KeyringMockMode {
-    fn current_mode() -> Self {
-        match std::env::var(USE_KEYRING_MOCK_ENV_VAR) {
-            Err(_) => Self::NoMock,
-            Ok(location) => match location.as_str() {
-                "" => Self::MockReject,
-                _ => Self::MockAvailable,
-            },
+/// The keyring of the operating system, through the `keyring` crate:
+/// the Keychain on macOS, the Credential Manager on Windows, and the Secret Service (libsecret) on Linux.
+struct SystemKeyring;
+
+/// A keyring stored in a JSON file, selected with `DFX_CI_MOCK_KEYRING_LOCATION`.
+/// An empty location simulates a keyring that rejects access.
+struct MockKeyring {
+    location: Option<PathBuf>,
+}
+
+/// A keyring that is never available, selected with `DFX_KEYRING_BACKEND=none`.
+/// New identities are password-protected instead, and nothing talks to the keyring of the system.
+struct NullKeyring;
+
+fn current_backend() -> Result<Box<dyn KeyringBackend>, KeyringError> {
+    if let Ok(location) = std::env::var(USE_KEYRING_MOCK_ENV_VAR) {
+        let location = (!location.is_empty()).then(|| PathBuf::from(location));
+        return Ok(Box::new(MockKeyring { location }));
+    }
+    match std::env::var(KEYRING_BACKEND_ENV_VAR).as_deref() {
+        Err(_) | Ok("system") => Ok(Box::new(SystemKeyring)),
+        Ok("none") => Ok(Box::new(NullKeyring)),
+        Ok(other) => Err(UnknownBackend(other.to_string())),
+    }
+}
+
+impl KeyringBackend for SystemKeyring {
+    fn get(&self, entry_name: &str) -> Result<String, KeyringError> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, entry_name);
+        entry.get_password().map_err(GetPasswordFailed)
+    }
+
+    fn set(&self, entry_name: &str, value: &str) -> Result<(), KeyringError> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, entry_name);
+        entry.set_password(value).map_err(SetPasswordFailed)
+    }
+
+    fn delete(&self, entry_name: &str) -> Result<(), KeyringError> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, entry_name);
+        if entry.get_password().is_ok() {
+            entry.delete_password().map_err(DeletePasswordFailed)?;
         }
+        Ok(())
+    }
+
+    /// Determines if keyring is available by trying to write a dummy entry.
+    fn available(&self, log: &Logger) -> bool {
+        trace!(log, "Checking for keyring availability.");
+        // by using the temp identity prefix this will not clash with real identities since that would be an invalid identity name
+        let dummy_entry_name = format!(
+            "{}{}{}",
+            KEYRING_IDENTITY_PREFIX, TEMP_IDENTITY_PREFIX, "dummy"
+        );
+        self.set(&dummy_entry_name, "dummy entry").is_ok()
     }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
-struct KeyringMock {
+struct KeyringMockStore {
     pub kv_store: HashMap<String, String>,
 }
 
-impl KeyringMock {
-    fn get_location() -> Result<PathBuf, KeyringError> {
-        match std::env::var(USE_KEYRING_MOCK_ENV_VAR) {
-            Ok(filename) => match filename.as_str() {
-                "" => Err(MockUnavailable()),
-                _ => Ok(PathBuf::from(filename)),
-            },
-            _ => unreachable!("Mock keyring unavailable."),
-        }
+impl MockKeyring {
+    fn location(&self) -> Result<&PathBuf, KeyringError> {
+        self.location.as_ref().ok_or(MockUnavailable())
     }
 
-    pub fn load() -> Result<Self, KeyringError> {
-        let location = Self::get_location()?;
+    fn load(&self) -> Result<KeyringMockStore, KeyringError> {
+        let location = self.location()?;
         if location.exists() {
-            load_json_file(&location).map_err(LoadMockKeyringFailed)
+            load_json_file(location).map_err(LoadMockKeyringFailed)
         } else {
-            Ok(Self::default())
+            Ok(KeyringMockStore::default())
         }
     }
 
-    pub fn save(&self) -> Result<(), KeyringError> {
-        let location = Self::get_location()?;
-        save_json_file(&location, self).map_err(SaveMockKeyringFailed)
+    fn save(&self, store: &KeyringMockStore) -> Result<(), KeyringError> {
+        save_json_file(self.location()?, store).map_err(SaveMockKeyringFailed)
+    }
+}
+
+impl KeyringBackend for MockKeyring {
+    fn get(&self, entry_name: &str) -> Result<String, KeyringError> {
+        self.load()?
+            .kv_store
+            .remove(entry_name)
+            .ok_or_else(|| MockKeyNotFound(entry_name.to_string()))
+    }
+
+    fn set(&self, entry_name: &str, value: &str) -> Result<(), KeyringError> {
+        let mut store = self.load()?;
+        store
+            .kv_store
+            .insert(entry_name.to_string(), value.to_string());
+        self.save(&store)
+    }
+
+    fn delete(&self, entry_name: &str) -> Result<(), KeyringError> {
+        let mut store = self.load()?;
+        store.kv_store.remove(entry_name);
+        self.save(&store)
+    }
+
+    fn available(&self, _log: &Logger) -> bool {
+        self.location.is_some()
+    }
+}
+
+impl KeyringBackend for NullKeyring {
+    fn get(&self, _entry_name: &str) -> Result<String, KeyringError> {
+        Err(Disabled())
+    }
+
+    fn set(&self, _entry_name: &str, _value: &str) -> Result<(), KeyringError> {
+        Err(Disabled())
+    }
+
+    /// Nothing is ever stored, so there is nothing to delete.
+    fn delete(&self, _entry_name: &str) -> Result<(), KeyringError> {
+        Ok(())
+    }
+
+    fn available(&self, _log: &Logger) -> bool {
+        false
     }
 }
 
 pub fn load_pem_from_keyring(identity_name_suffix: &str) -> Result<Vec<u8>, KeyringError> {
     let keyring_identity_name = keyring_identity_name_from_suffix(identity_name_suffix);
-    match KeyringMockMode::current_mode() {
-        KeyringMockMode::NoMock => {
-            let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, &keyring_identity_name);
-            let encoded_pem = entry.get_password().map_err(GetPasswordFailed)?;
-            let pem = hex::decode(encoded_pem).map_err(DecodePemFailed)?;
-            Ok(pem)
-        }
-        KeyringMockMode::MockAvailable => {
-            let mock = KeyringMock::load()?;
-            let encoded_pem = mock
-                .kv_store
-                .get(&keyring_identity_name)
-                .ok_or(MockKeyNotFound(keyring_identity_name))?;
-            let pem = hex::decode(encoded_pem).map_err(DecodePemFailed)?;
-            Ok(pem)
-        }
-        KeyringMockMode::MockReject => Err(MockUnavailable()),
-    }
+    let encoded_pem = current_backend()?.get(&keyring_identity_name)?;
+    let pem = hex::decode(encoded_pem).map_err(DecodePemFailed)?;
+    Ok(pem)
 }
 
 pub fn write_pem_to_keyring(
@@ -97,57 +170,33 @@ pub fn write_pem_to_keyring(
 ) -> Result<(), KeyringError> {
     let keyring_identity_name = keyring_identity_name_from_suffix(identity_name_suffix);
     let encoded_pem = hex::encode(pem_content);
-    match KeyringMockMode::current_mode() {
-        KeyringMockMode::NoMock => {
-            let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, &keyring_identity_name);
-            entry
-                .set_password(&encoded_pem)
-                .map_err(SetPasswordFailed)?;
-            Ok(())
-        }
-        KeyringMockMode::MockAvailable => {
-            let mut mock = KeyringMock::load()?;
-            mock.kv_store.insert(keyring_identity_name, encoded_pem);
-            mock.save()?;
-            Ok(())
-        }
-        KeyringMockMode::MockReject => Err(MockUnavailable()),
-    }
+    current_backend()?.set(&keyring_identity_name, &encoded_pem)
 }
 
-/// Determines if keyring is available by trying to write a dummy entry.
+/// Determines if the selected keyring backend can store identities.
 pub fn keyring_available(log: &Logger) -> bool {
-    match KeyringMockMode::current_mode() {
-        KeyringMockMode::NoMock => {
-            trace!(log, "Checking for keyring availability.");
-            // by using the temp identity prefix this will not clash with real identities since that would be an invalid identity name
-            let dummy_entry_name = format!(
-                "{}{}{}",
-                KEYRING_IDENTITY_PREFIX, TEMP_IDENTITY_PREFIX, "dummy"
-            );
-            let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, &dummy_entry_name);
-            entry.set_password("dummy entry").is_ok()
+    match current_backend() {
+        Ok(backend) => backend.available(log),
+        Err(err) => {
+            warn!(log, "{err}");
+            false
         }
-        KeyringMockMode::MockReject => false,
-        KeyringMockMode::MockAvailable => true,
     }
 }
 
 pub fn delete_pem_from_keyring(identity_name_suffix: &str) -> Result<(), KeyringError> {
     let keyring_identity_name = keyring_identity_name_from_suffix(identity_name_suffix);
-    match KeyringMockMode::current_mode() {
-        KeyringMockMode::NoMock => {
-            let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, &keyring_identity_name);
-            if entry.get_password().is_ok() {
-                entry.delete_password().map_err(DeletePasswordFailed)?;
-            }
-        }
-        KeyringMockMode::MockAvailable => {
-            let mut mock = KeyringMock::load()?;
-            mock.kv_store.remove(&keyring_identity_name);
-            mock.save()?;
-        }
-        KeyringMockMode::MockReject => return Err(MockUnavailable()),
-    }
-    Ok(())
+    current_backend()?.delete(&keyring_identity_name)
+}
+
+pub fn load_session_key_from_keyring(session_id: &str) -> Result<String, KeyringError> {
+    current_backend()?.get(&format!("{KEYRING_SESSION_PREFIX}{session_id}"))
+}
+
+pub fn write_session_key_to_keyring(session_id: &str, value: &str) -> Result<(), KeyringError> {
+    current_backend()?.set(&format!("{KEYRING_SESSION_PREFIX}{session_id}"), value)
+}
+
+pub fn delete_session_key_from_keyring(session_id: &str) -> Result<(), KeyringError> {
+    current_backend()?.delete(&format!("{KEYRING_SESSION_PREFIX}{session_id}"))
 }
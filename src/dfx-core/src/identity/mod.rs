@@ -5,6 +5,7 @@
 use crate::config::directories::{get_shared_network_data_directory, get_user_dfx_config_dir};
 use crate::error::identity::call_sender_from_wallet::CallSenderFromWalletError;
 use crate::error::identity::call_sender_from_wallet::CallSenderFromWalletError::ParsePrincipalFromIdFailed;
+use crate::error::identity::delegation_chain::DelegationChainError;
 use crate::error::identity::load_pem_identity::LoadPemIdentityError;
 use crate::error::identity::load_pem_identity::LoadPemIdentityError::ReadIdentityFileFailed;
 use crate::error::identity::map_wallets_to_renamed_identity::MapWalletsToRenamedIdentityError;
@@ -18,12 +19,15 @@ use crate::error::wallet_config::WalletConfigError;
 use crate::error::wallet_config::WalletConfigError::{
     EnsureWalletConfigDirFailed, LoadWalletConfigFailed, SaveWalletConfigFailed,
 };
+use crate::identity::delegation::DelegationChain;
 use crate::identity::identity_file_locations::IdentityFileLocations;
 use crate::json::{load_json_file, save_json_file};
 use candid::Principal;
+use humantime_serde::re::humantime;
 use ic_agent::agent::EnvelopeContent;
 use ic_agent::identity::{
-    AnonymousIdentity, BasicIdentity, Delegation, Secp256k1Identity, SignedDelegation,
+    AnonymousIdentity, BasicIdentity, DelegatedIdentity, Delegation, Secp256k1Identity,
+    SignedDelegation,
 };
 use ic_agent::Signature;
 use ic_identity_hsm::HardwareIdentity;
@@ -32,21 +36,26 @@ pub use identity_manager::{
     IdentityManager,
 };
 use serde::{Deserialize, Serialize};
-use slog::{info, Logger};
+use slog::{info, warn, Logger};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
-mod identity_file_locations;
+pub mod delegation;
+pub(crate) mod identity_file_locations;
 pub mod identity_manager;
 pub mod keyring_mock;
 pub mod pem_safekeeping;
 pub mod pem_utils;
+pub mod signed_message;
 
 pub const ANONYMOUS_IDENTITY_NAME: &str = "anonymous";
 pub const IDENTITY_JSON: &str = "identity.json";
 pub const TEMP_IDENTITY_PREFIX: &str = "___temp___";
 pub const WALLET_CONFIG_FILENAME: &str = "wallets.json";
 const HSM_SLOT_INDEX: usize = 0;
+/// Loading a delegated identity warns if its delegation expires sooner than this.
+const DELEGATION_EXPIRY_WARNING: Duration = Duration::from_secs(60 * 60);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WalletNetworkMap {
@@ -146,10 +155,41 @@ impl Identity {
             let (pem_content, was_encrypted) =
                 pem_safekeeping::load_pem(log, locations, name, &config)
                     .map_err(NewIdentityError::LoadPemFailed)?;
-            Identity::secp256k1(name, &pem_content, was_encrypted)
+            let identity = Identity::secp256k1(name, &pem_content, was_encrypted)
                 .or_else(|e| Identity::basic(name, &pem_content, was_encrypted).map_err(|_| e))
-                .map_err(NewIdentityError::LoadPemIdentityFailed)
+                .map_err(NewIdentityError::LoadPemIdentityFailed)?;
+            match &config.delegation {
+                Some(chain) => identity
+                    .delegated(chain, log)
+                    .map_err(NewIdentityError::LoadDelegationChainFailed),
+                None => Ok(identity),
+            }
+        }
+    }
+
+    /// Makes the identity sign calls as the principal that delegated to its key.
+    fn delegated(
+        self,
+        chain: &DelegationChain,
+        log: &Logger,
+    ) -> Result<Self, DelegationChainError> {
+        chain.ensure_not_expired()?;
+        if let Ok(remaining) = chain.expiration()?.duration_since(SystemTime::now()) {
+            if remaining < DELEGATION_EXPIRY_WARNING {
+                warn!(
+                    log,
+                    "The delegation of identity '{}' expires in {}. Import a new delegation to keep using it.",
+                    self.name,
+                    humantime::format_duration(Duration::from_secs(remaining.as_secs()))
+                );
+            }
         }
+        let inner = Box::new(DelegatedIdentity::new(
+            chain.public_key()?,
+            self.inner,
+            chain.to_signed_delegations()?,
+        ));
+        Ok(Self { inner, ..self })
     }
 
     /// Get the name of this identity.
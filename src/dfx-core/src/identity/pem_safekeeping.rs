@@ -1,7 +1,9 @@
 use super::identity_manager::EncryptionConfiguration;
 use super::IdentityConfiguration;
 use crate::error::encryption::EncryptionError;
-use crate::error::encryption::EncryptionError::{DecryptContentFailed, HashPasswordFailed};
+use crate::error::encryption::EncryptionError::{
+    DecryptContentFailed, HashPasswordFailed, InvalidKeyDerivationParameters,
+};
 use crate::error::fs::FsError;
 use crate::error::identity::load_pem::LoadPemError;
 use crate::error::identity::load_pem::LoadPemError::LoadFromKeyringFailed;
@@ -13,10 +15,15 @@ use crate::error::identity::save_pem::SavePemError;
 use crate::error::identity::save_pem::SavePemError::{
     CannotSavePemContentForHsm, WritePemToKeyringFailed,
 };
+use crate::error::identity::unlock_identity::UnlockIdentityError;
+use crate::error::identity::unlock_identity::UnlockIdentityError::{
+    DecryptPemFailed, WriteSessionKeyFailed,
+};
 use crate::error::identity::write_pem_to_file::WritePemToFileError;
 use crate::error::identity::write_pem_to_file::WritePemToFileError::{
     EncryptPemFileFailed, WritePemContentFailed,
 };
+use crate::error::keyring::KeyringError;
 use crate::identity::identity_file_locations::IdentityFileLocations;
 use crate::identity::keyring_mock;
 use crate::identity::pem_safekeeping::PromptMode::{DecryptingToUse, EncryptingToCreate};
@@ -25,6 +32,7 @@ use aes_gcm::{Aes256Gcm, Key, Nonce};
 use argon2::{password_hash::PasswordHasher, Argon2};
 use slog::{debug, trace, Logger};
 use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Loads an identity's PEM file content.
 pub(crate) fn load_pem(
@@ -158,6 +166,11 @@ fn maybe_decrypt_pem(
     config: Option<&IdentityConfiguration>,
 ) -> Result<(Vec<u8>, bool), EncryptionError> {
     if let Some(decryption_config) = config.and_then(|c| c.encryption.as_ref()) {
+        if let Some(key) = load_session_key(decryption_config) {
+            if let Ok(pem) = decrypt_with_key(pem_content, decryption_config, &key) {
+                return Ok((pem, true));
+            }
+        }
         let password = password_prompt(DecryptingToUse)?;
         let pem = decrypt(pem_content, decryption_config, &password)?;
         // print to stderr so that output redirection works for the identity export command
@@ -184,24 +197,40 @@ fn password_prompt(mode: PromptMode) -> Result<String, EncryptionError> {
         .map_err(EncryptionError::ReadUserPasswordFailed)
 }
 
-fn get_argon_params() -> argon2::Params {
-    argon2::Params::new(64000 /* in kb */, 3, 1, Some(32 /* in bytes */)).unwrap()
+fn get_argon_params(config: &EncryptionConfiguration) -> Result<argon2::Params, EncryptionError> {
+    let parameters = config.key_derivation_parameters();
+    argon2::Params::new(
+        parameters.memory_kib,
+        parameters.iterations,
+        parameters.parallelism,
+        Some(32 /* in bytes */),
+    )
+    .map_err(InvalidKeyDerivationParameters)
 }
 
-fn encrypt(
-    content: &[u8],
+fn derive_key(
     config: &EncryptionConfiguration,
     password: &str,
-) -> Result<Vec<u8>, EncryptionError> {
+) -> Result<Key<Aes256Gcm>, EncryptionError> {
     let argon2 = Argon2::new(
         argon2::Algorithm::Argon2id,
         argon2::Version::V0x13,
-        get_argon_params(),
+        get_argon_params(config)?,
     );
     let hash = argon2
         .hash_password(password.as_bytes(), &config.pw_salt)
-        .map_err(EncryptionError::HashPasswordFailed)?;
-    let key = Key::<Aes256Gcm>::clone_from_slice(hash.hash.unwrap().as_ref());
+        .map_err(HashPasswordFailed)?;
+    Ok(Key::<Aes256Gcm>::clone_from_slice(
+        hash.hash.unwrap().as_ref(),
+    ))
+}
+
+fn encrypt(
+    content: &[u8],
+    config: &EncryptionConfiguration,
+    password: &str,
+) -> Result<Vec<u8>, EncryptionError> {
+    let key = derive_key(config, password)?;
     let cipher = Aes256Gcm::new(&key);
     let nonce = Nonce::from_slice(config.file_nonce.as_slice());
 
@@ -217,16 +246,16 @@ fn decrypt(
     config: &EncryptionConfiguration,
     password: &str,
 ) -> Result<Vec<u8>, EncryptionError> {
-    let argon2 = Argon2::new(
-        argon2::Algorithm::Argon2id,
-        argon2::Version::V0x13,
-        get_argon_params(),
-    );
-    let hash = argon2
-        .hash_password(password.as_bytes(), &config.pw_salt)
-        .map_err(HashPasswordFailed)?;
-    let key = Key::<Aes256Gcm>::clone_from_slice(hash.hash.unwrap().as_ref());
-    let cipher = Aes256Gcm::new(&key);
+    let key = derive_key(config, password)?;
+    decrypt_with_key(encrypted_content, config, &key)
+}
+
+fn decrypt_with_key(
+    encrypted_content: &[u8],
+    config: &EncryptionConfiguration,
+    key: &Key<Aes256Gcm>,
+) -> Result<Vec<u8>, EncryptionError> {
+    let cipher = Aes256Gcm::new(key);
     let nonce = Nonce::from_slice(config.file_nonce.as_slice());
 
     cipher
@@ -234,6 +263,46 @@ fn decrypt(
         .map_err(DecryptContentFailed)
 }
 
+/// Prompts for the password of a password-protected pem file, and keeps the key derived from it in the keyring
+/// until `expires_at`, so that the file can be decrypted without a prompt until then.
+/// The password itself is not stored.
+pub(crate) fn unlock_pem_file(
+    path: &Path,
+    config: &EncryptionConfiguration,
+    expires_at: SystemTime,
+) -> Result<(), UnlockIdentityError> {
+    let content = crate::fs::read(path).map_err(UnlockIdentityError::ReadPemFileFailed)?;
+    let password = password_prompt(DecryptingToUse).map_err(DecryptPemFailed)?;
+    let key = derive_key(config, &password).map_err(DecryptPemFailed)?;
+    decrypt_with_key(&content, config, &key).map_err(DecryptPemFailed)?;
+    let expires_at = expires_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    keyring_mock::write_session_key_to_keyring(
+        &config.pw_salt,
+        &format!("{expires_at}:{}", hex::encode(key)),
+    )
+    .map_err(WriteSessionKeyFailed)
+}
+
+/// Removes the key that `unlock_pem_file` stored, if any.
+pub(crate) fn lock_pem_file(config: &EncryptionConfiguration) -> Result<(), KeyringError> {
+    keyring_mock::delete_session_key_from_keyring(&config.pw_salt)
+}
+
+fn load_session_key(config: &EncryptionConfiguration) -> Option<Key<Aes256Gcm>> {
+    let session = keyring_mock::load_session_key_from_keyring(&config.pw_salt).ok()?;
+    let (expires_at, key) = session.split_once(':')?;
+    let expires_at = UNIX_EPOCH + Duration::from_secs(expires_at.parse().ok()?);
+    if expires_at < SystemTime::now() {
+        let _ = lock_pem_file(config);
+        return None;
+    }
+    let key = hex::decode(key).ok()?;
+    (key.len() == 32).then(|| Key::<Aes256Gcm>::clone_from_slice(&key))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -5,6 +5,7 @@ use crate::error::identity::validate_pem_file::ValidatePemFileError::{
 use ic_agent::identity::BasicIdentity;
 use ic_agent::identity::PemError;
 use ic_agent::identity::Secp256k1Identity;
+use ic_agent::Identity;
 
 pub fn validate_pem_file(pem_content: &[u8]) -> Result<(), ValidatePemFileError> {
     let secp_res =
@@ -22,3 +23,13 @@ pub fn validate_pem_file(pem_content: &[u8]) -> Result<(), ValidatePemFileError>
 
     Ok(())
 }
+
+/// Returns the DER-encoded public key of a PEM-encoded private key.
+pub fn pem_public_key(pem_content: &[u8]) -> Result<Vec<u8>, ValidatePemFileError> {
+    validate_pem_file(pem_content)?;
+    let public_key = match Secp256k1Identity::from_pem(pem_content) {
+        Ok(identity) => identity.public_key(),
+        Err(_) => BasicIdentity::from_pem(pem_content)?.public_key(),
+    };
+    Ok(public_key.unwrap_or_default())
+}
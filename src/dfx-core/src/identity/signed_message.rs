@@ -0,0 +1,178 @@
+//! Messages signed with the key of an identity, so that the owner of a principal can prove it off-chain.
+use crate::error::identity::sign_message::SignMessageError;
+use crate::error::identity::sign_message::SignMessageError::{
+    DelegatedIdentityNotSupported, GetPrincipalFailed, NoPublicKey, SignFailed,
+};
+use crate::error::identity::verify_message::VerifyMessageError;
+use crate::error::identity::verify_message::VerifyMessageError::{
+    InvalidHex, InvalidPublicKey, InvalidSignature, PrincipalMismatch, UnsupportedKeyType,
+};
+use candid::Principal;
+use k256::ecdsa::signature::Verifier;
+use k256::pkcs8::DecodePublicKey;
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_FIXED, ED25519};
+use serde::{Deserialize, Serialize};
+
+/// Prefixes the signed content, so that a message signature cannot be mistaken for the signature of a call.
+const DOMAIN_SEPARATOR: &str = "dfx signed message";
+
+const ED25519_DER_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+/// The DER encoding of the OID of the secp256k1 curve.
+const SECP256K1_OID: [u8; 7] = [0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+/// The DER encoding of the OID of the prime256v1 curve, which HSMs use.
+const PRIME256V1_OID: [u8; 10] = [0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+/// A signature of a message, with everything needed to verify it.
+///
+/// The signed content is
+/// `dfx signed message\nprincipal: <principal>\ntimestamp: <timestamp>\n\n` followed by the message.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedMessage {
+    pub principal: Principal,
+    /// The DER-encoded public key, in hex.
+    pub public_key: String,
+    /// When the message was signed, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The message, if it is text. Otherwise the message has to be given to verify the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// In hex. Ed25519 signatures, or ECDSA signatures of the SHA-256 of the content as `r || s`.
+    pub signature: String,
+}
+
+impl SignedMessage {
+    pub fn sign(
+        identity: &dyn ic_agent::Identity,
+        message: &[u8],
+        timestamp: u64,
+    ) -> Result<Self, SignMessageError> {
+        let principal = identity.sender().map_err(GetPrincipalFailed)?;
+        let content = signed_content(&principal, timestamp, message);
+        let signature = identity.sign_arbitrary(&content).map_err(SignFailed)?;
+        if signature.delegations.is_some() {
+            return Err(DelegatedIdentityNotSupported());
+        }
+        let (Some(public_key), Some(signature)) = (signature.public_key, signature.signature)
+        else {
+            return Err(NoPublicKey());
+        };
+        Ok(Self {
+            principal,
+            public_key: hex::encode(public_key),
+            timestamp,
+            message: std::str::from_utf8(message).ok().map(str::to_string),
+            signature: hex::encode(signature),
+        })
+    }
+
+    /// Verifies that the principal signed the message: the message of the signature, unless another one is given.
+    pub fn verify(&self, message: Option<&[u8]>) -> Result<(), VerifyMessageError> {
+        let public_key =
+            hex::decode(&self.public_key).map_err(|e| InvalidHex("public_key".into(), e))?;
+        let signature =
+            hex::decode(&self.signature).map_err(|e| InvalidHex("signature".into(), e))?;
+        let key_principal = Principal::self_authenticating(&public_key);
+        if key_principal != self.principal {
+            return Err(PrincipalMismatch(
+                self.principal.to_text(),
+                key_principal.to_text(),
+            ));
+        }
+        let message = message
+            .or(self.message.as_deref().map(str::as_bytes))
+            .unwrap_or_default();
+        let content = signed_content(&self.principal, self.timestamp, message);
+
+        if public_key.len() == ED25519_DER_PREFIX.len() + 32
+            && public_key.starts_with(&ED25519_DER_PREFIX)
+        {
+            UnparsedPublicKey::new(&ED25519, &public_key[ED25519_DER_PREFIX.len()..])
+                .verify(&content, &signature)
+                .map_err(|_| InvalidSignature())
+        } else if contains(&public_key, &SECP256K1_OID) {
+            let key = k256::PublicKey::from_public_key_der(&public_key)
+                .map_err(|e| InvalidPublicKey(e.to_string()))?;
+            let signature = k256::ecdsa::Signature::try_from(signature.as_slice())
+                .map_err(|_| InvalidSignature())?;
+            k256::ecdsa::VerifyingKey::from(&key)
+                .verify(&content, &signature)
+                .map_err(|_| InvalidSignature())
+        } else if contains(&public_key, &PRIME256V1_OID) {
+            // The key is the uncompressed point at the end of the DER encoding.
+            let point = public_key
+                .len()
+                .checked_sub(65)
+                .map(|start| &public_key[start..])
+                .filter(|point| point[0] == 0x04)
+                .ok_or_else(|| InvalidPublicKey("expected an uncompressed point".to_string()))?;
+            UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point)
+                .verify(&content, &signature)
+                .map_err(|_| InvalidSignature())
+        } else {
+            Err(UnsupportedKeyType())
+        }
+    }
+}
+
+fn signed_content(principal: &Principal, timestamp: u64, message: &[u8]) -> Vec<u8> {
+    let mut content = format!(
+        "{}\nprincipal: {}\ntimestamp: {}\n\n",
+        DOMAIN_SEPARATOR, principal, timestamp
+    )
+    .into_bytes();
+    content.extend_from_slice(message);
+    content
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::identity_manager::{generate_key, KeyType};
+    use ic_agent::identity::{BasicIdentity, Secp256k1Identity};
+
+    fn sign_and_verify(identity: &dyn ic_agent::Identity) {
+        let signed = SignedMessage::sign(identity, b"I own this principal", 1_700_000_000).unwrap();
+        assert_eq!(signed.principal, identity.sender().unwrap());
+        assert_eq!(signed.message.as_deref(), Some("I own this principal"));
+        signed.verify(None).unwrap();
+        assert!(matches!(
+            signed.verify(Some(b"I own another principal".as_slice())),
+            Err(InvalidSignature())
+        ));
+
+        let tampered = SignedMessage {
+            timestamp: 1_700_000_001,
+            ..signed.clone()
+        };
+        assert!(matches!(tampered.verify(None), Err(InvalidSignature())));
+
+        let impersonated = SignedMessage {
+            principal: Principal::anonymous(),
+            ..signed
+        };
+        assert!(matches!(
+            impersonated.verify(None),
+            Err(PrincipalMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn signs_and_verifies_with_secp256k1_keys() {
+        let (pem, _) = generate_key(KeyType::Secp256k1).unwrap();
+        sign_and_verify(&Secp256k1Identity::from_pem(pem.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn signs_and_verifies_with_ed25519_keys() {
+        let (pem, _) = generate_key(KeyType::Ed25519).unwrap();
+        sign_and_verify(&BasicIdentity::from_pem(pem.as_slice()).unwrap());
+    }
+}
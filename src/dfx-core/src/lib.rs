@@ -3,6 +3,7 @@ pub mod cli;
 pub mod config;
 pub mod error;
 pub mod extension;
+pub mod fixture;
 pub mod foundation;
 pub mod fs;
 pub mod identity;
@@ -1,7 +1,7 @@
 use crate::config::directories::get_shared_network_data_directory;
 use crate::config::model::dfinity::{
-    Config, ConfigDefaults, ConfigLocalProvider, ConfigNetwork, NetworkType, NetworksConfig,
-    DEFAULT_PROJECT_LOCAL_BIND, DEFAULT_SHARED_LOCAL_BIND,
+    Config, ConfigDefaults, ConfigLocalProvider, ConfigNetwork, ConfigNetworkConnection,
+    NetworkType, NetworksConfig, DEFAULT_PROJECT_LOCAL_BIND, DEFAULT_SHARED_LOCAL_BIND,
 };
 use crate::config::model::local_server_descriptor::{
     LocalNetworkScopeDescriptor, LocalServerDescriptor,
@@ -52,6 +52,7 @@ pub enum LocalBindDetermination {
 fn config_network_to_network_descriptor(
     network_name: &str,
     config_network: &ConfigNetwork,
+    config_dir: &Path,
     project_defaults: Option<&ConfigDefaults>,
     data_directory: PathBuf,
     local_scope: LocalNetworkScopeDescriptor,
@@ -83,6 +84,10 @@ fn config_network_to_network_descriptor(
                 )?,
                 is_ic,
                 local_server_descriptor: None,
+                connection: network_provider
+                    .connection
+                    .clone()
+                    .with_base_dir(config_dir),
             })
         }
         ConfigNetwork::ConfigLocalProvider(local_provider) => {
@@ -91,6 +96,11 @@ fn config_network_to_network_descriptor(
                 .clone()
                 .or_else(|| project_defaults.and_then(|x| x.bitcoin.clone()))
                 .unwrap_or_default();
+            let candid_ui = local_provider
+                .candid_ui
+                .clone()
+                .or_else(|| project_defaults.and_then(|x| x.candid_ui.clone()))
+                .unwrap_or_default();
             let canister_http = local_provider
                 .canister_http
                 .clone()
@@ -125,9 +135,11 @@ fn config_network_to_network_descriptor(
                 data_directory,
                 bind_address,
                 bitcoin,
+                candid_ui,
                 canister_http,
                 proxy,
                 replica,
+                local_provider.tasks.clone(),
                 local_scope,
                 legacy_pid_path,
             )?;
@@ -137,11 +149,31 @@ fn config_network_to_network_descriptor(
                 r#type: network_type,
                 is_ic: false,
                 local_server_descriptor: Some(local_server_descriptor),
+                connection: local_provider.connection.clone().with_base_dir(config_dir),
             })
         }
     }
 }
 
+/// The network selected on the command line, or else the default network of the project, if it has one.
+/// Only commands that select a network like `--network` should apply the default: without a network,
+/// e.g. `dfx start` and `dfx stop`, `create_network_descriptor` always uses the local network.
+pub fn network_or_project_default(
+    project_config: Option<&Config>,
+    network: Option<String>,
+    logger: &Logger,
+) -> Option<String> {
+    network.or_else(|| {
+        let default = project_config?
+            .get_config()
+            .get_defaults()
+            .network
+            .clone()?;
+        info!(logger, "Using the project's default network '{}'", default);
+        Some(default)
+    })
+}
+
 pub fn create_network_descriptor(
     project_config: Option<Arc<Config>>,
     shared_config: Arc<NetworksConfig>,
@@ -154,32 +186,39 @@ pub fn create_network_descriptor(
     set_network_context(network);
     let network_name = get_network_context()?;
 
-    create_mainnet_network_descriptor(&network_name, &logger)
-        .or_else(|| {
-            create_project_network_descriptor(
-                &network_name,
-                project_config.clone(),
-                &local_bind_determination,
-                &logger,
-            )
-        })
-        .or_else(|| {
-            let project_config_for_warnings_only = project_config;
-            create_shared_network_descriptor(
-                &network_name,
-                shared_config,
-                project_config_for_warnings_only,
-                &local_bind_determination,
-                &logger,
-            )
-        })
-        .or_else(|| create_default_network_from_name(&network_name, &logger).map(Ok))
-        .or_else(|| create_url_based_network_descriptor(&network_name))
-        .unwrap_or(Err(NetworkNotFound(network_name)))
+    create_mainnet_network_descriptor(
+        &network_name,
+        project_config.as_deref(),
+        &shared_config,
+        &logger,
+    )
+    .or_else(|| {
+        create_project_network_descriptor(
+            &network_name,
+            project_config.clone(),
+            &local_bind_determination,
+            &logger,
+        )
+    })
+    .or_else(|| {
+        let project_config_for_warnings_only = project_config;
+        create_shared_network_descriptor(
+            &network_name,
+            shared_config,
+            project_config_for_warnings_only,
+            &local_bind_determination,
+            &logger,
+        )
+    })
+    .or_else(|| create_default_network_from_name(&network_name, &logger).map(Ok))
+    .or_else(|| create_url_based_network_descriptor(&network_name))
+    .unwrap_or(Err(NetworkNotFound(network_name)))
 }
 
 fn create_mainnet_network_descriptor(
     network_name: &str,
+    project_config: Option<&Config>,
+    shared_config: &NetworksConfig,
     logger: &Logger,
 ) -> Option<Result<NetworkDescriptor, NetworkConfigError>> {
     if network_name == "ic" {
@@ -187,7 +226,25 @@ fn create_mainnet_network_descriptor(
             logger,
             "Using built-in definition for network 'ic' (mainnet)"
         );
-        Some(Ok(NetworkDescriptor::ic()))
+        // The providers of mainnet cannot be changed, but the settings for
+        // connecting to them can, e.g. to go through a corporate proxy.
+        let project_network = project_config.and_then(|config| {
+            let network = config.get_config().get_network("ic")?;
+            Some((network, config.get_project_root()))
+        });
+        let shared_network = shared_config
+            .get_interface()
+            .get_network("ic")
+            .map(|network| (network, shared_config.get_path().parent().unwrap()));
+        let mut descriptor = NetworkDescriptor::ic();
+        if let Some((network, config_dir)) = project_network.or(shared_network) {
+            let connection = match network {
+                ConfigNetwork::ConfigNetworkProvider(provider) => &provider.connection,
+                ConfigNetwork::ConfigLocalProvider(local) => &local.connection,
+            };
+            descriptor.connection = connection.clone().with_base_dir(config_dir);
+        }
+        Some(Ok(descriptor))
     } else {
         None
     }
@@ -213,6 +270,7 @@ fn create_url_based_network_descriptor(
             r#type: network_type,
             is_ic,
             local_server_descriptor: None,
+            connection: ConfigNetworkConnection::default(),
         })
     })
 }
@@ -240,10 +298,13 @@ fn create_shared_network_descriptor(
                 r#type: NetworkType::Ephemeral,
                 bitcoin: None,
                 bootstrap: None,
+                candid_ui: None,
                 canister_http: None,
                 replica: None,
                 playground: None,
                 proxy: None,
+                tasks: vec![],
+                connection: ConfigNetworkConnection::default(),
             }))
         }
         (network_name, None) => {
@@ -291,6 +352,7 @@ fn create_shared_network_descriptor(
         config_network_to_network_descriptor(
             network_name,
             config_network,
+            shared_config.get_path().parent().unwrap(),
             None,
             data_directory,
             local_scope,
@@ -313,8 +375,10 @@ fn warn_if_ignoring_project_defaults_in_shared_network(
         let bitcoin = defaults.and_then(|x| x.get("bitcoin")).cloned();
         let replica = defaults.and_then(|x| x.get("replica")).cloned();
         let canister_http = defaults.and_then(|x| x.get("canister_http")).cloned();
+        let candid_ui = defaults.and_then(|x| x.get("candid_ui")).cloned();
 
-        if bitcoin.is_some() || replica.is_some() || canister_http.is_some() {
+        if bitcoin.is_some() || replica.is_some() || canister_http.is_some() || candid_ui.is_some()
+        {
             let mut example_network_json = json!({});
             if let Some(bitcoin) = bitcoin {
                 example_network_json["bitcoin"] = bitcoin;
@@ -325,6 +389,9 @@ fn warn_if_ignoring_project_defaults_in_shared_network(
             if let Some(canister_http) = canister_http {
                 example_network_json["canister_http"] = canister_http;
             }
+            if let Some(candid_ui) = candid_ui {
+                example_network_json["candid_ui"] = candid_ui;
+            }
             let example_networks_json = json!({
                 network_name: example_network_json
             });
@@ -364,6 +431,7 @@ fn create_project_network_descriptor(
             Some(config_network_to_network_descriptor(
                 network_name,
                 config_network,
+                config.get_project_root(),
                 Some(config.get_config().get_defaults()),
                 data_directory,
                 LocalNetworkScopeDescriptor::Project,
@@ -477,8 +545,8 @@ mod tests {
     use crate::config::model::canister_http_adapter::HttpAdapterLogLevel;
     use crate::config::model::dfinity::ReplicaSubnetType::{System, VerifiedApplication};
     use crate::config::model::dfinity::{
-        to_socket_addr, ConfigDefaultsBitcoin, ConfigDefaultsCanisterHttp, ConfigDefaultsReplica,
-        ReplicaLogLevel,
+        to_socket_addr, ConfigDefaultsBitcoin, ConfigDefaultsCandidUi, ConfigDefaultsCanisterHttp,
+        ConfigDefaultsReplica, ReplicaLogLevel,
     };
     use std::fs;
     use std::net::SocketAddr;
@@ -1018,6 +1086,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn candid_ui_config_from_project_defaults() {
+        let config = Config::from_str(
+            r#"{
+              "defaults": {
+                "candid_ui": {
+                  "enabled": false
+                }
+              },
+              "networks": {
+                "local": {
+                  "bind": "127.0.0.1:8000"
+                }
+              }
+        }"#,
+        )
+        .unwrap();
+
+        let network_descriptor = create_network_descriptor(
+            Some(Arc::new(config)),
+            Arc::new(NetworksConfig::new().unwrap()),
+            None,
+            None,
+            LocalBindDetermination::AsConfigured,
+        )
+        .unwrap();
+        let candid_ui_config = &network_descriptor
+            .local_server_descriptor()
+            .unwrap()
+            .candid_ui;
+
+        assert_eq!(candid_ui_config, &ConfigDefaultsCandidUi { enabled: false });
+    }
+
+    #[test]
+    fn project_default_network() {
+        let config = Arc::new(
+            Config::from_str(
+                r#"{
+            "defaults": {
+                "network": "ic"
+            }
+        }"#,
+            )
+            .unwrap(),
+        );
+
+        let logger = Logger::root(slog::Discard, slog::o!());
+        assert_eq!(
+            network_or_project_default(Some(&config), None, &logger).as_deref(),
+            Some("ic")
+        );
+        // the command line takes precedence
+        assert_eq!(
+            network_or_project_default(Some(&config), Some("local".to_string()), &logger)
+                .as_deref(),
+            Some("local")
+        );
+
+        // without a network, e.g. for dfx start and dfx stop, the local network is used
+        let network_descriptor = create_network_descriptor(
+            Some(config),
+            Arc::new(NetworksConfig::new().unwrap()),
+            None,
+            None,
+            LocalBindDetermination::AsConfigured,
+        )
+        .unwrap();
+        assert_eq!(network_descriptor.name, "local");
+    }
+
     #[test]
     fn url_is_url() {
         assert_eq!(
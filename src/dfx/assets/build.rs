@@ -321,6 +321,11 @@ fn add_assets(sources: Sources) {
         &mut f,
         "assets/project_templates/bitcoin",
     );
+    add_assets_from_directory(
+        "new_project_ci_files",
+        &mut f,
+        "assets/project_templates/ci",
+    );
 }
 
 /// Use a verion based on environment variable,
@@ -1,4 +1,5 @@
 use crate::actors::btc_adapter::signals::{BtcAdapterReady, BtcAdapterReadySubscribe};
+use crate::actors::log_file::{capture_to_log_file, RotatingLogFile};
 use crate::actors::shutdown::{wait_for_child_or_receiver, ChildOrReceiver};
 use crate::actors::shutdown_controller::signals::outbound::Shutdown;
 use crate::actors::shutdown_controller::signals::ShutdownSubscribe;
@@ -35,6 +36,8 @@ pub struct Config {
     pub socket_path: Option<PathBuf>,
     pub shutdown_controller: Addr<ShutdownController>,
     pub btc_adapter_pid_file_path: PathBuf,
+    /// The output of the ic-btc-adapter is appended to this file.
+    pub log_path: PathBuf,
 
     pub logger: Option<Logger>,
 }
@@ -187,8 +190,10 @@ fn btc_adapter_start_thread(
         let mut cmd = std::process::Command::new(btc_adapter_path);
         cmd.arg(&config.config_path.to_string_lossy().to_string());
 
-        cmd.stdout(std::process::Stdio::inherit());
-        cmd.stderr(std::process::Stdio::inherit());
+        cmd.stdout(std::process::Stdio::piped());
+        cmd.stderr(std::process::Stdio::piped());
+        let log_file = RotatingLogFile::shared(&config.log_path)
+            .expect("Could not open btc-adapter log file.");
 
         loop {
             if let Some(socket_path) = &config.socket_path {
@@ -199,6 +204,8 @@ fn btc_adapter_start_thread(
             let last_start = std::time::Instant::now();
             debug!(logger, "Starting ic-btc-adapter...");
             let mut child = cmd.spawn().expect("Could not start ic-btc-adapter.");
+            capture_to_log_file(&mut child, &log_file)
+                .expect("Could not capture btc-adapter output.");
 
             std::fs::write(&config.btc_adapter_pid_file_path, "")
                 .expect("Could not write to btc-adapter-pid file.");
@@ -1,6 +1,7 @@
 use crate::actors::canister_http_adapter::signals::{
     CanisterHttpAdapterReady, CanisterHttpAdapterReadySubscribe,
 };
+use crate::actors::log_file::{capture_to_log_file, RotatingLogFile};
 use crate::actors::shutdown::{wait_for_child_or_receiver, ChildOrReceiver};
 use crate::actors::shutdown_controller::signals::outbound::Shutdown;
 use crate::actors::shutdown_controller::signals::ShutdownSubscribe;
@@ -37,6 +38,8 @@ pub struct Config {
     pub socket_path: Option<PathBuf>,
     pub shutdown_controller: Addr<ShutdownController>,
     pub pid_file_path: PathBuf,
+    /// The output of the ic-https-outcalls-adapter is appended to this file.
+    pub log_path: PathBuf,
 
     pub logger: Option<Logger>,
 }
@@ -192,8 +195,10 @@ fn canister_http_adapter_start_thread(
         let mut cmd = std::process::Command::new(adapter_path);
         cmd.arg(&config.config_path.to_string_lossy().to_string());
 
-        cmd.stdout(std::process::Stdio::inherit());
-        cmd.stderr(std::process::Stdio::inherit());
+        cmd.stdout(std::process::Stdio::piped());
+        cmd.stderr(std::process::Stdio::piped());
+        let log_file = RotatingLogFile::shared(&config.log_path)
+            .expect("Could not open canister http adapter log file.");
 
         loop {
             if let Some(socket_path) = &config.socket_path {
@@ -205,6 +210,8 @@ fn canister_http_adapter_start_thread(
             let last_start = std::time::Instant::now();
             debug!(logger, "Starting canister http adapter...");
             let mut child = cmd.spawn().expect("Could not start canister http adapter.");
+            capture_to_log_file(&mut child, &log_file)
+                .expect("Could not capture canister http adapter output.");
 
             std::fs::write(&config.pid_file_path, "")
                 .expect("Could not write to canister http adapter pid file.");
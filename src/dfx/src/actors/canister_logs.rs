@@ -0,0 +1,132 @@
+use console::{Color, Style};
+use dfx_core::config::model::canister_id_store::CanisterIdStore;
+use dfx_core::config::model::dfinity::Config;
+use dfx_core::config::model::network_descriptor::NetworkDescriptor;
+use slog::{debug, Logger};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// The replica tags the lines that canisters print, and the messages of their panics, with this prefix.
+const CANISTER_LINE_PREFIX: &str = "[Canister ";
+
+const CANISTER_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Red,
+];
+
+/// Rewrites the output of the replica so that the lines printed by canisters are prefixed with
+/// the name of the canister, in a color of its own, and optionally only shows the lines of some canisters.
+/// All other lines are passed through unchanged.
+pub struct CanisterLogForwarder {
+    logger: Logger,
+    network_descriptor: NetworkDescriptor,
+    project_config: Option<Arc<Config>>,
+
+    /// Canister names or ids. If empty, the lines of all canisters are shown.
+    filter: Vec<String>,
+
+    /// The canister name of each canister id seen so far, if it is a canister of the project.
+    names: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl CanisterLogForwarder {
+    pub fn new(
+        logger: Logger,
+        network_descriptor: NetworkDescriptor,
+        project_config: Option<Arc<Config>>,
+        filter: Vec<String>,
+    ) -> Self {
+        CanisterLogForwarder {
+            logger,
+            network_descriptor,
+            project_config,
+            filter,
+            names: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Looks up the name of a canister in the canister ids of the project.
+    /// The store is read again for every new canister id, since canisters are created while the replica runs.
+    fn canister_name(&self, canister_id: &str) -> Option<String> {
+        let mut names = self.names.lock().unwrap();
+        names
+            .entry(canister_id.to_string())
+            .or_insert_with(|| {
+                match CanisterIdStore::new(
+                    &self.logger,
+                    &self.network_descriptor,
+                    self.project_config.clone(),
+                ) {
+                    Ok(store) => store.get_name(canister_id).cloned(),
+                    Err(err) => {
+                        debug!(self.logger, "Failed to read the canister ids: {err}");
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    /// Returns the line to print in place of a line of replica output, or None to drop it.
+    pub fn format_line(&self, line: &str) -> Option<String> {
+        let Some((canister_id, message)) = parse_canister_line(line) else {
+            return Some(line.to_string());
+        };
+        let name = self.canister_name(canister_id);
+        if !self.filter.is_empty()
+            && !self
+                .filter
+                .iter()
+                .any(|f| f == canister_id || Some(f) == name.as_ref())
+        {
+            return None;
+        }
+        let style = Style::new().for_stderr().fg(canister_color(canister_id));
+        let label = name.as_deref().unwrap_or(canister_id);
+        Some(format!(
+            "{} {message}",
+            style.apply_to(format!("[{label}]"))
+        ))
+    }
+}
+
+/// Splits a replica log line like
+/// `2023-06-01 10:00:00.000 UTC: [Canister bkyz2-fmaaa-aaaaa-qaaaq-cai] hello`
+/// into the canister id and the message.
+fn parse_canister_line(line: &str) -> Option<(&str, &str)> {
+    let start = line.find(CANISTER_LINE_PREFIX)? + CANISTER_LINE_PREFIX.len();
+    let rest = &line[start..];
+    let end = rest.find(']')?;
+    Some((&rest[..end], rest[end + 1..].trim_start()))
+}
+
+/// Picks the same color for a canister every time.
+fn canister_color(canister_id: &str) -> Color {
+    let hash = canister_id.bytes().fold(0usize, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(byte as usize)
+    });
+    CANISTER_COLORS[hash % CANISTER_COLORS.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_canister_lines() {
+        assert_eq!(
+            parse_canister_line(
+                "2023-06-01 10:00:00.000 UTC: [Canister bkyz2-fmaaa-aaaaa-qaaaq-cai] hello ] world"
+            ),
+            Some(("bkyz2-fmaaa-aaaaa-qaaaq-cai", "hello ] world"))
+        );
+        assert_eq!(
+            parse_canister_line("2023-06-01 10:00:00.000 UTC: Starting replica"),
+            None
+        );
+    }
+}
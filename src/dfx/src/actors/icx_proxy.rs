@@ -8,8 +8,10 @@ use actix::{
     Actor, ActorContext, ActorFutureExt, Addr, AsyncContext, Context, Handler, Recipient,
     ResponseActFuture, Running, WrapFuture,
 };
+use candid::Principal;
 use crossbeam::channel::{unbounded, Receiver, Sender};
 use slog::{debug, info, Logger};
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::thread::JoinHandle;
@@ -45,6 +47,9 @@ pub struct IcxProxyConfig {
 
     /// list of domains that can be served (localhost if none specified)
     pub domains: Vec<String>,
+
+    /// custom domains, each served by a single canister.  Become arguments like --dns-alias example.localhost:<canister id>
+    pub custom_domains: BTreeMap<String, Principal>,
 }
 
 /// The configuration for the icx_proxy actor.
@@ -100,6 +105,7 @@ impl IcxProxy {
                 fetch_root_key,
                 config.verbose,
                 config.domains.clone(),
+                config.custom_domains.clone(),
             ),
             "Failed to start ICX proxy thread.",
         )?;
@@ -194,6 +200,7 @@ fn icx_proxy_start_thread(
     fetch_root_key: bool,
     verbose: bool,
     domains: Vec<String>,
+    custom_domains: BTreeMap<String, Principal>,
 ) -> DfxResult<std::thread::JoinHandle<()>> {
     let thread_handler = move || {
         // Start the process, then wait for the file.
@@ -213,6 +220,9 @@ fn icx_proxy_start_thread(
         for domain in domains {
             cmd.args(["--domain", &domain]);
         }
+        for (domain, canister_id) in custom_domains {
+            cmd.args(["--dns-alias", &format!("{}:{}", domain, canister_id)]);
+        }
         if !verbose {
             cmd.arg("-q");
         }
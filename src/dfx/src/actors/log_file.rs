@@ -0,0 +1,113 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// A log file is rotated once it grows past this size.
+const MAX_LOG_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// The number of rotated log files to keep, as `<name>.1` (the most recent) to `<name>.5`.
+const MAX_ROTATED_LOG_FILES: usize = 5;
+
+/// A log file that is shared by the threads that capture the output of one process.
+pub type SharedLogFile = Arc<Mutex<RotatingLogFile>>;
+
+/// A file that the output of a process is appended to.
+/// When it grows too large, it is renamed to `<name>.1`, the older files are shifted by one,
+/// and the oldest one is removed.
+pub struct RotatingLogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLogFile {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingLogFile {
+            path: path.to_path_buf(),
+            file,
+            size,
+        })
+    }
+
+    pub fn shared(path: &Path) -> std::io::Result<SharedLogFile> {
+        Ok(Arc::new(Mutex::new(Self::open(path)?)))
+    }
+
+    /// Appends a line, rotating the file first if it is full.
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 + 1 > MAX_LOG_FILE_SIZE {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{line}")?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        for index in (1..MAX_ROTATED_LOG_FILES).rev() {
+            let from = rotated_log_path(&self.path, index);
+            if from.exists() {
+                std::fs::rename(from, rotated_log_path(&self.path, index + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, rotated_log_path(&self.path, 1))?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{index}"));
+    path.with_file_name(name)
+}
+
+/// Reads the output of a process in a thread of its own, until the process closes it.
+/// Each line is appended to the log file, then passed to `on_line`.
+pub fn capture_output<R, F>(
+    output: R,
+    log_file: SharedLogFile,
+    mut on_line: F,
+) -> std::io::Result<JoinHandle<()>>
+where
+    R: Read + Send + 'static,
+    F: FnMut(&str) + Send + 'static,
+{
+    std::thread::Builder::new()
+        .name("process-output-capture".to_owned())
+        .spawn(move || {
+            let mut reader = BufReader::new(output);
+            let mut buf = vec![];
+            // Lines that are not valid UTF-8 are kept, so that the process never blocks on a full pipe.
+            while matches!(reader.read_until(b'\n', &mut buf), Ok(n) if n > 0) {
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim_end_matches(&['\n', '\r'][..]);
+                let _ = log_file.lock().unwrap().write_line(line);
+                on_line(line);
+                buf.clear();
+            }
+        })
+}
+
+/// Appends the output of a process, spawned with piped stdout and stderr, to the log file only.
+pub fn capture_to_log_file(child: &mut Child, log_file: &SharedLogFile) -> std::io::Result<()> {
+    if let Some(stdout) = child.stdout.take() {
+        capture_output(stdout, log_file.clone(), |_| {})?;
+    }
+    if let Some(stderr) = child.stderr.take() {
+        capture_output(stderr, log_file.clone(), |_| {})?;
+    }
+    Ok(())
+}
@@ -2,26 +2,32 @@ use crate::actors::btc_adapter::signals::BtcAdapterReadySubscribe;
 use crate::actors::btc_adapter::BtcAdapter;
 use crate::actors::canister_http_adapter::signals::CanisterHttpAdapterReadySubscribe;
 use crate::actors::canister_http_adapter::CanisterHttpAdapter;
+use crate::actors::canister_logs::CanisterLogForwarder;
 use crate::actors::icx_proxy::signals::PortReadySubscribe;
 use crate::actors::icx_proxy::{IcxProxy, IcxProxyConfig};
 use crate::actors::replica::{BitcoinIntegrationConfig, Replica};
 use crate::actors::shutdown_controller::ShutdownController;
+use crate::actors::tasks::{ScheduledTask, ScheduledTasks};
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::replica_config::ReplicaConfig;
 use actix::{Actor, Addr, Recipient};
-use anyhow::Context;
+use anyhow::{bail, Context};
 use dfx_core::config::model::local_server_descriptor::LocalServerDescriptor;
 use fn_error_context::context;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 pub mod btc_adapter;
 pub mod canister_http_adapter;
+pub mod canister_logs;
 pub mod icx_proxy;
+pub mod log_file;
 pub mod replica;
 mod shutdown;
 pub mod shutdown_controller;
+pub mod tasks;
 
 #[context("Failed to start shutdown controller.")]
 pub fn start_shutdown_controller(env: &dyn Environment) -> DfxResult<Addr<ShutdownController>> {
@@ -38,6 +44,7 @@ pub fn start_btc_adapter_actor(
     socket_path: Option<PathBuf>,
     shutdown_controller: Addr<ShutdownController>,
     btc_adapter_pid_file_path: PathBuf,
+    log_path: PathBuf,
 ) -> DfxResult<Recipient<BtcAdapterReadySubscribe>> {
     let btc_adapter_path = env.get_cache().get_binary_command_path("ic-btc-adapter")?;
 
@@ -49,6 +56,7 @@ pub fn start_btc_adapter_actor(
 
         shutdown_controller,
         btc_adapter_pid_file_path,
+        log_path,
         logger: Some(env.get_logger().clone()),
     };
     Ok(BtcAdapter::new(actor_config).start().recipient())
@@ -61,6 +69,7 @@ pub fn start_canister_http_adapter_actor(
     socket_path: Option<PathBuf>,
     shutdown_controller: Addr<ShutdownController>,
     pid_file_path: PathBuf,
+    log_path: PathBuf,
 ) -> DfxResult<Recipient<CanisterHttpAdapterReadySubscribe>> {
     let adapter_path = env
         .get_cache()
@@ -74,6 +83,7 @@ pub fn start_canister_http_adapter_actor(
 
         shutdown_controller,
         pid_file_path,
+        log_path,
         logger: Some(env.get_logger().clone()),
     };
     Ok(CanisterHttpAdapter::new(actor_config).start().recipient())
@@ -126,6 +136,7 @@ pub fn start_replica_actor(
     shutdown_controller: Addr<ShutdownController>,
     btc_adapter_ready_subscribe: Option<Recipient<BtcAdapterReadySubscribe>>,
     canister_http_adapter_ready_subscribe: Option<Recipient<CanisterHttpAdapterReadySubscribe>>,
+    canister_log_forwarder: Option<Arc<CanisterLogForwarder>>,
 ) -> DfxResult<Addr<Replica>> {
     // get binary path
     let replica_path = env.get_cache().get_binary_command_path("replica")?;
@@ -145,12 +156,15 @@ pub fn start_replica_actor(
         ic_starter_path,
         replica_config,
         bitcoin_integration_config,
+        candid_ui_enabled: local_server_descriptor.candid_ui.enabled,
         replica_path,
         shutdown_controller,
         logger: Some(env.get_logger().clone()),
         replica_pid_path,
         btc_adapter_ready_subscribe,
         canister_http_adapter_ready_subscribe,
+        canister_log_forwarder,
+        log_path: local_server_descriptor.replica_log_path(),
     };
     Ok(Replica::new(actor_config).start())
 }
@@ -177,3 +191,56 @@ pub fn start_icx_proxy_actor(
     };
     Ok(IcxProxy::new(actor_config).start())
 }
+
+/// Checks the tasks configured for the local network, and resolves the program each one runs.
+#[context("Failed to read the tasks of the local network.")]
+pub fn resolve_scheduled_tasks(
+    local_server_descriptor: &LocalServerDescriptor,
+) -> DfxResult<Vec<ScheduledTask>> {
+    let mut tasks = vec![];
+    for task in &local_server_descriptor.tasks {
+        if task.interval.is_zero() {
+            bail!("Task '{}' must have an interval above zero.", task.name);
+        }
+        let (program, args) = match (&task.dfx, &task.command) {
+            (Some(args), None) => (
+                std::env::current_exe().context("Failed to get the path of dfx.")?,
+                args.clone(),
+            ),
+            (None, Some(command)) if !command.is_empty() => {
+                (PathBuf::from(&command[0]), command[1..].to_vec())
+            }
+            _ => bail!(
+                "Task '{}' must specify either 'dfx' arguments or a non-empty 'command', but not both.",
+                task.name
+            ),
+        };
+        tasks.push(ScheduledTask {
+            name: task.name.clone(),
+            interval: task.interval,
+            program,
+            args,
+        });
+    }
+    Ok(tasks)
+}
+
+#[context("Failed to start scheduled tasks actor.")]
+pub fn start_scheduled_tasks_actor(
+    env: &dyn Environment,
+    tasks: Vec<ScheduledTask>,
+    port_ready_subscribe: Recipient<PortReadySubscribe>,
+    shutdown_controller: Addr<ShutdownController>,
+    log_path: PathBuf,
+) -> DfxResult<Addr<ScheduledTasks>> {
+    let actor_config = tasks::Config {
+        logger: Some(env.get_logger().clone()),
+
+        port_ready_subscribe,
+        shutdown_controller,
+
+        tasks,
+        log_path,
+    };
+    Ok(ScheduledTasks::new(actor_config).start())
+}
@@ -2,7 +2,9 @@ use crate::actors::btc_adapter::signals::{BtcAdapterReady, BtcAdapterReadySubscr
 use crate::actors::canister_http_adapter::signals::{
     CanisterHttpAdapterReady, CanisterHttpAdapterReadySubscribe,
 };
+use crate::actors::canister_logs::CanisterLogForwarder;
 use crate::actors::icx_proxy::signals::{PortReadySignal, PortReadySubscribe};
+use crate::actors::log_file::{capture_output, RotatingLogFile};
 use crate::actors::replica::signals::ReplicaRestarted;
 use crate::actors::shutdown::{wait_for_child_or_receiver, ChildOrReceiver};
 use crate::actors::shutdown_controller::signals::outbound::Shutdown;
@@ -10,6 +12,7 @@ use crate::actors::shutdown_controller::signals::ShutdownSubscribe;
 use crate::actors::shutdown_controller::ShutdownController;
 use crate::lib::error::{DfxError, DfxResult};
 use crate::lib::integrations::bitcoin::initialize_bitcoin_canister;
+use crate::lib::integrations::candid_ui::initialize_candid_ui_canister;
 use crate::lib::integrations::create_integrations_agent;
 use crate::lib::replica_config::ReplicaConfig;
 use actix::{
@@ -19,7 +22,9 @@ use actix::{
 use anyhow::bail;
 use crossbeam::channel::{unbounded, Receiver, Sender};
 use slog::{debug, error, info, Logger};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread::JoinHandle;
 use std::time::Duration;
 use tokio::runtime::Builder;
@@ -46,12 +51,17 @@ pub struct Config {
     pub ic_starter_path: PathBuf,
     pub replica_config: ReplicaConfig,
     pub bitcoin_integration_config: Option<BitcoinIntegrationConfig>,
+    pub candid_ui_enabled: bool,
     pub replica_path: PathBuf,
     pub replica_pid_path: PathBuf,
     pub shutdown_controller: Addr<ShutdownController>,
     pub logger: Option<Logger>,
     pub btc_adapter_ready_subscribe: Option<Recipient<BtcAdapterReadySubscribe>>,
     pub canister_http_adapter_ready_subscribe: Option<Recipient<CanisterHttpAdapterReadySubscribe>>,
+    /// Rewrites the output of the replica. If None, the output is passed through as is.
+    pub canister_log_forwarder: Option<Arc<CanisterLogForwarder>>,
+    /// The output of the replica is also appended to this file.
+    pub log_path: PathBuf,
 }
 
 /// A replica actor. Starts the replica, can subscribe to a Ready signal and a
@@ -159,12 +169,15 @@ impl Replica {
                 logger,
                 config.clone(),
                 self.config.bitcoin_integration_config.clone(),
+                self.config.candid_ui_enabled,
                 port,
                 write_port_to,
                 ic_starter_path,
                 replica_path,
                 replica_pid_path,
                 artificial_delay,
+                self.config.canister_log_forwarder.clone(),
+                self.config.log_path.clone(),
                 addr,
                 receiver,
             ),
@@ -291,12 +304,15 @@ fn replica_start_thread(
     logger: Logger,
     config: ReplicaConfig,
     bitcoin_integration_config: Option<BitcoinIntegrationConfig>,
+    candid_ui_enabled: bool,
     port: Option<u16>,
     write_port_to: Option<PathBuf>,
     ic_starter_path: PathBuf,
     replica_path: PathBuf,
     replica_pid_path: PathBuf,
     artificial_delay: u32,
+    canister_log_forwarder: Option<Arc<CanisterLogForwarder>>,
+    log_path: PathBuf,
     addr: Addr<Replica>,
     receiver: Receiver<()>,
 ) -> DfxResult<std::thread::JoinHandle<()>> {
@@ -371,8 +387,10 @@ fn replica_start_thread(
         // at https://gitlab.com/dfinity-lab/core/ic/-/blob/master/ic-os/guestos/rootfs/etc/systemd/system/ic-replica.service
         cmd.env("RUST_MIN_STACK", "8192000");
 
-        cmd.stdout(std::process::Stdio::inherit());
-        cmd.stderr(std::process::Stdio::inherit());
+        cmd.stdout(std::process::Stdio::piped());
+        cmd.stderr(std::process::Stdio::piped());
+        let log_file =
+            RotatingLogFile::shared(&log_path).expect("Could not open replica log file.");
 
         loop {
             if let Some(port_path) = write_port_to.as_ref() {
@@ -381,6 +399,18 @@ fn replica_start_thread(
             let last_start = std::time::Instant::now();
             debug!(logger, "Starting replica...");
             let mut child = cmd.spawn().expect("Could not start replica.");
+            let stdout = child.stdout.take().expect("Replica output is not piped.");
+            let forwarder = canister_log_forwarder.clone();
+            capture_output(stdout, log_file.clone(), move |line| {
+                show_replica_output(forwarder.as_deref(), line, false)
+            })
+            .expect("Could not capture replica output.");
+            let stderr = child.stderr.take().expect("Replica output is not piped.");
+            let forwarder = canister_log_forwarder.clone();
+            capture_output(stderr, log_file.clone(), move |line| {
+                show_replica_output(forwarder.as_deref(), line, true)
+            })
+            .expect("Could not capture replica output.");
 
             std::fs::write(&replica_pid_path, "").expect("Could not write to replica-pid file.");
             std::fs::write(&replica_pid_path, child.id().to_string())
@@ -407,6 +437,7 @@ fn replica_start_thread(
                 port,
                 logger.clone(),
                 bitcoin_integration_config.clone(),
+                candid_ui_enabled,
             ) {
                 error!(logger, "Failed to initialize replica: {:#}", e);
                 let _ = child.kill();
@@ -452,22 +483,44 @@ fn replica_start_thread(
         .map_err(DfxError::from)
 }
 
+/// Shows a line of replica output in the terminal, rewritten if it was printed by a canister.
+fn show_replica_output(forwarder: Option<&CanisterLogForwarder>, line: &str, to_stderr: bool) {
+    let line = match forwarder {
+        Some(forwarder) => match forwarder.format_line(line) {
+            Some(line) => line,
+            None => return,
+        },
+        None => line.to_string(),
+    };
+    // Errors are ignored, so that the output of the replica is still captured
+    // after the terminal went away, for example with `dfx start --background`.
+    let _ = if to_stderr {
+        writeln!(std::io::stderr(), "{line}")
+    } else {
+        writeln!(std::io::stdout(), "{line}")
+    };
+}
+
 fn block_on_initialize_replica(
     port: u16,
     logger: Logger,
     bitcoin_integration_config: Option<BitcoinIntegrationConfig>,
+    candid_ui_enabled: bool,
 ) -> DfxResult {
     Builder::new_current_thread()
         .enable_all()
         .build()
         .unwrap()
-        .block_on(async move { initialize_replica(port, logger, bitcoin_integration_config).await })
+        .block_on(async move {
+            initialize_replica(port, logger, bitcoin_integration_config, candid_ui_enabled).await
+        })
 }
 
 async fn initialize_replica(
     port: u16,
     logger: Logger,
     bitcoin_integration_config: Option<BitcoinIntegrationConfig>,
+    candid_ui_enabled: bool,
 ) -> DfxResult {
     let agent_url = format!("http://localhost:{port}");
 
@@ -479,6 +532,9 @@ async fn initialize_replica(
     if let Some(bitcoin_integration_config) = bitcoin_integration_config {
         initialize_bitcoin_canister(&agent, &logger, bitcoin_integration_config).await?;
     }
+    if candid_ui_enabled {
+        initialize_candid_ui_canister(&agent, &logger).await?;
+    }
 
     info!(logger, "Initialized replica.");
 
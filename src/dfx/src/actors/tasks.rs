@@ -0,0 +1,202 @@
+use crate::actors::icx_proxy::signals::{PortReadySignal, PortReadySubscribe};
+use crate::actors::log_file::{capture_to_log_file, RotatingLogFile, SharedLogFile};
+use crate::actors::shutdown_controller::signals::outbound::Shutdown;
+use crate::actors::shutdown_controller::signals::ShutdownSubscribe;
+use crate::actors::shutdown_controller::ShutdownController;
+use actix::{
+    Actor, ActorContext, ActorFutureExt, Addr, AsyncContext, Context, Handler, Recipient,
+    ResponseActFuture, Running, WrapFuture,
+};
+use crossbeam::channel::{unbounded, Receiver, RecvTimeoutError, Sender};
+use slog::{debug, error, info, warn, Logger};
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// A task of the local network, resolved to the program to run.
+#[derive(Clone)]
+pub struct ScheduledTask {
+    pub name: String,
+    pub interval: Duration,
+    pub program: PathBuf,
+    pub args: Vec<String>,
+}
+
+/// The configuration for the scheduled tasks actor.
+pub struct Config {
+    pub logger: Option<Logger>,
+
+    pub port_ready_subscribe: Recipient<PortReadySubscribe>,
+    pub shutdown_controller: Addr<ShutdownController>,
+
+    pub tasks: Vec<ScheduledTask>,
+    pub log_path: PathBuf,
+}
+
+/// An actor that runs the scheduled tasks of the local network, each in a thread of its own,
+/// once the replica is ready. The output of the tasks goes to the tasks log of the network.
+pub struct ScheduledTasks {
+    logger: Logger,
+    config: Config,
+
+    /// Dropped to stop the threads, which all wait on the same channel.
+    stop_sender: Option<Sender<()>>,
+    thread_joins: Vec<JoinHandle<()>>,
+}
+
+impl ScheduledTasks {
+    pub fn new(config: Config) -> Self {
+        let logger =
+            (config.logger.clone()).unwrap_or_else(|| Logger::root(slog::Discard, slog::o!()));
+        ScheduledTasks {
+            config,
+            stop_sender: None,
+            thread_joins: vec![],
+            logger,
+        }
+    }
+
+    fn start_tasks(&mut self) -> std::io::Result<()> {
+        let log_file = RotatingLogFile::shared(&self.config.log_path)?;
+        let (sender, receiver) = unbounded();
+        for task in &self.config.tasks {
+            info!(
+                self.logger,
+                "Running task {} every {}.",
+                task.name,
+                humantime::format_duration(task.interval)
+            );
+            self.thread_joins.push(task_thread(
+                self.logger.clone(),
+                task.clone(),
+                log_file.clone(),
+                self.config.log_path.clone(),
+                receiver.clone(),
+            )?);
+        }
+        self.stop_sender = Some(sender);
+        Ok(())
+    }
+
+    fn stop_tasks(&mut self) {
+        if self.stop_sender.take().is_some() {
+            debug!(self.logger, "Stopping scheduled tasks...");
+            for join in self.thread_joins.drain(..) {
+                let _ = join.join();
+            }
+        }
+    }
+}
+
+impl Actor for ScheduledTasks {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.config
+            .port_ready_subscribe
+            .do_send(PortReadySubscribe(ctx.address().recipient()));
+
+        self.config
+            .shutdown_controller
+            .do_send(ShutdownSubscribe(ctx.address().recipient::<Shutdown>()));
+    }
+
+    fn stopping(&mut self, _ctx: &mut Self::Context) -> Running {
+        self.stop_tasks();
+
+        Running::Stop
+    }
+}
+
+impl Handler<PortReadySignal> for ScheduledTasks {
+    type Result = ();
+
+    fn handle(&mut self, _msg: PortReadySignal, _ctx: &mut Self::Context) {
+        // The tasks keep their schedule when the replica restarts.
+        if self.stop_sender.is_none() {
+            if let Err(err) = self.start_tasks() {
+                // Threads that were already started exit because the stop sender was dropped.
+                self.thread_joins.clear();
+                error!(self.logger, "Could not start the scheduled tasks: {}", err);
+            }
+        }
+    }
+}
+
+impl Handler<Shutdown> for ScheduledTasks {
+    type Result = ResponseActFuture<Self, Result<(), ()>>;
+
+    fn handle(&mut self, _msg: Shutdown, _ctx: &mut Self::Context) -> Self::Result {
+        Box::pin(async {}.into_actor(self).map(|_, _act, ctx| {
+            ctx.stop();
+            Ok(())
+        }))
+    }
+}
+
+/// Runs a task every interval, until the stop sender is dropped.
+fn task_thread(
+    logger: Logger,
+    task: ScheduledTask,
+    log_file: SharedLogFile,
+    log_path: PathBuf,
+    receiver: Receiver<()>,
+) -> std::io::Result<JoinHandle<()>> {
+    std::thread::Builder::new()
+        .name(format!("task-{}", task.name))
+        .spawn(move || loop {
+            if receiver.recv_timeout(task.interval) != Err(RecvTimeoutError::Timeout) {
+                return;
+            }
+            let _ = log_file
+                .lock()
+                .unwrap()
+                .write_line(&format!("[{}] started", task.name));
+            match run_task(&task, &log_file, &receiver) {
+                Ok(Some(status)) => {
+                    let _ = log_file
+                        .lock()
+                        .unwrap()
+                        .write_line(&format!("[{}] {}", task.name, status));
+                    if !status.success() {
+                        warn!(
+                            logger,
+                            "Task {} failed ({}). Its output is in {}.",
+                            task.name,
+                            status,
+                            log_path.display()
+                        );
+                    }
+                }
+                // Stopped while the task was running.
+                Ok(None) => return,
+                Err(err) => warn!(logger, "Failed to run task {}: {}", task.name, err),
+            }
+        })
+}
+
+/// Returns None if the task was killed because the tasks are stopping.
+fn run_task(
+    task: &ScheduledTask,
+    log_file: &SharedLogFile,
+    receiver: &Receiver<()>,
+) -> std::io::Result<Option<ExitStatus>> {
+    let mut child = Command::new(&task.program)
+        .args(&task.args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    capture_to_log_file(&mut child, log_file)?;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if receiver.recv_timeout(Duration::from_millis(100)) != Err(RecvTimeoutError::Timeout) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+    }
+}
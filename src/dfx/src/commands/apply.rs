@@ -0,0 +1,213 @@
+use crate::lib::agent::create_agent_environment;
+use crate::lib::bundle::{BundleManifest, BUNDLE_FORMAT_VERSION, BUNDLE_MANIFEST_FILE_NAME};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::state_tree::canister_info::{
+    read_state_tree_canister_controllers, read_state_tree_canister_module_hash,
+};
+use crate::util::{blob_from_arguments, get_candid_init_type};
+use anyhow::{anyhow, bail, Context};
+use clap::Parser;
+use dfx_core::canister::{install_canister_wasm, install_mode_to_prompt};
+use dfx_core::identity::CallSender;
+use dfx_core::json::load_json_file;
+use flate2::read::GzDecoder;
+use ic_utils::interfaces::management_canister::builders::InstallMode;
+use sha2::{Digest, Sha256};
+use slog::info;
+use std::path::{Path, PathBuf};
+use tokio::runtime::Runtime;
+
+/// Deploys the canisters in a bundle created by `dfx pack`, without the source of the project.
+/// The canisters are deployed to the network the bundle was packed for,
+/// into the canisters whose ids the bundle records. They must already exist.
+#[derive(Parser)]
+pub struct ApplyOpts {
+    /// Path of the bundle to deploy.
+    bundle: PathBuf,
+
+    /// The expected SHA-256 of the bundle, as printed by `dfx pack`.
+    #[arg(long)]
+    sha256: Option<String>,
+
+    /// Upgrade the canisters even if their wasm did not change.
+    #[arg(long)]
+    upgrade_unchanged: bool,
+
+    /// Skips the confirmation prompts.
+    #[arg(long, short)]
+    yes: bool,
+
+    /// Specify a wallet canister id to install the code with.
+    /// If none specified, the code is installed by the selected identity.
+    #[arg(long)]
+    wallet: Option<String>,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+}
+
+pub fn exec(env: &dyn Environment, opts: ApplyOpts) -> DfxResult {
+    let bundle = dfx_core::fs::read(&opts.bundle)?;
+    if let Some(expected) = &opts.sha256 {
+        let actual = hex::encode(Sha256::digest(&bundle));
+        if !actual.eq_ignore_ascii_case(expected) {
+            bail!(
+                "The SHA-256 of {} is {actual}, but {expected} was expected.",
+                opts.bundle.display()
+            );
+        }
+    }
+    let dir = tempfile::tempdir().context("Failed to create a temporary directory.")?;
+    tar::Archive::new(GzDecoder::new(bundle.as_slice()))
+        .unpack(dir.path())
+        .context("Failed to unpack the bundle.")?;
+    let manifest: BundleManifest = load_json_file(&dir.path().join(BUNDLE_MANIFEST_FILE_NAME))?;
+    if manifest.format_version != BUNDLE_FORMAT_VERSION {
+        bail!(
+            "The bundle has format version {}, but this version of dfx only supports {}. It was packed by dfx {}.",
+            manifest.format_version,
+            BUNDLE_FORMAT_VERSION,
+            manifest.dfx_version
+        );
+    }
+
+    let network_name = opts
+        .network
+        .selected_network_name()
+        .unwrap_or_else(|| manifest.network.clone());
+    if network_name != manifest.network {
+        bail!(
+            "The bundle was packed for network '{}', and cannot be deployed to network '{}'.",
+            manifest.network,
+            network_name
+        );
+    }
+    let env = create_agent_environment(env, Some(network_name))?;
+
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(apply(&env, &manifest, dir.path(), &opts))
+}
+
+async fn apply(
+    env: &dyn Environment,
+    manifest: &BundleManifest,
+    root: &Path,
+    opts: &ApplyOpts,
+) -> DfxResult {
+    let log = env.get_logger();
+    let agent = env.get_agent();
+    let call_sender = CallSender::from(&opts.wallet)
+        .map_err(|e| anyhow!("Failed to determine call sender: {}", e))?;
+    fetch_root_key_if_needed(env).await?;
+
+    // The bundle records the ids of the canisters, so it cannot be deployed into new ones.
+    let mut missing = vec![];
+    for canister in &manifest.canisters {
+        let controllers = read_state_tree_canister_controllers(agent, canister.canister_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to check that canister {} ({}) exists.",
+                    canister.name, canister.canister_id
+                )
+            })?;
+        if controllers.is_none() {
+            missing.push(format!("{} ({})", canister.name, canister.canister_id));
+        }
+    }
+    if !missing.is_empty() {
+        bail!(
+            "Canister(s) {} do not exist on network '{}'. `dfx apply` only deploys into existing canisters: \
+            create them, build the project for their ids and pack it again.",
+            missing.join(", "),
+            manifest.network
+        );
+    }
+
+    for canister in &manifest.canisters {
+        let name = canister.name.as_str();
+        let canister_id = canister.canister_id;
+        let wasm_module = dfx_core::fs::read(&root.join(&canister.wasm))?;
+        let new_hash = Sha256::digest(&wasm_module);
+        if hex::encode(new_hash) != canister.wasm_sha256 {
+            bail!("The SHA-256 of the wasm of canister {name} does not match the manifest.");
+        }
+
+        let installed_module_hash = read_state_tree_canister_module_hash(agent, canister_id)
+            .await
+            .with_context(|| {
+                format!("Failed to read the module hash of canister {name} ({canister_id}).")
+            })?;
+        let mode = match &installed_module_hash {
+            Some(old_hash) if old_hash[..] == new_hash[..] && !opts.upgrade_unchanged => {
+                info!(
+                    log,
+                    "Module hash {} is already installed in canister {name}.",
+                    hex::encode(old_hash)
+                );
+                None
+            }
+            Some(_) => Some(InstallMode::Upgrade {
+                skip_pre_upgrade: Some(false),
+            }),
+            None => Some(InstallMode::Install),
+        };
+
+        if let Some(mode) = mode {
+            let init_type = canister
+                .candid
+                .as_ref()
+                .and_then(|candid| get_candid_init_type(&root.join(candid)));
+            let install_args = blob_from_arguments(
+                Some(env),
+                canister.init_arg.as_deref(),
+                None,
+                Some("idl"),
+                &init_type,
+                true,
+            )?;
+            info!(
+                log,
+                "{} code for canister {name}, with canister ID {canister_id}",
+                install_mode_to_prompt(&mode)
+            );
+            install_canister_wasm(
+                agent,
+                canister_id,
+                Some(name),
+                &install_args,
+                mode,
+                &call_sender,
+                wasm_module,
+                opts.yes,
+            )
+            .await?;
+        }
+
+        if !canister.assets.is_empty() {
+            info!(log, "Uploading assets to canister {name}...");
+            let source_paths: Vec<PathBuf> =
+                canister.assets.iter().map(|dir| root.join(dir)).collect();
+            let source_paths: Vec<&Path> = source_paths.iter().map(|p| p.as_path()).collect();
+            let asset_canister = ic_utils::Canister::builder()
+                .with_agent(agent)
+                .with_canister_id(canister_id)
+                .build()
+                .context("Failed to build asset canister caller.")?;
+            ic_asset::sync(&asset_canister, &source_paths, log)
+                .await
+                .with_context(|| format!("Failed asset sync with canister {name}."))?;
+        }
+    }
+
+    info!(
+        log,
+        "Deployed {} canister(s) to network '{}'.",
+        manifest.canisters.len(),
+        manifest.network
+    );
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crate::lib::environment::{AgentEnvironment, Environment};
 use crate::lib::error::DfxResult;
 use crate::lib::models::canister::CanisterPool;
 use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::operations::canister::fetch_remote_candid_files;
 use clap::Parser;
 use dfx_core::config::model::dfinity::Config;
 use std::path::PathBuf;
@@ -29,12 +30,22 @@ pub struct CanisterBuildOpts {
     #[arg(long)]
     output_env_file: Option<PathBuf>,
 
+    /// Copies the Wasm module, Candid interface and metadata of each built canister to <OUTPUT_DIR>/<canister name>,
+    /// in a layout that does not change across dfx versions. Overrides `output_dir` in dfx.json.
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+
     #[command(flatten)]
     network: NetworkOpt,
+
+    /// Overrides the build profile of dfx.json: debug, release, test-coverage, or one of the profiles of dfx.json.
+    /// test-coverage instruments Rust canisters to collect code coverage.
+    #[arg(long, value_name = "PROFILE")]
+    profile: Option<String>,
 }
 
 pub fn exec(env: &dyn Environment, opts: CanisterBuildOpts) -> DfxResult {
-    let env = create_agent_environment(env, opts.network.to_network_name())?;
+    let env = create_agent_environment(env, opts.network.to_network_name(env))?;
 
     let logger = env.get_logger();
 
@@ -95,8 +106,13 @@ pub fn exec(env: &dyn Environment, opts: CanisterBuildOpts) -> DfxResult {
         BuildConfig::from_config(&config, env.get_network_descriptor().is_playground())?
             .with_build_mode_check(build_mode_check)
             .with_canisters_to_build(canisters_to_build)
-            .with_env_file(env_file);
-    runtime.block_on(canister_pool.build_or_fail(logger, &build_config))?;
+            .with_env_file(env_file)
+            .with_profile(opts.profile.as_deref())?
+            .with_output_dir(opts.output_dir);
+    runtime.block_on(async {
+        fetch_remote_candid_files(&env, &canister_pool).await?;
+        canister_pool.build_or_fail(logger, &build_config).await
+    })?;
 
     Ok(())
 }
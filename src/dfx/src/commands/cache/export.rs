@@ -0,0 +1,67 @@
+use super::{hash_files, CacheManifest, MANIFEST_FILE_NAME};
+use crate::config::cache::DiskBasedCache;
+use crate::lib::environment::Environment;
+use crate::lib::error::{DfxError, DfxResult};
+use anyhow::{bail, Context};
+use clap::Parser;
+use dfx_core::config::cache::{get_bin_cache, is_version_installed};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use slog::info;
+use std::path::PathBuf;
+
+/// Exports a versioned cache into a tarball that can be imported with `dfx cache import`.
+#[derive(Parser)]
+#[command(name = "export")]
+pub struct CacheExportOpts {
+    /// Path of the tarball to create.
+    output: PathBuf,
+
+    /// The version of dfx whose cache to export. Defaults to the version of this dfx.
+    #[arg(long)]
+    version: Option<String>,
+}
+
+pub fn exec(env: &dyn Environment, opts: CacheExportOpts) -> DfxResult {
+    let current_version = env.get_cache().version_str();
+    let version = opts.version.unwrap_or_else(|| current_version.clone());
+    if version == current_version {
+        DiskBasedCache::install(&version).map_err(DfxError::from)?;
+    }
+    if !is_version_installed(&version)? {
+        bail!("Version {version} of dfx is not in the cache.");
+    }
+    let root = get_bin_cache(&version)?;
+
+    let manifest = CacheManifest {
+        version: version.clone(),
+        files: hash_files(&root)?,
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest)?;
+
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, MANIFEST_FILE_NAME, manifest.as_slice())
+        .context("Failed to add the manifest to the tarball.")?;
+    builder
+        .append_dir_all(&version, &root)
+        .with_context(|| format!("Failed to add {} to the tarball.", root.display()))?;
+    let tarball = builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .context("Failed to compress the tarball.")?;
+    dfx_core::fs::write(&opts.output, &tarball)?;
+
+    info!(
+        env.get_logger(),
+        "Exported version {version} of the cache to {}.",
+        opts.output.display()
+    );
+    println!("{}", hex::encode(Sha256::digest(&tarball)));
+    Ok(())
+}
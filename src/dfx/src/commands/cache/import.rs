@@ -0,0 +1,96 @@
+use super::{hash_files, CacheManifest, MANIFEST_FILE_NAME};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::{bail, Context};
+use clap::Parser;
+use dfx_core::config::cache::get_bin_cache;
+use dfx_core::json::load_json_file;
+use flate2::read::GzDecoder;
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use semver::Version;
+use sha2::{Digest, Sha256};
+use slog::info;
+use std::path::{Path, PathBuf};
+
+/// Imports a versioned cache from a tarball created by `dfx cache export`.
+#[derive(Parser)]
+#[command(name = "import")]
+pub struct CacheImportOpts {
+    /// Path of the tarball to import.
+    tarball: PathBuf,
+
+    /// The expected SHA-256 of the tarball, as printed by `dfx cache export`.
+    #[arg(long)]
+    sha256: Option<String>,
+
+    /// Replaces the version if it is already in the cache.
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: CacheImportOpts) -> DfxResult {
+    let tarball = dfx_core::fs::read(&opts.tarball)?;
+    if let Some(expected) = &opts.sha256 {
+        let actual = hex::encode(Sha256::digest(&tarball));
+        if !actual.eq_ignore_ascii_case(expected) {
+            bail!(
+                "The SHA-256 of {} is {actual}, but {expected} was expected.",
+                opts.tarball.display()
+            );
+        }
+    }
+
+    let rand_string: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(12)
+        .map(|byte| byte as char)
+        .collect();
+    let temp_dir = get_bin_cache(&format!("_import_{}", rand_string))?;
+    let result = import(&tarball, &temp_dir, opts.force);
+    if temp_dir.exists() {
+        dfx_core::fs::remove_dir_all(&temp_dir)?;
+    }
+    let version = result?;
+
+    info!(
+        env.get_logger(),
+        "Imported version {version} of dfx into the cache."
+    );
+    Ok(())
+}
+
+fn import(tarball: &[u8], temp_dir: &Path, force: bool) -> DfxResult<String> {
+    dfx_core::fs::create_dir_all(temp_dir)?;
+    tar::Archive::new(GzDecoder::new(tarball))
+        .unpack(temp_dir)
+        .context("Failed to unpack the tarball.")?;
+
+    let manifest: CacheManifest = load_json_file(&temp_dir.join(MANIFEST_FILE_NAME))?;
+    let version = manifest.version;
+    Version::parse(&version)
+        .with_context(|| format!("The tarball contains an invalid version '{version}'."))?;
+
+    let unpacked = temp_dir.join(&version);
+    let files = hash_files(&unpacked)?;
+    for (path, hash) in &manifest.files {
+        match files.get(path) {
+            Some(actual) if actual == hash => {}
+            Some(_) => bail!("The SHA-256 of {path} does not match the manifest."),
+            None => bail!("{path} is listed in the manifest but missing from the tarball."),
+        }
+    }
+    if let Some(path) = files.keys().find(|p| !manifest.files.contains_key(*p)) {
+        bail!("{path} is in the tarball but not listed in the manifest.");
+    }
+
+    let destination = get_bin_cache(&version)?;
+    if destination.exists() {
+        if !force {
+            bail!("Version {version} of dfx is already in the cache. Use --force to replace it.");
+        }
+        dfx_core::fs::remove_dir_all(&destination)?;
+    }
+    dfx_core::fs::rename(&unpacked, &destination)?;
+    Ok(version)
+}
@@ -3,7 +3,7 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use clap::Parser;
 use dfx_core::config::cache::list_versions;
-use std::io::Write;
+use slog::info;
 
 /// Lists installed and used version.
 #[derive(Parser)]
@@ -11,29 +11,20 @@ use std::io::Write;
 pub struct CacheListOpts {}
 
 pub fn exec(env: &dyn Environment, _opts: CacheListOpts) -> DfxResult {
-    let mut current_printed = false;
+    let log = env.get_logger();
     let current_version = env.get_version();
     let mut all_versions = list_versions()?;
     all_versions.sort();
-    for version in all_versions {
-        if current_version == &version {
-            current_printed = true;
-            // Same version, prefix with `*`.
-            std::io::stderr().flush()?;
-            print!("{}", version);
-            std::io::stdout().flush()?;
-            eprintln!(" *");
-        } else {
-            eprintln!("{}", version);
-        }
+    // Only the versions go to stdout, so that scripts can read them.
+    for version in &all_versions {
+        println!("{}", version);
     }
 
-    if !current_printed {
+    if all_versions.contains(current_version) {
+        info!(log, "Current version: {} *", current_version);
+    } else {
         // The current version wasn't printed, so it's not in the cache.
-        std::io::stderr().flush()?;
-        print!("{}", dfx_version());
-        std::io::stdout().flush()?;
-        eprintln!(" [missing]");
+        info!(log, "Current version: {} [missing]", dfx_version());
     }
 
     Ok(())
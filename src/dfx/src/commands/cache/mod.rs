@@ -1,12 +1,32 @@
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use anyhow::Context;
 use clap::Parser;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+use walkdir::WalkDir;
 
 mod delete;
+mod export;
+mod import;
 mod install;
 mod list;
 mod show;
 
+/// Name of the file listing the contents of an exported cache.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Describes the contents of a tarball created by `dfx cache export`.
+#[derive(Serialize, Deserialize)]
+struct CacheManifest {
+    /// The dfx version of the exported cache.
+    version: String,
+    /// SHA-256 of each file, keyed by its path relative to the version directory.
+    files: BTreeMap<String, String>,
+}
+
 /// Manages the dfx version cache.
 #[derive(Parser)]
 #[command(name = "cache")]
@@ -18,6 +38,8 @@ pub struct CacheOpts {
 #[derive(Parser)]
 pub enum SubCommand {
     Delete(delete::CacheDeleteOpts),
+    Export(export::CacheExportOpts),
+    Import(import::CacheImportOpts),
     Install(install::CacheInstall),
     List(list::CacheListOpts),
     Show(show::CacheShowOpts),
@@ -26,8 +48,30 @@ pub enum SubCommand {
 pub fn exec(env: &dyn Environment, opts: CacheOpts) -> DfxResult {
     match opts.subcmd {
         SubCommand::Delete(v) => delete::exec(env, v),
+        SubCommand::Export(v) => export::exec(env, v),
+        SubCommand::Import(v) => import::exec(env, v),
         SubCommand::Install(v) => install::exec(env, v),
         SubCommand::List(v) => list::exec(env, v),
         SubCommand::Show(v) => show::exec(env, v),
     }
 }
+
+/// Computes the SHA-256 of every file below `dir`, keyed by its `/`-separated relative path.
+fn hash_files(dir: &Path) -> DfxResult<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.with_context(|| format!("Failed to walk {}.", dir.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(dir)?;
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let content = dfx_core::fs::read(entry.path())?;
+        files.insert(relative, hex::encode(Sha256::digest(content)));
+    }
+    Ok(files)
+}
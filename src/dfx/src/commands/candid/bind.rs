@@ -0,0 +1,52 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::util::clap::parsers::file_parser;
+use candid::pretty::candid::compile;
+use candid_parser::utils::CandidSource;
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(ValueEnum, Clone, Copy)]
+pub enum BindingTarget {
+    Did,
+    Js,
+    Ts,
+    Mo,
+    Rs,
+}
+
+/// Generates language bindings from a Candid file.
+#[derive(Parser)]
+pub struct CandidBindOpts {
+    /// The .did file to generate bindings for.
+    #[arg(value_parser = file_parser)]
+    did: PathBuf,
+
+    /// The language to generate bindings for.
+    #[arg(long, short, value_enum)]
+    target: BindingTarget,
+
+    /// Writes the bindings to this file instead of stdout.
+    #[arg(long)]
+    outfile: Option<PathBuf>,
+}
+
+pub fn exec(_env: &dyn Environment, opts: CandidBindOpts) -> DfxResult {
+    let (env, actor) = CandidSource::File(&opts.did).load()?;
+    let content = match opts.target {
+        BindingTarget::Did => compile(&env, &actor),
+        BindingTarget::Js => candid_parser::bindings::javascript::compile(&env, &actor),
+        BindingTarget::Ts => candid_parser::bindings::typescript::compile(&env, &actor),
+        BindingTarget::Mo => candid_parser::bindings::motoko::compile(&env, &actor),
+        BindingTarget::Rs => {
+            let config = candid_parser::bindings::rust::Config::new();
+            candid_parser::bindings::rust::compile(&config, &env, &actor)
+        }
+    };
+    if let Some(outfile) = opts.outfile {
+        dfx_core::fs::write(&outfile, content)?;
+    } else {
+        println!("{}", content);
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::util::clap::parsers::file_parser;
+use anyhow::Context;
+use candid_parser::utils::{service_compatible, CandidSource};
+use clap::Parser;
+use slog::info;
+use std::path::PathBuf;
+
+/// Type checks a Candid file.
+/// Optionally checks that the service is a backwards-compatible upgrade of a previous version.
+#[derive(Parser)]
+pub struct CandidCheckOpts {
+    /// The .did file to check.
+    #[arg(value_parser = file_parser)]
+    did: PathBuf,
+
+    /// A previous version of the .did file. Fails if the checked service is not a subtype of it.
+    #[arg(long, value_parser = file_parser)]
+    previous: Option<PathBuf>,
+}
+
+pub fn exec(env: &dyn Environment, opts: CandidCheckOpts) -> DfxResult {
+    CandidSource::File(&opts.did).load()?;
+    if let Some(previous) = &opts.previous {
+        service_compatible(CandidSource::File(&opts.did), CandidSource::File(previous))
+            .with_context(|| {
+                format!(
+                    "{} is not a backwards-compatible upgrade of {}.",
+                    opts.did.display(),
+                    previous.display()
+                )
+            })?;
+    }
+    info!(env.get_logger(), "{} is valid.", opts.did.display());
+    Ok(())
+}
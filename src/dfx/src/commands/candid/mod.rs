@@ -0,0 +1,30 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+
+mod bind;
+mod check;
+mod random;
+
+/// Works with Candid interface files, using the Candid version bundled with dfx.
+#[derive(Parser)]
+#[command(name = "candid")]
+pub struct CandidOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+pub enum SubCommand {
+    Bind(bind::CandidBindOpts),
+    Check(check::CandidCheckOpts),
+    Random(random::CandidRandomOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: CandidOpts) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::Bind(v) => bind::exec(env, v),
+        SubCommand::Check(v) => check::exec(env, v),
+        SubCommand::Random(v) => random::exec(env, v),
+    }
+}
@@ -0,0 +1,86 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::util::clap::parsers::file_parser;
+use anyhow::{anyhow, bail, Context};
+use candid::TypeEnv;
+use candid_parser::configs::Configs;
+use candid_parser::types::IDLTypes;
+use candid_parser::typing::ast_to_type;
+use candid_parser::utils::CandidSource;
+use clap::Parser;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::path::PathBuf;
+
+/// Generates random Candid values.
+/// The values are generated either for the arguments of a method, or for a tuple of types.
+#[derive(Parser)]
+pub struct CandidRandomOpts {
+    /// The .did file providing the service and type definitions.
+    #[arg(long, value_parser = file_parser)]
+    types: Option<PathBuf>,
+
+    /// Generates the arguments of this method of the service in the .did file.
+    #[arg(long, requires("types"), conflicts_with("args"))]
+    method: Option<String>,
+
+    /// Generates values for this tuple of types, e.g. '(nat, opt text)'.
+    /// Types defined in the .did file can be referenced.
+    #[arg(long, required_unless_present("method"))]
+    args: Option<String>,
+
+    /// Configuration for the random generator, in dhall format, e.g. '{ text = "name" }'.
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Seed for the random generator. The same seed always produces the same values.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Specifies the format for displaying the generated values.
+    #[arg(long, default_value = "idl", value_parser = ["idl", "raw"])]
+    output: String,
+}
+
+pub fn exec(_env: &dyn Environment, opts: CandidRandomOpts) -> DfxResult {
+    let (env, actor) = match &opts.types {
+        Some(path) => CandidSource::File(path).load()?,
+        None => (TypeEnv::new(), None),
+    };
+    let types = if let Some(method) = &opts.method {
+        let actor = actor.ok_or_else(|| anyhow!("The .did file has no main service type."))?;
+        env.get_method(&actor, method)?.args.clone()
+    } else if let Some(args) = &opts.args {
+        let idl_types: IDLTypes = args.parse()?;
+        idl_types
+            .args
+            .iter()
+            .map(|ty| ast_to_type(&env, ty))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        bail!("Either --method or --args must be specified.");
+    };
+
+    let config = match opts.config.as_deref() {
+        None | Some("") => "{=}",
+        Some(config) => config,
+    };
+    let config = Configs::from_dhall(config).context("Failed to parse random config.")?;
+    let mut rng = match opts.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let seed: Vec<u8> = (0..2048).map(|_| rng.gen::<u8>()).collect();
+    let args = candid_parser::random::any(&seed, &config, &env, &types)
+        .context("Failed to generate random values.")?;
+
+    if opts.output == "raw" {
+        let bytes = args
+            .to_bytes_with_types(&env, &types)
+            .context("Failed to serialize the generated values.")?;
+        println!("{}", hex::encode(bytes));
+    } else {
+        println!("{}", args);
+    }
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use crate::commands::canister::call::{do_wallet_call, CallIn};
+use crate::lib::cycles_cost::{ingress_reception_fee, instructions_for_execution_fee};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::operations::canister::{
+    get_canister_status, get_local_cid_and_candid_path, get_named_cid_and_candid_path,
+};
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::util::clap::argument_from_cli::ArgumentFromCliPositionalOpt;
+use crate::util::{blob_from_arguments, fetch_remote_did_file, get_candid_type};
+use anyhow::{bail, Context};
+use candid::Principal;
+use candid_parser::utils::CandidSource;
+use clap::Parser;
+use dfx_core::canister::build_wallet_canister;
+use dfx_core::identity::CallSender;
+use num_traits::ToPrimitive;
+use slog::info;
+use std::time::{Duration, Instant};
+
+/// Measures the cycles consumed by update calls to a canister method, and the instructions they execute.
+/// The cycles are measured as the difference of the canister's balance before and after each call,
+/// so the selected identity or the wallet must be a controller of the canister.
+/// The instructions are estimated from the cycles with the fees of a 13-node subnet.
+#[derive(Parser)]
+pub struct CanisterBenchmarkOpts {
+    /// Specifies the name or id of the canister to call.
+    canister_name: String,
+
+    /// Specifies the method name to call on the canister.
+    method_name: String,
+
+    #[command(flatten)]
+    argument_from_cli: ArgumentFromCliPositionalOpt,
+
+    /// The number of calls to measure.
+    #[arg(long, short = 'n', default_value = "10", value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// The number of calls to make before measuring, e.g. to initialize lazily allocated state.
+    #[arg(long, default_value = "1")]
+    warmup: u32,
+}
+
+pub async fn exec(
+    env: &dyn Environment,
+    opts: CanisterBenchmarkOpts,
+    call_sender: &CallSender,
+) -> DfxResult {
+    let agent = env.get_agent();
+    let log = env.get_logger();
+    fetch_root_key_if_needed(env).await?;
+
+    let canister_id_store = env.get_canister_id_store()?;
+    let callee_canister = opts.canister_name.as_str();
+    let method_name = opts.method_name.as_str();
+    let (canister_id, maybe_local_candid_path) = match Principal::from_text(callee_canister) {
+        Ok(id) => match canister_id_store.get_name(callee_canister) {
+            Some(canister_name) => get_local_cid_and_candid_path(env, canister_name, Some(id))?,
+            None => (id, None),
+        },
+        Err(_) => get_named_cid_and_candid_path(env, &canister_id_store, callee_canister)?,
+    };
+    let method_type = if let Some(did) = fetch_remote_did_file(agent, canister_id).await {
+        get_candid_type(CandidSource::Text(&did), method_name)
+    } else {
+        maybe_local_candid_path
+            .and_then(|path| get_candid_type(CandidSource::File(&path), method_name))
+    };
+    if method_type.as_ref().map_or(false, |(_, f)| f.is_query()) {
+        bail!("{method_name} is a query method. Query calls do not consume cycles.");
+    }
+
+    let (argument_from_cli, argument_type) = opts.argument_from_cli.get_argument_and_type()?;
+    let arg_value = blob_from_arguments(
+        Some(env),
+        argument_from_cli.as_deref(),
+        None,
+        argument_type.as_deref(),
+        &method_type,
+        false,
+    )?;
+
+    let call = || async {
+        match call_sender {
+            CallSender::SelectedId => agent
+                .update(&canister_id, method_name)
+                .with_arg(arg_value.clone())
+                .call_and_wait()
+                .await
+                .with_context(|| format!("Failed to call {method_name}.")),
+            CallSender::Wallet(wallet_id) => {
+                let wallet = build_wallet_canister(*wallet_id, agent).await?;
+                do_wallet_call(
+                    &wallet,
+                    &CallIn {
+                        canister: canister_id,
+                        method_name: method_name.to_string(),
+                        args: arg_value.clone(),
+                        cycles: 0,
+                    },
+                )
+                .await
+                .with_context(|| format!("Failed to call {method_name} through the wallet."))
+            }
+        }
+    };
+    // Calls through the wallet are inter-canister calls, for which the canister pays no ingress fees.
+    let ingress_fee = match call_sender {
+        CallSender::SelectedId => ingress_reception_fee(method_name, &arg_value),
+        CallSender::Wallet(_) => 0,
+    };
+
+    for _ in 0..opts.warmup {
+        call().await?;
+    }
+
+    info!(
+        log,
+        "Measuring {} calls of {} on canister {}...", opts.iterations, method_name, canister_id
+    );
+    let mut cycles = Vec::with_capacity(opts.iterations as usize);
+    let mut latencies = Vec::with_capacity(opts.iterations as usize);
+    for _ in 0..opts.iterations {
+        let before = balance(env, canister_id, call_sender).await?;
+        let start = Instant::now();
+        call().await?;
+        latencies.push(start.elapsed());
+        let after = balance(env, canister_id, call_sender).await?;
+        cycles.push(before.saturating_sub(after));
+    }
+
+    let instructions = Statistics::from(
+        cycles
+            .iter()
+            .map(|cycles| instructions_for_execution_fee(cycles.saturating_sub(ingress_fee)))
+            .collect::<Vec<_>>(),
+    );
+    let cycles = Statistics::from(cycles);
+    let latencies = Statistics::from(latencies);
+    println!(
+        "Cycles:  mean {}, median {}, p95 {}, min {}, max {}",
+        cycles.mean, cycles.median, cycles.p95, cycles.min, cycles.max
+    );
+    println!(
+        "Instructions (estimated): mean {}, median {}, p95 {}, min {}, max {}",
+        instructions.mean,
+        instructions.median,
+        instructions.p95,
+        instructions.min,
+        instructions.max
+    );
+    println!(
+        "Latency: mean {:.3?}, median {:.3?}, p95 {:.3?}, min {:.3?}, max {:.3?}",
+        latencies.mean, latencies.median, latencies.p95, latencies.min, latencies.max
+    );
+    Ok(())
+}
+
+async fn balance(
+    env: &dyn Environment,
+    canister_id: Principal,
+    call_sender: &CallSender,
+) -> DfxResult<u128> {
+    let status = get_canister_status(env, canister_id, call_sender).await?;
+    status
+        .cycles
+        .0
+        .to_u128()
+        .context("The cycle balance does not fit in a u128.")
+}
+
+struct Statistics<T> {
+    mean: T,
+    median: T,
+    p95: T,
+    min: T,
+    max: T,
+}
+
+impl<T: Copy + Ord> Statistics<T> {
+    fn new(mut samples: Vec<T>, mean: T) -> Self {
+        samples.sort();
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Statistics {
+            mean,
+            median: percentile(50),
+            p95: percentile(95),
+            min: samples[0],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl From<Vec<u128>> for Statistics<u128> {
+    fn from(samples: Vec<u128>) -> Self {
+        let mean = samples.iter().sum::<u128>() / samples.len() as u128;
+        Statistics::new(samples, mean)
+    }
+}
+
+impl From<Vec<Duration>> for Statistics<Duration> {
+    fn from(samples: Vec<Duration>) -> Self {
+        let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+        Statistics::new(samples, mean)
+    }
+}
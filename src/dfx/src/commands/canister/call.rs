@@ -1,12 +1,23 @@
+use crate::lib::cycles_cost::{
+    ingress_reception_fee, update_execution_fee, xnet_call_fee,
+    MAX_INSTRUCTIONS_PER_UPDATE_MESSAGE, REFERENCE_SUBNET_SIZE,
+    TEN_UPDATE_INSTRUCTIONS_EXECUTION_FEE,
+};
 use crate::lib::diagnosis::DiagnosedError;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
-use crate::lib::operations::canister::get_local_cid_and_candid_path;
+use crate::lib::operations::canister::{
+    get_local_cid_and_candid_path, get_named_cid_and_candid_path,
+};
+use crate::lib::requests::{
+    add_pending_request, now_secs, selected_identity_name, wait_for_reply, PendingRequest,
+};
 use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::scenario::{is_recording, record_call, RecordedCall};
 use crate::util::clap::argument_from_cli::ArgumentFromCliPositionalOpt;
-use crate::util::clap::parsers::cycle_amount_parser;
+use crate::util::clap::parsers::{cycle_amount_parser, duration_parser};
 use crate::util::{blob_from_arguments, fetch_remote_did_file, get_candid_type, print_idl_blob};
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use candid::Principal as CanisterId;
 use candid::{CandidType, Decode, Deserialize, Principal};
 use candid_parser::utils::CandidSource;
@@ -19,10 +30,14 @@ use ic_utils::interfaces::management_canister::builders::{CanisterInstall, Canis
 use ic_utils::interfaces::management_canister::MgmtMethod;
 use ic_utils::interfaces::wallet::{CallForwarder, CallResult};
 use ic_utils::interfaces::WalletCanister;
-use slog::warn;
+use slog::{info, warn, Logger};
 use std::option::Option;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Messages to canisters are limited to 2 MiB, including everything besides the argument.
+const MAX_ARGUMENT_SIZE: usize = 2_000_000;
 
 /// Calls a method on a deployed canister.
 #[derive(Parser)]
@@ -38,20 +53,32 @@ pub struct CanisterCallOpts {
     argument_from_cli: ArgumentFromCliPositionalOpt,
 
     /// Specifies not to wait for the result of the call to be returned by polling the replica.
-    /// Instead return a response ID.
+    /// Instead return a response ID, which `dfx request` can track, resume, or retry.
     #[arg(long)]
     r#async: bool,
 
+    /// Polls the status of an update call at this interval, e.g. 500ms or 2s,
+    /// instead of backing off exponentially.
+    #[arg(long, conflicts_with("async"), conflicts_with("query"), value_parser = duration_parser)]
+    poll_interval: Option<Duration>,
+
     /// Sends a query request to a canister instead of an update request.
     #[arg(long, conflicts_with("async"))]
     query: bool,
 
     /// Sends an update request to a canister. This is the default if the method is not a query method.
+    /// Calling a query method this way returns a certified response, agreed on by the replicas of the subnet.
     #[arg(long, conflicts_with("async"), conflicts_with("query"))]
     update: bool,
 
     /// Specifies the config for generating random argument.
-    #[arg(long, conflicts_with("argument"), conflicts_with("argument_file"))]
+    #[arg(
+        long,
+        conflicts_with("argument"),
+        conflicts_with("argument_file"),
+        conflicts_with("arg_file_as_blob"),
+        conflicts_with("arg_file_as_candid")
+    )]
     random: Option<String>,
 
     /// Specifies the format for displaying the method's return result.
@@ -69,18 +96,34 @@ pub struct CanisterCallOpts {
     /// for project canisters.
     #[arg(long)]
     candid: Option<PathBuf>,
+
+    /// Fails the command if the call takes longer than this, e.g. 500ms or 2s.
+    /// The response is still displayed.
+    #[arg(long, conflicts_with("async"), value_parser = duration_parser)]
+    max_latency: Option<Duration>,
+
+    /// Fails the command if the encoded response is larger than this number of bytes.
+    /// The response is still displayed.
+    #[arg(long, conflicts_with("async"))]
+    max_response_bytes: Option<u64>,
+
+    /// Before sending an update call, shows an estimate of the cycles it costs
+    /// and which canisters are charged for it.
+    #[arg(long, conflicts_with("query"))]
+    estimate_cycles: bool,
 }
 
 #[derive(Clone, CandidType, Deserialize, Debug)]
-struct CallIn<TCycles = u128> {
-    canister: CanisterId,
-    method_name: String,
+pub struct CallIn<TCycles = u128> {
+    pub canister: CanisterId,
+    pub method_name: String,
     #[serde(with = "serde_bytes")]
-    args: Vec<u8>,
-    cycles: TCycles,
+    pub args: Vec<u8>,
+    pub cycles: TCycles,
 }
 
-async fn do_wallet_call(wallet: &WalletCanister<'_>, args: &CallIn) -> DfxResult<Vec<u8>> {
+/// Calls a method through the wallet, and returns the response of the method.
+pub async fn do_wallet_call(wallet: &WalletCanister<'_>, args: &CallIn) -> DfxResult<Vec<u8>> {
     // todo change to wallet.call when IDLValue implements ArgumentDecoder
     let builder = if wallet.version_supports_u128_cycles() {
         wallet.update("wallet_call128").with_arg(args)
@@ -206,6 +249,130 @@ pub fn get_effective_canister_id(
     }
 }
 
+/// Shows the cycles that an update call costs before the instructions it executes, for --estimate-cycles.
+fn print_cost_estimate(
+    log: &Logger,
+    call_sender: &CallSender,
+    callee_canister: &str,
+    canister_id: CanisterId,
+    method_name: &str,
+    arg_value: &[u8],
+    cycles: u128,
+) -> DfxResult {
+    let execution = ("Update execution".to_string(), update_execution_fee(0));
+    let mut charges = vec![];
+    match call_sender {
+        CallSender::SelectedId => {
+            charges.push((
+                callee_canister.to_string(),
+                vec![
+                    (
+                        format!(
+                            "Ingress message reception ({} bytes)",
+                            method_name.len() + arg_value.len()
+                        ),
+                        ingress_reception_fee(method_name, arg_value),
+                    ),
+                    execution.clone(),
+                ],
+            ));
+        }
+        CallSender::Wallet(wallet_id) => {
+            let wallet_method = "wallet_call128";
+            let wallet_arg = candid::Encode!(&CallIn {
+                canister: canister_id,
+                method_name: method_name.to_string(),
+                args: arg_value.to_vec(),
+                cycles,
+            })?;
+            let mut wallet_charges = vec![
+                (
+                    format!(
+                        "Ingress message reception ({} bytes)",
+                        wallet_method.len() + wallet_arg.len()
+                    ),
+                    ingress_reception_fee(wallet_method, &wallet_arg),
+                ),
+                execution.clone(),
+                (
+                    format!(
+                        "Call to {callee_canister} ({} bytes)",
+                        method_name.len() + arg_value.len()
+                    ),
+                    xnet_call_fee(method_name, arg_value),
+                ),
+            ];
+            if cycles > 0 {
+                wallet_charges.push(("Cycles sent with the call".to_string(), cycles));
+            }
+            charges.push((format!("the wallet {wallet_id}"), wallet_charges));
+            charges.push((callee_canister.to_string(), vec![execution]));
+        }
+    }
+
+    let mut estimate =
+        format!("Estimated cost of the call in cycles, on a {REFERENCE_SUBNET_SIZE}-node subnet:");
+    for (payer, items) in charges {
+        let total: u128 = items.iter().map(|(_, amount)| amount).sum();
+        let width = items
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+        estimate.push_str(&format!("\n  Charged to {payer}:"));
+        for (label, amount) in &items {
+            estimate.push_str(&format!("\n    {label:<width$}  {amount:>15}"));
+        }
+        estimate.push_str(&format!(
+            "\n    {:<width$}  {total:>15}, plus {TEN_UPDATE_INSTRUCTIONS_EXECUTION_FEE} per 10 instructions executed",
+            "Total"
+        ));
+    }
+    info!(
+        log,
+        "{}\nThe cycles for {} instructions are reserved before each execution, and the unused ones are refunded. \
+        Use `dfx canister benchmark` to measure the actual cost.",
+        estimate,
+        MAX_INSTRUCTIONS_PER_UPDATE_MESSAGE
+    );
+    Ok(())
+}
+
+/// Enforces --max-latency and --max-response-bytes.
+fn check_call_budget(
+    env: &dyn Environment,
+    opts: &CanisterCallOpts,
+    latency: Duration,
+    response_bytes: usize,
+) -> DfxResult {
+    if opts.max_latency.is_none() && opts.max_response_bytes.is_none() {
+        return Ok(());
+    }
+    info!(
+        env.get_logger(),
+        "The call took {:.3?} and returned {} bytes.", latency, response_bytes
+    );
+    if let Some(max_latency) = opts.max_latency {
+        if latency > max_latency {
+            bail!(
+                "The call took {:.3?}, which exceeds the maximum latency of {:?}.",
+                latency,
+                max_latency
+            );
+        }
+    }
+    if let Some(max_response_bytes) = opts.max_response_bytes {
+        if response_bytes as u64 > max_response_bytes {
+            bail!(
+                "The response has {} bytes, which exceeds the maximum of {} bytes.",
+                response_bytes,
+                max_response_bytes
+            );
+        }
+    }
+    Ok(())
+}
+
 pub async fn exec(
     env: &dyn Environment,
     opts: CanisterCallOpts,
@@ -226,13 +393,10 @@ pub async fn exec(
                 (id, None)
             }
         }
-        Err(_) => {
-            let canister_id = canister_id_store.get(callee_canister)?;
-            get_local_cid_and_candid_path(env, callee_canister, Some(canister_id))?
-        }
+        Err(_) => get_named_cid_and_candid_path(env, &canister_id_store, callee_canister)?,
     };
-    let method_type = if let Some(path) = opts.candid {
-        get_candid_type(CandidSource::File(&path), method_name)
+    let method_type = if let Some(path) = &opts.candid {
+        get_candid_type(CandidSource::File(path), method_name)
     } else if let Some(did) = fetch_remote_did_file(agent, canister_id).await {
         get_candid_type(CandidSource::Text(&did), method_name)
     } else if let Some(path) = maybe_local_candid_path {
@@ -292,6 +456,17 @@ pub async fn exec(
         false,
     )?;
 
+    if arg_value.len() > MAX_ARGUMENT_SIZE {
+        return Err(DiagnosedError::new(
+            format!(
+                "The argument is {} bytes, more than the {MAX_ARGUMENT_SIZE} bytes that fit in a call.",
+                arg_value.len()
+            ),
+            "Split the payload into chunks, and send them in separate calls.".to_string(),
+        ))
+        .context("Argument too large.");
+    }
+
     // amount has been validated by cycle_amount_validator
     let cycles = opts.with_cycles.unwrap_or(0);
 
@@ -300,6 +475,24 @@ pub async fn exec(
         To figure out the id of your wallet, run 'dfx identity get-wallet (--network ic)'.".to_string())).context("Function caller is not a canister.");
     }
 
+    if opts.estimate_cycles {
+        if is_query {
+            info!(env.get_logger(), "Query calls do not cost cycles.");
+        } else {
+            print_cost_estimate(
+                env.get_logger(),
+                call_sender,
+                callee_canister,
+                canister_id,
+                method_name,
+                &arg_value,
+                cycles,
+            )?;
+        }
+    }
+
+    let recorded_argument = arg_value.clone();
+    let call_start = Instant::now();
     if is_query {
         let blob = match call_sender {
             CallSender::SelectedId => {
@@ -333,8 +526,18 @@ pub async fn exec(
             }
         };
         print_idl_blob(&blob, output_type, &method_type)?;
+        check_call_budget(env, &opts, call_start.elapsed(), blob.len())?;
+        record(
+            env,
+            call_sender,
+            canister_id,
+            method_name,
+            true,
+            recorded_argument,
+            blob,
+        )?;
     } else if opts.r#async {
-        let request_id = match call_sender {
+        let (request_id, pending_request) = match call_sender {
             CallSender::SelectedId => {
                 let effective_canister_id = get_effective_canister_id(
                     is_management_canister,
@@ -342,24 +545,48 @@ pub async fn exec(
                     &arg_value,
                     canister_id,
                 )?;
-                agent
+                let request_id = agent
                     .update(&canister_id, method_name)
                     .with_effective_canister_id(effective_canister_id)
-                    .with_arg(arg_value)
+                    .with_arg(arg_value.clone())
                     .call()
                     .await
-                    .context("Failed update call.")?
+                    .context("Failed update call.")?;
+                let pending_request = PendingRequest {
+                    canister_id,
+                    effective_canister_id,
+                    method_name: method_name.to_string(),
+                    arg: arg_value,
+                    wallet_callee: None,
+                    identity: selected_identity_name(env),
+                    sent_at: now_secs(),
+                    unknown_checked_at: None,
+                };
+                (request_id, pending_request)
             }
             CallSender::Wallet(wallet_id) => {
                 let wallet = build_wallet_canister(*wallet_id, agent).await?;
                 let mut args = Argument::default();
-                args.set_raw_arg(arg_value);
+                args.set_raw_arg(arg_value.clone());
 
-                request_id_via_wallet_call(&wallet, canister_id, method_name, args, cycles)
-                    .await
-                    .context("Failed request via wallet.")?
+                let request_id =
+                    request_id_via_wallet_call(&wallet, canister_id, method_name, args, cycles)
+                        .await
+                        .context("Failed request via wallet.")?;
+                let pending_request = PendingRequest {
+                    canister_id: *wallet_id,
+                    effective_canister_id: *wallet_id,
+                    method_name: method_name.to_string(),
+                    arg: arg_value,
+                    wallet_callee: Some(canister_id),
+                    identity: selected_identity_name(env),
+                    sent_at: now_secs(),
+                    unknown_checked_at: None,
+                };
+                (request_id, pending_request)
             }
         };
+        add_pending_request(env, &request_id, pending_request)?;
         eprint!("Request ID: ");
         println!("0x{}", String::from(request_id));
     } else {
@@ -371,13 +598,21 @@ pub async fn exec(
                     &arg_value,
                     canister_id,
                 )?;
-                agent
+                let call = agent
                     .update(&canister_id, method_name)
                     .with_effective_canister_id(effective_canister_id)
-                    .with_arg(arg_value)
-                    .call_and_wait()
-                    .await
-                    .context("Failed update call.")?
+                    .with_arg(arg_value);
+                if let Some(poll_interval) = opts.poll_interval {
+                    let request_id = call.call().await.context("Failed update call.")?;
+                    wait_for_reply(env, &request_id, effective_canister_id, Some(poll_interval))
+                        .await
+                        .context("Failed update call.")?
+                } else {
+                    call.call_and_wait().await.context("Failed update call.")?
+                }
+            }
+            CallSender::Wallet(_) if opts.poll_interval.is_some() => {
+                bail!("--poll-interval is not supported for calls proxied by a wallet.")
             }
             CallSender::Wallet(wallet_id) => {
                 let wallet = build_wallet_canister(*wallet_id, agent).await?;
@@ -396,7 +631,58 @@ pub async fn exec(
         };
 
         print_idl_blob(&blob, output_type, &method_type)?;
+        check_call_budget(env, &opts, call_start.elapsed(), blob.len())?;
+        record(
+            env,
+            call_sender,
+            canister_id,
+            method_name,
+            false,
+            recorded_argument,
+            blob,
+        )?;
     }
 
     Ok(())
 }
+
+/// Adds the call to the scenario being recorded with `dfx record`, if any.
+fn record(
+    env: &dyn Environment,
+    call_sender: &CallSender,
+    canister_id: CanisterId,
+    method_name: &str,
+    query: bool,
+    argument: Vec<u8>,
+    response: Vec<u8>,
+) -> DfxResult {
+    if !is_recording(env) {
+        return Ok(());
+    }
+    let Some(identity) = env.get_selected_identity() else {
+        return Ok(());
+    };
+    if call_sender != &CallSender::SelectedId {
+        warn!(
+            env.get_logger(),
+            "Calls through a wallet are not recorded by `dfx record`."
+        );
+        return Ok(());
+    }
+    let canister_id_store = env.get_canister_id_store()?;
+    let canister = canister_id_store
+        .get_name_in_project(&canister_id.to_text())
+        .cloned()
+        .unwrap_or_else(|| canister_id.to_text());
+    record_call(
+        env,
+        RecordedCall {
+            canister,
+            method: method_name.to_string(),
+            identity: identity.clone(),
+            query,
+            argument,
+            response,
+        },
+    )
+}
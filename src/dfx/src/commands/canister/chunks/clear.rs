@@ -0,0 +1,34 @@
+use super::selected_canisters;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::operations::canister;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use clap::Parser;
+use dfx_core::identity::CallSender;
+use slog::info;
+
+/// Removes all chunks stored for a canister, freeing the memory they use.
+#[derive(Parser)]
+pub struct ClearOpts {
+    /// Specifies the name or id of the canister. You must specify either a canister name/id or the --all flag.
+    canister: Option<String>,
+
+    /// Clears the chunk stores of all of the canisters configured in the dfx.json file.
+    #[arg(long, required_unless_present("canister"))]
+    all: bool,
+}
+
+pub async fn exec(env: &dyn Environment, opts: ClearOpts, call_sender: &CallSender) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+
+    for (canister, canister_id) in selected_canisters(env, opts.canister.as_deref(), opts.all)? {
+        info!(
+            env.get_logger(),
+            "Clearing the chunk store of canister {}, with canister_id {}",
+            canister,
+            canister_id.to_text(),
+        );
+        canister::clear_chunk_store(env, canister_id, call_sender).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use super::selected_canisters;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::operations::canister;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use byte_unit::Byte;
+use clap::Parser;
+use dfx_core::identity::CallSender;
+use serde::Serialize;
+
+/// The maximum size of a chunk in the chunk store.
+const MAX_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Lists the hashes of the chunks stored for a canister.
+/// The management canister does not report chunk sizes, so the stored bytes are an upper bound of 1 MiB per chunk.
+#[derive(Parser)]
+pub struct ListOpts {
+    /// Specifies the name or id of the canister. You must specify either a canister name/id or the --all flag.
+    canister: Option<String>,
+
+    /// Lists the chunks of all of the canisters configured in the dfx.json file.
+    #[arg(long, required_unless_present("canister"))]
+    all: bool,
+
+    /// Output the chunks as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct StoredChunks {
+    canister: String,
+    canister_id: String,
+    chunks: usize,
+    max_stored_bytes: u64,
+    hashes: Vec<String>,
+}
+
+pub async fn exec(env: &dyn Environment, opts: ListOpts, call_sender: &CallSender) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+
+    let mut listing = vec![];
+    for (canister, canister_id) in selected_canisters(env, opts.canister.as_deref(), opts.all)? {
+        let hashes: Vec<String> = canister::stored_chunks(env, canister_id, call_sender)
+            .await?
+            .iter()
+            .map(|chunk| hex::encode(&chunk.hash))
+            .collect();
+        listing.push(StoredChunks {
+            canister,
+            canister_id: canister_id.to_text(),
+            chunks: hashes.len(),
+            max_stored_bytes: hashes.len() as u64 * MAX_CHUNK_SIZE,
+            hashes,
+        });
+    }
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&listing)?);
+        return Ok(());
+    }
+    for stored in &listing {
+        println!(
+            "{} ({}): {} chunks, at most {} stored",
+            stored.canister,
+            stored.canister_id,
+            stored.chunks,
+            Byte::from_bytes(stored.max_stored_bytes as u128).get_appropriate_unit(true)
+        );
+        for hash in &stored.hashes {
+            println!("  {}", hash);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use candid::Principal;
+use clap::{Parser, Subcommand};
+use dfx_core::identity::CallSender;
+
+mod clear;
+mod list;
+
+/// Manages the chunk store of a canister, which holds the wasm chunks uploaded for chunked installs.
+#[derive(Parser)]
+pub struct ChunksOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Subcommand)]
+enum SubCommand {
+    Clear(clear::ClearOpts),
+    List(list::ListOpts),
+}
+
+pub async fn exec(env: &dyn Environment, opts: ChunksOpts, call_sender: &CallSender) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::Clear(v) => clear::exec(env, v, call_sender).await,
+        SubCommand::List(v) => list::exec(env, v, call_sender).await,
+    }
+}
+
+/// Resolves the canisters selected by a canister name/id or the --all flag.
+fn selected_canisters(
+    env: &dyn Environment,
+    canister: Option<&str>,
+    all: bool,
+) -> DfxResult<Vec<(String, Principal)>> {
+    let canister_id_store = env.get_canister_id_store()?;
+    if let Some(canister) = canister {
+        let canister_id = Principal::from_text(canister)
+            .or_else(|_| canister_id_store.get_including_aliases(canister))?;
+        Ok(vec![(canister.to_string(), canister_id)])
+    } else if all {
+        let config = env.get_config_or_anyhow()?;
+        let mut canisters = vec![];
+        if let Some(names) = &config.get_config().canisters {
+            for name in names.keys() {
+                canisters.push((name.clone(), canister_id_store.get(name)?));
+            }
+        }
+        Ok(canisters)
+    } else {
+        unreachable!()
+    }
+}
@@ -0,0 +1,127 @@
+use crate::lib::error::DfxResult;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::Environment;
+use anyhow::{bail, Context};
+use candid::{Decode, Encode, Principal};
+use clap::Parser;
+use dfx_core::util::network_to_pathcompat;
+use fn_error_context::context;
+use serde_bytes::ByteBuf;
+use slog::{info, warn};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The query method that returns the coverage counters of a canister as LLVM profile data,
+/// e.g. as captured by `minicov::capture_coverage`.
+const COVERAGE_METHOD: &str = "__get_coverage";
+
+/// Collects the code coverage of canisters built with the test-coverage profile,
+/// and merges it with llvm-profdata if it is installed.
+#[derive(Parser)]
+pub struct CanisterCoverageOpts {
+    /// Specifies the name or id of the canister to collect the coverage of.
+    canister: Option<String>,
+
+    /// Collects the coverage of all canisters configured in the dfx.json file.
+    #[arg(long, required_unless_present("canister"), conflicts_with("canister"))]
+    all: bool,
+
+    /// The directory to write the profile data to. Defaults to .dfx/<network>/coverage.
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+}
+
+pub async fn exec(env: &dyn Environment, opts: CanisterCoverageOpts) -> DfxResult {
+    let log = env.get_logger();
+    let canister_id_store = env.get_canister_id_store()?;
+    let output_dir = match opts.output_dir {
+        Some(output_dir) => output_dir,
+        None => env
+            .get_config_or_anyhow()?
+            .get_temp_path()
+            .join(network_to_pathcompat(&env.get_network_descriptor().name))
+            .join("coverage"),
+    };
+    dfx_core::fs::create_dir_all(&output_dir)?;
+
+    fetch_root_key_if_needed(env).await?;
+
+    let canisters = match opts.canister {
+        Some(canister) => {
+            let canister_id =
+                Principal::from_text(&canister).or_else(|_| canister_id_store.get(&canister))?;
+            vec![(canister, canister_id)]
+        }
+        None => {
+            let config = env.get_config_or_anyhow()?;
+            let names = config.get_config().canisters.iter().flat_map(|c| c.keys());
+            names
+                .filter_map(|name| Some((name.clone(), canister_id_store.find(name)?)))
+                .collect()
+        }
+    };
+
+    let mut profiles = vec![];
+    for (name, canister_id) in canisters {
+        match collect_coverage(
+            env,
+            canister_id,
+            &output_dir.join(format!("{name}.profraw")),
+        )
+        .await
+        {
+            Ok(path) => {
+                info!(log, "Wrote the coverage of {} to {}", name, path.display());
+                profiles.push(path);
+            }
+            Err(err) if opts.all => {
+                warn!(log, "Skipping canister {}: {:#}", name, err);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    if profiles.is_empty() {
+        bail!("No canister reported its coverage. Build the canisters with --profile test-coverage, and export the {} query method.", COVERAGE_METHOD);
+    }
+
+    let merged = output_dir.join("coverage.profdata");
+    match Command::new("llvm-profdata")
+        .arg("merge")
+        .arg("-sparse")
+        .args(&profiles)
+        .arg("-o")
+        .arg(&merged)
+        .status()
+    {
+        Ok(status) if status.success() => {
+            info!(log, "Merged the coverage into {}", merged.display());
+        }
+        Ok(status) => bail!("llvm-profdata failed: {}", status),
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            info!(
+                log,
+                "Install llvm-profdata to merge the profile data, e.g. with `rustup component add llvm-tools-preview`."
+            );
+        }
+        Err(err) => return Err(err).context("Failed to run llvm-profdata."),
+    }
+    Ok(())
+}
+
+#[context("Failed to collect the coverage of canister {}.", canister_id)]
+async fn collect_coverage(
+    env: &dyn Environment,
+    canister_id: Principal,
+    path: &Path,
+) -> DfxResult<PathBuf> {
+    let response = env
+        .get_agent()
+        .query(&canister_id, COVERAGE_METHOD)
+        .with_arg(Encode!()?)
+        .call()
+        .await?;
+    let profile = Decode!(&response, ByteBuf)?;
+    dfx_core::fs::write(path, profile.into_vec())?;
+    Ok(path.to_path_buf())
+}
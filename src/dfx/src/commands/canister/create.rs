@@ -3,9 +3,13 @@ use crate::lib::environment::Environment;
 use crate::lib::error::{DfxError, DfxResult};
 use crate::lib::ic_attributes::{
     get_compute_allocation, get_freezing_threshold, get_memory_allocation,
-    get_reserved_cycles_limit, CanisterSettings,
+    get_reserved_cycles_limit, get_wasm_memory_limit, CanisterSettings,
 };
-use crate::lib::operations::canister::create_canister;
+use crate::lib::identity::wallet::get_or_create_wallet_canister;
+use crate::lib::operations::canister::{
+    create_canister, for_each_canister, DEFAULT_ALL_CANISTERS_CONCURRENCY,
+};
+use crate::lib::operations::cycles_ledger::uses_cycles_ledger_instead_of_wallet;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::{
     compute_allocation_parser, freezing_threshold_parser, memory_allocation_parser,
@@ -21,7 +25,7 @@ use dfx_core::error::identity::instantiate_identity_from_name::InstantiateIdenti
 use dfx_core::identity::CallSender;
 use ic_agent::Identity as _;
 use icrc_ledger_types::icrc1::account::Subaccount;
-use slog::info;
+use slog::{debug, info};
 
 /// Creates an empty canister and associates the assigned Canister ID to the canister name.
 #[derive(Parser)]
@@ -33,6 +37,10 @@ pub struct CanisterCreateOpts {
     #[arg(long, required_unless_present("canister_name"))]
     all: bool,
 
+    /// With --all, the number of canisters to create at the same time.
+    #[arg(long, requires("all"), default_value = DEFAULT_ALL_CANISTERS_CONCURRENCY)]
+    concurrency: usize,
+
     /// Specifies the initial cycle balance to deposit into the newly created canister.
     /// The specified amount needs to take the canister create fee into account.
     /// This amount is deducted from the wallet's cycle balance.
@@ -180,6 +188,10 @@ pub async fn exec(
             Some(canister_name),
         )
         .with_context(|| format!("Failed to read reserved cycles limit of {}.", canister_name))?;
+        let wasm_memory_limit =
+            get_wasm_memory_limit(None, Some(config_interface), Some(canister_name)).with_context(
+                || format!("Failed to read Wasm memory limit of {}.", canister_name),
+            )?;
         create_canister(
             env,
             canister_name,
@@ -194,6 +206,7 @@ pub async fn exec(
                 memory_allocation,
                 freezing_threshold,
                 reserved_cycles_limit,
+                wasm_memory_limit,
             },
             opts.created_at_time,
             subnet_selection,
@@ -202,6 +215,7 @@ pub async fn exec(
         Ok(())
     } else if opts.all {
         // Create all canisters.
+        let mut canister_names = vec![];
         if let Some(canisters) = &config_interface.canisters {
             for canister_name in canisters.keys() {
                 if pull_canisters_in_config.contains_key(canister_name) {
@@ -219,62 +233,119 @@ pub async fn exec(
 
                     continue;
                 }
-                let specified_id = config_interface.get_specified_id(canister_name)?;
-                let compute_allocation = get_compute_allocation(
-                    opts.compute_allocation,
-                    Some(config_interface),
-                    Some(canister_name),
-                )
-                .with_context(|| {
-                    format!("Failed to read compute allocation of {}.", canister_name)
-                })?;
-                let memory_allocation = get_memory_allocation(
-                    opts.memory_allocation,
-                    Some(config_interface),
-                    Some(canister_name),
-                )
-                .with_context(|| {
-                    format!("Failed to read memory allocation of {}.", canister_name)
-                })?;
-                let freezing_threshold = get_freezing_threshold(
-                    opts.freezing_threshold,
-                    Some(config_interface),
-                    Some(canister_name),
-                )
-                .with_context(|| {
-                    format!("Failed to read freezing threshold of {}.", canister_name)
-                })?;
-                let reserved_cycles_limit = get_reserved_cycles_limit(
-                    opts.reserved_cycles_limit,
-                    Some(config_interface),
-                    Some(canister_name),
-                )
-                .with_context(|| {
-                    format!("Failed to read reserved cycles limit of {}.", canister_name)
-                })?;
-                create_canister(
-                    env,
-                    canister_name,
-                    with_cycles,
-                    specified_id,
-                    call_sender,
-                    opts.no_wallet,
-                    opts.from_subaccount,
-                    CanisterSettings {
-                        controllers: controllers.clone(),
+                canister_names.push(canister_name.clone());
+            }
+        }
+
+        // The canisters are created concurrently, so the wallet that creates them must exist beforehand.
+        // Otherwise, each of them would create a wallet of its own.
+        let canister_id_store = env.get_canister_id_store()?;
+        let needs_wallet = canister_names.iter().any(|canister_name| {
+            canister_id_store.find(canister_name).is_none()
+                && matches!(config_interface.get_specified_id(canister_name), Ok(None))
+        });
+        if needs_wallet
+            && !opts.no_wallet
+            && matches!(call_sender, CallSender::SelectedId)
+            && !uses_cycles_ledger_instead_of_wallet(env)
+            && !network.is_playground()
+        {
+            if let Err(err) = get_or_create_wallet_canister(
+                env,
+                network,
+                env.get_selected_identity().expect("No selected identity"),
+            )
+            .await
+            {
+                debug!(env.get_logger(), "Did not get a wallet: {err}");
+            }
+        }
+
+        let CanisterCreateOpts {
+            compute_allocation,
+            memory_allocation,
+            freezing_threshold,
+            reserved_cycles_limit,
+            no_wallet,
+            from_subaccount,
+            created_at_time,
+            concurrency,
+            ..
+        } = opts;
+        for_each_canister(
+            env,
+            "create",
+            canister_names,
+            concurrency,
+            |canister_name| {
+                let controllers = controllers.clone();
+                let subnet_selection = subnet_selection.clone();
+                async move {
+                    let canister_name = canister_name.as_str();
+                    let specified_id = config_interface.get_specified_id(canister_name)?;
+                    let compute_allocation = get_compute_allocation(
                         compute_allocation,
+                        Some(config_interface),
+                        Some(canister_name),
+                    )
+                    .with_context(|| {
+                        format!("Failed to read compute allocation of {}.", canister_name)
+                    })?;
+                    let memory_allocation = get_memory_allocation(
                         memory_allocation,
+                        Some(config_interface),
+                        Some(canister_name),
+                    )
+                    .with_context(|| {
+                        format!("Failed to read memory allocation of {}.", canister_name)
+                    })?;
+                    let freezing_threshold = get_freezing_threshold(
                         freezing_threshold,
+                        Some(config_interface),
+                        Some(canister_name),
+                    )
+                    .with_context(|| {
+                        format!("Failed to read freezing threshold of {}.", canister_name)
+                    })?;
+                    let reserved_cycles_limit = get_reserved_cycles_limit(
                         reserved_cycles_limit,
-                    },
-                    opts.created_at_time,
-                    subnet_selection.clone(),
-                )
-                .await?;
-            }
-            if !pull_canisters_in_config.is_empty() {
-                info!(env.get_logger(), "There are pull dependencies defined in dfx.json. Please deploy them using `dfx deps deploy`.");
-            }
+                        Some(config_interface),
+                        Some(canister_name),
+                    )
+                    .with_context(|| {
+                        format!("Failed to read reserved cycles limit of {}.", canister_name)
+                    })?;
+                    let wasm_memory_limit =
+                        get_wasm_memory_limit(None, Some(config_interface), Some(canister_name))
+                            .with_context(|| {
+                                format!("Failed to read Wasm memory limit of {}.", canister_name)
+                            })?;
+                    create_canister(
+                        env,
+                        canister_name,
+                        with_cycles,
+                        specified_id,
+                        call_sender,
+                        no_wallet,
+                        from_subaccount,
+                        CanisterSettings {
+                            controllers,
+                            compute_allocation,
+                            memory_allocation,
+                            freezing_threshold,
+                            reserved_cycles_limit,
+                            wasm_memory_limit,
+                        },
+                        created_at_time,
+                        subnet_selection,
+                    )
+                    .await
+                }
+            },
+        )
+        .await?;
+        if !pull_canisters_in_config.is_empty() {
+            info!(env.get_logger(), "There are pull dependencies defined in dfx.json. Please deploy them using `dfx deps deploy`.");
         }
         Ok(())
     } else {
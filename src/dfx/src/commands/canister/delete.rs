@@ -7,7 +7,7 @@ use crate::lib::operations::canister::{
     deposit_cycles, start_canister, stop_canister, update_settings,
 };
 use crate::lib::operations::cycles_ledger::{
-    wallet_deposit_to_cycles_ledger, CYCLES_LEDGER_ENABLED,
+    cycles_ledger_enabled, uses_cycles_ledger_instead_of_wallet, wallet_deposit_to_cycles_ledger,
 };
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::assets::wallet_wasm;
@@ -53,9 +53,18 @@ pub struct CanisterDeleteOpts {
     no_withdrawal: bool,
 
     /// Withdraw cycles from canister(s) to the specified canister/wallet before deleting.
+    /// Accepts a canister id or the name of a canister in the project.
     #[arg(long, conflicts_with("no_withdrawal"))]
     withdraw_cycles_to_canister: Option<String>,
 
+    /// Withdraw cycles from canister(s) to the cycles ledger account of the given principal before deleting.
+    #[arg(
+        long,
+        conflicts_with("withdraw_cycles_to_canister"),
+        conflicts_with("no_withdrawal")
+    )]
+    withdraw_cycles_to_account: Option<String>,
+
     /// Withdraw cycles to dank with the current principal.
     #[arg(
         long,
@@ -72,11 +81,15 @@ pub struct CanisterDeleteOpts {
     )]
     withdraw_cycles_to_dank_principal: Option<String>,
 
+    /// Show how many cycles would be withdrawn and where to, without deleting anything.
+    #[arg(long)]
+    dry_run: bool,
+
     /// Auto-confirm deletion for a non-stopped canister.
     #[arg(long, short)]
     yes: bool,
 
-    /// Subaccount of the selected identity to deposit cycles to.
+    /// Subaccount of the cycles ledger account to deposit cycles to.
     //TODO(SDK-1331): unhide
     #[arg(long, value_parser = icrc_subaccount_parser, hide = true)]
     to_subaccount: Option<Subaccount>,
@@ -87,12 +100,7 @@ async fn delete_canister(
     env: &dyn Environment,
     canister: &str,
     call_sender: &CallSender,
-    no_withdrawal: bool,
-    skip_confirmation: bool,
-    withdraw_cycles_to_canister: Option<String>,
-    withdraw_cycles_to_dank: bool,
-    withdraw_cycles_to_dank_principal: Option<String>,
-    to_cycles_ledger_subaccount: Option<Subaccount>,
+    opts: &CanisterDeleteOpts,
 ) -> DfxResult {
     let log = env.get_logger();
     let mut canister_id_store = env.get_canister_id_store()?;
@@ -106,19 +114,30 @@ async fn delete_canister(
 
     if !env.get_network_descriptor().is_playground() {
         let mut call_sender = call_sender;
-        let to_dank = withdraw_cycles_to_dank || withdraw_cycles_to_dank_principal.is_some();
+        let to_dank =
+            opts.withdraw_cycles_to_dank || opts.withdraw_cycles_to_dank_principal.is_some();
 
         // Get the canister to transfer the cycles to.
-        let withdraw_target = if no_withdrawal {
+        let withdraw_target = if opts.no_withdrawal {
             WithdrawTarget::NoWithdrawal
         } else if to_dank {
             WithdrawTarget::Dank
+        } else if let Some(owner) = &opts.withdraw_cycles_to_account {
+            let owner = Principal::from_text(owner)
+                .with_context(|| format!("Failed to read principal {:?}.", owner))?;
+            WithdrawTarget::CyclesLedger {
+                to: Account {
+                    owner,
+                    subaccount: opts.to_subaccount,
+                },
+            }
         } else {
-            match withdraw_cycles_to_canister {
-                Some(ref target_canister_id) => {
-                    let canister_id =
-                        Principal::from_text(target_canister_id).with_context(|| {
-                            format!("Failed to read canister id {:?}.", target_canister_id)
+            match &opts.withdraw_cycles_to_canister {
+                Some(target_canister) => {
+                    let canister_id = Principal::from_text(target_canister)
+                        .or_else(|_| canister_id_store.get(target_canister))
+                        .with_context(|| {
+                            format!("Failed to read canister id {:?}.", target_canister)
                         })?;
                     WithdrawTarget::Canister { canister_id }
                 }
@@ -132,10 +151,17 @@ async fn delete_canister(
                             .get_selected_identity()
                             .expect("No selected identity.")
                             .to_string();
-                        // If there is no wallet, then do not attempt to withdraw the cycles.
-                        match wallet_canister_id(network, &identity_name)? {
+                        // Projects that use the cycles ledger instead of a wallet get the cycles back
+                        // in the cycles ledger account even if there is a wallet.
+                        // If there is neither, then do not attempt to withdraw the cycles.
+                        let wallet = if uses_cycles_ledger_instead_of_wallet(env) {
+                            None
+                        } else {
+                            wallet_canister_id(network, &identity_name)?
+                        };
+                        match wallet {
                             Some(canister_id) => WithdrawTarget::Canister { canister_id },
-                            None if CYCLES_LEDGER_ENABLED => {
+                            None if cycles_ledger_enabled(env) => {
                                 let Some(my_principal) = env.get_selected_identity_principal()
                                 else {
                                     bail!("Identity has no principal attached")
@@ -143,7 +169,7 @@ async fn delete_canister(
                                 WithdrawTarget::CyclesLedger {
                                     to: Account {
                                         owner: my_principal,
-                                        subaccount: to_cycles_ledger_subaccount,
+                                        subaccount: opts.to_subaccount,
                                     },
                                 }
                             }
@@ -156,13 +182,46 @@ async fn delete_canister(
         let principal = env
             .get_selected_identity_principal()
             .expect("Selected identity not instantiated.");
-        let dank_target_principal = match withdraw_cycles_to_dank_principal {
+        let dank_target_principal = match &opts.withdraw_cycles_to_dank_principal {
             None => principal,
-            Some(principal) => Principal::from_text(&principal)
-                .with_context(|| format!("Failed to read principal {:?}.", &principal))?,
+            Some(principal) => Principal::from_text(principal)
+                .with_context(|| format!("Failed to read principal {:?}.", principal))?,
         };
         fetch_root_key_if_needed(env).await?;
 
+        if opts.dry_run {
+            let status = canister::get_canister_status(env, canister_id, call_sender).await?;
+            let cycles = status.cycles.0.to_u128().unwrap();
+            info!(
+                log,
+                "Canister {} ({}) has a balance of {} cycles.", canister, canister_id, cycles
+            );
+            match withdraw_target {
+                WithdrawTarget::NoWithdrawal => info!(
+                    log,
+                    "No cycles would be withdrawn. Deleting the canister would burn its balance."
+                ),
+                _ if cycles <= WITHDRAWAL_COST => info!(
+                    log,
+                    "Too few cycles to withdraw. Deleting the canister would burn its balance."
+                ),
+                WithdrawTarget::Dank => info!(
+                    log,
+                    "About {} cycles would be withdrawn to dank principal {}.",
+                    cycles - WITHDRAWAL_COST,
+                    dank_target_principal
+                ),
+                target => info!(
+                    log,
+                    "About {} cycles would be withdrawn to {}.",
+                    cycles - WITHDRAWAL_COST,
+                    target
+                ),
+            }
+            info!(log, "Dry run: canister {} was not deleted.", canister);
+            return Ok(());
+        }
+
         if withdraw_target != WithdrawTarget::NoWithdrawal {
             info!(
                 log,
@@ -171,7 +230,7 @@ async fn delete_canister(
 
             // Determine how many cycles we can withdraw.
             let status = canister::get_canister_status(env, canister_id, call_sender).await?;
-            if status.status != CanisterStatus::Stopped && !skip_confirmation {
+            if status.status != CanisterStatus::Stopped && !opts.yes {
                 ask_for_consent(&format!(
                     "Canister {canister} has not been stopped. Delete anyway?"
                 ))?;
@@ -188,6 +247,7 @@ async fn delete_canister(
                 memory_allocation: None,
                 freezing_threshold: Some(FreezingThreshold::try_from(0u8).unwrap()),
                 reserved_cycles_limit: None,
+                wasm_memory_limit: None,
             };
             info!(log, "Setting the controller to identity principal.");
             update_settings(env, canister_id, settings, call_sender).await?;
@@ -326,33 +386,11 @@ pub async fn exec(
     fetch_root_key_if_needed(env).await?;
 
     if let Some(canister) = opts.canister.as_deref() {
-        delete_canister(
-            env,
-            canister,
-            call_sender,
-            opts.no_withdrawal,
-            opts.yes,
-            opts.withdraw_cycles_to_canister,
-            opts.withdraw_cycles_to_dank,
-            opts.withdraw_cycles_to_dank_principal,
-            opts.to_subaccount,
-        )
-        .await
+        delete_canister(env, canister, call_sender, &opts).await
     } else if opts.all {
         if let Some(canisters) = &config.get_config().canisters {
             for canister in canisters.keys() {
-                delete_canister(
-                    env,
-                    canister,
-                    call_sender,
-                    opts.no_withdrawal,
-                    opts.yes,
-                    opts.withdraw_cycles_to_canister.clone(),
-                    opts.withdraw_cycles_to_dank,
-                    opts.withdraw_cycles_to_dank_principal.clone(),
-                    opts.to_subaccount,
-                )
-                .await?;
+                delete_canister(env, canister, call_sender, &opts).await?;
             }
         }
         Ok(())
@@ -368,3 +406,20 @@ enum WithdrawTarget {
     CyclesLedger { to: Account },
     Canister { canister_id: Principal },
 }
+
+impl std::fmt::Display for WithdrawTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WithdrawTarget::NoWithdrawal => write!(f, "nowhere"),
+            WithdrawTarget::Dank => write!(f, "dank"),
+            WithdrawTarget::CyclesLedger { to } => {
+                write!(f, "the cycles ledger account of {}", to.owner)?;
+                if let Some(subaccount) = to.subaccount {
+                    write!(f, " (subaccount {})", hex::encode(subaccount))?;
+                }
+                Ok(())
+            }
+            WithdrawTarget::Canister { canister_id } => write!(f, "canister {}", canister_id),
+        }
+    }
+}
@@ -2,6 +2,9 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::identity::wallet::get_or_create_wallet_canister;
 use crate::lib::operations::canister;
+use crate::lib::operations::cycles_ledger;
+use crate::lib::operations::cycles_ledger::uses_cycles_ledger_instead_of_wallet;
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::cycle_amount_parser;
 use anyhow::Context;
@@ -9,12 +12,14 @@ use candid::Principal;
 use clap::Parser;
 use dfx_core::identity::CallSender;
 use slog::info;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Deposit cycles into the specified canister.
 #[derive(Parser)]
 pub struct DepositCyclesOpts {
     /// Specifies the amount of cycles to send on the call.
-    /// Deducted from the wallet.
+    /// Deducted from the wallet, or from the cycles ledger account of the selected identity
+    /// if the project uses the cycles ledger instead of a wallet.
     #[arg(value_parser = cycle_amount_parser)]
     cycles: u128,
 
@@ -31,16 +36,38 @@ async fn deposit_cycles(
     env: &dyn Environment,
     canister: &str,
     call_sender: &CallSender,
+    from_cycles_ledger: bool,
     cycles: u128,
 ) -> DfxResult {
     let log = env.get_logger();
     let canister_id_store = env.get_canister_id_store()?;
-    let canister_id =
-        Principal::from_text(canister).or_else(|_| canister_id_store.get(canister))?;
+    let canister_id = Principal::from_text(canister)
+        .or_else(|_| canister_id_store.get_including_aliases(canister))?;
 
     info!(log, "Depositing {} cycles onto {}", cycles, canister,);
 
-    canister::deposit_cycles(env, canister_id, call_sender, cycles).await?;
+    if from_cycles_ledger {
+        let created_at_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+        let block_index = cycles_ledger::send(
+            env.get_agent(),
+            retry_policy(env),
+            log,
+            canister_id,
+            cycles,
+            created_at_time,
+            None,
+        )
+        .await?;
+        info!(
+            log,
+            "Sent {} cycles from the cycles ledger at block index {}", cycles, block_index
+        );
+    } else {
+        canister::deposit_cycles(env, canister_id, call_sender, cycles).await?;
+    }
 
     let status = canister::get_canister_status(env, canister_id, call_sender).await;
     if let Ok(status) = status {
@@ -64,8 +91,11 @@ pub async fn exec(
 
     let proxy_sender;
 
-    // choose default wallet if no wallet is specified
-    if call_sender == &CallSender::SelectedId {
+    // choose default wallet if no wallet is specified,
+    // unless the project uses the cycles ledger instead of a wallet
+    let from_cycles_ledger =
+        call_sender == &CallSender::SelectedId && uses_cycles_ledger_instead_of_wallet(env);
+    if call_sender == &CallSender::SelectedId && !from_cycles_ledger {
         let wallet = get_or_create_wallet_canister(
             env,
             env.get_network_descriptor(),
@@ -80,13 +110,13 @@ pub async fn exec(
     let cycles = opts.cycles;
 
     if let Some(canister) = opts.canister.as_deref() {
-        deposit_cycles(env, canister, call_sender, cycles).await
+        deposit_cycles(env, canister, call_sender, from_cycles_ledger, cycles).await
     } else if opts.all {
         let config = env.get_config_or_anyhow()?;
 
         if let Some(canisters) = &config.get_config().canisters {
             for canister in canisters.keys() {
-                deposit_cycles(env, canister, call_sender, cycles)
+                deposit_cycles(env, canister, call_sender, from_cycles_ledger, cycles)
                     .await
                     .with_context(|| format!("Failed to deposit cycles into {}.", canister))?;
             }
@@ -0,0 +1,127 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::operations::canister::get_canister_status;
+use crate::lib::operations::canister::snapshot::{
+    delete_canister_snapshot, read_canister_snapshot_chunk, read_canister_snapshot_data,
+    read_canister_snapshot_metadata, take_canister_snapshot, SnapshotData,
+};
+use crate::lib::root_key::fetch_root_key_if_needed;
+use anyhow::{bail, Context};
+use candid::Principal;
+use clap::Parser;
+use dfx_core::identity::CallSender;
+use dfx_core::json::save_json_file;
+use ic_utils::interfaces::management_canister::CanisterStatus;
+use slog::{info, warn};
+use std::path::{Path, PathBuf};
+
+pub(crate) const METADATA_FILE: &str = "metadata.json";
+pub(crate) const WASM_MODULE_FILE: &str = "wasm_module.bin";
+pub(crate) const WASM_MEMORY_FILE: &str = "wasm_memory.bin";
+pub(crate) const STABLE_MEMORY_FILE: &str = "stable_memory.bin";
+pub(crate) const WASM_CHUNKS_DIR: &str = "wasm_chunks";
+
+/// Exports the state of a canister, that is its Wasm module, memories and chunk store, to a directory.
+/// `dfx canister import-state` loads it into a canister on another network.
+/// The network must support downloading canister snapshots.
+#[derive(Parser)]
+pub struct ExportStateOpts {
+    /// Specifies the name or id of the canister to export.
+    canister: String,
+
+    /// The directory to export the state to. It must not exist yet.
+    dir: PathBuf,
+
+    /// Exports an existing snapshot of the canister, by its hex-encoded id, instead of taking a new one.
+    #[arg(long)]
+    snapshot: Option<String>,
+}
+
+pub async fn exec(
+    env: &dyn Environment,
+    opts: ExportStateOpts,
+    call_sender: &CallSender,
+) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+    let log = env.get_logger();
+    let canister_id_store = env.get_canister_id_store()?;
+    let canister_id = Principal::from_text(&opts.canister)
+        .or_else(|_| canister_id_store.get_including_aliases(&opts.canister))?;
+    if opts.dir.exists() {
+        bail!("{} already exists.", opts.dir.display());
+    }
+
+    if let Some(snapshot) = &opts.snapshot {
+        let snapshot_id = hex::decode(snapshot)
+            .with_context(|| format!("Snapshot id {snapshot:?} is not a valid hex string."))?;
+        return export(env, canister_id, &snapshot_id, &opts.dir, call_sender).await;
+    }
+
+    let status = get_canister_status(env, canister_id, call_sender).await?;
+    if status.status != CanisterStatus::Stopped {
+        bail!(
+            "Canister {} is not stopped. Stop it first with `dfx canister stop {}`, so that its state is consistent.",
+            opts.canister,
+            opts.canister
+        );
+    }
+    let snapshot_id = take_canister_snapshot(env, canister_id, call_sender).await?;
+    let result = export(env, canister_id, &snapshot_id, &opts.dir, call_sender).await;
+    // The snapshot was only taken for the export, and counts against the snapshots of the canister.
+    if let Err(err) = delete_canister_snapshot(env, canister_id, &snapshot_id, call_sender).await {
+        warn!(log, "{err:#}");
+    }
+    result?;
+
+    info!(
+        log,
+        "Exported the state of {} to {}. Import it with `dfx canister import-state`.",
+        opts.canister,
+        opts.dir.display()
+    );
+    Ok(())
+}
+
+async fn export(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    dir: &Path,
+    call_sender: &CallSender,
+) -> DfxResult {
+    let log = env.get_logger();
+    let metadata =
+        read_canister_snapshot_metadata(env, canister_id, snapshot_id, call_sender).await?;
+    dfx_core::fs::create_dir_all(&dir.join(WASM_CHUNKS_DIR))?;
+
+    for (data, size, file) in [
+        (
+            SnapshotData::WasmModule,
+            metadata.wasm_module_size,
+            WASM_MODULE_FILE,
+        ),
+        (
+            SnapshotData::WasmMemory,
+            metadata.wasm_memory_size,
+            WASM_MEMORY_FILE,
+        ),
+        (
+            SnapshotData::StableMemory,
+            metadata.stable_memory_size,
+            STABLE_MEMORY_FILE,
+        ),
+    ] {
+        info!(log, "Downloading {} bytes of {:?}...", size, data);
+        let bytes =
+            read_canister_snapshot_data(env, canister_id, snapshot_id, data, size, call_sender)
+                .await?;
+        dfx_core::fs::write(&dir.join(file), bytes)?;
+    }
+    for hash in &metadata.wasm_chunk_store {
+        let chunk =
+            read_canister_snapshot_chunk(env, canister_id, snapshot_id, hash, call_sender).await?;
+        dfx_core::fs::write(&dir.join(WASM_CHUNKS_DIR).join(hash), chunk)?;
+    }
+    save_json_file(&dir.join(METADATA_FILE), &metadata)?;
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::frozen_canisters::{
+    load_frozen_canisters, save_frozen_canisters, PriorSettings, FROZEN_FREEZING_THRESHOLD,
+};
+use crate::lib::ic_attributes::CanisterSettings;
+use crate::lib::operations::canister::{get_canister_status, update_settings};
+use crate::lib::root_key::fetch_root_key_if_needed;
+use anyhow::{bail, Context};
+use candid::Principal;
+use clap::Parser;
+use dfx_core::identity::CallSender;
+use ic_utils::interfaces::management_canister::attributes::FreezingThreshold;
+use num_traits::ToPrimitive;
+use slog::info;
+
+/// Freezes a canister, so that it rejects all calls, by raising its freezing threshold as far as it goes.
+/// The previous freezing threshold is recorded locally, and restored by `dfx canister unfreeze`.
+///
+/// A canister is frozen while its balance is below the cycles it burns per day while idle,
+/// times its freezing threshold in days. With the largest threshold, that is more cycles than
+/// any canister holds, so the canister stays frozen. A frozen canister still pays for its
+/// storage and its compute and memory allocations, and is uninstalled if it runs out of cycles.
+#[derive(Parser)]
+pub struct CanisterFreezeOpts {
+    /// Specifies the name or id of the canister to freeze.
+    /// You must specify either a canister name/id or the --all option.
+    canister: Option<String>,
+
+    /// Freezes all of the canisters configured in the dfx.json file.
+    #[arg(long, required_unless_present("canister"))]
+    all: bool,
+}
+
+async fn freeze_canister(
+    env: &dyn Environment,
+    canister: &str,
+    call_sender: &CallSender,
+) -> DfxResult {
+    let log = env.get_logger();
+    let canister_id_store = env.get_canister_id_store()?;
+    let canister_id = Principal::from_text(canister)
+        .or_else(|_| canister_id_store.get_including_aliases(canister))?;
+
+    let mut frozen = load_frozen_canisters(env)?;
+    if frozen.canisters.contains_key(&canister_id) {
+        bail!("Canister {canister} is already frozen. Run `dfx canister unfreeze {canister}` to unfreeze it.");
+    }
+
+    let status = get_canister_status(env, canister_id, call_sender).await?;
+    let freezing_threshold = status
+        .settings
+        .freezing_threshold
+        .0
+        .to_u64()
+        .context("The freezing threshold of the canister does not fit in 64 bits.")?;
+
+    // Record the settings before changing them, so that they are not lost if the update fails halfway.
+    frozen
+        .canisters
+        .insert(canister_id, PriorSettings { freezing_threshold });
+    save_frozen_canisters(env, &frozen)?;
+
+    let settings = CanisterSettings {
+        freezing_threshold: Some(FreezingThreshold::try_from(FROZEN_FREEZING_THRESHOLD).unwrap()),
+        ..Default::default()
+    };
+    if let Err(err) = update_settings(env, canister_id, settings, call_sender).await {
+        frozen.canisters.remove(&canister_id);
+        save_frozen_canisters(env, &frozen)?;
+        return Err(err);
+    }
+
+    let idle_cycles_burned_per_day = status.idle_cycles_burned_per_day.0.to_u128().unwrap_or(0);
+    let freezing_limit = idle_cycles_burned_per_day
+        .saturating_mul(FROZEN_FREEZING_THRESHOLD as u128)
+        / (24 * 60 * 60);
+    info!(
+        log,
+        "Froze canister {canister} ({canister_id}). It rejects all calls until its balance exceeds {freezing_limit} cycles \
+        ({idle_cycles_burned_per_day} cycles burned per day while idle, times a freezing threshold of {FROZEN_FREEZING_THRESHOLD} seconds).\n\
+        Run `dfx canister unfreeze {canister}` to restore its freezing threshold of {freezing_threshold} seconds."
+    );
+    if idle_cycles_burned_per_day == 0 {
+        info!(
+            log,
+            "Canister {canister} burns no cycles while idle, so it only freezes once it uses memory."
+        );
+    }
+    Ok(())
+}
+
+pub async fn exec(
+    env: &dyn Environment,
+    opts: CanisterFreezeOpts,
+    call_sender: &CallSender,
+) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+
+    if let Some(canister) = opts.canister.as_deref() {
+        freeze_canister(env, canister, call_sender).await
+    } else if opts.all {
+        let config = env.get_config_or_anyhow()?;
+        if let Some(canisters) = &config.get_config().canisters {
+            for canister in canisters.keys() {
+                freeze_canister(env, canister, call_sender).await?;
+            }
+        }
+        Ok(())
+    } else {
+        unreachable!()
+    }
+}
@@ -21,7 +21,7 @@ pub async fn exec(env: &dyn Environment, opts: CanisterIdOpts) -> DfxResult {
     let network_descriptor = create_network_descriptor(
         env.get_config(),
         env.get_networks_config(),
-        opts.network.to_network_name(),
+        opts.network.to_network_name(env),
         None,
         LocalBindDetermination::AsConfigured,
     )?;
@@ -29,8 +29,8 @@ pub async fn exec(env: &dyn Environment, opts: CanisterIdOpts) -> DfxResult {
         CanisterIdStore::new(env.get_logger(), &network_descriptor, env.get_config())?;
 
     let canister_name = opts.canister.as_str();
-    let canister_id =
-        Principal::from_text(canister_name).or_else(|_| canister_id_store.get(canister_name))?;
+    let canister_id = Principal::from_text(canister_name)
+        .or_else(|_| canister_id_store.get_including_aliases(canister_name))?;
     println!("{}", Principal::to_text(&canister_id));
     Ok(())
 }
@@ -0,0 +1,118 @@
+use super::export_state::{
+    METADATA_FILE, STABLE_MEMORY_FILE, WASM_CHUNKS_DIR, WASM_MEMORY_FILE, WASM_MODULE_FILE,
+};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::operations::canister::get_canister_status;
+use crate::lib::operations::canister::snapshot::{
+    delete_canister_snapshot, load_canister_snapshot, upload_canister_snapshot_chunk,
+    upload_canister_snapshot_data, upload_canister_snapshot_metadata, SnapshotData,
+    SnapshotMetadata,
+};
+use crate::lib::root_key::fetch_root_key_if_needed;
+use anyhow::bail;
+use candid::Principal;
+use clap::Parser;
+use dfx_core::cli::ask_for_consent;
+use dfx_core::identity::CallSender;
+use dfx_core::json::load_json_file;
+use ic_utils::interfaces::management_canister::CanisterStatus;
+use slog::{info, warn};
+use std::path::{Path, PathBuf};
+
+/// Replaces the state of a canister with a state exported by `dfx canister export-state`,
+/// e.g. from a canister on another network. The canister must exist and be stopped.
+/// The network must support uploading canister snapshots.
+#[derive(Parser)]
+pub struct ImportStateOpts {
+    /// Specifies the name or id of the canister to import the state into.
+    canister: String,
+
+    /// The directory that the state was exported to.
+    dir: PathBuf,
+
+    /// Skips the confirmation that the current state of the canister is replaced.
+    #[arg(long, short)]
+    yes: bool,
+}
+
+pub async fn exec(
+    env: &dyn Environment,
+    opts: ImportStateOpts,
+    call_sender: &CallSender,
+) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+    let log = env.get_logger();
+    let canister_id_store = env.get_canister_id_store()?;
+    let canister_id = Principal::from_text(&opts.canister)
+        .or_else(|_| canister_id_store.get_including_aliases(&opts.canister))?;
+    let metadata: SnapshotMetadata = load_json_file(&opts.dir.join(METADATA_FILE))?;
+
+    let status = get_canister_status(env, canister_id, call_sender).await?;
+    if status.status != CanisterStatus::Stopped {
+        bail!(
+            "Canister {} is not stopped. Stop it first with `dfx canister stop {}`.",
+            opts.canister,
+            opts.canister
+        );
+    }
+    if !opts.yes {
+        ask_for_consent(&format!(
+            "The current state of canister {} on network '{}' will be replaced.",
+            opts.canister,
+            env.get_network_descriptor().name
+        ))?;
+    }
+
+    let snapshot_id =
+        upload_canister_snapshot_metadata(env, canister_id, &metadata, call_sender).await?;
+    let result = import(
+        env,
+        canister_id,
+        &snapshot_id,
+        &metadata,
+        &opts.dir,
+        call_sender,
+    )
+    .await;
+    // The snapshot was only uploaded to be loaded, and counts against the snapshots of the canister.
+    if let Err(err) = delete_canister_snapshot(env, canister_id, &snapshot_id, call_sender).await {
+        warn!(log, "{err:#}");
+    }
+    result?;
+
+    info!(
+        log,
+        "Imported the state from {} into {}. Start the canister with `dfx canister start {}`.",
+        opts.dir.display(),
+        opts.canister,
+        opts.canister
+    );
+    Ok(())
+}
+
+async fn import(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    metadata: &SnapshotMetadata,
+    dir: &Path,
+    call_sender: &CallSender,
+) -> DfxResult {
+    let log = env.get_logger();
+    for (data, file) in [
+        (SnapshotData::WasmModule, WASM_MODULE_FILE),
+        (SnapshotData::WasmMemory, WASM_MEMORY_FILE),
+        (SnapshotData::StableMemory, STABLE_MEMORY_FILE),
+    ] {
+        let bytes = dfx_core::fs::read(&dir.join(file))?;
+        info!(log, "Uploading {} bytes of {:?}...", bytes.len(), data);
+        upload_canister_snapshot_data(env, canister_id, snapshot_id, data, &bytes, call_sender)
+            .await?;
+    }
+    for hash in &metadata.wasm_chunk_store {
+        let chunk = dfx_core::fs::read(&dir.join(WASM_CHUNKS_DIR).join(hash))?;
+        upload_canister_snapshot_chunk(env, canister_id, snapshot_id, &chunk, call_sender).await?;
+    }
+    load_canister_snapshot(env, canister_id, snapshot_id, call_sender).await
+}
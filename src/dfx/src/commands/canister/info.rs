@@ -1,15 +1,24 @@
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use crate::lib::identity::wallet::wallet_canister_id;
+use crate::lib::metadata::names::CANDID_SERVICE;
+use crate::lib::operations::canister::get_canister_status;
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::lib::state_tree::canister_info::{
     read_state_tree_canister_controllers, read_state_tree_canister_module_hash,
 };
+use crate::lib::subnet::get_subnet_for_canister;
 use anyhow::anyhow;
 use candid::Principal;
 use clap::Parser;
+use dfx_core::identity::CallSender;
 use itertools::Itertools;
 
 /// Get the hash of a canister’s WASM module and its current controllers.
+/// Also reports the subnet of the canister on mainnet, whether it publishes its Candid interface,
+/// and its cycle balance if the selected identity or its wallet controls it.
+/// Works for any canister, not only those of the current project.
 #[derive(Parser)]
 pub struct InfoOpts {
     /// Specifies the name or id of the canister to get its canister information.
@@ -20,22 +29,24 @@ pub async fn exec(env: &dyn Environment, opts: InfoOpts) -> DfxResult {
     let agent = env.get_agent();
 
     let callee_canister = opts.canister.as_str();
-    let canister_id_store = env.get_canister_id_store()?;
-
-    let canister_id = Principal::from_text(callee_canister)
-        .or_else(|_| canister_id_store.get(callee_canister))?;
+    let canister_id = match Principal::from_text(callee_canister) {
+        Ok(canister_id) => canister_id,
+        Err(_) => env.get_canister_id_store()?.get(callee_canister)?,
+    };
 
     fetch_root_key_if_needed(env).await?;
 
-    let controllers_sorted: Vec<_> = read_state_tree_canister_controllers(agent, canister_id)
+    let controllers = read_state_tree_canister_controllers(agent, canister_id)
         .await?
-        .ok_or_else(|| anyhow!("Canister {canister_id} does not exist."))?
+        .ok_or_else(|| anyhow!("Canister {canister_id} does not exist."))?;
+    let controllers_sorted: Vec<_> = controllers
         .iter()
         .map(Principal::to_text)
         .sorted()
         .collect();
 
-    let module_hash_hex = match read_state_tree_canister_module_hash(agent, canister_id).await? {
+    let module_hash = read_state_tree_canister_module_hash(agent, canister_id).await?;
+    let module_hash_hex = match &module_hash {
         None => "None".to_string(),
         Some(blob) => format!("0x{}", hex::encode(blob)),
     };
@@ -46,5 +57,53 @@ pub async fn exec(env: &dyn Environment, opts: InfoOpts) -> DfxResult {
         module_hash_hex
     );
 
+    // The registry that knows the subnets only exists on mainnet.
+    if env.get_network_descriptor().is_ic {
+        if let Ok(subnet) = get_subnet_for_canister(agent, retry_policy(env), canister_id).await {
+            println!("Subnet: {}", subnet);
+        }
+    }
+
+    if module_hash.is_some() {
+        let candid_available = agent
+            .read_state_canister_metadata(canister_id, CANDID_SERVICE)
+            .await
+            .is_ok();
+        println!(
+            "Candid interface: {}",
+            if candid_available {
+                "available"
+            } else {
+                "not available"
+            }
+        );
+    }
+
+    if let Some(call_sender) = controlling_call_sender(env, &controllers)? {
+        if let Ok(status) = get_canister_status(env, canister_id, &call_sender).await {
+            println!("Balance: {} Cycles", status.cycles);
+        }
+    }
+
     Ok(())
 }
+
+/// The caller that may read the status of a canister with these controllers, if any.
+fn controlling_call_sender(
+    env: &dyn Environment,
+    controllers: &[Principal],
+) -> DfxResult<Option<CallSender>> {
+    if let Some(principal) = env.get_selected_identity_principal() {
+        if controllers.contains(&principal) {
+            return Ok(Some(CallSender::SelectedId));
+        }
+    }
+    if let Some(identity_name) = env.get_selected_identity() {
+        if let Some(wallet_id) = wallet_canister_id(env.get_network_descriptor(), identity_name)? {
+            if controllers.contains(&wallet_id) {
+                return Ok(Some(CallSender::Wallet(wallet_id)));
+            }
+        }
+    }
+    Ok(None)
+}
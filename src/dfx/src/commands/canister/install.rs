@@ -2,10 +2,13 @@ use crate::lib::canister_info::CanisterInfo;
 use crate::lib::deps::get_pull_canisters_in_config;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use crate::lib::named_canister;
 use crate::lib::operations::canister::install_canister::install_canister;
+use crate::lib::operations::canister::{for_each_canister, DEFAULT_ALL_CANISTERS_CONCURRENCY};
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::blob_from_arguments;
 use crate::util::clap::argument_from_cli::ArgumentFromCliLongOpt;
+use crate::util::clap::init_preset_opt::InitPresetOpt;
 use dfx_core::canister::{install_canister_wasm, install_mode_to_prompt};
 use dfx_core::identity::CallSender;
 
@@ -24,9 +27,17 @@ pub struct CanisterInstallOpts {
     canister: Option<String>,
 
     /// Deploys all canisters configured in the project dfx.json files.
-    #[arg(long, required_unless_present("canister"), conflicts_with("argument"))]
+    #[arg(
+        long,
+        required_unless_present("canister"),
+        conflicts_with_all(["argument", "init_preset"])
+    )]
     all: bool,
 
+    /// With --all, the number of canisters to install at the same time.
+    #[arg(long, requires("all"), default_value = DEFAULT_ALL_CANISTERS_CONCURRENCY)]
+    concurrency: usize,
+
     /// Specifies not to wait for the result of the call to be returned by polling the replica. Instead return a response ID.
     #[arg(long)]
     async_call: bool,
@@ -44,6 +55,9 @@ pub struct CanisterInstallOpts {
     #[command(flatten)]
     argument_from_cli: ArgumentFromCliLongOpt,
 
+    #[command(flatten)]
+    init_preset: InitPresetOpt,
+
     /// Specifies a particular WASM file to install, bypassing the dfx.json project settings.
     #[arg(long, conflicts_with("all"))]
     wasm: Option<PathBuf>,
@@ -59,6 +73,10 @@ pub struct CanisterInstallOpts {
     /// Skips upgrading the asset canister, to only install the assets themselves.
     #[arg(long)]
     no_asset_upgrade: bool,
+
+    /// Installs wasm modules even if their hash is not in the allowed_wasm_hashes of the canister in dfx.json.
+    #[arg(long)]
+    allow_unverified: bool,
 }
 
 pub async fn exec(
@@ -87,7 +105,10 @@ pub async fn exec(
     let env_file = config.get_output_env_file(opts.output_env_file)?;
 
     if let Some(canister) = opts.canister.as_deref() {
-        let (argument_from_cli, argument_type) = opts.argument_from_cli.get_argument_and_type()?;
+        let (argument_from_cli, argument_type) = match opts.init_preset.get_argument(env)? {
+            Some(argument) => (Some(argument), Some("idl".to_string())),
+            None => opts.argument_from_cli.get_argument_and_type()?,
+        };
         // `opts.canister` is a Principal (canister ID)
         if let Ok(canister_id) = Principal::from_text(canister) {
             if let Some(wasm_path) = &opts.wasm {
@@ -154,6 +175,7 @@ pub async fn exec(
                     opts.yes,
                     None,
                     opts.no_asset_upgrade,
+                    opts.allow_unverified,
                 )
                 .await
                 .map_err(Into::into)
@@ -173,6 +195,7 @@ pub async fn exec(
                     opts.yes,
                     env_file.as_deref(),
                     opts.no_asset_upgrade,
+                    opts.allow_unverified,
                 )
                 .await
                 .map_err(Into::into)
@@ -180,6 +203,7 @@ pub async fn exec(
         }
     } else if opts.all {
         // Install all canisters.
+        let mut canister_names = vec![];
         if let Some(canisters) = &config.get_config().canisters {
             for canister in canisters.keys() {
                 if pull_canisters_in_config.contains_key(canister) {
@@ -194,9 +218,41 @@ pub async fn exec(
                     );
                     continue;
                 }
+                canister_names.push(canister.clone());
+            }
+        }
 
-                let canister_id = canister_id_store.get(canister)?;
-                let canister_info = CanisterInfo::load(&config, canister, Some(canister_id))?;
+        // The canisters are installed concurrently, so the Candid UI canister must exist beforehand.
+        // Otherwise, each of them would install one.
+        if !network.is_ic
+            && named_canister::get_ui_canister_id(network, &canister_id_store).is_none()
+        {
+            named_canister::install_ui_canister(env, &mut canister_id_store, None).await?;
+        }
+        // Installing into a canister reserved with the playground updates the canister id store.
+        let concurrency = if network.is_playground() {
+            1
+        } else {
+            opts.concurrency
+        };
+        let CanisterInstallOpts {
+            upgrade_unchanged,
+            yes,
+            no_asset_upgrade,
+            allow_unverified,
+            ..
+        } = opts;
+        let config = &config;
+        let env_file = env_file.as_deref();
+        for_each_canister(
+            env,
+            "install",
+            canister_names,
+            concurrency,
+            |canister| async move {
+                let mut canister_id_store = env.get_canister_id_store()?;
+                let canister_id = canister_id_store.get(&canister)?;
+                let canister_info = CanisterInfo::load(config, &canister, Some(canister_id))?;
                 install_canister(
                     env,
                     &mut canister_id_store,
@@ -207,15 +263,17 @@ pub async fn exec(
                     None,
                     mode,
                     call_sender,
-                    opts.upgrade_unchanged,
+                    upgrade_unchanged,
                     None,
-                    opts.yes,
-                    env_file.as_deref(),
-                    opts.no_asset_upgrade,
+                    yes,
+                    env_file,
+                    no_asset_upgrade,
+                    allow_unverified,
                 )
-                .await?;
-            }
-        }
+                .await
+            },
+        )
+        .await?;
         if !pull_canisters_in_config.is_empty() {
             info!(env.get_logger(), "There are pull dependencies defined in dfx.json. Please deploy them using `dfx deps deploy`.");
         }
@@ -23,7 +23,7 @@ pub async fn exec(env: &dyn Environment, opts: CanisterMetadataOpts) -> DfxResul
     let canister_id_store = env.get_canister_id_store()?;
 
     let canister_id = Principal::from_text(callee_canister)
-        .or_else(|_| canister_id_store.get(callee_canister))?;
+        .or_else(|_| canister_id_store.get_including_aliases(callee_canister))?;
 
     fetch_root_key_if_needed(env).await?;
     let metadata = agent
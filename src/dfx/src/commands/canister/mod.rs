@@ -7,22 +7,33 @@ use clap::{Parser, Subcommand};
 use dfx_core::identity::CallSender;
 use tokio::runtime::Runtime;
 
+mod benchmark;
 mod call;
+mod chunks;
+mod coverage;
 mod create;
 mod delete;
 mod deposit_cycles;
+mod export_state;
+mod freeze;
 mod id;
+mod import_state;
 mod info;
 mod install;
 mod metadata;
+mod open;
 mod request_status;
 mod send;
 mod sign;
 mod start;
 mod status;
 mod stop;
+mod unfreeze;
 mod uninstall_code;
 mod update_settings;
+mod url;
+mod verify;
+mod watch_metadata;
 
 /// Manages canisters deployed on a network replica.
 #[derive(Parser)]
@@ -42,30 +53,44 @@ pub struct CanisterOpts {
 
 #[derive(Subcommand)]
 pub enum SubCommand {
+    Benchmark(benchmark::CanisterBenchmarkOpts),
     Call(call::CanisterCallOpts),
+    Chunks(chunks::ChunksOpts),
+    Coverage(coverage::CanisterCoverageOpts),
     Create(create::CanisterCreateOpts),
     Delete(delete::CanisterDeleteOpts),
     DepositCycles(deposit_cycles::DepositCyclesOpts),
+    ExportState(export_state::ExportStateOpts),
+    Freeze(freeze::CanisterFreezeOpts),
     Id(id::CanisterIdOpts),
+    ImportState(import_state::ImportStateOpts),
     Info(info::InfoOpts),
     Install(install::CanisterInstallOpts),
     Metadata(metadata::CanisterMetadataOpts),
+    Open(open::CanisterOpenOpts),
     RequestStatus(request_status::RequestStatusOpts),
     Send(send::CanisterSendOpts),
     Sign(sign::CanisterSignOpts),
     Start(start::CanisterStartOpts),
     Status(status::CanisterStatusOpts),
     Stop(stop::CanisterStopOpts),
+    Unfreeze(unfreeze::CanisterUnfreezeOpts),
     UninstallCode(uninstall_code::UninstallCodeOpts),
     UpdateSettings(update_settings::UpdateSettingsOpts),
+    Url(url::CanisterUrlOpts),
+    Verify(verify::CanisterVerifyOpts),
+    WatchMetadata(watch_metadata::WatchMetadataOpts),
 }
 
 pub fn exec(env: &dyn Environment, opts: CanisterOpts) -> DfxResult {
     let agent_env;
-    let env = if matches!(&opts.subcmd, SubCommand::Id(_)) {
+    let env = if matches!(
+        &opts.subcmd,
+        SubCommand::Id(_) | SubCommand::Open(_) | SubCommand::Url(_)
+    ) {
         env
     } else {
-        agent_env = create_agent_environment(env, opts.network.to_network_name())?;
+        agent_env = create_agent_environment(env, opts.network.to_network_name(env))?;
         &agent_env
     };
     let runtime = Runtime::new().expect("Unable to create a runtime");
@@ -74,22 +99,33 @@ pub fn exec(env: &dyn Environment, opts: CanisterOpts) -> DfxResult {
         let call_sender = CallSender::from(&opts.wallet)
             .map_err(|e| anyhow!("Failed to determine call sender: {}", e))?;
         match opts.subcmd {
+            SubCommand::Benchmark(v) => benchmark::exec(env, v, &call_sender).await,
             SubCommand::Call(v) => call::exec(env, v, &call_sender).await,
+            SubCommand::Chunks(v) => chunks::exec(env, v, &call_sender).await,
+            SubCommand::Coverage(v) => coverage::exec(env, v).await,
             SubCommand::Create(v) => create::exec(env, v, &call_sender).await,
             SubCommand::Delete(v) => delete::exec(env, v, &call_sender).await,
             SubCommand::DepositCycles(v) => deposit_cycles::exec(env, v, &call_sender).await,
+            SubCommand::ExportState(v) => export_state::exec(env, v, &call_sender).await,
+            SubCommand::Freeze(v) => freeze::exec(env, v, &call_sender).await,
             SubCommand::Id(v) => id::exec(env, v).await,
+            SubCommand::ImportState(v) => import_state::exec(env, v, &call_sender).await,
             SubCommand::Install(v) => install::exec(env, v, &call_sender).await,
             SubCommand::Info(v) => info::exec(env, v).await,
             SubCommand::Metadata(v) => metadata::exec(env, v).await,
+            SubCommand::Open(v) => open::exec(env, v).await,
             SubCommand::RequestStatus(v) => request_status::exec(env, v).await,
             SubCommand::Send(v) => send::exec(env, v, &call_sender).await,
             SubCommand::Sign(v) => sign::exec(env, v, &call_sender).await,
             SubCommand::Start(v) => start::exec(env, v, &call_sender).await,
             SubCommand::Status(v) => status::exec(env, v, &call_sender).await,
             SubCommand::Stop(v) => stop::exec(env, v, &call_sender).await,
+            SubCommand::Unfreeze(v) => unfreeze::exec(env, v, &call_sender).await,
             SubCommand::UninstallCode(v) => uninstall_code::exec(env, v, &call_sender).await,
             SubCommand::UpdateSettings(v) => update_settings::exec(env, v, &call_sender).await,
+            SubCommand::Url(v) => url::exec(env, v).await,
+            SubCommand::Verify(v) => verify::exec(env, v).await,
+            SubCommand::WatchMetadata(v) => watch_metadata::exec(env, v).await,
         }
     })
 }
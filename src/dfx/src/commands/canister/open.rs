@@ -0,0 +1,81 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::named_canister::get_ui_canister_id;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::network::url::construct_ui_canister_url;
+use anyhow::{anyhow, bail, Context};
+use candid::Principal;
+use clap::Parser;
+use dfx_core::config::model::canister_id_store::CanisterIdStore;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use slog::info;
+use std::process::{Command, Stdio};
+
+/// Opens the Candid UI for a canister in the browser.
+#[derive(Parser)]
+pub struct CanisterOpenOpts {
+    /// Specifies the name or id of the canister.
+    canister: String,
+
+    /// Prints the URL instead of opening it.
+    #[arg(long)]
+    print: bool,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+}
+
+pub async fn exec(env: &dyn Environment, opts: CanisterOpenOpts) -> DfxResult {
+    let network_descriptor = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        opts.network.to_network_name(env),
+        None,
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+    let canister_id_store =
+        CanisterIdStore::new(env.get_logger(), &network_descriptor, env.get_config())?;
+    let canister_id = Principal::from_text(&opts.canister)
+        .or_else(|_| canister_id_store.get_including_aliases(&opts.canister))?;
+
+    let url = construct_ui_canister_url(
+        &network_descriptor,
+        &canister_id,
+        get_ui_canister_id(&network_descriptor, &canister_id_store),
+    )?
+    .ok_or_else(|| {
+        anyhow!(
+            "The Candid UI canister is not installed on network '{}'. It is installed by `dfx start` unless `candid_ui` is disabled, or by `dfx deploy` on networks that `dfx start` does not run.",
+            network_descriptor.name
+        )
+    })?;
+
+    if opts.print {
+        println!("{}", url);
+        return Ok(());
+    }
+    info!(env.get_logger(), "Opening {}", url);
+    open_in_browser(url.as_str())
+}
+
+fn open_in_browser(url: &str) -> DfxResult {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .with_context(|| format!("Failed to open a browser. Open {} instead.", url))?;
+    if !status.success() {
+        bail!("Failed to open a browser. Open {} instead.", url);
+    }
+    Ok(())
+}
@@ -1,15 +1,13 @@
 use crate::lib::environment::Environment;
-use crate::lib::error::{DfxError, DfxResult};
+use crate::lib::error::DfxResult;
+use crate::lib::requests::{remove_pending_request, wait_for_reply};
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers;
 use crate::util::print_idl_blob;
 use anyhow::Context;
-use backoff::backoff::Backoff;
-use backoff::ExponentialBackoff;
 use candid::Principal;
 use clap::Parser;
-use ic_agent::agent::RequestStatusResponse;
-use ic_agent::{AgentError, RequestId};
+use ic_agent::RequestId;
 use std::str::FromStr;
 
 /// Requests the status of a call from a canister.
@@ -36,7 +34,6 @@ pub struct RequestStatusOpts {
 pub async fn exec(env: &dyn Environment, opts: RequestStatusOpts) -> DfxResult {
     let request_id =
         RequestId::from_str(&opts.request_id[2..]).context("Invalid argument: request_id")?;
-    let agent = env.get_agent();
 
     fetch_root_key_if_needed(env).await?;
 
@@ -44,48 +41,10 @@ pub async fn exec(env: &dyn Environment, opts: RequestStatusOpts) -> DfxResult {
     let canister_id_store = env.get_canister_id_store()?;
 
     let canister_id = Principal::from_text(callee_canister)
-        .or_else(|_| canister_id_store.get(callee_canister))?;
+        .or_else(|_| canister_id_store.get_including_aliases(callee_canister))?;
 
-    let mut retry_policy = ExponentialBackoff::default();
-    let blob = async {
-        let mut request_accepted = false;
-        loop {
-            match agent
-                .request_status_raw(&request_id, canister_id)
-                .await
-                .context("Failed to fetch request status.")?
-            {
-                RequestStatusResponse::Replied(reply) => return Ok(reply.arg),
-                RequestStatusResponse::Rejected(response) => {
-                    return Err(DfxError::new(AgentError::ReplicaError(response)))
-                }
-                RequestStatusResponse::Unknown => (),
-                RequestStatusResponse::Received | RequestStatusResponse::Processing => {
-                    // The system will return Unknown until the request is accepted
-                    // and we generally cannot know how long that will take.
-                    // State transitions between Received and Processing may be
-                    // instantaneous. Therefore, once we know the request is accepted,
-                    // we restart the waiter so the request does not time out.
-                    if !request_accepted {
-                        retry_policy.reset();
-                        request_accepted = true;
-                    }
-                }
-                RequestStatusResponse::Done => {
-                    return Err(DfxError::new(AgentError::RequestStatusDoneNoReply(
-                        String::from(request_id),
-                    )))
-                }
-            };
-
-            let interval = retry_policy
-                .next_backoff()
-                .ok_or_else(|| DfxError::new(AgentError::TimeoutWaitingForResponse()))?;
-            tokio::time::sleep(interval).await;
-        }
-    }
-    .await
-    .map_err(DfxError::from)?;
+    let blob = wait_for_reply(env, &request_id, canister_id, None).await?;
+    remove_pending_request(env, &request_id)?;
 
     let output_type = opts.output.as_deref();
     print_idl_blob(&blob, output_type, &None)?;
@@ -1,7 +1,9 @@
 use crate::commands::canister::call::get_effective_canister_id;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
-use crate::lib::operations::canister::get_local_cid_and_candid_path;
+use crate::lib::operations::canister::{
+    get_local_cid_and_candid_path, get_named_cid_and_candid_path,
+};
 use crate::lib::sign::sign_transport::SignTransport;
 use crate::lib::sign::signed_message::SignedMessageV1;
 use crate::util::clap::argument_from_cli::ArgumentFromCliPositionalOpt;
@@ -78,10 +80,7 @@ pub async fn exec(
                 (id, None)
             }
         }
-        Err(_) => {
-            let canister_id = canister_id_store.get(callee_canister)?;
-            get_local_cid_and_candid_path(env, callee_canister, Some(canister_id))?
-        }
+        Err(_) => get_named_cid_and_candid_path(env, &canister_id_store, callee_canister)?,
     };
 
     let method_type =
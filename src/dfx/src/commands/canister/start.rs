@@ -1,11 +1,12 @@
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use crate::lib::frozen_canisters::is_frozen;
 use crate::lib::operations::canister;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use candid::Principal;
 use clap::Parser;
 use dfx_core::identity::CallSender;
-use slog::info;
+use slog::{info, warn};
 
 /// Starts a stopped canister.
 #[derive(Parser)]
@@ -25,8 +26,8 @@ async fn start_canister(
 ) -> DfxResult {
     let log = env.get_logger();
     let canister_id_store = env.get_canister_id_store()?;
-    let canister_id =
-        Principal::from_text(canister).or_else(|_| canister_id_store.get(canister))?;
+    let canister_id = Principal::from_text(canister)
+        .or_else(|_| canister_id_store.get_including_aliases(canister))?;
 
     info!(
         log,
@@ -36,6 +37,14 @@ async fn start_canister(
     );
 
     canister::start_canister(env, canister_id, call_sender).await?;
+    if is_frozen(env, canister_id)? {
+        warn!(
+            log,
+            "Canister {} was frozen with `dfx canister freeze`, so it still rejects calls. Run `dfx canister unfreeze {}` to unfreeze it.",
+            canister,
+            canister
+        );
+    }
 
     Ok(())
 }
@@ -2,11 +2,20 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::operations::canister;
 use crate::lib::root_key::fetch_root_key_if_needed;
-use candid::Principal;
+use crate::util::clap::parsers::duration_parser;
+use byte_unit::Byte;
+use candid::{Nat, Principal};
 use clap::Parser;
 use dfx_core::identity::CallSender;
 use fn_error_context::context;
-use slog::info;
+use num_traits::{ToPrimitive, Zero};
+use slog::{error, info, warn, Logger};
+use std::time::Duration;
+
+/// The Wasm memory of a canister cannot grow past 4 GiB, unless it uses 64-bit memory.
+const WASM_MEMORY_WALL: u64 = 4 * 1024 * 1024 * 1024;
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
 
 /// Returns the current status of a canister: Running, Stopping, or Stopped. Also carries information like balance, current settings, memory used and everything returned by 'info'.
 #[derive(Parser)]
@@ -18,6 +27,24 @@ pub struct CanisterStatusOpts {
     /// Returns status information for all of the canisters configured in the dfx.json file.
     #[arg(long, required_unless_present("canister"))]
     all: bool,
+
+    /// Warns when the memory of the canister reaches this percentage of its Wasm memory limit,
+    /// or of 4 GiB if it has no limit.
+    #[arg(long, default_value = "80", value_parser = clap::value_parser!(u8).range(1..=100))]
+    memory_warning_threshold: u8,
+
+    /// Reports an error when the memory of the canister reaches this percentage of its Wasm memory limit,
+    /// or of 4 GiB if it has no limit.
+    #[arg(long, default_value = "95", value_parser = clap::value_parser!(u8).range(1..=100))]
+    memory_critical_threshold: u8,
+
+    /// Keeps polling the status, printing one line per canister, until interrupted.
+    #[arg(long)]
+    watch: bool,
+
+    /// How often to poll the status with --watch, e.g. 30s or 5m.
+    #[arg(long, default_value = "10s", value_parser = duration_parser, requires("watch"))]
+    interval: Duration,
 }
 
 #[context("Failed to get canister status for '{}'.", canister)]
@@ -25,13 +52,39 @@ async fn canister_status(
     env: &dyn Environment,
     canister: &str,
     call_sender: &CallSender,
+    opts: &CanisterStatusOpts,
 ) -> DfxResult {
     let log = env.get_logger();
     let canister_id_store = env.get_canister_id_store()?;
-    let canister_id =
-        Principal::from_text(canister).or_else(|_| canister_id_store.get(canister))?;
+    let canister_id = Principal::from_text(canister)
+        .or_else(|_| canister_id_store.get_including_aliases(canister))?;
 
-    let status = canister::get_canister_status(env, canister_id, call_sender).await?;
+    let status = canister::get_full_canister_status(env, canister_id, call_sender).await?;
+    let wasm_memory_limit = status.wasm_memory_limit()?;
+    // Only the Wasm memory counts against the Wasm memory limit, when the replica reports it.
+    let memory_size = MemorySize {
+        bytes: status
+            .memory_metrics
+            .as_ref()
+            .map_or(&status.memory_size, |metrics| &metrics.wasm_memory_size)
+            .0
+            .to_u64()
+            .unwrap_or(u64::MAX),
+        includes_stable_memory: status.memory_metrics.is_none(),
+    };
+
+    if opts.watch {
+        info!(
+            log,
+            "{}: {}, memory {}, balance {} Cycles",
+            canister,
+            status.status,
+            format_bytes(memory_size.bytes),
+            status.cycles
+        );
+        check_memory_usage(log, canister, memory_size, wasm_memory_limit, opts);
+        return Ok(());
+    }
 
     let mut controllers: Vec<_> = status
         .settings
@@ -41,13 +94,40 @@ async fn canister_status(
         .collect();
     controllers.sort();
 
-    let reserved_cycles_limit = if let Some(limit) = status.settings.reserved_cycles_limit {
-        format!("{} Cycles", limit)
+    let reserved_cycles_limit = if let Some(limit) = &status.settings.reserved_cycles_limit {
+        let left = if limit.0 > status.reserved_cycles.0 {
+            Nat(&limit.0 - &status.reserved_cycles.0)
+        } else {
+            Nat::from(0u8)
+        };
+        format!("{} Cycles ({} Cycles left)", limit, left)
     } else {
         "Not Set".to_string()
     };
+    let wasm_memory_limit_text = if let Some(limit) = wasm_memory_limit {
+        format!("{} Bytes", limit)
+    } else {
+        "Not Set".to_string()
+    };
+    let memory_metrics = if let Some(metrics) = &status.memory_metrics {
+        format!(
+            "\nWasm Memory Size: {} Bytes\nStable Memory Size: {} Bytes",
+            metrics.wasm_memory_size, metrics.stable_memory_size
+        )
+    } else {
+        String::new()
+    };
+    let runway = runway_days(
+        &status.cycles,
+        &status.idle_cycles_burned_per_day,
+        &status.settings.freezing_threshold,
+    )
+    .map_or_else(
+        || "Unlimited".to_string(),
+        |days| format!("{} days until frozen, when idle", days),
+    );
 
-    info!(log, "Canister status call result for {}.\nStatus: {}\nControllers: {}\nMemory allocation: {}\nCompute allocation: {}\nFreezing threshold: {}\nMemory Size: {:?}\nBalance: {} Cycles\nReserved: {} Cycles\nReserved Cycles Limit: {}\nModule hash: {}\nNumber of queries: {}\nInstructions spent in queries: {}\nTotal query request paylod size (bytes): {}\nTotal query response payload size (bytes): {}",
+    info!(log, "Canister status call result for {}.\nStatus: {}\nControllers: {}\nMemory allocation: {}\nCompute allocation: {}\nFreezing threshold: {}\nMemory Size: {:?}{}\nBalance: {} Cycles\nReserved: {} Cycles\nReserved Cycles Limit: {}\nIdle Cycles Burned Per Day: {} Cycles\nRunway: {}\nWasm Memory Limit: {}\nModule hash: {}\nNumber of queries: {}\nInstructions spent in queries: {}\nTotal query request paylod size (bytes): {}\nTotal query response payload size (bytes): {}",
         canister,
         status.status,
         controllers.join(" "),
@@ -55,18 +135,97 @@ async fn canister_status(
         status.settings.compute_allocation,
         status.settings.freezing_threshold,
         status.memory_size,
+        memory_metrics,
         status.cycles,
         status.reserved_cycles,
         reserved_cycles_limit,
+        status.idle_cycles_burned_per_day,
+        runway,
+        wasm_memory_limit_text,
         status.module_hash.map_or_else(|| "None".to_string(), |v| format!("0x{}", hex::encode(v))),
         status.query_stats.num_calls_total,
         status.query_stats.num_instructions_total,
         status.query_stats.request_payload_bytes_total,
         status.query_stats.response_payload_bytes_total,
     );
+    check_memory_usage(log, canister, memory_size, wasm_memory_limit, opts);
     Ok(())
 }
 
+/// Returns how many days the canister can pay for its memory and compute allocation before it is frozen,
+/// or `None` if it burns no cycles when idle.
+/// The canister is frozen once its balance cannot pay for the freezing threshold, given in seconds.
+fn runway_days(
+    cycles: &Nat,
+    idle_cycles_burned_per_day: &Nat,
+    freezing_threshold: &Nat,
+) -> Option<Nat> {
+    if idle_cycles_burned_per_day.0.is_zero() {
+        return None;
+    }
+    let frozen_below = &idle_cycles_burned_per_day.0 * &freezing_threshold.0 / SECONDS_PER_DAY;
+    if cycles.0 <= frozen_below {
+        return Some(Nat::from(0u8));
+    }
+    Some(Nat(
+        (&cycles.0 - frozen_below) / &idle_cycles_burned_per_day.0
+    ))
+}
+
+/// The memory of a canister that counts against its Wasm memory limit.
+struct MemorySize {
+    bytes: u64,
+    /// Replicas that do not report memory metrics only report the total memory size,
+    /// which includes stable memory.
+    includes_stable_memory: bool,
+}
+
+/// Warns when the memory of a canister gets close to the most it can use.
+/// Without memory metrics, the memory size includes stable memory and overstates the Wasm memory,
+/// so it only ever gets a warning.
+fn check_memory_usage(
+    log: &Logger,
+    canister: &str,
+    memory_size: MemorySize,
+    wasm_memory_limit: Option<u64>,
+    opts: &CanisterStatusOpts,
+) {
+    let (limit, limit_name) = match wasm_memory_limit {
+        Some(limit) if limit > 0 => (limit, "its Wasm memory limit"),
+        _ => (
+            WASM_MEMORY_WALL,
+            "the Wasm memory that a 32-bit canister can use",
+        ),
+    };
+    let percent = memory_size.bytes as u128 * 100 / limit as u128;
+    let message = format!(
+        "Canister {canister} uses {}, {percent}% of {limit_name} ({}).",
+        format_bytes(memory_size.bytes),
+        format_bytes(limit)
+    );
+    if memory_size.includes_stable_memory {
+        if percent >= opts.memory_warning_threshold as u128 {
+            warn!(
+                log,
+                "{message} This includes stable memory, which does not count against this limit."
+            );
+        }
+    } else if percent >= opts.memory_critical_threshold as u128 {
+        error!(
+            log,
+            "{message} Once it is reached, calls that allocate memory fail."
+        );
+    } else if percent >= opts.memory_warning_threshold as u128 {
+        warn!(log, "{message}");
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    Byte::from_bytes(bytes as u128)
+        .get_appropriate_unit(true)
+        .to_string()
+}
+
 pub async fn exec(
     env: &dyn Environment,
     opts: CanisterStatusOpts,
@@ -74,17 +233,33 @@ pub async fn exec(
 ) -> DfxResult {
     fetch_root_key_if_needed(env).await?;
 
-    if let Some(canister) = opts.canister.as_deref() {
-        canister_status(env, canister, call_sender).await
+    let canisters = if let Some(canister) = opts.canister.as_deref() {
+        vec![canister.to_string()]
     } else if opts.all {
         let config = env.get_config_or_anyhow()?;
-        if let Some(canisters) = &config.get_config().canisters {
-            for canister in canisters.keys() {
-                canister_status(env, canister, call_sender).await?;
-            }
-        }
-        Ok(())
+        config
+            .get_config()
+            .canisters
+            .as_ref()
+            .map(|canisters| canisters.keys().cloned().collect())
+            .unwrap_or_default()
     } else {
         unreachable!()
+    };
+
+    if !opts.watch {
+        for canister in &canisters {
+            canister_status(env, canister, call_sender, &opts).await?;
+        }
+        return Ok(());
+    }
+    loop {
+        for canister in &canisters {
+            // Keep watching when the network is briefly unavailable.
+            if let Err(err) = canister_status(env, canister, call_sender, &opts).await {
+                warn!(env.get_logger(), "{:#}", err);
+            }
+        }
+        tokio::time::sleep(opts.interval).await;
     }
 }
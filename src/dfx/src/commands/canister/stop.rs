@@ -5,7 +5,9 @@ use crate::lib::root_key::fetch_root_key_if_needed;
 use candid::Principal;
 use clap::Parser;
 use dfx_core::identity::CallSender;
+use ic_utils::interfaces::management_canister::CanisterStatus;
 use slog::info;
+use std::time::Duration;
 
 /// Stops a currently running canister.
 #[derive(Parser)]
@@ -17,17 +19,23 @@ pub struct CanisterStopOpts {
     /// Stops all of the canisters configured in the dfx.json file.
     #[arg(long, required_unless_present("canister"))]
     all: bool,
+
+    /// Keeps waiting until the canister has stopped, even if the stop request times out
+    /// because the canister still has calls in progress.
+    #[arg(long)]
+    wait: bool,
 }
 
 async fn stop_canister(
     env: &dyn Environment,
     canister: &str,
     call_sender: &CallSender,
+    wait: bool,
 ) -> DfxResult {
     let log = env.get_logger();
     let canister_id_store = env.get_canister_id_store()?;
-    let canister_id =
-        Principal::from_text(canister).or_else(|_| canister_id_store.get(canister))?;
+    let canister_id = Principal::from_text(canister)
+        .or_else(|_| canister_id_store.get_including_aliases(canister))?;
 
     info!(
         log,
@@ -36,7 +44,29 @@ async fn stop_canister(
         canister_id.to_text(),
     );
 
-    canister::stop_canister(env, canister_id, call_sender).await?;
+    let result = canister::stop_canister(env, canister_id, call_sender).await;
+    if !wait {
+        return result;
+    }
+    if let Err(err) = result {
+        // The request times out when calls are still in progress, but the canister keeps stopping.
+        let status = canister::get_canister_status(env, canister_id, call_sender).await?;
+        if status.status != CanisterStatus::Stopping {
+            return Err(err);
+        }
+        info!(
+            log,
+            "Waiting for canister {} to finish its calls in progress.", canister
+        );
+    }
+    while canister::get_canister_status(env, canister_id, call_sender)
+        .await?
+        .status
+        != CanisterStatus::Stopped
+    {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    info!(log, "Canister {} is stopped.", canister);
 
     Ok(())
 }
@@ -49,12 +79,12 @@ pub async fn exec(
     fetch_root_key_if_needed(env).await?;
 
     if let Some(canister) = opts.canister.as_deref() {
-        stop_canister(env, canister, call_sender).await
+        stop_canister(env, canister, call_sender, opts.wait).await
     } else if opts.all {
         let config = env.get_config_or_anyhow()?;
         if let Some(canisters) = &config.get_config().canisters {
             for canister in canisters.keys() {
-                stop_canister(env, canister, call_sender).await?;
+                stop_canister(env, canister, call_sender, opts.wait).await?;
             }
         }
         Ok(())
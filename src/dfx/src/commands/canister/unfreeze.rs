@@ -0,0 +1,87 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::frozen_canisters::{load_frozen_canisters, save_frozen_canisters};
+use crate::lib::ic_attributes::CanisterSettings;
+use crate::lib::operations::canister::update_settings;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use anyhow::{bail, Context};
+use candid::Principal;
+use clap::Parser;
+use dfx_core::identity::CallSender;
+use ic_utils::interfaces::management_canister::attributes::FreezingThreshold;
+use slog::info;
+
+/// Unfreezes a canister frozen by `dfx canister freeze`, restoring the freezing threshold it had before.
+#[derive(Parser)]
+pub struct CanisterUnfreezeOpts {
+    /// Specifies the name or id of the canister to unfreeze.
+    /// You must specify either a canister name/id or the --all option.
+    canister: Option<String>,
+
+    /// Unfreezes all of the canisters configured in the dfx.json file that are frozen.
+    #[arg(long, required_unless_present("canister"))]
+    all: bool,
+}
+
+async fn unfreeze_canister(
+    env: &dyn Environment,
+    canister: &str,
+    call_sender: &CallSender,
+    skip_unfrozen: bool,
+) -> DfxResult {
+    let log = env.get_logger();
+    let canister_id_store = env.get_canister_id_store()?;
+    let canister_id = Principal::from_text(canister)
+        .or_else(|_| canister_id_store.get_including_aliases(canister))?;
+
+    let mut frozen = load_frozen_canisters(env)?;
+    let Some(prior) = frozen.canisters.get(&canister_id).cloned() else {
+        if skip_unfrozen {
+            return Ok(());
+        }
+        bail!(
+            "Canister {canister} was not frozen with `dfx canister freeze`, so there are no settings to restore. \
+            Use `dfx canister update-settings --freezing-threshold` to change its freezing threshold."
+        );
+    };
+
+    let settings = CanisterSettings {
+        freezing_threshold: Some(
+            FreezingThreshold::try_from(prior.freezing_threshold)
+                .context("Invalid recorded freezing threshold.")?,
+        ),
+        ..Default::default()
+    };
+    update_settings(env, canister_id, settings, call_sender).await?;
+    frozen.canisters.remove(&canister_id);
+    save_frozen_canisters(env, &frozen)?;
+
+    info!(
+        log,
+        "Unfroze canister {canister} ({canister_id}), restoring its freezing threshold of {} seconds.",
+        prior.freezing_threshold
+    );
+    Ok(())
+}
+
+pub async fn exec(
+    env: &dyn Environment,
+    opts: CanisterUnfreezeOpts,
+    call_sender: &CallSender,
+) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+
+    if let Some(canister) = opts.canister.as_deref() {
+        unfreeze_canister(env, canister, call_sender, false).await
+    } else if opts.all {
+        let config = env.get_config_or_anyhow()?;
+        if let Some(canisters) = &config.get_config().canisters {
+            for canister in canisters.keys() {
+                unfreeze_canister(env, canister, call_sender, true).await?;
+            }
+        }
+        Ok(())
+    } else {
+        unreachable!()
+    }
+}
@@ -2,8 +2,10 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::operations::canister;
 use crate::lib::root_key::fetch_root_key_if_needed;
+use anyhow::Context;
 use candid::Principal;
 use clap::Parser;
+use dfx_core::canister::{ask_to_destroy_state, DestructiveAction};
 use dfx_core::identity::CallSender;
 use slog::info;
 
@@ -18,17 +20,36 @@ pub struct UninstallCodeOpts {
     /// Uninstalls all of the canisters configured in the dfx.json file.
     #[arg(long, required_unless_present("canister"))]
     all: bool,
+
+    /// Skips the confirmation. Uninstalling destroys all data in the canister.
+    #[arg(long, short)]
+    yes: bool,
 }
 
 async fn uninstall_code(
     env: &dyn Environment,
     canister: &str,
     call_sender: &CallSender,
+    skip_consent: bool,
 ) -> DfxResult {
     let log = env.get_logger();
     let canister_id_store = env.get_canister_id_store()?;
-    let canister_id =
-        Principal::from_text(canister).or_else(|_| canister_id_store.get(canister))?;
+    let (canister_id, canister_name) = match Principal::from_text(canister) {
+        Ok(canister_id) => (canister_id, None),
+        Err(_) => (canister_id_store.get(canister)?, Some(canister)),
+    };
+
+    if !skip_consent {
+        ask_to_destroy_state(
+            env.get_agent(),
+            canister_id,
+            canister_name,
+            DestructiveAction::UninstallCode,
+            call_sender,
+        )
+        .await
+        .context("Refusing to uninstall code without approval")?;
+    }
 
     info!(
         log,
@@ -50,13 +71,13 @@ pub async fn exec(
     fetch_root_key_if_needed(env).await?;
 
     if let Some(canister) = opts.canister.as_deref() {
-        uninstall_code(env, canister, call_sender).await
+        uninstall_code(env, canister, call_sender, opts.yes).await
     } else if opts.all {
         let config = env.get_config_or_anyhow()?;
 
         if let Some(canisters) = &config.get_config().canisters {
             for canister in canisters.keys() {
-                uninstall_code(env, canister, call_sender).await?;
+                uninstall_code(env, canister, call_sender, opts.yes).await?;
             }
         }
         Ok(())
@@ -3,13 +3,13 @@ use crate::lib::environment::Environment;
 use crate::lib::error::{DfxError, DfxResult};
 use crate::lib::ic_attributes::{
     get_compute_allocation, get_freezing_threshold, get_memory_allocation,
-    get_reserved_cycles_limit, CanisterSettings,
+    get_reserved_cycles_limit, get_wasm_memory_limit, CanisterSettings,
 };
 use crate::lib::operations::canister::{get_canister_status, update_settings};
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::{
     compute_allocation_parser, freezing_threshold_parser, memory_allocation_parser,
-    reserved_cycles_limit_parser,
+    reserved_cycles_limit_parser, wasm_memory_limit_parser,
 };
 use anyhow::{bail, Context};
 use byte_unit::Byte;
@@ -76,6 +76,11 @@ pub struct UpdateSettingsOpts {
     #[arg(long, value_parser = reserved_cycles_limit_parser)]
     reserved_cycles_limit: Option<u128>,
 
+    /// Sets a soft limit on the Wasm memory usage of the canister, in bytes (or with a unit, e.g. 2GiB).
+    /// Update calls, timers, heartbeats, installs and post-upgrades fail if the Wasm memory usage exceeds this limit.
+    #[arg(long, value_parser = wasm_memory_limit_parser)]
+    wasm_memory_limit: Option<Byte>,
+
     /// Freezing thresholds above ~1.5 years require this flag as confirmation.
     #[arg(long)]
     confirm_very_long_freezing_threshold: bool,
@@ -126,7 +131,7 @@ pub async fn exec(
         let config_interface = config.as_ref().map(|config| config.get_config());
         let mut controllers = controllers;
         let canister_id = CanisterId::from_text(canister_name_or_id)
-            .or_else(|_| canister_id_store.get(canister_name_or_id))?;
+            .or_else(|_| canister_id_store.get_including_aliases(canister_name_or_id))?;
         let textual_cid = canister_id.to_text();
         let canister_name = canister_id_store.get_name(&textual_cid).map(|x| &**x);
 
@@ -138,6 +143,8 @@ pub async fn exec(
             get_freezing_threshold(opts.freezing_threshold, config_interface, canister_name)?;
         let reserved_cycles_limit =
             get_reserved_cycles_limit(opts.reserved_cycles_limit, config_interface, canister_name)?;
+        let wasm_memory_limit =
+            get_wasm_memory_limit(opts.wasm_memory_limit, config_interface, canister_name)?;
         if let Some(added) = &opts.add_controller {
             let status = get_canister_status(env, canister_id, call_sender).await?;
             let mut existing_controllers = status.settings.controllers;
@@ -170,6 +177,7 @@ pub async fn exec(
             memory_allocation,
             freezing_threshold,
             reserved_cycles_limit,
+            wasm_memory_limit,
         };
         update_settings(env, canister_id, settings, call_sender).await?;
         display_controller_update(&opts, canister_name_or_id);
@@ -213,6 +221,14 @@ pub async fn exec(
                 .with_context(|| {
                     format!("Failed to get reserved cycles limit for {}.", canister_name)
                 })?;
+                let wasm_memory_limit = get_wasm_memory_limit(
+                    opts.wasm_memory_limit,
+                    Some(config_interface),
+                    Some(canister_name),
+                )
+                .with_context(|| {
+                    format!("Failed to get Wasm memory limit for {}.", canister_name)
+                })?;
                 if let Some(added) = &opts.add_controller {
                     let status = get_canister_status(env, canister_id, call_sender).await?;
                     let mut existing_controllers = status.settings.controllers;
@@ -245,6 +261,7 @@ pub async fn exec(
                     memory_allocation,
                     freezing_threshold,
                     reserved_cycles_limit,
+                    wasm_memory_limit,
                 };
                 update_settings(env, canister_id, settings, call_sender).await?;
                 display_controller_update(&opts, canister_name);
@@ -0,0 +1,148 @@
+use crate::lib::canister_info::assets::AssetsCanisterInfo;
+use crate::lib::canister_info::CanisterInfo;
+use crate::lib::domains::read_domains_from_assets;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::named_canister::get_ui_canister_id;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::network::url::{
+    construct_frontend_url, construct_raw_url, construct_ui_canister_url,
+};
+use anyhow::{bail, Context};
+use candid::Principal;
+use clap::Parser;
+use dfx_core::config::model::canister_id_store::CanisterIdStore;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use url::Url;
+
+/// Prints the URLs at which a canister can be reached on the selected network.
+#[derive(Parser)]
+pub struct CanisterUrlOpts {
+    /// Specifies the name or id of the canister.
+    canister: String,
+
+    /// Prints only the URL of the frontend.
+    #[arg(long, conflicts_with = "candid")]
+    frontend: bool,
+
+    /// Prints only the URL of the Candid UI for the canister.
+    #[arg(long)]
+    candid: bool,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+}
+
+pub async fn exec(env: &dyn Environment, opts: CanisterUrlOpts) -> DfxResult {
+    let network_descriptor = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        opts.network.to_network_name(env),
+        None,
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+    let canister_id_store =
+        CanisterIdStore::new(env.get_logger(), &network_descriptor, env.get_config())?;
+
+    let (canister_id, canister_name) = match Principal::from_text(&opts.canister) {
+        Ok(id) => (id, canister_id_store.get_name(&opts.canister).cloned()),
+        Err(_) => (
+            canister_id_store.get(&opts.canister)?,
+            Some(opts.canister.clone()),
+        ),
+    };
+    let config = env.get_config();
+    let canister_config = config
+        .as_ref()
+        .zip(canister_name.as_ref())
+        .and_then(|(config, name)| {
+            let canister_config = config.get_config().canisters.as_ref()?.get(name)?;
+            Some((config, name, canister_config))
+        });
+
+    // Without a definition in dfx.json, the canister may serve either, so both are shown.
+    let (has_frontend, has_candid_ui, custom_domains) = match canister_config {
+        Some((config, name, canister_config)) => {
+            let info = CanisterInfo::load(config, name, Some(canister_id))?;
+            let has_frontend = info.is_assets() || canister_config.frontend.is_some();
+            let mut custom_domains = vec![];
+            if info.is_assets() {
+                for source_dir in info.as_info::<AssetsCanisterInfo>()?.get_source_paths() {
+                    custom_domains.extend(read_domains_from_assets(&source_dir)?);
+                }
+            }
+            (has_frontend, !info.is_assets(), custom_domains)
+        }
+        None => (true, true, vec![]),
+    };
+
+    let frontend_url = if has_frontend {
+        Some(construct_frontend_url(&network_descriptor, &canister_id)?)
+    } else {
+        None
+    };
+    let candid_url = if has_candid_ui {
+        construct_ui_canister_url(
+            &network_descriptor,
+            &canister_id,
+            get_ui_canister_id(&network_descriptor, &canister_id_store),
+        )?
+    } else {
+        None
+    };
+
+    if opts.frontend {
+        match frontend_url {
+            Some(url) => println!("{}", url),
+            None => bail!("Canister {} has no frontend.", opts.canister),
+        }
+        return Ok(());
+    }
+    if opts.candid {
+        match candid_url {
+            Some(url) => println!("{}", url),
+            None if !has_candid_ui => bail!(
+                "Canister {} is an asset canister, which is not called through the Candid UI.",
+                opts.canister
+            ),
+            None => bail!(
+                "The Candid UI canister is not installed on network '{}'. It is installed by `dfx start` unless `candid_ui` is disabled, or by `dfx deploy` on networks that `dfx start` does not run.",
+                network_descriptor.name
+            ),
+        }
+        return Ok(());
+    }
+
+    if let Some(url) = frontend_url {
+        println!("Frontend: {}", url);
+        if let Some(raw_url) = construct_raw_url(&network_descriptor, &canister_id)? {
+            println!("Raw: {}", raw_url);
+        }
+    }
+    if network_descriptor.is_ic {
+        for domain in custom_domains {
+            let url = Url::parse(&format!("https://{}", domain))
+                .with_context(|| format!("Invalid custom domain '{}'.", domain))?;
+            println!("Custom domain: {}", url);
+        }
+    } else if let Some(local_server_descriptor) = &network_descriptor.local_server_descriptor {
+        let port = Url::parse(&network_descriptor.providers[0])
+            .ok()
+            .and_then(|url| url.port())
+            .unwrap_or_else(|| local_server_descriptor.bind_address.port());
+        for (domain, _) in local_server_descriptor
+            .proxy
+            .custom_domains
+            .iter()
+            .filter(|(_, id)| **id == canister_id)
+        {
+            let url = Url::parse(&format!("http://{}:{}", domain, port))
+                .with_context(|| format!("Invalid custom domain '{}'.", domain))?;
+            println!("Custom domain: {}", url);
+        }
+    }
+    if let Some(url) = candid_url {
+        println!("Candid UI: {}", url);
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::lib::builders::BuildConfig;
+use crate::lib::canister_info::CanisterInfo;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::models::canister::CanisterPool;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::state_tree::canister_info::read_state_tree_canister_module_hash;
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use sha2::{Digest, Sha256};
+use slog::info;
+
+/// Rebuilds a canister of the project and checks that the result matches the module that is deployed.
+/// The canister is built like `dfx build` builds it, so in its build_container if it has one in dfx.json.
+#[derive(Parser)]
+pub struct CanisterVerifyOpts {
+    /// Specifies the name of the canister to verify.
+    canister: String,
+}
+
+pub async fn exec(env: &dyn Environment, opts: CanisterVerifyOpts) -> DfxResult {
+    let log = env.get_logger();
+    let config = env.get_config_or_anyhow()?;
+    let canister_name = opts.canister.as_str();
+    let canister_id = env.get_canister_id_store()?.get(canister_name)?;
+
+    fetch_root_key_if_needed(env).await?;
+    let deployed_hash = read_state_tree_canister_module_hash(env.get_agent(), canister_id)
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "No module is installed in canister {} ({}).",
+                canister_name,
+                canister_id
+            )
+        })?;
+
+    let canister_info = CanisterInfo::load(&config, canister_name, Some(canister_id))?;
+    info!(log, "Building canister '{}'...", canister_name);
+    let canister_names = config
+        .get_config()
+        .get_canister_names_with_dependencies(Some(canister_name))?;
+    let canister_pool = CanisterPool::load(env, false, &canister_names)?;
+    let build_config =
+        BuildConfig::from_config(&config, env.get_network_descriptor().is_playground())?
+            .with_canisters_to_build(vec![canister_name.to_string()]);
+    canister_pool.build_or_fail(log, &build_config).await?;
+
+    let wasm_path = canister_info.get_build_wasm_path();
+    let wasm = dfx_core::fs::read(&wasm_path)?;
+    let local_hash = Sha256::digest(wasm);
+
+    if local_hash[..] == deployed_hash[..] {
+        println!(
+            "Verified: the module deployed in canister {} ({}) matches the local build.\nModule hash: 0x{}",
+            canister_name,
+            canister_id,
+            hex::encode(local_hash)
+        );
+        Ok(())
+    } else {
+        bail!(
+            "Verification failed: the module deployed in canister {} ({}) does not match the local build.\nDeployed module hash: 0x{}\nLocal module hash:    0x{} ({})",
+            canister_name,
+            canister_id,
+            hex::encode(deployed_hash),
+            hex::encode(local_hash),
+            wasm_path.display()
+        )
+    }
+}
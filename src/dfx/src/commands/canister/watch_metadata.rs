@@ -0,0 +1,160 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::state_tree::canister_info::{
+    read_state_tree_canister_controllers, read_state_tree_canister_module_hash,
+};
+use crate::util::clap::parsers::duration_parser;
+use anyhow::Context;
+use candid::Principal;
+use clap::Parser;
+use ic_agent::Agent;
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::{json, Value};
+use slog::{info, warn, Logger};
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+/// Watches a canister for changes to its module hash or controllers.
+/// Polls the state tree of the canister, so any canister can be watched, not only those of the current project.
+/// Each change is printed to stdout as a line of JSON, and optionally posted to a webhook.
+#[derive(Parser)]
+pub struct WatchMetadataOpts {
+    /// Specifies the name or id of the canister to watch.
+    canister: String,
+
+    /// How often to poll the state of the canister, e.g. 10s or 5m.
+    #[arg(long, default_value = "30s", value_parser = duration_parser)]
+    interval: Duration,
+
+    /// Posts each change as JSON to this URL.
+    #[arg(long)]
+    webhook: Option<Url>,
+
+    /// Exits after the first change, e.g. to wait for a canister to be upgraded.
+    #[arg(long)]
+    until_change: bool,
+}
+
+/// A change to the state of a canister, as printed and posted to the webhook.
+#[derive(Serialize)]
+struct MetadataChange {
+    canister_id: String,
+    /// Either `module_hash` or `controllers`.
+    field: &'static str,
+    previous: Value,
+    current: Value,
+    /// When the change was detected, in RFC 3339 format.
+    detected_at: String,
+}
+
+#[derive(PartialEq)]
+struct CanisterMetadata {
+    module_hash: Value,
+    controllers: Value,
+}
+
+impl CanisterMetadata {
+    fn fields(&self) -> [(&'static str, &Value); 2] {
+        [
+            ("module_hash", &self.module_hash),
+            ("controllers", &self.controllers),
+        ]
+    }
+}
+
+pub async fn exec(env: &dyn Environment, opts: WatchMetadataOpts) -> DfxResult {
+    let log = env.get_logger();
+    let agent = env.get_agent();
+    let canister_id = match Principal::from_text(&opts.canister) {
+        Ok(canister_id) => canister_id,
+        Err(_) => env.get_canister_id_store()?.get(&opts.canister)?,
+    };
+    fetch_root_key_if_needed(env).await?;
+    let client = reqwest::Client::builder()
+        .use_rustls_tls()
+        .build()
+        .context("Could not create HTTP client.")?;
+
+    let mut previous = read_metadata(agent, canister_id).await?;
+    info!(
+        log,
+        "Watching canister {} every {}.",
+        canister_id,
+        humantime::format_duration(opts.interval)
+    );
+    loop {
+        tokio::time::sleep(opts.interval).await;
+        // A monitor should outlive transient errors of the network.
+        let current = match read_metadata(agent, canister_id).await {
+            Ok(current) => current,
+            Err(err) => {
+                warn!(
+                    log,
+                    "Failed to read the state of canister {}: {:#}", canister_id, err
+                );
+                continue;
+            }
+        };
+        if current == previous {
+            continue;
+        }
+        let detected_at = humantime::format_rfc3339_seconds(SystemTime::now()).to_string();
+        for ((field, previous), (_, current)) in previous.fields().into_iter().zip(current.fields())
+        {
+            if previous != current {
+                let change = MetadataChange {
+                    canister_id: canister_id.to_text(),
+                    field,
+                    previous: previous.clone(),
+                    current: current.clone(),
+                    detected_at: detected_at.clone(),
+                };
+                println!("{}", serde_json::to_string(&change)?);
+                if let Some(webhook) = &opts.webhook {
+                    post_change(log, &client, webhook, &change).await;
+                }
+            }
+        }
+        if opts.until_change {
+            return Ok(());
+        }
+        previous = current;
+    }
+}
+
+async fn read_metadata(agent: &Agent, canister_id: Principal) -> DfxResult<CanisterMetadata> {
+    let controllers = read_state_tree_canister_controllers(agent, canister_id)
+        .await?
+        .map(|controllers| {
+            controllers
+                .iter()
+                .map(Principal::to_text)
+                .sorted()
+                .collect_vec()
+        });
+    let module_hash = read_state_tree_canister_module_hash(agent, canister_id).await?;
+    Ok(CanisterMetadata {
+        module_hash: json!(module_hash.map(|blob| format!("0x{}", hex::encode(blob)))),
+        controllers: json!(controllers),
+    })
+}
+
+/// Errors of the webhook are only warned about, so that watching continues.
+async fn post_change(
+    log: &Logger,
+    client: &reqwest::Client,
+    webhook: &Url,
+    change: &MetadataChange,
+) {
+    let result = client
+        .post(webhook.clone())
+        .json(change)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    if let Err(err) = result {
+        warn!(log, "Failed to post the change to {}: {}", webhook, err);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::{bail, Context};
+use clap::Parser;
+
+/// Prints the value at a path of dfx.json, as JSON.
+#[derive(Parser)]
+pub struct ConfigGetOpts {
+    /// The path of the value, for example canisters.app.type or /canisters/app/type.
+    path: String,
+}
+
+pub fn exec(env: &dyn Environment, opts: ConfigGetOpts) -> DfxResult {
+    let config = env.get_config_or_anyhow()?;
+    let Some(value) = config.get_json_value(&opts.path)? else {
+        bail!("There is no value at '{}' in dfx.json.", opts.path);
+    };
+    let output = serde_json::to_string_pretty(value).context("Failed to serialize value.")?;
+    println!("{}", output);
+    Ok(())
+}
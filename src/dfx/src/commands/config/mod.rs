@@ -0,0 +1,32 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+
+mod get;
+mod set;
+mod unset;
+
+/// Reads and changes values in dfx.json.
+///
+/// Values are addressed by a path, either a list of keys separated by dots
+/// (canisters.app.type) or a JSON pointer (/canisters/app/type).
+#[derive(Parser)]
+pub struct ConfigOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Get(get::ConfigGetOpts),
+    Set(set::ConfigSetOpts),
+    Unset(unset::ConfigUnsetOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: ConfigOpts) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::Get(v) => get::exec(env, v),
+        SubCommand::Set(v) => set::exec(env, v),
+        SubCommand::Unset(v) => unset::exec(env, v),
+    }
+}
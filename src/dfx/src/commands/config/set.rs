@@ -0,0 +1,33 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+use serde_json::Value;
+use slog::info;
+
+/// Sets the value at a path of dfx.json, creating any missing objects along the way.
+/// dfx.json is only saved if it is still valid afterwards.
+#[derive(Parser)]
+pub struct ConfigSetOpts {
+    /// The path of the value, for example canisters.app.type or /canisters/app/type.
+    path: String,
+
+    /// The new value, as JSON. A value that is not valid JSON is used as a string.
+    value: String,
+
+    /// Always use the value as a string, even if it is valid JSON.
+    #[arg(long)]
+    string: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: ConfigSetOpts) -> DfxResult {
+    let mut config = env.get_config_or_anyhow()?.as_ref().clone();
+    let value = if opts.string {
+        Value::String(opts.value)
+    } else {
+        serde_json::from_str(&opts.value).unwrap_or(Value::String(opts.value))
+    };
+    config.set_json_value(&opts.path, Some(value))?;
+    config.save()?;
+    info!(env.get_logger(), "Set '{}' in dfx.json.", opts.path);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::bail;
+use clap::Parser;
+use slog::info;
+
+/// Removes the value at a path of dfx.json.
+/// dfx.json is only saved if it is still valid afterwards.
+#[derive(Parser)]
+pub struct ConfigUnsetOpts {
+    /// The path of the value, for example canisters.app.initialization_values.
+    path: String,
+}
+
+pub fn exec(env: &dyn Environment, opts: ConfigUnsetOpts) -> DfxResult {
+    let mut config = env.get_config_or_anyhow()?.as_ref().clone();
+    if config.get_json_value(&opts.path)?.is_none() {
+        bail!("There is no value at '{}' in dfx.json.", opts.path);
+    }
+    config.set_json_value(&opts.path, None)?;
+    config.save()?;
+    info!(env.get_logger(), "Removed '{}' from dfx.json.", opts.path);
+    Ok(())
+}
@@ -2,6 +2,7 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::nns_types::account_identifier::Subaccount;
 use crate::lib::operations::cycles_ledger;
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::cycle_amount_parser;
 use candid::Principal;
@@ -62,6 +63,7 @@ pub async fn exec(env: &dyn Environment, opts: ApproveOpts) -> DfxResult {
     let from_subaccount = opts.from_subaccount.map(|x| x.0);
     let result = cycles_ledger::approve(
         agent,
+        retry_policy(env),
         env.get_logger(),
         opts.amount,
         opts.spender,
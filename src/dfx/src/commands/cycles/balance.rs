@@ -2,6 +2,7 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::nns_types::account_identifier::Subaccount;
 use crate::lib::operations::cycles_ledger;
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::{format_as_trillions, pretty_thousand_separators};
 use candid::Principal;
@@ -35,7 +36,7 @@ pub async fn exec(env: &dyn Environment, opts: CyclesBalanceOpts) -> DfxResult {
 
     let subaccount = opts.subaccount.map(|x| x.0);
 
-    let balance = cycles_ledger::balance(agent, owner, subaccount).await?;
+    let balance = cycles_ledger::balance(agent, retry_policy(env), owner, subaccount).await?;
 
     if opts.precise {
         println!("{} cycles.", balance);
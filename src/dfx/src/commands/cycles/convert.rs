@@ -5,6 +5,7 @@ use crate::lib::ledger_types::{Memo as ICPMemo, NotifyError, NotifyMintCyclesSuc
 use crate::lib::nns_types::account_identifier::Subaccount as ICPSubaccount;
 use crate::lib::nns_types::icpts::{ICPTs, TRANSACTION_FEE};
 use crate::lib::operations::cmc::{notify_mint_cycles, transfer_cmc};
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::{e8s_parser, icrc_subaccount_parser};
 use anyhow::{anyhow, bail};
@@ -66,6 +67,7 @@ pub async fn exec(env: &dyn Environment, opts: ConvertOpts) -> DfxResult {
 
     let height = transfer_cmc(
         agent,
+        retry_policy(env),
         env.get_logger(),
         memo,
         amount,
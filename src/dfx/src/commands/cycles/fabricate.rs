@@ -0,0 +1,72 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::identity::wallet::get_or_create_wallet_canister;
+use crate::lib::operations::canister::provisional_deposit_cycles;
+use crate::lib::operations::cycles_ledger::{self, wallet_deposit_to_cycles_ledger};
+use crate::lib::retryable::retry_policy;
+use crate::lib::root_key::fetch_root_key_or_anyhow;
+use crate::util::clap::parsers::{cycle_amount_parser, icrc_subaccount_parser};
+use crate::util::format_as_trillions;
+use anyhow::Context;
+use candid::Principal;
+use clap::Parser;
+use dfx_core::identity::CallSender;
+use icrc_ledger_types::icrc1::account::{Account, Subaccount};
+use slog::info;
+
+/// Local development only: Fabricate cycles out of thin air and deposit them into an account of the
+/// local cycles ledger. The cycles are deposited through the wallet of the selected identity.
+#[derive(Parser)]
+pub struct FabricateOpts {
+    /// The number of cycles to fabricate.
+    #[arg(value_parser = cycle_amount_parser)]
+    amount: u128,
+
+    /// Deposit the cycles to the account of this principal. Defaults to the selected identity.
+    #[arg(long)]
+    to: Option<Principal>,
+
+    /// Deposit the cycles to this subaccount.
+    #[arg(long, value_parser = icrc_subaccount_parser)]
+    to_subaccount: Option<Subaccount>,
+}
+
+pub async fn exec(env: &dyn Environment, opts: FabricateOpts) -> DfxResult {
+    fetch_root_key_or_anyhow(env).await?;
+    let agent = env.get_agent();
+    let owner = match opts.to {
+        Some(principal) => principal,
+        None => env
+            .get_selected_identity_principal()
+            .context("No identity is selected.")?,
+    };
+
+    let wallet = get_or_create_wallet_canister(
+        env,
+        env.get_network_descriptor(),
+        env.get_selected_identity().expect("No selected identity"),
+    )
+    .await?;
+    let wallet_id = *wallet.canister_id_();
+    provisional_deposit_cycles(env, wallet_id, &CallSender::SelectedId, opts.amount).await?;
+    let to = Account {
+        owner,
+        subaccount: opts.to_subaccount,
+    };
+    wallet_deposit_to_cycles_ledger(agent, wallet_id, opts.amount, to)
+        .await
+        .context(
+            "Failed to deposit the cycles. Is the cycles ledger installed on the local network?",
+        )?;
+
+    let balance =
+        cycles_ledger::balance(agent, retry_policy(env), owner, opts.to_subaccount).await?;
+    info!(
+        env.get_logger(),
+        "Fabricated {} cycles into the account of {}. Balance: {} TC (trillion cycles).",
+        opts.amount,
+        owner,
+        format_as_trillions(balance)
+    );
+    Ok(())
+}
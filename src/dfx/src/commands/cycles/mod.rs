@@ -7,7 +7,9 @@ use tokio::runtime::Runtime;
 
 mod approve;
 mod balance;
-mod convert;
+pub mod convert;
+mod fabricate;
+mod rate;
 mod redeem_faucet_coupon;
 pub mod top_up;
 mod transfer;
@@ -28,19 +30,23 @@ enum SubCommand {
     Approve(approve::ApproveOpts),
     Balance(balance::CyclesBalanceOpts),
     Convert(convert::ConvertOpts),
+    Fabricate(fabricate::FabricateOpts),
+    Rate(rate::RateOpts),
     TopUp(top_up::TopUpOpts),
     Transfer(transfer::TransferOpts),
     RedeemFaucetCoupon(redeem_faucet_coupon::RedeemFaucetCouponOpts),
 }
 
 pub fn exec(env: &dyn Environment, opts: CyclesOpts) -> DfxResult {
-    let agent_env = create_agent_environment(env, opts.network.to_network_name())?;
+    let agent_env = create_agent_environment(env, opts.network.to_network_name(env))?;
     let runtime = Runtime::new().expect("Unable to create a runtime");
     runtime.block_on(async {
         match opts.subcmd {
             SubCommand::Approve(v) => approve::exec(&agent_env, v).await,
             SubCommand::Balance(v) => balance::exec(&agent_env, v).await,
             SubCommand::Convert(v) => convert::exec(&agent_env, v).await,
+            SubCommand::Fabricate(v) => fabricate::exec(&agent_env, v).await,
+            SubCommand::Rate(v) => rate::exec(&agent_env, v).await,
             SubCommand::TopUp(v) => top_up::exec(&agent_env, v).await,
             SubCommand::Transfer(v) => transfer::exec(&agent_env, v).await,
             SubCommand::RedeemFaucetCoupon(v) => redeem_faucet_coupon::exec(&agent_env, v).await,
@@ -0,0 +1,54 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::nns_types::icpts::ICPTs;
+use crate::lib::operations::ledger::xdr_permyriad_per_icp;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::util::clap::parsers::cycle_amount_parser;
+use crate::util::currency_conversion::{cycles_to_icp, icp_to_cycles, xdr_per_icp, CYCLES_PER_XDR};
+use crate::util::{format_as_trillions, pretty_thousand_separators};
+use clap::Parser;
+
+/// Show the current ICP <> XDR conversion rate of the cycles minting canister,
+/// and how many cycles an amount of ICP is worth, or how much ICP an amount of cycles costs.
+#[derive(Parser)]
+pub struct RateOpts {
+    /// Show how many cycles this amount of ICP is converted into.
+    /// Can be specified as a Decimal with the fractional portion up to 8 decimal places
+    /// i.e. 100.012
+    #[arg(long, conflicts_with("cycles"))]
+    icp: Option<ICPTs>,
+
+    /// Show how much ICP is needed to get this number of cycles.
+    /// Accepts suffixes like 3.5TC, 2t or 5_000_000.
+    #[arg(long, value_parser = cycle_amount_parser)]
+    cycles: Option<u128>,
+}
+
+pub async fn exec(env: &dyn Environment, opts: RateOpts) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+
+    let rate = xdr_permyriad_per_icp(env.get_agent()).await?;
+    let cycles_per_icp = icp_to_cycles(rate, &ICPTs::from_e8s(100_000_000))?;
+    println!("Conversion rate: 1 ICP <> {} XDR", xdr_per_icp(rate));
+    println!(
+        "1 ICP <> {} TC (trillion cycles), 1 XDR <> {} TC.",
+        pretty_thousand_separators(format_as_trillions(cycles_per_icp)),
+        format_as_trillions(CYCLES_PER_XDR)
+    );
+
+    if let Some(icp) = opts.icp {
+        let cycles = icp_to_cycles(rate, &icp)?;
+        println!(
+            "{icp} <> {} TC ({cycles} cycles).",
+            pretty_thousand_separators(format_as_trillions(cycles))
+        );
+    }
+    if let Some(cycles) = opts.cycles {
+        let icp = cycles_to_icp(rate, cycles)?;
+        println!(
+            "{} TC ({cycles} cycles) <> {icp}.",
+            pretty_thousand_separators(format_as_trillions(cycles))
+        );
+    }
+    Ok(())
+}
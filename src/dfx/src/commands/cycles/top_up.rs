@@ -1,15 +1,26 @@
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use crate::lib::identity::wallet::wallet_canister_id;
 use crate::lib::nns_types::account_identifier::Subaccount;
+use crate::lib::operations::canister::deposit_cycles;
 use crate::lib::operations::cycles_ledger;
+use crate::lib::operations::cycles_ledger::uses_cycles_ledger_instead_of_wallet;
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::cycle_amount_parser;
+use crate::util::{format_as_trillions, pretty_thousand_separators};
+use anyhow::anyhow;
 use candid::Principal;
 use clap::Parser;
-use slog::warn;
+use dfx_core::cli::ask_for_consent;
+use dfx_core::identity::CallSender;
+use slog::{info, warn};
+use std::io::{stdin, IsTerminal};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Send cycles to a canister.
+/// The cycles are withdrawn from the cycles ledger. If the cycles ledger balance is too low
+/// and the selected identity has a cycles wallet, the wallet deposits the cycles instead.
 #[derive(Parser)]
 pub struct TopUpOpts {
     /// Send cycles to this canister.
@@ -27,6 +38,11 @@ pub struct TopUpOpts {
     /// https://internetcomputer.org/docs/current/developer-docs/integrations/icrc-1/#transaction-deduplication-
     #[arg(long)]
     created_at_time: Option<u64>,
+
+    /// Send the cycles without asking for confirmation.
+    /// Confirmation is only asked for in an interactive terminal.
+    #[arg(long, short)]
+    yes: bool,
 }
 
 pub async fn exec(env: &dyn Environment, opts: TopUpOpts) -> DfxResult {
@@ -45,8 +61,59 @@ pub async fn exec(env: &dyn Environment, opts: TopUpOpts) -> DfxResult {
 
     let to = get_canister_id(env, &opts.to)?;
     let from_subaccount = opts.from_subaccount.map(|x| x.0);
+    let owner = env
+        .get_selected_identity_principal()
+        .ok_or_else(|| anyhow!("Selected identity not instantiated."))?;
+    let fee = cycles_ledger::fee(agent, retry_policy(env)).await?;
+    let balance = cycles_ledger::balance(agent, retry_policy(env), owner, from_subaccount).await?;
+    let confirm = !opts.yes && stdin().is_terminal();
+
+    // Falling back to the wallet only makes sense for the default account: a subaccount
+    // was chosen explicitly, so its balance is what the user wants to spend.
+    // Projects that use the cycles ledger instead of a wallet never fall back to it.
+    if balance < amount.saturating_add(fee)
+        && from_subaccount.is_none()
+        && !uses_cycles_ledger_instead_of_wallet(env)
+    {
+        if let Some(wallet_id) = selected_wallet(env)? {
+            info!(
+                env.get_logger(),
+                "The cycles ledger balance of {} is {} cycles, which does not cover {} cycles plus the fee of {} cycles. Using cycles wallet {} instead.",
+                owner,
+                balance,
+                amount,
+                fee,
+                wallet_id
+            );
+            if confirm {
+                ask_for_consent(&format!(
+                    "Sending {} TC ({} cycles) from cycles wallet {} to canister {}.",
+                    pretty_thousand_separators(format_as_trillions(amount)),
+                    amount,
+                    wallet_id,
+                    to
+                ))?;
+            }
+            deposit_cycles(env, to, &CallSender::Wallet(wallet_id), amount).await?;
+            println!("Deposited {amount} cycles from cycles wallet {wallet_id}");
+            return Ok(());
+        }
+    }
+
+    if confirm {
+        ask_for_consent(&format!(
+            "Sending {} TC ({} cycles) to canister {}. The cycles ledger charges a fee of {} cycles, so {} cycles will be withdrawn from a balance of {} cycles.",
+            pretty_thousand_separators(format_as_trillions(amount)),
+            amount,
+            to,
+            fee,
+            amount.saturating_add(fee),
+            balance
+        ))?;
+    }
     let result = cycles_ledger::send(
         agent,
+        retry_policy(env),
         env.get_logger(),
         to,
         amount,
@@ -70,7 +137,17 @@ pub async fn exec(env: &dyn Environment, opts: TopUpOpts) -> DfxResult {
 fn get_canister_id(env: &dyn Environment, s: &str) -> DfxResult<Principal> {
     let principal = Principal::from_text(s).or_else(|_| {
         env.get_canister_id_store()
-            .and_then(|canister_id_store| canister_id_store.get(s))
+            .and_then(|canister_id_store| canister_id_store.get_including_aliases(s))
     })?;
     Ok(principal)
 }
+
+fn selected_wallet(env: &dyn Environment) -> DfxResult<Option<Principal>> {
+    let Some(identity_name) = env.get_selected_identity() else {
+        return Ok(None);
+    };
+    Ok(wallet_canister_id(
+        env.get_network_descriptor(),
+        identity_name,
+    )?)
+}
@@ -2,6 +2,7 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::nns_types::account_identifier::Subaccount;
 use crate::lib::operations::cycles_ledger;
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::cycle_amount_parser;
 use candid::Principal;
@@ -75,6 +76,7 @@ pub async fn exec(env: &dyn Environment, opts: TransferOpts) -> DfxResult {
         };
         cycles_ledger::transfer_from(
             agent,
+            retry_policy(env),
             env.get_logger(),
             spender_subaccount,
             from,
@@ -87,6 +89,7 @@ pub async fn exec(env: &dyn Environment, opts: TransferOpts) -> DfxResult {
     } else {
         cycles_ledger::transfer(
             agent,
+            retry_policy(env),
             env.get_logger(),
             amount,
             from_subaccount,
@@ -2,13 +2,15 @@ use crate::lib::agent::create_agent_environment;
 use crate::lib::canister_info::CanisterInfo;
 use crate::lib::error::DfxResult;
 use crate::lib::network::network_opt::NetworkOpt;
-use crate::lib::operations::canister::deploy_canisters::deploy_canisters;
+use crate::lib::network::url::{construct_frontend_url, construct_ui_canister_url};
 use crate::lib::operations::canister::deploy_canisters::DeployMode::{
     ComputeEvidence, ForceReinstallSingleCanister, NormalDeploy, PrepareForProposal,
 };
+use crate::lib::operations::canister::deploy_canisters::{deploy_canisters, DeployPlanMode};
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::lib::{environment::Environment, named_canister};
 use crate::util::clap::argument_from_cli::ArgumentFromCliLongOpt;
+use crate::util::clap::init_preset_opt::InitPresetOpt;
 use crate::util::clap::parsers::{cycle_amount_parser, icrc_subaccount_parser};
 use crate::util::clap::subnet_selection_opt::SubnetSelectionOpt;
 use anyhow::{anyhow, bail, Context};
@@ -17,7 +19,6 @@ use clap::Parser;
 use console::Style;
 use dfx_core::config::model::network_descriptor::NetworkDescriptor;
 use dfx_core::identity::CallSender;
-use fn_error_context::context;
 use ic_utils::interfaces::management_canister::builders::InstallMode;
 use icrc_ledger_types::icrc1::account::Subaccount;
 use slog::info;
@@ -25,11 +26,8 @@ use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 use tokio::runtime::Runtime;
-use url::Host::Domain;
 use url::Url;
 
-const MAINNET_CANDID_INTERFACE_PRINCIPAL: &str = "a4gq6-oaaaa-aaaab-qaa4q-cai";
-
 /// Deploys all or a specific canister from the code in your project. By default, all canisters are deployed.
 #[derive(Parser)]
 pub struct DeployOpts {
@@ -37,9 +35,21 @@ pub struct DeployOpts {
     /// If you don’t specify a canister name, all canisters defined in the dfx.json file are deployed.
     canister_name: Option<String>,
 
+    /// Deploys the dependencies of the canister, but not the canister itself.
+    #[arg(long, requires("canister_name"), conflicts_with_all(["mode", "by_proposal", "compute_evidence"]))]
+    only_dependencies: bool,
+
+    /// Skips installing this canister. Can be specified more than once.
+    /// An excluded canister is still created and built if a deployed canister depends on it.
+    #[arg(long, value_name = "CANISTER")]
+    exclude: Vec<String>,
+
     #[command(flatten)]
     argument_from_cli: ArgumentFromCliLongOpt,
 
+    #[command(flatten)]
+    init_preset: InitPresetOpt,
+
     /// Force the type of deployment to be reinstall, which overwrites the module.
     /// In other words, this erases all data in the canister.
     /// By default, upgrade will be chosen automatically if the module already exists,
@@ -87,10 +97,24 @@ pub struct DeployOpts {
     #[arg(long, short)]
     yes: bool,
 
+    /// Shows what the deploy would do without deploying: the canisters it would create, install,
+    /// upgrade or leave unchanged, with the hashes of their modules, and the settings it would change.
+    /// Existing canisters are built to compare their modules with the deployed ones.
+    #[arg(long, conflicts_with_all(["by_proposal", "compute_evidence", "auto_approve"]))]
+    plan: bool,
+
+    /// Deploys to mainnet without showing the plan and asking for confirmation first.
+    #[arg(long)]
+    auto_approve: bool,
+
     /// Skips upgrading the asset canister, to only install the assets themselves.
     #[arg(long)]
     no_asset_upgrade: bool,
 
+    /// Installs wasm modules even if their hash is not in the allowed_wasm_hashes of the canister in dfx.json.
+    #[arg(long)]
+    allow_unverified: bool,
+
     /// Prepare (upload) assets for later commit by proposal.
     #[arg(long, conflicts_with("compute_evidence"))]
     by_proposal: bool,
@@ -99,6 +123,10 @@ pub struct DeployOpts {
     #[arg(long, conflicts_with("by_proposal"))]
     compute_evidence: bool,
 
+    /// After uploading assets, check that they are served with a valid certificate.
+    #[arg(long, conflicts_with_all(["by_proposal", "compute_evidence"]))]
+    check_certification: bool,
+
     /// Transaction timestamp, in nanoseconds, for use in controlling transaction deduplication, default is system time.
     /// https://internetcomputer.org/docs/current/developer-docs/integrations/icrc-1/#transaction-deduplication-
     //TODO(SDK-1331): unhide
@@ -112,17 +140,28 @@ pub struct DeployOpts {
 
     #[command(flatten)]
     subnet_selection: SubnetSelectionOpt,
+
+    /// Overrides the build profile of dfx.json: debug, release, test-coverage, or one of the profiles of dfx.json.
+    /// test-coverage instruments Rust canisters to collect code coverage.
+    #[arg(long, value_name = "PROFILE")]
+    profile: Option<String>,
 }
 
 pub fn exec(env: &dyn Environment, opts: DeployOpts) -> DfxResult {
-    let env = create_agent_environment(env, opts.network.to_network_name())?;
+    let env = create_agent_environment(env, opts.network.to_network_name(env))?;
     let runtime = Runtime::new().expect("Unable to create a runtime");
 
     let canister_name = opts.canister_name.as_deref();
-    let (argument_from_cli, argument_type) = opts.argument_from_cli.get_argument_and_type()?;
+    let (argument_from_cli, argument_type) = match opts.init_preset.get_argument(&env)? {
+        Some(argument) => (Some(argument), Some("idl".to_string())),
+        None => opts.argument_from_cli.get_argument_and_type()?,
+    };
     if argument_from_cli.is_some() && canister_name.is_none() {
         bail!("The init argument can only be set when deploying a single canister.");
     }
+    if argument_from_cli.is_some() && opts.only_dependencies {
+        bail!("The init argument cannot be set with --only-dependencies, because the canister itself is not installed.");
+    }
     let mode = opts
         .mode
         .as_deref()
@@ -171,6 +210,18 @@ pub fn exec(env: &dyn Environment, opts: DeployOpts) -> DfxResult {
     let call_sender = CallSender::from(&opts.wallet)
         .map_err(|e| anyhow!("Failed to determine call sender: {}", e))?;
 
+    let plan_mode = if opts.plan {
+        DeployPlanMode::PrintOnly
+    } else if env.get_network_descriptor().is_ic
+        && !opts.yes
+        && !opts.auto_approve
+        && matches!(deploy_mode, NormalDeploy | ForceReinstallSingleCanister(_))
+    {
+        DeployPlanMode::Confirm
+    } else {
+        DeployPlanMode::Skip
+    };
+
     runtime.block_on(fetch_root_key_if_needed(&env))?;
 
     runtime.block_on(deploy_canisters(
@@ -189,10 +240,16 @@ pub fn exec(env: &dyn Environment, opts: DeployOpts) -> DfxResult {
         opts.yes,
         env_file,
         opts.no_asset_upgrade,
+        opts.allow_unverified,
         subnet_selection,
+        opts.check_certification,
+        opts.profile.as_deref(),
+        plan_mode,
+        opts.only_dependencies,
+        &opts.exclude,
     ))?;
 
-    if matches!(deploy_mode, NormalDeploy | ForceReinstallSingleCanister(_)) {
+    if !opts.plan && matches!(deploy_mode, NormalDeploy | ForceReinstallSingleCanister(_)) {
         display_urls(&env)?;
     }
     Ok(())
@@ -207,7 +264,7 @@ fn display_urls(env: &dyn Environment) -> DfxResult {
     let mut frontend_urls = BTreeMap::new();
     let mut candid_urls: BTreeMap<&String, Url> = BTreeMap::new();
 
-    let ui_canister_id = named_canister::get_ui_canister_id(&canister_id_store);
+    let ui_canister_id = named_canister::get_ui_canister_id(network, &canister_id_store);
 
     if let Some(canisters) = &config.get_config().canisters {
         for (canister_name, canister_config) in canisters {
@@ -261,65 +318,3 @@ fn display_urls(env: &dyn Environment) -> DfxResult {
 
     Ok(())
 }
-
-#[context("Failed to construct frontend url for canister {} on network '{}'.", canister_id, network.name)]
-fn construct_frontend_url(network: &NetworkDescriptor, canister_id: &Principal) -> DfxResult<Url> {
-    let mut url = Url::parse(&network.providers[0]).with_context(|| {
-        format!(
-            "Failed to parse url for network provider {}.",
-            &network.providers[0]
-        )
-    })?;
-
-    if let Some(Domain(domain)) = url.host() {
-        let host = format!("{}.{}", canister_id, domain);
-        url.set_host(Some(&host))
-            .with_context(|| format!("Failed to set host to {}.", host))?;
-    } else {
-        let query = format!("canisterId={}", canister_id);
-        url.set_query(Some(&query));
-    };
-
-    Ok(url)
-}
-
-#[context("Failed to construct ui canister url for {} on network '{}'.", canister_id, network.name)]
-fn construct_ui_canister_url(
-    network: &NetworkDescriptor,
-    canister_id: &Principal,
-    ui_canister_id: Option<Principal>,
-) -> DfxResult<Option<Url>> {
-    if network.is_ic {
-        let url = format!(
-            "https://{}.raw.icp0.io/?id={}",
-            MAINNET_CANDID_INTERFACE_PRINCIPAL, canister_id
-        );
-        let url = Url::parse(&url).with_context(|| {
-            format!(
-                "Failed to parse candid url {} for canister {}.",
-                &url, canister_id
-            )
-        })?;
-        Ok(Some(url))
-    } else if let Some(ui_canister_id) = ui_canister_id {
-        let mut url = Url::parse(&network.providers[0]).with_context(|| {
-            format!(
-                "Failed to parse network provider {}.",
-                &network.providers[0]
-            )
-        })?;
-        if let Some(Domain(domain)) = url.host() {
-            let host = format!("{}.{}", ui_canister_id, domain);
-            let query = format!("id={}", canister_id);
-            url.set_host(Some(&host))
-                .with_context(|| format!("Failed to set host to {}", &host))?;
-            url.set_query(Some(&query));
-        } else {
-            let query = format!("canisterId={}&id={}", ui_canister_id, canister_id);
-            url.set_query(Some(&query));
-        }
-        Ok(Some(url))
-    } else {
-        Ok(None)
-    }
-}
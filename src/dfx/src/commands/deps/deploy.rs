@@ -7,6 +7,7 @@ use crate::lib::deps::{
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::wasm::allowlist::verify_wasm_hash;
 
 use anyhow::Context;
 use candid::Principal;
@@ -14,6 +15,7 @@ use clap::Parser;
 use fn_error_context::context;
 use ic_agent::Agent;
 use ic_utils::interfaces::{management_canister::builders::InstallMode, ManagementCanister};
+use sha2::{Digest, Sha256};
 use slog::{info, Logger};
 
 /// Deploy pulled dependencies locally.
@@ -22,6 +24,10 @@ pub struct DepsDeployOpts {
     /// Specify the canister to deploy. You can specify its name (as defined in dfx.json) or Principal.
     /// If not specified, all pulled canisters will be deployed.
     canister: Option<String>,
+
+    /// Installs wasm modules even if their hash is not in the allowed_wasm_hashes of the canister in dfx.json.
+    #[arg(long)]
+    allow_unverified: bool,
 }
 
 pub async fn exec(env: &dyn Environment, opts: DepsDeployOpts) -> DfxResult {
@@ -32,7 +38,8 @@ pub async fn exec(env: &dyn Environment, opts: DepsDeployOpts) -> DfxResult {
         return Ok(());
     }
 
-    let project_root = env.get_config_or_anyhow()?.get_project_root().to_path_buf();
+    let config = env.get_config_or_anyhow()?;
+    let project_root = config.get_project_root().to_path_buf();
     let pulled_json = load_pulled_json(&project_root)?;
     validate_pulled(&pulled_json, &pull_canisters_in_config)
         .with_context(|| "Please rerun `dfx deps pull`.")?;
@@ -54,7 +61,22 @@ pub async fn exec(env: &dyn Environment, opts: DepsDeployOpts) -> DfxResult {
         // Safe to unwrap:
         // canister_ids are guaranteed to exist in pulled.json
         let pulled_canister = pulled_json.canisters.get(&canister_id).unwrap();
-        create_and_install(agent, logger, &canister_id, &init_json, pulled_canister).await?;
+        // Indirect dependencies are not in dfx.json, so they have no allowlist.
+        let allowed_wasm_hashes = pulled_canister
+            .name
+            .as_ref()
+            .and_then(|name| config.get_config().canisters.as_ref()?.get(name))
+            .and_then(|canister| canister.allowed_wasm_hashes.as_deref());
+        create_and_install(
+            agent,
+            logger,
+            &canister_id,
+            &init_json,
+            pulled_canister,
+            allowed_wasm_hashes,
+            opts.allow_unverified,
+        )
+        .await?;
     }
 
     Ok(())
@@ -67,10 +89,21 @@ async fn create_and_install(
     canister_id: &Principal,
     init_json: &InitJson,
     pulled_canister: &PulledCanister,
+    allowed_wasm_hashes: Option<&[String]>,
+    allow_unverified: bool,
 ) -> DfxResult {
     let arg_raw = init_json.get_arg_raw(canister_id)?;
     try_create_canister(agent, logger, canister_id, pulled_canister).await?;
-    install_pulled_canister(agent, logger, canister_id, arg_raw, pulled_canister).await?;
+    install_pulled_canister(
+        agent,
+        logger,
+        canister_id,
+        arg_raw,
+        pulled_canister,
+        allowed_wasm_hashes,
+        allow_unverified,
+    )
+    .await?;
     Ok(())
 }
 
@@ -81,11 +114,20 @@ async fn install_pulled_canister(
     canister_id: &Principal,
     install_args: Vec<u8>,
     pulled_canister: &PulledCanister,
+    allowed_wasm_hashes: Option<&[String]>,
+    allow_unverified: bool,
 ) -> DfxResult {
     let canister_prompt = get_canister_prompt(canister_id, pulled_canister);
     info!(logger, "Installing canister: {canister_prompt}");
     let pulled_canister_path = get_pulled_wasm_path(canister_id, pulled_canister.gzip)?;
     let wasm = dfx_core::fs::read(&pulled_canister_path)?;
+    verify_wasm_hash(
+        logger,
+        &canister_prompt,
+        allowed_wasm_hashes,
+        &Sha256::digest(&wasm),
+        allow_unverified,
+    )?;
     let mgr = ManagementCanister::create(agent);
     mgr.install_code(canister_id, &wasm)
         // always reinstall pulled canister
@@ -0,0 +1,238 @@
+use crate::lib::deps::{get_pulled_json_path, load_pulled_json};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::Context;
+use candid::Principal;
+use clap::{Parser, ValueEnum};
+use dfx_core::config::model::dfinity::CanisterTypeProperties;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+#[derive(ValueEnum, Clone, Copy)]
+pub enum GraphFormat {
+    Dot,
+    Json,
+    Mermaid,
+}
+
+/// Prints the dependency graph of the canisters in dfx.json.
+/// Indirect dependencies of pull canisters are included once `dfx deps pull` has run.
+/// Fails if the dependencies are circular.
+#[derive(Parser)]
+pub struct DepsGraphOpts {
+    /// The format of the graph.
+    #[arg(long, value_enum, default_value = "dot")]
+    format: GraphFormat,
+}
+
+#[derive(Serialize)]
+struct Node {
+    name: String,
+    #[serde(rename = "type")]
+    canister_type: &'static str,
+    /// The canister id, for pull canisters and canisters that are remote on the selected network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    remote: bool,
+}
+
+#[derive(Serialize)]
+struct Edge {
+    from: String,
+    to: String,
+}
+
+#[derive(Serialize)]
+struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+pub fn exec(env: &dyn Environment, opts: DepsGraphOpts) -> DfxResult {
+    let graph = build_graph(env)?;
+    let output = match opts.format {
+        GraphFormat::Dot => to_dot(&graph),
+        GraphFormat::Json => serde_json::to_string_pretty(&graph)? + "\n",
+        GraphFormat::Mermaid => to_mermaid(&graph),
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+fn build_graph(env: &dyn Environment) -> DfxResult<Graph> {
+    let config = env.get_config_or_anyhow()?;
+    let config_interface = config.get_config();
+    let network_name = &env.get_network_descriptor().name;
+    let canisters = config_interface.canisters.clone().unwrap_or_default();
+
+    let mut graph = Graph {
+        nodes: vec![],
+        edges: vec![],
+    };
+    for (name, canister) in &canisters {
+        // Reports circular dependencies the same way as building and deploying.
+        config_interface.get_canister_names_with_dependencies(Some(name))?;
+
+        let remote_id = config_interface.get_remote_canister_id(name, network_name)?;
+        let id = match &canister.type_specific {
+            CanisterTypeProperties::Pull { id } => Some(*id),
+            _ => remote_id,
+        };
+        graph.nodes.push(Node {
+            name: name.clone(),
+            canister_type: canister.type_specific.name(),
+            id: id.map(|id| id.to_text()),
+            remote: remote_id.is_some(),
+        });
+        for dependency in &canister.dependencies {
+            graph.edges.push(Edge {
+                from: name.clone(),
+                to: dependency.clone(),
+            });
+        }
+    }
+
+    // The dependencies of pull canisters are only known from their metadata, which `dfx deps pull` saved.
+    let project_root = config.get_project_root();
+    if get_pulled_json_path(project_root).exists() {
+        let pulled_json = load_pulled_json(project_root)?;
+        let names: BTreeMap<_, _> = config_interface
+            .get_pull_canisters()
+            .context("Failed to get pull canisters.")?
+            .into_iter()
+            .map(|(name, id)| (id, name))
+            .collect();
+        let node_name = |id: &Principal| names.get(id).cloned().unwrap_or_else(|| id.to_text());
+        for (id, pulled_canister) in &pulled_json.canisters {
+            if !names.contains_key(id) {
+                graph.nodes.push(Node {
+                    name: id.to_text(),
+                    canister_type: "pull",
+                    id: Some(id.to_text()),
+                    remote: false,
+                });
+            }
+            for dependency in &pulled_canister.dependencies {
+                graph.edges.push(Edge {
+                    from: node_name(id),
+                    to: node_name(dependency),
+                });
+            }
+        }
+    }
+    Ok(graph)
+}
+
+fn node_label(node: &Node) -> String {
+    let mut label = format!("{} ({}", node.name, node.canister_type);
+    if node.remote {
+        label.push_str(", remote");
+    }
+    label.push(')');
+    label
+}
+
+fn to_dot(graph: &Graph) -> String {
+    let mut out = String::from("digraph canisters {\n");
+    for node in &graph.nodes {
+        let style = if node.remote || node.canister_type == "pull" {
+            ", style=dashed"
+        } else {
+            ""
+        };
+        writeln!(
+            out,
+            "  {:?} [label={:?}{}];",
+            node.name,
+            node_label(node),
+            style
+        )
+        .unwrap();
+    }
+    for edge in &graph.edges {
+        writeln!(out, "  {:?} -> {:?};", edge.from, edge.to).unwrap();
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn to_mermaid(graph: &Graph) -> String {
+    // Canister names may contain characters that mermaid does not allow in node ids.
+    let ids: BTreeMap<&str, String> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.name.as_str(), format!("n{}", i)))
+        .collect();
+    let mut out = String::from("graph TD\n");
+    for node in &graph.nodes {
+        writeln!(
+            out,
+            "  {}[\"{}\"]",
+            ids[node.name.as_str()],
+            node_label(node).replace('"', "#quot;")
+        )
+        .unwrap();
+    }
+    for edge in &graph.edges {
+        // pulled.json may refer to canisters it does not list if it was edited by hand.
+        if let (Some(from), Some(to)) = (ids.get(edge.from.as_str()), ids.get(edge.to.as_str())) {
+            writeln!(out, "  {} --> {}", from, to).unwrap();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        Graph {
+            nodes: vec![
+                Node {
+                    name: "backend".to_string(),
+                    canister_type: "motoko",
+                    id: None,
+                    remote: false,
+                },
+                Node {
+                    name: "internet-identity".to_string(),
+                    canister_type: "pull",
+                    id: Some("rdmx6-jaaaa-aaaaa-aaadq-cai".to_string()),
+                    remote: false,
+                },
+            ],
+            edges: vec![Edge {
+                from: "backend".to_string(),
+                to: "internet-identity".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn formats_dot() {
+        assert_eq!(
+            to_dot(&graph()),
+            r#"digraph canisters {
+  "backend" [label="backend (motoko)"];
+  "internet-identity" [label="internet-identity (pull)", style=dashed];
+  "backend" -> "internet-identity";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn formats_mermaid() {
+        assert_eq!(
+            to_mermaid(&graph()),
+            r#"graph TD
+  n0["backend (motoko)"]
+  n1["internet-identity (pull)"]
+  n0 --> n1
+"#
+        );
+    }
+}
@@ -5,6 +5,7 @@ use clap::Parser;
 use tokio::runtime::Runtime;
 
 mod deploy;
+mod graph;
 mod init;
 mod pull;
 
@@ -26,18 +27,20 @@ enum SubCommand {
     Pull(pull::DepsPullOpts),
     Init(init::DepsInitOpts),
     Deploy(deploy::DepsDeployOpts),
+    Graph(graph::DepsGraphOpts),
 }
 
 /// Executes `dfx deps` and its subcommands.
 pub fn exec(env: &dyn Environment, opts: DepsOpts) -> DfxResult {
     // all deps subcommands should use anounymous identity
-    let agent_env = create_anonymous_agent_environment(env, opts.network.to_network_name())?;
+    let agent_env = create_anonymous_agent_environment(env, opts.network.to_network_name(env))?;
     let runtime = Runtime::new().expect("Unable to create a runtime");
     runtime.block_on(async {
         match opts.subcmd {
             SubCommand::Pull(v) => pull::exec(&agent_env, v).await,
             SubCommand::Init(v) => init::exec(&agent_env, v).await,
             SubCommand::Deploy(v) => deploy::exec(&agent_env, v).await,
+            SubCommand::Graph(v) => graph::exec(&agent_env, v),
         }
     })
 }
@@ -46,7 +46,7 @@ pub async fn exec(env: &dyn Environment, opts: DepsPullOpts) -> DfxResult {
 
     let network = opts
         .network
-        .to_network_name()
+        .selected_network_name()
         .unwrap_or_else(|| "ic".to_string());
     let env = create_anonymous_agent_environment(env, Some(network))?;
 
@@ -14,7 +14,7 @@ pub struct DiagnoseOpts {
 }
 
 pub fn exec(env: &dyn Environment, opts: DiagnoseOpts) -> DfxResult {
-    let env = create_agent_environment(env, opts.network.to_network_name())?;
+    let env = create_agent_environment(env, opts.network.to_network_name(env))?;
     let runtime = Runtime::new().expect("Unable to create a runtime");
     runtime.block_on(async { migrate(&env, env.get_network_descriptor(), false).await })?;
     Ok(())
@@ -0,0 +1,23 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::telemetry::{get_event_log_path, set_enabled};
+use clap::Parser;
+use slog::info;
+
+/// Stops recording dfx invocations.
+#[derive(Parser)]
+pub struct DisableOpts {
+    /// Also deletes the events recorded so far.
+    #[arg(long)]
+    clear: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: DisableOpts) -> DfxResult {
+    set_enabled(false)?;
+    let log_path = get_event_log_path()?;
+    if opts.clear && log_path.exists() {
+        dfx_core::fs::remove_file(&log_path)?;
+    }
+    info!(env.get_logger(), "Stopped recording dfx invocations.");
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::telemetry::{get_event_log_path, set_enabled};
+use clap::Parser;
+use slog::info;
+
+/// Starts recording the commands you run, their durations and error categories to a local file.
+#[derive(Parser)]
+pub struct EnableOpts {}
+
+pub fn exec(env: &dyn Environment, _opts: EnableOpts) -> DfxResult {
+    set_enabled(true)?;
+    info!(
+        env.get_logger(),
+        "Recording dfx invocations to {}. Nothing is sent anywhere.",
+        get_event_log_path()?.display()
+    );
+    Ok(())
+}
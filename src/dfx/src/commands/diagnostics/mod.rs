@@ -0,0 +1,31 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+
+mod disable;
+mod enable;
+mod report;
+
+/// Manages the local-only log of dfx invocations, and bundles diagnostics for support requests.
+/// The log is opt-in and is never sent anywhere by dfx.
+#[derive(Parser)]
+#[command(name = "diagnostics")]
+pub struct DiagnosticsOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Disable(disable::DisableOpts),
+    Enable(enable::EnableOpts),
+    Report(report::ReportOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: DiagnosticsOpts) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::Disable(v) => disable::exec(env, v),
+        SubCommand::Enable(v) => enable::exec(env, v),
+        SubCommand::Report(v) => report::exec(env, v),
+    }
+}
@@ -0,0 +1,91 @@
+use crate::config::dfx_version_str;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::telemetry::{get_event_log_path, is_enabled};
+use crate::util::append_file;
+use anyhow::Context;
+use clap::Parser;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use slog::{info, warn};
+use std::path::PathBuf;
+use time::OffsetDateTime;
+
+/// Bundles the recorded events and information about this environment into a tarball,
+/// to attach to a support request. The tarball is only written to disk.
+#[derive(Parser)]
+pub struct ReportOpts {
+    /// Path of the tarball to create.
+    #[arg(long, default_value = "dfx-diagnostics.tar.gz")]
+    output: PathBuf,
+
+    /// Include the dfx.json of the current project. It is left out by default,
+    /// because it may contain private information such as URLs or canister names.
+    #[arg(long)]
+    include_dfx_json: bool,
+}
+
+#[derive(Serialize)]
+struct Report {
+    created: OffsetDateTime,
+    dfx_version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    recording_enabled: bool,
+    in_project: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: ReportOpts) -> DfxResult {
+    let log = env.get_logger();
+    let config = env.get_config();
+    let report = Report {
+        created: OffsetDateTime::now_utc(),
+        dfx_version: dfx_version_str(),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        recording_enabled: is_enabled(),
+        in_project: config.is_some(),
+    };
+
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    append_file(
+        &mut builder,
+        "report.json",
+        &serde_json::to_vec_pretty(&report)?,
+    )?;
+
+    let event_log = get_event_log_path()?;
+    if event_log.exists() {
+        append_file(
+            &mut builder,
+            "events.jsonl",
+            &dfx_core::fs::read(&event_log)?,
+        )?;
+    } else {
+        warn!(
+            log,
+            "No events have been recorded. Run `dfx diagnostics enable` and reproduce the problem to include them."
+        );
+    }
+
+    if let Some(config) = config.filter(|_| opts.include_dfx_json) {
+        append_file(
+            &mut builder,
+            "dfx.json",
+            &dfx_core::fs::read(config.get_path())?,
+        )?;
+    }
+
+    let tarball = builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .context("Failed to compress the diagnostics report.")?;
+    dfx_core::fs::write(&opts.output, tarball)?;
+    info!(
+        log,
+        "Wrote the diagnostics report to {}. It has not been sent anywhere; review it before sharing.",
+        opts.output.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::lib::diagnosis::{check_environment, CheckStatus};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use anyhow::bail;
+use clap::Parser;
+
+/// Checks the toolchains, the identity, the network and the system clock for common setup
+/// problems, and suggests how to fix them.
+#[derive(Parser)]
+pub struct DoctorOpts {
+    #[command(flatten)]
+    network: NetworkOpt,
+}
+
+pub fn exec(env: &dyn Environment, opts: DoctorOpts) -> DfxResult {
+    let checks = check_environment(env, opts.network.to_network_name(env));
+    for check in &checks {
+        let status = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Error => "error",
+        };
+        println!("[{status:^7}] {}: {}", check.name, check.details);
+        if let Some(fix) = &check.fix {
+            println!("          Fix: {fix}");
+        }
+    }
+
+    let errors = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Error)
+        .count();
+    if errors > 0 {
+        bail!("dfx doctor found {errors} problem(s).");
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::lib::canister_info::assets::AssetsCanisterInfo;
+use crate::lib::canister_info::CanisterInfo;
+use crate::lib::domains::{
+    add_domain_to_assets, assets_config_includes_well_known, dns_records, load_domains_json,
+    register_domain, save_domains_json, DomainRegistration, DEFAULT_BOUNDARY_NODE_URL,
+};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use slog::{info, warn};
+use std::path::PathBuf;
+use url::Url;
+
+/// Adds a custom domain to an asset canister.
+/// Writes the domain to the canister's `.well-known/ic-domains` asset and shows the DNS records to create.
+/// Once the DNS records exist and the canister is deployed, run the command again with --register.
+#[derive(Parser)]
+pub struct DomainsAddOpts {
+    /// The custom domain, for example "example.com".
+    domain: String,
+
+    /// The asset canister to serve on the domain, as named in dfx.json.
+    #[arg(long)]
+    canister: String,
+
+    /// The directory to write `.well-known/ic-domains` to.
+    /// Defaults to the first source directory of the canister.
+    #[arg(long)]
+    assets_dir: Option<PathBuf>,
+
+    /// Submit the registration of the domain to the boundary nodes.
+    #[arg(long)]
+    register: bool,
+
+    /// The URL of the boundary node API.
+    #[arg(long, default_value = DEFAULT_BOUNDARY_NODE_URL)]
+    boundary_node: Url,
+}
+
+pub async fn exec(env: &dyn Environment, opts: DomainsAddOpts) -> DfxResult {
+    let log = env.get_logger();
+    let domain = opts.domain.trim_end_matches('.').to_lowercase();
+    if domain.contains("://") || domain.contains('/') || !domain.contains('.') {
+        bail!("'{}' is not a valid domain name. Specify only the host name, for example 'example.com'.", opts.domain);
+    }
+
+    let config = env.get_config_or_anyhow()?;
+    let canister_info = CanisterInfo::load(&config, &opts.canister, None)?;
+    if !canister_info.is_assets() {
+        bail!(
+            "Canister '{}' is not an asset canister. Only asset canisters can serve custom domains.",
+            opts.canister
+        );
+    }
+    let source_dir = match opts.assets_dir {
+        Some(assets_dir) => assets_dir,
+        None => canister_info
+            .as_info::<AssetsCanisterInfo>()?
+            .get_source_paths()
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Canister '{}' has no asset source directory.",
+                    opts.canister
+                )
+            })?,
+    };
+    let domains_path = add_domain_to_assets(&source_dir, &domain)?;
+    info!(log, "Added '{}' to {}.", domain, domains_path.display());
+    if !assets_config_includes_well_known(&source_dir) {
+        warn!(
+            log,
+            "The asset configuration in {} does not mention the .well-known directory, which is ignored by default. Add {{\"match\": \".well-known\", \"ignore\": false}} to it.",
+            source_dir.display()
+        );
+    }
+
+    let canister_id = env.get_canister_id_store()?.find(&opts.canister);
+    let Some(canister_id) = canister_id else {
+        info!(
+            log,
+            "Canister '{}' does not exist yet on network '{}'. Deploy it to see the DNS records to create.",
+            opts.canister,
+            env.get_network_descriptor().name
+        );
+        return Ok(());
+    };
+
+    if !opts.register {
+        println!("Create the following DNS records for '{domain}':");
+        for (name, record_type, value) in dns_records(&domain, &canister_id) {
+            println!("  {name:<40} {record_type:<6} {value}");
+        }
+        info!(
+            log,
+            "Once the DNS records exist and '{}' has been deployed with the updated assets, run `dfx domains add {} --canister {} --register`.",
+            opts.canister,
+            domain,
+            opts.canister
+        );
+        return Ok(());
+    }
+
+    let request_id = register_domain(&opts.boundary_node, &domain).await?;
+    if let Some(temp_dir) = env.get_project_temp_dir() {
+        let mut domains_json = load_domains_json(&temp_dir)?;
+        domains_json.registrations.insert(
+            domain.clone(),
+            DomainRegistration {
+                canister: canister_id,
+                request_id: request_id.clone(),
+            },
+        );
+        save_domains_json(&temp_dir, &domains_json)?;
+    }
+    info!(
+        log,
+        "Submitted the registration of '{}' (request id {}). Run `dfx domains status {}` to follow its progress.",
+        domain,
+        request_id,
+        domain
+    );
+    Ok(())
+}
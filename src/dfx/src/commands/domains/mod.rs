@@ -0,0 +1,43 @@
+use crate::lib::agent::create_anonymous_agent_environment;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use clap::Parser;
+use tokio::runtime::Runtime;
+
+mod add;
+mod status;
+
+/// Manages custom domains for asset canisters.
+/// Unless specified otherwise with --network, canister ids are looked up on the IC mainnet.
+#[derive(Parser)]
+#[command(name = "domains")]
+pub struct DomainsOpts {
+    #[command(flatten)]
+    network: NetworkOpt,
+
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Add(add::DomainsAddOpts),
+    Status(status::DomainsStatusOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: DomainsOpts) -> DfxResult {
+    // custom domains are served by the boundary nodes of the IC mainnet
+    let network = opts
+        .network
+        .selected_network_name()
+        .or_else(|| Some("ic".to_string()));
+    let agent_env = create_anonymous_agent_environment(env, network)?;
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(async {
+        match opts.subcmd {
+            SubCommand::Add(v) => add::exec(&agent_env, v).await,
+            SubCommand::Status(v) => status::exec(&agent_env, v).await,
+        }
+    })
+}
@@ -0,0 +1,75 @@
+use crate::lib::domains::{
+    get_registration_status, load_domains_json, RegistrationStatus, DEFAULT_BOUNDARY_NODE_URL,
+};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use std::time::Duration;
+use url::Url;
+
+/// Shows the registration state of custom domains.
+#[derive(Parser)]
+pub struct DomainsStatusOpts {
+    /// The domain to show. Defaults to all domains registered from this project.
+    domain: Option<String>,
+
+    /// The id of the registration request, for domains that were not registered from this project.
+    #[arg(long, requires("domain"))]
+    request_id: Option<String>,
+
+    /// Keep polling until all registrations are available or have failed.
+    #[arg(long)]
+    watch: bool,
+
+    /// The URL of the boundary node API.
+    #[arg(long, default_value = DEFAULT_BOUNDARY_NODE_URL)]
+    boundary_node: Url,
+}
+
+pub async fn exec(env: &dyn Environment, opts: DomainsStatusOpts) -> DfxResult {
+    let requests: Vec<(String, String)> = if let Some(request_id) = opts.request_id {
+        vec![(opts.domain.unwrap(), request_id)]
+    } else {
+        let temp_dir = env.get_project_temp_dir().ok_or_else(|| {
+            anyhow!("Not in a project. Use --request-id to specify the registration request.")
+        })?;
+        let domains_json = load_domains_json(&temp_dir)?;
+        match &opts.domain {
+            Some(domain) => {
+                let registration = domains_json.registrations.get(domain).ok_or_else(|| {
+                    anyhow!("Domain '{domain}' has not been registered from this project. Use --request-id to specify the registration request.")
+                })?;
+                vec![(domain.clone(), registration.request_id.clone())]
+            }
+            None => domains_json
+                .registrations
+                .into_iter()
+                .map(|(domain, registration)| (domain, registration.request_id))
+                .collect(),
+        }
+    };
+    if requests.is_empty() {
+        bail!("No domains have been registered from this project. Use `dfx domains add --register` to register one.");
+    }
+
+    loop {
+        let mut pending = false;
+        for (domain, request_id) in &requests {
+            let status: RegistrationStatus =
+                get_registration_status(&opts.boundary_node, request_id).await?;
+            println!(
+                "{}: {} (canister {})",
+                domain,
+                status.state_description(),
+                status.canister
+            );
+            pending |= !status.is_final();
+        }
+        if !opts.watch || !pending {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(10)).await;
+    }
+    Ok(())
+}
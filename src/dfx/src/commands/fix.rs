@@ -14,7 +14,7 @@ pub struct FixOpts {
 }
 
 pub fn exec(env: &dyn Environment, opts: FixOpts) -> DfxResult {
-    let env = create_agent_environment(env, opts.network.to_network_name())?;
+    let env = create_agent_environment(env, opts.network.to_network_name(env))?;
     let runtime = Runtime::new().expect("Failed to create runtime");
     runtime.block_on(async { migrate(&env, env.get_network_descriptor(), true).await })?;
     Ok(())
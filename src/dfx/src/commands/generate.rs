@@ -1,10 +1,14 @@
 use crate::config::cache::DiskBasedCache;
 use crate::lib::agent::create_anonymous_agent_environment;
 use crate::lib::builders::BuildConfig;
+use crate::lib::declarations::docs::DocFormat;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::models::canister::CanisterPool;
-use clap::Parser;
+use crate::lib::operations::canister::fetch_remote_candid_files;
+use clap::{Parser, Subcommand};
+use slog::info;
+use std::path::PathBuf;
 use tokio::runtime::Runtime;
 
 /// Generate type declarations for canisters from the code in your project
@@ -14,14 +18,44 @@ pub struct GenerateOpts {
     /// If you do not specify a canister name, generates types for all canisters.
     canister_name: Option<String>,
 
-    // Deprecated/hidden because it had/has no effect.
-    // Cannot use 'hide' on a flattened  object - inlined the flattened network specifier
-    #[arg(long, global = true, hide = true)]
+    /// The network whose canister ids are written to the environment files of the declarations.
+    /// Defaults to the local network.
+    #[arg(long, global = true)]
     network: Option<String>,
+
+    #[command(subcommand)]
+    subcmd: Option<GenerateSubcommand>,
+}
+
+#[derive(Subcommand)]
+enum GenerateSubcommand {
+    Docs(GenerateDocsOpts),
+}
+
+/// Generate documentation of the interfaces of canisters from their Candid files,
+/// including the comments above their methods and types.
+#[derive(Parser)]
+struct GenerateDocsOpts {
+    /// Specifies the name of the canister to generate documentation for.
+    /// If you do not specify a canister name, generates documentation for all canisters.
+    canister_name: Option<String>,
+
+    /// The format of the documentation.
+    #[arg(long, value_enum, default_value = "md")]
+    format: DocFormat,
+
+    /// The directory to write the documentation to, as one file per canister.
+    #[arg(long, default_value = "docs/canisters")]
+    output: PathBuf,
 }
 
 pub fn exec(env: &dyn Environment, opts: GenerateOpts) -> DfxResult {
-    let env = create_anonymous_agent_environment(env, None)?;
+    let docs = opts.subcmd.map(|GenerateSubcommand::Docs(docs)| docs);
+    let canister_name = match &docs {
+        Some(docs) => docs.canister_name.as_deref(),
+        None => opts.canister_name.as_deref(),
+    };
+    let env = create_anonymous_agent_environment(env, opts.network)?;
     let log = env.get_logger();
 
     // Read the config.
@@ -34,10 +68,12 @@ pub fn exec(env: &dyn Environment, opts: GenerateOpts) -> DfxResult {
     // Option can be None which means generate types for all canisters
     let canisters_to_load = config
         .get_config()
-        .get_canister_names_with_dependencies(opts.canister_name.as_deref())?;
+        .get_canister_names_with_dependencies(canister_name)?;
     let canisters_to_generate = canisters_to_load.clone().into_iter().collect();
 
     let canister_pool_load = CanisterPool::load(&env, false, &canisters_to_load)?;
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(fetch_remote_candid_files(&env, &canister_pool_load))?;
 
     // If generate for motoko canister, build first
     let mut build_before_generate = Vec::new();
@@ -73,12 +109,22 @@ pub fn exec(env: &dyn Environment, opts: GenerateOpts) -> DfxResult {
     {
         let canister_pool_build = CanisterPool::load(&env, true, &build_dependees)?;
         slog::info!(log, "Building canisters before generate for Motoko");
-        let runtime = Runtime::new().expect("Unable to create a runtime");
         runtime.block_on(canister_pool_build.build_or_fail(log, &build_config))?;
     }
 
     for canister in canister_pool_load.canisters_to_build(&generate_config) {
-        canister.generate(&canister_pool_load, &generate_config)?;
+        match &docs {
+            Some(docs) => {
+                let output_path = canister.generate_docs(
+                    &canister_pool_load,
+                    &generate_config,
+                    docs.format,
+                    &docs.output,
+                )?;
+                info!(log, "Generated {}", output_path.display());
+            }
+            None => canister.generate(&canister_pool_load, &generate_config)?,
+        }
     }
 
     Ok(())
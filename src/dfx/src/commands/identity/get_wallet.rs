@@ -12,7 +12,7 @@ use tokio::runtime::Runtime;
 pub struct GetWalletOpts {}
 
 pub fn exec(env: &dyn Environment, _opts: GetWalletOpts, network: NetworkOpt) -> DfxResult {
-    let agent_env = create_agent_environment(env, network.to_network_name())?;
+    let agent_env = create_agent_environment(env, network.to_network_name(env))?;
     let runtime = Runtime::new().expect("Unable to create a runtime");
 
     runtime.block_on(async { fetch_root_key_if_needed(&agent_env).await })?;
@@ -3,7 +3,9 @@ use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use anyhow::Context;
 use clap::Parser;
-use dfx_core::identity::identity_manager::{IdentityCreationParameters, IdentityStorageMode};
+use dfx_core::identity::identity_manager::{
+    IdentityCreationParameters, IdentityStorageMode, KeyType,
+};
 use slog::{info, warn};
 use std::fs;
 use std::path::PathBuf;
@@ -22,6 +24,10 @@ pub struct ImportOpts {
     #[arg(long, conflicts_with("pem_file"), required_unless_present("pem_file"))]
     seed_file: Option<PathBuf>,
 
+    /// The type of key to derive from the seed phrase. Defaults to secp256k1.
+    #[arg(long, requires("seed_file"), value_parser = ["secp256k1", "ed25519"])]
+    key_type: Option<String>,
+
     /// DEPRECATED: Please use --storage-mode=plaintext instead
     #[arg(long)]
     disable_encryption: bool,
@@ -59,7 +65,15 @@ pub fn exec(env: &dyn Environment, opts: ImportOpts) -> DfxResult {
     } else {
         let mnemonic =
             fs::read_to_string(opts.seed_file.unwrap()).context("Failed to read seed file")?;
-        IdentityCreationParameters::SeedPhrase { mnemonic, mode }
+        let key_type = match opts.key_type {
+            Some(key_type) => KeyType::from_str(&key_type)?,
+            None => KeyType::default(),
+        };
+        IdentityCreationParameters::SeedPhrase {
+            mnemonic,
+            mode,
+            key_type,
+        }
     };
 
     create_new_dfx_identity(env, log, name, params, opts.force)?;
@@ -0,0 +1,91 @@
+use crate::commands::identity::new::create_new_dfx_identity;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::{bail, Context};
+use clap::Parser;
+use dfx_core::identity::delegation::DelegationChain;
+use dfx_core::identity::identity_manager::{
+    ed25519_seed_to_pem, IdentityCreationParameters, IdentityStorageMode,
+};
+use dfx_core::json::load_json_file;
+use slog::info;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Creates an identity that makes calls as the principal that delegated to a session key,
+/// e.g. the principal that Internet Identity gives you for a frontend.
+#[derive(Parser)]
+pub struct ImportDelegationOpts {
+    /// The identity to create.
+    new_identity: String,
+
+    /// The JSON file with the delegation chain, as returned by DelegationChain.toJSON() of @dfinity/identity.
+    delegation_file: PathBuf,
+
+    /// The session key the delegation chain delegates to: a PEM file,
+    /// or a JSON file as returned by Ed25519KeyIdentity.toJSON() of @dfinity/identity.
+    #[arg(long)]
+    session_key: PathBuf,
+
+    /// How the session key is stored. By default, if keyring/keychain is available, keys are stored there.
+    /// Otherwise, a password-protected file is used as fallback.
+    /// Mode 'plaintext' is not safe, but convenient for use in CI.
+    #[arg(long, value_parser = ["keyring", "password-protected", "plaintext"])]
+    storage_mode: Option<String>,
+
+    /// If the identity already exists, remove and re-import it.
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: ImportDelegationOpts) -> DfxResult {
+    let log = env.get_logger();
+    let mode = match opts.storage_mode {
+        Some(mode_str) => IdentityStorageMode::from_str(&mode_str)?,
+        None => IdentityStorageMode::default(),
+    };
+    let chain: DelegationChain = load_json_file(&opts.delegation_file)?;
+    let principal = chain.principal()?;
+    let expiration = chain.expiration()?;
+    let session_key_pem = read_session_key(&opts.session_key)?;
+    let name = opts.new_identity.as_str();
+    let params = IdentityCreationParameters::Delegation {
+        session_key_pem,
+        chain,
+        mode,
+    };
+
+    create_new_dfx_identity(env, log, name, params, opts.force)?;
+
+    info!(
+        log,
+        r#"Imported identity: "{}" with principal {}. The delegation expires at {}."#,
+        name,
+        principal,
+        humantime::format_rfc3339_seconds(expiration)
+    );
+    Ok(())
+}
+
+/// Reads a session key, converting the JSON of an Ed25519KeyIdentity to PEM:
+/// `[<DER-encoded public key>, <secret key>]` in hex, where the secret key starts with the 32-byte seed.
+fn read_session_key(path: &Path) -> DfxResult<Vec<u8>> {
+    let content = dfx_core::fs::read(path)?;
+    if content.iter().find(|b| !b.is_ascii_whitespace()) != Some(&b'[') {
+        return Ok(content);
+    }
+    let (_, secret_key): (String, String) = serde_json::from_slice(&content)
+        .with_context(|| format!("Failed to parse session key {}.", path.display()))?;
+    let secret_key = hex::decode(secret_key)
+        .with_context(|| format!("Invalid secret key in {}.", path.display()))?;
+    if secret_key.len() != 32 && secret_key.len() != 64 {
+        bail!(
+            "Invalid secret key in {}: expected 32 or 64 bytes, got {}.",
+            path.display(),
+            secret_key.len()
+        );
+    }
+    let mut seed = [0; 32];
+    seed.copy_from_slice(&secret_key[..32]);
+    Ok(ed25519_seed_to_pem(&seed)?)
+}
@@ -0,0 +1,22 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+use slog::info;
+
+/// Requires the password of an identity that was unlocked with `dfx identity unlock` again.
+#[derive(Parser)]
+pub struct LockOpts {
+    /// The identity to lock. Defaults to the selected identity.
+    identity: Option<String>,
+}
+
+pub fn exec(env: &dyn Environment, opts: LockOpts) -> DfxResult {
+    let log = env.get_logger();
+    let identity_manager = env.new_identity_manager()?;
+    let identity = opts
+        .identity
+        .unwrap_or_else(|| identity_manager.get_selected_identity_name().clone());
+    identity_manager.lock(log, &identity)?;
+    info!(log, "Identity '{}' is locked.", identity);
+    Ok(())
+}
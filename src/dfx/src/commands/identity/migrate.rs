@@ -0,0 +1,92 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+use dfx_core::cli::ask_for_consent;
+use dfx_core::identity::identity_manager::IdentityStorageMode;
+use slog::info;
+use std::str::FromStr;
+
+/// Moves the keys of identities that are stored in plaintext to the preferred storage mode.
+/// Password-protected keys that were encrypted with outdated key derivation parameters are encrypted again.
+/// Hardware identities are not affected.
+#[derive(Parser)]
+pub struct MigrateOpts {
+    /// The storage mode to move keys to. By default, keys are moved to the keyring/keychain if it is available,
+    /// and to password-protected files otherwise.
+    #[arg(long, value_parser = ["keyring", "password-protected"])]
+    storage_mode: Option<String>,
+
+    /// Only report which identities would be migrated.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Skips the confirmation prompt.
+    #[arg(long, short)]
+    yes: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: MigrateOpts) -> DfxResult {
+    let log = env.get_logger();
+    let identity_manager = env.new_identity_manager()?;
+    let preferred_mode = match opts.storage_mode {
+        Some(mode) => IdentityStorageMode::from_str(&mode)?,
+        None => IdentityStorageMode::default(),
+    };
+
+    let migrations = identity_manager.find_identities_to_migrate(log, preferred_mode)?;
+    if migrations.is_empty() {
+        info!(
+            log,
+            "All identities are stored in the preferred storage mode."
+        );
+        return Ok(());
+    }
+
+    let report = migrations
+        .iter()
+        .map(|migration| {
+            if migration.from == migration.to {
+                format!(
+                    "  {}: {} (encrypt again with current key derivation parameters)",
+                    migration.name, migration.from
+                )
+            } else {
+                format!(
+                    "  {}: {} -> {}",
+                    migration.name, migration.from, migration.to
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if opts.dry_run {
+        println!("The following identities would be migrated:\n{}", report);
+        return Ok(());
+    }
+    if !opts.yes {
+        ask_for_consent(&format!(
+            "The keys of the following identities will be moved:\n{}",
+            report
+        ))?;
+    }
+
+    for migration in &migrations {
+        identity_manager.migrate_identity(log, migration)?;
+        if migration.from == migration.to {
+            info!(
+                log,
+                "Encrypted identity '{}' again with current key derivation parameters.",
+                migration.name
+            );
+        } else {
+            info!(
+                log,
+                "Migrated identity '{}' from {} to {} storage.",
+                migration.name,
+                migration.from,
+                migration.to
+            );
+        }
+    }
+    Ok(())
+}
@@ -7,13 +7,21 @@ mod deploy_wallet;
 mod export;
 mod get_wallet;
 mod import;
+mod import_delegation;
 mod list;
+mod lock;
+mod migrate;
 mod new;
 mod principal;
 mod remove;
 mod rename;
+mod rotate_key;
+mod set_allowed_networks;
 mod set_wallet;
+mod sign_message;
+mod unlock;
 mod r#use;
+mod verify_message;
 mod whoami;
 
 /// Manages identities used to communicate with the Internet Computer network.
@@ -34,13 +42,21 @@ enum SubCommand {
     Export(export::ExportOpts),
     GetWallet(get_wallet::GetWalletOpts),
     Import(import::ImportOpts),
+    ImportDelegation(import_delegation::ImportDelegationOpts),
     List(list::ListOpts),
+    Lock(lock::LockOpts),
+    Migrate(migrate::MigrateOpts),
     New(new::NewIdentityOpts),
     GetPrincipal(principal::GetPrincipalOpts),
     Remove(remove::RemoveOpts),
     Rename(rename::RenameOpts),
+    RotateKey(rotate_key::RotateKeyOpts),
+    SetAllowedNetworks(set_allowed_networks::SetAllowedNetworksOpts),
     SetWallet(set_wallet::SetWalletOpts),
+    SignMessage(sign_message::SignMessageOpts),
+    Unlock(unlock::UnlockOpts),
     Use(r#use::UseOpts),
+    VerifyMessage(verify_message::VerifyMessageOpts),
     Whoami(whoami::WhoAmIOpts),
 }
 
@@ -50,13 +66,21 @@ pub fn exec(env: &dyn Environment, opts: IdentityOpts) -> DfxResult {
         SubCommand::Export(v) => export::exec(env, v),
         SubCommand::GetWallet(v) => get_wallet::exec(env, v, opts.network),
         SubCommand::List(v) => list::exec(env, v),
+        SubCommand::Lock(v) => lock::exec(env, v),
+        SubCommand::Migrate(v) => migrate::exec(env, v),
         SubCommand::New(v) => new::exec(env, v),
         SubCommand::GetPrincipal(v) => principal::exec(env, v),
         SubCommand::Import(v) => import::exec(env, v),
+        SubCommand::ImportDelegation(v) => import_delegation::exec(env, v),
         SubCommand::Remove(v) => remove::exec(env, v),
         SubCommand::Rename(v) => rename::exec(env, v),
+        SubCommand::RotateKey(v) => rotate_key::exec(env, v, opts.network),
+        SubCommand::SetAllowedNetworks(v) => set_allowed_networks::exec(env, v),
         SubCommand::SetWallet(v) => set_wallet::exec(env, v, opts.network),
+        SubCommand::SignMessage(v) => sign_message::exec(env, v),
+        SubCommand::Unlock(v) => unlock::exec(env, v),
         SubCommand::Use(v) => r#use::exec(env, v),
-        SubCommand::Whoami(v) => whoami::exec(env, v),
+        SubCommand::VerifyMessage(v) => verify_message::exec(env, v),
+        SubCommand::Whoami(v) => whoami::exec(env, v, opts.network),
     }
 }
@@ -5,7 +5,7 @@ use anyhow::Context;
 use clap::Parser;
 use dfx_core::error::identity::create_new_identity::CreateNewIdentityError::SwitchBackToIdentityFailed;
 use dfx_core::identity::identity_manager::{
-    HardwareIdentityConfiguration, IdentityCreationParameters, IdentityStorageMode,
+    HardwareIdentityConfiguration, IdentityCreationParameters, IdentityStorageMode, KeyType,
 };
 use regex::Regex;
 use slog::{info, warn, Logger};
@@ -45,6 +45,10 @@ pub struct NewIdentityOpts {
         value_parser = ["keyring", "password-protected", "plaintext"])]
     storage_mode: Option<String>,
 
+    /// The type of key to generate. Defaults to secp256k1.
+    #[arg(long, conflicts_with("hsm_key_id"), value_parser = ["secp256k1", "ed25519"])]
+    key_type: Option<String>,
+
     /// If the identity already exists, remove and re-create it.
     #[arg(long)]
     force: bool,
@@ -83,7 +87,12 @@ pub fn exec(env: &dyn Environment, opts: NewIdentityOpts) -> DfxResult {
                 IdentityStorageMode::default()
             };
 
-            Pem { mode }
+            let key_type = match opts.key_type {
+                Some(key_type) => KeyType::from_str(&key_type)?,
+                None => KeyType::default(),
+            };
+
+            Pem { mode, key_type }
         }
     };
 
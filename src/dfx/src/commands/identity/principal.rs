@@ -1,21 +1,75 @@
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use crate::util::candid_principal_vec;
 use anyhow::anyhow;
+use candid::Principal;
 use clap::Parser;
+use dfx_core::identity::ANONYMOUS_IDENTITY_NAME;
 use ic_agent::identity::Identity;
+use slog::warn;
+use std::collections::BTreeMap;
 
 /// Shows the textual representation of the Principal associated with the current identity.
 #[derive(Parser)]
-pub struct GetPrincipalOpts {}
+pub struct GetPrincipalOpts {
+    /// Shows the principals of all identities, except the anonymous identity.
+    /// Identities that cannot be loaded, for example because their password is wrong, are skipped with a warning.
+    #[arg(long)]
+    all: bool,
 
-pub fn exec(env: &dyn Environment, _opts: GetPrincipalOpts) -> DfxResult {
-    let identity = env
-        .new_identity_manager()?
-        .instantiate_selected_identity(env.get_logger())?;
-    let principal_id = identity
-        .as_ref()
-        .sender()
-        .map_err(|err| anyhow!("{}", err))?;
-    println!("{}", principal_id.to_text());
+    /// Outputs a JSON object of the principal of each identity, by identity name.
+    #[arg(long, conflicts_with("candid"))]
+    json: bool,
+
+    /// Outputs the principals as a Candid vector, e.g. to use in init arguments.
+    #[arg(long)]
+    candid: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: GetPrincipalOpts) -> DfxResult {
+    let log = env.get_logger();
+    let mut mgr = env.new_identity_manager()?;
+    let mut principals = BTreeMap::new();
+    if opts.all {
+        for name in mgr.get_identity_names(log)? {
+            if name == ANONYMOUS_IDENTITY_NAME {
+                continue;
+            }
+            match mgr.instantiate_identity_from_name(&name, log) {
+                Ok(identity) => {
+                    let principal = identity.sender().map_err(|err| anyhow!("{}", err))?;
+                    principals.insert(name, principal);
+                }
+                Err(err) => warn!(log, "Skipping identity {name}: {err}"),
+            }
+        }
+    } else {
+        let name = mgr.get_selected_identity_name().clone();
+        let principal = mgr
+            .instantiate_selected_identity(log)?
+            .as_ref()
+            .sender()
+            .map_err(|err| anyhow!("{}", err))?;
+        principals.insert(name, principal);
+    }
+
+    if opts.json {
+        let principals: BTreeMap<_, _> = principals
+            .into_iter()
+            .map(|(name, principal)| (name, principal.to_text()))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&principals)?);
+    } else if opts.candid {
+        let principals: Vec<Principal> = principals.into_values().collect();
+        println!("{}", candid_principal_vec(&principals));
+    } else if opts.all {
+        for (name, principal) in principals {
+            println!("{name} {principal}");
+        }
+    } else {
+        for principal in principals.values() {
+            println!("{}", principal.to_text());
+        }
+    }
     Ok(())
 }
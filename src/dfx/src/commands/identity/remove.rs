@@ -12,6 +12,11 @@ pub struct RemoveOpts {
     /// Required if the identity has wallets configured so that users do not accidentally lose access to wallets.
     #[arg(long)]
     drop_wallets: bool,
+
+    /// Required if the identity has backups of its previous keys, made by `dfx identity rotate-key`,
+    /// so that users do not accidentally lose them.
+    #[arg(long)]
+    drop_key_backups: bool,
 }
 
 pub fn exec(env: &dyn Environment, opts: RemoveOpts) -> DfxResult {
@@ -19,8 +24,13 @@ pub fn exec(env: &dyn Environment, opts: RemoveOpts) -> DfxResult {
 
     let log = env.get_logger();
 
-    env.new_identity_manager()?
-        .remove(log, name, opts.drop_wallets, Some(log))?;
+    env.new_identity_manager()?.remove(
+        log,
+        name,
+        opts.drop_wallets,
+        opts.drop_key_backups,
+        Some(log),
+    )?;
 
     info!(log, r#"Removed identity "{}"."#, name);
     Ok(())
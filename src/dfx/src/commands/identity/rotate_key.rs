@@ -0,0 +1,172 @@
+use crate::lib::environment::{AgentEnvironment, Environment};
+use crate::lib::error::DfxResult;
+use crate::lib::identity::wallet::get_identity_wallets;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use anyhow::{anyhow, Context};
+use candid::Principal;
+use clap::Parser;
+use dfx_core::canister::build_wallet_canister;
+use dfx_core::identity::identity_manager::KeyType;
+use dfx_core::util::expiry_duration;
+use fn_error_context::context;
+use ic_agent::Agent;
+use ic_utils::call::{AsyncCall, SyncCall};
+use slog::{info, warn};
+use std::str::FromStr;
+use tokio::runtime::Runtime;
+
+/// Replaces the key of an identity with a newly generated one.
+/// The new principal is added as a controller of the identity's wallets, and the previous key is backed up in encrypted form.
+#[derive(Parser)]
+pub struct RotateKeyOpts {
+    /// The identity to rotate the key of. Defaults to the currently selected identity.
+    identity: Option<String>,
+
+    /// Rotate the key without updating the identity's wallets.
+    /// The new principal will not be able to control them.
+    #[arg(long)]
+    skip_wallets: bool,
+
+    /// Keep the previous principal as a controller and custodian of the identity's wallets.
+    #[arg(long, conflicts_with("skip_wallets"))]
+    keep_previous_principal: bool,
+
+    /// The type of the new key. Defaults to secp256k1.
+    #[arg(long, value_parser = ["secp256k1", "ed25519"])]
+    key_type: Option<String>,
+}
+
+pub fn exec(env: &dyn Environment, opts: RotateKeyOpts, network: NetworkOpt) -> DfxResult {
+    let log = env.get_logger();
+    let identity_manager = env.new_identity_manager()?;
+    let name = opts
+        .identity
+        .unwrap_or_else(|| identity_manager.get_selected_identity_name().clone());
+
+    let key_type = match opts.key_type {
+        Some(key_type) => KeyType::from_str(&key_type)?,
+        None => KeyType::default(),
+    };
+    let rotation = identity_manager.prepare_key_rotation(log, &name, key_type)?;
+    let wallets = if opts.skip_wallets {
+        vec![]
+    } else {
+        get_identity_wallets(env, &identity_manager, &name, network.to_network_name(env))?
+    };
+
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    let mut previous_principal = None;
+    for (network, wallet) in &wallets {
+        info!(
+            log,
+            "Adding {} as a controller of wallet {} on network '{}'.",
+            rotation.principal,
+            wallet,
+            network.name
+        );
+        let agent_env =
+            AgentEnvironment::new(env, network.clone(), expiry_duration(), Some(name.as_str()))?;
+        previous_principal = Some(
+            agent_env
+                .get_agent()
+                .get_principal()
+                .map_err(|e| anyhow!(e))?,
+        );
+        runtime
+            .block_on(async {
+                fetch_root_key_if_needed(&agent_env).await?;
+                update_wallet(
+                    agent_env.get_agent(),
+                    *wallet,
+                    "add_controller",
+                    rotation.principal,
+                )
+                .await
+            })
+            .with_context(|| {
+                format!(
+                    "Failed to add the new principal to wallet {} on network '{}'. The key of identity '{}' was not rotated. Use --skip-wallets to rotate it without updating wallets.",
+                    wallet, network.name, name
+                )
+            })?;
+    }
+
+    info!(
+        log,
+        "Backing up the current key of identity '{}'. Please choose a passphrase for the backup.",
+        name
+    );
+    let backup_dir = identity_manager.rotate_key(log, &name, &rotation)?;
+    eprintln!("Your seed phrase for the new key of identity '{name}': {}\nThis can be used to reconstruct your key in case of emergency, so write it down in a safe place.", rotation.mnemonic.phrase());
+    info!(
+        log,
+        "Rotated the key of identity '{}'. Its principal is now {}. The previous key was backed up to {}.",
+        name,
+        rotation.principal,
+        backup_dir.display()
+    );
+
+    if let (Some(previous_principal), false) = (previous_principal, opts.keep_previous_principal) {
+        for (network, wallet) in &wallets {
+            let agent_env = AgentEnvironment::new(
+                env,
+                network.clone(),
+                expiry_duration(),
+                Some(name.as_str()),
+            )?;
+            let result = runtime.block_on(async {
+                fetch_root_key_if_needed(&agent_env).await?;
+                remove_previous_principal(agent_env.get_agent(), *wallet, previous_principal).await
+            });
+            if let Err(err) = result {
+                warn!(
+                    log,
+                    "Failed to remove the previous principal {} from wallet {} on network '{}': {:#}",
+                    previous_principal,
+                    wallet,
+                    network.name,
+                    err
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn remove_previous_principal(
+    agent: &Agent,
+    wallet_id: Principal,
+    previous_principal: Principal,
+) -> DfxResult {
+    update_wallet(agent, wallet_id, "remove_controller", previous_principal).await?;
+    let wallet = build_wallet_canister(wallet_id, agent).await?;
+    let (custodians,): (Vec<Principal>,) = wallet
+        .query("get_custodians")
+        .build()
+        .call()
+        .await
+        .context("Failed to query the wallet's custodians.")?;
+    if custodians.contains(&previous_principal) {
+        update_wallet(agent, wallet_id, "deauthorize", previous_principal).await?;
+    }
+    Ok(())
+}
+
+#[context("Failed to call '{}' on wallet {}.", method, wallet_id)]
+async fn update_wallet(
+    agent: &Agent,
+    wallet_id: Principal,
+    method: &str,
+    principal: Principal,
+) -> DfxResult {
+    let wallet = build_wallet_canister(wallet_id, agent).await?;
+    wallet
+        .update(method)
+        .with_arg(principal)
+        .build::<()>()
+        .call_and_wait()
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+use slog::info;
+
+/// Restricts the networks an identity can be used on.
+#[derive(Parser)]
+pub struct SetAllowedNetworksOpts {
+    /// The identity to restrict.
+    identity: String,
+
+    /// The networks the identity can be used on.
+    #[arg(required_unless_present("any"))]
+    networks: Vec<String>,
+
+    /// Lift the restriction, so that the identity can be used on any network.
+    #[arg(long, conflicts_with("networks"))]
+    any: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: SetAllowedNetworksOpts) -> DfxResult {
+    let log = env.get_logger();
+    let networks = (!opts.any).then_some(opts.networks);
+    env.new_identity_manager()?
+        .set_allowed_networks(log, &opts.identity, networks.clone())?;
+    match networks {
+        Some(networks) => info!(
+            log,
+            "Identity '{}' can only be used on: {}.",
+            opts.identity,
+            networks.join(", ")
+        ),
+        None => info!(
+            log,
+            "Identity '{}' can be used on any network.", opts.identity
+        ),
+    }
+    Ok(())
+}
@@ -24,7 +24,7 @@ pub struct SetWalletOpts {
 }
 
 pub fn exec(env: &dyn Environment, opts: SetWalletOpts, network: NetworkOpt) -> DfxResult {
-    let agent_env = create_agent_environment(env, network.to_network_name())?;
+    let agent_env = create_agent_environment(env, network.to_network_name(env))?;
     let env = &agent_env;
     let log = env.get_logger();
 
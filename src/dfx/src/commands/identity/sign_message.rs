@@ -0,0 +1,38 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+use dfx_core::identity::signed_message::SignedMessage;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Signs a message with the key of the selected identity, to prove off-chain that you own its principal.
+/// Prints the signature as JSON, with the principal, public key, and timestamp needed to verify it.
+#[derive(Parser)]
+pub struct SignMessageOpts {
+    /// The message to sign.
+    #[arg(conflicts_with("file"), required_unless_present("file"))]
+    message: Option<String>,
+
+    /// Signs the contents of this file instead.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+pub fn exec(env: &dyn Environment, opts: SignMessageOpts) -> DfxResult {
+    let log = env.get_logger();
+    let identity = env
+        .new_identity_manager()?
+        .instantiate_selected_identity(log)?;
+    let message = match (opts.message, opts.file) {
+        (Some(message), _) => message.into_bytes(),
+        (None, Some(file)) => dfx_core::fs::read(&file)?,
+        (None, None) => unreachable!(),
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let signed = SignedMessage::sign(identity.as_ref(), &message, timestamp)?;
+    println!("{}", serde_json::to_string_pretty(&signed)?);
+    Ok(())
+}
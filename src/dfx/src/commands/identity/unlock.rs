@@ -0,0 +1,34 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::util::clap::parsers::duration_parser;
+use clap::Parser;
+use slog::info;
+use std::time::{Duration, SystemTime};
+
+/// Asks for the password of a password-protected identity once, and uses the identity without asking again
+/// until the unlock expires. The key derived from the password is kept in the keyring of the system meanwhile.
+#[derive(Parser)]
+pub struct UnlockOpts {
+    /// The identity to unlock. Defaults to the selected identity.
+    identity: Option<String>,
+
+    /// How long the identity stays unlocked, for example `30m` or `8h`.
+    #[arg(long, default_value = "1h", value_parser = duration_parser)]
+    duration: Duration,
+}
+
+pub fn exec(env: &dyn Environment, opts: UnlockOpts) -> DfxResult {
+    let log = env.get_logger();
+    let identity_manager = env.new_identity_manager()?;
+    let identity = opts
+        .identity
+        .unwrap_or_else(|| identity_manager.get_selected_identity_name().clone());
+    identity_manager.unlock(log, &identity, SystemTime::now() + opts.duration)?;
+    info!(
+        log,
+        "Identity '{}' is unlocked for {}.",
+        identity,
+        humantime::format_duration(opts.duration)
+    );
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::util::clap::parsers::duration_parser;
+use anyhow::bail;
+use candid::Principal;
+use clap::Parser;
+use dfx_core::identity::signed_message::SignedMessage;
+use dfx_core::json::load_json_file;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Verifies a signature made with `dfx identity sign-message`, and shows the principal that signed the message.
+#[derive(Parser)]
+pub struct VerifyMessageOpts {
+    /// The JSON file with the signature.
+    signature_file: PathBuf,
+
+    /// The file with the message, if it is not in the signature file.
+    #[arg(long)]
+    file: Option<PathBuf>,
+
+    /// Fails unless this principal signed the message.
+    #[arg(long)]
+    principal: Option<Principal>,
+
+    /// Fails if the message was signed longer ago than this, e.g. 10m or 1h.
+    #[arg(long, value_parser = duration_parser)]
+    max_age: Option<Duration>,
+}
+
+pub fn exec(_env: &dyn Environment, opts: VerifyMessageOpts) -> DfxResult {
+    let signed: SignedMessage = load_json_file(&opts.signature_file)?;
+    let message = match &opts.file {
+        Some(file) => Some(dfx_core::fs::read(file)?),
+        None if signed.message.is_none() => {
+            bail!("The signature file does not contain the message. Pass the file with the message with --file.")
+        }
+        None => None,
+    };
+    signed.verify(message.as_deref())?;
+
+    if let Some(principal) = opts.principal {
+        if principal != signed.principal {
+            bail!(
+                "The message was signed by {}, not by {}.",
+                signed.principal,
+                principal
+            );
+        }
+    }
+    let signed_at = UNIX_EPOCH + Duration::from_secs(signed.timestamp);
+    if let Some(max_age) = opts.max_age {
+        let age = SystemTime::now()
+            .duration_since(signed_at)
+            .unwrap_or_default();
+        if age > max_age {
+            bail!(
+                "The message was signed {} ago, which is longer than {} ago.",
+                humantime::format_duration(Duration::from_secs(age.as_secs())),
+                humantime::format_duration(max_age)
+            );
+        }
+    }
+    println!(
+        "Valid signature by {}, signed at {}.",
+        signed.principal,
+        humantime::format_rfc3339_seconds(signed_at)
+    );
+    Ok(())
+}
@@ -1,14 +1,84 @@
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use crate::lib::identity::wallet::get_identity_wallets;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
+use anyhow::anyhow;
 use clap::Parser;
+use ic_agent::identity::Identity;
+use icrc_ledger_types::icrc1::account::Account;
+use serde::Serialize;
+use std::collections::BTreeMap;
 
 /// Shows the name of the current identity.
+/// With --verbose, also shows the principal, the ICP ledger account identifier, the cycles ledger account,
+/// and the wallets of the identity.
 #[derive(Parser)]
-pub struct WhoAmIOpts {}
+pub struct WhoAmIOpts {
+    /// Subaccount to use for the ledger account identifier and the cycles ledger account (64 character long hex string).
+    /// Implies --verbose.
+    #[arg(long)]
+    subaccount: Option<Subaccount>,
 
-pub fn exec(env: &dyn Environment, _opts: WhoAmIOpts) -> DfxResult {
-    let mgr = env.new_identity_manager()?;
-    let identity = mgr.get_selected_identity_name();
-    println!("{}", identity);
+    /// Output the verbose information as JSON. Implies --verbose.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct IdentityInfo {
+    identity: String,
+    principal: String,
+    account_id: String,
+    cycles_ledger_account: String,
+    wallets: BTreeMap<String, String>,
+}
+
+pub fn exec(env: &dyn Environment, opts: WhoAmIOpts, network: NetworkOpt) -> DfxResult {
+    let mut mgr = env.new_identity_manager()?;
+    let identity = mgr.get_selected_identity_name().clone();
+    if env.get_verbose_level() <= 0 && !opts.json && opts.subaccount.is_none() {
+        println!("{}", identity);
+        return Ok(());
+    }
+
+    let principal = mgr
+        .instantiate_selected_identity(env.get_logger())?
+        .sender()
+        .map_err(|err| anyhow!("{}", err))?;
+    let account_id = AccountIdentifier::new(principal, opts.subaccount).to_string();
+    let cycles_ledger_account = Account {
+        owner: principal,
+        subaccount: opts.subaccount.map(|subaccount| subaccount.0),
+    }
+    .to_string();
+    let wallets = get_identity_wallets(env, &mgr, &identity, network.to_network_name(env))?
+        .into_iter()
+        .map(|(network, wallet)| (network.name, wallet.to_text()))
+        .collect();
+    let info = IdentityInfo {
+        identity,
+        principal: principal.to_text(),
+        account_id,
+        cycles_ledger_account,
+        wallets,
+    };
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else {
+        println!("Identity: {}", info.identity);
+        println!("Principal: {}", info.principal);
+        println!("Ledger account id: {}", info.account_id);
+        println!("Cycles ledger account: {}", info.cycles_ledger_account);
+        if info.wallets.is_empty() {
+            println!("Wallets: none");
+        } else {
+            println!("Wallets:");
+            for (network, wallet) in &info.wallets {
+                println!("  {}: {}", network, wallet);
+            }
+        }
+    }
     Ok(())
 }
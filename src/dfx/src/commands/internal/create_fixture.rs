@@ -0,0 +1,90 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+use dfx_core::fixture::{FixtureCanister, FixtureCanisterType, ProjectFixtureBuilder};
+use serde_json::json;
+use slog::info;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Creates a throwaway project for integration tests, with deterministic identities and a preconfigured local network.
+/// Prints the project directory, the value to use for DFX_CONFIG_ROOT, and the principals of the identities as JSON.
+#[derive(Parser)]
+pub struct CreateFixtureOpts {
+    /// The name of the project directory.
+    #[arg(long, default_value = "fixture")]
+    name: String,
+
+    /// A canister to add, as <name>:<type>[:<dependency>,...].
+    /// Supported types are motoko, assets, and custom. Custom canisters build without any toolchain.
+    #[arg(long = "canister", value_parser = fixture_canister_parser)]
+    canisters: Vec<FixtureCanister>,
+
+    /// An identity to create, with a key derived from its name.
+    /// The first identity becomes the default. An identity named 'default' is always created.
+    #[arg(long = "identity")]
+    identities: Vec<String>,
+
+    /// The port the local network binds to. By default, a free port is chosen when the network starts.
+    #[arg(long)]
+    port: Option<u16>,
+
+    /// Create the fixture in this directory instead of a new temporary directory.
+    #[arg(long)]
+    directory: Option<PathBuf>,
+}
+
+fn fixture_canister_parser(input: &str) -> Result<FixtureCanister, String> {
+    let mut parts = input.splitn(3, ':');
+    let name = parts.next().unwrap_or_default();
+    let canister_type = parts
+        .next()
+        .ok_or_else(|| "Expected <name>:<type>[:<dependency>,...].".to_string())?;
+    if name.is_empty() {
+        return Err("The canister name must not be empty.".to_string());
+    }
+    let canister_type = FixtureCanisterType::from_str(canister_type).map_err(|e| e.to_string())?;
+    let mut canister = FixtureCanister::new(name, canister_type);
+    if let Some(dependencies) = parts.next() {
+        for dependency in dependencies.split(',').filter(|d| !d.is_empty()) {
+            canister = canister.with_dependency(dependency);
+        }
+    }
+    Ok(canister)
+}
+
+pub fn exec(env: &dyn Environment, opts: CreateFixtureOpts) -> DfxResult {
+    let mut builder = ProjectFixtureBuilder::new(&opts.name);
+    for canister in opts.canisters {
+        builder = builder.canister(canister);
+    }
+    for identity in &opts.identities {
+        builder = builder.identity(identity);
+    }
+    if let Some(port) = opts.port {
+        builder = builder.local_network_port(port);
+    }
+    if let Some(directory) = &opts.directory {
+        builder = builder.location(directory);
+    }
+    let fixture = builder.create()?.keep();
+
+    info!(
+        env.get_logger(),
+        "Created fixture project in {}.",
+        fixture.project_dir().display()
+    );
+    let identities: BTreeMap<_, _> = fixture
+        .identities()
+        .iter()
+        .map(|(name, principal)| (name.clone(), principal.to_text()))
+        .collect();
+    let output = json!({
+        "project_dir": fixture.project_dir(),
+        "config_root": fixture.config_root(),
+        "identities": identities,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
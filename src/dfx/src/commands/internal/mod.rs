@@ -0,0 +1,24 @@
+use crate::lib::error::DfxResult;
+use crate::Environment;
+use clap::Parser;
+
+mod create_fixture;
+
+/// Commands for testing dfx and the tooling built on top of it.
+#[derive(Parser)]
+#[command(name = "internal")]
+pub struct InternalOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    CreateFixture(create_fixture::CreateFixtureOpts),
+}
+
+pub fn exec(env: &dyn Environment, cmd: InternalOpts) -> DfxResult {
+    match cmd.subcmd {
+        SubCommand::CreateFixture(v) => create_fixture::exec(env, v),
+    }
+}
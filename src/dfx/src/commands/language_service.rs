@@ -41,13 +41,15 @@ pub fn exec(env: &dyn Environment, opts: LanguageServiceOpts) -> DfxResult {
         Err(anyhow!("The `_language-service` command is meant to be run by editors to start a language service. You probably don't want to run it from a terminal.\nIf you _really_ want to, you can pass the --force-tty flag."))
     } else if let Some(config) = env.get_config() {
         let main_path = get_main_path(config.get_config(), opts.canister)?;
-        let packtool = &config
-            .get_config()
-            .get_defaults()
-            .get_build()
-            .get_packtool();
+        let build_defaults = config.get_config().get_defaults().get_build();
 
-        let mut package_arguments = package_arguments::load(env.get_cache().as_ref(), packtool)?;
+        let mut package_arguments = package_arguments::load(
+            env.get_logger(),
+            env.get_cache().as_ref(),
+            &build_defaults.get_packtool(),
+            build_defaults.get_packages(),
+            config.get_project_root(),
+        )?;
 
         // Include actor alias flags
         let canister_names = config
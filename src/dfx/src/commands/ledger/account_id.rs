@@ -31,7 +31,7 @@ pub async fn exec(env: &dyn Environment, opts: AccountIdOpts) -> DfxResult {
         principal
     } else if let Some(alias) = opts.of_canister {
         let canister_id_store = env.get_canister_id_store()?;
-        Principal::from_text(&alias).or_else(|_| canister_id_store.get(&alias))?
+        Principal::from_text(&alias).or_else(|_| canister_id_store.get_including_aliases(&alias))?
     } else {
         env.get_selected_identity_principal()
             .context("No identity is selected")?
@@ -3,21 +3,28 @@ use crate::lib::error::DfxResult;
 use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
 use crate::lib::operations::ledger;
 use crate::lib::root_key::fetch_root_key_if_needed;
-use anyhow::anyhow;
+use anyhow::{bail, Context};
 use candid::Principal;
 use clap::Parser;
+use slog::info;
 use std::str::FromStr;
 
 /// Prints the account balance of the user
 #[derive(Parser)]
 pub struct BalanceOpts {
-    /// Specifies an AccountIdentifier to get the balance of
+    /// Specifies an AccountIdentifier to get the balance of, or the principal or canister name
+    /// of the owner of the account. Defaults to the selected identity.
     of: Option<String>,
 
-    /// Subaccount of the selected identity to get the balance of
-    #[arg(long, conflicts_with("of"))]
+    /// Subaccount of the owner to get the balance of (64 character long hex string).
+    #[arg(long)]
     subaccount: Option<Subaccount>,
 
+    /// Prints the subaccounts with a balance among the first N subaccounts of the owner,
+    /// where subaccount i holds i in its last 8 bytes, like `SubAccount.fromID` of `@dfinity/ledger-icp`.
+    #[arg(long, value_name = "N", conflicts_with("subaccount"))]
+    scan_subaccounts: Option<u64>,
+
     /// Canister ID of the ledger canister.
     #[arg(long)]
     ledger_canister_id: Option<Principal>,
@@ -25,19 +32,51 @@ pub struct BalanceOpts {
 
 pub async fn exec(env: &dyn Environment, opts: BalanceOpts) -> DfxResult {
     fetch_root_key_if_needed(env).await?;
-    let sender = env
-        .get_selected_identity_principal()
-        .expect("Selected identity not instantiated.");
-    let subacct = opts.subaccount;
-    let acc_id = opts
-        .of
-        .map_or_else(
-            || Ok(AccountIdentifier::new(sender, subacct)),
-            |v| AccountIdentifier::from_str(&v),
-        )
-        .map_err(|err| anyhow!(err))?;
     let agent = env.get_agent();
 
+    let owner = match opts.of.as_deref() {
+        None => env
+            .get_selected_identity_principal()
+            .expect("Selected identity not instantiated."),
+        Some(of) => {
+            if let Ok(acc_id) = AccountIdentifier::from_str(of) {
+                if opts.subaccount.is_some() || opts.scan_subaccounts.is_some() {
+                    bail!("The subaccounts of an AccountIdentifier cannot be derived. Specify the principal of its owner instead.");
+                }
+                let balance = ledger::balance(agent, &acc_id, opts.ledger_canister_id).await?;
+                println!("{balance}");
+                return Ok(());
+            }
+            match Principal::from_text(of) {
+                Ok(principal) => principal,
+                Err(_) => env.get_canister_id_store()?.get(of).with_context(|| {
+                    format!("'{of}' is not an AccountIdentifier, a principal, or a canister name.")
+                })?,
+            }
+        }
+    };
+
+    if let Some(count) = opts.scan_subaccounts {
+        let mut found = false;
+        for index in 0..count {
+            let subaccount = Subaccount::from(index);
+            let acc_id = AccountIdentifier::new(owner, Some(subaccount));
+            let balance = ledger::balance(agent, &acc_id, opts.ledger_canister_id).await?;
+            if balance.get_e8s() > 0 {
+                println!("{} {balance}", hex::encode(subaccount.0));
+                found = true;
+            }
+        }
+        if !found {
+            info!(
+                env.get_logger(),
+                "None of the first {count} subaccounts of {owner} has a balance."
+            );
+        }
+        return Ok(());
+    }
+
+    let acc_id = AccountIdentifier::new(owner, opts.subaccount);
     let balance = ledger::balance(agent, &acc_id, opts.ledger_canister_id).await?;
 
     println!("{balance}");
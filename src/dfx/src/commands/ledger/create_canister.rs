@@ -7,9 +7,11 @@ use crate::lib::ledger_types::NotifyError::Refunded;
 use crate::lib::nns_types::account_identifier::Subaccount;
 use crate::lib::nns_types::icpts::{ICPTs, TRANSACTION_FEE};
 use crate::lib::operations::cmc::{notify_create, transfer_cmc};
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::e8s_parser;
 use crate::util::clap::subnet_selection_opt::SubnetSelectionOpt;
+use crate::util::currency_conversion::print_cycles_estimate;
 use anyhow::{bail, Context};
 use candid::Principal;
 use clap::Parser;
@@ -73,8 +75,15 @@ pub async fn exec(env: &dyn Environment, opts: CreateCanisterOpts) -> DfxResult
 
     fetch_root_key_if_needed(env).await?;
 
+    // Resolve the subnet selection before sending any ICP, so that an invalid
+    // selection does not leave the transfer waiting for a notification.
+    let subnet_selection = opts.subnet_selection.into_subnet_selection(env).await?;
+
+    print_cycles_estimate(agent, env.get_logger(), &amount).await;
+
     let height = transfer_cmc(
         agent,
+        retry_policy(env),
         env.get_logger(),
         memo,
         amount,
@@ -86,7 +95,6 @@ pub async fn exec(env: &dyn Environment, opts: CreateCanisterOpts) -> DfxResult
     .await?;
     println!("Using transfer at block height {height}");
 
-    let subnet_selection = opts.subnet_selection.into_subnet_selection(env).await?;
     let result = notify_create(agent, controller, height, subnet_selection).await;
 
     match result {
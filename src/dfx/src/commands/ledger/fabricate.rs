@@ -0,0 +1,84 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::ledger_types::{Memo, MAINNET_LEDGER_CANISTER_ID};
+use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
+use crate::lib::nns_types::icpts::ICPTs;
+use crate::lib::operations::ledger;
+use crate::lib::retryable::retry_policy;
+use crate::lib::root_key::fetch_root_key_or_anyhow;
+use anyhow::{anyhow, bail, Context};
+use candid::Principal;
+use clap::Parser;
+use std::str::FromStr;
+
+/// Local development only: Mint ICP on the local ledger into the specified account.
+/// The ICP is transferred from the minting account of the ledger, so the selected identity must own it,
+/// e.g. because it deployed the ledger with the `icp-ledger` init preset.
+#[derive(Parser)]
+pub struct FabricateOpts {
+    /// ICP to mint, as a Decimal with the fractional portion up to 8 decimal places, i.e. 100.012
+    amount: ICPTs,
+
+    /// AccountIdentifier, or principal, of the account to mint the ICP into.
+    #[arg(long)]
+    to: String,
+
+    /// Subaccount of the principal to mint the ICP into.
+    #[arg(long)]
+    to_subaccount: Option<Subaccount>,
+
+    /// Canister ID of the ledger canister.
+    #[arg(long)]
+    ledger_canister_id: Option<Principal>,
+}
+
+pub async fn exec(env: &dyn Environment, opts: FabricateOpts) -> DfxResult {
+    fetch_root_key_or_anyhow(env).await?;
+    let agent = env.get_agent();
+
+    let to = match Principal::from_text(&opts.to) {
+        Ok(principal) => AccountIdentifier::new(principal, opts.to_subaccount),
+        Err(_) => {
+            if opts.to_subaccount.is_some() {
+                bail!("--to-subaccount requires a principal in --to, not an AccountIdentifier.");
+            }
+            AccountIdentifier::from_str(&opts.to)
+                .map_err(|e| anyhow!(e))
+                .with_context(|| format!("Failed to parse destination '{}'.", opts.to))?
+        }
+    };
+
+    let minter = env
+        .get_selected_identity_principal()
+        .context("No identity is selected.")?;
+    let Some(minting_account) = ledger::minting_account(agent, opts.ledger_canister_id).await?
+    else {
+        bail!("The ledger has no minting account.");
+    };
+    if minting_account.owner != minter {
+        bail!(
+            "The minting account of the ledger belongs to {}, not to the selected identity ({}). Run the command with `--identity` set to the identity that owns it.",
+            minting_account.owner,
+            minter
+        );
+    }
+
+    // Transfers from the minting account are mints, which have no fee.
+    ledger::transfer(
+        agent,
+        retry_policy(env),
+        env.get_logger(),
+        &opts
+            .ledger_canister_id
+            .unwrap_or(MAINNET_LEDGER_CANISTER_ID),
+        Memo(0),
+        opts.amount,
+        ICPTs::ZERO,
+        minting_account.subaccount.map(Subaccount),
+        to.to_address(),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -89,8 +89,8 @@ async fn deposit_minted_cycles(
 ) -> DfxResult {
     let log = env.get_logger();
     let canister_id_store = env.get_canister_id_store()?;
-    let canister_id =
-        Principal::from_text(canister).or_else(|_| canister_id_store.get(canister))?;
+    let canister_id = Principal::from_text(canister)
+        .or_else(|_| canister_id_store.get_including_aliases(canister))?;
 
     info!(log, "Fabricating {} cycles onto {}", cycles, canister,);
 
@@ -11,10 +11,11 @@ use tokio::runtime::Runtime;
 mod account_id;
 mod balance;
 pub mod create_canister;
+mod fabricate;
 mod fabricate_cycles;
 mod notify;
 pub mod show_subnet_types;
-mod top_up;
+pub mod top_up;
 mod transfer;
 
 /// Ledger commands.
@@ -33,6 +34,7 @@ enum SubCommand {
     AccountId(account_id::AccountIdOpts),
     Balance(balance::BalanceOpts),
     CreateCanister(create_canister::CreateCanisterOpts),
+    Fabricate(fabricate::FabricateOpts),
     FabricateCycles(fabricate_cycles::FabricateCyclesOpts),
     Notify(notify::NotifyOpts),
     ShowSubnetTypes(show_subnet_types::ShowSubnetTypesOpts),
@@ -41,13 +43,14 @@ enum SubCommand {
 }
 
 pub fn exec(env: &dyn Environment, opts: LedgerOpts) -> DfxResult {
-    let agent_env = create_agent_environment(env, opts.network.to_network_name())?;
+    let agent_env = create_agent_environment(env, opts.network.to_network_name(env))?;
     let runtime = Runtime::new().expect("Unable to create a runtime");
     runtime.block_on(async {
         match opts.subcmd {
             SubCommand::AccountId(v) => account_id::exec(&agent_env, v).await,
             SubCommand::Balance(v) => balance::exec(&agent_env, v).await,
             SubCommand::CreateCanister(v) => create_canister::exec(&agent_env, v).await,
+            SubCommand::Fabricate(v) => fabricate::exec(&agent_env, v).await,
             SubCommand::FabricateCycles(v) => fabricate_cycles::exec(&agent_env, v).await,
             SubCommand::Notify(v) => notify::exec(&agent_env, v).await,
             SubCommand::ShowSubnetTypes(v) => show_subnet_types::exec(&agent_env, v).await,
@@ -4,12 +4,14 @@ use clap::{Parser, Subcommand};
 
 mod convert;
 mod create_canister;
+mod scan;
 mod top_up;
 
 /// Notify the ledger about a send transaction to the cycles minting canister.
 /// This command should only be used if `dfx ledger create-canister` or `dfx ledger top-up`
 /// successfully sent a message to the ledger, and a transaction was recorded at some block height, but
 /// for some reason the subsequent notify failed.
+/// Use `dfx ledger notify scan` to find such transactions.
 #[derive(Parser)]
 pub struct NotifyOpts {
     #[command(subcommand)]
@@ -20,6 +22,7 @@ pub struct NotifyOpts {
 pub enum Subcmd {
     CreateCanister(create_canister::NotifyCreateOpts),
     Convert(convert::ConvertOpts),
+    Scan(scan::NotifyScanOpts),
     TopUp(top_up::NotifyTopUpOpts),
 }
 
@@ -27,6 +30,7 @@ pub async fn exec(env: &dyn Environment, opts: NotifyOpts) -> DfxResult {
     match opts.subcmd {
         Subcmd::CreateCanister(opts) => create_canister::exec(env, opts).await,
         Subcmd::Convert(opts) => convert::exec(env, opts).await,
+        Subcmd::Scan(opts) => scan::exec(env, opts).await,
         Subcmd::TopUp(opts) => top_up::exec(env, opts).await,
     }
 }
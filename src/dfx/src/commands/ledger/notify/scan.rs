@@ -0,0 +1,161 @@
+use crate::commands::cycles::convert::MEMO_MINT_CYCLES;
+use crate::commands::ledger::create_canister::MEMO_CREATE_CANISTER;
+use crate::commands::ledger::top_up::MEMO_TOP_UP_CANISTER;
+use crate::lib::error::{NotifyCreateCanisterError, NotifyTopUpError};
+use crate::lib::ledger_types::NotifyError::Refunded;
+use crate::lib::ledger_types::{BlockIndex, Operation, MAINNET_CYCLE_MINTER_CANISTER_ID};
+use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
+use crate::lib::operations::cmc::{notify_create, notify_top_up};
+use crate::lib::operations::ledger::query_blocks;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::{environment::Environment, error::DfxResult};
+use crate::util::clap::subnet_selection_opt::SubnetSelectionOpt;
+use anyhow::anyhow;
+use candid::Principal;
+use clap::Parser;
+use slog::info;
+use std::cmp::min;
+
+const BLOCKS_PER_QUERY: u64 = 1000;
+
+/// Scans the most recent blocks of the ledger for transfers from the selected identity to the cycles
+/// minting canister, and prints how to notify each of them.
+/// Transfers that were already notified are listed too: notifying them again returns the earlier result.
+#[derive(Parser)]
+pub struct NotifyScanOpts {
+    /// Number of most recent blocks to scan. Blocks that the ledger has archived are not scanned.
+    #[arg(long, default_value = "2000")]
+    blocks: u64,
+
+    /// Subaccount the transfers were sent from.
+    #[arg(long)]
+    from_subaccount: Option<Subaccount>,
+
+    /// A canister that may have been topped up. The canisters of the project are always considered.
+    #[arg(long, value_name = "PRINCIPAL")]
+    canister: Vec<Principal>,
+
+    /// Notify the cycles minting canister about the create-canister and top-up transfers that were found.
+    /// The subnet options apply to the canisters that are created.
+    #[arg(long)]
+    notify: bool,
+
+    #[command(flatten)]
+    subnet_selection: SubnetSelectionOpt,
+}
+
+enum CmcTransfer {
+    CreateCanister,
+    TopUp(Option<Principal>),
+    Convert,
+}
+
+pub async fn exec(env: &dyn Environment, opts: NotifyScanOpts) -> DfxResult {
+    let agent = env.get_agent();
+    let caller = agent
+        .get_principal()
+        .map_err(|err| anyhow!("Failed to get selected identity principal: {err}"))?;
+
+    fetch_root_key_if_needed(env).await?;
+
+    let mut canisters = opts.canister.clone();
+    if env.get_config().is_some() {
+        for canister_id in env.get_canister_id_store()?.get_name_id_map().values() {
+            canisters.push(Principal::from_text(canister_id)?);
+        }
+    }
+    let cmc_account = |principal: &Principal| {
+        AccountIdentifier::new(
+            MAINNET_CYCLE_MINTER_CANISTER_ID,
+            Some(Subaccount::from(principal)),
+        )
+        .to_vec()
+    };
+    let caller_cmc_account = cmc_account(&caller);
+    let top_up_accounts: Vec<_> = canisters
+        .iter()
+        .map(|canister| (cmc_account(canister), *canister))
+        .collect();
+    let from = AccountIdentifier::new(caller, opts.from_subaccount).to_vec();
+
+    let chain_length = query_blocks(agent, 0, 0).await?.chain_length;
+    let mut start = chain_length.saturating_sub(opts.blocks);
+    let mut transfers: Vec<(BlockIndex, CmcTransfer)> = vec![];
+    while start < chain_length {
+        let length = min(BLOCKS_PER_QUERY, chain_length - start);
+        let response = query_blocks(agent, start, length).await?;
+        for (block_index, block) in (response.first_block_index..).zip(response.blocks) {
+            let Some(Operation::Transfer {
+                from: sender, to, ..
+            }) = block.transaction.operation
+            else {
+                continue;
+            };
+            if sender != from {
+                continue;
+            }
+            let transfer = match block.transaction.memo.0 {
+                MEMO_CREATE_CANISTER if to == caller_cmc_account => CmcTransfer::CreateCanister,
+                MEMO_MINT_CYCLES if to == caller_cmc_account => CmcTransfer::Convert,
+                MEMO_TOP_UP_CANISTER => CmcTransfer::TopUp(
+                    top_up_accounts
+                        .iter()
+                        .find(|(account, _)| *account == to)
+                        .map(|(_, canister)| *canister),
+                ),
+                _ => continue,
+            };
+            transfers.push((block_index, transfer));
+        }
+        start += length;
+    }
+
+    info!(
+        env.get_logger(),
+        "Found {} transfer(s) to the cycles minting canister in the last {} block(s).",
+        transfers.len(),
+        chain_length - chain_length.saturating_sub(opts.blocks)
+    );
+    let subnet_selection = if opts.notify {
+        opts.subnet_selection.into_subnet_selection(env).await?
+    } else {
+        None
+    };
+    for (block_index, transfer) in transfers {
+        match transfer {
+            CmcTransfer::CreateCanister if opts.notify => {
+                match notify_create(agent, caller, block_index, subnet_selection.clone()).await {
+                    Ok(canister) => println!("Block {block_index}: canister {canister} created."),
+                    Err(NotifyCreateCanisterError::Notify(Refunded { reason, .. })) => {
+                        println!("Block {block_index}: refunded with message: {reason}")
+                    }
+                    Err(err) => println!("Block {block_index}: {err}"),
+                }
+            }
+            CmcTransfer::TopUp(Some(canister)) if opts.notify => {
+                match notify_top_up(agent, canister, block_index).await {
+                    Ok(cycles) => {
+                        println!("Block {block_index}: canister {canister} topped up with {cycles} cycles.")
+                    }
+                    Err(NotifyTopUpError::Notify(Refunded { reason, .. })) => {
+                        println!("Block {block_index}: refunded with message: {reason}")
+                    }
+                    Err(err) => println!("Block {block_index}: {err}"),
+                }
+            }
+            CmcTransfer::CreateCanister => println!(
+                "Block {block_index}: dfx ledger notify create-canister {block_index} {caller}"
+            ),
+            CmcTransfer::TopUp(Some(canister)) => {
+                println!("Block {block_index}: dfx ledger notify top-up {block_index} {canister}")
+            }
+            CmcTransfer::TopUp(None) => println!(
+                "Block {block_index}: top-up of an unknown canister. Pass it with --canister, or run: dfx ledger notify top-up {block_index} <canister>"
+            ),
+            CmcTransfer::Convert => {
+                println!("Block {block_index}: dfx ledger notify convert {block_index}")
+            }
+        }
+    }
+    Ok(())
+}
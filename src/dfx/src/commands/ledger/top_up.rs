@@ -6,13 +6,15 @@ use crate::lib::ledger_types::NotifyError::Refunded;
 use crate::lib::nns_types::account_identifier::Subaccount;
 use crate::lib::nns_types::icpts::{ICPTs, TRANSACTION_FEE};
 use crate::lib::operations::cmc::{notify_top_up, transfer_cmc};
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::e8s_parser;
+use crate::util::currency_conversion::print_cycles_estimate;
 use anyhow::{bail, Context};
 use candid::Principal;
 use clap::Parser;
 
-const MEMO_TOP_UP_CANISTER: u64 = 1347768404_u64;
+pub const MEMO_TOP_UP_CANISTER: u64 = 1347768404_u64;
 
 /// Top up a canister with cycles minted from ICP
 #[derive(Parser)]
@@ -71,8 +73,11 @@ pub async fn exec(env: &dyn Environment, opts: TopUpOpts) -> DfxResult {
 
     fetch_root_key_if_needed(env).await?;
 
+    print_cycles_estimate(agent, env.get_logger(), &amount).await;
+
     let height = transfer_cmc(
         agent,
+        retry_policy(env),
         env.get_logger(),
         memo,
         amount,
@@ -5,6 +5,7 @@ use crate::lib::ledger_types::{Memo, MAINNET_LEDGER_CANISTER_ID};
 use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
 use crate::lib::nns_types::icpts::{ICPTs, TRANSACTION_FEE};
 use crate::lib::operations::ledger::transfer;
+use crate::lib::retryable::retry_policy;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::clap::parsers::{e8s_parser, memo_parser};
 use anyhow::{anyhow, Context};
@@ -80,6 +81,7 @@ pub async fn exec(env: &dyn Environment, opts: TransferOpts) -> DfxResult {
 
     let _block_height = transfer(
         agent,
+        retry_policy(env),
         env.get_logger(),
         &canister_id,
         memo,
@@ -0,0 +1,105 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use anyhow::{bail, Context};
+use clap::{Parser, ValueEnum};
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Shows the output of a process started by `dfx start`.
+/// The output is kept in the logs directory of the local network, and rotated once it grows past 10 MiB.
+#[derive(Parser)]
+pub struct LogsOpts {
+    /// The process to show the output of.
+    process: LogSource,
+
+    /// Keeps printing the output as the process writes it, like `tail -f`.
+    #[arg(long)]
+    tail: bool,
+
+    /// Only shows this many of the most recent lines.
+    #[arg(long, short = 'n')]
+    lines: Option<usize>,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+enum LogSource {
+    Replica,
+    BtcAdapter,
+    CanisterHttpAdapter,
+    /// The scheduled tasks configured in the `tasks` of the local network.
+    Tasks,
+}
+
+pub fn exec(env: &dyn Environment, opts: LogsOpts) -> DfxResult {
+    let network_descriptor = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        opts.network.to_network_name(env),
+        None,
+        LocalBindDetermination::AsConfigured,
+    )?;
+    let local_server_descriptor = network_descriptor.local_server_descriptor()?;
+    let path = match opts.process {
+        LogSource::Replica => local_server_descriptor.replica_log_path(),
+        LogSource::BtcAdapter => local_server_descriptor.btc_adapter_log_path(),
+        LogSource::CanisterHttpAdapter => local_server_descriptor.canister_http_adapter_log_path(),
+        LogSource::Tasks => local_server_descriptor.tasks_log_path(),
+    };
+    if !path.exists() {
+        bail!(
+            "There is no output of this process yet in {}. Is the local network running?",
+            path.display()
+        );
+    }
+
+    let contents = dfx_core::fs::read_to_string(&path)?;
+    let skip = match opts.lines {
+        Some(lines) => contents.lines().count().saturating_sub(lines),
+        None => 0,
+    };
+    let mut stdout = std::io::stdout().lock();
+    for line in contents.lines().skip(skip) {
+        writeln!(stdout, "{line}")?;
+    }
+    stdout.flush()?;
+    drop(stdout);
+
+    if opts.tail {
+        follow(&path, contents.len() as u64)?;
+    }
+    Ok(())
+}
+
+/// Prints what is appended to the log file, starting at `position`, until interrupted.
+/// When the file is rotated, the new file is followed from its start.
+fn follow(path: &Path, mut position: u64) -> DfxResult {
+    loop {
+        std::thread::sleep(Duration::from_millis(500));
+        let Ok(mut file) = File::open(path) else {
+            continue;
+        };
+        let len = file.metadata()?.len();
+        if len < position {
+            position = 0;
+        }
+        if len == position {
+            continue;
+        }
+        file.seek(SeekFrom::Start(position))
+            .with_context(|| format!("Failed to read {}.", path.display()))?;
+        let mut appended = vec![];
+        file.read_to_end(&mut appended)
+            .with_context(|| format!("Failed to read {}.", path.display()))?;
+        position += appended.len() as u64;
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(&appended)?;
+        stdout.flush()?;
+    }
+}
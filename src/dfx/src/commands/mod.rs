@@ -3,60 +3,97 @@ use crate::lib::error::DfxResult;
 use anyhow::bail;
 use clap::Subcommand;
 
+mod apply;
 mod beta;
 mod build;
 mod cache;
+mod candid;
 mod canister;
+mod config;
 mod cycles;
 mod deploy;
 mod deps;
 mod diagnose;
+mod diagnostics;
+mod doctor;
+mod domains;
 mod extension;
 mod fix;
 mod generate;
 mod identity;
 mod info;
+mod internal;
 mod language_service;
 mod ledger;
+mod logs;
+mod network;
 mod new;
+mod pack;
 mod ping;
+mod principal;
+mod proxy;
 mod quickstart;
+mod record;
 mod remote;
+mod repl;
+mod replay;
+mod request;
 mod schema;
 mod start;
 mod stop;
+mod subnet;
+mod test;
 mod toolchain;
 mod upgrade;
 mod wallet;
 
 #[derive(Subcommand)]
 pub enum DfxCommand {
+    Apply(apply::ApplyOpts),
     #[command(hide = true)]
     Beta(beta::BetaOpts),
     Build(build::CanisterBuildOpts),
     Cache(cache::CacheOpts),
+    Candid(candid::CandidOpts),
     Canister(canister::CanisterOpts),
+    Config(config::ConfigOpts),
     //TODO(SDK-1331): unhide
     #[command(hide = true)]
     Cycles(cycles::CyclesOpts),
     Deploy(deploy::DeployOpts),
     Deps(deps::DepsOpts),
     Diagnose(diagnose::DiagnoseOpts),
+    Diagnostics(diagnostics::DiagnosticsOpts),
+    Doctor(doctor::DoctorOpts),
+    Domains(domains::DomainsOpts),
     Fix(fix::FixOpts),
     Extension(extension::ExtensionOpts),
     Generate(generate::GenerateOpts),
     Identity(identity::IdentityOpts),
     Info(info::InfoOpts),
+    #[command(hide = true)]
+    Internal(internal::InternalOpts),
     #[command(name = "_language-service")]
     LanguageServices(language_service::LanguageServiceOpts),
     Ledger(ledger::LedgerOpts),
+    Logs(logs::LogsOpts),
+    Network(network::NetworkOpts),
     New(new::NewOpts),
+    Pack(pack::PackOpts),
     Ping(ping::PingOpts),
+    Principal(principal::PrincipalOpts),
+    Proxy(proxy::ProxyOpts),
     Quickstart(quickstart::QuickstartOpts),
+    Record(record::RecordOpts),
     Remote(remote::RemoteOpts),
+    Repl(repl::ReplOpts),
+    Replay(replay::ReplayOpts),
+    Request(request::RequestOpts),
     Schema(schema::SchemaOpts),
     Start(start::StartOpts),
     Stop(stop::StopOpts),
+    Subnet(subnet::SubnetOpts),
+    Test(test::TestOpts),
     #[command(hide = true)]
     Toolchain(toolchain::ToolchainOpts),
     #[command(hide = true)]
@@ -66,28 +103,46 @@ pub enum DfxCommand {
 
 pub fn exec(env: &dyn Environment, cmd: DfxCommand) -> DfxResult {
     match cmd {
+        DfxCommand::Apply(v) => apply::exec(env, v),
         DfxCommand::Beta(v) => beta::exec(env, v),
         DfxCommand::Build(v) => build::exec(env, v),
         DfxCommand::Cache(v) => cache::exec(env, v),
+        DfxCommand::Candid(v) => candid::exec(env, v),
         DfxCommand::Canister(v) => canister::exec(env, v),
+        DfxCommand::Config(v) => config::exec(env, v),
         DfxCommand::Cycles(v) => cycles::exec(env, v),
         DfxCommand::Deploy(v) => deploy::exec(env, v),
         DfxCommand::Deps(v) => deps::exec(env, v),
         DfxCommand::Diagnose(v) => diagnose::exec(env, v),
+        DfxCommand::Diagnostics(v) => diagnostics::exec(env, v),
+        DfxCommand::Doctor(v) => doctor::exec(env, v),
+        DfxCommand::Domains(v) => domains::exec(env, v),
         DfxCommand::Fix(v) => fix::exec(env, v),
         DfxCommand::Extension(v) => extension::exec(env, v),
         DfxCommand::Generate(v) => generate::exec(env, v),
         DfxCommand::Identity(v) => identity::exec(env, v),
         DfxCommand::Info(v) => info::exec(env, v),
+        DfxCommand::Internal(v) => internal::exec(env, v),
         DfxCommand::LanguageServices(v) => language_service::exec(env, v),
         DfxCommand::Ledger(v) => ledger::exec(env, v),
+        DfxCommand::Logs(v) => logs::exec(env, v),
+        DfxCommand::Network(v) => network::exec(env, v),
         DfxCommand::New(v) => new::exec(env, v),
+        DfxCommand::Pack(v) => pack::exec(env, v),
         DfxCommand::Ping(v) => ping::exec(env, v),
+        DfxCommand::Principal(v) => principal::exec(env, v),
+        DfxCommand::Proxy(v) => proxy::exec(env, v),
         DfxCommand::Quickstart(v) => quickstart::exec(env, v),
+        DfxCommand::Record(v) => record::exec(env, v),
         DfxCommand::Remote(v) => remote::exec(env, v),
+        DfxCommand::Repl(v) => repl::exec(env, v),
+        DfxCommand::Replay(v) => replay::exec(env, v),
+        DfxCommand::Request(v) => request::exec(env, v),
         DfxCommand::Schema(v) => schema::exec(v),
         DfxCommand::Start(v) => start::exec(env, v),
         DfxCommand::Stop(v) => stop::exec(env, v),
+        DfxCommand::Subnet(v) => subnet::exec(env, v),
+        DfxCommand::Test(v) => test::exec(env, v),
         DfxCommand::Toolchain(v) => toolchain::exec(env, v),
         DfxCommand::Upgrade(v) => upgrade::exec(env, v),
         DfxCommand::Wallet(v) => wallet::exec(env, v),
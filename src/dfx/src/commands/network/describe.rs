@@ -0,0 +1,86 @@
+use super::summarize_network;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use clap::Parser;
+use dfx_core::network::provider::LocalBindDetermination;
+
+/// Shows the effective configuration of a network, after defaults have been applied.
+/// The network is selected the same way as for other commands, so without --network this describes
+/// the network that other commands use.
+#[derive(Parser)]
+pub struct DescribeOpts {
+    #[command(flatten)]
+    network: NetworkOpt,
+
+    /// Output the configuration as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: DescribeOpts) -> DfxResult {
+    // Describe the port that a running local network actually listens on.
+    let network = summarize_network(
+        env,
+        opts.network.to_network_name(env),
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&network)?);
+        return Ok(());
+    }
+    println!("Name: {}", network.name);
+    println!("Defined in: {}", network.source);
+    println!("Type: {}", network.type_description());
+    println!("Providers: {}", network.providers.join(", "));
+    println!("Mainnet: {}", if network.is_ic { "yes" } else { "no" });
+    println!("Default: {}", if network.is_default { "yes" } else { "no" });
+    if let Some(timeout) = network.connection.http_timeout {
+        println!("HTTP timeout: {}", humantime::format_duration(timeout));
+    }
+    if let Some(retry) = network.connection.retry {
+        println!("Retries: {retry}");
+    }
+    if let Some(local) = &network.local {
+        println!("Bind address: {}", local.bind);
+        println!("Data directory: {}", local.data_directory.display());
+        if let Some(subnet_type) = &local.replica.subnet_type {
+            println!(
+                "Replica subnet type: {}",
+                subnet_type.as_ic_starter_string()
+            );
+        }
+        if let Some(log_level) = &local.replica.log_level {
+            println!("Replica log level: {}", log_level.as_ic_starter_string());
+        }
+        println!(
+            "Bitcoin: {}",
+            if local.bitcoin.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        println!(
+            "Canister HTTP: {}",
+            if local.canister_http.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        println!(
+            "Candid UI: {}",
+            if local.candid_ui.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        for (domain, canister) in &local.proxy.custom_domains {
+            println!("Custom domain: {domain} -> {canister}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use super::summarize_network;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+use dfx_core::config::model::network_descriptor::PLAYGROUND_NETWORK_NAME;
+use dfx_core::network::provider::LocalBindDetermination;
+use slog::warn;
+use std::collections::BTreeSet;
+
+/// Lists the built-in networks, the networks defined in dfx.json, and the shared networks defined in networks.json.
+/// The default network is marked with an asterisk.
+#[derive(Parser)]
+pub struct ListOpts {
+    /// Output the networks as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: ListOpts) -> DfxResult {
+    let mut names: BTreeSet<String> = ["ic", "local", PLAYGROUND_NETWORK_NAME]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    names.extend(
+        env.get_networks_config()
+            .get_interface()
+            .networks
+            .keys()
+            .cloned(),
+    );
+    if let Some(config) = env.get_config() {
+        if let Some(networks) = &config.get_config().networks {
+            names.extend(networks.keys().cloned());
+        }
+    }
+
+    let mut networks = vec![];
+    for name in names {
+        match summarize_network(env, Some(name), LocalBindDetermination::AsConfigured) {
+            Ok(network) => networks.push(network),
+            Err(err) => warn!(env.get_logger(), "{err:#}"),
+        }
+    }
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&networks)?);
+        return Ok(());
+    }
+    let name_width = networks.iter().map(|n| n.name.len() + 2).max().unwrap_or(0);
+    let type_width = networks
+        .iter()
+        .map(|n| n.type_description().len())
+        .max()
+        .unwrap_or(0);
+    for network in &networks {
+        let name = if network.is_default {
+            format!("{} *", network.name)
+        } else {
+            network.name.clone()
+        };
+        println!(
+            "{name:<name_width$}  {:<type_width$}  {}  ({})",
+            network.type_description(),
+            network.providers.join(", "),
+            network.source
+        );
+    }
+    Ok(())
+}
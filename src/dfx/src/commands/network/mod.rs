@@ -0,0 +1,151 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::Context;
+use clap::Parser;
+use dfx_core::config::model::dfinity::{
+    ConfigDefaultsBitcoin, ConfigDefaultsCandidUi, ConfigDefaultsCanisterHttp, ConfigDefaultsProxy,
+    ConfigDefaultsReplica, ConfigNetworkConnection,
+};
+use dfx_core::config::model::local_server_descriptor::LocalNetworkScopeDescriptor;
+use dfx_core::config::model::network_descriptor::{NetworkTypeDescriptor, PLAYGROUND_NETWORK_NAME};
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+mod describe;
+mod list;
+mod ping;
+
+/// Shows the networks that dfx can connect to, and how they are configured.
+#[derive(Parser)]
+#[command(name = "network")]
+pub struct NetworkOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Describe(describe::DescribeOpts),
+    List(list::ListOpts),
+    Ping(ping::PingOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: NetworkOpts) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::Describe(v) => describe::exec(env, v),
+        SubCommand::List(v) => list::exec(env, v),
+        SubCommand::Ping(v) => ping::exec(env, v),
+    }
+}
+
+/// A network after the project defaults and the built-in defaults have been applied.
+#[derive(Serialize)]
+struct NetworkSummary {
+    name: String,
+    /// Where the network is defined.
+    source: String,
+    r#type: &'static str,
+    providers: Vec<String>,
+    is_ic: bool,
+    /// Whether commands use this network when no network is selected.
+    is_default: bool,
+    connection: ConfigNetworkConnection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local: Option<LocalNetworkSummary>,
+}
+
+#[derive(Serialize)]
+struct LocalNetworkSummary {
+    bind: SocketAddr,
+    scope: &'static str,
+    data_directory: PathBuf,
+    replica: ConfigDefaultsReplica,
+    proxy: ConfigDefaultsProxy,
+    bitcoin: ConfigDefaultsBitcoin,
+    canister_http: ConfigDefaultsCanisterHttp,
+    candid_ui: ConfigDefaultsCandidUi,
+}
+
+/// Resolves a network the same way other commands do. Without a name, resolves the default network.
+fn summarize_network(
+    env: &dyn Environment,
+    network: Option<String>,
+    local_bind_determination: LocalBindDetermination,
+) -> DfxResult<NetworkSummary> {
+    let descriptor = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        network.clone(),
+        None,
+        local_bind_determination,
+    )
+    .with_context(|| match &network {
+        Some(network) => format!("Failed to load network '{network}'."),
+        None => "Failed to load the default network.".to_string(),
+    })?;
+    Ok(NetworkSummary {
+        source: network_source(env, &descriptor.name),
+        r#type: match descriptor.r#type {
+            NetworkTypeDescriptor::Ephemeral { .. } => "ephemeral",
+            NetworkTypeDescriptor::Persistent => "persistent",
+            NetworkTypeDescriptor::Playground { .. } => "playground",
+        },
+        is_default: descriptor.name == default_network_name(env),
+        local: descriptor
+            .local_server_descriptor
+            .map(|local| LocalNetworkSummary {
+                bind: local.bind_address,
+                scope: match local.scope {
+                    LocalNetworkScopeDescriptor::Project => "project",
+                    LocalNetworkScopeDescriptor::Shared { .. } => "shared",
+                },
+                data_directory: local.data_directory,
+                replica: local.replica,
+                proxy: local.proxy,
+                bitcoin: local.bitcoin,
+                canister_http: local.canister_http,
+                candid_ui: local.candid_ui,
+            }),
+        name: descriptor.name,
+        providers: descriptor.providers,
+        is_ic: descriptor.is_ic,
+        connection: descriptor.connection,
+    })
+}
+
+/// Follows the order in which `create_network_descriptor` looks networks up.
+fn network_source(env: &dyn Environment, name: &str) -> String {
+    if name == "ic" {
+        return "built-in".to_string();
+    }
+    if let Some(config) = env.get_config() {
+        if config.get_config().get_network(name).is_some() {
+            return config.get_path().display().to_string();
+        }
+    }
+    let shared_config = env.get_networks_config();
+    if shared_config.get_interface().get_network(name).is_some() {
+        return shared_config.get_path().display().to_string();
+    }
+    match name {
+        "local" | PLAYGROUND_NETWORK_NAME => "built-in".to_string(),
+        _ => "URL".to_string(),
+    }
+}
+
+fn default_network_name(env: &dyn Environment) -> String {
+    env.get_config()
+        .and_then(|config| config.get_config().get_defaults().network.clone())
+        .unwrap_or_else(|| "local".to_string())
+}
+
+impl NetworkSummary {
+    fn type_description(&self) -> String {
+        match &self.local {
+            Some(local) => format!("{}, local ({})", self.r#type, local.scope),
+            None => self.r#type.to_string(),
+        }
+    }
+}
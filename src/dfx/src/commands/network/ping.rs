@@ -0,0 +1,69 @@
+use crate::lib::environment::{create_agent, Environment};
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use anyhow::bail;
+use clap::Parser;
+use dfx_core::identity::Identity;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use dfx_core::util::expiry_duration;
+use std::time::Instant;
+use tokio::runtime::Runtime;
+
+/// Pings the provider of a network, and shows whether it is healthy and how long it took to respond.
+/// Fails if no provider could be reached.
+#[derive(Parser)]
+pub struct PingOpts {
+    #[command(flatten)]
+    network: NetworkOpt,
+
+    /// Pings each of the providers of the network, instead of the first one only.
+    #[arg(long)]
+    all_providers: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: PingOpts) -> DfxResult {
+    let network_descriptor = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        opts.network.to_network_name(env),
+        None,
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+    let providers = if opts.all_providers {
+        network_descriptor.providers.clone()
+    } else {
+        vec![network_descriptor.first_provider()?.to_string()]
+    };
+
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    let reachable = runtime.block_on(async {
+        let mut reachable = 0;
+        for url in &providers {
+            let agent = create_agent(
+                env.get_logger().clone(),
+                url,
+                Box::new(Identity::anonymous()),
+                expiry_duration(),
+                &network_descriptor.connection,
+            )?;
+            let start = Instant::now();
+            match agent.status().await {
+                Ok(status) => {
+                    let health = status.replica_health_status.as_deref().unwrap_or("unknown");
+                    println!("{url}: {health} ({} ms)", start.elapsed().as_millis());
+                    reachable += 1;
+                }
+                Err(err) => println!("{url}: unreachable ({err})"),
+            }
+        }
+        DfxResult::Ok(reachable)
+    })?;
+
+    if reachable == 0 {
+        bail!(
+            "No provider of network '{}' could be reached.",
+            network_descriptor.name
+        );
+    }
+    Ok(())
+}
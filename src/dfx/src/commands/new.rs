@@ -4,11 +4,16 @@ use crate::lib::error::{DfxError, DfxResult};
 use crate::lib::info::replica_rev;
 use crate::lib::manifest::{get_latest_version, is_upgrade_necessary};
 use crate::lib::program;
+use crate::lib::project_templates::{
+    archive_template, fetch_git_template, load_template_registry, post_generate_steps,
+    resolve_template, run_post_generate_steps, ProjectTemplate,
+};
 use crate::util::assets;
 use crate::util::clap::parsers::project_name_parser;
 use anyhow::{anyhow, bail, ensure, Context};
 use clap::{Parser, ValueEnum};
 use console::{style, Style};
+use dfx_core::cli::ask_for_consent;
 use dfx_core::json::{load_json_file, save_json_file};
 use dialoguer::theme::ColorfulTheme;
 use dialoguer::{FuzzySelect, MultiSelect};
@@ -68,6 +73,20 @@ pub struct NewOpts {
 
     #[arg(long, value_enum)]
     extras: Vec<Extra>,
+
+    /// Generate the project from a template: a built-in template (motoko, rust, azle, kybra, sveltekit, react, vue, vanilla, simple-assets),
+    /// a template registered in templates.json in the dfx config directory, or the url of a git repository.
+    #[arg(long, conflicts_with_all = ["type", "frontend", "no_frontend", "extras"])]
+    template: Option<String>,
+
+    /// The branch or tag of the git template to use.
+    #[arg(long, requires("template"))]
+    template_rev: Option<String>,
+
+    /// Runs the commands that the git template runs after generating the project, without asking.
+    /// Without it, dfx shows the commands and asks before running them.
+    #[arg(long, requires("template"))]
+    run_template_scripts: bool,
 }
 
 #[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
@@ -126,6 +145,7 @@ enum Extra {
     InternetIdentity,
     Bitcoin,
     FrontendTests,
+    Ci,
 }
 
 impl Display for Extra {
@@ -134,6 +154,7 @@ impl Display for Extra {
             Self::InternetIdentity => "Internet Identity",
             Self::Bitcoin => "Bitcoin (Regtest)",
             Self::FrontendTests => "Frontend tests",
+            Self::Ci => "CI workflow (GitHub Actions)",
         }
         .fmt(f)
     }
@@ -456,70 +477,68 @@ fn get_agent_js_version_from_npm(dist_tag: &str) -> DfxResult<String> {
         })
 }
 
-pub fn exec(env: &dyn Environment, mut opts: NewOpts) -> DfxResult {
-    use BackendType::*;
-    let log = env.get_logger();
-    let dry_run = opts.dry_run;
-
-    let r#type = if let Some(r#type) = opts.r#type {
-        r#type
-    } else if opts.frontend.is_none() && opts.extras.is_empty() && io::stdout().is_terminal() {
-        opts = get_opts_interactively(opts)?;
-        opts.r#type.unwrap()
-    } else {
-        Motoko
-    };
-
-    let project_name = Path::new(opts.project_name.as_str());
-    if project_name.exists() {
-        bail!("Cannot create a new project because the directory already exists.");
-    }
-
-    let current_version = env.get_version();
-    let version_str = format!("{}", current_version);
-
-    // It is fine for the following command to timeout or fail. We
-    // drop the error.
-    let latest_version = get_latest_version(RELEASE_ROOT, Some(CHECK_VERSION_TIMEOUT)).ok();
-
-    if is_upgrade_necessary(latest_version.as_ref(), current_version) {
-        warn_upgrade(log, latest_version.as_ref(), current_version);
+#[context("Failed to generate project from template {}.", url)]
+fn scaffold_from_git_template(
+    log: &Logger,
+    url: &str,
+    rev: Option<&str>,
+    project_name: &Path,
+    dry_run: bool,
+    run_template_scripts: bool,
+    variables: &BTreeMap<String, String>,
+) -> DfxResult {
+    let template_dir = tempfile::tempdir().context("Failed to create temporary directory.")?;
+    let checkout = template_dir.path().join("template");
+    fetch_git_template(url, rev, &checkout)?;
+    let (mut files, manifest) = archive_template(&checkout)?;
+    write_files_from_entries(log, &mut files, project_name, dry_run, variables)?;
+    let steps = post_generate_steps(&manifest, variables);
+    if dry_run || steps.is_empty() {
+        return Ok(());
     }
-
-    DiskBasedCache::install(&env.get_cache().version_str())?;
-
-    info!(
-        log,
-        r#"Creating new project "{}"..."#,
-        project_name.display()
-    );
-    if dry_run {
-        warn!(
-            log,
-            r#"Running in dry mode. Nothing will be committed to disk."#
-        );
+    if !run_template_scripts {
+        // The commands come from the template repository, so they only run with the consent of the user.
+        let commands = steps
+            .iter()
+            .map(|step| format!("    {step}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let consent = if io::stdin().is_terminal() {
+            ask_for_consent(&format!(
+                "The template runs the following commands in the new project:\n{commands}"
+            ))
+            .is_ok()
+        } else {
+            false
+        };
+        if !consent {
+            warn!(
+                log,
+                "Skipped the commands of the template. Run them yourself, or pass --run-template-scripts:\n{}",
+                commands
+            );
+            return Ok(());
+        }
     }
+    run_post_generate_steps(log, project_name, &steps)
+}
 
-    let project_name_str = project_name
-        .to_str()
-        .ok_or_else(|| anyhow!("Invalid argument: project_name"))?;
-
-    let variables: BTreeMap<String, String> = [
-        ("project_name".to_string(), project_name_str.to_string()),
-        ("dfx_version".to_string(), version_str.clone()),
-        ("dot".to_string(), ".".to_string()),
-        ("ic_commit".to_string(), replica_rev().to_string()),
-    ]
-    .iter()
-    .cloned()
-    .collect();
-
+fn scaffold_from_builtin_templates(
+    env: &dyn Environment,
+    opts: &NewOpts,
+    r#type: BackendType,
+    project_name: &Path,
+    dry_run: bool,
+    variables: &BTreeMap<String, String>,
+) -> DfxResult {
+    use BackendType::*;
+    let log = env.get_logger();
     write_files_from_entries(
         log,
         &mut assets::new_project_base_files().context("Failed to get base project archive.")?,
         project_name,
         dry_run,
-        &variables,
+        variables,
     )?;
 
     let frontend = if opts.no_frontend {
@@ -534,7 +553,7 @@ pub fn exec(env: &dyn Environment, mut opts: NewOpts) -> DfxResult {
             &mut assets::new_project_js_files().context("Failed to get JS config archive.")?,
             project_name,
             dry_run,
-            &variables,
+            variables,
         )?;
     }
 
@@ -550,7 +569,7 @@ pub fn exec(env: &dyn Environment, mut opts: NewOpts) -> DfxResult {
         &mut new_project_files,
         project_name,
         dry_run,
-        &variables,
+        variables,
     )?;
 
     if opts.extras.contains(&Extra::InternetIdentity) {
@@ -559,7 +578,7 @@ pub fn exec(env: &dyn Environment, mut opts: NewOpts) -> DfxResult {
             &mut assets::new_project_internet_identity_files()?,
             project_name,
             dry_run,
-            &variables,
+            variables,
         )?;
     }
     if opts.extras.contains(&Extra::Bitcoin) {
@@ -568,7 +587,16 @@ pub fn exec(env: &dyn Environment, mut opts: NewOpts) -> DfxResult {
             &mut assets::new_project_bitcoin_files()?,
             project_name,
             dry_run,
-            &variables,
+            variables,
+        )?;
+    }
+    if opts.extras.contains(&Extra::Ci) {
+        write_files_from_entries(
+            log,
+            &mut assets::new_project_ci_files()?,
+            project_name,
+            dry_run,
+            variables,
         )?;
     }
     if frontend != FrontendType::None {
@@ -579,8 +607,101 @@ pub fn exec(env: &dyn Environment, mut opts: NewOpts) -> DfxResult {
             frontend,
             &opts.extras,
             &opts.agent_version,
+            variables,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn exec(env: &dyn Environment, mut opts: NewOpts) -> DfxResult {
+    use BackendType::*;
+    let log = env.get_logger();
+    let dry_run = opts.dry_run;
+
+    let mut git_template = None;
+    if let Some(template) = opts.template.take() {
+        let registry = load_template_registry()?;
+        match resolve_template(&registry, &template, opts.template_rev.take())? {
+            ProjectTemplate::Backend(name) => {
+                opts.r#type = Some(BackendType::from_str(&name, true).map_err(|e| anyhow!(e))?);
+            }
+            ProjectTemplate::Frontend(name) => {
+                opts.r#type = Some(Motoko);
+                opts.frontend = Some(FrontendType::from_str(&name, true).map_err(|e| anyhow!(e))?);
+            }
+            ProjectTemplate::Git { url, rev } => {
+                opts.r#type = Some(Motoko);
+                git_template = Some((url, rev));
+            }
+        }
+    }
+
+    let r#type = if let Some(r#type) = opts.r#type {
+        r#type
+    } else if opts.frontend.is_none() && opts.extras.is_empty() && io::stdout().is_terminal() {
+        opts = get_opts_interactively(opts)?;
+        opts.r#type.unwrap()
+    } else {
+        Motoko
+    };
+
+    let project_name = Path::new(opts.project_name.as_str());
+    if project_name.exists() {
+        bail!("Cannot create a new project because the directory already exists.");
+    }
+
+    let current_version = env.get_version();
+    let version_str = format!("{}", current_version);
+
+    // It is fine for the following command to timeout or fail. We
+    // drop the error.
+    let latest_version = get_latest_version(RELEASE_ROOT, Some(CHECK_VERSION_TIMEOUT)).ok();
+
+    if is_upgrade_necessary(latest_version.as_ref(), current_version) {
+        warn_upgrade(log, latest_version.as_ref(), current_version);
+    }
+
+    DiskBasedCache::install(&env.get_cache().version_str())?;
+
+    info!(
+        log,
+        r#"Creating new project "{}"..."#,
+        project_name.display()
+    );
+    if dry_run {
+        warn!(
+            log,
+            r#"Running in dry mode. Nothing will be committed to disk."#
+        );
+    }
+
+    let project_name_str = project_name
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid argument: project_name"))?;
+
+    let variables: BTreeMap<String, String> = [
+        ("project_name".to_string(), project_name_str.to_string()),
+        ("dfx_version".to_string(), version_str.clone()),
+        ("dot".to_string(), ".".to_string()),
+        ("ic_commit".to_string(), replica_rev().to_string()),
+    ]
+    .iter()
+    .cloned()
+    .collect();
+
+    if let Some((url, rev)) = &git_template {
+        scaffold_from_git_template(
+            log,
+            url,
+            rev.as_deref(),
+            project_name,
+            dry_run,
+            opts.run_template_scripts,
             &variables,
         )?;
+    } else {
+        scaffold_from_builtin_templates(env, &opts, r#type, project_name, dry_run, &variables)?;
     }
 
     if !dry_run {
@@ -673,6 +794,7 @@ fn get_opts_interactively(opts: NewOpts) -> DfxResult<NewOpts> {
     if frontend != None && frontend != SimpleAssets {
         extras_list.push(FrontendTests);
     }
+    extras_list.push(Ci);
     let extras = MultiSelect::with_theme(&theme)
         .items(&extras_list)
         .with_prompt("Add extra features (space to select, enter to confirm)")
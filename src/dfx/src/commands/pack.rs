@@ -0,0 +1,142 @@
+use crate::config::dfx_version_str;
+use crate::lib::agent::create_agent_environment;
+use crate::lib::bundle::{
+    BundleManifest, BundledCanister, BUNDLE_FORMAT_VERSION, BUNDLE_MANIFEST_FILE_NAME,
+};
+use crate::lib::canister_info::assets::AssetsCanisterInfo;
+use crate::lib::canister_info::CanisterInfo;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::util::append_file;
+use anyhow::{bail, Context};
+use clap::Parser;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use slog::info;
+use std::path::PathBuf;
+
+/// Packs the built canisters of the project into a bundle that `dfx apply` deploys without the project.
+/// Build the canisters for the network with `dfx build` first.
+#[derive(Parser)]
+pub struct PackOpts {
+    /// Specifies the name of the canister to pack, along with its dependencies.
+    /// If you don’t specify a canister name, all canisters defined in the dfx.json file are packed.
+    canister_name: Option<String>,
+
+    /// Path of the bundle to create.
+    #[arg(long, short, default_value = "bundle.tar.gz")]
+    output: PathBuf,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+}
+
+pub fn exec(env: &dyn Environment, opts: PackOpts) -> DfxResult {
+    let env = create_agent_environment(env, opts.network.to_network_name(env))?;
+    let config = env.get_config_or_anyhow()?;
+    let network = env.get_network_descriptor();
+    let canister_id_store = env.get_canister_id_store()?;
+    let pull_canisters = config.get_config().get_pull_canisters()?;
+
+    let mut canister_names = config
+        .get_config()
+        .get_canister_names_with_dependencies(opts.canister_name.as_deref())?;
+    canister_names.sort();
+
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let mut canisters = vec![];
+    for name in canister_names {
+        if pull_canisters.contains_key(&name)
+            || config
+                .get_config()
+                .is_remote_canister(&name, &network.name)?
+        {
+            continue;
+        }
+        let canister_id = canister_id_store.get(&name)?;
+        let info = CanisterInfo::load(&config, &name, Some(canister_id))?;
+
+        let wasm_path = info.get_build_wasm_path();
+        if !wasm_path.exists() {
+            bail!(
+                "Canister {name} has not been built for network '{}'. Run `dfx build --network {}` first.",
+                network.name,
+                network.name
+            );
+        }
+        let wasm_module = dfx_core::fs::read(&wasm_path)?;
+        let file_name = wasm_path
+            .file_name()
+            .context("The wasm path has no file name.")?
+            .to_string_lossy();
+        let wasm = format!("canisters/{name}/{file_name}");
+        append_file(&mut builder, &wasm, &wasm_module)?;
+
+        let idl_path = info.get_constructor_idl_path();
+        let candid = if idl_path.exists() {
+            let candid = format!("canisters/{name}/{name}.did");
+            append_file(&mut builder, &candid, &dfx_core::fs::read(&idl_path)?)?;
+            Some(candid)
+        } else {
+            None
+        };
+
+        let mut assets = vec![];
+        if info.is_assets() {
+            for (index, source_path) in info
+                .as_info::<AssetsCanisterInfo>()?
+                .get_source_paths()
+                .iter()
+                .enumerate()
+            {
+                let dir = format!("assets/{name}/{index}");
+                builder.append_dir_all(&dir, source_path).with_context(|| {
+                    format!("Failed to add {} to the bundle.", source_path.display())
+                })?;
+                assets.push(dir);
+            }
+        }
+
+        canisters.push(BundledCanister {
+            name: name.clone(),
+            canister_id,
+            wasm,
+            wasm_sha256: hex::encode(Sha256::digest(&wasm_module)),
+            candid,
+            init_arg: info.get_init_arg().map(String::from),
+            assets,
+        });
+    }
+    if canisters.is_empty() {
+        bail!("There are no canisters to pack.");
+    }
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        dfx_version: dfx_version_str().to_string(),
+        network: network.name.clone(),
+        canisters,
+    };
+    append_file(
+        &mut builder,
+        BUNDLE_MANIFEST_FILE_NAME,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    let bundle = builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .context("Failed to compress the bundle.")?;
+    dfx_core::fs::write(&opts.output, &bundle)?;
+
+    info!(
+        env.get_logger(),
+        "Packed {} canister(s) for network '{}' into {}.",
+        manifest.canisters.len(),
+        manifest.network,
+        opts.output.display()
+    );
+    println!("{}", hex::encode(Sha256::digest(&bundle)));
+    Ok(())
+}
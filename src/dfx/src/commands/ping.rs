@@ -2,6 +2,7 @@ use crate::lib::environment::{create_agent, Environment};
 use crate::lib::error::{DfxError, DfxResult};
 use anyhow::{bail, Context};
 use clap::Parser;
+use dfx_core::config::model::dfinity::ConfigNetworkConnection;
 use dfx_core::identity::Identity;
 use dfx_core::network::provider::{
     command_line_provider_to_url, create_network_descriptor, get_network_context,
@@ -29,7 +30,7 @@ pub struct PingOpts {
 pub fn exec(env: &dyn Environment, opts: PingOpts) -> DfxResult {
     // For ping, "provider" could either be a URL or a network name.
     // If not passed, we default to the "local" network.
-    let agent_url = create_network_descriptor(
+    let (agent_url, connection) = create_network_descriptor(
         env.get_config(),
         env.get_networks_config(),
         opts.network,
@@ -38,19 +39,25 @@ pub fn exec(env: &dyn Environment, opts: PingOpts) -> DfxResult {
     )
     .and_then(|network_descriptor| {
         let url = network_descriptor.first_provider()?.to_string();
-        Ok(url)
+        Ok((url, network_descriptor.connection))
     })
     .or_else::<DfxError, _>(|err| {
         let logger = env.get_logger();
         warn!(logger, "{:#}", err);
         let network_name = get_network_context()?;
         let url = command_line_provider_to_url(&network_name)?;
-        Ok(url)
+        Ok((url, ConfigNetworkConnection::default()))
     })?;
 
     let timeout = expiry_duration();
     let identity = Box::new(Identity::anonymous());
-    let agent = create_agent(env.get_logger().clone(), &agent_url, identity, timeout)?;
+    let agent = create_agent(
+        env.get_logger().clone(),
+        &agent_url,
+        identity,
+        timeout,
+        &connection,
+    )?;
 
     let runtime = Runtime::new().expect("Unable to create a runtime");
     runtime.block_on(async {
@@ -0,0 +1,24 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
+use candid::Principal;
+use clap::Parser;
+
+/// Prints the ICP ledger account identifier of a principal.
+#[derive(Parser)]
+pub struct AccountIdOpts {
+    /// The principal that owns the account.
+    principal: Principal,
+
+    /// Subaccount identifier (64 character long hex string).
+    #[arg(long)]
+    subaccount: Option<Subaccount>,
+}
+
+pub fn exec(_env: &dyn Environment, opts: AccountIdOpts) -> DfxResult {
+    println!(
+        "{}",
+        AccountIdentifier::new(opts.principal, opts.subaccount)
+    );
+    Ok(())
+}
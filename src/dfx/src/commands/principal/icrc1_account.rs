@@ -0,0 +1,26 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::nns_types::account_identifier::Subaccount;
+use candid::Principal;
+use clap::Parser;
+use icrc_ledger_types::icrc1::account::Account;
+
+/// Prints the textual encoding of the ICRC-1 account of a principal.
+#[derive(Parser)]
+pub struct Icrc1AccountOpts {
+    /// The principal that owns the account.
+    principal: Principal,
+
+    /// Subaccount identifier (64 character long hex string).
+    #[arg(long)]
+    subaccount: Option<Subaccount>,
+}
+
+pub fn exec(_env: &dyn Environment, opts: Icrc1AccountOpts) -> DfxResult {
+    let account = Account {
+        owner: opts.principal,
+        subaccount: opts.subaccount.map(|subaccount| subaccount.0),
+    };
+    println!("{}", account);
+    Ok(())
+}
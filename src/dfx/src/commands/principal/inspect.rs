@@ -0,0 +1,62 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
+use anyhow::bail;
+use candid::Principal;
+use clap::Parser;
+use icrc_ledger_types::icrc1::account::Account;
+use std::str::FromStr;
+
+/// Prints what a principal, ICRC-1 account or ICP account identifier consists of,
+/// and the accounts that can be derived from it.
+#[derive(Parser)]
+pub struct InspectOpts {
+    /// A principal, the textual encoding of an ICRC-1 account, or an ICP account identifier.
+    value: String,
+}
+
+pub fn exec(_env: &dyn Environment, opts: InspectOpts) -> DfxResult {
+    if let Ok(principal) = Principal::from_text(&opts.value) {
+        println!("Principal: {}", principal);
+        println!("Kind: {}", principal_kind(&principal));
+        println!(
+            "Account identifier: {}",
+            AccountIdentifier::new(principal, None)
+        );
+    } else if let Ok(account) = Account::from_str(&opts.value) {
+        println!("ICRC-1 account: {}", account);
+        println!("Owner: {}", account.owner);
+        println!("Kind: {}", principal_kind(&account.owner));
+        if let Some(subaccount) = account.subaccount {
+            println!("Subaccount: {}", hex::encode(subaccount));
+        }
+        println!(
+            "Account identifier: {}",
+            AccountIdentifier::new(account.owner, account.subaccount.map(Subaccount))
+        );
+    } else if let Ok(account_id) = AccountIdentifier::from_hex(&opts.value) {
+        println!("Account identifier: {}", account_id);
+        println!(
+            "The checksum is valid. The owner of an account identifier cannot be derived from it."
+        );
+    } else {
+        bail!(
+            "'{}' is not a principal, an ICRC-1 account or an account identifier.",
+            opts.value
+        );
+    }
+    Ok(())
+}
+
+/// The kind of a principal, from the class in its last byte.
+fn principal_kind(principal: &Principal) -> &'static str {
+    match principal.as_slice().last() {
+        None => "management canister",
+        Some(0x01) if principal.as_slice().len() == 10 => "canister",
+        Some(0x01) => "opaque",
+        Some(0x02) => "self-authenticating (user)",
+        Some(0x03) => "derived",
+        Some(0x04) => "anonymous",
+        Some(_) => "unknown",
+    }
+}
@@ -0,0 +1,38 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+
+mod account_id;
+mod icrc1_account;
+mod inspect;
+mod neuron_account;
+mod random;
+mod top_up_account;
+
+/// Converts principals to ledger accounts and back, without calling any canister.
+#[derive(Parser)]
+pub struct PrincipalOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    AccountId(account_id::AccountIdOpts),
+    Icrc1Account(icrc1_account::Icrc1AccountOpts),
+    Inspect(inspect::InspectOpts),
+    NeuronAccount(neuron_account::NeuronAccountOpts),
+    Random(random::RandomOpts),
+    TopUpAccount(top_up_account::TopUpAccountOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: PrincipalOpts) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::AccountId(v) => account_id::exec(env, v),
+        SubCommand::Icrc1Account(v) => icrc1_account::exec(env, v),
+        SubCommand::Inspect(v) => inspect::exec(env, v),
+        SubCommand::NeuronAccount(v) => neuron_account::exec(env, v),
+        SubCommand::Random(v) => random::exec(env, v),
+        SubCommand::TopUpAccount(v) => top_up_account::exec(env, v),
+    }
+}
@@ -0,0 +1,40 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::ledger_types::MAINNET_GOVERNANCE_CANISTER_ID;
+use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
+use candid::Principal;
+use clap::Parser;
+use sha2::{Digest, Sha256};
+
+/// Prints the ICP ledger account to transfer to in order to stake a neuron.
+#[derive(Parser)]
+pub struct NeuronAccountOpts {
+    /// The principal that controls the neuron.
+    controller: Principal,
+
+    /// The memo of the neuron, which tells apart the neurons of the same controller.
+    #[arg(long, default_value = "0")]
+    memo: u64,
+
+    /// Prints the subaccount of the governance canister instead of the account identifier.
+    #[arg(long)]
+    subaccount: bool,
+}
+
+pub fn exec(_env: &dyn Environment, opts: NeuronAccountOpts) -> DfxResult {
+    let mut hasher = Sha256::new();
+    hasher.update([0x0c]);
+    hasher.update(b"neuron-stake");
+    hasher.update(opts.controller.as_slice());
+    hasher.update(opts.memo.to_be_bytes());
+    let subaccount = Subaccount(hasher.finalize().into());
+    if opts.subaccount {
+        println!("{}", hex::encode(subaccount.0));
+    } else {
+        println!(
+            "{}",
+            AccountIdentifier::new(MAINNET_GOVERNANCE_CANISTER_ID, Some(subaccount))
+        );
+    }
+    Ok(())
+}
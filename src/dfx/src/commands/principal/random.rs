@@ -0,0 +1,24 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use candid::Principal;
+use clap::Parser;
+use rand::{thread_rng, RngCore};
+
+/// Prints random self-authenticating principals, for use in tests.
+/// Nobody holds the keys of these principals, so they cannot sign calls.
+#[derive(Parser)]
+pub struct RandomOpts {
+    /// The number of principals to print.
+    #[arg(long, default_value = "1")]
+    count: usize,
+}
+
+pub fn exec(_env: &dyn Environment, opts: RandomOpts) -> DfxResult {
+    let mut rng = thread_rng();
+    for _ in 0..opts.count {
+        let mut public_key = [0u8; 32];
+        rng.fill_bytes(&mut public_key);
+        println!("{}", Principal::self_authenticating(public_key));
+    }
+    Ok(())
+}
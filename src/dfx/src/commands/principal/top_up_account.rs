@@ -0,0 +1,25 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::ledger_types::MAINNET_CYCLE_MINTER_CANISTER_ID;
+use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
+use candid::Principal;
+use clap::Parser;
+
+/// Prints the ICP ledger account of the cycles minting canister that tops up a canister.
+/// ICP sent to it is converted to cycles for the canister by notifying the cycles minting canister.
+#[derive(Parser)]
+pub struct TopUpAccountOpts {
+    /// The canister to top up.
+    canister: Principal,
+}
+
+pub fn exec(_env: &dyn Environment, opts: TopUpAccountOpts) -> DfxResult {
+    println!(
+        "{}",
+        AccountIdentifier::new(
+            MAINNET_CYCLE_MINTER_CANISTER_ID,
+            Some(Subaccount::from(&opts.canister))
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::webserver::{run_dev_proxy, DevProxyConfig, API_PATH};
+use anyhow::Context;
+use clap::Parser;
+use dfx_core::config::model::canister_id_store::CanisterIdStore;
+use dfx_core::config::model::network_descriptor::NetworkDescriptor;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use slog::{info, warn};
+use std::net::SocketAddr;
+use tokio::process::Command;
+use tokio::runtime::Runtime;
+use url::Url;
+
+/// Serves a frontend dev server and the API of the network on the same address.
+/// Requests to /api go to the network, and all other requests to the dev server.
+#[derive(Parser)]
+pub struct ProxyOpts {
+    /// The URL of the frontend dev server, for example http://localhost:5173.
+    #[arg(long)]
+    dev_server: Url,
+
+    /// The address to serve on.
+    #[arg(long, default_value = "127.0.0.1:3000")]
+    bind: SocketAddr,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+
+    /// The command that starts the dev server, after `--`.
+    /// It runs with the canister ids of the project in CANISTER_ID_<name> environment variables,
+    /// and is stopped with the proxy.
+    #[arg(last = true)]
+    command: Vec<String>,
+}
+
+pub fn exec(env: &dyn Environment, opts: ProxyOpts) -> DfxResult {
+    let logger = env.get_logger().clone();
+    let network_descriptor = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        opts.network.to_network_name(env),
+        None,
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+    let network_url = Url::parse(network_descriptor.first_provider()?)
+        .context("Failed to parse the URL of the network.")?;
+    let environment_variables = canister_environment_variables(env, &network_descriptor)?;
+
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(async {
+        let mut dev_server = match opts.command.split_first() {
+            Some((program, args)) => Some(
+                Command::new(program)
+                    .args(args)
+                    .envs(environment_variables)
+                    .kill_on_drop(true)
+                    .spawn()
+                    .with_context(|| format!("Failed to run '{}'.", opts.command.join(" ")))?,
+            ),
+            None => None,
+        };
+
+        info!(
+            logger,
+            "Serving on http://{}: {} to {}, everything else to {}",
+            opts.bind,
+            API_PATH,
+            network_url,
+            opts.dev_server
+        );
+        let shutdown_logger = logger.clone();
+        let shutdown = async move {
+            match dev_server.as_mut() {
+                Some(dev_server) => tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    status = dev_server.wait() => {
+                        warn!(shutdown_logger, "The dev server exited: {:?}", status);
+                    }
+                },
+                None => {
+                    let _ = tokio::signal::ctrl_c().await;
+                }
+            }
+        };
+        let config = DevProxyConfig {
+            bind: opts.bind,
+            network_url,
+            dev_server_url: opts.dev_server,
+        };
+        run_dev_proxy(config, logger.clone(), shutdown).await
+    })
+}
+
+/// The variables the frontend build uses to find the canisters, as in the `.env` file written by `dfx deploy`.
+pub(crate) fn canister_environment_variables(
+    env: &dyn Environment,
+    network_descriptor: &NetworkDescriptor,
+) -> DfxResult<Vec<(String, String)>> {
+    let mut vars = vec![("DFX_NETWORK".to_string(), network_descriptor.name.clone())];
+    let Some(config) = env.get_config() else {
+        return Ok(vars);
+    };
+    let canister_id_store =
+        CanisterIdStore::new(env.get_logger(), network_descriptor, Some(config.clone()))?;
+    let canister_names = config.get_config().canisters.iter().flat_map(|c| c.keys());
+    for name in canister_names {
+        if let Some(canister_id) = canister_id_store.find(name) {
+            let var_name = name.replace('-', "_");
+            vars.push((
+                format!("CANISTER_ID_{}", var_name.to_ascii_uppercase()),
+                canister_id.to_text(),
+            ));
+            vars.push((format!("CANISTER_ID_{}", var_name), canister_id.to_text()));
+        }
+    }
+    Ok(vars)
+}
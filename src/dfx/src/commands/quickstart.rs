@@ -17,10 +17,12 @@ use crate::{
             cmc::{notify_create, transfer_cmc},
             ledger::{balance, xdr_permyriad_per_icp},
         },
+        retryable::retry_policy,
     },
     util::assets::wallet_wasm,
 };
 use anyhow::{bail, Context};
+use backoff::ExponentialBackoff;
 use candid::Principal;
 use clap::Parser;
 use dialoguer::{Confirm, Input};
@@ -152,13 +154,21 @@ async fn step_deploy_wallet(
         eprintln!("Run this command again at any time to continue from here.");
         return Ok(());
     }
-    let wallet = step_interact_ledger(agent, env.get_logger(), ident_principal, rounded).await?;
+    let wallet = step_interact_ledger(
+        agent,
+        retry_policy(env),
+        env.get_logger(),
+        ident_principal,
+        rounded,
+    )
+    .await?;
     step_finish_wallet(env, agent, wallet, ident).await?;
     Ok(())
 }
 
 async fn step_interact_ledger(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     logger: &Logger,
     ident_principal: Principal,
     to_spend: Decimal,
@@ -171,6 +181,7 @@ async fn step_interact_ledger(
     let icpts = ICPTs::from_decimal(to_spend)?;
     let height = transfer_cmc(
         agent,
+        retry_policy,
         logger,
         Memo(MEMO_CREATE_CANISTER /* 👽 */),
         icpts,
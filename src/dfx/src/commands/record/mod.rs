@@ -0,0 +1,27 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use clap::Parser;
+
+mod start;
+mod stop;
+
+/// Records the calls made with `dfx canister call` into a scenario file, to re-execute them with `dfx replay`.
+#[derive(Parser)]
+#[command(name = "record")]
+pub struct RecordOpts {
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Start(start::RecordStartOpts),
+    Stop(stop::RecordStopOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: RecordOpts) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::Start(v) => start::exec(env, v),
+        SubCommand::Stop(v) => stop::exec(env, v),
+    }
+}
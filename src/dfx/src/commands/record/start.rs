@@ -0,0 +1,25 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::scenario::start_recording;
+use clap::Parser;
+use slog::info;
+use std::path::PathBuf;
+
+/// Starts recording the calls made with `dfx canister call` in this project.
+#[derive(Parser)]
+pub struct RecordStartOpts {
+    /// The file to write the scenario to when the recording stops.
+    #[arg(long, default_value = "scenario.json")]
+    output: PathBuf,
+}
+
+pub fn exec(env: &dyn Environment, opts: RecordStartOpts) -> DfxResult {
+    let config = env.get_config_or_anyhow()?;
+    start_recording(&config, &opts.output)?;
+    info!(
+        env.get_logger(),
+        "Recording calls. Run `dfx record stop` to write them to {}.",
+        opts.output.display()
+    );
+    Ok(())
+}
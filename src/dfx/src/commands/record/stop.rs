@@ -0,0 +1,21 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::scenario::stop_recording;
+use clap::Parser;
+use slog::info;
+
+/// Stops recording and writes the recorded calls to the scenario file.
+#[derive(Parser)]
+pub struct RecordStopOpts {}
+
+pub fn exec(env: &dyn Environment, _opts: RecordStopOpts) -> DfxResult {
+    let config = env.get_config_or_anyhow()?;
+    let (output, calls) = stop_recording(&config)?;
+    info!(
+        env.get_logger(),
+        "Recorded {} call(s) to {}.",
+        calls,
+        output.display()
+    );
+    Ok(())
+}
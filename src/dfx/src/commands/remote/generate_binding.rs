@@ -2,10 +2,12 @@ use crate::lib::agent::create_agent_environment;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::models::canister::CanisterPool;
+use crate::lib::operations::canister::fetch_remote_candid_files;
 use anyhow::Context;
 use candid_parser::utils::CandidSource;
 use clap::Parser;
 use slog::info;
+use tokio::runtime::Runtime;
 
 /// Generate bindings for remote canisters from their .did declarations
 #[derive(Parser)]
@@ -37,6 +39,8 @@ pub fn exec(env: &dyn Environment, opts: GenerateBindingOpts) -> DfxResult {
         .get_config()
         .get_canister_names_with_dependencies(opts.canister.as_deref())?;
     let canister_pool = CanisterPool::load(&env, false, &canister_names)?;
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(fetch_remote_candid_files(&env, &canister_pool))?;
 
     for canister in canister_pool.get_canister_list() {
         let info = canister.get_info();
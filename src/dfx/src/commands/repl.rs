@@ -0,0 +1,257 @@
+use crate::lib::agent::create_agent_environment;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::operations::canister::{
+    get_local_cid_and_candid_path, get_named_cid_and_candid_path,
+};
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::util::{blob_from_arguments, fetch_remote_did_file, print_idl_blob};
+use anyhow::{anyhow, bail, Context};
+use candid::types::{Function, Type, TypeEnv};
+use candid::Principal;
+use candid_parser::utils::CandidSource;
+use clap::Parser;
+use dialoguer::{BasicHistory, Completion, Input};
+use slog::{error, info, warn};
+use tokio::runtime::Runtime;
+
+const COMMANDS: [&str; 4] = [".exit", ".help", ".methods", ".use"];
+
+/// Starts an interactive session for calling the methods of a canister.
+/// Method names are completed with Tab, and the arguments of a method
+/// are prompted for if they are not given.
+#[derive(Parser)]
+pub struct ReplOpts {
+    /// The name or id of the canister to call.
+    /// Another canister can be selected in the session with `.use <canister>`.
+    canister: Option<String>,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+}
+
+/// The canister that the session calls.
+struct Target {
+    name: String,
+    canister_id: Principal,
+    /// The Candid interface, if the canister has one.
+    interface: Option<(TypeEnv, Type)>,
+}
+
+impl Target {
+    fn methods(&self) -> Vec<(String, Type)> {
+        self.interface
+            .as_ref()
+            .and_then(|(env, actor)| env.as_service(actor).ok())
+            .map(|methods| methods.to_vec())
+            .unwrap_or_default()
+    }
+
+    fn method_type(&self, method: &str) -> Option<(TypeEnv, Function)> {
+        let (env, actor) = self.interface.as_ref()?;
+        let func = env.get_method(actor, method).ok()?.clone();
+        Some((env.clone(), func))
+    }
+}
+
+/// Completes the method names of the target and the session commands.
+struct MethodCompletion {
+    candidates: Vec<String>,
+}
+
+impl Completion for MethodCompletion {
+    fn get(&self, input: &str) -> Option<String> {
+        if input.is_empty() || input.contains(char::is_whitespace) {
+            return None;
+        }
+        let matches: Vec<&str> = self
+            .candidates
+            .iter()
+            .map(String::as_str)
+            .filter(|candidate| candidate.starts_with(input))
+            .collect();
+        let prefix = common_prefix(&matches)?;
+        (prefix.len() > input.len()).then(|| prefix.to_string())
+    }
+}
+
+fn common_prefix<'a>(words: &[&'a str]) -> Option<&'a str> {
+    let (first, rest) = words.split_first()?;
+    let mut len = first.len();
+    for word in rest {
+        len = first
+            .char_indices()
+            .zip(word.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((i, c), _)| i + c.len_utf8())
+            .min(len);
+    }
+    Some(&first[..len])
+}
+
+pub fn exec(env: &dyn Environment, opts: ReplOpts) -> DfxResult {
+    let env = create_agent_environment(env, opts.network.to_network_name(env))?;
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(async {
+        fetch_root_key_if_needed(&env).await?;
+        let mut target = match &opts.canister {
+            Some(canister) => Some(load_target(&env, canister).await?),
+            None => None,
+        };
+        let log = env.get_logger();
+        info!(
+            log,
+            "Type `.help` for the available commands, and `.exit` or Ctrl-D to quit."
+        );
+
+        let mut history = BasicHistory::new().max_entries(100).no_duplicates(true);
+        loop {
+            let methods = target.as_ref().map(Target::methods).unwrap_or_default();
+            let completion = MethodCompletion {
+                candidates: methods
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .chain(COMMANDS.iter().map(|c| c.to_string()))
+                    .collect(),
+            };
+            let prompt = target
+                .as_ref()
+                .map_or_else(|| "dfx".to_string(), |target| target.name.clone());
+            let Ok(line) = Input::<String>::new()
+                .with_prompt(prompt)
+                .allow_empty(true)
+                .completion_with(&completion)
+                .history_with(&mut history)
+                .interact_text()
+            else {
+                break;
+            };
+            let line = line.trim();
+            let (command, rest) = match line.split_once(char::is_whitespace) {
+                Some((command, rest)) => (command, rest.trim()),
+                None => (line, ""),
+            };
+            let result = match command {
+                "" => Ok(()),
+                ".exit" => break,
+                ".help" => {
+                    print_help();
+                    Ok(())
+                }
+                ".methods" => {
+                    for (name, ty) in &methods {
+                        println!("{} : {}", name, ty);
+                    }
+                    Ok(())
+                }
+                ".use" if rest.is_empty() => Err(anyhow!("Usage: .use <canister>")),
+                ".use" => load_target(&env, rest)
+                    .await
+                    .map(|loaded| target = Some(loaded)),
+                method => match &target {
+                    Some(target) => call(&env, target, method, rest).await,
+                    None => Err(anyhow!(
+                        "No canister is selected. Select one with `.use <canister>`."
+                    )),
+                },
+            };
+            if let Err(err) = result {
+                error!(log, "{:#}", err);
+            }
+        }
+        Ok(())
+    })
+}
+
+fn print_help() {
+    println!(
+        r#"<method> [argument]  Calls a method. The argument is prompted for if it is not given.
+.methods             Lists the methods of the canister.
+.use <canister>      Calls another canister, by name or id.
+.help                Shows this help.
+.exit                Ends the session."#
+    );
+}
+
+async fn load_target(env: &dyn Environment, canister: &str) -> DfxResult<Target> {
+    let canister_id_store = env.get_canister_id_store()?;
+    let (canister_id, local_candid_path) = match Principal::from_text(canister) {
+        Ok(id) => match canister_id_store.get_name(canister) {
+            Some(name) => get_local_cid_and_candid_path(env, name, Some(id))?,
+            None => (id, None),
+        },
+        Err(_) => get_named_cid_and_candid_path(env, &canister_id_store, canister)?,
+    };
+    let interface = match fetch_remote_did_file(env.get_agent(), canister_id).await {
+        Some(did) => CandidSource::Text(&did).load().ok(),
+        None => local_candid_path.and_then(|path| CandidSource::File(&path).load().ok()),
+    }
+    .and_then(|(env, actor)| Some((env, actor?)));
+    if interface.is_none() {
+        warn!(
+            env.get_logger(),
+            "Cannot fetch the Candid interface of {}. Arguments will be sent with inferred types.",
+            canister
+        );
+    }
+    Ok(Target {
+        name: canister.to_string(),
+        canister_id,
+        interface,
+    })
+}
+
+async fn call(env: &dyn Environment, target: &Target, method: &str, argument: &str) -> DfxResult {
+    let method_type = target.method_type(method);
+    if target.interface.is_some() && method_type.is_none() {
+        bail!("{} has no method '{}'.", target.name, method);
+    }
+    let argument = (!argument.is_empty()).then_some(argument);
+    let arg_value = blob_from_arguments(Some(env), argument, None, None, &method_type, false)?;
+
+    let agent = env.get_agent();
+    let is_query = method_type
+        .as_ref()
+        .map_or(false, |(_, func)| func.is_query());
+    let blob = if is_query {
+        agent
+            .query(&target.canister_id, method)
+            .with_arg(arg_value)
+            .call()
+            .await
+            .context("Failed query call.")?
+    } else {
+        agent
+            .update(&target.canister_id, method)
+            .with_arg(arg_value)
+            .call_and_wait()
+            .await
+            .context("Failed update call.")?
+    };
+    print_idl_blob(&blob, None, &method_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_common_prefix() {
+        let completion = MethodCompletion {
+            candidates: vec![
+                "get_balance".to_string(),
+                "get_blocks".to_string(),
+                "transfer".to_string(),
+                ".methods".to_string(),
+            ],
+        };
+        assert_eq!(completion.get("tr"), Some("transfer".to_string()));
+        assert_eq!(completion.get("get"), Some("get_b".to_string()));
+        assert_eq!(completion.get("get_b"), None);
+        assert_eq!(completion.get(".m"), Some(".methods".to_string()));
+        assert_eq!(completion.get("transfer ("), None);
+        assert_eq!(completion.get("x"), None);
+    }
+}
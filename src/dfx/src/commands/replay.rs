@@ -0,0 +1,121 @@
+use crate::lib::agent::create_agent_environment;
+use crate::lib::environment::{AgentEnvironment, Environment};
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::scenario::{load_scenario, RecordedCall};
+use anyhow::{bail, Context};
+use candid::{IDLArgs, Principal};
+use clap::Parser;
+use dfx_core::util::expiry_duration;
+use slog::{info, warn};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tokio::runtime::Runtime;
+
+/// Re-executes the calls of a scenario recorded with `dfx record`, with the same identities,
+/// and compares the responses with the recorded ones.
+/// The calls go to the canisters that are deployed on the network, in their current state.
+/// To replay against a fresh deployment, run `dfx test -- dfx replay <scenario>`.
+#[derive(Parser)]
+pub struct ReplayOpts {
+    /// The scenario file.
+    scenario: PathBuf,
+
+    #[command(flatten)]
+    network: NetworkOpt,
+
+    /// Do not compare the responses with the recorded responses.
+    #[arg(long)]
+    no_check: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: ReplayOpts) -> DfxResult {
+    let env = create_agent_environment(env, opts.network.to_network_name(env))?;
+    let log = env.get_logger();
+    let scenario = load_scenario(&opts.scenario)?;
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    info!(
+        log,
+        "Replaying the calls against the canisters deployed on network '{}', in their current state.",
+        env.get_network_descriptor().name
+    );
+    runtime.block_on(async {
+        let canister_id_store = env.get_canister_id_store()?;
+        let mut agent_envs: BTreeMap<String, AgentEnvironment> = BTreeMap::new();
+        let mut mismatches = 0;
+        for (index, call) in scenario.calls.iter().enumerate() {
+            let canister_id = Principal::from_text(&call.canister)
+                .or_else(|_| canister_id_store.get(&call.canister))?;
+            if !agent_envs.contains_key(&call.identity) {
+                let agent_env = AgentEnvironment::new(
+                    &env,
+                    env.get_network_descriptor().clone(),
+                    expiry_duration(),
+                    Some(&call.identity),
+                )?;
+                fetch_root_key_if_needed(&agent_env).await?;
+                agent_envs.insert(call.identity.clone(), agent_env);
+            }
+            let agent_env = &agent_envs[&call.identity];
+
+            info!(
+                log,
+                "{}. {} calls {}.{}",
+                index + 1,
+                call.identity,
+                call.canister,
+                call.method
+            );
+            let response = replay_call(agent_env, canister_id, call)
+                .await
+                .with_context(|| format!("Failed to replay call {}.", index + 1))?;
+            if !opts.no_check && response != call.response {
+                warn!(
+                    log,
+                    "The response differs from the recorded one.\n  recorded: {}\n  replayed: {}",
+                    display(&call.response),
+                    display(&response)
+                );
+                mismatches += 1;
+            }
+        }
+        if mismatches > 0 {
+            bail!(
+                "{} of {} response(s) differ from the recorded ones.",
+                mismatches,
+                scenario.calls.len()
+            );
+        }
+        info!(log, "Replayed {} call(s).", scenario.calls.len());
+        Ok(())
+    })
+}
+
+async fn replay_call(
+    env: &dyn Environment,
+    canister_id: Principal,
+    call: &RecordedCall,
+) -> DfxResult<Vec<u8>> {
+    let agent = env.get_agent();
+    let response = if call.query {
+        agent
+            .query(&canister_id, &call.method)
+            .with_arg(call.argument.clone())
+            .call()
+            .await?
+    } else {
+        agent
+            .update(&canister_id, &call.method)
+            .with_arg(call.argument.clone())
+            .call_and_wait()
+            .await?
+    };
+    Ok(response)
+}
+
+fn display(blob: &[u8]) -> String {
+    IDLArgs::from_bytes(blob)
+        .map(|args| args.to_string())
+        .unwrap_or_else(|_| hex::encode(blob))
+}
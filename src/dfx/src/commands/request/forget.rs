@@ -0,0 +1,37 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::requests::{load_pending_requests, save_pending_requests};
+use crate::util::clap::parsers::request_id_parser;
+use anyhow::bail;
+use clap::Parser;
+
+/// Stops tracking requests, e.g. ones whose reply is no longer needed.
+#[derive(Parser)]
+pub struct RequestForgetOpts {
+    /// The request id, as shown by `dfx canister call --async`.
+    #[arg(value_parser = request_id_parser, required_unless_present("all"))]
+    request_id: Option<String>,
+
+    /// Forgets all of the requests sent on the network.
+    #[arg(long, conflicts_with("request_id"))]
+    all: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: RequestForgetOpts) -> DfxResult {
+    let mut pending = load_pending_requests(env)?;
+    if opts.all {
+        pending.requests.clear();
+    } else if let Some(request_id) = &opts.request_id {
+        if pending
+            .requests
+            .remove(&request_id.to_ascii_lowercase())
+            .is_none()
+        {
+            bail!(
+                "Request {} is not tracked. See `dfx request list`.",
+                request_id
+            );
+        }
+    }
+    save_pending_requests(env, &pending)
+}
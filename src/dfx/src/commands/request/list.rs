@@ -0,0 +1,47 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::requests::load_pending_requests;
+use clap::Parser;
+use std::time::Duration;
+
+/// Lists the requests sent with `dfx canister call --async` on the network
+/// whose reply has not been shown yet.
+#[derive(Parser)]
+pub struct RequestListOpts {
+    /// Outputs the requests as JSON, by request id.
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn exec(env: &dyn Environment, opts: RequestListOpts) -> DfxResult {
+    let pending = load_pending_requests(env)?;
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&pending.requests)?);
+        return Ok(());
+    }
+    for (request_id, request) in &pending.requests {
+        let callee = match request.wallet_callee {
+            Some(callee) => format!("{} (through wallet {})", callee, request.canister_id),
+            None => request.canister_id.to_string(),
+        };
+        // Whole seconds are precise enough, and easier to read.
+        let age = Duration::from_secs(request.age().as_secs());
+        let expired = if request.can_retry() {
+            ", expired before it was received"
+        } else if request.is_expired() {
+            ", expired"
+        } else {
+            ""
+        };
+        println!(
+            "{} {}.{} sent {} ago by {}{}",
+            request_id,
+            callee,
+            request.method_name,
+            humantime::format_duration(age),
+            request.identity,
+            expired
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::lib::agent::create_agent_environment;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use clap::Parser;
+use tokio::runtime::Runtime;
+
+mod forget;
+mod list;
+mod retry;
+mod status;
+
+/// Tracks, resumes, and retries the requests sent with `dfx canister call --async`.
+#[derive(Parser)]
+#[command(name = "request")]
+pub struct RequestOpts {
+    #[command(flatten)]
+    network: NetworkOpt,
+
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Forget(forget::RequestForgetOpts),
+    List(list::RequestListOpts),
+    Retry(retry::RequestRetryOpts),
+    Status(status::RequestStatusOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: RequestOpts) -> DfxResult {
+    let agent_env = create_agent_environment(env, opts.network.to_network_name(env))?;
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(async {
+        match opts.subcmd {
+            SubCommand::Forget(v) => forget::exec(&agent_env, v),
+            SubCommand::List(v) => list::exec(&agent_env, v),
+            SubCommand::Retry(v) => retry::exec(&agent_env, v).await,
+            SubCommand::Status(v) => status::exec(&agent_env, v).await,
+        }
+    })
+}
@@ -0,0 +1,109 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::requests::{
+    ensure_sent_by_selected_identity, load_pending_requests, now_secs, request_id_to_text,
+    save_pending_requests, REQUEST_EXPIRY, STATUS_RETENTION,
+};
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::util::clap::parsers::request_id_parser;
+use anyhow::{bail, Context};
+use clap::Parser;
+use ic_agent::agent::RequestStatusResponse;
+use ic_agent::RequestId;
+use slog::warn;
+use std::str::FromStr;
+
+/// Sends a request again that expired before the IC received it, with the same method and argument.
+/// A request is only sent again if a status check after its expiry, while the IC still keeps the status
+/// of the requests it received, found its status unknown. Before the expiry, the IC may still receive the
+/// request, and later, the IC prunes the status of executed requests too.
+#[derive(Parser)]
+pub struct RequestRetryOpts {
+    /// The request id, as shown by `dfx canister call --async`.
+    #[arg(value_parser = request_id_parser)]
+    request_id: String,
+
+    /// Sends the request again although its status was not checked in time.
+    /// If the IC received it, the method is called twice.
+    #[arg(long)]
+    force: bool,
+}
+
+pub async fn exec(env: &dyn Environment, opts: RequestRetryOpts) -> DfxResult {
+    let log = env.get_logger();
+    let agent = env.get_agent();
+    fetch_root_key_if_needed(env).await?;
+
+    let request_id =
+        RequestId::from_str(&opts.request_id[2..]).context("Invalid argument: request_id")?;
+    let mut pending = load_pending_requests(env)?;
+    let request_key = request_id_to_text(&request_id);
+    let Some(request) = pending.requests.get_mut(&request_key) else {
+        bail!(
+            "Request {} is not tracked. See `dfx request list`.",
+            opts.request_id
+        );
+    };
+    ensure_sent_by_selected_identity(env, &request_id, request)?;
+    if request.wallet_callee.is_some() {
+        bail!("Requests proxied by a wallet cannot be retried. Call the canister again instead.");
+    }
+
+    match agent
+        .request_status_raw(&request_id, request.effective_canister_id)
+        .await
+        .context("Failed to fetch request status.")?
+    {
+        RequestStatusResponse::Unknown => {
+            request.record_unknown_status();
+            if !request.can_retry() {
+                if !request.is_expired() {
+                    bail!(
+                        "Request {} may still be received, until {} after it was sent. Retry it after that, within {} after it was sent.",
+                        opts.request_id,
+                        humantime::format_duration(REQUEST_EXPIRY),
+                        humantime::format_duration(STATUS_RETENTION)
+                    );
+                }
+                if !opts.force {
+                    bail!(
+                        "The status of request {} was not checked within {} after it was sent, so dfx cannot tell whether the IC received it: \
+                        after that, the IC also forgets the status of executed requests. \
+                        Call the canister again, or send the request again with --force if calling the method twice is safe.",
+                        opts.request_id,
+                        humantime::format_duration(STATUS_RETENTION)
+                    );
+                }
+                warn!(
+                    log,
+                    "Sending request {} again without knowing whether the IC received it. The method may be called twice.",
+                    opts.request_id
+                );
+            }
+        }
+        _ => bail!(
+            "Request {} was received by the IC. See its status with `dfx request status {}`.",
+            opts.request_id,
+            opts.request_id
+        ),
+    }
+
+    let mut request = pending.requests.remove(&request_key).unwrap();
+    let new_request_id = agent
+        .update(&request.canister_id, &request.method_name)
+        .with_effective_canister_id(request.effective_canister_id)
+        .with_arg(request.arg.clone())
+        .call()
+        .await
+        .context("Failed update call.")?;
+    request.sent_at = now_secs();
+    request.unknown_checked_at = None;
+    pending
+        .requests
+        .insert(request_id_to_text(&new_request_id), request);
+    save_pending_requests(env, &pending)?;
+
+    eprint!("Request ID: ");
+    println!("{}", request_id_to_text(&new_request_id));
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::{DfxError, DfxResult};
+use crate::lib::requests::{
+    add_pending_request, ensure_sent_by_selected_identity, load_pending_requests,
+    remove_pending_request, request_id_to_text, wait_for_reply, REQUEST_EXPIRY,
+};
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::util::clap::parsers::{duration_parser, request_id_parser};
+use crate::util::print_idl_blob;
+use anyhow::{bail, Context};
+use candid::Principal;
+use clap::Parser;
+use ic_agent::agent::RequestStatusResponse;
+use ic_agent::{AgentError, RequestId};
+use slog::info;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Shows the status of a request. Shows the reply, once the canister replied.
+/// The status is read from a certificate of the subnet, which dfx verifies.
+#[derive(Parser)]
+pub struct RequestStatusOpts {
+    /// The request id, as shown by `dfx canister call --async`.
+    #[arg(value_parser = request_id_parser)]
+    request_id: String,
+
+    /// The name or id of the canister the request was sent to.
+    /// Only needed for requests that were not sent with `dfx canister call --async` on this network.
+    #[arg(long)]
+    canister: Option<String>,
+
+    /// Waits for the canister to reply, and shows the reply.
+    #[arg(long)]
+    wait: bool,
+
+    /// With --wait, polls the status at this interval, e.g. 500ms or 2s,
+    /// instead of backing off exponentially.
+    #[arg(long, requires("wait"), value_parser = duration_parser)]
+    poll_interval: Option<Duration>,
+
+    /// Specifies the format for displaying the reply.
+    #[arg(long, value_parser = ["idl", "raw", "pp"])]
+    output: Option<String>,
+}
+
+pub async fn exec(env: &dyn Environment, opts: RequestStatusOpts) -> DfxResult {
+    let log = env.get_logger();
+    let agent = env.get_agent();
+    fetch_root_key_if_needed(env).await?;
+
+    let request_id =
+        RequestId::from_str(&opts.request_id[2..]).context("Invalid argument: request_id")?;
+    let pending = load_pending_requests(env)?
+        .requests
+        .remove(&request_id_to_text(&request_id));
+    if let Some(pending) = &pending {
+        ensure_sent_by_selected_identity(env, &request_id, pending)?;
+    }
+    let effective_canister_id = match (&opts.canister, &pending) {
+        (Some(canister), _) => match Principal::from_text(canister) {
+            Ok(canister_id) => canister_id,
+            Err(_) => env.get_canister_id_store()?.get(canister)?,
+        },
+        (None, Some(pending)) => pending.effective_canister_id,
+        (None, None) => bail!(
+            "Request {} was not sent with `dfx canister call --async` on this network. Specify the canister it was sent to with --canister.",
+            opts.request_id
+        ),
+    };
+    let output_type = opts.output.as_deref();
+
+    if opts.wait {
+        let blob =
+            wait_for_reply(env, &request_id, effective_canister_id, opts.poll_interval).await?;
+        remove_pending_request(env, &request_id)?;
+        print_idl_blob(&blob, output_type, &None)?;
+        return Ok(());
+    }
+
+    match agent
+        .request_status_raw(&request_id, effective_canister_id)
+        .await
+        .context("Failed to fetch request status.")?
+    {
+        RequestStatusResponse::Replied(reply) => {
+            remove_pending_request(env, &request_id)?;
+            print_idl_blob(&reply.arg, output_type, &None)?;
+        }
+        RequestStatusResponse::Rejected(response) => {
+            remove_pending_request(env, &request_id)?;
+            return Err(DfxError::new(AgentError::ReplicaError(response)));
+        }
+        RequestStatusResponse::Done => {
+            remove_pending_request(env, &request_id)?;
+            println!("done");
+            info!(
+                log,
+                "The request was completed, but its reply is no longer available."
+            );
+        }
+        RequestStatusResponse::Received => println!("received"),
+        RequestStatusResponse::Processing => println!("processing"),
+        RequestStatusResponse::Unknown => {
+            println!("unknown");
+            if let Some(mut pending) = pending {
+                pending.record_unknown_status();
+                if pending.can_retry() {
+                    add_pending_request(env, &request_id, pending)?;
+                    info!(
+                        log,
+                        "The request expired before the IC received it. Send it again with `dfx request retry {}`.",
+                        opts.request_id
+                    );
+                } else if !pending.is_expired() {
+                    info!(
+                        log,
+                        "The IC has not received the request yet. It may still be received, until {} after it was sent.",
+                        humantime::format_duration(REQUEST_EXPIRY)
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
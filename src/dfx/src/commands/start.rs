@@ -1,8 +1,10 @@
+use crate::actors::canister_logs::CanisterLogForwarder;
 use crate::actors::icx_proxy::signals::PortReadySubscribe;
 use crate::actors::icx_proxy::IcxProxyConfig;
 use crate::actors::{
-    start_btc_adapter_actor, start_canister_http_adapter_actor, start_icx_proxy_actor,
-    start_replica_actor, start_shutdown_controller,
+    resolve_scheduled_tasks, start_btc_adapter_actor, start_canister_http_adapter_actor,
+    start_icx_proxy_actor, start_replica_actor, start_scheduled_tasks_actor,
+    start_shutdown_controller,
 };
 use crate::config::dfx_version_str;
 use crate::error_invalid_argument;
@@ -34,6 +36,7 @@ use std::io::Read;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sysinfo::{Pid, System, SystemExt};
 use tokio::runtime::Runtime;
@@ -80,6 +83,11 @@ pub struct StartOpts {
     /// A list of domains that can be served. These are used for canister resolution [default: localhost]
     #[arg(long)]
     domain: Vec<String>,
+
+    /// Only show the output of this canister, given by name or id. Can be repeated.
+    /// By default, the output of all canisters is shown.
+    #[arg(long, value_name = "CANISTER")]
+    filter: Vec<String>,
 }
 
 // The frontend webserver is brought up by the bg process; thus, the fg process
@@ -146,6 +154,7 @@ pub fn exec(
         artificial_delay,
         use_old_metering,
         domain,
+        filter,
     }: StartOpts,
 ) -> DfxResult {
     if !background {
@@ -238,6 +247,7 @@ pub fn exec(
     })?;
 
     let replica_port_path = empty_writable_path(local_server_descriptor.replica_port_path())?;
+    let scheduled_tasks = resolve_scheduled_tasks(local_server_descriptor)?;
 
     if background {
         send_background()?;
@@ -292,6 +302,7 @@ pub fn exec(
         .unwrap_or_default();
 
     let proxy_domains = local_server_descriptor.proxy.domain.clone().into_vec();
+    let proxy_custom_domains = local_server_descriptor.proxy.custom_domains.clone();
 
     let replica_config = {
         let replica_config = ReplicaConfig::new(
@@ -333,6 +344,13 @@ pub fn exec(
     save_json_file(&previous_config_path, &effective_config)
         .context("Failed to write replica configuration")?;
 
+    let canister_log_forwarder = Arc::new(CanisterLogForwarder::new(
+        env.get_logger().clone(),
+        network_descriptor.clone(),
+        env.get_config(),
+        filter,
+    ));
+
     let network_descriptor = network_descriptor.clone();
 
     let system = actix::System::new();
@@ -348,6 +366,7 @@ pub fn exec(
                         btc_adapter_config.get_socket_path(),
                         shutdown_controller.clone(),
                         btc_adapter_pid_file_path,
+                        local_server_descriptor.btc_adapter_log_path(),
                     )
                 })
                 .transpose()?;
@@ -359,6 +378,7 @@ pub fn exec(
                         canister_http_adapter_config.get_socket_path(),
                         shutdown_controller.clone(),
                         canister_http_adapter_pid_file_path,
+                        local_server_descriptor.canister_http_adapter_log_path(),
                     )
                 })
                 .transpose()?;
@@ -370,15 +390,27 @@ pub fn exec(
                 shutdown_controller.clone(),
                 btc_adapter_ready_subscribe,
                 canister_http_adapter_ready_subscribe,
+                Some(canister_log_forwarder),
             )?;
             replica.recipient()
         };
 
+        if !scheduled_tasks.is_empty() {
+            start_scheduled_tasks_actor(
+                env,
+                scheduled_tasks,
+                port_ready_subscribe.clone(),
+                shutdown_controller.clone(),
+                local_server_descriptor.tasks_log_path(),
+            )?;
+        }
+
         let icx_proxy_config = IcxProxyConfig {
             bind: address_and_port,
             replica_urls: vec![], // will be determined after replica starts
             fetch_root_key: !network_descriptor.is_ic,
             domains: proxy_domains,
+            custom_domains: proxy_custom_domains,
             verbose: env.get_verbose_level() > 0,
         };
 
@@ -3,6 +3,7 @@ use crate::lib::error::DfxResult;
 use anyhow::bail;
 use clap::Parser;
 use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use slog::info;
 use std::time::Duration;
 use sysinfo::{Pid, Process, ProcessExt, Signal, System, SystemExt};
 
@@ -92,7 +93,10 @@ pub fn exec(env: &dyn Environment, _opts: StopOpts) -> DfxResult {
         }
     }
     if !found {
-        eprintln!("No local network replica found. Nothing to do.");
+        info!(
+            env.get_logger(),
+            "No local network replica found. Nothing to do."
+        );
     }
 
     Ok(())
@@ -0,0 +1,44 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::lib::subnet::get_subnet_info;
+use candid::Principal;
+use clap::Parser;
+
+/// Shows the number of nodes of a subnet, and the metrics it certifies: its number of canisters,
+/// the size of their state, the cycles they consumed and the update calls they executed.
+#[derive(Parser)]
+pub struct SubnetInfoOpts {
+    /// The id of the subnet, as shown by `dfx subnet of`.
+    subnet_id: Principal,
+
+    /// Outputs the information as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+pub async fn exec(env: &dyn Environment, opts: SubnetInfoOpts) -> DfxResult {
+    fetch_root_key_if_needed(env).await?;
+    let agent = env.get_agent();
+    let info = get_subnet_info(agent, opts.subnet_id).await?;
+
+    if opts.json {
+        let json = serde_json::json!({
+            "subnet_id": opts.subnet_id.to_text(),
+            "nodes": info.node_count,
+            "canisters": info.num_canisters,
+            "canister_state_bytes": info.canister_state_bytes,
+            "consumed_cycles_total": info.consumed_cycles_total.to_string(),
+            "update_transactions_total": info.update_transactions_total,
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        println!("Subnet: {}", opts.subnet_id);
+        println!("Nodes: {}", info.node_count);
+        println!("Canisters: {}", info.num_canisters);
+        println!("Canister state: {} Bytes", info.canister_state_bytes);
+        println!("Cycles consumed: {}", info.consumed_cycles_total);
+        println!("Update calls executed: {}", info.update_transactions_total);
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::lib::agent::create_anonymous_agent_environment;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::network::network_opt::NetworkOpt;
+use clap::Parser;
+use tokio::runtime::Runtime;
+
+mod info;
+mod of;
+
+/// Shows information about subnets.
+/// Unless specified otherwise with --network, subnets and canisters are looked up on the IC mainnet.
+#[derive(Parser)]
+#[command(name = "subnet")]
+pub struct SubnetOpts {
+    #[command(flatten)]
+    network: NetworkOpt,
+
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Info(info::SubnetInfoOpts),
+    Of(of::SubnetOfOpts),
+}
+
+pub fn exec(env: &dyn Environment, opts: SubnetOpts) -> DfxResult {
+    let network = opts
+        .network
+        .selected_network_name()
+        .or_else(|| Some("ic".to_string()));
+    let agent_env = create_anonymous_agent_environment(env, network)?;
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(async {
+        match opts.subcmd {
+            SubCommand::Info(v) => info::exec(&agent_env, v).await,
+            SubCommand::Of(v) => of::exec(&agent_env, v).await,
+        }
+    })
+}
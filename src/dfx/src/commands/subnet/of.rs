@@ -0,0 +1,28 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::retryable::retry_policy;
+use crate::lib::subnet::get_subnet_for_canister;
+use anyhow::bail;
+use candid::Principal;
+use clap::Parser;
+
+/// Shows the id of the subnet that hosts a canister on mainnet.
+#[derive(Parser)]
+pub struct SubnetOfOpts {
+    /// The name or id of the canister.
+    canister: String,
+}
+
+pub async fn exec(env: &dyn Environment, opts: SubnetOfOpts) -> DfxResult {
+    // The registry that knows the subnets only exists on mainnet.
+    if !env.get_network_descriptor().is_ic {
+        bail!("The subnet of a canister can only be looked up on mainnet.");
+    }
+    let canister_id = match Principal::from_text(&opts.canister) {
+        Ok(id) => id,
+        Err(_) => env.get_canister_id_store()?.get(&opts.canister)?,
+    };
+    let subnet = get_subnet_for_canister(env.get_agent(), retry_policy(env), canister_id).await?;
+    println!("{}", subnet);
+    Ok(())
+}
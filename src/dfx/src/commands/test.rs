@@ -0,0 +1,93 @@
+use crate::commands::proxy::canister_environment_variables;
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::{bail, Context};
+use clap::Parser;
+use dfx_core::config::cache::get_cache_root;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use slog::{info, warn};
+use std::path::Path;
+use std::process::Command;
+
+/// Starts an isolated local network, deploys the project to it, runs a test command, and stops the network.
+/// The network has its own temporary state and port, so a local network that is already running is not affected.
+#[derive(Parser)]
+pub struct TestOpts {
+    /// The test command, after `--`.
+    /// It runs with the canister ids of the project in CANISTER_ID_<name> environment variables.
+    #[arg(last = true, required = true)]
+    command: Vec<String>,
+}
+
+pub fn exec(env: &dyn Environment, opts: TestOpts) -> DfxResult {
+    let log = env.get_logger();
+    env.get_config_or_anyhow()?;
+
+    let state_dir = tempfile::tempdir().context("Failed to create a temporary directory.")?;
+    isolate_state(state_dir.path())?;
+
+    info!(log, "Starting an isolated local network...");
+    run_dfx(&["start", "--background", "--host", "127.0.0.1:0"])?;
+
+    let result = deploy_and_test(env, &opts.command);
+
+    info!(log, "Stopping the isolated local network...");
+    if let Err(err) = run_dfx(&["stop"]) {
+        warn!(log, "Failed to stop the isolated local network: {:#}", err);
+    }
+    result
+}
+
+/// Moves the canister ids and the network data of this command and of the dfx commands it runs
+/// into `state_dir`, while they keep using the cache of the user.
+fn isolate_state(state_dir: &Path) -> DfxResult {
+    if std::env::var_os("DFX_CACHE_ROOT").is_none() {
+        let cache_root = get_cache_root()?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&cache_root, state_dir.join("cache"))
+            .context("Failed to link the cache into the temporary directory.")?;
+        #[cfg(windows)]
+        std::env::set_var("DFX_CACHE_ROOT", &cache_root);
+    }
+    std::env::set_var("DFX_STATE_DIR", state_dir);
+    Ok(())
+}
+
+fn deploy_and_test(env: &dyn Environment, command: &[String]) -> DfxResult {
+    run_dfx(&["deploy", "--network", "local", "--yes"])?;
+
+    let network_descriptor = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        Some("local".to_string()),
+        None,
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+    let environment_variables = canister_environment_variables(env, &network_descriptor)?;
+
+    let (program, args) = command.split_first().unwrap();
+    info!(env.get_logger(), "Running '{}'...", command.join(" "));
+    let status = Command::new(program)
+        .args(args)
+        .envs(environment_variables)
+        .status()
+        .with_context(|| format!("Failed to run '{}'.", command.join(" ")))?;
+    if !status.success() {
+        bail!("The test command failed: {}", status);
+    }
+    Ok(())
+}
+
+/// Runs a dfx subcommand with this executable, so that the network and the deployment
+/// behave exactly as with the corresponding commands.
+fn run_dfx(args: &[&str]) -> DfxResult {
+    let exe = std::env::current_exe().context("Failed to get current executable.")?;
+    let status = Command::new(exe)
+        .args(args)
+        .status()
+        .with_context(|| format!("Failed to run 'dfx {}'.", args.join(" ")))?;
+    if !status.success() {
+        bail!("'dfx {}' failed: {}", args.join(" "), status);
+    }
+    Ok(())
+}
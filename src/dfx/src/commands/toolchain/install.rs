@@ -0,0 +1,18 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::toolchain;
+use clap::Parser;
+use semver::Version;
+use tokio::runtime::Runtime;
+
+/// Installs a version of dfx, so that projects that pin it in dfx.json can run it.
+#[derive(Parser)]
+pub struct ToolchainInstallOpts {
+    /// The version to install, for example 0.20.1.
+    version: Version,
+}
+
+pub fn exec(env: &dyn Environment, opts: ToolchainInstallOpts) -> DfxResult {
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(toolchain::install(env.get_logger(), &opts.version))
+}
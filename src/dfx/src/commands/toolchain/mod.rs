@@ -1,23 +1,28 @@
-use crate::lib::dfxvm::display_dfxvm_installation_instructions;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
-use anyhow::bail;
 use clap::Parser;
-use std::ffi::OsString;
 
-/// Manage the dfx toolchains (obsolete)
+mod install;
+mod r#use;
+
+/// Installs versions of dfx, and pins the version that a project uses.
+/// dfx forwards the commands of a project that pins another version to that version.
 #[derive(Parser)]
-#[command(name = "toolchain", disable_help_flag = true)]
+#[command(name = "toolchain")]
 pub struct ToolchainOpts {
-    #[arg(allow_hyphen_values = true)]
-    _params: Vec<OsString>,
+    #[command(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser)]
+enum SubCommand {
+    Install(install::ToolchainInstallOpts),
+    Use(r#use::ToolchainUseOpts),
 }
 
-pub fn exec(_env: &dyn Environment, _opts: ToolchainOpts) -> DfxResult {
-    println!("The toolchain command has been removed.");
-    println!("Please use the dfx version manager (dfxvm) to manage dfx versions.");
-    println!();
-    display_dfxvm_installation_instructions();
-    println!();
-    bail!("toolchain command removed");
+pub fn exec(env: &dyn Environment, opts: ToolchainOpts) -> DfxResult {
+    match opts.subcmd {
+        SubCommand::Install(v) => install::exec(env, v),
+        SubCommand::Use(v) => r#use::exec(env, v),
+    }
 }
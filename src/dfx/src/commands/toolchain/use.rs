@@ -0,0 +1,29 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::toolchain;
+use clap::Parser;
+use semver::Version;
+use serde_json::Value;
+use slog::info;
+use tokio::runtime::Runtime;
+
+/// Pins the version of dfx that the project uses, in the "dfx" field of dfx.json,
+/// and installs it if needed.
+#[derive(Parser)]
+pub struct ToolchainUseOpts {
+    /// The version to use, for example 0.20.1.
+    version: Version,
+}
+
+pub fn exec(env: &dyn Environment, opts: ToolchainUseOpts) -> DfxResult {
+    let mut config = env.get_config_or_anyhow()?.as_ref().clone();
+    let runtime = Runtime::new().expect("Unable to create a runtime");
+    runtime.block_on(toolchain::install(env.get_logger(), &opts.version))?;
+    config.set_json_value("dfx", Some(Value::String(opts.version.to_string())))?;
+    config.save()?;
+    info!(
+        env.get_logger(),
+        "The project now uses dfx {}.", opts.version
+    );
+    Ok(())
+}
@@ -1,9 +1,22 @@
 use crate::commands::wallet::get_wallet;
-use crate::lib::environment::Environment;
+use crate::lib::environment::{AgentEnvironment, Environment};
 use crate::lib::error::DfxResult;
+use crate::lib::identity::wallet::wallet_canister_id;
+use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util::{format_as_trillions, pretty_thousand_separators};
-use anyhow::Context;
+use anyhow::{anyhow, Context};
+use candid::Principal;
 use clap::Parser;
+use dfx_core::canister::build_wallet_canister;
+use dfx_core::config::model::network_descriptor::NetworkDescriptor;
+use dfx_core::identity::Identity as DfxIdentity;
+use dfx_core::identity::IdentityManager;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use dfx_core::util::expiry_duration;
+use futures::future::join_all;
+use slog::warn;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
 
 /// Get the cycle balance of the selected Identity's cycles wallet.
 #[derive(Parser)]
@@ -11,6 +24,10 @@ pub struct WalletBalanceOpts {
     /// Get balance raw value (without upscaling to trillions of cycles).
     #[arg(long)]
     precise: bool,
+
+    /// Get the balances of the wallets of all identities on all configured networks.
+    #[arg(long)]
+    pub(crate) all_networks: bool,
 }
 
 pub async fn exec(env: &dyn Environment, opts: WalletBalanceOpts) -> DfxResult {
@@ -20,14 +37,144 @@ pub async fn exec(env: &dyn Environment, opts: WalletBalanceOpts) -> DfxResult {
         .await
         .context("Failed to fetch wallet balance.")?;
 
-    if opts.precise {
-        println!("{} cycles.", balance.amount);
-    } else {
-        println!(
-            "{} TC (trillion cycles).",
-            pretty_thousand_separators(format_as_trillions(balance.amount))
+    println!("{}", format_balance(balance.amount, opts.precise));
+
+    Ok(())
+}
+
+/// Reports the balance of every wallet configured for any identity on any network.
+/// Networks that cannot be reached are reported without failing the others.
+pub async fn exec_all_networks(env: &dyn Environment, opts: WalletBalanceOpts) -> DfxResult {
+    let log = env.get_logger();
+    let identity_names = env.new_identity_manager()?.get_identity_names(log)?;
+
+    let mut network_names: BTreeSet<String> = ["local", "ic"].map(String::from).into();
+    network_names.extend(
+        env.get_networks_config()
+            .get_interface()
+            .networks
+            .keys()
+            .cloned(),
+    );
+    if let Some(config) = env.get_config() {
+        network_names.extend(
+            config
+                .get_config()
+                .networks
+                .iter()
+                .flat_map(|n| n.keys().cloned()),
         );
     }
 
+    // Identities are instantiated one after another, because this may prompt for a password.
+    // Each identity is instantiated only once and then shared by all networks.
+    let mut identity_manager = env.new_identity_manager()?;
+    let mut identities = BTreeMap::new();
+    let mut wallets = vec![];
+    for network_name in network_names {
+        let network = match create_network_descriptor(
+            env.get_config(),
+            env.get_networks_config(),
+            Some(network_name.clone()),
+            None,
+            LocalBindDetermination::ApplyRunningWebserverPort,
+        ) {
+            Ok(network) => network,
+            Err(err) => {
+                warn!(log, "Skipping network '{}': {}", network_name, err);
+                continue;
+            }
+        };
+        for identity_name in &identity_names {
+            let Some(wallet_id) = wallet_canister_id(&network, identity_name)? else {
+                continue;
+            };
+            let agent_env = agent_environment(
+                env,
+                &mut identity_manager,
+                &mut identities,
+                &network,
+                identity_name,
+            );
+            wallets.push((
+                network_name.clone(),
+                identity_name.clone(),
+                wallet_id,
+                agent_env,
+            ));
+        }
+    }
+
+    let balances = join_all(
+        wallets
+            .iter()
+            .map(|(_, _, wallet_id, agent_env)| async move {
+                let agent_env = agent_env.as_ref().map_err(|err| anyhow!("{:#}", err))?;
+                wallet_balance(agent_env, *wallet_id).await
+            }),
+    )
+    .await;
+
+    if wallets.is_empty() {
+        println!("No wallets are configured.");
+    }
+    for ((network_name, identity_name, wallet_id, _), balance) in wallets.iter().zip(balances) {
+        let balance = match balance {
+            Ok(balance) => format_balance(balance, opts.precise),
+            Err(err) => format!("Failed to fetch wallet balance: {:#}", err),
+        };
+        println!(
+            "{} {} {}: {}",
+            network_name, identity_name, wallet_id, balance
+        );
+    }
     Ok(())
 }
+
+fn agent_environment<'a>(
+    env: &'a dyn Environment,
+    identity_manager: &mut IdentityManager,
+    identities: &mut BTreeMap<String, Arc<DfxIdentity>>,
+    network: &NetworkDescriptor,
+    identity_name: &str,
+) -> DfxResult<AgentEnvironment<'a>> {
+    identity_manager.require_network_allowed(identity_name, &network.name)?;
+    let identity = match identities.get(identity_name) {
+        Some(identity) => identity.clone(),
+        None => {
+            let identity: Arc<DfxIdentity> = identity_manager
+                .instantiate_identity_from_name(identity_name, env.get_logger())?
+                .into();
+            identities.insert(identity_name.to_string(), identity.clone());
+            identity
+        }
+    };
+    AgentEnvironment::with_identity(
+        env,
+        network.clone(),
+        expiry_duration(),
+        identity_manager.clone(),
+        identity,
+    )
+}
+
+async fn wallet_balance(env: &dyn Environment, wallet_id: Principal) -> DfxResult<u128> {
+    fetch_root_key_if_needed(env).await?;
+    let balance = build_wallet_canister(wallet_id, env.get_agent())
+        .await?
+        .wallet_balance()
+        .await
+        .context("Failed to fetch wallet balance.")?;
+    Ok(balance.amount)
+}
+
+fn format_balance(amount: u128, precise: bool) -> String {
+    if precise {
+        format!("{} cycles.", amount)
+    } else {
+        format!(
+            "{} TC (trillion cycles).",
+            pretty_thousand_separators(format_as_trillions(amount))
+        )
+    }
+}
@@ -4,12 +4,17 @@ use crate::lib::error::DfxResult;
 use anyhow::Context;
 use candid::Principal;
 use clap::Parser;
+use dfx_core::cli::ask_for_consent;
 
 /// Deauthorize a wallet custodian.
 #[derive(Parser)]
 pub struct DeauthorizeOpts {
     /// Principal of the custodian to deauthorize.
     custodian: String,
+
+    /// Skips the confirmation prompt when removing the selected identity.
+    #[arg(long, short)]
+    yes: bool,
 }
 
 pub async fn exec(env: &dyn Environment, opts: DeauthorizeOpts) -> DfxResult {
@@ -19,6 +24,9 @@ pub async fn exec(env: &dyn Environment, opts: DeauthorizeOpts) -> DfxResult {
             opts.custodian
         )
     })?;
+    if !opts.yes && env.get_selected_identity_principal() == Some(custodian) {
+        ask_for_consent("You are trying to deauthorize yourself as a custodian of the wallet. Unless you are also a controller, you will no longer be able to use it.")?;
+    }
     wallet_update(env, "deauthorize", custodian).await?;
     println!("Deauthorized {} as a custodian.", opts.custodian);
     Ok(())
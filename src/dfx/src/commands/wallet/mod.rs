@@ -4,7 +4,7 @@ use crate::lib::error::DfxResult;
 use crate::lib::identity::wallet::get_or_create_wallet_canister;
 use crate::lib::network::network_opt::NetworkOpt;
 use crate::lib::root_key::fetch_root_key_if_needed;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use candid::utils::ArgumentDecoder;
 use candid::CandidType;
 use clap::Parser;
@@ -25,6 +25,7 @@ mod redeem_faucet_coupon;
 mod remove_controller;
 mod send;
 mod set_name;
+mod transfer_ownership;
 mod upgrade;
 
 /// Helper commands to manage the user's cycles wallet.
@@ -52,14 +53,25 @@ enum SubCommand {
     RemoveController(remove_controller::RemoveControllerOpts),
     Send(send::SendOpts),
     SetName(set_name::SetNameOpts),
+    TransferOwnership(transfer_ownership::TransferOwnershipOpts),
     Upgrade(upgrade::UpgradeOpts),
 }
 
 pub fn exec(env: &dyn Environment, opts: WalletOpts) -> DfxResult {
-    let agent_env = create_agent_environment(env, opts.network.to_network_name())?;
     let runtime = Runtime::new().expect("Unable to create a runtime");
+    // Balances across networks use an agent per network and identity.
+    let subcmd = match opts.subcmd {
+        SubCommand::Balance(v) if v.all_networks => {
+            if opts.network.selected_network_name().is_some() {
+                bail!("--all-networks cannot be combined with a network selection.");
+            }
+            return runtime.block_on(balance::exec_all_networks(env, v));
+        }
+        subcmd => subcmd,
+    };
+    let agent_env = create_agent_environment(env, opts.network.to_network_name(env))?;
     runtime.block_on(async {
-        match opts.subcmd {
+        match subcmd {
             SubCommand::Addresses(v) => list_addresses::exec(&agent_env, v).await,
             SubCommand::AddController(v) => add_controller::exec(&agent_env, v).await,
             SubCommand::Authorize(v) => authorize::exec(&agent_env, v).await,
@@ -72,6 +84,7 @@ pub fn exec(env: &dyn Environment, opts: WalletOpts) -> DfxResult {
             SubCommand::RemoveController(v) => remove_controller::exec(&agent_env, v).await,
             SubCommand::Send(v) => send::exec(&agent_env, v).await,
             SubCommand::SetName(v) => set_name::exec(&agent_env, v).await,
+            SubCommand::TransferOwnership(v) => transfer_ownership::exec(&agent_env, v).await,
             SubCommand::Upgrade(v) => upgrade::exec(&agent_env, v).await,
         }
     })
@@ -4,12 +4,17 @@ use crate::lib::error::DfxResult;
 use anyhow::Context;
 use candid::Principal;
 use clap::Parser;
+use dfx_core::cli::ask_for_consent;
 
 /// Remove a wallet controller.
 #[derive(Parser)]
 pub struct RemoveControllerOpts {
     /// Principal of the controller to remove.
     controller: String,
+
+    /// Skips the confirmation prompt when removing the selected identity.
+    #[arg(long, short)]
+    yes: bool,
 }
 
 pub async fn exec(env: &dyn Environment, opts: RemoveControllerOpts) -> DfxResult {
@@ -19,6 +24,9 @@ pub async fn exec(env: &dyn Environment, opts: RemoveControllerOpts) -> DfxResul
             opts.controller
         )
     })?;
+    if !opts.yes && env.get_selected_identity_principal() == Some(controller) {
+        ask_for_consent("You are trying to remove yourself as a controller of the wallet. You will no longer be able to use or upgrade it.")?;
+    }
     wallet_update(env, "remove_controller", controller).await?;
     println!("Removed {} as a controller.", controller);
     Ok(())
@@ -0,0 +1,87 @@
+use crate::commands::wallet::{get_wallet, wallet_query, wallet_update};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::ic_attributes::CanisterSettings;
+use crate::lib::identity::wallet::remove_wallet_id;
+use crate::lib::operations::canister::{get_canister_status, update_settings};
+use anyhow::Context;
+use candid::Principal;
+use clap::Parser;
+use dfx_core::cli::ask_for_consent;
+use dfx_core::identity::CallSender;
+use slog::{info, warn};
+
+/// Transfer the wallet to another principal.
+/// The new owner becomes a controller of the wallet and of the wallet canister,
+/// and the selected identity stops being a controller and custodian of either.
+#[derive(Parser)]
+pub struct TransferOwnershipOpts {
+    /// Principal of the new owner.
+    new_owner: String,
+
+    /// Skips the confirmation prompt.
+    #[arg(long, short)]
+    yes: bool,
+}
+
+pub async fn exec(env: &dyn Environment, opts: TransferOwnershipOpts) -> DfxResult {
+    let new_owner = Principal::from_text(&opts.new_owner).with_context(|| {
+        format!(
+            "Failed to parse {:?} as the principal of the new owner.",
+            opts.new_owner
+        )
+    })?;
+    let log = env.get_logger();
+    let me = env
+        .get_selected_identity_principal()
+        .expect("Selected identity not instantiated.");
+    let identity_name = env
+        .get_selected_identity()
+        .expect("No selected identity.")
+        .to_string();
+    let wallet_id = *get_wallet(env).await?.canister_id_();
+    if !opts.yes {
+        ask_for_consent(&format!(
+            "This transfers the wallet {wallet_id} to {new_owner}. \
+            The selected identity ({identity_name}, {me}) will no longer be able to use or upgrade it."
+        ))?;
+    }
+
+    wallet_update::<_, ()>(env, "add_controller", new_owner).await?;
+    info!(log, "Added {new_owner} as a controller of the wallet.");
+
+    let status = get_canister_status(env, wallet_id, &CallSender::SelectedId).await;
+    match status {
+        Ok(status) if status.settings.controllers.contains(&me) => {
+            let mut controllers = status.settings.controllers;
+            controllers.retain(|controller| *controller != me);
+            if !controllers.contains(&new_owner) {
+                controllers.push(new_owner);
+            }
+            let settings = CanisterSettings {
+                controllers: Some(controllers),
+                ..Default::default()
+            };
+            update_settings(env, wallet_id, settings, &CallSender::SelectedId).await?;
+            info!(
+                log,
+                "Replaced {me} with {new_owner} as a controller of the wallet canister."
+            );
+        }
+        _ => warn!(
+            log,
+            "The selected identity is not a controller of the wallet canister, so its controllers were not changed."
+        ),
+    }
+
+    let (custodians,): (Vec<Principal>,) = wallet_query(env, "get_custodians", ()).await?;
+    if custodians.contains(&me) {
+        wallet_update::<_, ()>(env, "deauthorize", me).await?;
+    }
+    wallet_update::<_, ()>(env, "remove_controller", me).await?;
+    info!(log, "Removed {me} as a controller of the wallet.");
+
+    remove_wallet_id(env.get_network_descriptor(), &identity_name)?;
+    println!("Transferred the wallet {wallet_id} to {new_owner}.");
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Copies the artifacts of built canisters to the output directory configured with `output_dir` in dfx.json
+//! or `dfx build --output-dir`, for release tooling.
+//! Unlike the layout of `.dfx`, the layout of the output directory is stable across dfx versions:
+//! ```text
+//! <dir>/<canister name>.wasm (or .wasm.gz)
+//! <dir>/<canister name>.did
+//! <dir>/metadata.json
+//! ```
+//! Changes to the layout bump `LAYOUT_VERSION`.
+use crate::config::dfx_version_str;
+use crate::lib::builders::BuildConfig;
+use crate::lib::canister_info::CanisterInfo;
+use crate::lib::error::DfxResult;
+use candid::Principal;
+use dfx_core::config::model::dfinity::Profile;
+use dfx_core::json::save_json_file;
+use fn_error_context::context;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+const LAYOUT_VERSION: u32 = 1;
+const METADATA_FILE: &str = "metadata.json";
+
+/// Describes the artifacts of a canister in `metadata.json`.
+#[derive(Serialize)]
+pub struct ArtifactsMetadata {
+    pub layout_version: u32,
+    pub name: String,
+    /// The type of the canister in dfx.json, e.g. "motoko".
+    #[serde(rename = "type")]
+    pub canister_type: String,
+    /// The file name of the Wasm module, relative to the output directory.
+    pub wasm: String,
+    /// The SHA-256 of the Wasm module, in hex, as `dfx canister info` reports it once installed.
+    pub wasm_sha256: String,
+    /// The file name of the Candid interface, relative to the output directory.
+    pub candid: String,
+    /// The id the canister was built with, if it has one on the network.
+    pub canister_id: Option<Principal>,
+    pub network: String,
+    pub profile: Profile,
+    pub dfx_version: String,
+}
+
+/// Copies the artifacts of a built canister to `dir`, replacing the ones of a previous build.
+#[context("Failed to copy the artifacts of canister {} to {}.", info.get_name(), dir.display())]
+pub fn copy_artifacts(info: &CanisterInfo, build_config: &BuildConfig, dir: &Path) -> DfxResult {
+    dfx_core::fs::create_dir_all(dir)?;
+    let name = info.get_name();
+
+    let build_wasm_path = info.get_build_wasm_path();
+    let wasm = build_wasm_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("{name}.wasm"));
+    // A previous build may have had the other extension, e.g. before gzip was turned on.
+    for stale in [format!("{name}.wasm"), format!("{name}.wasm.gz")] {
+        if stale != wasm && dir.join(&stale).exists() {
+            dfx_core::fs::remove_file(&dir.join(stale))?;
+        }
+    }
+    let wasm_module = dfx_core::fs::read(&build_wasm_path)?;
+    dfx_core::fs::write(dir.join(&wasm), &wasm_module)?;
+
+    // The constructor interface has the init type too, which installing the canister needs.
+    let candid = format!("{name}.did");
+    let idl_path = if info.get_constructor_idl_path().exists() {
+        info.get_constructor_idl_path()
+    } else {
+        info.get_service_idl_path()
+    };
+    dfx_core::fs::copy(&idl_path, &dir.join(&candid))?;
+
+    let metadata = ArtifactsMetadata {
+        layout_version: LAYOUT_VERSION,
+        name: name.to_string(),
+        canister_type: info.get_type_specific_properties().name().to_string(),
+        wasm,
+        wasm_sha256: hex::encode(Sha256::digest(&wasm_module)),
+        candid,
+        canister_id: info.get_canister_id().ok(),
+        network: build_config.network_name.clone(),
+        profile: build_config.profile(),
+        dfx_version: dfx_version_str().to_string(),
+    };
+    save_json_file(&dir.join(METADATA_FILE), &metadata)?;
+    Ok(())
+}
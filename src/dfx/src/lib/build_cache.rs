@@ -0,0 +1,260 @@
+//! A remote cache of built canisters, configured with `build_cache` in dfx.json.
+//! Only canisters with `build_inputs` are cached. A build is keyed by the hash of everything it depends on:
+//! the dfx version, the network and build profile, the configuration of the canister,
+//! the canister ids it is built with, and the contents of its build inputs.
+use crate::config::dfx_version_str;
+use crate::lib::builders::BuildConfig;
+use crate::lib::canister_info::CanisterInfo;
+use crate::lib::error::DfxResult;
+use crate::lib::models::canister::{Canister, CanisterPool};
+use anyhow::{bail, Context};
+use dfx_core::config::model::dfinity::Config;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use fn_error_context::context;
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use slog::{info, warn, Logger};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use url::Url;
+use walkdir::WalkDir;
+
+/// Sent as a bearer token to the cache, if set.
+const BUILD_CACHE_TOKEN_VAR: &str = "DFX_BUILD_CACHE_TOKEN";
+
+pub struct BuildCache {
+    url: Url,
+    read_only: bool,
+    token: Option<String>,
+    client: reqwest::Client,
+    /// The configuration of each canister in dfx.json, as JSON.
+    canister_configs: BTreeMap<String, Vec<u8>>,
+}
+
+impl BuildCache {
+    #[context("Failed to set up the build cache.")]
+    pub fn from_config(config: &Config) -> DfxResult<Option<Self>> {
+        let config_interface = config.get_config();
+        let Some(build_cache) = &config_interface.build_cache else {
+            return Ok(None);
+        };
+        // A trailing slash makes `join` append the key to the path instead of replacing its last segment.
+        let url = if build_cache.url.ends_with('/') {
+            build_cache.url.clone()
+        } else {
+            format!("{}/", build_cache.url)
+        };
+        let url = Url::parse(&url)
+            .with_context(|| format!("Invalid build cache url '{}'.", build_cache.url))?;
+        let mut canister_configs = BTreeMap::new();
+        for (name, canister_config) in config_interface.canisters.iter().flatten() {
+            canister_configs.insert(name.clone(), serde_json::to_vec(canister_config)?);
+        }
+        Ok(Some(BuildCache {
+            url,
+            read_only: build_cache.read_only,
+            token: std::env::var(BUILD_CACHE_TOKEN_VAR).ok(),
+            client: reqwest::Client::builder()
+                .use_rustls_tls()
+                .build()
+                .context("Could not create HTTP client.")?,
+            canister_configs,
+        }))
+    }
+
+    /// The key of the build of a canister, or None if the canister has no build inputs.
+    #[context("Failed to compute the build cache key of canister '{}'.", canister.get_name())]
+    pub fn cache_key(
+        &self,
+        pool: &CanisterPool,
+        canister: &Canister,
+        build_config: &BuildConfig,
+    ) -> DfxResult<Option<String>> {
+        let info = canister.get_info();
+        if info.get_build_inputs().is_empty() {
+            return Ok(None);
+        }
+        let mut hasher = Sha256::new();
+        update_field(&mut hasher, dfx_version_str().as_bytes());
+        update_field(&mut hasher, build_config.network_name.as_bytes());
+        update_field(
+            &mut hasher,
+            format!("{:?}", build_config.profile()).as_bytes(),
+        );
+        if let Some(named_profile) = build_config.named_profile() {
+            update_field(&mut hasher, &serde_json::to_vec(named_profile)?);
+        }
+        update_field(&mut hasher, info.get_name().as_bytes());
+        if let Some(canister_config) = self.canister_configs.get(info.get_name()) {
+            update_field(&mut hasher, canister_config);
+        }
+        update_field(&mut hasher, canister.canister_id().as_slice());
+        // The ids of the dependencies are compiled into the canister through environment variables.
+        for dependency in info.get_dependencies() {
+            if let Some(dependency) = pool.get_first_canister_with_name(dependency) {
+                update_field(&mut hasher, dependency.get_name().as_bytes());
+                update_field(&mut hasher, dependency.canister_id().as_slice());
+            }
+        }
+        for input in info.get_build_inputs() {
+            hash_build_input(&mut hasher, info.get_workspace_root(), input)?;
+        }
+        Ok(Some(hex::encode(hasher.finalize())))
+    }
+
+    /// Restores the build of a canister from the cache. Returns false if the cache does not have it.
+    /// Errors of the cache are only warned about, so that the canister is built instead.
+    pub async fn restore(&self, logger: &Logger, key: &str, info: &CanisterInfo) -> bool {
+        match self.download(key, info).await {
+            Ok(found) => found,
+            Err(err) => {
+                warn!(
+                    logger,
+                    "Failed to restore canister {} from the build cache: {:#}",
+                    info.get_name(),
+                    err
+                );
+                false
+            }
+        }
+    }
+
+    /// Uploads the build of a canister to the cache, unless the cache is read-only.
+    /// Errors of the cache are only warned about, since the build itself succeeded.
+    pub async fn store(&self, logger: &Logger, key: &str, info: &CanisterInfo) {
+        if self.read_only {
+            return;
+        }
+        match self.upload(key, info).await {
+            Ok(()) => info!(
+                logger,
+                "Uploaded canister {} to the build cache.",
+                info.get_name()
+            ),
+            Err(err) => warn!(
+                logger,
+                "Failed to upload canister {} to the build cache: {:#}",
+                info.get_name(),
+                err
+            ),
+        }
+    }
+
+    fn artifact_url(&self, key: &str) -> DfxResult<Url> {
+        Ok(self.url.join(&format!("{key}.tar.gz"))?)
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn download(&self, key: &str, info: &CanisterInfo) -> DfxResult<bool> {
+        let url = self.artifact_url(key)?;
+        let response = self.authorize(self.client.get(url.clone())).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        if !response.status().is_success() {
+            bail!("{} responded with {}.", url, response.status());
+        }
+        let tarball = response.bytes().await?;
+        let output_root = info.get_output_root();
+        dfx_core::fs::create_dir_all(output_root)?;
+        tar::Archive::new(GzDecoder::new(Cursor::new(tarball)))
+            .unpack(output_root)
+            .with_context(|| format!("Failed to unpack {} to {}.", url, output_root.display()))?;
+        Ok(true)
+    }
+
+    async fn upload(&self, key: &str, info: &CanisterInfo) -> DfxResult {
+        let url = self.artifact_url(key)?;
+        let output_root = info.get_output_root();
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for path in artifact_paths(info) {
+            if path.exists() {
+                let name = path.strip_prefix(output_root)?;
+                builder
+                    .append_path_with_name(&path, name)
+                    .with_context(|| format!("Failed to add {} to the tarball.", path.display()))?;
+            }
+        }
+        let tarball = builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .context("Failed to compress the tarball.")?;
+        let response = self
+            .authorize(self.client.put(url.clone()))
+            .body(tarball)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            bail!("{} responded with {}.", url, response.status());
+        }
+        Ok(())
+    }
+}
+
+/// The files that a build leaves in the output directory of the canister, which are all that installing it needs.
+fn artifact_paths(info: &CanisterInfo) -> Vec<PathBuf> {
+    vec![
+        info.get_build_wasm_path(),
+        info.get_constructor_idl_path(),
+        info.get_service_idl_path(),
+        info.get_init_args_txt_path(),
+        info.get_index_js_path(),
+    ]
+}
+
+/// Hashes the length of a field before it, so that the boundaries between fields are unambiguous.
+fn update_field(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// Hashes the path and contents of a file, or of every file in a directory, in a stable order.
+#[context("Failed to hash build input {}.", input.display())]
+fn hash_build_input(hasher: &mut Sha256, workspace_root: &Path, input: &Path) -> DfxResult {
+    let path = workspace_root.join(input);
+    if !path.exists() {
+        bail!("Build input {} does not exist.", path.display());
+    }
+    for entry in WalkDir::new(&path).sort_by_file_name() {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            let relative = entry.path().strip_prefix(workspace_root)?;
+            update_field(hasher, relative.to_string_lossy().as_bytes());
+            update_field(hasher, &dfx_core::fs::read(entry.path())?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_dir(root: &Path) -> String {
+        let mut hasher = Sha256::new();
+        hash_build_input(&mut hasher, root, Path::new("src")).unwrap();
+        hex::encode(hasher.finalize())
+    }
+
+    #[test]
+    fn build_input_hash_depends_on_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.path().join("src/nested/mod.rs"), "").unwrap();
+        let before = hash_dir(dir.path());
+        assert_eq!(before, hash_dir(dir.path()));
+
+        std::fs::write(dir.path().join("src/nested/mod.rs"), "fn b() {}").unwrap();
+        assert_ne!(before, hash_dir(dir.path()));
+    }
+}
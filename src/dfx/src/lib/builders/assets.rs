@@ -221,7 +221,7 @@ fn build_frontend(
                 .with_context(|| format!("Cannot parse command '{}'.", command))?;
             // No commands, noop.
             if !args.is_empty() {
-                super::run_command(args, &vars, project_root)
+                super::run_command(args, &vars, project_root, None)
                     .with_context(|| format!("Failed to run {}.", command))?;
             }
         }
@@ -1,5 +1,6 @@
 use crate::lib::builders::{
-    BuildConfig, BuildOutput, CanisterBuilder, IdlBuildOutput, WasmBuildOutput,
+    interpolate_canister_ids, BuildConfig, BuildOutput, CanisterBuilder, IdlBuildOutput,
+    WasmBuildOutput,
 };
 use crate::lib::canister_info::custom::CustomCanisterInfo;
 use crate::lib::canister_info::CanisterInfo;
@@ -52,7 +53,11 @@ impl CustomBuilderExtra {
         let wasm = info.get_output_wasm_path().to_owned();
         let input_candid_url = info.get_input_candid_url().to_owned();
         let candid = info.get_output_idl_path().to_owned();
-        let build = info.get_build_tasks().to_owned();
+        let build = info
+            .get_build_tasks()
+            .iter()
+            .map(|command| interpolate_canister_ids(command, pool))
+            .collect::<DfxResult<Vec<_>>>()?;
 
         Ok(CustomBuilderExtra {
             dependencies,
@@ -132,8 +137,13 @@ impl CanisterBuilder for CustomBuilder {
                 .with_context(|| format!("Cannot parse command '{}'.", command))?;
             // No commands, noop.
             if !args.is_empty() {
-                super::run_command(args, &vars, info.get_workspace_root())
-                    .with_context(|| format!("Failed to run {}.", command))?;
+                super::run_command(
+                    args,
+                    &vars,
+                    info.get_workspace_root(),
+                    info.get_build_container(),
+                )
+                .with_context(|| format!("Failed to run {}.", command))?;
             }
         }
 
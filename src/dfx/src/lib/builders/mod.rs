@@ -1,18 +1,21 @@
 use crate::config::dfx_version_str;
 use crate::lib::canister_info::CanisterInfo;
+use crate::lib::declarations::binding_emitter;
+use crate::lib::declarations::docs::{write_docs, DocFormat};
 use crate::lib::environment::Environment;
 use crate::lib::error::{BuildError, DfxError, DfxResult};
 use crate::lib::models::canister::CanisterPool;
 use anyhow::{anyhow, bail, Context};
 use candid::Principal as CanisterId;
 use candid_parser::utils::CandidSource;
-use dfx_core::config::model::dfinity::{Config, Profile};
+use dfx_core::config::interpolation::interpolate;
+use dfx_core::config::model::dfinity::{BuildContainer, Config, ConfigBuildProfile, Profile};
 use dfx_core::network::provider::get_network_context;
 use dfx_core::util;
 use fn_error_context::context;
 use handlebars::Handlebars;
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsStr;
 use std::fmt::Write;
 use std::fs;
@@ -125,6 +128,8 @@ pub trait CanisterBuilder {
             })?;
         }
 
+        write_declarations_env_file(info, pool, &config.network_name)?;
+
         let bindings = info
             .get_declarations_config()
             .bindings
@@ -189,17 +194,31 @@ pub trait CanisterBuilder {
             compile_handlebars_files("js", info, generate_output_dir)?;
         }
 
-        // Motoko
-        if bindings.contains(&"mo".to_string()) {
-            let output_mo_path = generate_output_dir
-                .join(info.get_name())
-                .with_extension("mo");
-            let content =
-                ensure_trailing_newline(candid_parser::bindings::motoko::compile(&env, &ty));
-            std::fs::write(&output_mo_path, content).with_context(|| {
-                format!("Failed to write to {}.", output_mo_path.to_string_lossy())
-            })?;
-            eprintln!("  {}", &output_mo_path.display());
+        // Single-file bindings: Motoko, Rust, Python, Kotlin
+        for binding in bindings {
+            if let Some(emitter) = binding_emitter(binding) {
+                let output_path = generate_output_dir
+                    .join(info.get_name())
+                    .with_extension(emitter.extension());
+                let content = ensure_trailing_newline(emitter.emit(info.get_name(), &env, &ty));
+                std::fs::write(&output_path, content).with_context(|| {
+                    format!("Failed to write to {}.", output_path.to_string_lossy())
+                })?;
+                eprintln!("  {}", &output_path.display());
+            }
+        }
+
+        // Documentation of the interface, from the Candid file with its comments
+        for binding in bindings {
+            if let Some(format) = DocFormat::from_binding(binding) {
+                let output_path = write_docs(
+                    info.get_name(),
+                    format,
+                    &generated_idl_path,
+                    generate_output_dir,
+                )?;
+                eprintln!("  {}", &output_path.display());
+            }
         }
 
         // Candid, delete if not required
@@ -323,22 +342,34 @@ fn ensure_trailing_newline(s: String) -> String {
     }
 }
 
-pub fn run_command(args: Vec<String>, vars: &[Env<'_>], cwd: &Path) -> DfxResult<()> {
+pub fn run_command(
+    args: Vec<String>,
+    vars: &[Env<'_>],
+    cwd: &Path,
+    build_container: Option<&BuildContainer>,
+) -> DfxResult<()> {
     let (command_name, arguments) = args.split_first().unwrap();
-    let canonicalized = dfx_core::fs::canonicalize(&cwd.join(command_name))
-        .or_else(|_| which::which(command_name))
-        .map_err(|_| anyhow!("Cannot find command or file {command_name}"))?;
-    let mut cmd = Command::new(canonicalized);
+    let mut cmd = if build_container.is_some() {
+        // The command is looked up in the container.
+        Command::new(command_name)
+    } else {
+        let canonicalized = dfx_core::fs::canonicalize(&cwd.join(command_name))
+            .or_else(|_| which::which(command_name))
+            .map_err(|_| anyhow!("Cannot find command or file {command_name}"))?;
+        Command::new(canonicalized)
+    };
 
-    cmd.args(arguments)
-        .current_dir(cwd)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit());
+    cmd.args(arguments).current_dir(cwd);
 
     for (key, value) in vars {
         cmd.env(key.as_ref(), value);
     }
 
+    if let Some(build_container) = build_container {
+        cmd = in_build_container(&cmd, build_container, cwd);
+    }
+    cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+
     let output = cmd
         .output()
         .with_context(|| format!("Error executing custom build step {cmd:#?}"))?;
@@ -351,6 +382,90 @@ pub fn run_command(args: Vec<String>, vars: &[Env<'_>], cwd: &Path) -> DfxResult
     }
 }
 
+/// Wraps a build command so that it runs in the build container of a canister, with `docker run`.
+/// The project and the files outside of it that the command refers to (read-only) are mounted at the same paths
+/// in the container, so that the paths in the command stay valid. The program is looked up in the container,
+/// even if the command refers to it by its path on the host.
+pub fn in_build_container(
+    cmd: &Command,
+    build_container: &BuildContainer,
+    project_root: &Path,
+) -> Command {
+    let mut docker = Command::new("docker");
+    docker.args(["run", "--rm"]);
+    docker
+        .arg("--volume")
+        .arg(format!("{0}:{0}", project_root.display()));
+
+    let mounts: BTreeSet<&Path> = cmd
+        .get_args()
+        .map(Path::new)
+        .filter(|path| path.is_absolute() && !path.starts_with(project_root))
+        .filter_map(|path| {
+            if path.is_dir() {
+                Some(path)
+            } else if path.exists() {
+                path.parent()
+            } else {
+                None
+            }
+        })
+        .collect();
+    for mount in mounts {
+        docker
+            .arg("--volume")
+            .arg(format!("{0}:{0}:ro", mount.display()));
+    }
+
+    docker
+        .arg("--workdir")
+        .arg(cmd.get_current_dir().unwrap_or(project_root));
+    for (key, value) in cmd.get_envs() {
+        if let Some(value) = value {
+            let mut env = key.to_os_string();
+            env.push("=");
+            env.push(value);
+            docker.arg("--env").arg(env);
+        }
+    }
+
+    // Files written to the project belong to its owner rather than to root.
+    #[cfg(unix)]
+    if let Ok(metadata) = std::fs::metadata(project_root) {
+        use std::os::unix::fs::MetadataExt;
+        docker
+            .arg("--user")
+            .arg(format!("{}:{}", metadata.uid(), metadata.gid()));
+    }
+
+    let program = Path::new(cmd.get_program());
+    let program = match program.file_name() {
+        Some(name) if program.is_absolute() && !program.starts_with(project_root) => name,
+        _ => cmd.get_program(),
+    };
+    docker
+        .arg(&build_container.image)
+        .arg(program)
+        .args(cmd.get_args());
+    docker
+}
+
+/// Replaces the `${canister_id:<name>}` placeholders in a value from dfx.json with the ids of the canisters.
+pub fn interpolate_canister_ids(value: &str, pool: &CanisterPool) -> DfxResult<String> {
+    interpolate(value, |kind, arg| match (kind, arg) {
+        ("canister_id", Some(name)) => {
+            let canister = pool.get_first_canister_with_name(name).ok_or_else(|| {
+                anyhow!(
+                    "Cannot replace '${{canister_id:{0}}}': a canister with the name '{0}' was not found in the current project.",
+                    name
+                )
+            })?;
+            Ok(Some(canister.canister_id().to_text()))
+        }
+        _ => Ok(None),
+    })
+}
+
 type Env<'a> = (Cow<'static, str>, Cow<'a, OsStr>);
 
 pub fn get_and_write_environment_variables<'a>(
@@ -429,9 +544,76 @@ pub fn get_and_write_environment_variables<'a>(
     Ok(vars)
 }
 
+/// Writes `DFX_NETWORK` and the ids of the canisters in the pool to the environment file of the
+/// declarations of a canister, and to a copy of it for the network.
+/// The ids of other canisters that the file already lists are kept, so that generating the
+/// declarations of one canister does not drop the ids of the others.
+#[context("Failed to write the environment file of canister '{}'.", info.get_name())]
+fn write_declarations_env_file(
+    info: &CanisterInfo,
+    pool: &CanisterPool,
+    network_name: &str,
+) -> DfxResult {
+    let Some(env_file) = &info.get_declarations_config().env_file else {
+        return Ok(());
+    };
+    use Cow::*;
+    let mut vars: Vec<Env<'_>> = vec![(Borrowed("DFX_NETWORK"), Borrowed(network_name.as_ref()))];
+    for canister in pool.get_canister_list() {
+        if let Ok(canister_id) = canister.get_info().get_canister_id() {
+            vars.push((
+                Owned(format!(
+                    "CANISTER_ID_{}",
+                    canister.get_name().replace('-', "_").to_ascii_uppercase(),
+                )),
+                Owned(canister_id.to_text().into()),
+            ));
+        }
+    }
+    let mut network_env_file = env_file.as_os_str().to_owned();
+    network_env_file.push(format!(".{network_name}"));
+    for path in [env_file.clone(), PathBuf::from(network_env_file)] {
+        dfx_core::fs::composite::ensure_parent_dir_exists(&path)?;
+        let mut path_vars = vars.clone();
+        for (var, val) in read_environment_variables(&path)? {
+            if var.starts_with("CANISTER_ID_") && !path_vars.iter().any(|(v, _)| *v == var) {
+                path_vars.push((Owned(var), Owned(val.into())));
+            }
+        }
+        write_environment_variables(&path_vars, &path)?;
+        eprintln!(
+            "Wrote the canister ids of network '{network_name}' to {}.",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+const START_TAG: &str = "\n# DFX CANISTER ENVIRONMENT VARIABLES";
+const END_TAG: &str = "\n# END DFX CANISTER ENVIRONMENT VARIABLES";
+
+/// Reads the variables of the dfx section of an environment file, if the file has one.
+fn read_environment_variables(path: &Path) -> DfxResult<Vec<(String, String)>> {
+    if !path.try_exists()? {
+        return Ok(vec![]);
+    }
+    let existing_file = fs::read_to_string(path)?;
+    let Some(start_pos) = existing_file.rfind(START_TAG) else {
+        return Ok(vec![]);
+    };
+    let section = &existing_file[start_pos + START_TAG.len()..];
+    let section = section.find(END_TAG).map_or(section, |end| &section[..end]);
+    Ok(section
+        .lines()
+        .filter_map(|line| {
+            let (var, val) = line.split_once('=')?;
+            let val = val.strip_prefix('\'')?.strip_suffix('\'')?;
+            Some((var.to_string(), val.to_string()))
+        })
+        .collect())
+}
+
 fn write_environment_variables(vars: &[Env<'_>], write_path: &Path) -> DfxResult {
-    const START_TAG: &str = "\n# DFX CANISTER ENVIRONMENT VARIABLES";
-    const END_TAG: &str = "\n# END DFX CANISTER ENVIRONMENT VARIABLES";
     let mut write_string = String::from(START_TAG);
     for (var, val) in vars {
         if let Some(val) = val.to_str() {
@@ -469,6 +651,10 @@ fn write_environment_variables(vars: &[Env<'_>], write_path: &Path) -> DfxResult
 #[derive(Clone, Debug)]
 pub struct BuildConfig {
     profile: Profile,
+    /// The profile selected with `--profile`, if it is one of the `profiles` of dfx.json.
+    named_profile: Option<ConfigBuildProfile>,
+    /// The `profiles` of dfx.json.
+    profiles: BTreeMap<String, ConfigBuildProfile>,
     pub build_mode_check: bool,
     pub network_name: String,
     pub network_is_playground: bool,
@@ -484,6 +670,10 @@ pub struct BuildConfig {
     pub canisters_to_build: Option<Vec<String>>,
     /// If environment variables should be output to a `.env` file, `env_file` is set to its path.
     pub env_file: Option<PathBuf>,
+    /// The artifacts of each canister are copied to `<output_dir>/<canister name>` after the build.
+    output_dir: Option<PathBuf>,
+    /// Whether `output_dir` overrides the output directories of the canisters, as `--output-dir` does.
+    output_dir_overrides_canisters: bool,
 }
 
 impl BuildConfig {
@@ -498,12 +688,21 @@ impl BuildConfig {
             network_name,
             network_is_playground,
             profile: config_intf.profile.unwrap_or(Profile::Debug),
+            named_profile: None,
+            profiles: config_intf.profiles.clone().unwrap_or_default(),
             build_mode_check: false,
             build_root: canister_root.clone(),
             idl_root: canister_root.join("idl/"), // TODO: possibly move to `network_root.join("idl/")`
             lsp_root: network_root.join("lsp/"),
             canisters_to_build: None,
             env_file: config.get_output_env_file(None)?,
+            output_dir: config_intf
+                .get_defaults()
+                .get_build()
+                .output_dir
+                .as_ref()
+                .map(|dir| config.get_project_root().join(dir)),
+            output_dir_overrides_canisters: false,
         })
     }
 
@@ -524,6 +723,68 @@ impl BuildConfig {
     pub fn with_env_file(self, env_file: Option<PathBuf>) -> Self {
         Self { env_file, ..self }
     }
+
+    /// Overrides the profile of dfx.json with a built-in profile (debug, release or test-coverage),
+    /// or with one of the `profiles` of dfx.json.
+    pub fn with_profile(self, profile: Option<&str>) -> DfxResult<Self> {
+        let Some(name) = profile else {
+            return Ok(self);
+        };
+        let profile = match name {
+            "debug" => Profile::Debug,
+            "release" => Profile::Release,
+            "test-coverage" => Profile::TestCoverage,
+            _ => {
+                let Some(named_profile) = self.profiles.get(name).cloned() else {
+                    bail!(
+                        "Unknown build profile '{}', which is not in the profiles of dfx.json. Must be one of debug, release or test-coverage.",
+                        name
+                    );
+                };
+                return Ok(Self {
+                    profile: named_profile.inherits.unwrap_or(Profile::Debug),
+                    named_profile: Some(named_profile),
+                    ..self
+                });
+            }
+        };
+        Ok(Self {
+            profile,
+            named_profile: None,
+            ..self
+        })
+    }
+
+    /// Copies the artifacts of every canister to `<output_dir>/<canister name>`, regardless of dfx.json.
+    pub fn with_output_dir(self, output_dir: Option<PathBuf>) -> Self {
+        match output_dir {
+            Some(output_dir) => Self {
+                output_dir: Some(output_dir),
+                output_dir_overrides_canisters: true,
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    /// The built-in profile, or the one that the selected profile of dfx.json inherits.
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// The selected profile, if it is one of the `profiles` of dfx.json.
+    pub fn named_profile(&self) -> Option<&ConfigBuildProfile> {
+        self.named_profile.as_ref()
+    }
+
+    /// The directory to copy the artifacts of a canister to after the build, if any.
+    pub fn artifacts_dir(&self, info: &CanisterInfo) -> Option<PathBuf> {
+        match (info.get_artifacts_dir(), &self.output_dir) {
+            (Some(dir), _) if !self.output_dir_overrides_canisters => Some(dir.to_path_buf()),
+            (_, Some(output_dir)) => Some(output_dir.join(info.get_name())),
+            (dir, None) => dir.map(Path::to_path_buf),
+        }
+    }
 }
 
 pub struct BuilderPool {
@@ -1,5 +1,6 @@
 use crate::lib::builders::{
-    BuildConfig, BuildOutput, CanisterBuilder, IdlBuildOutput, WasmBuildOutput,
+    interpolate_canister_ids, BuildConfig, BuildOutput, CanisterBuilder, IdlBuildOutput,
+    WasmBuildOutput,
 };
 use crate::lib::canister_info::motoko::MotokoCanisterInfo;
 use crate::lib::canister_info::CanisterInfo;
@@ -115,6 +116,13 @@ impl CanisterBuilder for MotokoBuilder {
     ) -> DfxResult<BuildOutput> {
         let motoko_info = canister_info.as_info::<MotokoCanisterInfo>()?;
         let profile = config.profile;
+        if matches!(profile, Profile::TestCoverage) {
+            warn!(
+                self.logger,
+                "Coverage instrumentation is not supported for Motoko canisters. Building canister '{}' in debug mode.",
+                canister_info.get_name()
+            );
+        }
         let input_path = motoko_info.get_main_path();
         let output_wasm_path = motoko_info.get_output_wasm_path();
 
@@ -143,17 +151,36 @@ impl CanisterBuilder for MotokoBuilder {
             dfx_core::fs::write(management_idl_path, management_idl()?)?;
         }
 
-        let package_arguments =
-            package_arguments::load(cache.as_ref(), motoko_info.get_packtool())?;
+        let package_arguments = package_arguments::load(
+            &self.logger,
+            cache.as_ref(),
+            motoko_info.get_packtool(),
+            motoko_info.get_packages(),
+            canister_info.get_workspace_root(),
+        )?;
 
         let moc_arguments = match motoko_info.get_args() {
             Some(args) => [
                 package_arguments,
-                args.split_whitespace().map(str::to_string).collect(),
+                interpolate_canister_ids(args, pool)?
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
             ]
             .concat(),
             None => package_arguments,
         };
+        let moc_arguments = match config.named_profile().and_then(|p| p.moc_args.as_deref()) {
+            Some(args) => [
+                moc_arguments,
+                interpolate_canister_ids(args, pool)?
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+            ]
+            .concat(),
+            None => moc_arguments,
+        };
 
         let candid_service_metadata_visibility = canister_info
             .get_metadata(CANDID_SERVICE)
@@ -180,7 +207,7 @@ impl CanisterBuilder for MotokoBuilder {
             idl_path: idl_dir_path,
             idl_map: &id_map,
         };
-        motoko_compile(&self.logger, cache.as_ref(), &params)?;
+        motoko_compile(&self.logger, cache.as_ref(), &params, canister_info)?;
 
         Ok(BuildOutput {
             canister_id: canister_info
@@ -273,9 +300,18 @@ impl MotokoParams<'_> {
 
 /// Compile a motoko file.
 #[context("Failed to compile Motoko.")]
-fn motoko_compile(logger: &Logger, cache: &dyn Cache, params: &MotokoParams<'_>) -> DfxResult {
+fn motoko_compile(
+    logger: &Logger,
+    cache: &dyn Cache,
+    params: &MotokoParams<'_>,
+    canister_info: &CanisterInfo,
+) -> DfxResult {
     let mut cmd = cache.get_binary_command("moc")?;
     params.to_args(&mut cmd);
+    if let Some(build_container) = canister_info.get_build_container() {
+        info!(logger, "Building in container {}", build_container.image);
+        cmd = super::in_build_container(&cmd, build_container, canister_info.get_workspace_root());
+    }
     run_command(logger, &mut cmd, params.suppress_warning).context("Failed to run 'moc'.")?;
     Ok(())
 }
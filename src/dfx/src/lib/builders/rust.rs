@@ -3,11 +3,14 @@ use crate::lib::builders::{
 };
 use crate::lib::canister_info::rust::RustCanisterInfo;
 use crate::lib::canister_info::CanisterInfo;
+use crate::lib::declarations::binding_emitter;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::models::canister::CanisterPool;
 use anyhow::{anyhow, bail, Context};
 use candid::Principal as CanisterId;
+use candid_parser::utils::CandidSource;
+use dfx_core::config::model::dfinity::Profile;
 use fn_error_context::context;
 use slog::{info, o};
 use std::path::PathBuf;
@@ -62,17 +65,27 @@ impl CanisterBuilder for RustBuilder {
 
         let canister_id = canister_info.get_canister_id().unwrap();
 
+        let named_profile = config.named_profile();
+        let cargo_profile = named_profile
+            .and_then(|profile| profile.cargo_profile.as_deref())
+            .unwrap_or("release");
+
         let mut cargo = Command::new("cargo");
         cargo
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
             .arg("build")
             .arg("--target")
             .arg("wasm32-unknown-unknown")
-            .arg("--release")
+            .arg("--profile")
+            .arg(cargo_profile)
             .arg("-p")
             .arg(package)
             .arg("--locked");
+        if let Some(profile) = named_profile {
+            if !profile.features.is_empty() {
+                cargo.arg("--features").arg(profile.features.join(","));
+            }
+            cargo.args(&profile.cargo_args);
+        }
 
         let dependencies = self
             .get_dependencies(pool, canister_info)
@@ -87,10 +100,40 @@ impl CanisterBuilder for RustBuilder {
         for (key, val) in vars {
             cargo.env(key.as_ref(), val);
         }
+        for (key, path) in generate_dependency_bindings(pool, canister_info, &dependencies)? {
+            cargo.env(key, path);
+        }
+        if matches!(config.profile(), Profile::TestCoverage) {
+            // Instrument the canister for coverage without the profiler runtime, which is not available for wasm.
+            // The canister collects the counters itself, e.g. with the `minicov` crate.
+            let rustflags = std::env::var("RUSTFLAGS").unwrap_or_default();
+            let rustflags = format!("{rustflags} -Cinstrument-coverage -Zno-profiler-runtime")
+                .trim_start()
+                .to_string();
+            info!(
+                self.logger,
+                "Instrumenting for coverage with RUSTFLAGS=\"{}\"", rustflags
+            );
+            cargo.env("RUSTFLAGS", rustflags);
+        }
+
+        if let Some(build_container) = canister_info.get_build_container() {
+            info!(
+                self.logger,
+                "Building in container {}", build_container.image
+            );
+            cargo = super::in_build_container(
+                &cargo,
+                build_container,
+                canister_info.get_workspace_root(),
+            );
+        }
+        cargo.stdout(Stdio::inherit()).stderr(Stdio::inherit());
 
         info!(
             self.logger,
-            "Executing: cargo build --target wasm32-unknown-unknown --release -p {} --locked",
+            "Executing: cargo build --target wasm32-unknown-unknown --profile {} -p {} --locked",
+            cargo_profile,
             package
         );
         let output = cargo.output().context("Failed to run 'cargo build'. You might need to run `cargo update` (or a similar command like `cargo vendor`) if you have updated `Cargo.toml`, because `dfx build` uses the --locked flag with Cargo.")?;
@@ -101,7 +144,7 @@ impl CanisterBuilder for RustBuilder {
 
         Ok(BuildOutput {
             canister_id,
-            wasm: WasmBuildOutput::File(rust_info.get_output_wasm_path().to_path_buf()),
+            wasm: WasmBuildOutput::File(rust_info.get_output_wasm_path(cargo_profile)),
             idl: IdlBuildOutput::File(rust_info.get_output_idl_path().to_path_buf()),
         })
     }
@@ -124,3 +167,50 @@ impl CanisterBuilder for RustBuilder {
         }
     }
 }
+
+/// Generates Rust bindings from the Candid interfaces of the dependencies of a canister:
+/// the types of each dependency and a `Service` struct to call it with ic-cdk, in a module named after it.
+/// Returns the environment variables that point to them, so that the canister can include them with
+/// `include!(env!("CANISTER_BINDINGS_<NAME>"))`.
+fn generate_dependency_bindings(
+    pool: &CanisterPool,
+    info: &CanisterInfo,
+    dependencies: &[CanisterId],
+) -> DfxResult<Vec<(String, PathBuf)>> {
+    let emitter = binding_emitter("rs").expect("Rust bindings are always available");
+    let bindings_dir = info.get_output_root().join("bindings");
+    let mut vars = vec![];
+    for dependency in dependencies {
+        let canister = pool.get_canister(dependency).unwrap();
+        let Some(output) = canister.get_build_output() else {
+            continue;
+        };
+        let IdlBuildOutput::File(candid_path) = &output.idl;
+        let name = canister.get_name();
+        let (env, actor) = CandidSource::File(candid_path).load().with_context(|| {
+            format!(
+                "Failed to load the Candid interface of canister '{}'.",
+                name
+            )
+        })?;
+        let module = name.replace('-', "_");
+        let bindings = format!(
+            "pub mod {} {{\n{}\n}}\n",
+            module,
+            emitter.emit(name, &env, &actor)
+        );
+
+        dfx_core::fs::create_dir_all(&bindings_dir)?;
+        let bindings_path = bindings_dir.join(format!("{}.rs", module));
+        dfx_core::fs::write(&bindings_path, bindings)?;
+        vars.push((
+            format!("CANISTER_BINDINGS_{}", module),
+            bindings_path.clone(),
+        ));
+        vars.push((
+            format!("CANISTER_BINDINGS_{}", module.to_ascii_uppercase()),
+            bindings_path,
+        ));
+    }
+    Ok(vars)
+}
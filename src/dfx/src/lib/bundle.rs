@@ -0,0 +1,38 @@
+//! Bundles of built canisters, created by `dfx pack` and deployed by `dfx apply`.
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+
+/// The name of the manifest at the root of a bundle.
+pub const BUNDLE_MANIFEST_FILE_NAME: &str = "bundle.json";
+
+/// The version of the bundle format that this dfx writes and reads.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Describes the contents of a bundle. Paths are relative to the root of the bundle.
+#[derive(Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    /// The version of dfx that created the bundle.
+    pub dfx_version: String,
+    /// The network the canisters were built for.
+    pub network: String,
+    pub canisters: Vec<BundledCanister>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BundledCanister {
+    pub name: String,
+    pub canister_id: Principal,
+    pub wasm: String,
+    /// Hex-encoded SHA-256 of the wasm module.
+    pub wasm_sha256: String,
+    /// The Candid interface of the canister, including its init arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candid: Option<String>,
+    /// The init argument, in Candid text format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub init_arg: Option<String>,
+    /// Directories with the assets to upload to an asset canister.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<String>,
+}
@@ -1,14 +1,17 @@
 #![allow(dead_code)]
 use crate::lib::error::DfxResult;
 use crate::lib::metadata::config::CanisterMetadataConfig;
+use crate::util::{fuzzy_parse_argument, get_candid_init_type};
 use anyhow::{anyhow, Context};
 use candid::Principal as CanisterId;
 use candid::Principal;
 use core::panic;
+use dfx_core::config::interpolation::interpolate_network;
 use dfx_core::config::model::dfinity::{
-    CanisterDeclarationsConfig, CanisterMetadataSection, CanisterTypeProperties, Config, Pullable,
-    WasmOptLevel,
+    BuildContainer, CanisterDeclarationsConfig, CanisterInitArg, CanisterMetadataSection,
+    CanisterTypeProperties, Config, MotokoPackageManager, Pullable, WasmOptLevel,
 };
+use dfx_core::json::structure::SerdeVec;
 use dfx_core::network::provider::get_network_context;
 use dfx_core::util;
 use fn_error_context::context;
@@ -38,7 +41,8 @@ pub struct CanisterInfo {
 
     declarations_config: CanisterDeclarationsConfig,
     remote_id: Option<Principal>, // id on the currently selected network
-    remote_candid: Option<PathBuf>, // always exists if the field is configured
+    remote_candid: Option<PathBuf>, // always exists if the field is configured, or the canister is remote
+    remote_candid_is_fetched: bool, // the interface is fetched from the network into remote_candid
 
     workspace_root: PathBuf,
     output_root: PathBuf, // <project dir>/.dfx/<network>/canisters/<canister>
@@ -46,6 +50,7 @@ pub struct CanisterInfo {
     canister_id: Option<CanisterId>,
 
     packtool: Option<String>,
+    packages: Option<MotokoPackageManager>,
     args: Option<String>,
     type_specific: CanisterTypeProperties,
 
@@ -59,6 +64,10 @@ pub struct CanisterInfo {
     pull_dependencies: Vec<(String, CanisterId)>,
     gzip: bool,
     init_arg: Option<String>,
+    allowed_wasm_hashes: Option<Vec<String>>,
+    build_container: Option<BuildContainer>,
+    build_inputs: Vec<PathBuf>,
+    artifacts_dir: Option<PathBuf>,
 }
 
 impl CanisterInfo {
@@ -113,11 +122,30 @@ impl CanisterInfo {
             .as_ref()
             .and_then(|remote| remote.id.get(&network_name))
             .copied();
-        let remote_candid = canister_config
+        let configured_remote_candid = canister_config
             .remote
             .as_ref()
             .and_then(|r| r.candid.as_ref())
             .cloned();
+        // Remote canisters use the interface from their metadata, cached under .dfx/<network>/remote_candid,
+        // unless it is known: from `remote.candid`, the `candid` of rust and custom canisters, or for assets canisters.
+        let has_candid = !matches!(
+            canister_config.type_specific,
+            CanisterTypeProperties::Motoko
+        );
+        let remote_candid_is_fetched =
+            remote_id.is_some() && configured_remote_candid.is_none() && !has_candid;
+        let remote_candid = if remote_candid_is_fetched {
+            Some(
+                config
+                    .get_temp_path()
+                    .join(util::network_to_pathcompat(&network_name))
+                    .join("remote_candid")
+                    .join(format!("{name}.did")),
+            )
+        } else {
+            configured_remote_candid
+        };
 
         // Fill the default config values if None provided
         let declarations_config = CanisterDeclarationsConfig {
@@ -129,32 +157,58 @@ impl CanisterInfo {
                 .or_else(|| Some(vec!["js".to_string(), "ts".to_string(), "did".to_string()])),
             env_override: declarations_config_pre.env_override,
             node_compatibility: declarations_config_pre.node_compatibility,
+            env_file: declarations_config_pre
+                .env_file
+                .map(|env_file| workspace_root.join(env_file)),
         };
 
         let output_root = build_root.join(name);
 
-        let type_specific = canister_config.type_specific.clone();
+        let mut type_specific = canister_config.type_specific.clone();
+        if let CanisterTypeProperties::Custom {
+            wasm,
+            candid,
+            build,
+        } = &mut type_specific
+        {
+            *wasm = interpolate_network(wasm, &network_name);
+            *candid = interpolate_network(candid, &network_name);
+            *build = SerdeVec::Many(
+                std::mem::take(build)
+                    .into_vec()
+                    .iter()
+                    .map(|command| interpolate_network(command, &network_name))
+                    .collect(),
+            );
+        }
 
         let args = match &canister_config.args {
             Some(args) if !args.is_empty() => canister_config.args.clone(),
             _ => build_defaults.get_args(),
-        };
+        }
+        .map(|args| interpolate_network(&args, &network_name));
 
         let post_install = canister_config.post_install.clone().into_vec();
         let metadata = CanisterMetadataConfig::new(&canister_config.metadata, &network_name);
 
         let gzip = canister_config.gzip.unwrap_or(false);
-        let init_arg = canister_config.init_arg.clone();
+        let init_arg = canister_config
+            .init_arg
+            .as_ref()
+            .and_then(|init_arg| init_arg.for_network(&network_name))
+            .map(String::from);
 
         let canister_info = CanisterInfo {
             name: name.to_string(),
             declarations_config,
             remote_id,
             remote_candid,
+            remote_candid_is_fetched,
             workspace_root: workspace_root.to_path_buf(),
             output_root,
             canister_id,
             packtool: build_defaults.get_packtool(),
+            packages: build_defaults.get_packages(),
             args,
             type_specific,
             dependencies,
@@ -167,8 +221,19 @@ impl CanisterInfo {
             pull_dependencies,
             gzip,
             init_arg,
+            allowed_wasm_hashes: canister_config.allowed_wasm_hashes.clone(),
+            build_container: canister_config.build_container.clone(),
+            build_inputs: canister_config.build_inputs.clone(),
+            artifacts_dir: canister_config
+                .output_dir
+                .as_ref()
+                .map(|dir| workspace_root.join(dir)),
         };
 
+        if let Some(init_arg) = &canister_config.init_arg {
+            canister_info.check_init_args(init_arg)?;
+        }
+
         Ok(canister_info)
     }
 
@@ -194,6 +259,15 @@ impl CanisterInfo {
             None
         }
     }
+    /// The id of the remote canister and the path to cache its interface at,
+    /// if the interface is to be fetched from the network instead of being configured.
+    pub fn get_remote_candid_to_fetch(&self) -> Option<(Principal, PathBuf)> {
+        if self.remote_candid_is_fetched {
+            self.remote_id.zip(self.remote_candid.clone())
+        } else {
+            None
+        }
+    }
     pub fn get_workspace_root(&self) -> &Path {
         &self.workspace_root
     }
@@ -227,6 +301,10 @@ impl CanisterInfo {
         &self.packtool
     }
 
+    pub fn get_packages(&self) -> Option<MotokoPackageManager> {
+        self.packages
+    }
+
     pub fn get_post_install(&self) -> &[String] {
         &self.post_install
     }
@@ -367,4 +445,52 @@ impl CanisterInfo {
     pub fn get_init_arg(&self) -> Option<&str> {
         self.init_arg.as_deref()
     }
+
+    pub fn get_allowed_wasm_hashes(&self) -> Option<&[String]> {
+        self.allowed_wasm_hashes.as_deref()
+    }
+
+    pub fn get_build_container(&self) -> Option<&BuildContainer> {
+        self.build_container.as_ref()
+    }
+
+    /// The files and directories whose contents determine the build, relative to the workspace root.
+    pub fn get_build_inputs(&self) -> &[PathBuf] {
+        &self.build_inputs
+    }
+
+    /// The directory the artifacts of the canister are copied to after a build, if its `output_dir` is set.
+    pub fn get_artifacts_dir(&self) -> Option<&Path> {
+        self.artifacts_dir.as_deref()
+    }
+
+    /// Checks the `init_arg` of every network against the init type of the canister,
+    /// so that a mistake is not only found when deploying to the network it is for.
+    /// Nothing is checked while the candid interface is unknown, e.g. before a Motoko canister is built.
+    #[context("Invalid init_arg for canister '{}' in dfx.json.", self.name)]
+    fn check_init_args(&self, init_arg: &CanisterInitArg) -> DfxResult {
+        let idl_path = match &self.type_specific {
+            _ if self.get_constructor_idl_path().exists() => self.get_constructor_idl_path(),
+            CanisterTypeProperties::Rust { candid, .. } => self.workspace_root.join(candid),
+            CanisterTypeProperties::Custom { candid, .. } => self.workspace_root.join(candid),
+            _ => return Ok(()),
+        };
+        let Some((env, init_type)) = get_candid_init_type(&idl_path) else {
+            return Ok(());
+        };
+        for (network, arg) in init_arg.all() {
+            fuzzy_parse_argument(arg, &env, &init_type.args).with_context(|| match network {
+                Some(network) => format!(
+                    "The init_arg for network '{}' does not match the init type in {}.",
+                    network,
+                    idl_path.display()
+                ),
+                None => format!(
+                    "The init_arg does not match the init type in {}.",
+                    idl_path.display()
+                ),
+            })?;
+        }
+        Ok(())
+    }
 }
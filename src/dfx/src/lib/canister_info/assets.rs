@@ -13,6 +13,8 @@ pub struct AssetsCanisterInfo {
     output_idl_path: PathBuf,
     build: Vec<String>,
     workspace: Option<String>,
+    upload_parallelism: Option<usize>,
+    upload_resume_path: PathBuf,
 }
 
 impl AssetsCanisterInfo {
@@ -31,6 +33,13 @@ impl AssetsCanisterInfo {
     pub fn get_npm_workspace(&self) -> Option<&str> {
         self.workspace.as_deref()
     }
+    pub fn get_upload_parallelism(&self) -> Option<usize> {
+        self.upload_parallelism
+    }
+    /// Where an interrupted asset upload records its progress, so that the next attempt can resume it.
+    pub fn get_upload_resume_path(&self) -> &Path {
+        self.upload_resume_path.as_path()
+    }
 
     #[context("Failed to assert source paths.")]
     pub fn assert_source_paths(&self) -> DfxResult<()> {
@@ -59,24 +68,27 @@ impl CanisterInfoFactory for AssetsCanisterInfo {
     fn create(info: &CanisterInfo) -> DfxResult<Self> {
         let input_root = info.get_workspace_root().to_path_buf();
         // If there are no "source" field, we just ignore this.
-        let (source_paths, build, workspace) = if let CanisterTypeProperties::Assets {
-            source,
-            build,
-            workspace,
-        } = info.type_specific.clone()
-        {
-            (source, build.into_vec(), workspace)
-        } else {
-            bail!(
-                "Attempted to construct an assets canister from a type:{} canister config",
-                info.type_specific.name()
-            )
-        };
+        let (source_paths, build, workspace, upload_parallelism) =
+            if let CanisterTypeProperties::Assets {
+                source,
+                build,
+                workspace,
+                upload_parallelism,
+            } = info.type_specific.clone()
+            {
+                (source, build.into_vec(), workspace, upload_parallelism)
+            } else {
+                bail!(
+                    "Attempted to construct an assets canister from a type:{} canister config",
+                    info.type_specific.name()
+                )
+            };
 
         let output_root = info.get_output_root();
 
         let output_wasm_path = output_root.join(Path::new("assetstorage.wasm.gz"));
         let output_idl_path = output_wasm_path.with_extension("").with_extension("did");
+        let upload_resume_path = output_root.join("upload-resume.json");
 
         Ok(AssetsCanisterInfo {
             input_root,
@@ -85,6 +97,8 @@ impl CanisterInfoFactory for AssetsCanisterInfo {
             output_idl_path,
             build,
             workspace,
+            upload_parallelism,
+            upload_resume_path,
         })
     }
 }
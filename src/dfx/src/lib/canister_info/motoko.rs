@@ -1,7 +1,7 @@
 use crate::lib::canister_info::{CanisterInfo, CanisterInfoFactory};
 use crate::lib::error::DfxResult;
 use anyhow::{ensure, Context};
-use dfx_core::config::model::dfinity::CanisterTypeProperties;
+use dfx_core::config::model::dfinity::{CanisterTypeProperties, MotokoPackageManager};
 use std::path::{Path, PathBuf};
 
 pub struct MotokoCanisterInfo {
@@ -16,6 +16,7 @@ pub struct MotokoCanisterInfo {
     output_assets_root: PathBuf,
 
     packtool: Option<String>,
+    packages: Option<MotokoPackageManager>,
     moc_args: Option<String>,
 }
 
@@ -47,6 +48,9 @@ impl MotokoCanisterInfo {
     pub fn get_packtool(&self) -> &Option<String> {
         &self.packtool
     }
+    pub fn get_packages(&self) -> Option<MotokoPackageManager> {
+        self.packages
+    }
     pub fn get_args(&self) -> &Option<String> {
         &self.moc_args
     }
@@ -87,6 +91,7 @@ impl CanisterInfoFactory for MotokoCanisterInfo {
             output_canister_js_path,
             output_assets_root,
             packtool: info.get_packtool().clone(),
+            packages: info.get_packages(),
             moc_args: info.get_args().clone(),
         })
     }
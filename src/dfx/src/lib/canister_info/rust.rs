@@ -8,7 +8,7 @@ use std::process::{Command, Stdio};
 
 pub struct RustCanisterInfo {
     package: String,
-    output_wasm_path: PathBuf,
+    target_directory: PathBuf,
     output_idl_path: PathBuf,
 }
 
@@ -17,8 +17,18 @@ impl RustCanisterInfo {
         &self.package
     }
 
-    pub fn get_output_wasm_path(&self) -> &Path {
-        self.output_wasm_path.as_path()
+    /// The path of the wasm that `cargo build --profile <cargo_profile>` produces.
+    pub fn get_output_wasm_path(&self, cargo_profile: &str) -> PathBuf {
+        // Cargo writes the built-in dev and test profiles to `debug`, and bench to `release`.
+        let profile_dir = match cargo_profile {
+            "dev" | "test" => "debug",
+            "bench" => "release",
+            profile => profile,
+        };
+        self.target_directory.join(format!(
+            "wasm32-unknown-unknown/{profile_dir}/{}.wasm",
+            self.package
+        ))
     }
 
     pub fn get_output_idl_path(&self) -> &Path {
@@ -54,8 +64,6 @@ impl CanisterInfoFactory for RustCanisterInfo {
             };
 
         let workspace_root = info.get_workspace_root();
-        let output_wasm_path =
-            target_directory.join(format!("wasm32-unknown-unknown/release/{package}.wasm"));
         let candid = if let Some(remote_candid) = info.get_remote_candid_if_remote() {
             remote_candid
         } else {
@@ -65,7 +73,7 @@ impl CanisterInfoFactory for RustCanisterInfo {
 
         Ok(Self {
             package,
-            output_wasm_path,
+            target_directory,
             output_idl_path,
         })
     }
@@ -0,0 +1,72 @@
+//! The cost model that subnets use to charge canisters for messages,
+//! used to estimate the cost of calls before they are made.
+//! The fees are those of a 13-node application subnet. Subnets with more nodes charge proportionally more.
+//! See https://internetcomputer.org/docs/current/developer-docs/gas-cost
+
+/// The number of nodes of the subnet the fees apply to.
+pub const REFERENCE_SUBNET_SIZE: u128 = 13;
+
+/// Charged to a canister for every ingress message it receives.
+pub const INGRESS_MESSAGE_RECEPTION_FEE: u128 = 1_200_000;
+
+/// Charged to a canister for every byte of the method name and argument of an ingress message it receives.
+pub const INGRESS_BYTE_RECEPTION_FEE: u128 = 2_000;
+
+/// Charged to a canister for every update message it executes, on top of its instructions.
+pub const UPDATE_MESSAGE_EXECUTION_FEE: u128 = 5_000_000;
+
+/// Charged to a canister for every ten instructions it executes.
+pub const TEN_UPDATE_INSTRUCTIONS_EXECUTION_FEE: u128 = 4;
+
+/// Charged to a canister for every call it makes to another canister.
+pub const XNET_CALL_FEE: u128 = 260_000;
+
+/// Charged to a canister for every byte of the method name and argument of a call to another canister.
+pub const XNET_BYTE_TRANSMISSION_FEE: u128 = 1_000;
+
+/// The most instructions an update message can execute.
+/// The cycles for this many instructions are reserved before execution, and the unused ones refunded.
+pub const MAX_INSTRUCTIONS_PER_UPDATE_MESSAGE: u128 = 40_000_000_000;
+
+/// The cost of receiving an ingress message, charged to the canister it is sent to.
+pub fn ingress_reception_fee(method_name: &str, argument: &[u8]) -> u128 {
+    INGRESS_MESSAGE_RECEPTION_FEE
+        + INGRESS_BYTE_RECEPTION_FEE * (method_name.len() + argument.len()) as u128
+}
+
+/// The cost of executing an update message with the given number of instructions.
+pub fn update_execution_fee(instructions: u128) -> u128 {
+    UPDATE_MESSAGE_EXECUTION_FEE + instructions * TEN_UPDATE_INSTRUCTIONS_EXECUTION_FEE / 10
+}
+
+/// The number of instructions that an update message executed, given what executing it cost.
+/// The inverse of `update_execution_fee`.
+pub fn instructions_for_execution_fee(fee: u128) -> u128 {
+    fee.saturating_sub(UPDATE_MESSAGE_EXECUTION_FEE) * 10 / TEN_UPDATE_INSTRUCTIONS_EXECUTION_FEE
+}
+
+/// The cost of a call from one canister to another, charged to the caller.
+pub fn xnet_call_fee(method_name: &str, argument: &[u8]) -> u128 {
+    XNET_CALL_FEE + XNET_BYTE_TRANSMISSION_FEE * (method_name.len() + argument.len()) as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fees() {
+        assert_eq!(ingress_reception_fee("inc", &[0; 7]), 1_220_000);
+        assert_eq!(update_execution_fee(0), 5_000_000);
+        assert_eq!(
+            update_execution_fee(MAX_INSTRUCTIONS_PER_UPDATE_MESSAGE),
+            16_005_000_000
+        );
+        assert_eq!(xnet_call_fee("inc", &[0; 7]), 270_000);
+        assert_eq!(
+            instructions_for_execution_fee(update_execution_fee(1_000)),
+            1_000
+        );
+        assert_eq!(instructions_for_execution_fee(1_000), 0);
+    }
+}
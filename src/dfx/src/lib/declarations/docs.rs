@@ -0,0 +1,321 @@
+//! Documentation of a canister interface, rendered from its Candid file together with the comments
+//! above its methods and types.
+use crate::lib::error::DfxResult;
+use candid::types::{FuncMode, Type, TypeEnv, TypeInner};
+use candid_parser::utils::CandidSource;
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DocFormat {
+    #[value(name = "md")]
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    /// Returns the format for a value of `declarations.bindings`, if it is one of the documentation formats.
+    pub fn from_binding(binding: &str) -> Option<Self> {
+        match binding {
+            "md" => Some(DocFormat::Markdown),
+            "html" => Some(DocFormat::Html),
+            _ => None,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+/// A documented method or type.
+struct Item {
+    name: String,
+    signature: String,
+    doc: Option<String>,
+}
+
+struct InterfaceDoc {
+    canister_name: String,
+    service_doc: Option<String>,
+    init: Option<String>,
+    methods: Vec<Item>,
+    types: Vec<Item>,
+}
+
+/// Writes the documentation of the interface in a Candid file to `<output_dir>/<canister_name>.<md|html>`.
+pub fn write_docs(
+    canister_name: &str,
+    format: DocFormat,
+    idl_path: &Path,
+    output_dir: &Path,
+) -> DfxResult<PathBuf> {
+    let source = dfx_core::fs::read_to_string(idl_path)?;
+    let (env, actor) = CandidSource::File(idl_path).load()?;
+    let content = render(format, &interface_doc(canister_name, &source, &env, &actor));
+    dfx_core::fs::create_dir_all(output_dir)?;
+    let output_path = output_dir
+        .join(canister_name)
+        .with_extension(format.extension());
+    dfx_core::fs::write(&output_path, content)?;
+    Ok(output_path)
+}
+
+fn interface_doc(
+    canister_name: &str,
+    source: &str,
+    env: &TypeEnv,
+    actor: &Option<Type>,
+) -> InterfaceDoc {
+    let comments = doc_comments(source);
+    let mut doc = InterfaceDoc {
+        canister_name: canister_name.to_string(),
+        service_doc: comments.service,
+        init: None,
+        methods: vec![],
+        types: vec![],
+    };
+    if let Some(actor) = actor {
+        let service = match actor.as_ref() {
+            TypeInner::Class(args, service) => {
+                doc.init = Some(args_signature(args));
+                service
+            }
+            _ => actor,
+        };
+        for (name, method) in env.as_service(service).unwrap() {
+            let Ok(func) = env.as_func(method) else {
+                continue;
+            };
+            let modes = func
+                .modes
+                .iter()
+                .map(|mode| match mode {
+                    FuncMode::Query => " query",
+                    FuncMode::CompositeQuery => " composite_query",
+                    FuncMode::Oneway => " oneway",
+                })
+                .collect::<String>();
+            doc.methods.push(Item {
+                name: name.clone(),
+                signature: format!(
+                    "{} : {} -> {}{};",
+                    name,
+                    args_signature(&func.args),
+                    args_signature(&func.rets),
+                    modes
+                ),
+                doc: comments.methods.get(name).cloned(),
+            });
+        }
+    }
+    for (name, ty) in &env.0 {
+        doc.types.push(Item {
+            name: name.clone(),
+            signature: format!("type {} = {};", name, ty),
+            doc: comments.types.get(name).cloned(),
+        });
+    }
+    doc
+}
+
+fn args_signature(args: &[Type]) -> String {
+    format!(
+        "({})",
+        args.iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[derive(Default)]
+struct DocComments {
+    service: Option<String>,
+    methods: BTreeMap<String, String>,
+    types: BTreeMap<String, String>,
+}
+
+/// Collects the `//` comments directly above the service, its methods, and the type definitions.
+/// The Candid parser drops comments, so they are taken from the source.
+fn doc_comments(source: &str) -> DocComments {
+    let mut comments = DocComments::default();
+    let mut pending: Vec<&str> = vec![];
+    let mut in_service = false;
+    for line in source.lines() {
+        let line = line.trim();
+        if let Some(comment) = line.strip_prefix("//") {
+            let comment = comment.trim_start_matches('/');
+            pending.push(comment.strip_prefix(' ').unwrap_or(comment));
+            continue;
+        }
+        let doc = (!pending.is_empty()).then(|| pending.join("\n"));
+        pending.clear();
+        let Some(doc) = doc else {
+            in_service |= line.starts_with("service");
+            continue;
+        };
+        if let Some(rest) = line.strip_prefix("type ") {
+            let name = rest.split(|c: char| c == '=' || c.is_whitespace()).next();
+            if let Some(name) = name.filter(|name| !name.is_empty()) {
+                comments.types.insert(name.to_string(), doc);
+            }
+        } else if line.starts_with("service") {
+            in_service = true;
+            comments.service = Some(doc);
+        } else if in_service {
+            if let Some((name, _)) = line.split_once(':') {
+                let name = name.trim().trim_matches('"');
+                if !name.is_empty() {
+                    comments.methods.insert(name.to_string(), doc);
+                }
+            }
+        }
+    }
+    comments
+}
+
+fn render(format: DocFormat, doc: &InterfaceDoc) -> String {
+    match format {
+        DocFormat::Markdown => render_markdown(doc),
+        DocFormat::Html => render_html(doc),
+    }
+}
+
+fn render_markdown(doc: &InterfaceDoc) -> String {
+    let mut out = format!("# {}\n\n", doc.canister_name);
+    if let Some(service_doc) = &doc.service_doc {
+        out.push_str(&format!("{}\n\n", service_doc));
+    }
+    if let Some(init) = &doc.init {
+        out.push_str(&format!(
+            "## Initialization\n\n```candid\n{}\n```\n\n",
+            init
+        ));
+    }
+    for (title, items) in [("Methods", &doc.methods), ("Types", &doc.types)] {
+        if items.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("## {}\n\n", title));
+        for item in items {
+            out.push_str(&format!(
+                "### `{}`\n\n```candid\n{}\n```\n\n",
+                item.name, item.signature
+            ));
+            if let Some(item_doc) = &item.doc {
+                out.push_str(&format!("{}\n\n", item_doc));
+            }
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+fn render_html(doc: &InterfaceDoc) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        escape_html(&doc.canister_name)
+    );
+    if let Some(service_doc) = &doc.service_doc {
+        out.push_str(&html_paragraphs(service_doc));
+    }
+    if let Some(init) = &doc.init {
+        out.push_str(&format!(
+            "<h2>Initialization</h2>\n<pre><code>{}</code></pre>\n",
+            escape_html(init)
+        ));
+    }
+    for (title, items) in [("Methods", &doc.methods), ("Types", &doc.types)] {
+        if items.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("<h2>{}</h2>\n", title));
+        for item in items {
+            out.push_str(&format!(
+                "<h3 id=\"{0}\"><code>{0}</code></h3>\n<pre><code>{1}</code></pre>\n",
+                escape_html(&item.name),
+                escape_html(&item.signature)
+            ));
+            if let Some(item_doc) = &item.doc {
+                out.push_str(&html_paragraphs(item_doc));
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn html_paragraphs(text: &str) -> String {
+    text.split("\n\n")
+        .map(|paragraph| format!("<p>{}</p>\n", escape_html(paragraph)))
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID: &str = r#"
+// An account of the ledger.
+type Account = record { owner : principal; subaccount : opt blob };
+// The ledger of the project.
+service : (nat) -> {
+  /// The balance of an account, in tokens.
+  ///
+  /// Accounts that were never used have a balance of 0.
+  balance : (Account) -> (nat) query;
+  reset : () -> () oneway;
+}
+"#;
+
+    fn interface() -> InterfaceDoc {
+        let (env, actor) = CandidSource::Text(DID).load().unwrap();
+        interface_doc("ledger", DID, &env, &actor)
+    }
+
+    #[test]
+    fn collects_doc_comments() {
+        let comments = doc_comments(DID);
+        assert_eq!(
+            comments.service.as_deref(),
+            Some("The ledger of the project.")
+        );
+        assert_eq!(
+            comments.methods["balance"],
+            "The balance of an account, in tokens.\n\nAccounts that were never used have a balance of 0."
+        );
+        assert!(!comments.methods.contains_key("reset"));
+        assert_eq!(comments.types["Account"], "An account of the ledger.");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = render(DocFormat::Markdown, &interface());
+        assert!(markdown.starts_with("# ledger\n\nThe ledger of the project.\n"));
+        assert!(markdown.contains("## Initialization\n\n```candid\n(nat)\n```"));
+        assert!(markdown.contains(
+            "### `balance`\n\n```candid\nbalance : (Account) -> (nat) query;\n```\n\nThe balance of an account, in tokens."
+        ));
+        assert!(markdown.contains("reset : () -> () oneway;"));
+        assert!(markdown.contains("### `Account`"));
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = render(DocFormat::Html, &interface());
+        assert!(html.contains("<h1>ledger</h1>"));
+        assert!(html.contains("<p>Accounts that were never used have a balance of 0.</p>"));
+        assert!(html.contains("balance : (Account) -&gt; (nat) query;"));
+    }
+}
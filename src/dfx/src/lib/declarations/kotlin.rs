@@ -0,0 +1,290 @@
+//! Kotlin type declarations, and an interface with a function per method of the canister.
+//!
+//! The declarations do not depend on a particular agent, except for principals and references,
+//! which use the types of ic4j.
+use super::{is_tuple, to_pascal_case, BindingEmitter};
+use candid::types::{Field, FuncMode, Label, Type, TypeEnv, TypeInner};
+use candid_parser::bindings::analysis::chase_actor;
+use std::collections::BTreeSet;
+
+const KEYWORDS: [&str; 28] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+pub(super) struct KotlinEmitter;
+
+impl BindingEmitter for KotlinEmitter {
+    fn extension(&self) -> &'static str {
+        "kt"
+    }
+
+    fn emit(&self, canister_name: &str, env: &TypeEnv, actor: &Option<Type>) -> String {
+        let def_list: Vec<&str> = match actor {
+            Some(actor) => chase_actor(env, actor).unwrap(),
+            None => env.0.keys().map(String::as_str).collect(),
+        };
+        let mut declarations = Declarations {
+            items: vec![],
+            names: def_list.iter().map(|id| id.to_string()).collect(),
+        };
+        for id in &def_list {
+            declarations.named(id, env.find_type(id).unwrap());
+        }
+        if let Some(actor) = actor {
+            declarations.service(canister_name, env, actor);
+        }
+
+        format!(
+            "// The interface of canister '{}'.\nimport java.math.BigInteger\nimport org.ic4j.types.Principal\n\n{}\n",
+            canister_name,
+            declarations.items.join("\n\n")
+        )
+    }
+}
+
+struct Declarations {
+    items: Vec<String>,
+    /// Names of the declared types, so that generated names do not clash with them.
+    names: BTreeSet<String>,
+}
+
+impl Declarations {
+    fn named(&mut self, id: &str, t: &Type) {
+        let item = match t.as_ref() {
+            TypeInner::Record(fields) if !is_tuple(fields) => self.record(id, fields),
+            TypeInner::Variant(fields) => self.variant(id, fields),
+            _ => format!("typealias {} = {}", ident(id), self.ty(t, id)),
+        };
+        self.items.push(item);
+    }
+
+    fn service(&mut self, canister_name: &str, env: &TypeEnv, actor: &Type) {
+        let methods = env.as_service(actor).unwrap();
+        let mut functions = vec![];
+        for (name, func) in methods {
+            let func = env.as_func(func).unwrap();
+            let method = to_pascal_case(name);
+            let params = func
+                .args
+                .iter()
+                .enumerate()
+                .map(|(i, t)| format!("arg{}: {}", i, self.ty(t, &format!("{method}Arg{i}"))))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let ret = match func.rets.as_slice() {
+                [] => String::new(),
+                [t] => format!(": {}", self.ty(t, &format!("{method}Ret"))),
+                rets => {
+                    let name = self.fresh_name(&format!("{method}Result"));
+                    let fields = rets
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| format!("val _{}: {}", i, self.ty(t, &format!("{name}{i}"))))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    self.items
+                        .push(format!("data class {}({})", ident(&name), fields));
+                    format!(": {}", ident(&name))
+                }
+            };
+            let mode = match func.modes.first() {
+                Some(FuncMode::Query) => "    // query\n",
+                Some(FuncMode::CompositeQuery) => "    // composite query\n",
+                Some(FuncMode::Oneway) => "    // oneway\n",
+                None => "",
+            };
+            functions.push(format!(
+                "{}    suspend fun {}({}){}",
+                mode,
+                ident(name),
+                params,
+                ret
+            ));
+        }
+        self.items.push(format!(
+            "interface {} {{\n{}\n}}",
+            ident(&to_pascal_case(canister_name)),
+            functions.join("\n")
+        ));
+    }
+
+    fn record(&mut self, name: &str, fields: &[Field]) -> String {
+        if fields.is_empty() {
+            return format!("class {}", ident(name));
+        }
+        let fields = fields
+            .iter()
+            .map(|field| {
+                let path = format!("{}{}", name, to_pascal_case(&label_name(&field.id)));
+                format!(
+                    "val {}: {}",
+                    field_name(&field.id),
+                    self.ty(&field.ty, &path)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("data class {}({})", ident(name), fields)
+    }
+
+    fn variant(&mut self, name: &str, fields: &[Field]) -> String {
+        let cases = fields
+            .iter()
+            .map(|field| {
+                let case = case_name(&field.id);
+                match field.ty.as_ref() {
+                    TypeInner::Null => format!("    object {} : {}()", case, ident(name)),
+                    _ => {
+                        let t = self.ty(&field.ty, &format!("{}{}", name, case));
+                        format!(
+                            "    data class {}(val value: {}) : {}()",
+                            case,
+                            t,
+                            ident(name)
+                        )
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("sealed class {} {{\n{}\n}}", ident(name), cases)
+    }
+
+    /// The Kotlin type of a candid type. Anonymous records and variants are declared as classes named after `path`.
+    fn ty(&mut self, t: &Type, path: &str) -> String {
+        use TypeInner::*;
+        match t.as_ref() {
+            Null => "Unit".to_string(),
+            Bool => "Boolean".to_string(),
+            Nat | Int => "BigInteger".to_string(),
+            Nat8 => "UByte".to_string(),
+            Nat16 => "UShort".to_string(),
+            Nat32 => "UInt".to_string(),
+            Nat64 => "ULong".to_string(),
+            Int8 => "Byte".to_string(),
+            Int16 => "Short".to_string(),
+            Int32 => "Int".to_string(),
+            Int64 => "Long".to_string(),
+            Float32 => "Float".to_string(),
+            Float64 => "Double".to_string(),
+            Text => "String".to_string(),
+            Reserved => "Any?".to_string(),
+            Empty => "Nothing".to_string(),
+            Principal => "Principal".to_string(),
+            Var(id) => ident(id),
+            Opt(inner) => {
+                let inner = self.ty(inner, path);
+                if inner.ends_with('?') {
+                    format!("java.util.Optional<{}>", inner)
+                } else {
+                    format!("{}?", inner)
+                }
+            }
+            Vec(inner) if matches!(inner.as_ref(), Nat8) => "ByteArray".to_string(),
+            Vec(inner) => format!("List<{}>", self.ty(inner, path)),
+            Record(fields) if is_tuple(fields) => {
+                let name = self.fresh_name(path);
+                let fields = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        format!("val _{}: {}", i, self.ty(&field.ty, &format!("{name}{i}")))
+                    })
+                    .collect::<std::vec::Vec<_>>()
+                    .join(", ");
+                self.items
+                    .push(format!("data class {}({})", ident(&name), fields));
+                ident(&name)
+            }
+            Record(fields) => {
+                let name = self.fresh_name(path);
+                let item = self.record(&name, fields);
+                self.items.push(item);
+                ident(&name)
+            }
+            Variant(fields) => {
+                let name = self.fresh_name(path);
+                let item = self.variant(&name, fields);
+                self.items.push(item);
+                ident(&name)
+            }
+            Func(_) => "org.ic4j.types.Func".to_string(),
+            Service(_) => "org.ic4j.types.Service".to_string(),
+            Class(_, _) | Knot(_) | Unknown | Future => unreachable!(),
+        }
+    }
+
+    fn fresh_name(&mut self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut i = 1;
+        while !self.names.insert(name.clone()) {
+            i += 1;
+            name = format!("{}{}", base, i);
+        }
+        name
+    }
+}
+
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&id)
+}
+
+fn ident(id: &str) -> String {
+    if is_identifier(id) {
+        id.to_string()
+    } else {
+        format!("`{}`", id)
+    }
+}
+
+fn label_name(label: &Label) -> String {
+    match label {
+        Label::Named(name) => name.clone(),
+        Label::Id(n) | Label::Unnamed(n) => n.to_string(),
+    }
+}
+
+fn field_name(label: &Label) -> String {
+    match label {
+        Label::Named(name) => ident(name),
+        Label::Id(n) | Label::Unnamed(n) => format!("_{}", n),
+    }
+}
+
+fn case_name(label: &Label) -> String {
+    match label {
+        Label::Named(name) if is_identifier(&to_pascal_case(name)) => to_pascal_case(name),
+        Label::Named(name) => ident(name),
+        Label::Id(n) | Label::Unnamed(n) => format!("V{}", n),
+    }
+}
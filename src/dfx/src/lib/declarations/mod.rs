@@ -0,0 +1,145 @@
+//! Emitters for the canister declarations generated by `dfx generate` that consist of a single file.
+//!
+//! The JavaScript and TypeScript declarations are generated by the builders, since they come with
+//! additional files rendered from templates. The `md` and `html` bindings are documentation of the
+//! interface, rendered by the `docs` module.
+use candid::types::{Label, Type, TypeEnv};
+
+pub mod docs;
+mod kotlin;
+mod python;
+
+/// Generates the bindings of a canister interface in one language.
+pub trait BindingEmitter {
+    /// The extension of the generated file, which is named after the canister.
+    fn extension(&self) -> &'static str;
+
+    fn emit(&self, canister_name: &str, env: &TypeEnv, actor: &Option<Type>) -> String;
+}
+
+struct MotokoEmitter;
+
+impl BindingEmitter for MotokoEmitter {
+    fn extension(&self) -> &'static str {
+        "mo"
+    }
+
+    fn emit(&self, _canister_name: &str, env: &TypeEnv, actor: &Option<Type>) -> String {
+        candid_parser::bindings::motoko::compile(env, actor)
+    }
+}
+
+/// Types and a `Service` struct for calling the canister from another Rust canister with ic-cdk.
+struct RustEmitter;
+
+impl BindingEmitter for RustEmitter {
+    fn extension(&self) -> &'static str {
+        "rs"
+    }
+
+    fn emit(&self, _canister_name: &str, env: &TypeEnv, actor: &Option<Type>) -> String {
+        let config = candid_parser::bindings::rust::Config::new();
+        candid_parser::bindings::rust::compile(&config, env, actor)
+    }
+}
+
+/// Returns the emitter for a value of `declarations.bindings`,
+/// or None for the bindings generated by the builders and for unknown bindings.
+pub fn binding_emitter(binding: &str) -> Option<Box<dyn BindingEmitter>> {
+    match binding {
+        "mo" => Some(Box::new(MotokoEmitter)),
+        "rs" => Some(Box::new(RustEmitter)),
+        "py" => Some(Box::new(python::PythonEmitter)),
+        "kt" => Some(Box::new(kotlin::KotlinEmitter)),
+        _ => None,
+    }
+}
+
+/// Whether a record type is a tuple, i.e. its fields are labeled 0, 1, 2...
+fn is_tuple(fields: &[candid::types::Field]) -> bool {
+    !fields.is_empty()
+        && fields
+            .iter()
+            .enumerate()
+            .all(|(i, field)| field.id.get_id() == i as u32)
+}
+
+/// The name of a field label, with numeric labels as `_<n>_`, like in the JavaScript bindings.
+fn label_name(label: &Label) -> String {
+    match label {
+        Label::Named(name) => name.clone(),
+        Label::Id(n) | Label::Unnamed(n) => format!("_{}_", n),
+    }
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid_parser::utils::CandidSource;
+
+    const DID: &str = r#"
+type Tree = variant { leaf : nat; node : record { left : Tree; right : Tree } };
+type Account = record { owner : principal; subaccount : opt blob };
+service : (nat) -> {
+  balance : (Account) -> (nat) query;
+  depth : (Tree) -> (nat32, text);
+  reset : () -> () oneway;
+}
+"#;
+
+    fn emit(binding: &str) -> String {
+        let (env, actor) = CandidSource::Text(DID).load().unwrap();
+        binding_emitter(binding)
+            .unwrap()
+            .emit("ledger_backend", &env, &actor)
+    }
+
+    #[test]
+    fn emits_python() {
+        let python = emit("py");
+        assert!(python.contains("from ic.candid import Types"));
+        assert!(python.contains("Tree = Types.Rec()"));
+        assert!(python.contains(
+            "Account = Types.Record({'owner': Types.Principal, 'subaccount': Types.Opt(Types.Vec(Types.Nat8))})"
+        ));
+        assert!(python.contains("'balance': Types.Func([Account], [Types.Nat], ['query'])"));
+        assert!(python.contains("init = [Types.Nat]"));
+    }
+
+    #[test]
+    fn emits_kotlin() {
+        let kotlin = emit("kt");
+        assert!(
+            kotlin.contains("data class Account(val owner: Principal, val subaccount: ByteArray?)")
+        );
+        assert!(kotlin.contains("sealed class Tree {"));
+        assert!(kotlin.contains("data class Node(val value: TreeNode) : Tree()"));
+        assert!(kotlin.contains("interface LedgerBackend {"));
+        assert!(kotlin.contains("suspend fun balance(arg0: Account): BigInteger"));
+        assert!(kotlin.contains("suspend fun depth(arg0: Tree): DepthResult"));
+    }
+
+    #[test]
+    fn emits_rust() {
+        let rust = emit("rs");
+        assert!(rust.contains("pub struct Account"));
+        assert!(rust.contains("pub struct Service(pub Principal);"));
+    }
+
+    #[test]
+    fn converts_to_pascal_case() {
+        assert_eq!(to_pascal_case("ledger_backend"), "LedgerBackend");
+        assert_eq!(to_pascal_case("get-balance"), "GetBalance");
+    }
+}
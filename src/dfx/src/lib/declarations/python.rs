@@ -0,0 +1,156 @@
+//! Interface definitions for the ic-py agent, mirroring the `idlFactory` of the JavaScript bindings.
+use super::{is_tuple, label_name, BindingEmitter};
+use candid::types::{FuncMode, Function, Type, TypeEnv, TypeInner};
+use candid_parser::bindings::analysis::{chase_actor, chase_types, infer_rec};
+use std::collections::BTreeSet;
+
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+pub(super) struct PythonEmitter;
+
+impl BindingEmitter for PythonEmitter {
+    fn extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn emit(&self, canister_name: &str, env: &TypeEnv, actor: &Option<Type>) -> String {
+        let mut out = format!(
+            "# The interface of canister '{}', for use with ic-py.\nfrom ic.candid import Types\n\n",
+            canister_name
+        );
+        let def_list: Vec<&str> = match actor {
+            Some(actor) => chase_actor(env, actor).unwrap(),
+            None => env.0.keys().map(String::as_str).collect(),
+        };
+        out.push_str(&defs(env, &def_list));
+
+        if let Some(actor) = actor {
+            let (init, service) = match actor.as_ref() {
+                TypeInner::Class(args, service) => (args.as_slice(), service),
+                _ => (&[][..], actor),
+            };
+            out.push_str(&format!("\nservice = {}\n", ty(service)));
+
+            // The init args may refer to types that are not part of the service.
+            let init_defs: Vec<&str> = chase_types(env, init)
+                .unwrap()
+                .into_iter()
+                .filter(|id| !def_list.contains(id))
+                .collect();
+            out.push_str(&defs(env, &init_defs));
+            out.push_str(&format!("init = {}\n", args(init)));
+        }
+        out
+    }
+}
+
+fn defs(env: &TypeEnv, def_list: &[&str]) -> String {
+    let recs: BTreeSet<&str> = infer_rec(env, def_list).unwrap();
+    let mut out = String::new();
+    for id in &recs {
+        out.push_str(&format!("{} = Types.Rec()\n", ident(id)));
+    }
+    for id in def_list {
+        let t = ty(env.find_type(id).unwrap());
+        if recs.contains(id) {
+            out.push_str(&format!("{}.fill({})\n", ident(id), t));
+        } else {
+            out.push_str(&format!("{} = {}\n", ident(id), t));
+        }
+    }
+    out
+}
+
+fn ident(id: &str) -> String {
+    if KEYWORDS.contains(&id) {
+        format!("{}_", id)
+    } else {
+        id.to_string()
+    }
+}
+
+fn ty(t: &Type) -> String {
+    use TypeInner::*;
+    match t.as_ref() {
+        Null => "Types.Null".to_string(),
+        Bool => "Types.Bool".to_string(),
+        Nat => "Types.Nat".to_string(),
+        Int => "Types.Int".to_string(),
+        Nat8 => "Types.Nat8".to_string(),
+        Nat16 => "Types.Nat16".to_string(),
+        Nat32 => "Types.Nat32".to_string(),
+        Nat64 => "Types.Nat64".to_string(),
+        Int8 => "Types.Int8".to_string(),
+        Int16 => "Types.Int16".to_string(),
+        Int32 => "Types.Int32".to_string(),
+        Int64 => "Types.Int64".to_string(),
+        Float32 => "Types.Float32".to_string(),
+        Float64 => "Types.Float64".to_string(),
+        Text => "Types.Text".to_string(),
+        Reserved => "Types.Reserved".to_string(),
+        Empty => "Types.Empty".to_string(),
+        Principal => "Types.Principal".to_string(),
+        Var(id) => ident(id),
+        Opt(t) => format!("Types.Opt({})", ty(t)),
+        Vec(t) => format!("Types.Vec({})", ty(t)),
+        Record(fields) if is_tuple(fields) => format!(
+            "Types.Tuple({})",
+            fields
+                .iter()
+                .map(|field| ty(&field.ty))
+                .collect::<std::vec::Vec<_>>()
+                .join(", ")
+        ),
+        Record(fields) => format!("Types.Record({})", self::fields(fields)),
+        Variant(fields) => format!("Types.Variant({})", self::fields(fields)),
+        Func(func) => function(func),
+        Service(methods) => format!(
+            "Types.Service({{{}}})",
+            methods
+                .iter()
+                .map(|(name, t)| format!("'{}': {}", name, ty(t)))
+                .collect::<std::vec::Vec<_>>()
+                .join(", ")
+        ),
+        Class(_, _) | Knot(_) | Unknown | Future => unreachable!(),
+    }
+}
+
+fn fields(fields: &[candid::types::Field]) -> String {
+    format!(
+        "{{{}}}",
+        fields
+            .iter()
+            .map(|field| format!("'{}': {}", label_name(&field.id), ty(&field.ty)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn function(func: &Function) -> String {
+    let modes = func
+        .modes
+        .iter()
+        .map(|mode| match mode {
+            FuncMode::Query => "'query'",
+            FuncMode::CompositeQuery => "'composite_query'",
+            FuncMode::Oneway => "'oneway'",
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "Types.Func({}, {}, [{}])",
+        args(&func.args),
+        args(&func.rets),
+        modes
+    )
+}
+
+fn args(args: &[Type]) -> String {
+    format!("[{}]", args.iter().map(ty).collect::<Vec<_>>().join(", "))
+}
@@ -211,7 +211,7 @@ fn get_init_json_path(project_root: &Path) -> PathBuf {
     get_deps_dir(project_root).join("init.json")
 }
 
-fn get_pulled_json_path(project_root: &Path) -> PathBuf {
+pub fn get_pulled_json_path(project_root: &Path) -> PathBuf {
     get_deps_dir(project_root).join("pulled.json")
 }
 
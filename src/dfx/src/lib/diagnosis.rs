@@ -1,12 +1,21 @@
 use super::environment::Environment;
 use crate::lib::error_code;
 use anyhow::Error as AnyhowError;
+use dfx_core::config::model::dfinity::CanisterTypeProperties;
+use dfx_core::config::model::local_server_descriptor::LocalServerDescriptor;
+use dfx_core::config::model::network_descriptor::NetworkDescriptor;
+use dfx_core::identity::identity_manager::IdentityStorageMode;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
 use ic_agent::agent::{RejectCode, RejectResponse};
 use ic_agent::AgentError;
 use ic_asset::error::{GatherAssetDescriptorsError, SyncError, UploadContentError};
 use regex::Regex;
+use std::net::TcpListener;
 use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
 use thiserror::Error as ThisError;
+use time::{Date, Month, OffsetDateTime, Time};
 
 /// Contains two Option<Strings> that can be displayed to the user:
 ///   - Error explanation: Goes into a bit of detail on what the error is and/or where the user can find out more about it.
@@ -144,3 +153,349 @@ See also release notes: https://forum.dfinity.org/t/dfx-0-11-0-is-promoted-with-
 
     (Some(explanation.to_string()), Some(suggestion.to_string()))
 }
+
+/// The severity of a problem found by `dfx doctor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// The outcome of one of the environment checks run by `dfx doctor`.
+pub struct EnvironmentCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub details: String,
+    /// What the user can do to resolve the problem.
+    pub fix: Option<String>,
+}
+
+impl EnvironmentCheck {
+    fn ok(name: &'static str, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            details: details.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        name: &'static str,
+        status: CheckStatus,
+        details: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            details: details.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Clock differences beyond this make the IC reject ingress messages, which expire after 5 minutes.
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(5 * 60);
+/// Clock differences beyond this are reported, as they eat into the ingress expiry.
+const WARN_CLOCK_SKEW: Duration = Duration::from_secs(30);
+
+/// Checks the environment for problems that commonly break a new setup: missing toolchains,
+/// a busy bind address, an unusable identity, an unreachable network, and clock skew.
+pub fn check_environment(env: &dyn Environment, network: Option<String>) -> Vec<EnvironmentCheck> {
+    let config = env.get_config();
+    let needs_rust = config.as_ref().map_or(false, |config| {
+        config
+            .get_config()
+            .canisters
+            .iter()
+            .flat_map(|canisters| canisters.values())
+            .any(|canister| matches!(canister.type_specific, CanisterTypeProperties::Rust { .. }))
+    });
+    let needs_node = config.as_ref().map_or(false, |config| {
+        config.get_project_root().join("package.json").exists()
+    });
+
+    let mut checks = vec![
+        check_program(
+            "Cargo",
+            "cargo",
+            needs_rust,
+            "Install Rust from https://rustup.rs.",
+        ),
+        check_wasm32_target(needs_rust),
+        check_program(
+            "Node.js",
+            "node",
+            needs_node,
+            "Install Node.js from https://nodejs.org.",
+        ),
+        check_identity(env),
+    ];
+
+    match create_network_descriptor(
+        config,
+        env.get_networks_config(),
+        network,
+        None,
+        LocalBindDetermination::AsConfigured,
+    ) {
+        Ok(network_descriptor) => {
+            if let Some(local_server_descriptor) = &network_descriptor.local_server_descriptor {
+                checks.push(check_bind_address(local_server_descriptor));
+            }
+            checks.extend(check_network(&network_descriptor));
+        }
+        Err(err) => checks.push(EnvironmentCheck::problem(
+            "Network",
+            CheckStatus::Error,
+            format!("{err:#}"),
+            "Check the network configuration in dfx.json and networks.json.",
+        )),
+    }
+    checks
+}
+
+fn run_version_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|err| format!("{program} was not found: {err}"))?;
+    if !output.status.success() {
+        return Err(format!("`{program} {}` failed", args.join(" ")));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn check_program(name: &'static str, program: &str, required: bool, fix: &str) -> EnvironmentCheck {
+    match run_version_command(program, &["--version"]) {
+        Ok(version) => EnvironmentCheck::ok(name, version),
+        Err(err) => {
+            let status = if required {
+                CheckStatus::Error
+            } else {
+                CheckStatus::Warning
+            };
+            EnvironmentCheck::problem(name, status, err, fix)
+        }
+    }
+}
+
+fn check_wasm32_target(required: bool) -> EnvironmentCheck {
+    const NAME: &str = "wasm32 target";
+    const TARGET: &str = "wasm32-unknown-unknown";
+    let status = if required {
+        CheckStatus::Error
+    } else {
+        CheckStatus::Warning
+    };
+    match run_version_command("rustup", &["target", "list", "--installed"]) {
+        Ok(targets) if targets.lines().any(|target| target.trim() == TARGET) => {
+            EnvironmentCheck::ok(NAME, format!("{TARGET} is installed"))
+        }
+        Ok(_) => EnvironmentCheck::problem(
+            NAME,
+            status,
+            format!("{TARGET} is not installed"),
+            format!("Run `rustup target add {TARGET}`."),
+        ),
+        Err(err) => EnvironmentCheck::problem(
+            NAME,
+            status,
+            err,
+            format!(
+                "Install rustup from https://rustup.rs, then run `rustup target add {TARGET}`."
+            ),
+        ),
+    }
+}
+
+fn check_identity(env: &dyn Environment) -> EnvironmentCheck {
+    const NAME: &str = "Identity";
+    let fix = "Run `dfx identity list` to see the available identities, and `dfx identity use <name>` to select one.";
+    let log = env.get_logger();
+    let mut identity_manager = match env.new_identity_manager() {
+        Ok(identity_manager) => identity_manager,
+        Err(err) => {
+            return EnvironmentCheck::problem(NAME, CheckStatus::Error, format!("{err:#}"), fix)
+        }
+    };
+    let name = identity_manager.get_selected_identity_name().clone();
+    let storage_mode = match identity_manager.get_identity_config_or_default(&name) {
+        Ok(config) => config.storage_mode(),
+        Err(err) => {
+            return EnvironmentCheck::problem(NAME, CheckStatus::Error, format!("{err:#}"), fix)
+        }
+    };
+    // Loading these identities would prompt for a password or a PIN.
+    if matches!(
+        storage_mode,
+        None | Some(IdentityStorageMode::PasswordProtected)
+    ) {
+        return match identity_manager.require_identity_exists(log, &name) {
+            Ok(()) => EnvironmentCheck::ok(NAME, format!("'{name}' (not loaded, it is protected)")),
+            Err(err) => {
+                EnvironmentCheck::problem(NAME, CheckStatus::Error, format!("{err:#}"), fix)
+            }
+        };
+    }
+    match identity_manager.instantiate_identity_from_name(&name, log) {
+        Ok(_) => match identity_manager.get_selected_identity_principal() {
+            Some(principal) => EnvironmentCheck::ok(NAME, format!("'{name}' ({principal})")),
+            None => EnvironmentCheck::ok(NAME, format!("'{name}'")),
+        },
+        Err(err) => EnvironmentCheck::problem(NAME, CheckStatus::Error, format!("{err:#}"), fix),
+    }
+}
+
+fn check_bind_address(local_server_descriptor: &LocalServerDescriptor) -> EnvironmentCheck {
+    const NAME: &str = "Bind address";
+    let address = local_server_descriptor.bind_address;
+    let pid_path = local_server_descriptor.dfx_pid_path();
+    if dfx_core::fs::read_to_string(&pid_path).map_or(false, |pid| !pid.trim().is_empty()) {
+        return EnvironmentCheck::ok(
+            NAME,
+            format!("{address} is used by the running local network"),
+        );
+    }
+    match TcpListener::bind(address) {
+        Ok(_) => EnvironmentCheck::ok(NAME, format!("{address} is available")),
+        Err(err) => EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Error,
+            format!("{address} is not available: {err}"),
+            format!(
+                "Stop the program that uses port {}, or set another address with `networks.local.bind` in dfx.json or networks.json.",
+                address.port()
+            ),
+        ),
+    }
+}
+
+fn check_network(network_descriptor: &NetworkDescriptor) -> Vec<EnvironmentCheck> {
+    const NAME: &str = "Network";
+    let is_local = network_descriptor.local_server_descriptor.is_some();
+    let url = match network_descriptor.first_provider() {
+        Ok(url) => url.trim_end_matches('/').to_string(),
+        Err(err) => {
+            return vec![EnvironmentCheck::problem(
+                NAME,
+                CheckStatus::Error,
+                format!("{err:#}"),
+                "Configure a provider for the network in dfx.json or networks.json.",
+            )]
+        }
+    };
+    let response = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .and_then(|client| {
+            let start = Instant::now();
+            let response = client.get(format!("{url}/api/v2/status")).send()?;
+            Ok((response, start.elapsed()))
+        });
+    let (response, latency) = match response {
+        Ok(response) => response,
+        Err(_) if is_local => {
+            return vec![EnvironmentCheck::problem(
+                NAME,
+                CheckStatus::Warning,
+                format!("The local network is not running at {url}"),
+                "Run `dfx start --background`.",
+            )];
+        }
+        Err(err) => {
+            return vec![EnvironmentCheck::problem(
+                NAME,
+                CheckStatus::Error,
+                format!("{url} is not reachable: {err}"),
+                "Check your internet connection and proxy settings.",
+            )];
+        }
+    };
+    let mut checks = vec![EnvironmentCheck::ok(
+        NAME,
+        format!(
+            "'{}' is reachable at {url} ({} ms)",
+            network_descriptor.name,
+            latency.as_millis()
+        ),
+    )];
+
+    // A local network runs on this machine's clock, so only remote networks can reveal skew.
+    if !is_local {
+        let server_time = response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|date| date.to_str().ok())
+            .and_then(parse_http_date);
+        checks.push(match server_time {
+            Some(server_time) => check_clock_skew(&url, server_time),
+            None => EnvironmentCheck::problem(
+                "Clock",
+                CheckStatus::Warning,
+                format!("{url} did not report its time"),
+                "Make sure your system clock is synchronized, e.g. with NTP.",
+            ),
+        });
+    }
+    checks
+}
+
+fn check_clock_skew(url: &str, server_time: OffsetDateTime) -> EnvironmentCheck {
+    const NAME: &str = "Clock";
+    let skew = Duration::from_secs(
+        (OffsetDateTime::now_utc() - server_time)
+            .whole_seconds()
+            .unsigned_abs(),
+    );
+    let details = format!("{} s difference with {url}", skew.as_secs());
+    let fix = "Synchronize your system clock, e.g. by enabling NTP.";
+    if skew > MAX_CLOCK_SKEW {
+        EnvironmentCheck::problem(NAME, CheckStatus::Error, details, fix)
+    } else if skew > WARN_CLOCK_SKEW {
+        EnvironmentCheck::problem(NAME, CheckStatus::Warning, details, fix)
+    } else {
+        EnvironmentCheck::ok(NAME, details)
+    }
+}
+
+/// Parses an HTTP `Date` header in the IMF-fixdate format, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn parse_http_date(date: &str) -> Option<OffsetDateTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let [_, day, month, year, time, "GMT"] = date.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let month = MONTHS.iter().position(|m| *m == month)? as u8 + 1;
+    let date = Date::from_calendar_date(
+        year.parse().ok()?,
+        Month::try_from(month).ok()?,
+        day.parse().ok()?,
+    )
+    .ok()?;
+    let [hour, minute, second] = time
+        .split(':')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u8>>>()?[..]
+    else {
+        return None;
+    };
+    let time = Time::from_hms(hour, minute, second).ok()?;
+    Some(date.with_time(time).assume_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_http_dates() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(date.unix_timestamp(), 784111777);
+        assert!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT").is_none());
+        assert!(parse_http_date("Sun, 06 Nov 1994 08:49:37 CET").is_none());
+    }
+}
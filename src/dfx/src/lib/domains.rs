@@ -0,0 +1,215 @@
+//! Custom domains for asset canisters, registered with the boundary nodes.
+//!
+//! See https://internetcomputer.org/docs/current/developer-docs/production/custom-domain/
+use crate::lib::error::DfxResult;
+use anyhow::{bail, Context};
+use candid::Principal;
+use dfx_core::json::{load_json_file, save_json_file};
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+pub const DEFAULT_BOUNDARY_NODE_URL: &str = "https://icp0.io";
+const DOMAINS_FILE: &str = ".well-known/ic-domains";
+const ASSETS_CONFIG_FILE: &str = ".ic-assets.json";
+const REGISTRATIONS_FILE: &str = "domains.json";
+
+/// Registration requests submitted for the custom domains of a project.
+#[derive(Serialize, Deserialize, Default)]
+pub struct DomainsJson {
+    pub registrations: BTreeMap<String, DomainRegistration>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DomainRegistration {
+    pub canister: Principal,
+    pub request_id: String,
+}
+
+#[derive(Deserialize)]
+struct RegistrationResponse {
+    id: String,
+}
+
+#[derive(Deserialize)]
+pub struct RegistrationStatus {
+    pub name: String,
+    pub canister: Principal,
+    pub state: Value,
+}
+
+impl RegistrationStatus {
+    /// Formats the registration state, which is either a string or `{ "Failed": <reason> }`.
+    pub fn state_description(&self) -> String {
+        match &self.state {
+            Value::String(state) => state.clone(),
+            Value::Object(map) => match map.get("Failed") {
+                Some(reason) => format!("Failed: {}", reason.as_str().unwrap_or_default()),
+                None => self.state.to_string(),
+            },
+            other => other.to_string(),
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        match &self.state {
+            Value::String(state) => state == "Available",
+            _ => true,
+        }
+    }
+}
+
+fn get_registrations_path(project_temp_dir: &Path) -> PathBuf {
+    project_temp_dir.join(REGISTRATIONS_FILE)
+}
+
+#[context("Failed to load domain registrations.")]
+pub fn load_domains_json(project_temp_dir: &Path) -> DfxResult<DomainsJson> {
+    let path = get_registrations_path(project_temp_dir);
+    if path.exists() {
+        Ok(load_json_file(&path)?)
+    } else {
+        Ok(DomainsJson::default())
+    }
+}
+
+#[context("Failed to save domain registrations.")]
+pub fn save_domains_json(project_temp_dir: &Path, domains_json: &DomainsJson) -> DfxResult {
+    let path = get_registrations_path(project_temp_dir);
+    dfx_core::fs::composite::ensure_parent_dir_exists(&path)?;
+    save_json_file(&path, domains_json)?;
+    Ok(())
+}
+
+/// Adds the domain to the `.well-known/ic-domains` file of the asset source directory,
+/// and makes sure the `.well-known` directory is not ignored when uploading assets.
+///
+/// Returns the path of the `ic-domains` file.
+#[context("Failed to add domain '{}' to {}.", domain, source_dir.display())]
+pub fn add_domain_to_assets(source_dir: &Path, domain: &str) -> DfxResult<PathBuf> {
+    let domains_path = source_dir.join(DOMAINS_FILE);
+    let mut domains = if domains_path.exists() {
+        read_domains(&domains_path)?
+    } else {
+        vec![]
+    };
+    if !domains.iter().any(|d| d == domain) {
+        domains.push(domain.to_string());
+        dfx_core::fs::composite::ensure_parent_dir_exists(&domains_path)?;
+        dfx_core::fs::write(&domains_path, format!("{}\n", domains.join("\n")))?;
+    }
+
+    let assets_config_path = source_dir.join(ASSETS_CONFIG_FILE);
+    let assets_config_json5_path = source_dir.join(format!("{ASSETS_CONFIG_FILE}5"));
+    if !assets_config_path.exists() && !assets_config_json5_path.exists() {
+        let config = serde_json::json!([{ "match": ".well-known", "ignore": false }]);
+        save_json_file(&assets_config_path, &config)?;
+    }
+    Ok(domains_path)
+}
+
+/// Reads the custom domains listed in the `.well-known/ic-domains` file of the asset source directory.
+pub fn read_domains_from_assets(source_dir: &Path) -> DfxResult<Vec<String>> {
+    let domains_path = source_dir.join(DOMAINS_FILE);
+    if domains_path.exists() {
+        read_domains(&domains_path)
+    } else {
+        Ok(vec![])
+    }
+}
+
+fn read_domains(domains_path: &Path) -> DfxResult<Vec<String>> {
+    Ok(dfx_core::fs::read_to_string(domains_path)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Returns true if the asset configuration in the source directory mentions the `.well-known` directory.
+pub fn assets_config_includes_well_known(source_dir: &Path) -> bool {
+    [
+        source_dir.join(ASSETS_CONFIG_FILE),
+        source_dir.join(format!("{ASSETS_CONFIG_FILE}5")),
+    ]
+    .iter()
+    .filter_map(|path| dfx_core::fs::read_to_string(path).ok())
+    .any(|content| content.contains(".well-known"))
+}
+
+/// The DNS records a domain needs before it can be registered.
+pub fn dns_records(domain: &str, canister_id: &Principal) -> Vec<(String, &'static str, String)> {
+    vec![
+        (domain.to_string(), "CNAME", format!("{domain}.icp1.io")),
+        (
+            format!("_canister-id.{domain}"),
+            "TXT",
+            canister_id.to_text(),
+        ),
+        (
+            format!("_acme-challenge.{domain}"),
+            "CNAME",
+            format!("_acme-challenge.{domain}.icp2.io"),
+        ),
+    ]
+}
+
+fn registrations_url(boundary_node: &Url, request_id: Option<&str>) -> DfxResult<Url> {
+    let path = match request_id {
+        Some(id) => format!("registrations/{id}"),
+        None => "registrations".to_string(),
+    };
+    boundary_node
+        .join(&path)
+        .with_context(|| format!("Invalid boundary node url {}.", boundary_node))
+}
+
+fn http_client() -> DfxResult<reqwest::Client> {
+    reqwest::Client::builder()
+        .use_rustls_tls()
+        .build()
+        .context("Could not create HTTP client.")
+}
+
+/// Submits a registration request for a domain, returning the request id.
+#[context("Failed to register domain '{}' with {}.", domain, boundary_node)]
+pub async fn register_domain(boundary_node: &Url, domain: &str) -> DfxResult<String> {
+    let response = http_client()?
+        .post(registrations_url(boundary_node, None)?)
+        .json(&serde_json::json!({ "name": domain }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        bail!("The boundary node rejected the registration ({status}): {body}");
+    }
+    let response: RegistrationResponse = response
+        .json()
+        .await
+        .context("Failed to parse the registration response.")?;
+    Ok(response.id)
+}
+
+#[context("Failed to get the status of registration request '{}'.", request_id)]
+pub async fn get_registration_status(
+    boundary_node: &Url,
+    request_id: &str,
+) -> DfxResult<RegistrationStatus> {
+    let response = http_client()?
+        .get(registrations_url(boundary_node, Some(request_id))?)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        bail!("The boundary node returned an error ({status}): {body}");
+    }
+    response
+        .json()
+        .await
+        .context("Failed to parse the registration status.")
+}
@@ -1,21 +1,28 @@
 use crate::config::cache::DiskBasedCache;
 use crate::config::dfx_version;
 use crate::lib::error::DfxResult;
+use crate::lib::failover::FailoverTransport;
+use crate::lib::logger::LogFormat;
 use crate::lib::progress_bar::ProgressBar;
+use crate::lib::retryable::RetryTransport;
 use crate::lib::warning::{is_warning_disabled, DfxWarning::MainnetPlainTextIdentity};
 use anyhow::{anyhow, Context};
 use candid::Principal;
 use dfx_core::config::cache::Cache;
 use dfx_core::config::model::canister_id_store::CanisterIdStore;
-use dfx_core::config::model::dfinity::{Config, NetworksConfig};
+use dfx_core::config::model::dfinity::{Config, ConfigNetworkConnection, NetworksConfig};
 use dfx_core::config::model::network_descriptor::NetworkDescriptor;
 use dfx_core::error::canister_id_store::CanisterIdStoreError;
 use dfx_core::error::extension::ExtensionError;
 use dfx_core::error::identity::new_identity_manager::NewIdentityManagerError;
 use dfx_core::extension::manager::ExtensionManager;
 use dfx_core::identity::identity_manager::IdentityManager;
+use dfx_core::identity::Identity as DfxIdentity;
 use fn_error_context::context;
-use ic_agent::{Agent, Identity};
+use ic_agent::agent::http_transport::ReqwestTransport;
+use ic_agent::agent::{EnvelopeContent, Transport};
+use ic_agent::identity::{Delegation, SignedDelegation};
+use ic_agent::{Agent, Identity, Signature};
 use semver::Version;
 use slog::{warn, Logger, Record};
 use std::borrow::Cow;
@@ -90,6 +97,7 @@ pub struct EnvironmentImpl {
 
     logger: Option<slog::Logger>,
     verbose_level: i64,
+    log_format: LogFormat,
 
     identity_override: Option<String>,
 
@@ -97,9 +105,10 @@ pub struct EnvironmentImpl {
 }
 
 impl EnvironmentImpl {
-    pub fn new() -> DfxResult<Self> {
+    /// `overlay` selects an environment overlay to merge over dfx.json.
+    pub fn new(overlay: Option<&str>) -> DfxResult<Self> {
         let shared_networks_config = NetworksConfig::new()?;
-        let config = Config::from_current_dir()?;
+        let config = Config::from_current_dir(overlay)?;
         if let Some(ref config) = config {
             let temp_dir = config.get_temp_path();
             create_dir_all(&temp_dir).with_context(|| {
@@ -141,6 +150,7 @@ impl EnvironmentImpl {
             version: version.clone(),
             logger: None,
             verbose_level: 0,
+            log_format: LogFormat::Text,
             identity_override: None,
             effective_canister_id: Principal::from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 1, 1]),
         })
@@ -151,8 +161,13 @@ impl EnvironmentImpl {
         self
     }
 
+    /// Without an identity selected on the command line, the project's default identity is used, if it has one.
     pub fn with_identity_override(mut self, identity: Option<String>) -> Self {
-        self.identity_override = identity;
+        self.identity_override = identity.or_else(|| {
+            self.config
+                .as_ref()
+                .and_then(|config| config.get_config().get_defaults().identity.clone())
+        });
         self
     }
 
@@ -161,6 +176,11 @@ impl EnvironmentImpl {
         self
     }
 
+    pub fn with_log_format(mut self, log_format: LogFormat) -> Self {
+        self.log_format = log_format;
+        self
+    }
+
     pub fn with_effective_canister_id(mut self, effective_canister_id: Option<String>) -> Self {
         match effective_canister_id {
             None => self,
@@ -231,8 +251,11 @@ impl Environment for EnvironmentImpl {
     }
 
     fn new_spinner(&self, message: Cow<'static, str>) -> ProgressBar {
-        // Only show the progress bar if the level is INFO or more.
-        if self.verbose_level >= 0 {
+        if self.log_format == LogFormat::Json {
+            // A spinner would garble the JSON output.
+            ProgressBar::new_event_spinner(self.get_logger(), message)
+        } else if self.verbose_level >= 0 {
+            // Only show the progress bar if the level is INFO or more.
             ProgressBar::new_spinner(message)
         } else {
             ProgressBar::discard()
@@ -277,11 +300,41 @@ impl<'a> AgentEnvironment<'a> {
     ) -> DfxResult<Self> {
         let logger = backend.get_logger().clone();
         let mut identity_manager = backend.new_identity_manager()?;
+        // Checked before loading the identity, so that a disallowed identity does not ask for its password.
+        let identity_name =
+            use_identity.unwrap_or_else(|| identity_manager.get_selected_identity_name().as_str());
+        identity_manager.require_network_allowed(identity_name, &network_descriptor.name)?;
         let identity = if let Some(identity_name) = use_identity {
             identity_manager.instantiate_identity_from_name(identity_name, &logger)?
         } else {
             identity_manager.instantiate_selected_identity(&logger)?
         };
+        Self::with_identity(
+            backend,
+            network_descriptor,
+            timeout,
+            identity_manager,
+            Arc::from(identity),
+        )
+    }
+
+    /// Creates an agent environment that signs with an identity that was already instantiated,
+    /// so that one identity can be used on several networks without asking for its password again.
+    /// The caller is responsible for checking that the identity is allowed on the network.
+    /// The principal of the identity manager is set to that of the identity.
+    pub fn with_identity(
+        backend: &'a dyn Environment,
+        network_descriptor: NetworkDescriptor,
+        timeout: Duration,
+        mut identity_manager: IdentityManager,
+        identity: Arc<DfxIdentity>,
+    ) -> DfxResult<Self> {
+        let logger = backend.get_logger().clone();
+        identity_manager.set_selected_identity_principal(
+            identity
+                .sender()
+                .map_err(|err| anyhow!("Failed to get the principal of the identity: {}", err))?,
+        );
         if network_descriptor.is_ic
             && identity.insecure
             && !is_warning_disabled(MainnetPlainTextIdentity)
@@ -289,17 +342,63 @@ impl<'a> AgentEnvironment<'a> {
             warn!(logger, "The {} identity is not stored securely. Do not use it to control a lot of cycles/ICP. Create a new identity with `dfx identity new` \
                 and use it in mainnet-facing commands with the `--identity` flag", identity.name());
         }
-        let url = network_descriptor.first_provider()?;
+        let identity = Box::new(SharedIdentity(identity));
+        let agent = if network_descriptor.providers.len() > 1 {
+            create_failover_agent(
+                logger,
+                &network_descriptor.providers,
+                identity,
+                timeout,
+                &network_descriptor.connection,
+            )?
+        } else {
+            create_agent(
+                logger,
+                network_descriptor.first_provider()?,
+                identity,
+                timeout,
+                &network_descriptor.connection,
+            )?
+        };
 
         Ok(AgentEnvironment {
             backend,
-            agent: create_agent(logger, url, identity, timeout)?,
+            agent,
             network_descriptor: network_descriptor.clone(),
             identity_manager,
         })
     }
 }
 
+/// Signs with an identity that is shared by several agents.
+struct SharedIdentity(Arc<DfxIdentity>);
+
+impl Identity for SharedIdentity {
+    fn sender(&self) -> Result<Principal, String> {
+        self.0.sender()
+    }
+
+    fn public_key(&self) -> Option<Vec<u8>> {
+        self.0.public_key()
+    }
+
+    fn delegation_chain(&self) -> Vec<SignedDelegation> {
+        self.0.delegation_chain()
+    }
+
+    fn sign(&self, content: &EnvelopeContent) -> Result<Signature, String> {
+        self.0.sign(content)
+    }
+
+    fn sign_arbitrary(&self, content: &[u8]) -> Result<Signature, String> {
+        self.0.sign_arbitrary(content)
+    }
+
+    fn sign_delegation(&self, content: &Delegation) -> Result<Signature, String> {
+        self.0.sign_delegation(content)
+    }
+}
+
 impl<'a> Environment for AgentEnvironment<'a> {
     fn get_cache(&self) -> Arc<dyn Cache> {
         self.backend.get_cache()
@@ -382,16 +481,115 @@ pub fn create_agent(
     url: &str,
     identity: Box<dyn Identity + Send + Sync>,
     timeout: Duration,
+    connection: &ConfigNetworkConnection,
+) -> DfxResult<Agent> {
+    let transport = create_transport(url, connection)?;
+    build_agent(transport, identity, timeout, connection)
+}
+
+/// Creates an agent that sends requests to all of the providers of a network in turn,
+/// moving on to the next provider when one cannot be reached.
+#[context("Failed to create agent with urls {}.", providers.join(", "))]
+pub fn create_failover_agent(
+    logger: Logger,
+    providers: &[String],
+    identity: Box<dyn Identity + Send + Sync>,
+    timeout: Duration,
+    connection: &ConfigNetworkConnection,
+) -> DfxResult<Agent> {
+    let transports = providers
+        .iter()
+        .map(|url| Ok((url.clone(), create_transport(url, connection)?)))
+        .collect::<DfxResult<Vec<_>>>()?;
+    let transport = FailoverTransport::new(logger, transports);
+    build_agent(transport, identity, timeout, connection)
+}
+
+fn create_transport(
+    url: &str,
+    connection: &ConfigNetworkConnection,
+) -> DfxResult<ReqwestTransport> {
+    let transport = if connection.http_timeout.is_some()
+        || connection.http_proxy.is_some()
+        || connection.ca_bundle.is_some()
+    {
+        ReqwestTransport::create_with_client(url, create_http_client(connection)?)?
+    } else {
+        ReqwestTransport::create(url)?
+    };
+    Ok(transport)
+}
+
+fn build_agent<T: Transport + 'static>(
+    transport: T,
+    identity: Box<dyn Identity + Send + Sync>,
+    timeout: Duration,
+    connection: &ConfigNetworkConnection,
 ) -> DfxResult<Agent> {
     let disable_query_verification =
         std::env::var("DFX_DISABLE_QUERY_VERIFICATION").is_ok_and(|x| !x.trim().is_empty());
-    let agent = Agent::builder()
-        .with_transport(ic_agent::agent::http_transport::ReqwestTransport::create(
-            url,
-        )?)
+    let builder = match connection.retry {
+        Some(retries) if retries > 0 => Agent::builder().with_transport(RetryTransport::new(
+            transport,
+            retries,
+            connection.backoff,
+        )),
+        _ => Agent::builder().with_transport(transport),
+    };
+    let agent = builder
         .with_boxed_identity(identity)
         .with_verify_query_signatures(!disable_query_verification)
         .with_ingress_expiry(Some(timeout))
         .build()?;
     Ok(agent)
 }
+
+/// Creates the HTTP client for the agent with the timeout, proxy and CA bundle of the network.
+#[context("Failed to create HTTP client.")]
+fn create_http_client(connection: &ConfigNetworkConnection) -> DfxResult<reqwest::Client> {
+    let mut builder = reqwest::Client::builder().use_rustls_tls();
+    if let Some(http_timeout) = connection.http_timeout {
+        builder = builder.timeout(http_timeout);
+    }
+    if let Some(proxy) = &connection.http_proxy {
+        let proxy = reqwest::Proxy::all(proxy)
+            .with_context(|| format!("Invalid proxy URL '{}'.", proxy))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(ca_bundle) = &connection.ca_bundle {
+        let pem = dfx_core::fs::read(ca_bundle)?;
+        for certificate in split_pem_certificates(&pem) {
+            let certificate = reqwest::Certificate::from_pem(certificate)
+                .with_context(|| format!("Invalid certificate in {}.", ca_bundle.display()))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    Ok(builder.build()?)
+}
+
+/// Splits a PEM bundle into its certificates, since reqwest reads only one certificate at a time.
+fn split_pem_certificates(pem: &[u8]) -> Vec<&[u8]> {
+    const END: &[u8] = b"-----END CERTIFICATE-----";
+    let mut certificates = vec![];
+    let mut rest = pem;
+    while let Some(end) = rest.windows(END.len()).position(|window| window == END) {
+        certificates.push(&rest[..end + END.len()]);
+        rest = &rest[end + END.len()..];
+    }
+    certificates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_pem_bundle() {
+        let pem = b"# root\n-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n\
+            -----BEGIN CERTIFICATE-----\nMIIC\n-----END CERTIFICATE-----\n";
+        let certificates = split_pem_certificates(pem);
+        assert_eq!(certificates.len(), 2);
+        assert!(certificates[0].ends_with(b"MIIB\n-----END CERTIFICATE-----"));
+        assert!(certificates[1].starts_with(b"\n-----BEGIN CERTIFICATE-----\nMIIC"));
+    }
+}
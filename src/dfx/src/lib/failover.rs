@@ -0,0 +1,87 @@
+use crate::lib::retryable::retryable;
+use candid::Principal;
+use ic_agent::agent::{AgentFuture, Transport};
+use ic_agent::AgentError;
+use slog::{warn, Logger};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Sends requests to the providers of a network in turn: a request that fails to reach a provider
+/// is sent to the next one, which then stays in use until it fails in turn.
+/// Errors returned by a provider that was reached, like a rejected call, are not retried.
+pub struct FailoverTransport<T> {
+    logger: Logger,
+    providers: Vec<(String, T)>,
+    current: AtomicUsize,
+}
+
+impl<T: Transport> FailoverTransport<T> {
+    pub fn new(logger: Logger, providers: Vec<(String, T)>) -> Self {
+        assert!(!providers.is_empty());
+        Self {
+            logger,
+            providers,
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    async fn with_failover<'a, V>(
+        &'a self,
+        request: impl Fn(&'a T) -> AgentFuture<'a, V>,
+    ) -> Result<V, AgentError> {
+        let start = self.current.load(Ordering::Relaxed);
+        let mut index = start;
+        loop {
+            let (url, transport) = &self.providers[index];
+            match request(transport).await {
+                Err(err) if retryable(&err) => {
+                    index = (index + 1) % self.providers.len();
+                    if index == start {
+                        return Err(err);
+                    }
+                    warn!(
+                        self.logger,
+                        "Provider {} failed ({}), trying {}.", url, err, self.providers[index].0
+                    );
+                }
+                result => {
+                    self.current.store(index, Ordering::Relaxed);
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+impl<T: Transport> Transport for FailoverTransport<T> {
+    fn call(&self, effective_canister_id: Principal, envelope: Vec<u8>) -> AgentFuture<()> {
+        Box::pin(self.with_failover(move |transport| {
+            transport.call(effective_canister_id, envelope.clone())
+        }))
+    }
+
+    fn read_state(
+        &self,
+        effective_canister_id: Principal,
+        envelope: Vec<u8>,
+    ) -> AgentFuture<Vec<u8>> {
+        Box::pin(self.with_failover(move |transport| {
+            transport.read_state(effective_canister_id, envelope.clone())
+        }))
+    }
+
+    fn read_subnet_state(&self, subnet_id: Principal, envelope: Vec<u8>) -> AgentFuture<Vec<u8>> {
+        Box::pin(self.with_failover(move |transport| {
+            transport.read_subnet_state(subnet_id, envelope.clone())
+        }))
+    }
+
+    fn query(&self, effective_canister_id: Principal, envelope: Vec<u8>) -> AgentFuture<Vec<u8>> {
+        Box::pin(self.with_failover(move |transport| {
+            transport.query(effective_canister_id, envelope.clone())
+        }))
+    }
+
+    fn status(&self) -> AgentFuture<Vec<u8>> {
+        Box::pin(self.with_failover(move |transport| transport.status()))
+    }
+}
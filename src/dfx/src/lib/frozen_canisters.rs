@@ -0,0 +1,64 @@
+//! The settings that `dfx canister freeze` replaced, so that `dfx canister unfreeze` can restore them.
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use candid::Principal;
+use dfx_core::config::directories::get_shared_network_data_directory;
+use dfx_core::json::{load_json_file, save_json_file};
+use dfx_core::util::network_to_pathcompat;
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const FROZEN_CANISTERS_FILE: &str = "frozen_canisters.json";
+
+/// The freezing threshold that `dfx canister freeze` sets: the largest one the IC accepts.
+pub const FROZEN_FREEZING_THRESHOLD: u64 = u64::MAX;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct FrozenCanisters {
+    /// The settings of each frozen canister before it was frozen, by canister id.
+    pub canisters: BTreeMap<Principal, PriorSettings>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PriorSettings {
+    /// In seconds.
+    pub freezing_threshold: u64,
+}
+
+/// The record is kept with the other state of the network: in the temp directory of the project,
+/// or in the shared network data directory outside of a project.
+fn frozen_canisters_path(env: &dyn Environment) -> DfxResult<PathBuf> {
+    let network = network_to_pathcompat(&env.get_network_descriptor().name);
+    let dir = match env.get_project_temp_dir() {
+        Some(temp_dir) => temp_dir.join(network),
+        None => get_shared_network_data_directory(&network)?,
+    };
+    Ok(dir.join(FROZEN_CANISTERS_FILE))
+}
+
+#[context("Failed to load the settings of frozen canisters.")]
+pub fn load_frozen_canisters(env: &dyn Environment) -> DfxResult<FrozenCanisters> {
+    let path = frozen_canisters_path(env)?;
+    if path.exists() {
+        Ok(load_json_file(&path)?)
+    } else {
+        Ok(FrozenCanisters::default())
+    }
+}
+
+#[context("Failed to save the settings of frozen canisters.")]
+pub fn save_frozen_canisters(env: &dyn Environment, frozen: &FrozenCanisters) -> DfxResult {
+    let path = frozen_canisters_path(env)?;
+    dfx_core::fs::composite::ensure_parent_dir_exists(&path)?;
+    save_json_file(&path, frozen)?;
+    Ok(())
+}
+
+/// Whether `dfx canister freeze` froze the canister, and it has not been unfrozen since.
+pub fn is_frozen(env: &dyn Environment, canister_id: Principal) -> DfxResult<bool> {
+    Ok(load_frozen_canisters(env)?
+        .canisters
+        .contains_key(&canister_id))
+}
@@ -17,6 +17,8 @@ pub struct CanisterSettings {
     pub memory_allocation: Option<MemoryAllocation>,
     pub freezing_threshold: Option<FreezingThreshold>,
     pub reserved_cycles_limit: Option<ReservedCyclesLimit>,
+    /// Not supported by the canister settings of ic-utils yet, so only applied with `update_settings`.
+    pub wasm_memory_limit: Option<u64>,
 }
 
 impl From<CanisterSettings>
@@ -88,6 +90,7 @@ impl TryFrom<ic_utils::interfaces::management_canister::builders::CanisterSettin
                     )
                 })
                 .transpose()?,
+            wasm_memory_limit: None,
         })
     }
 }
@@ -176,3 +179,30 @@ pub fn get_reserved_cycles_limit(
         })
         .transpose()
 }
+
+#[context("Failed to get Wasm memory limit.")]
+pub fn get_wasm_memory_limit(
+    wasm_memory_limit: Option<Byte>,
+    config_interface: Option<&ConfigInterface>,
+    canister_name: Option<&str>,
+) -> DfxResult<Option<u64>> {
+    let wasm_memory_limit = match (wasm_memory_limit, config_interface, canister_name) {
+        (Some(wasm_memory_limit), _, _) => Some(wasm_memory_limit),
+        (None, Some(config_interface), Some(canister_name)) => {
+            config_interface.get_wasm_memory_limit(canister_name)?
+        }
+        _ => None,
+    };
+    wasm_memory_limit
+        .map(|arg| {
+            u64::try_from(arg.get_bytes())
+                .ok()
+                .filter(|limit| *limit <= 1 << 48)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Wasm memory limit must be between 0 and 2^48 (i.e 256TB), inclusively."
+                    )
+                })
+        })
+        .transpose()
+}
@@ -12,14 +12,18 @@ use dfx_core::error::wallet_config::WalletConfigError;
 use dfx_core::error::wallet_config::WalletConfigError::{
     EnsureWalletConfigDirFailed, GetWalletConfigPathFailed, SaveWalletConfigFailed,
 };
-use dfx_core::identity::{Identity, WalletGlobalConfig, WalletNetworkMap, WALLET_CONFIG_FILENAME};
+use dfx_core::identity::{
+    Identity, IdentityManager, WalletGlobalConfig, WalletNetworkMap, WALLET_CONFIG_FILENAME,
+};
 use dfx_core::json::save_json_file;
+use dfx_core::network::provider::{create_network_descriptor, LocalBindDetermination};
+use fn_error_context::context;
 use ic_agent::agent::{RejectCode, RejectResponse};
 use ic_agent::AgentError;
 use ic_utils::call::AsyncCall;
 use ic_utils::interfaces::management_canister::builders::InstallMode;
 use ic_utils::interfaces::{ManagementCanister, WalletCanister};
-use slog::info;
+use slog::{info, warn};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -200,7 +204,6 @@ pub fn set_wallet_id(
     Identity::save_wallet_config(&wallet_path, &config)
 }
 
-#[allow(dead_code)]
 pub fn remove_wallet_id(network: &NetworkDescriptor, name: &str) -> Result<(), WalletConfigError> {
     let (wallet_path, mut config) = wallet_config(network, name)?;
     // Update the wallet map in it.
@@ -255,3 +258,56 @@ fn wallet_config(
         },
     ))
 }
+
+/// Lists the wallets of an identity: those on persistent networks, and the one on the selected network.
+/// Wallets on networks that are no longer defined are skipped with a warning.
+#[context("Failed to find the wallets of identity '{}'.", name)]
+pub fn get_identity_wallets(
+    env: &dyn Environment,
+    identity_manager: &IdentityManager,
+    name: &str,
+    network: Option<String>,
+) -> DfxResult<Vec<(NetworkDescriptor, Principal)>> {
+    let mut wallets = vec![];
+    let persistent_wallet_config = identity_manager
+        .get_identity_dir_path(name)
+        .join(WALLET_CONFIG_FILENAME);
+    if persistent_wallet_config.exists() {
+        let config = Identity::load_wallet_config(&persistent_wallet_config)?;
+        if let Some(networks) = config.identities.get(name) {
+            for (network_name, wallet) in &networks.networks {
+                match create_network_descriptor(
+                    env.get_config(),
+                    env.get_networks_config(),
+                    Some(network_name.clone()),
+                    None,
+                    LocalBindDetermination::ApplyRunningWebserverPort,
+                ) {
+                    Ok(network) => wallets.push((network, *wallet)),
+                    Err(err) => warn!(
+                        env.get_logger(),
+                        "Skipping the wallet {} of identity '{}' on network '{}': {}",
+                        wallet,
+                        name,
+                        network_name,
+                        err
+                    ),
+                }
+            }
+        }
+    }
+
+    let selected_network = create_network_descriptor(
+        env.get_config(),
+        env.get_networks_config(),
+        network,
+        None,
+        LocalBindDetermination::ApplyRunningWebserverPort,
+    )?;
+    if let NetworkTypeDescriptor::Ephemeral { .. } = selected_network.r#type {
+        if let Some(wallet) = wallet_canister_id(&selected_network, name)? {
+            wallets.push((selected_network, wallet));
+        }
+    }
+    Ok(wallets)
+}
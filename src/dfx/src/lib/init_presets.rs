@@ -0,0 +1,252 @@
+//! Install arguments for well-known canisters, generated from a few parameters,
+//! so that users do not have to write their Candid by hand.
+use crate::lib::error::DfxResult;
+use crate::lib::nns_types::account_identifier::AccountIdentifier;
+use crate::util::candid_principal_vec;
+use anyhow::{bail, Context};
+use candid::Principal;
+use clap::ValueEnum;
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InitPreset {
+    /// Upgrade argument of the asset canister that replaces the principals with each permission.
+    /// Parameters: prepare, commit, manage-permissions (comma-separated principals).
+    AssetPermissions,
+
+    /// Init argument of the ICP ledger.
+    /// Parameters: minting-account, initial-balance (<account id>:<e8s>, repeatable),
+    /// transfer-fee (e8s), token-symbol, token-name.
+    IcpLedger,
+
+    /// Init argument of an ICRC-1 ledger.
+    /// Parameters: minting-account, initial-balance (<principal>:<amount>, repeatable),
+    /// transfer-fee, decimals, token-symbol, token-name, archive-controller.
+    Icrc1Ledger,
+
+    /// Init argument of Internet Identity.
+    /// Parameters: captcha (true or false).
+    InternetIdentity,
+}
+
+/// The parameters given to a preset, in the order they were given.
+struct PresetParams<'a> {
+    preset: InitPreset,
+    params: &'a [(String, String)],
+}
+
+impl PresetParams<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn get_all(&self, key: &str) -> impl Iterator<Item = &str> {
+        let key = key.to_string();
+        self.params
+            .iter()
+            .filter(move |(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn check_keys(&self, allowed: &[&str]) -> DfxResult {
+        for (key, _) in self.params {
+            if !allowed.contains(&key.as_str()) {
+                bail!(
+                    "The {} preset has no parameter '{key}'. Its parameters are: {}.",
+                    self.preset.to_possible_value().unwrap().get_name(),
+                    allowed.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn principals(&self, key: &str) -> DfxResult<Vec<Principal>> {
+        let mut principals = vec![];
+        for value in self.get_all(key) {
+            for text in value.split(',').filter(|text| !text.is_empty()) {
+                principals.push(
+                    Principal::from_text(text.trim())
+                        .with_context(|| format!("Invalid principal '{text}' for {key}."))?,
+                );
+            }
+        }
+        Ok(principals)
+    }
+
+    fn number(&self, key: &str, default: u128) -> DfxResult<u128> {
+        match self.get(key) {
+            Some(value) => {
+                let value = value.replace('_', "");
+                value
+                    .parse()
+                    .with_context(|| format!("Invalid number '{value}' for {key}."))
+            }
+            None => Ok(default),
+        }
+    }
+
+    fn text(&self, key: &str, default: &str) -> String {
+        candid_text(self.get(key).unwrap_or(default))
+    }
+
+    /// Splits the values of a repeatable `<owner>:<amount>` parameter.
+    fn balances(&self, key: &str) -> DfxResult<Vec<(&str, u128)>> {
+        self.get_all(key)
+            .map(|value| -> DfxResult<(&str, u128)> {
+                let (owner, amount) = value.rsplit_once(':').with_context(|| {
+                    format!("Invalid {key} '{value}': expected <owner>:<amount>.")
+                })?;
+                let amount = amount
+                    .replace('_', "")
+                    .parse()
+                    .with_context(|| format!("Invalid amount in {key} '{value}'."))?;
+                Ok((owner, amount))
+            })
+            .collect()
+    }
+}
+
+/// Returns the argument of a preset, in Candid text format.
+/// Parameters that are not given default to values that suit a local network, with `caller` as the
+/// minting account and the archive controller of the ledgers.
+pub fn init_preset_argument(
+    preset: InitPreset,
+    params: &[(String, String)],
+    caller: Principal,
+) -> DfxResult<String> {
+    let params = PresetParams { preset, params };
+    match preset {
+        InitPreset::AssetPermissions => {
+            params.check_keys(&["prepare", "commit", "manage-permissions"])?;
+            if params.params.is_empty() {
+                bail!("The asset-permissions preset requires at least one of prepare, commit or manage-permissions.");
+            }
+            let prepare = candid_principal_vec(&params.principals("prepare")?);
+            let commit = candid_principal_vec(&params.principals("commit")?);
+            let manage_permissions =
+                candid_principal_vec(&params.principals("manage-permissions")?);
+            Ok(format!(
+                "(opt variant {{ Upgrade = record {{ set_permissions = opt record {{ prepare = {prepare}; commit = {commit}; manage_permissions = {manage_permissions} }} }} }})"
+            ))
+        }
+        InitPreset::IcpLedger => {
+            params.check_keys(&[
+                "minting-account",
+                "initial-balance",
+                "transfer-fee",
+                "token-symbol",
+                "token-name",
+            ])?;
+            let minting_account = match params.get("minting-account") {
+                Some(account) => account_id(account)?,
+                None => AccountIdentifier::new(caller, None),
+            };
+            let mut initial_values = vec![];
+            for (account, e8s) in params.balances("initial-balance")? {
+                initial_values.push(format!(
+                    "record {{ \"{}\"; record {{ e8s = {e8s} : nat64 }} }}",
+                    account_id(account)?
+                ));
+            }
+            Ok(format!(
+                "(variant {{ Init = record {{ minting_account = \"{minting_account}\"; initial_values = vec {{ {} }}; send_whitelist = vec {{}}; transfer_fee = opt record {{ e8s = {} : nat64 }}; token_symbol = opt {}; token_name = opt {} }} }})",
+                initial_values.join("; "),
+                params.number("transfer-fee", 10_000)?,
+                params.text("token-symbol", "LICP"),
+                params.text("token-name", "Local ICP"),
+            ))
+        }
+        InitPreset::Icrc1Ledger => {
+            params.check_keys(&[
+                "minting-account",
+                "initial-balance",
+                "transfer-fee",
+                "decimals",
+                "token-symbol",
+                "token-name",
+                "archive-controller",
+            ])?;
+            let minting_account = match params.get("minting-account") {
+                Some(principal) => principal_of(principal)?,
+                None => caller,
+            };
+            let archive_controller = match params.get("archive-controller") {
+                Some(principal) => principal_of(principal)?,
+                None => caller,
+            };
+            let mut initial_balances = vec![];
+            for (owner, amount) in params.balances("initial-balance")? {
+                initial_balances.push(format!(
+                    "record {{ record {{ owner = principal \"{}\"; subaccount = null }}; {amount} : nat }}",
+                    principal_of(owner)?
+                ));
+            }
+            Ok(format!(
+                "(variant {{ Init = record {{ minting_account = record {{ owner = principal \"{minting_account}\"; subaccount = null }}; transfer_fee = {} : nat; decimals = opt ({} : nat8); token_symbol = {}; token_name = {}; metadata = vec {{}}; initial_balances = vec {{ {} }}; archive_options = record {{ num_blocks_to_archive = 1000 : nat64; trigger_threshold = 2000 : nat64; controller_id = principal \"{archive_controller}\" }} }} }})",
+                params.number("transfer-fee", 10_000)?,
+                params.number("decimals", 8)?,
+                params.text("token-symbol", "TOK"),
+                params.text("token-name", "Token"),
+                initial_balances.join("; "),
+            ))
+        }
+        InitPreset::InternetIdentity => {
+            params.check_keys(&["captcha"])?;
+            let captcha = match params.get("captcha") {
+                None | Some("false") => "CaptchaDisabled",
+                Some("true") => "CaptchaEnabled",
+                Some(other) => {
+                    bail!("Invalid value '{other}' for captcha: expected true or false.")
+                }
+            };
+            Ok(format!(
+                "(opt record {{ captcha_config = opt record {{ max_unsolved_captchas = 50 : nat64; captcha_trigger = variant {{ Static = variant {{ {captcha} }} }} }} }})"
+            ))
+        }
+    }
+}
+
+fn principal_of(text: &str) -> DfxResult<Principal> {
+    Principal::from_text(text).with_context(|| format!("Invalid principal '{text}'."))
+}
+
+fn account_id(text: &str) -> DfxResult<AccountIdentifier> {
+    match Principal::from_text(text) {
+        Ok(principal) => Ok(AccountIdentifier::new(principal, None)),
+        Err(_) => text
+            .parse()
+            .map_err(|err| anyhow::anyhow!("Invalid account identifier '{text}': {err}")),
+    }
+}
+
+/// Quotes a string as a Candid text literal.
+fn candid_text(text: &str) -> String {
+    format!("{text:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_permissions() {
+        let params = [("commit".to_string(), "aaaaa-aa".to_string())];
+        assert_eq!(
+            init_preset_argument(InitPreset::AssetPermissions, &params, Principal::anonymous())
+                .unwrap(),
+            "(opt variant { Upgrade = record { set_permissions = opt record { prepare = vec {  }; commit = vec { principal \"aaaaa-aa\" }; manage_permissions = vec {  } } } })"
+        );
+    }
+
+    #[test]
+    fn unknown_parameter() {
+        let params = [("fee".to_string(), "1".to_string())];
+        assert!(
+            init_preset_argument(InitPreset::IcpLedger, &params, Principal::anonymous()).is_err()
+        );
+    }
+}
@@ -27,7 +27,11 @@ pub async fn post_install_store_assets(
         .build()
         .context("Failed to build asset canister caller.")?;
 
-    ic_asset::sync(&canister, &source_paths, logger)
+    let options = ic_asset::SyncOptions {
+        parallelism: assets_canister_info.get_upload_parallelism(),
+        resume_file: Some(assets_canister_info.get_upload_resume_path().to_path_buf()),
+    };
+    ic_asset::sync_with_options(&canister, &source_paths, &options, logger)
         .await
         .with_context(|| {
             format!(
@@ -70,3 +74,24 @@ pub async fn prepare_assets_for_proposal(
 
     Ok(())
 }
+
+#[context("Failed to check the certification of assets in canister '{}'.", info.get_name())]
+pub async fn check_assets_certification(
+    info: &CanisterInfo,
+    agent: &Agent,
+    logger: &Logger,
+) -> DfxResult {
+    let canister_id = info
+        .get_canister_id()
+        .context("Could not find canister ID.")?;
+
+    let canister = ic_utils::Canister::builder()
+        .with_agent(agent)
+        .with_canister_id(canister_id)
+        .build()
+        .context("Failed to build asset canister caller.")?;
+
+    ic_asset::check_certification(&canister, logger).await?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::lib::error::DfxResult;
+use crate::lib::integrations::initialize_integration_canister;
+use crate::util::assets::ui_canister_wasm;
+use candid::Principal;
+use fn_error_context::context;
+use ic_agent::Agent;
+use slog::{debug, Logger};
+
+/// The local Candid UI canister has the id of the one on mainnet.
+pub const CANDID_UI_CANISTER_ID: Principal =
+    Principal::from_slice(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x00, 0x39, 0x01, 0x01]);
+
+#[context("Failed to initialize candid ui canister")]
+pub async fn initialize_candid_ui_canister(agent: &Agent, logger: &Logger) -> DfxResult {
+    debug!(logger, "Initializing candid ui canister");
+
+    let name = "candid ui";
+    let wasm = ui_canister_wasm()?;
+
+    initialize_integration_canister(agent, logger, name, CANDID_UI_CANISTER_ID, &wasm, "()").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candid_ui_canister_id() {
+        assert_eq!(
+            CANDID_UI_CANISTER_ID,
+            Principal::from_text("a4gq6-oaaaa-aaaab-qaa4q-cai").unwrap()
+        );
+    }
+}
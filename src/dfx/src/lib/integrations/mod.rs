@@ -6,6 +6,7 @@ use crate::lib::state_tree::canister_info::read_state_tree_canister_module_hash;
 use crate::util::blob_from_arguments;
 use anyhow::bail;
 use candid::Principal;
+use dfx_core::config::model::dfinity::ConfigNetworkConnection;
 use dfx_core::identity::Identity;
 use dfx_core::{error::root_key::FetchRootKeyError, util::expiry_duration};
 use fn_error_context::context;
@@ -17,12 +18,20 @@ use slog::{debug, info, Logger};
 use std::time::Duration;
 
 pub mod bitcoin;
+pub mod candid_ui;
 pub mod status;
 
 pub async fn create_integrations_agent(url: &str, logger: &Logger) -> DfxResult<Agent> {
     let timeout = expiry_duration();
     let identity = Box::new(Identity::anonymous());
-    let agent = create_agent(logger.clone(), url, identity, timeout).unwrap();
+    let agent = create_agent(
+        logger.clone(),
+        url,
+        identity,
+        timeout,
+        &ConfigNetworkConnection::default(),
+    )
+    .unwrap();
     agent
         .fetch_root_key()
         .await
@@ -1,5 +1,6 @@
 use crate::lib::error::DfxResult;
 use crate::lib::integrations::bitcoin::MAINNET_BITCOIN_CANISTER_ID;
+use crate::lib::integrations::candid_ui::CANDID_UI_CANISTER_ID;
 use crate::lib::integrations::{create_integrations_agent, wait_for_canister_installed};
 use dfx_core::config::model::local_server_descriptor::LocalServerDescriptor;
 use slog::Logger;
@@ -9,7 +10,7 @@ pub async fn wait_for_integrations_initialized(
     logger: &Logger,
     local_server_descriptor: &LocalServerDescriptor,
 ) -> DfxResult {
-    if !local_server_descriptor.bitcoin.enabled {
+    if !local_server_descriptor.bitcoin.enabled && !local_server_descriptor.candid_ui.enabled {
         return Ok(());
     }
 
@@ -18,6 +19,9 @@ pub async fn wait_for_integrations_initialized(
     if local_server_descriptor.bitcoin.enabled {
         wait_for_canister_installed(&agent, &MAINNET_BITCOIN_CANISTER_ID).await?;
     }
+    if local_server_descriptor.candid_ui.enabled {
+        wait_for_canister_installed(&agent, &CANDID_UI_CANISTER_ID).await?;
+    }
 
     Ok(())
 }
@@ -7,6 +7,7 @@ use crate::lib::nns_types::icpts::ICPTs;
 use candid::CandidType;
 use candid::Nat;
 use candid::Principal;
+use candid::Reserved;
 use icrc_ledger_types::icrc1::account::Subaccount as ICRCSubaccount;
 use icrc_ledger_types::icrc1::transfer::BlockIndex as ICRCBlockIndex;
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,10 @@ pub const MAINNET_LEDGER_CANISTER_ID: Principal =
 pub const MAINNET_CYCLE_MINTER_CANISTER_ID: Principal =
     Principal::from_slice(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x01, 0x01]);
 
+/// Id of the governance canister of the NNS on the IC.
+pub const MAINNET_GOVERNANCE_CANISTER_ID: Principal =
+    Principal::from_slice(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01]);
+
 pub type AccountIdBlob = [u8; 32];
 
 /// Arguments for the `transfer` call.
@@ -123,11 +128,54 @@ pub struct AccountBalanceArgs {
     pub account: String,
 }
 
-#[derive(CandidType)]
+#[derive(CandidType, Deserialize)]
 pub struct TimeStamp {
     pub timestamp_nanos: u64,
 }
 
+/// Arguments for the `query_blocks` call.
+#[derive(CandidType)]
+pub struct GetBlocksArgs {
+    pub start: BlockIndex,
+    pub length: u64,
+}
+
+/// The blocks in the requested range that the ledger has not archived yet.
+#[derive(CandidType, Deserialize)]
+pub struct QueryBlocksResponse {
+    pub chain_length: u64,
+    pub blocks: Vec<Block>,
+    /// The index of the first block in `blocks`.
+    pub first_block_index: BlockIndex,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Block {
+    pub transaction: Transaction,
+    pub timestamp: TimeStamp,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Transaction {
+    pub memo: Memo,
+    pub operation: Option<Operation>,
+}
+
+/// Only transfers are decoded, the other operations are kept opaque.
+#[derive(CandidType, Deserialize)]
+pub enum Operation {
+    Transfer {
+        from: Vec<u8>,
+        to: Vec<u8>,
+        amount: ICPTs,
+        fee: ICPTs,
+    },
+    Mint(Reserved),
+    Burn(Reserved),
+    Approve(Reserved),
+    TransferFrom(Reserved),
+}
+
 #[derive(CandidType)]
 pub struct NotifyCreateCanisterArg {
     pub block_index: BlockIndex,
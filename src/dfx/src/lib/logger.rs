@@ -1,7 +1,11 @@
 use crate::config::dfx_version_str;
-use slog::{Drain, Level, Logger};
+use crate::lib::progress_bar::PROGRESS_TAG;
+use serde_json::{Map, Value};
+use slog::{Drain, Level, Logger, KV};
 use std::fs::File;
+use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 /// The logging mode to use.
 pub enum LoggingMode {
@@ -17,6 +21,19 @@ pub enum LoggingMode {
     File(PathBuf),
 }
 
+/// The format of the messages logged to STDERR.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable messages.
+    #[default]
+    Text,
+
+    /// One JSON object per message, with the message's key-value pairs as fields.
+    /// Progress events are always included, so that tools wrapping dfx can follow long-running
+    /// operations regardless of the verbosity.
+    Json,
+}
+
 /// A Slog formatter that writes to a term decorator.
 pub struct DfxFormat<D>
 where
@@ -60,9 +77,119 @@ impl<D: slog_term::Decorator> slog::Drain for DfxFormat<D> {
     }
 }
 
+/// A Slog drain that writes each record to STDERR as a line of JSON.
+pub struct JsonFormat;
+
+/// Collects the key-value pairs of a record into a JSON object.
+struct JsonSerializer(Map<String, Value>);
+
+impl slog::Serializer for JsonSerializer {
+    fn emit_arguments(&mut self, key: slog::Key, val: &std::fmt::Arguments<'_>) -> slog::Result {
+        self.0
+            .insert(key.to_string(), Value::String(val.to_string()));
+        Ok(())
+    }
+
+    fn emit_none(&mut self, key: slog::Key) -> slog::Result {
+        self.0.insert(key.to_string(), Value::Null);
+        Ok(())
+    }
+}
+
+impl slog::Drain for JsonFormat {
+    type Ok = ();
+    type Err = std::io::Error;
+
+    fn log(
+        &self,
+        record: &slog::Record<'_>,
+        values: &slog::OwnedKVList,
+    ) -> Result<Self::Ok, Self::Err> {
+        let mut serializer = JsonSerializer(Map::new());
+        serializer.0.insert(
+            "level".to_string(),
+            Value::String(record.level().as_str().to_lowercase()),
+        );
+        if !record.tag().is_empty() {
+            serializer
+                .0
+                .insert("tag".to_string(), Value::String(record.tag().to_string()));
+        }
+        serializer
+            .0
+            .insert("msg".to_string(), Value::String(record.msg().to_string()));
+        values.serialize(record, &mut serializer)?;
+        record.kv().serialize(record, &mut serializer)?;
+
+        let mut stderr = std::io::stderr().lock();
+        writeln!(stderr, "{}", Value::Object(serializer.0))?;
+        stderr.flush()
+    }
+}
+
+/// Per-module log levels, from the `DFX_LOG` environment variable.
+///
+/// The syntax is a comma-separated list of `<module>=<level>` directives, e.g.
+/// `dfx::lib::builders=debug,ic_asset=warn`, where the module is a prefix of the module path of
+/// the messages. A directive without a module sets the level of all other modules.
+/// The levels are `trace`, `debug`, `info`, `warn`, `error`, `crit`, and `off`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    default: Option<Option<Level>>,
+    /// By module prefix, longest first. `None` turns logging off for the module.
+    modules: Vec<(String, Option<Level>)>,
+}
+
+impl LogFilter {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut filter = LogFilter::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => filter
+                    .modules
+                    .push((module.trim().to_string(), parse_level(level.trim())?)),
+                None => filter.default = Some(parse_level(directive)?),
+            }
+        }
+        filter
+            .modules
+            .sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        Ok(filter)
+    }
+
+    /// The level of a module: the level of the longest matching module prefix,
+    /// else the level without a module, else the level of the verbosity flags.
+    fn level_for(&self, module: &str, verbosity_level: Option<Level>) -> Option<Level> {
+        self.modules
+            .iter()
+            .find(|(prefix, _)| {
+                module == prefix
+                    || module
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+            .or(self.default)
+            .unwrap_or(verbosity_level)
+    }
+}
+
+fn parse_level(level: &str) -> Result<Option<Level>, String> {
+    if level.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    Level::from_str(level)
+        .map(Some)
+        .map_err(|_| format!("Invalid log level '{level}' in DFX_LOG."))
+}
+
 /// Create a log drain.
-fn create_drain(mode: LoggingMode) -> Logger {
+fn create_drain(mode: LoggingMode, format: LogFormat) -> Logger {
     match mode {
+        LoggingMode::Stderr if format == LogFormat::Json => Logger::root(
+            slog_async::Async::new(JsonFormat.fuse()).build().fuse(),
+            slog::o!(),
+        ),
         LoggingMode::Stderr => {
             let decorator = slog_term::TermDecorator::new().build();
             let drain = DfxFormat::new(decorator).fuse();
@@ -78,8 +205,8 @@ fn create_drain(mode: LoggingMode) -> Logger {
         // A Tee mode is basically 2 drains duplicated.
         LoggingMode::Tee(out) => Logger::root(
             slog::Duplicate::new(
-                create_drain(LoggingMode::Stderr),
-                create_drain(LoggingMode::File(out)),
+                create_drain(LoggingMode::Stderr, format),
+                create_drain(LoggingMode::File(out), format),
             )
             .fuse(),
             slog::o!(),
@@ -89,25 +216,77 @@ fn create_drain(mode: LoggingMode) -> Logger {
 
 /// Create a root logger.
 /// The verbose_level can be negative, in which case it's a quiet mode which removes warnings,
-/// then errors entirely.
-pub fn create_root_logger(verbose_level: i64, mode: LoggingMode) -> Logger {
-    let log_level = match verbose_level {
-        -3 => Level::Critical,
-        -2 => Level::Error,
-        -1 => Level::Warning,
-        0 => Level::Info,
-        1 => Level::Debug,
-        x => {
-            if x > 0 {
-                Level::Trace
-            } else {
-                return Logger::root(slog::Discard, slog::o!());
-            }
-        }
+/// then errors entirely. The filter overrides the level for the modules it names.
+pub fn create_root_logger(
+    verbose_level: i64,
+    filter: LogFilter,
+    mode: LoggingMode,
+    format: LogFormat,
+) -> Logger {
+    let verbosity_level = match verbose_level {
+        -3 => Some(Level::Critical),
+        -2 => Some(Level::Error),
+        -1 => Some(Level::Warning),
+        0 => Some(Level::Info),
+        1 => Some(Level::Debug),
+        x if x > 0 => Some(Level::Trace),
+        _ => None,
     };
+    if verbosity_level.is_none() && filter == LogFilter::default() {
+        return Logger::root(slog::Discard, slog::o!());
+    }
 
-    let drain = slog::LevelFilter::new(create_drain(mode), log_level).fuse();
+    let drain = create_drain(mode, format);
+    let drain = slog::Filter::new(drain, move |record: &slog::Record<'_>| {
+        filter
+            .level_for(record.module(), verbosity_level)
+            .is_some_and(|level| record.level().is_at_least(level))
+            || (format == LogFormat::Json && record.tag() == PROGRESS_TAG)
+    })
+    .fuse();
     let drain = slog_async::Async::new(drain).build().fuse();
 
     Logger::root(drain, slog::o!("version" => dfx_version_str()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_filter_matches_longest_module_prefix() {
+        let filter =
+            LogFilter::parse("dfx::lib=debug, dfx::lib::builders=off,ic_asset=warn").unwrap();
+        assert_eq!(
+            filter.level_for("dfx::lib::models::canister", Some(Level::Info)),
+            Some(Level::Debug)
+        );
+        assert_eq!(
+            filter.level_for("dfx::lib::builders::motoko", Some(Level::Info)),
+            None
+        );
+        assert_eq!(
+            filter.level_for("ic_asset", Some(Level::Info)),
+            Some(Level::Warning)
+        );
+        assert_eq!(
+            filter.level_for("ic_asset_canister", Some(Level::Info)),
+            Some(Level::Info)
+        );
+        assert_eq!(filter.level_for("dfx::commands", None), None);
+    }
+
+    #[test]
+    fn log_filter_sets_the_default_level() {
+        let filter = LogFilter::parse("trace,dfx::commands=error").unwrap();
+        assert_eq!(
+            filter.level_for("dfx::lib", Some(Level::Info)),
+            Some(Level::Trace)
+        );
+        assert_eq!(
+            filter.level_for("dfx::commands::deploy", Some(Level::Info)),
+            Some(Level::Error)
+        );
+        assert!(LogFilter::parse("dfx=loud").is_err());
+    }
+}
@@ -1,16 +1,25 @@
 pub mod agent;
+pub mod artifacts;
+pub mod build_cache;
 pub mod builders;
+pub mod bundle;
 pub mod canister_info;
+pub mod cycles_cost;
 pub mod cycles_ledger_types;
+pub mod declarations;
 pub mod deps;
 pub mod dfxvm;
 pub mod diagnosis;
+pub mod domains;
 pub mod environment;
 pub mod error;
 pub mod error_code;
+pub mod failover;
+pub mod frozen_canisters;
 pub mod ic_attributes;
 pub mod identity;
 pub mod info;
+pub mod init_presets;
 pub mod installers;
 pub mod integrations;
 pub mod ledger_types;
@@ -27,12 +36,18 @@ pub mod package_arguments;
 pub mod program;
 pub mod progress_bar;
 pub mod project;
+pub mod project_templates;
 pub mod replica;
 pub mod replica_config;
+pub mod requests;
 pub mod retryable;
 pub mod root_key;
+pub mod scenario;
 pub mod sign;
 pub mod state_tree;
 pub mod subnet;
+pub mod telemetry;
+pub mod toolchain;
 pub mod warning;
 pub mod wasm;
+pub mod webserver;
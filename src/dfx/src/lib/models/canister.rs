@@ -1,12 +1,16 @@
+use crate::lib::artifacts::copy_artifacts;
+use crate::lib::build_cache::BuildCache;
 use crate::lib::builders::{
     custom_download, BuildConfig, BuildOutput, BuilderPool, CanisterBuilder, IdlBuildOutput,
     WasmBuildOutput,
 };
 use crate::lib::canister_info::CanisterInfo;
+use crate::lib::declarations::docs::{write_docs, DocFormat};
 use crate::lib::environment::Environment;
 use crate::lib::error::{BuildError, DfxError, DfxResult};
 use crate::lib::metadata::dfx::DfxMetadata;
 use crate::lib::metadata::names::{CANDID_ARGS, CANDID_SERVICE, DFX};
+use crate::lib::progress_bar::{report_step, Step, StepStatus};
 use crate::lib::wasm::file::{compress_bytes, read_wasm_module};
 use crate::util::assets;
 use anyhow::{anyhow, bail, Context};
@@ -14,7 +18,7 @@ use candid::Principal as CanisterId;
 use candid_parser::utils::CandidSource;
 use dfx_core::config::model::canister_id_store::CanisterIdStore;
 use dfx_core::config::model::dfinity::{
-    CanisterMetadataSection, Config, MetadataVisibility, WasmOptLevel,
+    CanisterMetadataSection, Config, MetadataVisibility, Profile, WasmOptLevel,
 };
 use fn_error_context::context;
 use ic_wasm::metadata::{add_metadata, remove_metadata, Kind};
@@ -23,12 +27,12 @@ use itertools::Itertools;
 use petgraph::graph::{DiGraph, NodeIndex};
 use rand::{thread_rng, RngCore};
 use slog::{error, info, trace, warn, Logger};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, HashSet};
 use std::convert::TryFrom;
 use std::ffi::OsStr;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::Arc;
 
@@ -41,6 +45,7 @@ pub struct Canister {
     info: CanisterInfo,
     builder: Arc<dyn CanisterBuilder>,
     output: RefCell<Option<BuildOutput>>,
+    restored_from_cache: Cell<bool>,
 }
 unsafe impl Send for Canister {}
 unsafe impl Sync for Canister {}
@@ -53,6 +58,7 @@ impl Canister {
             info,
             builder,
             output: RefCell::new(None),
+            restored_from_cache: Cell::new(false),
         }
     }
 
@@ -72,6 +78,21 @@ impl Canister {
         Ok(self.get_build_output().unwrap())
     }
 
+    /// Records the output of a build restored from the build cache, so that the canister is not built again.
+    pub fn restore_from_cache(&self) {
+        let output = BuildOutput {
+            canister_id: self.canister_id(),
+            wasm: WasmBuildOutput::File(self.info.get_build_wasm_path()),
+            idl: IdlBuildOutput::File(self.info.get_constructor_idl_path()),
+        };
+        let _ = self.output.replace(Some(output));
+        self.restored_from_cache.set(true);
+    }
+
+    pub fn is_restored_from_cache(&self) -> bool {
+        self.restored_from_cache.get()
+    }
+
     pub fn postbuild(&self, pool: &CanisterPool, build_config: &BuildConfig) -> DfxResult {
         self.builder.postbuild(pool, &self.info, build_config)
     }
@@ -108,10 +129,39 @@ impl Canister {
         self.builder.generate(pool, &self.info, build_config)
     }
 
+    /// Writes the documentation of the interface of the canister to the output directory,
+    /// without touching its type declarations.
+    #[context("Failed while trying to generate interface documentation for '{}'.", self.info.get_name())]
+    pub fn generate_docs(
+        &self,
+        pool: &CanisterPool,
+        build_config: &BuildConfig,
+        format: DocFormat,
+        output_dir: &Path,
+    ) -> DfxResult<PathBuf> {
+        // The builders write the Candid file to the declarations directory, where it may not belong.
+        let declarations_idl_path = self
+            .info
+            .get_declarations_config()
+            .output
+            .as_ref()
+            .context("`output` must not be None")?
+            .join(self.info.get_name())
+            .with_extension("did");
+        let existed = declarations_idl_path.exists();
+        let idl_path = self.builder.generate_idl(pool, &self.info, build_config)?;
+        let output_path = write_docs(self.info.get_name(), format, &idl_path, output_dir);
+        if !existed && idl_path == declarations_idl_path {
+            let _ = std::fs::remove_file(&idl_path);
+        }
+        output_path
+    }
+
     #[context("Failed to post-process wasm of canister '{}'.", self.info.get_name())]
     pub(crate) fn wasm_post_process(
         &self,
         logger: &Logger,
+        build_config: &BuildConfig,
         build_output: &BuildOutput,
     ) -> DfxResult {
         let build_output_wasm_path = match &build_output.wasm {
@@ -131,8 +181,19 @@ impl Canister {
         let mut m = read_wasm_module(build_output_wasm_path)?;
         let mut modified = false;
 
-        // optimize or shrink
-        if let Some(level) = info.get_optimize() {
+        // the selected profile of dfx.json overrides the settings of the canister
+        let named_profile = build_config.named_profile();
+        let optimize = named_profile
+            .and_then(|profile| profile.optimize)
+            .or(info.get_optimize());
+        let shrink = named_profile
+            .and_then(|profile| profile.shrink)
+            .or(info.get_shrink());
+
+        // optimize or shrink, unless this would strip the coverage instrumentation
+        if matches!(build_config.profile(), Profile::TestCoverage) {
+            trace!(logger, "Skipping optimization of instrumented WASM");
+        } else if let Some(level) = optimize {
             trace!(logger, "Optimizing WASM at level {}", level);
             ic_wasm::optimize::optimize(
                 &mut m,
@@ -143,8 +204,7 @@ impl Canister {
             )
             .context("Failed to optimize the WASM module.")?;
             modified = true;
-        } else if info.get_shrink() == Some(true)
-            || (info.get_shrink().is_none() && (info.is_rust() || info.is_motoko()))
+        } else if shrink == Some(true) || (shrink.is_none() && (info.is_rust() || info.is_motoko()))
         {
             trace!(logger, "Shrinking WASM");
             ic_wasm::shrink::shrink(&mut m);
@@ -400,6 +460,7 @@ fn check_valid_subtype(compiled_idl_path: &Path, specified_idl_path: &Path) -> D
 pub struct CanisterPool {
     canisters: Vec<Arc<Canister>>,
     logger: Logger,
+    build_cache: Option<BuildCache>,
 }
 
 struct PoolConstructHelper<'a> {
@@ -454,6 +515,7 @@ impl CanisterPool {
         Ok(CanisterPool {
             canisters: canisters_map,
             logger,
+            build_cache: BuildCache::from_config(&config)?,
         })
     }
 
@@ -608,6 +670,21 @@ impl CanisterPool {
         canister.build(self, build_config)
     }
 
+    /// A canister restored from the build cache only needs its interface where dependents look for it.
+    fn step_restored(&self, build_config: &BuildConfig, canister: &Canister) -> DfxResult<()> {
+        let service_idl_path = canister.info.get_service_idl_path();
+        let file_name = format!("{}.did", canister.canister_id().to_text());
+        for to in [
+            build_config.idl_root.join(&file_name),
+            build_config.lsp_root.join(&file_name),
+        ] {
+            dfx_core::fs::composite::ensure_parent_dir_exists(&to)?;
+            dfx_core::fs::copy(&service_idl_path, &to)?;
+            dfx_core::fs::set_permissions_readwrite(&to)?;
+        }
+        Ok(())
+    }
+
     fn step_postbuild(
         &self,
         build_config: &BuildConfig,
@@ -616,7 +693,7 @@ impl CanisterPool {
     ) -> DfxResult<()> {
         canister.candid_post_process(self.get_logger(), build_config, build_output)?;
 
-        canister.wasm_post_process(self.get_logger(), build_output)?;
+        canister.wasm_post_process(self.get_logger(), build_config, build_output)?;
 
         build_canister_js(&canister.canister_id(), &canister.info)?;
 
@@ -683,36 +760,63 @@ impl CanisterPool {
                     trace!(log, "Not building canister '{}'.", canister.get_name());
                     continue;
                 }
-                result.push(
-                    self.step_prebuild(build_config, canister)
+                if canister.is_restored_from_cache() {
+                    let canister_result = self
+                        .step_restored(build_config, canister)
                         .map_err(|e| {
-                            BuildError::PreBuildStepFailed(
+                            BuildError::PostBuildStepFailed(
                                 *canister_id,
                                 canister.get_name().to_string(),
                                 Box::new(e),
                             )
                         })
-                        .and_then(|_| {
-                            self.step_build(build_config, canister).map_err(|e| {
-                                BuildError::BuildStepFailed(
+                        .map(|_| canister.get_build_output().unwrap());
+                    result.push(canister_result);
+                    continue;
+                }
+                report_step(
+                    log,
+                    Step::Build,
+                    StepStatus::Started,
+                    Some(canister.get_name()),
+                    None,
+                );
+                let canister_result = self
+                    .step_prebuild(build_config, canister)
+                    .map_err(|e| {
+                        BuildError::PreBuildStepFailed(
+                            *canister_id,
+                            canister.get_name().to_string(),
+                            Box::new(e),
+                        )
+                    })
+                    .and_then(|_| {
+                        self.step_build(build_config, canister).map_err(|e| {
+                            BuildError::BuildStepFailed(
+                                *canister_id,
+                                canister.get_name().to_string(),
+                                Box::new(e),
+                            )
+                        })
+                    })
+                    .and_then(|o| {
+                        self.step_postbuild(build_config, canister, o)
+                            .map_err(|e| {
+                                BuildError::PostBuildStepFailed(
                                     *canister_id,
                                     canister.get_name().to_string(),
                                     Box::new(e),
                                 )
                             })
-                        })
-                        .and_then(|o| {
-                            self.step_postbuild(build_config, canister, o)
-                                .map_err(|e| {
-                                    BuildError::PostBuildStepFailed(
-                                        *canister_id,
-                                        canister.get_name().to_string(),
-                                        Box::new(e),
-                                    )
-                                })
-                                .map(|_| o)
-                        }),
-                );
+                            .map(|_| o)
+                    });
+                let status = if canister_result.is_ok() {
+                    StepStatus::Finished
+                } else {
+                    StepStatus::Failed
+                };
+                report_step(log, Step::Build, status, Some(canister.get_name()), None);
+                result.push(canister_result);
             }
         }
 
@@ -727,15 +831,60 @@ impl CanisterPool {
     #[context("Failed while trying to build all canisters.")]
     pub async fn build_or_fail(&self, log: &Logger, build_config: &BuildConfig) -> DfxResult<()> {
         self.download(build_config).await?;
+        let to_store = self.restore_from_build_cache(log, build_config).await?;
         let outputs = self.build(log, build_config)?;
 
         for output in outputs {
             output.map_err(DfxError::new)?;
         }
 
+        for canister in self.canisters_to_build(build_config) {
+            let info = canister.get_info();
+            if info.is_pull() {
+                continue;
+            }
+            if let Some(dir) = build_config.artifacts_dir(info) {
+                copy_artifacts(info, build_config, &dir)?;
+            }
+        }
+
+        if let Some(build_cache) = &self.build_cache {
+            for (canister, key) in to_store {
+                build_cache.store(log, &key, canister.get_info()).await;
+            }
+        }
         Ok(())
     }
 
+    /// Restores the canisters to build from the build cache where possible.
+    /// Returns the canisters that were not in the cache, with their cache keys.
+    async fn restore_from_build_cache(
+        &self,
+        log: &Logger,
+        build_config: &BuildConfig,
+    ) -> DfxResult<Vec<(&Arc<Canister>, String)>> {
+        let Some(build_cache) = &self.build_cache else {
+            return Ok(vec![]);
+        };
+        let mut to_store = vec![];
+        for canister in self.canisters_to_build(build_config) {
+            let Some(key) = build_cache.cache_key(self, canister, build_config)? else {
+                continue;
+            };
+            if build_cache.restore(log, &key, canister.get_info()).await {
+                info!(
+                    log,
+                    "Restored canister {} from the build cache.",
+                    canister.get_name()
+                );
+                canister.restore_from_cache();
+            } else {
+                to_store.push((canister, key));
+            }
+        }
+        Ok(to_store)
+    }
+
     async fn download(&self, build_config: &BuildConfig) -> DfxResult {
         for canister in self.canisters_to_build(build_config) {
             let info = canister.get_info();
@@ -3,16 +3,17 @@
 //! Contains the Candid UI canister for now
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
+use crate::lib::integrations::candid_ui::CANDID_UI_CANISTER_ID;
 use crate::lib::root_key::fetch_root_key_if_needed;
 use crate::util;
 use anyhow::{anyhow, Context};
 use candid::Principal;
 use dfx_core::config::model::canister_id_store::CanisterIdStore;
+use dfx_core::config::model::network_descriptor::NetworkDescriptor;
 use fn_error_context::context;
 use ic_utils::interfaces::management_canister::builders::InstallMode;
 use ic_utils::interfaces::ManagementCanister;
 use slog::info;
-use std::io::Read;
 
 const UI_CANISTER: &str = "__Candid_UI";
 
@@ -35,24 +36,7 @@ pub async fn install_ui_canister(
         env.get_logger(),
         "Creating UI canister on the {} network.", network.name
     );
-    let mut canister_assets =
-        util::assets::ui_canister().context("Failed to get ui canister assets.")?;
-    let mut wasm = Vec::new();
-    for file in canister_assets
-        .entries()
-        .context("Failed to get ui canister asset entries.")?
-    {
-        let mut file = file.context("Failed to examine archive entry.")?;
-        if file
-            .header()
-            .path()
-            .context("Failed to get archive entry path.")?
-            .ends_with("ui.wasm")
-        {
-            file.read_to_end(&mut wasm)
-                .context("Failed to read wasm.")?;
-        }
-    }
+    let wasm = util::assets::ui_canister_wasm()?;
     let canister_id = match some_canister_id {
         Some(id) => id,
         None => {
@@ -79,6 +63,17 @@ pub async fn install_ui_canister(
     );
     Ok(canister_id)
 }
-pub fn get_ui_canister_id(id_store: &CanisterIdStore) -> Option<Principal> {
-    id_store.find(UI_CANISTER)
+
+/// Returns the id of the Candid UI canister on the network, if there is one.
+/// Local networks install it on start unless `candid_ui` is disabled.
+pub fn get_ui_canister_id(
+    network: &NetworkDescriptor,
+    id_store: &CanisterIdStore,
+) -> Option<Principal> {
+    match &network.local_server_descriptor {
+        Some(local_server_descriptor) if local_server_descriptor.candid_ui.enabled => {
+            Some(CANDID_UI_CANISTER_ID)
+        }
+        _ => id_store.find(UI_CANISTER),
+    }
 }
@@ -1,2 +1,3 @@
 pub mod id;
 pub mod network_opt;
+pub mod url;
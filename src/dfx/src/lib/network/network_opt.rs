@@ -1,11 +1,14 @@
+use crate::lib::environment::Environment;
 use clap::{ArgGroup, Args};
+use dfx_core::network::provider::network_or_project_default;
 
 #[derive(Args, Clone, Debug, Default)]
 #[clap(
 group(ArgGroup::new("network-select").multiple(false)),
 )]
 pub struct NetworkOpt {
-    /// Override the compute network to connect to. By default, the local network is used.
+    /// Override the compute network to connect to. By default, the default network of the project
+    /// (`defaults.network` in dfx.json) is used, or else the local network.
     /// A valid URL (starting with `http:` or `https:`) can be used here, and a special
     /// ephemeral network will be created specifically for this request. E.g.
     /// "http://localhost:12345/" is a valid network name.
@@ -23,7 +26,17 @@ pub struct NetworkOpt {
 }
 
 impl NetworkOpt {
-    pub fn to_network_name(&self) -> Option<String> {
+    /// The selected network, or else the default network of the project.
+    pub fn to_network_name(&self, env: &dyn Environment) -> Option<String> {
+        network_or_project_default(
+            env.get_config().as_deref(),
+            self.selected_network_name(),
+            env.get_logger(),
+        )
+    }
+
+    /// The network selected on the command line, if any.
+    pub fn selected_network_name(&self) -> Option<String> {
         if self.playground {
             Some("playground".to_string())
         } else if self.ic {
@@ -0,0 +1,140 @@
+//! The URLs at which canisters can be reached through the HTTP gateway of a network.
+use crate::lib::error::DfxResult;
+use anyhow::Context;
+use candid::Principal;
+use dfx_core::config::model::network_descriptor::NetworkDescriptor;
+use fn_error_context::context;
+use url::Host::Domain;
+use url::Url;
+
+pub const MAINNET_CANDID_INTERFACE_PRINCIPAL: &str = "a4gq6-oaaaa-aaaab-qaa4q-cai";
+
+#[context("Failed to construct frontend url for canister {} on network '{}'.", canister_id, network.name)]
+pub fn construct_frontend_url(
+    network: &NetworkDescriptor,
+    canister_id: &Principal,
+) -> DfxResult<Url> {
+    let mut url = Url::parse(&network.providers[0]).with_context(|| {
+        format!(
+            "Failed to parse url for network provider {}.",
+            &network.providers[0]
+        )
+    })?;
+
+    if let Some(Domain(domain)) = url.host() {
+        let host = format!("{}.{}", canister_id, domain);
+        url.set_host(Some(&host))
+            .with_context(|| format!("Failed to set host to {}.", host))?;
+    } else {
+        let query = format!("canisterId={}", canister_id);
+        url.set_query(Some(&query));
+    };
+
+    Ok(url)
+}
+
+/// The URL that serves the canister without certifying its responses.
+/// Only available if the network provider is reached through a domain name.
+#[context("Failed to construct raw url for canister {} on network '{}'.", canister_id, network.name)]
+pub fn construct_raw_url(
+    network: &NetworkDescriptor,
+    canister_id: &Principal,
+) -> DfxResult<Option<Url>> {
+    let mut url = Url::parse(&network.providers[0]).with_context(|| {
+        format!(
+            "Failed to parse url for network provider {}.",
+            &network.providers[0]
+        )
+    })?;
+
+    if let Some(Domain(domain)) = url.host() {
+        let host = format!("{}.raw.{}", canister_id, domain);
+        url.set_host(Some(&host))
+            .with_context(|| format!("Failed to set host to {}.", host))?;
+        Ok(Some(url))
+    } else {
+        Ok(None)
+    }
+}
+
+#[context("Failed to construct ui canister url for {} on network '{}'.", canister_id, network.name)]
+pub fn construct_ui_canister_url(
+    network: &NetworkDescriptor,
+    canister_id: &Principal,
+    ui_canister_id: Option<Principal>,
+) -> DfxResult<Option<Url>> {
+    if network.is_ic {
+        let url = format!(
+            "https://{}.raw.icp0.io/?id={}",
+            MAINNET_CANDID_INTERFACE_PRINCIPAL, canister_id
+        );
+        let url = Url::parse(&url).with_context(|| {
+            format!(
+                "Failed to parse candid url {} for canister {}.",
+                &url, canister_id
+            )
+        })?;
+        Ok(Some(url))
+    } else if let Some(ui_canister_id) = ui_canister_id {
+        let mut url = Url::parse(&network.providers[0]).with_context(|| {
+            format!(
+                "Failed to parse network provider {}.",
+                &network.providers[0]
+            )
+        })?;
+        if let Some(Domain(domain)) = url.host() {
+            let host = format!("{}.{}", ui_canister_id, domain);
+            let query = format!("id={}", canister_id);
+            url.set_host(Some(&host))
+                .with_context(|| format!("Failed to set host to {}", &host))?;
+            url.set_query(Some(&query));
+        } else {
+            let query = format!("canisterId={}&id={}", ui_canister_id, canister_id);
+            url.set_query(Some(&query));
+        }
+        Ok(Some(url))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_for_local_domain() {
+        let mut network = NetworkDescriptor::ic();
+        network.providers = vec!["http://localhost:4943".to_string()];
+        network.is_ic = false;
+        let canister_id = Principal::from_text("bkyz2-fmaaa-aaaaa-qaaaq-cai").unwrap();
+        assert_eq!(
+            construct_frontend_url(&network, &canister_id)
+                .unwrap()
+                .as_str(),
+            "http://bkyz2-fmaaa-aaaaa-qaaaq-cai.localhost:4943/"
+        );
+        assert_eq!(
+            construct_raw_url(&network, &canister_id)
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "http://bkyz2-fmaaa-aaaaa-qaaaq-cai.raw.localhost:4943/"
+        );
+    }
+
+    #[test]
+    fn urls_for_ip_address() {
+        let mut network = NetworkDescriptor::ic();
+        network.providers = vec!["http://127.0.0.1:4943".to_string()];
+        network.is_ic = false;
+        let canister_id = Principal::from_text("bkyz2-fmaaa-aaaaa-qaaaq-cai").unwrap();
+        assert_eq!(
+            construct_frontend_url(&network, &canister_id)
+                .unwrap()
+                .as_str(),
+            "http://127.0.0.1:4943/?canisterId=bkyz2-fmaaa-aaaaa-qaaaq-cai"
+        );
+        assert!(construct_raw_url(&network, &canister_id).unwrap().is_none());
+    }
+}
@@ -183,6 +183,16 @@ impl From<&Principal> for Subaccount {
     }
 }
 
+impl From<u64> for Subaccount {
+    /// The subaccount with the index in its last 8 bytes, in big-endian,
+    /// like `SubAccount.fromID` of the `@dfinity/ledger-icp` package.
+    fn from(index: u64) -> Self {
+        let mut subaccount = [0; std::mem::size_of::<Subaccount>()];
+        subaccount[24..].copy_from_slice(&index.to_be_bytes());
+        Subaccount(subaccount)
+    }
+}
+
 impl TryFrom<&[u8]> for Subaccount {
     type Error = std::array::TryFromSliceError;
 
@@ -7,7 +7,11 @@ use crate::lib::ic_attributes::CanisterSettings as DfxCanisterSettings;
 use crate::lib::identity::wallet::{get_or_create_wallet_canister, GetOrCreateWalletCanisterError};
 use crate::lib::ledger_types::MAINNET_CYCLE_MINTER_CANISTER_ID;
 use crate::lib::operations::canister::motoko_playground::reserve_canister_with_playground;
-use crate::lib::operations::cycles_ledger::{create_with_cycles_ledger, CYCLES_LEDGER_ENABLED};
+use crate::lib::operations::canister::update_settings;
+use crate::lib::operations::cycles_ledger::{
+    create_with_cycles_ledger, cycles_ledger_enabled, uses_cycles_ledger_instead_of_wallet,
+};
+use crate::lib::progress_bar::{report_step, Step, StepStatus};
 use anyhow::{anyhow, bail, Context};
 use candid::Principal;
 use dfx_core::canister::build_wallet_canister;
@@ -21,7 +25,7 @@ use ic_utils::interfaces::management_canister::builders::CanisterSettings;
 use ic_utils::interfaces::ManagementCanister;
 use ic_utils::Argument;
 use icrc_ledger_types::icrc1::account::Subaccount;
-use slog::{debug, info, warn};
+use slog::{debug, warn};
 use std::format;
 
 // The cycle fee for create request is 0.1T cycles.
@@ -45,12 +49,18 @@ pub async fn create_canister(
     subnet_selection: Option<SubnetSelection>,
 ) -> DfxResult {
     let log = env.get_logger();
-    info!(log, "Creating canister {}...", canister_name);
+    report_step(
+        log,
+        Step::Create,
+        StepStatus::Started,
+        Some(canister_name),
+        Some(format!("Creating canister {}...", canister_name)),
+    );
 
     let config = env.get_config_or_anyhow()?;
     let config_interface = config.get_config();
 
-    let mut canister_id_store = env.get_canister_id_store()?;
+    let canister_id_store = env.get_canister_id_store()?;
 
     let network_name = get_network_context()?;
 
@@ -73,12 +83,17 @@ pub async fn create_canister(
     };
 
     if let Some(canister_id) = canister_id_store.find(canister_name) {
-        info!(
+        report_step(
             log,
-            "{} canister was already created {}and has canister id: {}",
-            canister_name,
-            non_default_network,
-            canister_id.to_text()
+            Step::Create,
+            StepStatus::Skipped,
+            Some(canister_name),
+            Some(format!(
+                "{} canister was already created {}and has canister id: {}",
+                canister_name,
+                non_default_network,
+                canister_id.to_text()
+            )),
         );
         return Ok(());
     }
@@ -123,41 +138,52 @@ The command line value will be used.",
     // Replace call_sender with wallet canister unless:
     // 1. specified_id is in effect OR
     // 2. --no-wallet is set explicitly OR
-    // 3. call_sender is already wallet
-    let call_sender =
-        if specified_id.is_some() || no_wallet || matches!(call_sender, CallSender::Wallet(_)) {
-            *call_sender
-        } else {
-            match get_or_create_wallet_canister(
-                env,
-                env.get_network_descriptor(),
-                env.get_selected_identity().expect("No selected identity"),
-            )
-            .await
-            {
-                Ok(wallet) => CallSender::Wallet(*wallet.canister_id_()),
-                Err(err) => {
-                    if CYCLES_LEDGER_ENABLED
-                        && matches!(
-                            err,
-                            GetOrCreateWalletCanisterError::NoWalletConfigured { .. }
-                        )
-                    {
-                        debug!(env.get_logger(), "No wallet configured.");
-                        *call_sender
-                    } else {
-                        bail!(err)
-                    }
+    // 3. the project uses the cycles ledger instead of a wallet OR
+    // 4. call_sender is already wallet
+    let call_sender = if specified_id.is_some()
+        || no_wallet
+        || uses_cycles_ledger_instead_of_wallet(env)
+        || matches!(call_sender, CallSender::Wallet(_))
+    {
+        *call_sender
+    } else {
+        match get_or_create_wallet_canister(
+            env,
+            env.get_network_descriptor(),
+            env.get_selected_identity().expect("No selected identity"),
+        )
+        .await
+        {
+            Ok(wallet) => CallSender::Wallet(*wallet.canister_id_()),
+            Err(err) => {
+                if cycles_ledger_enabled(env)
+                    && matches!(
+                        err,
+                        GetOrCreateWalletCanisterError::NoWalletConfigured { .. }
+                    )
+                {
+                    debug!(env.get_logger(), "No wallet configured.");
+                    *call_sender
+                } else {
+                    bail!(err)
                 }
             }
-        };
+        }
+    };
 
+    let wasm_memory_limit = settings.wasm_memory_limit;
     let agent = env.get_agent();
     let cid = match call_sender {
         CallSender::SelectedId => {
             let auto_wallet_disabled = std::env::var("DFX_DISABLE_AUTO_WALLET").is_ok();
             let ic_network = env.get_network_descriptor().is_ic;
-            if CYCLES_LEDGER_ENABLED && (ic_network || auto_wallet_disabled) {
+            // Projects that use the cycles ledger instead of a wallet also use it on local networks,
+            // unless a specific canister id is requested, which only the management canister can provide.
+            let cycles_ledger_mode =
+                uses_cycles_ledger_instead_of_wallet(env) && specified_id.is_none();
+            if cycles_ledger_enabled(env)
+                && (ic_network || auto_wallet_disabled || cycles_ledger_mode)
+            {
                 create_with_cycles_ledger(
                     env,
                     agent,
@@ -170,6 +196,17 @@ The command line value will be used.",
                 )
                 .await
             } else {
+                // The management canister creates canisters on the subnet it is called on.
+                if subnet_selection.is_some() {
+                    if ic_network {
+                        bail!("Creating a canister on a specific subnet requires a wallet. Remove --no-wallet and try again.");
+                    }
+                    warn!(
+                        log,
+                        "Ignoring the subnet selection: canisters created without a wallet are created on the only subnet of network '{}'.",
+                        env.get_network_descriptor().name
+                    );
+                }
                 create_with_management_canister(env, agent, with_cycles, specified_id, settings)
                     .await
             }
@@ -178,15 +215,28 @@ The command line value will be used.",
             create_with_wallet(agent, &wallet_id, with_cycles, settings, subnet_selection).await
         }
     }?;
+    // Canisters cannot be created with a Wasm memory limit yet, so it is set right after.
+    if wasm_memory_limit.is_some() {
+        let settings = DfxCanisterSettings {
+            wasm_memory_limit,
+            ..Default::default()
+        };
+        update_settings(env, cid, settings, &call_sender).await?;
+    }
     let canister_id = cid.to_text();
-    info!(
+    report_step(
         log,
-        "{} canister created {}with canister id: {}",
-        canister_name,
-        non_default_network,
-        canister_id
+        Step::Create,
+        StepStatus::Finished,
+        Some(canister_name),
+        Some(format!(
+            "{} canister created {}with canister id: {}",
+            canister_name, non_default_network, canister_id
+        )),
     );
-    canister_id_store.add(canister_name, &canister_id, None)?;
+    // Other canisters may have been created concurrently, so the store is read again before it is written.
+    env.get_canister_id_store()?
+        .add(canister_name, &canister_id, None)?;
 
     Ok(())
 }
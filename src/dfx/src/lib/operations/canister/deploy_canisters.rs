@@ -4,27 +4,35 @@ use crate::lib::canister_info::CanisterInfo;
 use crate::lib::cycles_ledger_types::create_canister::SubnetSelection;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
-use crate::lib::ic_attributes::CanisterSettings;
-use crate::lib::installers::assets::prepare_assets_for_proposal;
+use crate::lib::ic_attributes::{
+    get_compute_allocation, get_freezing_threshold, get_memory_allocation,
+    get_reserved_cycles_limit, get_wasm_memory_limit, CanisterSettings,
+};
+use crate::lib::installers::assets::{check_assets_certification, prepare_assets_for_proposal};
 use crate::lib::models::canister::CanisterPool;
 use crate::lib::operations::canister::deploy_canisters::DeployMode::{
     ComputeEvidence, ForceReinstallSingleCanister, NormalDeploy, PrepareForProposal,
 };
 use crate::lib::operations::canister::motoko_playground::reserve_canister_with_playground;
-use crate::lib::operations::canister::{create_canister, install_canister::install_canister};
+use crate::lib::operations::canister::{
+    create_canister, fetch_remote_candid_files, get_canister_status, get_canister_status_extras,
+    install_canister::install_canister, update_settings,
+};
+use crate::lib::progress_bar::{report_step, Step, StepStatus};
+use crate::lib::state_tree::canister_info::read_state_tree_canister_module_hash;
 use anyhow::{anyhow, bail, Context};
-use candid::Principal;
+use candid::{Nat, Principal};
+use dfx_core::cli::ask_for_consent;
 use dfx_core::config::model::canister_id_store::CanisterIdStore;
 use dfx_core::config::model::dfinity::Config;
 use dfx_core::identity::CallSender;
 use fn_error_context::context;
-use ic_utils::interfaces::management_canister::attributes::{
-    ComputeAllocation, FreezingThreshold, MemoryAllocation, ReservedCyclesLimit,
-};
 use ic_utils::interfaces::management_canister::builders::InstallMode;
 use icrc_ledger_types::icrc1::account::Subaccount;
+use sha2::{Digest, Sha256};
 use slog::info;
-use std::convert::TryFrom;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 
 #[derive(Eq, PartialEq, Debug, Clone)]
@@ -52,7 +60,13 @@ pub async fn deploy_canisters(
     skip_consent: bool,
     env_file: Option<PathBuf>,
     no_asset_upgrade: bool,
+    allow_unverified: bool,
     subnet_selection: Option<SubnetSelection>,
+    check_certification: bool,
+    profile: Option<&str>,
+    plan_mode: DeployPlanMode,
+    only_dependencies: bool,
+    exclude: &[String],
 ) -> DfxResult {
     let log = env.get_logger();
 
@@ -71,7 +85,33 @@ pub async fn deploy_canisters(
         }
     }
 
-    let canisters_to_load = canister_with_dependencies(&config, some_canister)?;
+    for canister_name in exclude {
+        if !config
+            .get_config()
+            .canisters
+            .as_ref()
+            .is_some_and(|canisters| canisters.contains_key(canister_name))
+        {
+            bail!("Cannot exclude canister {canister_name}: it is not defined in dfx.json.");
+        }
+        if some_canister == Some(canister_name.as_str()) && !only_dependencies {
+            bail!("Canister {canister_name} cannot be both deployed and excluded.");
+        }
+    }
+
+    let mut canisters_to_load = canister_with_dependencies(&config, some_canister, exclude)?;
+    if only_dependencies {
+        // The canister itself is neither created, built nor installed.
+        canisters_to_load.retain(|canister_name| Some(canister_name.as_str()) != some_canister);
+        if canisters_to_load.is_empty() {
+            info!(
+                log,
+                "Canister {} has no dependencies to deploy.",
+                some_canister.unwrap_or_default()
+            );
+            return Ok(());
+        }
+    }
 
     let canisters_to_build = match deploy_mode {
         PrepareForProposal(canister_name) | ComputeEvidence(canister_name) => {
@@ -93,21 +133,32 @@ pub async fn deploy_canisters(
             .collect(),
     };
 
+    // Excluded canisters are still built if a deployed canister depends on them, but never installed.
     let canisters_to_install: Vec<String> = canisters_to_build
         .clone()
         .into_iter()
         .filter(|canister_name| !pull_canisters_in_config.contains_key(canister_name))
+        .filter(|canister_name| !exclude.contains(canister_name))
         .collect();
 
-    if some_canister.is_some() {
-        info!(log, "Deploying: {}", canisters_to_install.join(" "));
+    let message = if some_canister.is_some() || !exclude.is_empty() {
+        format!("Deploying: {}", canisters_to_install.join(" "))
     } else {
-        info!(log, "Deploying all canisters.");
-    }
-    if canisters_to_load
+        "Deploying all canisters.".to_string()
+    };
+    let force_reinstall = matches!(deploy_mode, ForceReinstallSingleCanister(_));
+    let all_created = canisters_to_load
         .iter()
-        .any(|canister| initial_canister_id_store.find(canister).is_none())
-    {
+        .all(|canister| initial_canister_id_store.find(canister).is_some());
+    if plan_mode != DeployPlanMode::Skip && !all_created {
+        let plan = creation_plan(&canisters_to_install, &initial_canister_id_store);
+        if !confirm_plan(env, &plan, plan_mode)? {
+            return Ok(());
+        }
+    }
+
+    report_step(log, Step::Deploy, StepStatus::Started, None, Some(message));
+    if !all_created {
         register_canisters(
             env,
             &canisters_to_load,
@@ -132,12 +183,37 @@ pub async fn deploy_canisters(
         &canisters_to_build,
         &config,
         env_file.clone(),
+        profile,
     )
     .await?;
 
+    if plan_mode != DeployPlanMode::Skip && all_created {
+        let plan = deploy_plan(
+            env,
+            &canisters_to_install,
+            &initial_canister_id_store,
+            &config,
+            force_reinstall,
+            upgrade_unchanged,
+            no_asset_upgrade,
+            call_sender,
+        )
+        .await?;
+        if !confirm_plan(env, &plan, plan_mode)? {
+            return Ok(());
+        }
+    }
+
     match deploy_mode {
         NormalDeploy | ForceReinstallSingleCanister(_) => {
-            let force_reinstall = matches!(deploy_mode, ForceReinstallSingleCanister(_));
+            update_initialization_settings(
+                env,
+                &canisters_to_install,
+                &initial_canister_id_store,
+                &config,
+                call_sender,
+            )
+            .await?;
             install_canisters(
                 env,
                 &canisters_to_install,
@@ -152,9 +228,19 @@ pub async fn deploy_canisters(
                 skip_consent,
                 env_file.as_deref(),
                 no_asset_upgrade,
+                allow_unverified,
             )
             .await?;
-            info!(log, "Deployed canisters.");
+            if check_certification {
+                check_certification_of_assets(env, &canisters_to_install, &config).await?;
+            }
+            report_step(
+                log,
+                Step::Deploy,
+                StepStatus::Finished,
+                None,
+                Some("Deployed canisters.".to_string()),
+            );
         }
         PrepareForProposal(canister_name) => {
             prepare_assets_for_commit(env, &initial_canister_id_store, &config, canister_name)
@@ -168,14 +254,28 @@ pub async fn deploy_canisters(
     Ok(())
 }
 
+/// Without a canister name, all canisters that are not excluded are deployed.
+/// Excluded canisters are still collected if another canister depends on them.
 #[context("Failed to collect canisters and their dependencies.")]
 fn canister_with_dependencies(
     config: &Config,
     some_canister: Option<&str>,
+    exclude: &[String],
 ) -> DfxResult<Vec<String>> {
-    let mut canister_names = config
-        .get_config()
-        .get_canister_names_with_dependencies(some_canister)?;
+    let config_interface = config.get_config();
+    let mut canister_names = if some_canister.is_none() && !exclude.is_empty() {
+        let mut canister_names = BTreeSet::new();
+        for canister_name in config_interface.get_canister_names_with_dependencies(None)? {
+            if !exclude.contains(&canister_name) {
+                canister_names.extend(
+                    config_interface.get_canister_names_with_dependencies(Some(&canister_name))?,
+                );
+            }
+        }
+        canister_names.into_iter().collect()
+    } else {
+        config_interface.get_canister_names_with_dependencies(some_canister)?
+    };
     canister_names.sort();
     Ok(canister_names)
 }
@@ -210,39 +310,6 @@ async fn register_canisters(
     } else {
         info!(env.get_logger(), "Creating canisters...");
         for canister_name in &canisters_to_create {
-            let config_interface = config.get_config();
-            let compute_allocation = config_interface
-                .get_compute_allocation(canister_name)?
-                .map(|arg| {
-                    ComputeAllocation::try_from(arg)
-                        .context("Compute Allocation must be a percentage.")
-                })
-                .transpose()?;
-            let memory_allocation = config_interface
-                .get_memory_allocation(canister_name)?
-                .map(|arg| {
-                    u64::try_from(arg.get_bytes())
-                        .map_err(|e| anyhow!(e))
-                        .and_then(|n| Ok(MemoryAllocation::try_from(n)?))
-                        .context(
-                            "Memory allocation must be between 0 and 2^48 (i.e 256TB), inclusively.",
-                        )
-                })
-                .transpose()?;
-            let freezing_threshold =
-                config_interface
-                    .get_freezing_threshold(canister_name)?
-                    .map(|arg| {
-                        FreezingThreshold::try_from(arg.as_secs())
-                            .expect("Freezing threshold must be between 0 and 2^64-1, inclusively.")
-                    });
-            let reserved_cycles_limit = config_interface
-                .get_reserved_cycles_limit(canister_name)?
-                .map(|arg| {
-                    ReservedCyclesLimit::try_from(arg)
-                        .expect("Reserved cycles limit must be between 0 and 2^128-1, inclusively.")
-                });
-            let controllers = None;
             create_canister(
                 env,
                 canister_name,
@@ -251,13 +318,7 @@ async fn register_canisters(
                 call_sender,
                 no_wallet,
                 from_subaccount,
-                CanisterSettings {
-                    controllers,
-                    compute_allocation,
-                    memory_allocation,
-                    freezing_threshold,
-                    reserved_cycles_limit,
-                },
+                initialization_settings(config, canister_name)?,
                 created_at_time,
                 subnet_selection.clone(),
             )
@@ -267,6 +328,63 @@ async fn register_canisters(
     Ok(())
 }
 
+/// The settings of a canister from its `initialization_values` in dfx.json.
+#[context(
+    "Failed to read the initialization values of canister {}.",
+    canister_name
+)]
+fn initialization_settings(config: &Config, canister_name: &str) -> DfxResult<CanisterSettings> {
+    let config_interface = Some(config.get_config());
+    let canister_name = Some(canister_name);
+    Ok(CanisterSettings {
+        controllers: None,
+        compute_allocation: get_compute_allocation(None, config_interface, canister_name)?,
+        memory_allocation: get_memory_allocation(None, config_interface, canister_name)?,
+        freezing_threshold: get_freezing_threshold(None, config_interface, canister_name)?,
+        reserved_cycles_limit: get_reserved_cycles_limit(None, config_interface, canister_name)?,
+        wasm_memory_limit: get_wasm_memory_limit(None, config_interface, canister_name)?,
+    })
+}
+
+/// Applies the initialization values in dfx.json to canisters that were created before,
+/// so that values added or changed after creation take effect.
+/// Only the settings that differ from those of the canister are sent.
+#[context("Failed to update the settings of canisters.")]
+async fn update_initialization_settings(
+    env: &dyn Environment,
+    canister_names: &[String],
+    initial_canister_id_store: &CanisterIdStore,
+    config: &Config,
+    call_sender: &CallSender,
+) -> DfxResult {
+    if env.get_network_descriptor().is_playground() {
+        return Ok(());
+    }
+    for canister_name in canister_names {
+        let Some(canister_id) = initial_canister_id_store.find(canister_name) else {
+            continue;
+        };
+        let (settings, changes) = changed_settings(
+            env,
+            canister_id,
+            initialization_settings(config, canister_name)?,
+            call_sender,
+        )
+        .await?;
+        if changes.is_empty() {
+            continue;
+        }
+        info!(
+            env.get_logger(),
+            "Updating settings of {}: {}",
+            canister_name,
+            changes.join(", ")
+        );
+        update_settings(env, canister_id, settings, call_sender).await?;
+    }
+    Ok(())
+}
+
 #[context("Failed to build all canisters.")]
 async fn build_canisters(
     env: &dyn Environment,
@@ -274,6 +392,7 @@ async fn build_canisters(
     canisters_to_build: &[String],
     config: &Config,
     env_file: Option<PathBuf>,
+    profile: Option<&str>,
 ) -> DfxResult<CanisterPool> {
     let log = env.get_logger();
     info!(log, "Building canisters...");
@@ -283,7 +402,9 @@ async fn build_canisters(
     let build_config =
         BuildConfig::from_config(config, env.get_network_descriptor().is_playground())?
             .with_canisters_to_build(canisters_to_build.into())
-            .with_env_file(env_file);
+            .with_env_file(env_file)
+            .with_profile(profile)?;
+    fetch_remote_candid_files(env, &canister_pool).await?;
     canister_pool.build_or_fail(log, &build_config).await?;
     Ok(canister_pool)
 }
@@ -303,12 +424,13 @@ async fn install_canisters(
     skip_consent: bool,
     env_file: Option<&Path>,
     no_asset_upgrade: bool,
+    allow_unverified: bool,
 ) -> DfxResult {
     info!(env.get_logger(), "Installing canisters...");
 
     let mut canister_id_store = env.get_canister_id_store()?;
 
-    for canister_name in canister_names {
+    for (index, canister_name) in canister_names.iter().enumerate() {
         let install_mode = if force_reinstall {
             Some(InstallMode::Reinstall)
         } else {
@@ -321,7 +443,7 @@ async fn install_canisters(
         let canister_id = canister_id_store.get(canister_name)?;
         let canister_info = CanisterInfo::load(config, canister_name, Some(canister_id))?;
 
-        install_canister(
+        let result = install_canister(
             env,
             &mut canister_id_store,
             canister_id,
@@ -336,13 +458,48 @@ async fn install_canisters(
             skip_consent,
             env_file,
             no_asset_upgrade,
+            allow_unverified,
         )
-        .await?;
+        .await;
+        if result.is_err() {
+            report_step(
+                env.get_logger(),
+                Step::Install,
+                StepStatus::Failed,
+                Some(canister_name),
+                None,
+            );
+        }
+        result.with_context(|| partial_install_report(canister_names, index))?;
     }
 
     Ok(())
 }
 
+/// Describes which canisters were installed before the canister at `failed` failed to install.
+/// Installs cannot be rolled back, so the user has to know where to resume.
+fn partial_install_report(canister_names: &[String], failed: usize) -> String {
+    let mut report = format!("Failed to install canister {}.", canister_names[failed]);
+    if failed > 0 {
+        report.push_str(&format!(
+            " Already installed: {}.",
+            canister_names[..failed].join(", ")
+        ));
+    }
+    if failed + 1 < canister_names.len() {
+        report.push_str(&format!(
+            " Not installed: {}.",
+            canister_names[failed + 1..].join(", ")
+        ));
+    }
+    if failed > 0 || failed + 1 < canister_names.len() {
+        report.push_str(
+            " Run the deploy again to resume: canisters whose module did not change are skipped.",
+        );
+    }
+    report
+}
+
 #[context("Failed to prepare assets for commit.")]
 async fn prepare_assets_for_commit(
     env: &dyn Environment,
@@ -367,6 +524,23 @@ async fn prepare_assets_for_commit(
     Ok(())
 }
 
+#[context("Failed to check the certification of assets.")]
+async fn check_certification_of_assets(
+    env: &dyn Environment,
+    canister_names: &[String],
+    config: &Config,
+) -> DfxResult {
+    let canister_id_store = env.get_canister_id_store()?;
+    for canister_name in canister_names {
+        let canister_id = canister_id_store.get(canister_name)?;
+        let canister_info = CanisterInfo::load(config, canister_name, Some(canister_id))?;
+        if canister_info.is_assets() {
+            check_assets_certification(&canister_info, env.get_agent(), env.get_logger()).await?;
+        }
+    }
+    Ok(())
+}
+
 #[context("Failed to compute evidence.")]
 async fn compute_evidence(
     env: &dyn Environment,
@@ -405,3 +579,259 @@ async fn compute_evidence(
 
     Ok(())
 }
+
+/// Whether `dfx deploy` shows what it is about to change before changing it.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum DeployPlanMode {
+    /// Deploys without showing a plan.
+    Skip,
+    /// Shows the plan, and deploys if the user confirms it.
+    Confirm,
+    /// Shows the plan without deploying.
+    PrintOnly,
+}
+
+/// What a deploy does to a canister.
+enum PlannedAction {
+    CreateAndInstall,
+    /// Installed or upgraded, once the new canisters are created and built.
+    Deploy,
+    Install {
+        new_hash: Vec<u8>,
+    },
+    Upgrade {
+        old_hash: Vec<u8>,
+        new_hash: Vec<u8>,
+    },
+    Reinstall {
+        old_hash: Vec<u8>,
+        new_hash: Vec<u8>,
+    },
+    Unchanged {
+        hash: Vec<u8>,
+    },
+}
+
+struct PlannedCanister {
+    name: String,
+    canister_id: Option<Principal>,
+    action: PlannedAction,
+    /// The settings from dfx.json that differ from those of the canister.
+    settings: Vec<String>,
+    syncs_assets: bool,
+}
+
+impl Display for PlannedCanister {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.canister_id {
+            Some(canister_id) => write!(f, "  {} ({}): ", self.name, canister_id)?,
+            None => write!(f, "  {}: ", self.name)?,
+        }
+        match &self.action {
+            PlannedAction::CreateAndInstall => write!(f, "create, then install")?,
+            PlannedAction::Deploy => write!(f, "install or upgrade, once built")?,
+            PlannedAction::Install { new_hash } => {
+                write!(f, "install 0x{}", hex::encode(new_hash))?
+            }
+            PlannedAction::Upgrade { old_hash, new_hash } => write!(
+                f,
+                "upgrade 0x{} -> 0x{}",
+                hex::encode(old_hash),
+                hex::encode(new_hash)
+            )?,
+            PlannedAction::Reinstall { old_hash, new_hash } => write!(
+                f,
+                "reinstall 0x{} -> 0x{} (erases the state of the canister)",
+                hex::encode(old_hash),
+                hex::encode(new_hash)
+            )?,
+            PlannedAction::Unchanged { hash } => write!(f, "unchanged 0x{}", hex::encode(hash))?,
+        }
+        for setting in &self.settings {
+            write!(f, "\n    {}", setting)?;
+        }
+        if self.syncs_assets {
+            write!(f, "\n    assets are synchronized")?;
+        }
+        Ok(())
+    }
+}
+
+/// The plan for a deploy that creates canisters. Canisters have to be created before they can
+/// be built, so the modules to install are not known yet.
+fn creation_plan(
+    canister_names: &[String],
+    canister_id_store: &CanisterIdStore,
+) -> Vec<PlannedCanister> {
+    canister_names
+        .iter()
+        .map(|canister_name| {
+            let canister_id = canister_id_store.find(canister_name);
+            PlannedCanister {
+                name: canister_name.clone(),
+                canister_id,
+                action: match canister_id {
+                    Some(_) => PlannedAction::Deploy,
+                    None => PlannedAction::CreateAndInstall,
+                },
+                settings: vec![],
+                syncs_assets: false,
+            }
+        })
+        .collect()
+}
+
+/// The plan for canisters that exist and have been built, comparing their new modules and
+/// settings with those of the deployed canisters.
+#[context("Failed to plan the deploy.")]
+async fn deploy_plan(
+    env: &dyn Environment,
+    canister_names: &[String],
+    canister_id_store: &CanisterIdStore,
+    config: &Config,
+    force_reinstall: bool,
+    upgrade_unchanged: bool,
+    no_asset_upgrade: bool,
+    call_sender: &CallSender,
+) -> DfxResult<Vec<PlannedCanister>> {
+    let agent = env.get_agent();
+    let mut plan = vec![];
+    for canister_name in canister_names {
+        let canister_id = canister_id_store.get(canister_name)?;
+        let canister_info = CanisterInfo::load(config, canister_name, Some(canister_id))?;
+        let new_hash =
+            Sha256::digest(dfx_core::fs::read(&canister_info.get_build_wasm_path())?).to_vec();
+        let installed_hash = read_state_tree_canister_module_hash(agent, canister_id).await?;
+        let action = match installed_hash {
+            None => PlannedAction::Install { new_hash },
+            Some(old_hash) if force_reinstall => PlannedAction::Reinstall { old_hash, new_hash },
+            Some(hash) if canister_info.is_assets() && no_asset_upgrade => {
+                PlannedAction::Unchanged { hash }
+            }
+            Some(hash) if hash == new_hash && !upgrade_unchanged => {
+                PlannedAction::Unchanged { hash }
+            }
+            Some(old_hash) => PlannedAction::Upgrade { old_hash, new_hash },
+        };
+        let settings = if env.get_network_descriptor().is_playground() {
+            vec![]
+        } else {
+            changed_settings(
+                env,
+                canister_id,
+                initialization_settings(config, canister_name)?,
+                call_sender,
+            )
+            .await?
+            .1
+        };
+        plan.push(PlannedCanister {
+            name: canister_name.clone(),
+            canister_id: Some(canister_id),
+            action,
+            settings,
+            syncs_assets: canister_info.is_assets(),
+        });
+    }
+    Ok(plan)
+}
+
+/// Returns the settings in dfx.json that differ from the current settings of the canister,
+/// and a description of each change.
+async fn changed_settings(
+    env: &dyn Environment,
+    canister_id: Principal,
+    settings: CanisterSettings,
+    call_sender: &CallSender,
+) -> DfxResult<(CanisterSettings, Vec<String>)> {
+    let mut changed = CanisterSettings::default();
+    let mut changes = vec![];
+    if settings.compute_allocation.is_none()
+        && settings.memory_allocation.is_none()
+        && settings.freezing_threshold.is_none()
+        && settings.reserved_cycles_limit.is_none()
+        && settings.wasm_memory_limit.is_none()
+    {
+        return Ok((changed, changes));
+    }
+    let current = get_canister_status(env, canister_id, call_sender)
+        .await?
+        .settings;
+    let mut differs = |name: &str, current: Option<Nat>, new: Nat| {
+        if current.as_ref() == Some(&new) {
+            return false;
+        }
+        let current = current.map_or_else(|| "not set".to_string(), |v| v.to_string());
+        changes.push(format!("{name}: {current} -> {new}"));
+        true
+    };
+    if let Some(v) = settings.compute_allocation {
+        if differs(
+            "compute_allocation",
+            Some(current.compute_allocation),
+            Nat::from(u8::from(v)),
+        ) {
+            changed.compute_allocation = Some(v);
+        }
+    }
+    if let Some(v) = settings.memory_allocation {
+        if differs(
+            "memory_allocation",
+            Some(current.memory_allocation),
+            Nat::from(u64::from(v)),
+        ) {
+            changed.memory_allocation = Some(v);
+        }
+    }
+    if let Some(v) = settings.freezing_threshold {
+        if differs(
+            "freezing_threshold",
+            Some(current.freezing_threshold),
+            Nat::from(u64::from(v)),
+        ) {
+            changed.freezing_threshold = Some(v);
+        }
+    }
+    if let Some(v) = settings.reserved_cycles_limit {
+        if differs(
+            "reserved_cycles_limit",
+            current.reserved_cycles_limit,
+            Nat::from(u128::from(v)),
+        ) {
+            changed.reserved_cycles_limit = Some(v);
+        }
+    }
+    if let Some(v) = settings.wasm_memory_limit {
+        // The canister status of ic-utils does not include the wasm memory limit yet.
+        let current = get_canister_status_extras(env, canister_id, call_sender)
+            .await?
+            .wasm_memory_limit;
+        if differs("wasm_memory_limit", current.map(Nat::from), Nat::from(v)) {
+            changed.wasm_memory_limit = Some(v);
+        }
+    }
+    Ok((changed, changes))
+}
+
+/// Shows the plan, and returns whether to go ahead with the deploy.
+fn confirm_plan(
+    env: &dyn Environment,
+    plan: &[PlannedCanister],
+    plan_mode: DeployPlanMode,
+) -> DfxResult<bool> {
+    println!(
+        "Deploy plan for network '{}':",
+        env.get_network_descriptor().name
+    );
+    for planned in plan {
+        println!("{}", planned);
+    }
+    match plan_mode {
+        DeployPlanMode::Skip => Ok(true),
+        DeployPlanMode::PrintOnly => Ok(false),
+        DeployPlanMode::Confirm => {
+            ask_for_consent("The deploy makes the changes above.")?;
+            Ok(true)
+        }
+    }
+}
@@ -6,12 +6,17 @@ use crate::lib::installers::assets::post_install_store_assets;
 use crate::lib::models::canister::CanisterPool;
 use crate::lib::named_canister;
 use crate::lib::operations::canister::motoko_playground::authorize_asset_uploader;
+use crate::lib::progress_bar::{report_step, Step, StepStatus};
+use crate::lib::retryable::retry_policy;
 use crate::lib::state_tree::canister_info::read_state_tree_canister_module_hash;
+use crate::lib::wasm::allowlist::verify_wasm_hash;
 use crate::util::assets::wallet_wasm;
-use crate::util::{blob_from_arguments, get_candid_init_type, read_module_metadata};
+use crate::util::{
+    blob_from_arguments, get_candid_init_type, missing_init_arg_message, read_module_metadata,
+    requires_arguments,
+};
 use anyhow::{anyhow, bail, Context};
 use backoff::backoff::Backoff;
-use backoff::ExponentialBackoff;
 use candid::Principal;
 use dfx_core::canister::{build_wallet_canister, install_canister_wasm, install_mode_to_prompt};
 use dfx_core::cli::ask_for_consent;
@@ -49,11 +54,12 @@ pub async fn install_canister(
     skip_consent: bool,
     env_file: Option<&Path>,
     no_asset_upgrade: bool,
+    allow_unverified: bool,
 ) -> DfxResult {
     let log = env.get_logger();
     let agent = env.get_agent();
     let network = env.get_network_descriptor();
-    if !network.is_ic && named_canister::get_ui_canister_id(canister_id_store).is_none() {
+    if !network.is_ic && named_canister::get_ui_canister_id(network, canister_id_store).is_none() {
         named_canister::install_ui_canister(env, canister_id_store, None).await?;
     }
     let installed_module_hash = read_state_tree_canister_module_hash(agent, canister_id).await?;
@@ -73,9 +79,14 @@ pub async fn install_canister(
     });
     let mode_str = install_mode_to_prompt(&mode);
     let canister_name = canister_info.get_name();
-    info!(
+    report_step(
         log,
-        "{mode_str} code for canister {canister_name}, with canister ID {canister_id}",
+        Step::Install,
+        StepStatus::Started,
+        Some(canister_name),
+        Some(format!(
+            "{mode_str} code for canister {canister_name}, with canister ID {canister_id}"
+        )),
     );
     if !skip_consent && matches!(mode, InstallMode::Reinstall | InstallMode::Upgrade { .. }) {
         let candid = read_module_metadata(agent, canister_id, "candid:service").await;
@@ -122,6 +133,13 @@ pub async fn install_canister(
     let wasm_module = dfx_core::fs::read(&wasm_path)?;
     let new_hash = Sha256::digest(&wasm_module);
     debug!(log, "New wasm module hash: {}", hex::encode(new_hash));
+    verify_wasm_hash(
+        log,
+        canister_name,
+        canister_info.get_allowed_wasm_hashes(),
+        &new_hash,
+        allow_unverified,
+    )?;
 
     if matches!(mode, InstallMode::Upgrade { .. })
         && matches!(&installed_module_hash, Some(old_hash) if old_hash[..] == new_hash[..])
@@ -164,6 +182,12 @@ The command line value will be used.",
             (None, Some(_)) => (argument_from_json, Some("idl")), // `init_arg` in dfx.json is always in Candid format
             (None, None) => (None, None),
         };
+        // --yes means nobody is there to answer a prompt for the argument.
+        if let (true, None, Some((_, init_func))) = (skip_consent, argument, &init_type) {
+            if requires_arguments(init_func) {
+                bail!(missing_init_arg_message(init_func));
+            }
+        }
         let install_args =
             blob_from_arguments(Some(env), argument, None, argument_type, &init_type, true)?;
         if let Some(timestamp) = canister_id_store.get_timestamp(canister_info.get_name()) {
@@ -257,6 +281,13 @@ The command line value will be used.",
         )?;
     }
 
+    report_step(
+        log,
+        Step::Install,
+        StepStatus::Finished,
+        Some(canister_info.get_name()),
+        None,
+    );
     Ok(())
 }
 
@@ -302,7 +333,7 @@ async fn wait_for_module_hash(
     old_hash: Option<&[u8]>,
     new_hash: &[u8],
 ) -> DfxResult {
-    let mut retry_policy = ExponentialBackoff::default();
+    let mut retry_policy = retry_policy(env);
     let mut times = 0;
     loop {
         match read_state_tree_canister_module_hash(agent, canister_id).await? {
@@ -1,29 +1,136 @@
 pub(crate) mod create_canister;
 pub(crate) mod deploy_canisters;
 pub(crate) mod install_canister;
+pub(crate) mod snapshot;
 pub use create_canister::create_canister;
 
 use crate::lib::canister_info::CanisterInfo;
 use crate::lib::environment::Environment;
 use crate::lib::error::DfxResult;
 use crate::lib::ic_attributes::CanisterSettings as DfxCanisterSettings;
+use crate::lib::models::canister::CanisterPool;
+use crate::lib::root_key::fetch_root_key_if_needed;
+use crate::util::fetch_remote_did_file;
 use anyhow::{bail, Context};
 use candid::utils::ArgumentDecoder;
 use candid::CandidType;
 use candid::Principal as CanisterId;
-use candid::Principal;
+use candid::{Nat, Principal};
 use dfx_core::canister::build_wallet_canister;
+use dfx_core::config::model::canister_id_store::CanisterIdStore;
 use dfx_core::identity::CallSender;
 use fn_error_context::context;
+use futures::{stream, StreamExt};
 use ic_utils::interfaces::management_canister::builders::CanisterSettings;
-use ic_utils::interfaces::management_canister::{MgmtMethod, StatusCallResult};
+use ic_utils::interfaces::management_canister::{
+    CanisterStatus, MgmtMethod, QueryStats, StatusCallResult,
+};
 use ic_utils::interfaces::ManagementCanister;
 use ic_utils::Argument;
 pub use install_canister::install_wallet;
+use num_traits::ToPrimitive;
+use serde::Deserialize;
+use slog::{error, info, warn};
+use std::future::Future;
 use std::path::PathBuf;
 
 pub mod motoko_playground;
 
+/// Fetches the Candid interface of the remote canisters of the pool that have no candid file configured,
+/// from their `candid:service` metadata, unless it is cached already.
+/// Delete the cached files under .dfx/<network>/remote_candid to fetch them again.
+/// A canister whose interface cannot be fetched only gets a warning, like a missing candid file.
+pub async fn fetch_remote_candid_files(env: &dyn Environment, pool: &CanisterPool) -> DfxResult {
+    let to_fetch: Vec<_> = pool
+        .get_canister_list()
+        .into_iter()
+        .filter_map(|canister| {
+            let (id, path) = canister.get_info().get_remote_candid_to_fetch()?;
+            (!path.exists()).then(|| (canister.get_name().to_string(), id, path))
+        })
+        .collect();
+    if to_fetch.is_empty() {
+        return Ok(());
+    }
+    let log = env.get_logger();
+    if let Err(err) = fetch_root_key_if_needed(env).await {
+        warn!(
+            log,
+            "Cannot fetch the Candid interface of remote canisters: {err:#}"
+        );
+        return Ok(());
+    }
+    for (name, id, path) in to_fetch {
+        info!(
+            log,
+            "Fetching the Candid interface of remote canister {name} ({id})."
+        );
+        match fetch_remote_did_file(env.get_agent(), id).await {
+            Some(candid) => {
+                dfx_core::fs::composite::ensure_parent_dir_exists(&path)?;
+                dfx_core::fs::write(&path, candid)?;
+            }
+            None => warn!(
+                log,
+                "Failed to fetch the Candid interface of remote canister {name} ({id}). \
+                Set remote.candid in dfx.json to its candid file if the canister has no candid:service metadata."
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// The number of canisters that `--all` works on at the same time, unless `--concurrency` is given.
+pub const DEFAULT_ALL_CANISTERS_CONCURRENCY: &str = "4";
+
+/// Runs `operation` for each of the canisters, at most `concurrency` of them at a time.
+/// A failure does not stop the operation on the other canisters.
+/// If any of them failed, the canisters that succeeded and those that failed are listed at the end.
+pub async fn for_each_canister<F, Fut>(
+    env: &dyn Environment,
+    action: &str,
+    canisters: Vec<String>,
+    concurrency: usize,
+    operation: F,
+) -> DfxResult
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = DfxResult>,
+{
+    let log = env.get_logger();
+    let mut results: Vec<(String, DfxResult)> = stream::iter(canisters)
+        .map(|canister| {
+            let result = operation(canister.clone());
+            async move { (canister, result.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+    if results.iter().all(|(_, result)| result.is_ok()) {
+        return Ok(());
+    }
+
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let (succeeded, failed): (Vec<_>, Vec<_>) =
+        results.into_iter().partition(|(_, result)| result.is_ok());
+    if !succeeded.is_empty() {
+        let names: Vec<_> = succeeded.iter().map(|(name, _)| name.as_str()).collect();
+        info!(log, "Canisters that succeeded: {}", names.join(", "));
+    }
+    for (name, result) in &failed {
+        if let Err(err) = result {
+            error!(log, "Failed to {action} canister {name}: {err:#}");
+        }
+    }
+    let names: Vec<_> = failed.iter().map(|(name, _)| name.as_str()).collect();
+    bail!(
+        "Failed to {action} {} of {} canister(s): {}.",
+        failed.len(),
+        failed.len() + succeeded.len(),
+        names.join(", ")
+    )
+}
+
 #[context(
     "Failed to call update function '{}' regarding canister '{}'.",
     method,
@@ -96,6 +203,98 @@ pub async fn get_canister_status(
     Ok(out)
 }
 
+/// The memory of a canister, split by kind.
+#[derive(Deserialize, CandidType)]
+pub struct MemoryMetrics {
+    pub wasm_memory_size: Nat,
+    pub stable_memory_size: Nat,
+}
+
+/// The settings of a canister, including those that the canister status of ic-utils does not have yet.
+#[derive(Deserialize, CandidType)]
+pub struct DefiniteSettings {
+    pub controllers: Vec<Principal>,
+    pub compute_allocation: Nat,
+    pub memory_allocation: Nat,
+    pub freezing_threshold: Nat,
+    pub reserved_cycles_limit: Option<Nat>,
+    pub wasm_memory_limit: Option<Nat>,
+}
+
+/// The status of a canister, including the fields that the canister status of ic-utils does not have yet.
+#[derive(Deserialize, CandidType)]
+pub struct FullStatusCallResult {
+    pub status: CanisterStatus,
+    pub settings: DefiniteSettings,
+    pub module_hash: Option<Vec<u8>>,
+    pub memory_size: Nat,
+    pub cycles: Nat,
+    pub reserved_cycles: Nat,
+    pub idle_cycles_burned_per_day: Nat,
+    pub query_stats: QueryStats,
+    /// Only reported by newer replicas.
+    pub memory_metrics: Option<MemoryMetrics>,
+}
+
+impl FullStatusCallResult {
+    pub fn wasm_memory_limit(&self) -> DfxResult<Option<u64>> {
+        self.settings
+            .wasm_memory_limit
+            .as_ref()
+            .map(|limit| {
+                limit
+                    .0
+                    .to_u64()
+                    .context("The Wasm memory limit does not fit in 64 bits.")
+            })
+            .transpose()
+    }
+}
+
+/// The fields of the canister status that the canister status of ic-utils does not have yet.
+pub struct CanisterStatusExtras {
+    pub wasm_memory_limit: Option<u64>,
+    /// Only reported by newer replicas.
+    pub memory_metrics: Option<MemoryMetrics>,
+}
+
+/// Returns the status of a canister, with the Wasm memory limit and the memory metrics.
+#[context("Failed to get canister status of {}.", canister_id)]
+pub async fn get_full_canister_status(
+    env: &dyn Environment,
+    canister_id: Principal,
+    call_sender: &CallSender,
+) -> DfxResult<FullStatusCallResult> {
+    #[derive(CandidType)]
+    struct In {
+        canister_id: Principal,
+    }
+
+    let (out,): (FullStatusCallResult,) = do_management_call(
+        env,
+        canister_id,
+        MgmtMethod::CanisterStatus.as_ref(),
+        In { canister_id },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(out)
+}
+
+/// Returns the Wasm memory limit and the memory metrics of a canister.
+pub async fn get_canister_status_extras(
+    env: &dyn Environment,
+    canister_id: Principal,
+    call_sender: &CallSender,
+) -> DfxResult<CanisterStatusExtras> {
+    let status = get_full_canister_status(env, canister_id, call_sender).await?;
+    Ok(CanisterStatusExtras {
+        wasm_memory_limit: status.wasm_memory_limit()?,
+        memory_metrics: status.memory_metrics,
+    })
+}
+
 #[context("Failed to start canister {}.", canister_id)]
 pub async fn start_canister(
     env: &dyn Environment,
@@ -153,18 +352,43 @@ pub async fn update_settings(
     settings: DfxCanisterSettings,
     call_sender: &CallSender,
 ) -> DfxResult {
+    // The canister settings of ic-utils do not have the Wasm memory limit yet.
+    #[derive(candid::CandidType)]
+    struct Settings {
+        controllers: Option<Vec<Principal>>,
+        compute_allocation: Option<Nat>,
+        memory_allocation: Option<Nat>,
+        freezing_threshold: Option<Nat>,
+        reserved_cycles_limit: Option<Nat>,
+        wasm_memory_limit: Option<Nat>,
+    }
     #[derive(candid::CandidType)]
     struct In {
         canister_id: Principal,
-        settings: CanisterSettings,
+        settings: Settings,
     }
+    let wasm_memory_limit = settings.wasm_memory_limit.map(Nat::from);
+    let CanisterSettings {
+        controllers,
+        compute_allocation,
+        memory_allocation,
+        freezing_threshold,
+        reserved_cycles_limit,
+    } = settings.into();
     do_management_call(
         env,
         canister_id,
         MgmtMethod::UpdateSettings.as_ref(),
         In {
             canister_id,
-            settings: settings.into(),
+            settings: Settings {
+                controllers,
+                compute_allocation,
+                memory_allocation,
+                freezing_threshold,
+                reserved_cycles_limit,
+                wasm_memory_limit,
+            },
         },
         call_sender,
         0,
@@ -196,6 +420,58 @@ pub async fn uninstall_code(
     Ok(())
 }
 
+/// The hash of a chunk in the chunk store of a canister, as returned by `stored_chunks`.
+#[derive(CandidType, Deserialize)]
+pub struct ChunkHash {
+    #[serde(with = "serde_bytes")]
+    pub hash: Vec<u8>,
+}
+
+#[context("Failed to list the stored chunks of {}.", canister_id)]
+pub async fn stored_chunks(
+    env: &dyn Environment,
+    canister_id: Principal,
+    call_sender: &CallSender,
+) -> DfxResult<Vec<ChunkHash>> {
+    #[derive(CandidType)]
+    struct In {
+        canister_id: Principal,
+    }
+    let (out,): (Vec<ChunkHash>,) = do_management_call(
+        env,
+        canister_id,
+        "stored_chunks",
+        In { canister_id },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(out)
+}
+
+#[context("Failed to clear the chunk store of {}.", canister_id)]
+pub async fn clear_chunk_store(
+    env: &dyn Environment,
+    canister_id: Principal,
+    call_sender: &CallSender,
+) -> DfxResult {
+    #[derive(CandidType)]
+    struct In {
+        canister_id: Principal,
+    }
+    do_management_call(
+        env,
+        canister_id,
+        "clear_chunk_store",
+        In { canister_id },
+        call_sender,
+        0,
+    )
+    .await?;
+
+    Ok(())
+}
+
 #[context("Failed to delete {}.", canister_id)]
 pub async fn delete_canister(
     env: &dyn Environment,
@@ -293,3 +569,21 @@ pub fn get_local_cid_and_candid_path(
         canister_info.get_output_idl_path(),
     ))
 }
+
+/// Looks up a canister that a command addresses by name: a canister of the project, with its Candid file,
+/// or else an alias from dfx.json, which has none.
+pub fn get_named_cid_and_candid_path(
+    env: &dyn Environment,
+    canister_id_store: &CanisterIdStore,
+    canister_name: &str,
+) -> DfxResult<(CanisterId, Option<PathBuf>)> {
+    if canister_id_store.is_alias(canister_name) {
+        Ok((
+            canister_id_store.get_including_aliases(canister_name)?,
+            None,
+        ))
+    } else {
+        let canister_id = canister_id_store.get(canister_name)?;
+        get_local_cid_and_candid_path(env, canister_name, Some(canister_id))
+    }
+}
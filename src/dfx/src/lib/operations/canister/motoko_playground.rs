@@ -116,7 +116,6 @@ pub async fn reserve_canister_with_playground(
         bail!("Cannot reserve playground canister in CI, please run `dfx start` to use the local replica.")
     }
 
-    let mut canister_id_store = env.get_canister_id_store()?;
     let (timestamp, nonce) = create_nonce();
     let get_can_arg = Encode!(&GetCanisterIdArgs { timestamp, nonce }, &Origin::new())?;
     let result = agent
@@ -126,6 +125,8 @@ pub async fn reserve_canister_with_playground(
         .await
         .context("Failed to reserve canister at the playground.")?;
     let reserved_canister = Decode!(&result, CanisterInfo)?;
+    // Other canisters may have been reserved concurrently, so the store is read after the call.
+    let mut canister_id_store = env.get_canister_id_store()?;
     canister_id_store.add(
         canister_name,
         &reserved_canister.id.to_string(),
@@ -0,0 +1,461 @@
+//! Canister snapshots, and the management canister methods to download and upload their data,
+//! which `dfx canister export-state` and `dfx canister import-state` use to move the state of a canister.
+use super::{do_management_call, ChunkHash};
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use candid::{CandidType, Principal};
+use dfx_core::identity::CallSender;
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+
+/// The largest slice of snapshot data that is downloaded or uploaded with one call.
+pub const SNAPSHOT_DATA_CHUNK_SIZE: u64 = 1024 * 1024;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum ExportedGlobal {
+    #[serde(rename = "i32")]
+    I32(i32),
+    #[serde(rename = "i64")]
+    I64(i64),
+    #[serde(rename = "f32")]
+    F32(f32),
+    #[serde(rename = "f64")]
+    F64(f64),
+    #[serde(rename = "v128")]
+    V128(u128),
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum GlobalTimer {
+    #[serde(rename = "inactive")]
+    Inactive,
+    #[serde(rename = "active")]
+    Active(u64),
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum OnLowWasmMemoryHookStatus {
+    #[serde(rename = "condition_not_satisfied")]
+    ConditionNotSatisfied,
+    #[serde(rename = "ready")]
+    Ready,
+    #[serde(rename = "executed")]
+    Executed,
+}
+
+/// Everything about a snapshot besides its data.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SnapshotMetadata {
+    pub wasm_module_size: u64,
+    pub exported_globals: Vec<ExportedGlobal>,
+    pub wasm_memory_size: u64,
+    pub stable_memory_size: u64,
+    /// The hex-encoded hashes of the chunks in the chunk store of the canister.
+    pub wasm_chunk_store: Vec<String>,
+    #[serde(with = "hex")]
+    pub certified_data: Vec<u8>,
+    pub global_timer: Option<GlobalTimer>,
+    pub on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+}
+
+/// The parts of the data of a snapshot.
+#[derive(Clone, Copy, Debug)]
+pub enum SnapshotData {
+    WasmModule,
+    WasmMemory,
+    StableMemory,
+}
+
+#[derive(CandidType, Serialize)]
+enum ReadDataKind {
+    #[serde(rename = "wasm_module")]
+    WasmModule { offset: u64, size: u64 },
+    #[serde(rename = "wasm_memory")]
+    WasmMemory { offset: u64, size: u64 },
+    #[serde(rename = "stable_memory")]
+    StableMemory { offset: u64, size: u64 },
+    #[serde(rename = "wasm_chunk")]
+    WasmChunk {
+        #[serde(with = "serde_bytes")]
+        hash: Vec<u8>,
+    },
+}
+
+#[derive(CandidType, Serialize)]
+enum UploadDataKind {
+    #[serde(rename = "wasm_module")]
+    WasmModule { offset: u64 },
+    #[serde(rename = "wasm_memory")]
+    WasmMemory { offset: u64 },
+    #[serde(rename = "stable_memory")]
+    StableMemory { offset: u64 },
+    #[serde(rename = "wasm_chunk")]
+    WasmChunk,
+}
+
+#[derive(CandidType, Serialize)]
+struct SnapshotArgs<'a> {
+    canister_id: Principal,
+    #[serde(with = "serde_bytes")]
+    snapshot_id: &'a [u8],
+}
+
+/// Takes a snapshot of the canister, and returns its id.
+#[context("Failed to take a snapshot of {}.", canister_id)]
+pub async fn take_canister_snapshot(
+    env: &dyn Environment,
+    canister_id: Principal,
+    call_sender: &CallSender,
+) -> DfxResult<Vec<u8>> {
+    #[derive(CandidType)]
+    struct In {
+        canister_id: Principal,
+        replace_snapshot: Option<Vec<u8>>,
+    }
+    #[derive(CandidType, Deserialize)]
+    struct Out {
+        #[serde(with = "serde_bytes")]
+        id: Vec<u8>,
+    }
+    let (out,): (Out,) = do_management_call(
+        env,
+        canister_id,
+        "take_canister_snapshot",
+        In {
+            canister_id,
+            replace_snapshot: None,
+        },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(out.id)
+}
+
+#[context(
+    "Failed to delete snapshot {} of {}.",
+    hex::encode(snapshot_id),
+    canister_id
+)]
+pub async fn delete_canister_snapshot(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    call_sender: &CallSender,
+) -> DfxResult {
+    do_management_call(
+        env,
+        canister_id,
+        "delete_canister_snapshot",
+        SnapshotArgs {
+            canister_id,
+            snapshot_id,
+        },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Replaces the state of the canister with that of the snapshot. The canister must be stopped.
+#[context(
+    "Failed to load snapshot {} into {}.",
+    hex::encode(snapshot_id),
+    canister_id
+)]
+pub async fn load_canister_snapshot(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    call_sender: &CallSender,
+) -> DfxResult {
+    #[derive(CandidType, Serialize)]
+    struct In<'a> {
+        canister_id: Principal,
+        #[serde(with = "serde_bytes")]
+        snapshot_id: &'a [u8],
+        sender_canister_version: Option<u64>,
+    }
+    do_management_call(
+        env,
+        canister_id,
+        "load_canister_snapshot",
+        In {
+            canister_id,
+            snapshot_id,
+            sender_canister_version: None,
+        },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(())
+}
+
+#[context(
+    "Failed to read the metadata of snapshot {} of {}.",
+    hex::encode(snapshot_id),
+    canister_id
+)]
+pub async fn read_canister_snapshot_metadata(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    call_sender: &CallSender,
+) -> DfxResult<SnapshotMetadata> {
+    #[derive(CandidType, Deserialize)]
+    struct Out {
+        wasm_module_size: u64,
+        exported_globals: Vec<ExportedGlobal>,
+        wasm_memory_size: u64,
+        stable_memory_size: u64,
+        wasm_chunk_store: Vec<ChunkHash>,
+        #[serde(with = "serde_bytes")]
+        certified_data: Vec<u8>,
+        global_timer: Option<GlobalTimer>,
+        on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+    }
+    let (out,): (Out,) = do_management_call(
+        env,
+        canister_id,
+        "read_canister_snapshot_metadata",
+        SnapshotArgs {
+            canister_id,
+            snapshot_id,
+        },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(SnapshotMetadata {
+        wasm_module_size: out.wasm_module_size,
+        exported_globals: out.exported_globals,
+        wasm_memory_size: out.wasm_memory_size,
+        stable_memory_size: out.stable_memory_size,
+        wasm_chunk_store: out
+            .wasm_chunk_store
+            .iter()
+            .map(|chunk| hex::encode(&chunk.hash))
+            .collect(),
+        certified_data: out.certified_data,
+        global_timer: out.global_timer,
+        on_low_wasm_memory_hook_status: out.on_low_wasm_memory_hook_status,
+    })
+}
+
+/// Downloads a part of the data of a snapshot, one slice at a time.
+#[context(
+    "Failed to download the {:?} of snapshot {} of {}.",
+    data,
+    hex::encode(snapshot_id),
+    canister_id
+)]
+pub async fn read_canister_snapshot_data(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    data: SnapshotData,
+    size: u64,
+    call_sender: &CallSender,
+) -> DfxResult<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(size as usize);
+    while (bytes.len() as u64) < size {
+        let offset = bytes.len() as u64;
+        let size = SNAPSHOT_DATA_CHUNK_SIZE.min(size - offset);
+        let kind = match data {
+            SnapshotData::WasmModule => ReadDataKind::WasmModule { offset, size },
+            SnapshotData::WasmMemory => ReadDataKind::WasmMemory { offset, size },
+            SnapshotData::StableMemory => ReadDataKind::StableMemory { offset, size },
+        };
+        bytes.extend(read_data(env, canister_id, snapshot_id, kind, call_sender).await?);
+    }
+    Ok(bytes)
+}
+
+#[context(
+    "Failed to download chunk {} of snapshot {} of {}.",
+    hash,
+    hex::encode(snapshot_id),
+    canister_id
+)]
+pub async fn read_canister_snapshot_chunk(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    hash: &str,
+    call_sender: &CallSender,
+) -> DfxResult<Vec<u8>> {
+    let kind = ReadDataKind::WasmChunk {
+        hash: hex::decode(hash)?,
+    };
+    read_data(env, canister_id, snapshot_id, kind, call_sender).await
+}
+
+async fn read_data(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    kind: ReadDataKind,
+    call_sender: &CallSender,
+) -> DfxResult<Vec<u8>> {
+    #[derive(CandidType, Serialize)]
+    struct In<'a> {
+        canister_id: Principal,
+        #[serde(with = "serde_bytes")]
+        snapshot_id: &'a [u8],
+        kind: ReadDataKind,
+    }
+    #[derive(CandidType, Deserialize)]
+    struct Out {
+        #[serde(with = "serde_bytes")]
+        chunk: Vec<u8>,
+    }
+    let (out,): (Out,) = do_management_call(
+        env,
+        canister_id,
+        "read_canister_snapshot_data",
+        In {
+            canister_id,
+            snapshot_id,
+            kind,
+        },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(out.chunk)
+}
+
+/// Creates a snapshot of the canister from uploaded metadata, and returns its id.
+/// The data of the snapshot has to be uploaded before it can be loaded.
+#[context("Failed to upload the snapshot metadata to {}.", canister_id)]
+pub async fn upload_canister_snapshot_metadata(
+    env: &dyn Environment,
+    canister_id: Principal,
+    metadata: &SnapshotMetadata,
+    call_sender: &CallSender,
+) -> DfxResult<Vec<u8>> {
+    #[derive(CandidType, Serialize)]
+    struct In<'a> {
+        canister_id: Principal,
+        replace_snapshot: Option<Vec<u8>>,
+        wasm_module_size: u64,
+        exported_globals: &'a [ExportedGlobal],
+        wasm_memory_size: u64,
+        stable_memory_size: u64,
+        #[serde(with = "serde_bytes")]
+        certified_data: &'a [u8],
+        global_timer: &'a Option<GlobalTimer>,
+        on_low_wasm_memory_hook_status: &'a Option<OnLowWasmMemoryHookStatus>,
+    }
+    #[derive(CandidType, Deserialize)]
+    struct Out {
+        #[serde(with = "serde_bytes")]
+        snapshot_id: Vec<u8>,
+    }
+    let (out,): (Out,) = do_management_call(
+        env,
+        canister_id,
+        "upload_canister_snapshot_metadata",
+        In {
+            canister_id,
+            replace_snapshot: None,
+            wasm_module_size: metadata.wasm_module_size,
+            exported_globals: &metadata.exported_globals,
+            wasm_memory_size: metadata.wasm_memory_size,
+            stable_memory_size: metadata.stable_memory_size,
+            certified_data: &metadata.certified_data,
+            global_timer: &metadata.global_timer,
+            on_low_wasm_memory_hook_status: &metadata.on_low_wasm_memory_hook_status,
+        },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(out.snapshot_id)
+}
+
+/// Uploads a part of the data of a snapshot, one slice at a time.
+#[context(
+    "Failed to upload the {:?} of snapshot {} to {}.",
+    data,
+    hex::encode(snapshot_id),
+    canister_id
+)]
+pub async fn upload_canister_snapshot_data(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    data: SnapshotData,
+    bytes: &[u8],
+    call_sender: &CallSender,
+) -> DfxResult {
+    for (index, chunk) in bytes.chunks(SNAPSHOT_DATA_CHUNK_SIZE as usize).enumerate() {
+        let offset = index as u64 * SNAPSHOT_DATA_CHUNK_SIZE;
+        let kind = match data {
+            SnapshotData::WasmModule => UploadDataKind::WasmModule { offset },
+            SnapshotData::WasmMemory => UploadDataKind::WasmMemory { offset },
+            SnapshotData::StableMemory => UploadDataKind::StableMemory { offset },
+        };
+        upload_data(env, canister_id, snapshot_id, kind, chunk, call_sender).await?;
+    }
+    Ok(())
+}
+
+#[context(
+    "Failed to upload a chunk of snapshot {} to {}.",
+    hex::encode(snapshot_id),
+    canister_id
+)]
+pub async fn upload_canister_snapshot_chunk(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    chunk: &[u8],
+    call_sender: &CallSender,
+) -> DfxResult {
+    upload_data(
+        env,
+        canister_id,
+        snapshot_id,
+        UploadDataKind::WasmChunk,
+        chunk,
+        call_sender,
+    )
+    .await
+}
+
+async fn upload_data(
+    env: &dyn Environment,
+    canister_id: Principal,
+    snapshot_id: &[u8],
+    kind: UploadDataKind,
+    chunk: &[u8],
+    call_sender: &CallSender,
+) -> DfxResult {
+    #[derive(CandidType, Serialize)]
+    struct In<'a> {
+        canister_id: Principal,
+        #[serde(with = "serde_bytes")]
+        snapshot_id: &'a [u8],
+        kind: UploadDataKind,
+        #[serde(with = "serde_bytes")]
+        chunk: &'a [u8],
+    }
+    do_management_call(
+        env,
+        canister_id,
+        "upload_canister_snapshot_data",
+        In {
+            canister_id,
+            snapshot_id,
+            kind,
+            chunk,
+        },
+        call_sender,
+        0,
+    )
+    .await?;
+    Ok(())
+}
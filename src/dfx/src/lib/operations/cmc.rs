@@ -10,6 +10,7 @@ use crate::lib::ledger_types::{
 use crate::lib::nns_types::account_identifier::{AccountIdentifier, Subaccount};
 use crate::lib::nns_types::icpts::ICPTs;
 use crate::lib::operations::ledger::transfer;
+use backoff::ExponentialBackoff;
 use candid::{Decode, Encode, Principal};
 use ic_agent::Agent;
 use icrc_ledger_types::icrc1::account::Subaccount as ICRCSubaccount;
@@ -22,6 +23,7 @@ const NOTIFY_MINT_CYCLES_METHOD: &str = "notify_mint_cycles";
 
 pub async fn transfer_cmc(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     logger: &Logger,
     memo: Memo,
     amount: ICPTs,
@@ -35,6 +37,7 @@ pub async fn transfer_cmc(
         AccountIdentifier::new(MAINNET_CYCLE_MINTER_CANISTER_ID, Some(to_subaccount)).to_address();
     transfer(
         agent,
+        retry_policy,
         logger,
         &MAINNET_LEDGER_CANISTER_ID,
         memo,
@@ -35,7 +35,26 @@ use slog::{info, Logger};
 //TODO(SDK-1331): feature flag can be removed
 pub const CYCLES_LEDGER_ENABLED: bool = false;
 
+/// Whether the cycles ledger may be used in place of a cycles wallet.
+pub fn cycles_ledger_enabled(env: &dyn Environment) -> bool {
+    CYCLES_LEDGER_ENABLED || uses_cycles_ledger_instead_of_wallet(env)
+}
+
+/// Whether canisters are created and cycles are deposited from the cycles ledger account of the selected identity,
+/// without provisioning or using a cycles wallet. Projects opt in with `defaults.cycles_ledger.enabled` in dfx.json.
+pub fn uses_cycles_ledger_instead_of_wallet(env: &dyn Environment) -> bool {
+    env.get_config().map_or(false, |config| {
+        config
+            .get_config()
+            .get_defaults()
+            .cycles_ledger
+            .as_ref()
+            .map_or(false, |cycles_ledger| cycles_ledger.enabled)
+    })
+}
+
 const ICRC1_BALANCE_OF_METHOD: &str = "icrc1_balance_of";
+const ICRC1_FEE_METHOD: &str = "icrc1_fee";
 const ICRC1_TRANSFER_METHOD: &str = "icrc1_transfer";
 const ICRC2_APPROVE_METHOD: &str = "icrc2_approve";
 const ICRC2_TRANSFER_FROM_METHOD: &str = "icrc2_transfer_from";
@@ -47,6 +66,7 @@ const CYCLES_LEDGER_CANISTER_ID: Principal =
 
 pub async fn balance(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     owner: Principal,
     subaccount: Option<icrc1::account::Subaccount>,
 ) -> DfxResult<u128> {
@@ -56,8 +76,6 @@ pub async fn balance(
         .build()?;
     let arg = icrc1::account::Account { owner, subaccount };
 
-    let retry_policy = ExponentialBackoff::default();
-
     retry(retry_policy, || async {
         let result = canister
             .query(ICRC1_BALANCE_OF_METHOD)
@@ -76,8 +94,34 @@ pub async fn balance(
     .await
 }
 
+/// The fee the cycles ledger charges for a transfer, in cycles.
+pub async fn fee(agent: &Agent, retry_policy: ExponentialBackoff) -> DfxResult<u128> {
+    let canister = Canister::builder()
+        .with_agent(agent)
+        .with_canister_id(CYCLES_LEDGER_CANISTER_ID)
+        .build()?;
+
+    retry(retry_policy, || async {
+        let result = canister
+            .query(ICRC1_FEE_METHOD)
+            .with_arg(())
+            .build()
+            .call()
+            .await;
+        match result {
+            Ok((fee,)) => Ok(fee),
+            Err(agent_err) if retryable(&agent_err) => {
+                Err(backoff::Error::transient(anyhow!(agent_err)))
+            }
+            Err(agent_err) => Err(backoff::Error::permanent(anyhow!(agent_err))),
+        }
+    })
+    .await
+}
+
 pub async fn transfer(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     logger: &Logger,
     amount: u128,
     from_subaccount: Option<icrc1::account::Subaccount>,
@@ -91,8 +135,6 @@ pub async fn transfer(
         .with_canister_id(CYCLES_LEDGER_CANISTER_ID)
         .build()?;
 
-    let retry_policy = ExponentialBackoff::default();
-
     let block_index = retry(retry_policy, || async {
         let arg = icrc1::transfer::TransferArg {
             from_subaccount,
@@ -139,6 +181,7 @@ pub async fn transfer(
 
 pub async fn transfer_from(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     logger: &Logger,
     spender_subaccount: Option<icrc1::account::Subaccount>,
     from: icrc1::account::Account,
@@ -152,8 +195,6 @@ pub async fn transfer_from(
         .with_canister_id(CYCLES_LEDGER_CANISTER_ID)
         .build()?;
 
-    let retry_policy = ExponentialBackoff::default();
-
     let block_index = retry(retry_policy, || async {
         let arg = icrc2::transfer_from::TransferFromArgs {
             spender_subaccount,
@@ -197,6 +238,7 @@ pub async fn transfer_from(
 
 pub async fn approve(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     logger: &Logger,
     amount: u128,
     spender: Principal,
@@ -212,8 +254,6 @@ pub async fn approve(
         .with_canister_id(CYCLES_LEDGER_CANISTER_ID)
         .build()?;
 
-    let retry_policy = ExponentialBackoff::default();
-
     let block_index = retry(retry_policy, || async {
         let arg = icrc2::approve::ApproveArgs {
             from_subaccount,
@@ -258,6 +298,7 @@ pub async fn approve(
 
 pub async fn send(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     logger: &Logger,
     to: Principal,
     amount: u128,
@@ -269,7 +310,6 @@ pub async fn send(
         .with_canister_id(CYCLES_LEDGER_CANISTER_ID)
         .build()?;
 
-    let retry_policy = ExponentialBackoff::default();
     let block_index: BlockIndex = retry(retry_policy, || async {
         let arg = cycles_ledger_types::send::SendArgs {
             from_subaccount,
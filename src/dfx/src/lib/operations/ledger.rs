@@ -1,4 +1,6 @@
-use crate::lib::ledger_types::{AccountIdBlob, BlockHeight, Memo, TransferError};
+use crate::lib::ledger_types::{
+    AccountIdBlob, BlockHeight, BlockIndex, GetBlocksArgs, Memo, QueryBlocksResponse, TransferError,
+};
 use crate::lib::nns_types::account_identifier::Subaccount;
 use crate::lib::{
     error::DfxResult,
@@ -20,11 +22,14 @@ use ic_agent::{
     lookup_value, Agent, AgentError,
 };
 use ic_utils::{call::SyncCall, Canister};
+use icrc_ledger_types::icrc1::account::Account;
 use slog::{info, Logger};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const ACCOUNT_BALANCE_METHOD: &str = "account_balance_dfx";
 const TRANSFER_METHOD: &str = "transfer";
+const QUERY_BLOCKS_METHOD: &str = "query_blocks";
+const ICRC1_MINTING_ACCOUNT_METHOD: &str = "icrc1_minting_account";
 
 pub async fn balance(
     agent: &Agent,
@@ -47,6 +52,43 @@ pub async fn balance(
     Ok(result)
 }
 
+/// Returns the minting account of the ledger. Transfers from it mint ICP.
+pub async fn minting_account(
+    agent: &Agent,
+    ledger_canister_id: Option<Principal>,
+) -> DfxResult<Option<Account>> {
+    let canister = Canister::builder()
+        .with_agent(agent)
+        .with_canister_id(ledger_canister_id.unwrap_or(MAINNET_LEDGER_CANISTER_ID))
+        .build()?;
+    let (result,) = canister
+        .query(ICRC1_MINTING_ACCOUNT_METHOD)
+        .build()
+        .call()
+        .await
+        .context("Failed to query the minting account of the ledger.")?;
+    Ok(result)
+}
+
+/// Returns the blocks of the ledger from `start`, leaving out those that have been archived.
+pub async fn query_blocks(
+    agent: &Agent,
+    start: BlockIndex,
+    length: u64,
+) -> DfxResult<QueryBlocksResponse> {
+    let canister = Canister::builder()
+        .with_agent(agent)
+        .with_canister_id(MAINNET_LEDGER_CANISTER_ID)
+        .build()?;
+    let (response,) = canister
+        .query(QUERY_BLOCKS_METHOD)
+        .with_arg(GetBlocksArgs { start, length })
+        .build()
+        .call()
+        .await?;
+    Ok(response)
+}
+
 /// Returns XDR-permyriad (i.e. ten-thousandths-of-an-XDR) per ICP.
 pub async fn xdr_permyriad_per_icp(agent: &Agent) -> DfxResult<u64> {
     let canister = Canister::builder()
@@ -99,6 +141,7 @@ pub async fn xdr_permyriad_per_icp(agent: &Agent) -> DfxResult<u64> {
 #[context("Failed to transfer funds.")]
 pub async fn transfer(
     agent: &Agent,
+    mut retry_policy: ExponentialBackoff,
     logger: &Logger,
     canister_id: &Principal,
     memo: Memo,
@@ -115,8 +158,6 @@ pub async fn transfer(
             .as_nanos() as u64,
     );
 
-    let mut retry_policy = ExponentialBackoff::default();
-
     let block_height: BlockHeight = loop {
         match agent
             .update(canister_id, TRANSFER_METHOD)
@@ -1,7 +1,14 @@
 use crate::lib::error::{BuildError, DfxError, DfxResult};
 use anyhow::{anyhow, bail};
 use dfx_core::config::cache::Cache;
+use dfx_core::config::directories::get_state_dir_override;
+use dfx_core::config::model::dfinity::MotokoPackageManager;
+use dfx_core::json::{load_json_file, save_json_file};
 use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use slog::{debug, info, Logger};
+use std::path::Path;
 use std::process::Command;
 
 /// Package arguments for moc or mo-ide as returned by
@@ -9,16 +16,30 @@ use std::process::Command;
 /// or, if there is no package tool, the base library.
 pub type PackageArguments = Vec<String>;
 
+const PACKAGES_CACHE_FILE: &str = "motoko-packages.json";
+
+/// The package arguments that a package manager returned, and the hash of the manifest they are for.
+#[derive(Serialize, Deserialize)]
+struct CachedPackageArguments {
+    package_manager: MotokoPackageManager,
+    manifest_hash: String,
+    arguments: PackageArguments,
+}
+
 #[context("Failed to load package arguments.")]
-pub fn load(cache: &dyn Cache, packtool: &Option<String>) -> DfxResult<PackageArguments> {
+pub fn load(
+    logger: &Logger,
+    cache: &dyn Cache,
+    packtool: &Option<String>,
+    packages: Option<MotokoPackageManager>,
+    project_root: &Path,
+) -> DfxResult<PackageArguments> {
+    if let Some(package_manager) = packages {
+        return load_from_package_manager(logger, cache, package_manager, project_root);
+    }
+
     if packtool.is_none() {
-        let stdlib_path = cache
-            .get_binary_command_path("base")?
-            .into_os_string()
-            .into_string()
-            .map_err(|_| anyhow!("Path contains invalid Unicode data."))?;
-        let base = vec![String::from("--package"), String::from("base"), stdlib_path];
-        return Ok(base);
+        return base_package(cache);
     }
 
     let commandline: Vec<String> = packtool
@@ -33,6 +54,108 @@ pub fn load(cache: &dyn Cache, packtool: &Option<String>) -> DfxResult<PackageAr
         cmd.arg(arg);
     }
 
+    run_package_tool(&mut cmd)
+}
+
+fn base_package(cache: &dyn Cache) -> DfxResult<PackageArguments> {
+    let stdlib_path = cache
+        .get_binary_command_path("base")?
+        .into_os_string()
+        .into_string()
+        .map_err(|_| anyhow!("Path contains invalid Unicode data."))?;
+    Ok(vec![
+        String::from("--package"),
+        String::from("base"),
+        stdlib_path,
+    ])
+}
+
+/// Asks the package manager for the package sources, unless it already did so for the current manifest.
+/// The base library that comes with dfx is added if the package manager does not provide it.
+fn load_from_package_manager(
+    logger: &Logger,
+    cache: &dyn Cache,
+    package_manager: MotokoPackageManager,
+    project_root: &Path,
+) -> DfxResult<PackageArguments> {
+    let manifest_hash = hash_manifest(package_manager, project_root)?;
+    let cache_path = get_state_dir_override()
+        .unwrap_or_else(|| project_root.join(".dfx"))
+        .join(PACKAGES_CACHE_FILE);
+
+    if let Ok(cached) = load_json_file::<CachedPackageArguments>(&cache_path) {
+        if cached.package_manager == package_manager
+            && cached.manifest_hash == manifest_hash
+            && package_paths_exist(&cached.arguments, project_root)
+        {
+            debug!(
+                logger,
+                "Using the Motoko packages resolved by {} from {}.",
+                package_manager,
+                cache_path.display()
+            );
+            return Ok(cached.arguments);
+        }
+    }
+
+    info!(
+        logger,
+        "Resolving Motoko packages with {}...", package_manager
+    );
+    let mut cmd = Command::new(package_manager.command());
+    cmd.arg("sources").current_dir(project_root);
+    let mut arguments = run_package_tool(&mut cmd)?;
+    if !arguments
+        .windows(2)
+        .any(|pair| pair[0] == "--package" && pair[1] == "base")
+    {
+        arguments.extend(base_package(cache)?);
+    }
+
+    let cached = CachedPackageArguments {
+        package_manager,
+        manifest_hash,
+        arguments,
+    };
+    let saved = dfx_core::fs::create_dir_all(cache_path.parent().unwrap())
+        .map_err(DfxError::from)
+        .and_then(|_| save_json_file(&cache_path, &cached).map_err(DfxError::from));
+    if let Err(err) = saved {
+        debug!(logger, "Failed to cache the Motoko packages: {:#}", err);
+    }
+    Ok(cached.arguments)
+}
+
+fn hash_manifest(package_manager: MotokoPackageManager, project_root: &Path) -> DfxResult<String> {
+    let mut hasher = Sha256::new();
+    let mut found = false;
+    for file in package_manager.manifest_files() {
+        let path = project_root.join(file);
+        if path.exists() {
+            hasher.update(file.as_bytes());
+            hasher.update(dfx_core::fs::read(&path)?);
+            found = true;
+        }
+    }
+    if !found {
+        bail!(
+            "The Motoko package manager is {}, but there is no {} in {}.",
+            package_manager,
+            package_manager.manifest_files()[0],
+            project_root.display()
+        );
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn package_paths_exist(arguments: &[String], project_root: &Path) -> bool {
+    arguments
+        .windows(3)
+        .filter(|package| package[0] == "--package")
+        .all(|package| project_root.join(&package[2]).exists())
+}
+
+fn run_package_tool(cmd: &mut Command) -> DfxResult<PackageArguments> {
     let output = match cmd.output() {
         Ok(output) => output,
         Err(err) => bail!(
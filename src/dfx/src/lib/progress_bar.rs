@@ -1,26 +1,15 @@
 use indicatif::{ProgressBar as IndicatifProgressBar, ProgressDrawTarget};
+use slog::{Level, Logger};
 use std::borrow::Cow;
 
+/// The tag of log records that report progress. With `--log-format json`, these records are
+/// emitted regardless of the verbosity.
+pub const PROGRESS_TAG: &str = "progress";
+
 pub struct ProgressBar {
     bar: Option<IndicatifProgressBar>,
-}
-
-macro_rules! forward_fn_impl {
-    ($name: ident) => {
-        pub fn $name(&self) {
-            if let Some(ref progress_bar) = self.bar {
-                progress_bar.$name();
-            }
-        }
-    };
-
-    ($name: ident, $( $tname: ident: $t: ty )+) => {
-        pub fn $name(&self, $($tname: $t,)+) {
-            if let Some(ref progress_bar) = self.bar {
-                progress_bar.$name( $($tname,)+ );
-            }
-        }
-    }
+    /// Reports the progress as log events instead of drawing a spinner.
+    logger: Option<Logger>,
 }
 
 impl ProgressBar {
@@ -33,12 +22,107 @@ impl ProgressBar {
 
         ProgressBar {
             bar: Some(progress_bar),
+            logger: None,
         }
     }
 
-    forward_fn_impl!(finish_with_message, message: Cow<'static, str>);
+    /// A spinner that reports its start and its end as progress events.
+    pub fn new_event_spinner(logger: &Logger, message: Cow<'static, str>) -> Self {
+        slog::log!(logger, Level::Info, PROGRESS_TAG, "{}", message;
+            "event" => "spinner",
+            "status" => "started"
+        );
+        ProgressBar {
+            bar: None,
+            logger: Some(logger.clone()),
+        }
+    }
+
+    pub fn finish_with_message(&self, message: Cow<'static, str>) {
+        if let Some(ref logger) = self.logger {
+            slog::log!(logger, Level::Info, PROGRESS_TAG, "{}", message;
+                "event" => "spinner",
+                "status" => "finished"
+            );
+        }
+        if let Some(ref progress_bar) = self.bar {
+            progress_bar.finish_with_message(message);
+        }
+    }
 
     pub fn discard() -> Self {
-        ProgressBar { bar: None }
+        ProgressBar {
+            bar: None,
+            logger: None,
+        }
+    }
+}
+
+/// A step of a long-running operation that tools wrapping dfx may want to follow.
+#[derive(Clone, Copy, Debug)]
+pub enum Step {
+    Create,
+    Build,
+    Install,
+    Deploy,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum StepStatus {
+    Started,
+    Finished,
+    Failed,
+    Skipped,
+}
+
+impl Step {
+    fn as_str(self) -> &'static str {
+        match self {
+            Step::Create => "create",
+            Step::Build => "build",
+            Step::Install => "install",
+            Step::Deploy => "deploy",
+        }
+    }
+}
+
+impl StepStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            StepStatus::Started => "started",
+            StepStatus::Finished => "finished",
+            StepStatus::Failed => "failed",
+            StepStatus::Skipped => "skipped",
+        }
     }
 }
+
+/// Reports the progress of a step, optionally for a single canister.
+///
+/// With a message, the event is logged at the info level and reads like any other message.
+/// Without one, it is logged at the debug level, so that it only shows up in text output with
+/// `-v`, while `--log-format json` always includes it.
+pub fn report_step(
+    logger: &Logger,
+    step: Step,
+    status: StepStatus,
+    canister: Option<&str>,
+    message: Option<String>,
+) {
+    let (level, message) = match message {
+        Some(message) => (Level::Info, message),
+        None => (
+            Level::Debug,
+            match canister {
+                Some(canister) => format!("{} {}: {canister}", step.as_str(), status.as_str()),
+                None => format!("{} {}", step.as_str(), status.as_str()),
+            },
+        ),
+    };
+    slog::log!(logger, level, PROGRESS_TAG, "{}", message;
+        "event" => "step",
+        "step" => step.as_str(),
+        "status" => status.as_str(),
+        "canister" => canister
+    );
+}
@@ -0,0 +1,285 @@
+//! Templates for `dfx new`.
+//!
+//! Besides the built-in templates, projects can be generated from git repositories, given either by url
+//! or by a name registered in `templates.json` in the dfx config directory:
+//!
+//! ```json
+//! { "templates": { "my-template": { "url": "https://github.com/me/my-template.git", "rev": "main" } } }
+//! ```
+//!
+//! A git template may contain a `dfx-template.json` manifest listing commands to run in the new project.
+use crate::lib::error::DfxResult;
+use anyhow::{bail, ensure, Context};
+use dfx_core::config::directories::get_user_dfx_config_dir;
+use dfx_core::json::load_json_file;
+use fn_error_context::context;
+use serde::Deserialize;
+use slog::{info, warn, Logger};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tar::Archive;
+use walkdir::WalkDir;
+
+pub const TEMPLATE_MANIFEST: &str = "dfx-template.json";
+const TEMPLATE_REGISTRY_FILE: &str = "templates.json";
+
+pub const BUILTIN_BACKEND_TEMPLATES: [&str; 4] = ["motoko", "rust", "azle", "kybra"];
+pub const BUILTIN_FRONTEND_TEMPLATES: [&str; 5] =
+    ["sveltekit", "react", "vue", "vanilla", "simple-assets"];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProjectTemplate {
+    /// A built-in backend template, with the default frontend.
+    Backend(String),
+    /// A built-in frontend template, with the default backend.
+    Frontend(String),
+    Git {
+        url: String,
+        rev: Option<String>,
+    },
+}
+
+/// Templates registered by the user, by name.
+#[derive(Deserialize, Default)]
+pub struct TemplateRegistry {
+    #[serde(default)]
+    pub templates: BTreeMap<String, RegisteredTemplate>,
+}
+
+#[derive(Deserialize)]
+pub struct RegisteredTemplate {
+    pub url: String,
+    pub rev: Option<String>,
+    pub description: Option<String>,
+}
+
+/// The `dfx-template.json` manifest of a git template.
+#[derive(Deserialize, Default)]
+pub struct TemplateManifest {
+    pub description: Option<String>,
+
+    /// Commands to run in the new project after its files are generated, e.g. `npm install`.
+    /// Template variables such as `{project_name}` are replaced in the commands.
+    #[serde(default)]
+    pub post_generate: Vec<String>,
+}
+
+fn get_template_registry_path() -> DfxResult<PathBuf> {
+    Ok(get_user_dfx_config_dir()?.join(TEMPLATE_REGISTRY_FILE))
+}
+
+#[context("Failed to load the template registry.")]
+pub fn load_template_registry() -> DfxResult<TemplateRegistry> {
+    let path = get_template_registry_path()?;
+    if path.exists() {
+        Ok(load_json_file(&path)?)
+    } else {
+        Ok(TemplateRegistry::default())
+    }
+}
+
+fn is_git_url(template: &str) -> bool {
+    template.contains("://")
+        || template.starts_with("git@")
+        || template.ends_with(".git")
+        || Path::new(template).is_dir()
+}
+
+/// Resolves the value of `dfx new --template`: a built-in template, a registered template, or a git url.
+pub fn resolve_template(
+    registry: &TemplateRegistry,
+    template: &str,
+    rev: Option<String>,
+) -> DfxResult<ProjectTemplate> {
+    let builtin = if BUILTIN_BACKEND_TEMPLATES.contains(&template) {
+        Some(ProjectTemplate::Backend(template.to_string()))
+    } else if BUILTIN_FRONTEND_TEMPLATES.contains(&template) {
+        Some(ProjectTemplate::Frontend(template.to_string()))
+    } else {
+        None
+    };
+    if let Some(builtin) = builtin {
+        ensure!(
+            rev.is_none(),
+            "--template-rev can only be used with git templates."
+        );
+        return Ok(builtin);
+    }
+
+    if let Some(registered) = registry.templates.get(template) {
+        return Ok(ProjectTemplate::Git {
+            url: registered.url.clone(),
+            rev: rev.or_else(|| registered.rev.clone()),
+        });
+    }
+    if is_git_url(template) {
+        return Ok(ProjectTemplate::Git {
+            url: template.to_string(),
+            rev,
+        });
+    }
+
+    let mut available: Vec<&str> = BUILTIN_BACKEND_TEMPLATES
+        .iter()
+        .chain(BUILTIN_FRONTEND_TEMPLATES.iter())
+        .copied()
+        .collect();
+    available.extend(registry.templates.keys().map(String::as_str));
+    bail!(
+        "Unknown template '{}'. Available templates are: {}. Other templates can be used by their git url.",
+        template,
+        available.join(", ")
+    );
+}
+
+/// Clones a git template into a directory.
+#[context("Failed to fetch template from {}.", url)]
+pub fn fetch_git_template(url: &str, rev: Option<&str>, into: &Path) -> DfxResult {
+    let mut command = Command::new("git");
+    command.arg("clone").arg("--quiet").arg("--depth").arg("1");
+    if let Some(rev) = rev {
+        command.arg("--branch").arg(rev);
+    }
+    let status = command
+        .arg(url)
+        .arg(into)
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .status()
+        .context("Failed to run 'git clone'. Is git installed?")?;
+    ensure!(status.success(), "'git clone' failed: {}", status);
+    Ok(())
+}
+
+/// Packs the files of a fetched template, except for its git metadata and manifest, so they can be
+/// written to the new project like the files of a built-in template.
+#[context("Failed to read template in {}.", dir.display())]
+pub fn archive_template(dir: &Path) -> DfxResult<(Archive<Cursor<Vec<u8>>>, TemplateManifest)> {
+    let manifest_path = dir.join(TEMPLATE_MANIFEST);
+    let manifest = if manifest_path.exists() {
+        load_json_file(&manifest_path)?
+    } else {
+        TemplateManifest::default()
+    };
+
+    let mut builder = tar::Builder::new(vec![]);
+    for entry in WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git")
+    {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(dir)?;
+        if !entry.file_type().is_file() || relative == Path::new(TEMPLATE_MANIFEST) {
+            continue;
+        }
+        builder
+            .append_path_with_name(entry.path(), relative)
+            .with_context(|| format!("Failed to read {}.", entry.path().display()))?;
+    }
+    let bytes = builder.into_inner()?;
+    Ok((Archive::new(Cursor::new(bytes)), manifest))
+}
+
+/// The post-generation commands of a template manifest, with the variables of the new project filled in.
+pub fn post_generate_steps(
+    manifest: &TemplateManifest,
+    variables: &BTreeMap<String, String>,
+) -> Vec<String> {
+    manifest
+        .post_generate
+        .iter()
+        .map(|step| {
+            let mut step = step.clone();
+            for (name, value) in variables {
+                step = step.replace(&format!("{{{}}}", name), value);
+            }
+            step
+        })
+        .collect()
+}
+
+/// Runs the post-generation commands of a template in the new project.
+/// A failing command does not fail project creation, but is reported.
+pub fn run_post_generate_steps(log: &Logger, project_dir: &Path, steps: &[String]) -> DfxResult {
+    for step in steps {
+        info!(log, "Running '{}'...", step);
+
+        #[cfg(not(windows))]
+        let mut command = {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(step);
+            command
+        };
+        #[cfg(windows)]
+        let mut command = {
+            let mut command = Command::new("cmd");
+            command.arg("/C").arg(step);
+            command
+        };
+        let status = command
+            .current_dir(project_dir)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status();
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => warn!(log, "'{}' failed: {}", step, status),
+            Err(err) => warn!(log, "Failed to run '{}': {}", step, err),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_builtin_templates() {
+        let registry = TemplateRegistry::default();
+        assert_eq!(
+            resolve_template(&registry, "rust", None).unwrap(),
+            ProjectTemplate::Backend("rust".to_string())
+        );
+        assert_eq!(
+            resolve_template(&registry, "react", None).unwrap(),
+            ProjectTemplate::Frontend("react".to_string())
+        );
+        assert!(resolve_template(&registry, "rust", Some("main".to_string())).is_err());
+    }
+
+    #[test]
+    fn resolves_git_templates() {
+        let mut registry = TemplateRegistry::default();
+        registry.templates.insert(
+            "mine".to_string(),
+            RegisteredTemplate {
+                url: "https://example.com/mine.git".to_string(),
+                rev: Some("v1".to_string()),
+                description: None,
+            },
+        );
+        assert_eq!(
+            resolve_template(&registry, "mine", None).unwrap(),
+            ProjectTemplate::Git {
+                url: "https://example.com/mine.git".to_string(),
+                rev: Some("v1".to_string())
+            }
+        );
+        assert_eq!(
+            resolve_template(
+                &registry,
+                "git@github.com:me/t.git",
+                Some("dev".to_string())
+            )
+            .unwrap(),
+            ProjectTemplate::Git {
+                url: "git@github.com:me/t.git".to_string(),
+                rev: Some("dev".to_string())
+            }
+        );
+        assert!(resolve_template(&registry, "unknown", None).is_err());
+    }
+}
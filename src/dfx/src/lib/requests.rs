@@ -0,0 +1,283 @@
+//! Requests sent with `dfx canister call --async`, kept so that `dfx request` can track, resume,
+//! and retry them later.
+use crate::lib::environment::Environment;
+use crate::lib::error::{DfxError, DfxResult};
+use crate::lib::retryable::retry_policy;
+use anyhow::{bail, Context};
+use backoff::backoff::Backoff;
+use candid::Principal;
+use dfx_core::config::directories::get_shared_network_data_directory;
+use dfx_core::identity::ANONYMOUS_IDENTITY_NAME;
+use dfx_core::json::{load_json_file, save_json_file};
+use dfx_core::util::network_to_pathcompat;
+use fn_error_context::context;
+use ic_agent::agent::RequestStatusResponse;
+use ic_agent::{AgentError, RequestId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const PENDING_REQUESTS_FILE: &str = "pending_requests.json";
+
+/// How long after it was sent the IC may still receive a request: dfx signs requests with this expiry.
+pub const REQUEST_EXPIRY: Duration = Duration::from_secs(4 * 60);
+
+/// How long the IC keeps the status of a request it received, at least, counted from when it was sent.
+/// After that, the status of a request that was executed may be unknown too.
+pub const STATUS_RETENTION: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PendingRequests {
+    /// By request id, as `0x` followed by its hex encoding.
+    pub requests: BTreeMap<String, PendingRequest>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingRequest {
+    /// The canister the request was sent to: the wallet, if the call was proxied by it.
+    pub canister_id: Principal,
+    pub effective_canister_id: Principal,
+    pub method_name: String,
+    #[serde(with = "hex")]
+    pub arg: Vec<u8>,
+    /// The canister that the wallet called, if the call was proxied by it.
+    #[serde(default)]
+    pub wallet_callee: Option<Principal>,
+    /// The identity that signed the request. Only it can read the status of the request.
+    pub identity: String,
+    /// In seconds since the Unix epoch.
+    pub sent_at: u64,
+    /// When a status check after the ingress expiry of the request, while the IC still kept its status,
+    /// found its status unknown, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub unknown_checked_at: Option<u64>,
+}
+
+impl PendingRequest {
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now_secs().saturating_sub(self.sent_at))
+    }
+
+    /// The ingress expiry of the request, in seconds since the Unix epoch.
+    pub fn ingress_expiry(&self) -> u64 {
+        self.sent_at + REQUEST_EXPIRY.as_secs()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        now_secs() > self.ingress_expiry()
+    }
+
+    /// Records that the status of the request is unknown, if that shows that the IC never received it.
+    pub fn record_unknown_status(&mut self) {
+        self.record_unknown_status_at(now_secs());
+    }
+
+    fn record_unknown_status_at(&mut self, checked_at: u64) {
+        if self.unknown_status_proves_not_received(checked_at) {
+            self.unknown_checked_at = Some(checked_at);
+        }
+    }
+
+    /// Whether the request can be sent again without calling the method twice: a status check showed
+    /// that the IC never received it.
+    pub fn can_retry(&self) -> bool {
+        self.unknown_checked_at.map_or(false, |checked_at| {
+            self.unknown_status_proves_not_received(checked_at)
+        })
+    }
+
+    /// An unknown status only shows that the IC never received the request after the request expired,
+    /// so that it can no longer be received, and while the IC still keeps the status of requests it received.
+    fn unknown_status_proves_not_received(&self, checked_at: u64) -> bool {
+        checked_at > self.ingress_expiry()
+            && checked_at <= self.sent_at + STATUS_RETENTION.as_secs()
+    }
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// The identity that signs the calls, which is the only one that can read their status.
+pub fn selected_identity_name(env: &dyn Environment) -> String {
+    env.get_selected_identity()
+        .cloned()
+        .unwrap_or_else(|| ANONYMOUS_IDENTITY_NAME.to_string())
+}
+
+/// Fails unless the selected identity sent the request, since the IC only shows the status of a request
+/// to its sender.
+pub fn ensure_sent_by_selected_identity(
+    env: &dyn Environment,
+    request_id: &RequestId,
+    request: &PendingRequest,
+) -> DfxResult {
+    if selected_identity_name(env) != request.identity {
+        bail!(
+            "Request {} was sent by identity '{}'. Run the command with `--identity {}`.",
+            request_id_to_text(request_id),
+            request.identity,
+            request.identity
+        );
+    }
+    Ok(())
+}
+
+pub fn request_id_to_text(request_id: &RequestId) -> String {
+    format!("0x{}", String::from(*request_id))
+}
+
+/// The requests are kept with the other state of the network: in the temp directory of the project,
+/// or in the shared network data directory outside of a project.
+fn pending_requests_path(env: &dyn Environment) -> DfxResult<PathBuf> {
+    let network = network_to_pathcompat(&env.get_network_descriptor().name);
+    let dir = match env.get_project_temp_dir() {
+        Some(temp_dir) => temp_dir.join(network),
+        None => get_shared_network_data_directory(&network)?,
+    };
+    Ok(dir.join(PENDING_REQUESTS_FILE))
+}
+
+#[context("Failed to load the pending requests.")]
+pub fn load_pending_requests(env: &dyn Environment) -> DfxResult<PendingRequests> {
+    let path = pending_requests_path(env)?;
+    if path.exists() {
+        Ok(load_json_file(&path)?)
+    } else {
+        Ok(PendingRequests::default())
+    }
+}
+
+#[context("Failed to save the pending requests.")]
+pub fn save_pending_requests(env: &dyn Environment, pending: &PendingRequests) -> DfxResult {
+    let path = pending_requests_path(env)?;
+    dfx_core::fs::composite::ensure_parent_dir_exists(&path)?;
+    save_json_file(&path, pending)?;
+    Ok(())
+}
+
+pub fn add_pending_request(
+    env: &dyn Environment,
+    request_id: &RequestId,
+    request: PendingRequest,
+) -> DfxResult {
+    let mut pending = load_pending_requests(env)?;
+    pending
+        .requests
+        .insert(request_id_to_text(request_id), request);
+    save_pending_requests(env, &pending)
+}
+
+pub fn remove_pending_request(env: &dyn Environment, request_id: &RequestId) -> DfxResult {
+    let mut pending = load_pending_requests(env)?;
+    if pending
+        .requests
+        .remove(&request_id_to_text(request_id))
+        .is_some()
+    {
+        save_pending_requests(env, &pending)?;
+    }
+    Ok(())
+}
+
+/// Polls the status of a request until the canister replies, and returns the reply.
+/// The status is read from a certificate of the subnet, which the agent verifies.
+/// Without a poll interval, the status is polled with exponential backoff, from the `backoff` of the network.
+pub async fn wait_for_reply(
+    env: &dyn Environment,
+    request_id: &RequestId,
+    effective_canister_id: Principal,
+    poll_interval: Option<Duration>,
+) -> DfxResult<Vec<u8>> {
+    let agent = env.get_agent();
+    let mut retry_policy = retry_policy(env);
+    let mut request_accepted = false;
+    loop {
+        match agent
+            .request_status_raw(request_id, effective_canister_id)
+            .await
+            .context("Failed to fetch request status.")?
+        {
+            RequestStatusResponse::Replied(reply) => return Ok(reply.arg),
+            RequestStatusResponse::Rejected(response) => {
+                return Err(DfxError::new(AgentError::ReplicaError(response)))
+            }
+            RequestStatusResponse::Unknown => (),
+            RequestStatusResponse::Received | RequestStatusResponse::Processing => {
+                // The system will return Unknown until the request is accepted
+                // and we generally cannot know how long that will take.
+                // State transitions between Received and Processing may be
+                // instantaneous. Therefore, once we know the request is accepted,
+                // we restart the waiter so the request does not time out.
+                if !request_accepted {
+                    retry_policy.reset();
+                    request_accepted = true;
+                }
+            }
+            RequestStatusResponse::Done => {
+                return Err(DfxError::new(AgentError::RequestStatusDoneNoReply(
+                    String::from(*request_id),
+                )))
+            }
+        };
+
+        let backoff = retry_policy
+            .next_backoff()
+            .ok_or_else(|| DfxError::new(AgentError::TimeoutWaitingForResponse()))?;
+        tokio::time::sleep(poll_interval.unwrap_or(backoff)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_request(sent_at: u64) -> PendingRequest {
+        PendingRequest {
+            canister_id: Principal::management_canister(),
+            effective_canister_id: Principal::management_canister(),
+            method_name: "greet".to_string(),
+            arg: vec![],
+            wallet_callee: None,
+            identity: "default".to_string(),
+            sent_at,
+            unknown_checked_at: None,
+        }
+    }
+
+    #[test]
+    fn unknown_status_before_expiry_does_not_allow_retry() {
+        let mut request = pending_request(1000);
+        request.record_unknown_status_at(1000);
+        request.record_unknown_status_at(1000 + REQUEST_EXPIRY.as_secs());
+        assert_eq!(request.unknown_checked_at, None);
+        assert!(!request.can_retry());
+    }
+
+    #[test]
+    fn unknown_status_after_expiry_allows_retry_within_status_retention() {
+        let mut request = pending_request(1000);
+        request.record_unknown_status_at(1000 + REQUEST_EXPIRY.as_secs() + 1);
+        assert!(request.can_retry());
+
+        let mut request = pending_request(1000);
+        request.record_unknown_status_at(1000 + STATUS_RETENTION.as_secs());
+        assert!(request.can_retry());
+    }
+
+    #[test]
+    fn unknown_status_after_status_retention_does_not_allow_retry() {
+        let mut request = pending_request(1000);
+        request.record_unknown_status_at(1000 + STATUS_RETENTION.as_secs() + 1);
+        assert_eq!(request.unknown_checked_at, None);
+        assert!(!request.can_retry());
+
+        // recorded by an older version of dfx, before the request expired
+        request.unknown_checked_at = Some(1001);
+        assert!(!request.can_retry());
+    }
+}
@@ -1,4 +1,26 @@
+use crate::lib::environment::Environment;
+use backoff::ExponentialBackoff;
+use candid::Principal;
+use ic_agent::agent::{AgentFuture, Transport};
 use ic_agent::AgentError;
+use std::time::Duration;
+
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The policy for waiting on requests to the network of the environment, and for retrying them.
+/// The first interval is the `backoff` of the network.
+pub fn retry_policy(env: &dyn Environment) -> ExponentialBackoff {
+    let backoff = env
+        .get_network_descriptor()
+        .connection
+        .backoff
+        .unwrap_or(DEFAULT_BACKOFF);
+    ExponentialBackoff {
+        current_interval: backoff,
+        initial_interval: backoff,
+        ..ExponentialBackoff::default()
+    }
+}
 
 pub fn retryable(agent_error: &AgentError) -> bool {
     matches!(
@@ -6,3 +28,75 @@ pub fn retryable(agent_error: &AgentError) -> bool {
         AgentError::TimeoutWaitingForResponse() | AgentError::TransportError(_)
     )
 }
+
+/// Sends a request again if it failed with a retryable error,
+/// as configured with `retry` and `backoff` for the network.
+/// Sending the same signed request again is safe: the IC executes it at most once.
+pub struct RetryTransport<T> {
+    inner: T,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl<T: Transport> RetryTransport<T> {
+    pub fn new(inner: T, retries: u32, backoff: Option<Duration>) -> Self {
+        Self {
+            inner,
+            retries,
+            backoff: backoff.unwrap_or(DEFAULT_BACKOFF),
+        }
+    }
+
+    async fn with_retries<'a, V>(
+        &'a self,
+        request: impl Fn() -> AgentFuture<'a, V>,
+    ) -> Result<V, AgentError> {
+        let mut backoff = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Err(err) if attempt < self.retries && retryable(&err) => {
+                    attempt += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<T: Transport> Transport for RetryTransport<T> {
+    fn call(&self, effective_canister_id: Principal, envelope: Vec<u8>) -> AgentFuture<()> {
+        Box::pin(
+            self.with_retries(move || self.inner.call(effective_canister_id, envelope.clone())),
+        )
+    }
+
+    fn read_state(
+        &self,
+        effective_canister_id: Principal,
+        envelope: Vec<u8>,
+    ) -> AgentFuture<Vec<u8>> {
+        Box::pin(self.with_retries(move || {
+            self.inner
+                .read_state(effective_canister_id, envelope.clone())
+        }))
+    }
+
+    fn read_subnet_state(&self, subnet_id: Principal, envelope: Vec<u8>) -> AgentFuture<Vec<u8>> {
+        Box::pin(
+            self.with_retries(move || self.inner.read_subnet_state(subnet_id, envelope.clone())),
+        )
+    }
+
+    fn query(&self, effective_canister_id: Principal, envelope: Vec<u8>) -> AgentFuture<Vec<u8>> {
+        Box::pin(
+            self.with_retries(move || self.inner.query(effective_canister_id, envelope.clone())),
+        )
+    }
+
+    fn status(&self) -> AgentFuture<Vec<u8>> {
+        Box::pin(self.with_retries(move || self.inner.status()))
+    }
+}
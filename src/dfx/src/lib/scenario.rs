@@ -0,0 +1,110 @@
+//! Scenarios of canister calls, recorded with `dfx record` and re-executed with `dfx replay`.
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use anyhow::{bail, Context};
+use dfx_core::config::model::dfinity::Config;
+use dfx_core::json::{load_json_file, save_json_file};
+use fn_error_context::context;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The recording in progress, in the temp directory of the project.
+const RECORDING_FILE: &str = "recording.json";
+
+/// A sequence of calls, in the order they were made.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Scenario {
+    pub calls: Vec<RecordedCall>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecordedCall {
+    /// The name of the canister in the project, or its id for other canisters,
+    /// so that the scenario can be replayed against a fresh deployment.
+    pub canister: String,
+    pub method: String,
+    /// The name of the identity that made the call.
+    pub identity: String,
+    pub query: bool,
+    /// The Candid-encoded argument.
+    #[serde(with = "hex")]
+    pub argument: Vec<u8>,
+    /// The Candid-encoded response.
+    #[serde(with = "hex")]
+    pub response: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Recording {
+    output: PathBuf,
+    scenario: Scenario,
+}
+
+fn recording_path(config: &Config) -> PathBuf {
+    config.get_temp_path().join(RECORDING_FILE)
+}
+
+#[context("Failed to start recording.")]
+pub fn start_recording(config: &Config, output: &Path) -> DfxResult {
+    let path = recording_path(config);
+    if path.exists() {
+        bail!("A recording is already in progress. Stop it with `dfx record stop` first.");
+    }
+    dfx_core::fs::composite::ensure_parent_dir_exists(&path)?;
+    // The scenario is written when the recording stops, possibly from another directory.
+    let output = std::env::current_dir()
+        .context("Failed to get current working directory.")?
+        .join(output);
+    let recording = Recording {
+        output,
+        scenario: Scenario::default(),
+    };
+    save_json_file(&path, &recording)?;
+    Ok(())
+}
+
+/// Saves the recorded scenario to the file given when the recording started.
+/// Returns the file and the number of recorded calls.
+#[context("Failed to stop recording.")]
+pub fn stop_recording(config: &Config) -> DfxResult<(PathBuf, usize)> {
+    let path = recording_path(config);
+    if !path.exists() {
+        bail!("No recording is in progress. Start one with `dfx record start`.");
+    }
+    let recording: Recording = load_json_file(&path)?;
+    save_scenario(&recording.output, &recording.scenario)?;
+    dfx_core::fs::remove_file(&path)?;
+    Ok((recording.output, recording.scenario.calls.len()))
+}
+
+/// Whether `dfx record start` was run in the project, and not stopped yet.
+pub fn is_recording(env: &dyn Environment) -> bool {
+    env.get_config()
+        .map_or(false, |config| recording_path(&config).exists())
+}
+
+/// Appends a call to the recording in progress, if any.
+#[context("Failed to record call.")]
+pub fn record_call(env: &dyn Environment, call: RecordedCall) -> DfxResult {
+    let Some(config) = env.get_config() else {
+        return Ok(());
+    };
+    let path = recording_path(&config);
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut recording: Recording = load_json_file(&path)?;
+    recording.scenario.calls.push(call);
+    save_json_file(&path, &recording)?;
+    Ok(())
+}
+
+#[context("Failed to load scenario from {}.", path.display())]
+pub fn load_scenario(path: &Path) -> DfxResult<Scenario> {
+    Ok(load_json_file(path)?)
+}
+
+fn save_scenario(path: &Path, scenario: &Scenario) -> DfxResult {
+    save_json_file(path, scenario)?;
+    Ok(())
+}
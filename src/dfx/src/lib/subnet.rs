@@ -3,9 +3,12 @@ use anyhow::anyhow;
 use backoff::future::retry;
 use backoff::ExponentialBackoff;
 use candid::{CandidType, Deserialize, Principal};
+use fn_error_context::context;
+use ic_agent::hash_tree::Label;
 use ic_agent::{Agent, AgentError};
 use ic_utils::call::SyncCall;
 use ic_utils::Canister;
+use std::collections::BTreeSet;
 
 use super::retryable::retryable;
 
@@ -24,6 +27,7 @@ pub struct GetSubnetForCanisterResponse {
 
 pub async fn get_subnet_for_canister(
     agent: &Agent,
+    retry_policy: ExponentialBackoff,
     canister_id: Principal,
 ) -> DfxResult<Principal> {
     let registry_canister = Canister::builder()
@@ -31,8 +35,6 @@ pub async fn get_subnet_for_canister(
         .with_canister_id(MAINNET_REGISTRY_CANISTER_ID)
         .build()?;
 
-    let retry_policy = ExponentialBackoff::default();
-
     retry(retry_policy, || async {
         let arg = GetSubnetForCanisterRequest {
             principal: Some(canister_id),
@@ -65,6 +67,44 @@ pub async fn get_subnet_for_canister(
     .await
 }
 
+/// What a subnet certifies about itself in its state tree.
+pub struct SubnetInfo {
+    pub node_count: usize,
+    pub num_canisters: u64,
+    pub canister_state_bytes: u64,
+    pub consumed_cycles_total: u128,
+    pub update_transactions_total: u64,
+}
+
+#[context("Failed to read the state of subnet {}.", subnet_id)]
+pub async fn get_subnet_info(agent: &Agent, subnet_id: Principal) -> DfxResult<SubnetInfo> {
+    let metrics = agent.read_state_subnet_metrics(subnet_id).await?;
+
+    // The certificate lists subnet/<subnet id>/node/<node id>/public_key for each node.
+    let paths = vec![vec![
+        "subnet".into(),
+        Label::from_bytes(subnet_id.as_slice()),
+        "node".into(),
+    ]];
+    let certificate = agent.read_subnet_state_raw(paths, subnet_id).await?;
+    let node_count = certificate
+        .tree
+        .list_paths()
+        .into_iter()
+        .filter(|path| path.len() > 3 && path[2].as_bytes() == b"node")
+        .map(|path| path[3].as_bytes().to_vec())
+        .collect::<BTreeSet<_>>()
+        .len();
+
+    Ok(SubnetInfo {
+        node_count,
+        num_canisters: metrics.num_canisters,
+        canister_state_bytes: metrics.canister_state_bytes,
+        consumed_cycles_total: metrics.consumed_cycles_total,
+        update_transactions_total: metrics.update_transactions_total,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,135 @@
+//! Opt-in, local-only record of dfx invocations.
+//!
+//! When enabled, every command appends one event to `telemetry/events.jsonl` in the dfx config
+//! directory. Events contain the command path (never its arguments), how long it ran, and a
+//! coarse category of the error it failed with, if any. Nothing is sent anywhere: the log is only
+//! read by `dfx diagnostics report`, which bundles it for the user to attach to a support request.
+use crate::config::dfx_version_str;
+use crate::lib::error::DfxResult;
+use anyhow::Error;
+use dfx_core::config::directories::get_user_dfx_config_dir;
+use dfx_core::error::cache::CacheError;
+use dfx_core::error::cli::UserConsent;
+use dfx_core::error::structured_file::StructuredFileError;
+use dfx_core::json::{load_json_file, save_json_file};
+use ic_agent::AgentError;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// Environment variable that overrides the stored setting: `local` enables recording, `off` disables it.
+const TELEMETRY_ENV_VAR: &str = "DFX_TELEMETRY";
+
+/// The log is truncated to its most recent half once it grows past this size.
+const MAX_LOG_SIZE: u64 = 4 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Default)]
+struct TelemetryConfig {
+    enabled: bool,
+}
+
+/// One invocation of dfx, as recorded in the event log.
+#[derive(Serialize, Deserialize)]
+pub struct TelemetryEvent {
+    pub timestamp: OffsetDateTime,
+    pub dfx_version: String,
+    /// The subcommand path, e.g. `canister install`. Arguments are never recorded.
+    pub command: String,
+    pub duration_ms: u128,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_category: Option<String>,
+}
+
+pub fn get_telemetry_dir() -> DfxResult<PathBuf> {
+    Ok(get_user_dfx_config_dir()?.join("telemetry"))
+}
+
+pub fn get_event_log_path() -> DfxResult<PathBuf> {
+    Ok(get_telemetry_dir()?.join("events.jsonl"))
+}
+
+fn get_config_path() -> DfxResult<PathBuf> {
+    Ok(get_telemetry_dir()?.join("config.json"))
+}
+
+/// Whether events are recorded. Recording is off unless the user opted in.
+pub fn is_enabled() -> bool {
+    match std::env::var(TELEMETRY_ENV_VAR).as_deref() {
+        Ok("local") => return true,
+        Ok("off") => return false,
+        _ => {}
+    }
+    get_config_path()
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| load_json_file::<TelemetryConfig>(&path).ok())
+        .map_or(false, |config| config.enabled)
+}
+
+pub fn set_enabled(enabled: bool) -> DfxResult {
+    dfx_core::fs::create_dir_all(&get_telemetry_dir()?)?;
+    save_json_file(&get_config_path()?, &TelemetryConfig { enabled })?;
+    Ok(())
+}
+
+/// Appends an event for a finished command, if recording is enabled.
+/// Failing to record is never an error for the command itself.
+pub fn record_event(command: &str, duration: Duration, result: &DfxResult) {
+    if !is_enabled() {
+        return;
+    }
+    let event = TelemetryEvent {
+        timestamp: OffsetDateTime::now_utc(),
+        dfx_version: dfx_version_str().to_string(),
+        command: command.to_string(),
+        duration_ms: duration.as_millis(),
+        success: result.is_ok(),
+        error_category: result
+            .as_ref()
+            .err()
+            .map(|e| categorize_error(e).to_string()),
+    };
+    let _ = append_event(&event);
+}
+
+fn append_event(event: &TelemetryEvent) -> DfxResult {
+    let path = get_event_log_path()?;
+    dfx_core::fs::create_dir_all(&get_telemetry_dir()?)?;
+    if path.exists() && dfx_core::fs::metadata(&path)?.len() > MAX_LOG_SIZE {
+        let content = dfx_core::fs::read_to_string(&path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let kept = lines[lines.len() / 2..].join("\n");
+        dfx_core::fs::write(&path, format!("{kept}\n"))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(event)?)?;
+    Ok(())
+}
+
+/// Reduces an error to a category that does not contain any user data.
+fn categorize_error(err: &Error) -> &'static str {
+    for cause in err.chain() {
+        if cause.is::<UserConsent>() {
+            return "user-declined";
+        }
+        if cause.is::<AgentError>() {
+            return "agent";
+        }
+        if cause.is::<CacheError>() {
+            return "cache";
+        }
+        if cause.is::<StructuredFileError>() {
+            return "config";
+        }
+        if cause.is::<std::io::Error>() {
+            return "io";
+        }
+    }
+    "other"
+}
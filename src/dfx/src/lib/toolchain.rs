@@ -0,0 +1,72 @@
+//! Other versions of dfx, installed into the cache so that projects that pin a version
+//! with the "dfx" field of dfx.json can run it. dfx forwards the commands of such projects
+//! to the dfx binary in the cache of that version.
+use crate::config::cache::DiskBasedCache;
+use crate::config::dfx_version;
+use crate::lib::error::DfxResult;
+use crate::util::download_file;
+use anyhow::{bail, Context};
+use dfx_core::config::cache::{get_bin_cache_root, get_binary_path_from_version};
+use flate2::read::GzDecoder;
+use reqwest::Url;
+use semver::Version;
+use slog::{info, Logger};
+use std::io::Cursor;
+use std::process::Command;
+use tar::Archive;
+
+const DFX_RELEASES_URL: &str = "https://github.com/dfinity/sdk/releases/download";
+
+/// Whether the dfx of this version is in the cache, so that commands can be forwarded to it.
+pub fn is_installed(version: &Version) -> DfxResult<bool> {
+    Ok(get_binary_path_from_version(&version.to_string(), "dfx")?.is_file())
+}
+
+fn release_url(version: &Version) -> DfxResult<Url> {
+    let platform = match std::env::consts::OS {
+        "linux" => "x86_64-linux",
+        "macos" => "x86_64-darwin",
+        os => bail!("There are no dfx releases for {os}."),
+    };
+    let url = format!("{DFX_RELEASES_URL}/{version}/dfx-{version}-{platform}.tar.gz");
+    Url::parse(&url).with_context(|| format!("Invalid release url {url}."))
+}
+
+/// Downloads the release of this version of dfx, and lets it install its own cache,
+/// which includes a copy of the dfx binary.
+pub async fn install(logger: &Logger, version: &Version) -> DfxResult {
+    if is_installed(version)? {
+        info!(logger, "dfx {version} is already installed.");
+        return Ok(());
+    }
+    if version == dfx_version() {
+        DiskBasedCache::install(&version.to_string())?;
+        info!(logger, "Installed dfx {version}.");
+        return Ok(());
+    }
+
+    let url = release_url(version)?;
+    info!(logger, "Downloading dfx {version} from {url}.");
+    let tarball = download_file(&url).await?;
+    let temp_dir = tempfile::tempdir_in(get_bin_cache_root()?)
+        .context("Failed to create a temporary directory.")?;
+    Archive::new(GzDecoder::new(Cursor::new(tarball)))
+        .unpack(temp_dir.path())
+        .with_context(|| format!("Failed to unpack {url}."))?;
+    let dfx = temp_dir.path().join("dfx");
+    if !dfx.is_file() {
+        bail!("The release archive {url} does not contain dfx.");
+    }
+
+    let status = Command::new(&dfx)
+        .args(["cache", "install"])
+        .current_dir(temp_dir.path())
+        .env_remove("DFX_VERSION")
+        .status()
+        .with_context(|| format!("Failed to run {}.", dfx.display()))?;
+    if !status.success() || !is_installed(version)? {
+        bail!("dfx {version} failed to install its cache.");
+    }
+    info!(logger, "Installed dfx {version}.");
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use crate::lib::error::DfxResult;
+use anyhow::bail;
+use slog::{warn, Logger};
+
+/// Checks the hash of a wasm module against the `allowed_wasm_hashes` of a canister in dfx.json.
+/// Without an allowlist, any module is accepted.
+/// With `allow_unverified`, a module that is not in the allowlist is installed anyway, with a warning.
+pub fn verify_wasm_hash(
+    logger: &Logger,
+    canister: &str,
+    allowed_wasm_hashes: Option<&[String]>,
+    module_hash: &[u8],
+    allow_unverified: bool,
+) -> DfxResult {
+    let Some(allowed_wasm_hashes) = allowed_wasm_hashes else {
+        return Ok(());
+    };
+    let module_hash = hex::encode(module_hash);
+    if allowed_wasm_hashes
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(&module_hash))
+    {
+        return Ok(());
+    }
+    if allow_unverified {
+        warn!(
+            logger,
+            "Installing wasm module with hash {} in canister '{}', which is not in its allowed_wasm_hashes.",
+            module_hash,
+            canister
+        );
+        Ok(())
+    } else {
+        bail!(
+            "The wasm module for canister '{}' has hash {}, which is not in its allowed_wasm_hashes in dfx.json. Pass --allow-unverified to install it anyway.",
+            canister,
+            module_hash
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use sha2::{Digest, Sha256};
+    use std::io::Write;
+
+    const MODULE: &[u8] = b"\0asm\x01\0\0\0";
+
+    fn logger() -> Logger {
+        Logger::root(slog::Discard, slog::o!())
+    }
+
+    fn hash(module: &[u8]) -> Vec<u8> {
+        Sha256::digest(module).to_vec()
+    }
+
+    #[test]
+    fn accepts_allowed_hash() {
+        let allowed = [hex::encode(hash(MODULE)).to_uppercase()];
+        verify_wasm_hash(&logger(), "app", Some(&allowed[..]), &hash(MODULE), false).unwrap();
+        verify_wasm_hash(&logger(), "app", None, &hash(MODULE), false).unwrap();
+    }
+
+    #[test]
+    fn rejects_other_hash() {
+        let allowed = [hex::encode(hash(b"other"))];
+        let err = verify_wasm_hash(&logger(), "app", Some(&allowed[..]), &hash(MODULE), false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(&hex::encode(hash(MODULE))), "{err}");
+        verify_wasm_hash(&logger(), "app", Some(&allowed[..]), &hash(MODULE), true).unwrap();
+    }
+
+    #[test]
+    fn checks_hash_of_gzipped_module() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(MODULE).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        // The allowlist holds the hash of the module as it is installed, which is the gzipped one.
+        let allowed = [hex::encode(hash(&gzipped))];
+        verify_wasm_hash(&logger(), "app", Some(&allowed[..]), &hash(&gzipped), false).unwrap();
+        let allowed = [hex::encode(hash(MODULE))];
+        verify_wasm_hash(&logger(), "app", Some(&allowed[..]), &hash(&gzipped), false).unwrap_err();
+    }
+}
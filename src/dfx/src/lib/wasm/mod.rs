@@ -1 +1,2 @@
+pub mod allowlist;
 pub mod file;
@@ -0,0 +1,177 @@
+//! A reverse proxy for developing frontends with a dev server such as webpack or vite.
+//!
+//! Requests to the API of the network go to the network, and everything else goes to
+//! the dev server, so the frontend can call canisters from the same origin it is served from.
+use crate::lib::error::DfxResult;
+use anyhow::Context;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderValue, HOST, UPGRADE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Request, Response, Server, StatusCode, Uri};
+use hyper_rustls::HttpsConnector;
+use slog::{debug, Logger};
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use url::Url;
+
+/// Requests with this path, or a path below it, go to the network.
+pub const API_PATH: &str = "/api";
+
+pub struct DevProxyConfig {
+    pub bind: SocketAddr,
+    pub network_url: Url,
+    pub dev_server_url: Url,
+}
+
+struct DevProxy {
+    network_url: Url,
+    dev_server_url: Url,
+    client: Client<HttpsConnector<HttpConnector>>,
+    logger: Logger,
+}
+
+/// Serves the proxy until `shutdown` completes.
+pub async fn run_dev_proxy(
+    config: DevProxyConfig,
+    logger: Logger,
+    shutdown: impl Future<Output = ()>,
+) -> DfxResult {
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_webpki_roots()
+        .https_or_http()
+        .enable_http1()
+        .build();
+    let proxy = Arc::new(DevProxy {
+        network_url: config.network_url,
+        dev_server_url: config.dev_server_url,
+        client: Client::builder().build(connector),
+        logger,
+    });
+    let make_service = make_service_fn(move |_| {
+        let proxy = proxy.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let proxy = proxy.clone();
+                async move { Ok::<_, Infallible>(proxy.forward(request).await) }
+            }))
+        }
+    });
+    Server::try_bind(&config.bind)
+        .with_context(|| format!("Failed to bind to {}.", config.bind))?
+        .serve(make_service)
+        .with_graceful_shutdown(shutdown)
+        .await
+        .context("The proxy failed.")?;
+    Ok(())
+}
+
+impl DevProxy {
+    async fn forward(&self, mut request: Request<Body>) -> Response<Body> {
+        let upstream = if is_api_path(request.uri().path()) {
+            &self.network_url
+        } else {
+            &self.dev_server_url
+        };
+        let uri = match upstream_uri(upstream, request.uri()) {
+            Ok(uri) => uri,
+            Err(err) => return error_response(StatusCode::BAD_REQUEST, format!("{:#}", err)),
+        };
+        debug!(
+            self.logger,
+            "{} {} -> {}",
+            request.method(),
+            request.uri(),
+            uri
+        );
+
+        // The dev server pushes updates through a websocket, which is passed through as is.
+        let client_upgrade = request
+            .headers()
+            .contains_key(UPGRADE)
+            .then(|| hyper::upgrade::on(&mut request));
+        let (mut parts, body) = request.into_parts();
+        if let Some(host) = uri
+            .authority()
+            .and_then(|authority| HeaderValue::from_str(authority.as_str()).ok())
+        {
+            parts.headers.insert(HOST, host);
+        }
+        parts.uri = uri;
+
+        match self.client.request(Request::from_parts(parts, body)).await {
+            Ok(mut response) => {
+                if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+                    if let Some(client_upgrade) = client_upgrade {
+                        let upstream_upgrade = hyper::upgrade::on(&mut response);
+                        tokio::spawn(async move {
+                            if let (Ok(mut client), Ok(mut upstream)) =
+                                (client_upgrade.await, upstream_upgrade.await)
+                            {
+                                let _ =
+                                    tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+                            }
+                        });
+                    }
+                }
+                response
+            }
+            Err(err) => error_response(
+                StatusCode::BAD_GATEWAY,
+                format!("Failed to reach {}: {}", upstream, err),
+            ),
+        }
+    }
+}
+
+fn is_api_path(path: &str) -> bool {
+    path == API_PATH
+        || path
+            .strip_prefix(API_PATH)
+            .map_or(false, |rest| rest.starts_with('/'))
+}
+
+/// The URI of the request at the upstream server: its path and query appended to the upstream URL.
+fn upstream_uri(upstream: &Url, uri: &Uri) -> DfxResult<Uri> {
+    let path_and_query = uri.path_and_query().map_or("/", |pq| pq.as_str());
+    let upstream = upstream.as_str().trim_end_matches('/');
+    format!("{}{}", upstream, path_and_query)
+        .parse()
+        .with_context(|| format!("Invalid request path {}.", path_and_query))
+}
+
+fn error_response(status: StatusCode, message: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(message));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_paths() {
+        assert!(is_api_path("/api"));
+        assert!(is_api_path("/api/v2/status"));
+        assert!(!is_api_path("/apidocs"));
+        assert!(!is_api_path("/"));
+        assert!(!is_api_path("/src/api/index.js"));
+    }
+
+    #[test]
+    fn upstream_uris() {
+        let uri: Uri = "/api/v2/status?x=1".parse().unwrap();
+        let upstream = Url::parse("http://127.0.0.1:4943").unwrap();
+        assert_eq!(
+            upstream_uri(&upstream, &uri).unwrap(),
+            "http://127.0.0.1:4943/api/v2/status?x=1"
+        );
+        let upstream = Url::parse("http://localhost:5173/").unwrap();
+        assert_eq!(
+            upstream_uri(&upstream, &"/".parse().unwrap()).unwrap(),
+            "http://localhost:5173/"
+        );
+    }
+}
@@ -3,15 +3,17 @@ use crate::config::{dfx_version, dfx_version_str};
 use crate::lib::diagnosis::{diagnose, Diagnosis, NULL_DIAGNOSIS};
 use crate::lib::environment::{Environment, EnvironmentImpl};
 use crate::lib::error::DfxResult;
-use crate::lib::logger::{create_root_logger, LoggingMode};
+use crate::lib::logger::{create_root_logger, LogFilter, LogFormat, LoggingMode};
+use crate::lib::toolchain;
 use crate::lib::warning::{is_warning_disabled, DfxWarning::VersionCheck};
 use anyhow::Error;
-use clap::{ArgAction, CommandFactory, Parser};
+use clap::{ArgAction, ArgMatches, CommandFactory, FromArgMatches, Parser};
 use dfx_core::extension::manager::ExtensionManager;
 use semver::Version;
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::time::Instant;
 
 mod actors;
 mod commands;
@@ -39,14 +41,28 @@ pub struct CliOpts {
     #[arg(long, global = true)]
     logfile: Option<String>,
 
+    /// The format of the messages written to stderr. With json, every message is a JSON object
+    /// on its own line, and the progress of build, create, install and deploy steps is reported as events.
+    #[arg(long, default_value = "text", value_parser = ["text", "json"], global = true)]
+    log_format: String,
+
     /// The user identity to run this command as. It contains your principal as well as some things DFX associates with it like the wallet.
     #[arg(long, env = "DFX_IDENTITY", global = true)]
     identity: Option<String>,
 
+    /// The environment overlay to merge over dfx.json: the 'envs.<ENV>' section of dfx.json and the dfx.<ENV>.json file.
+    #[arg(long, env = "DFX_ENV", global = true)]
+    env: Option<String>,
+
     /// The effective canister id for provisional canister creation must be a canister id in the canister ranges of the subnet on which new canisters should be created.
     #[arg(long, global = true, value_name = "PRINCIPAL")]
     provisional_create_canister_effective_canister_id: Option<String>,
 
+    /// The directory to use instead of the .dfx directory of the project.
+    /// It also holds the data of shared networks and the cache, unless DFX_CACHE_ROOT is set.
+    #[arg(long, env = "DFX_STATE_DIR", global = true)]
+    state_dir: Option<PathBuf>,
+
     #[command(subcommand)]
     command: commands::DfxCommand,
 }
@@ -61,19 +77,34 @@ fn maybe_redirect_dfx(version: &Version) -> Option<()> {
     // Verify we're using the same version as the dfx.json, and if not just redirect the
     // call to the cache.
     if dfx_version() != version {
+        let pinned_by = match std::env::var("DFX_VERSION") {
+            Ok(v) if !v.is_empty() => "DFX_VERSION is set",
+            _ => "the \"dfx\" field of dfx.json pins it",
+        };
+        if !toolchain::is_installed(version).unwrap_or(false) {
+            eprintln!(
+                "Error: This project uses dfx {version}, because {pinned_by}, but dfx {} is running and dfx {version} is not installed.\n\
+                To install it, run `dfx toolchain install {version}`.\n\
+                To use the running version in this project instead, run `dfx toolchain use {}`.",
+                dfx_version(),
+                dfx_version()
+            );
+            std::process::exit(1)
+        }
+
         // Show a warning to the user.
         if !is_warning_disabled(VersionCheck) {
             eprintln!(
                 concat!(
                     "Warning: The version of DFX used ({}) is different than the version ",
                     "being run ({}).\n",
-                    "This might happen because your dfx.json specifies an older version, or ",
-                    "DFX_VERSION is set in your environment.\n",
+                    "This happens because {}.\n",
                     "We are forwarding the command line to the old version. To disable this ",
                     "warning, set the DFX_WARNING=-version_check environment variable.\n"
                 ),
                 version,
-                dfx_version()
+                dfx_version(),
+                pinned_by
             );
         }
 
@@ -91,8 +122,8 @@ fn maybe_redirect_dfx(version: &Version) -> Option<()> {
 }
 
 /// Setup a logger with the proper configuration, based on arguments.
-/// Returns a topple of whether or not to have a progress bar, and a logger.
-fn setup_logging(opts: &CliOpts) -> (i64, slog::Logger) {
+/// Returns a triple of the verbosity level, the log format, and a logger.
+fn setup_logging(opts: &CliOpts) -> (i64, LogFormat, slog::Logger) {
     // Create a logger with our argument matches.
     let verbose_level = opts.verbose as i64 - opts.quiet as i64;
 
@@ -102,7 +133,25 @@ fn setup_logging(opts: &CliOpts) -> (i64, slog::Logger) {
         _ => LoggingMode::Stderr,
     };
 
-    (verbose_level, create_root_logger(verbose_level, mode))
+    let format = match opts.log_format.as_str() {
+        "json" => LogFormat::Json,
+        _ => LogFormat::Text,
+    };
+
+    // An invalid filter is reported once the logger exists, rather than failing every command.
+    let (filter, filter_error) = match std::env::var("DFX_LOG") {
+        Ok(spec) => match LogFilter::parse(&spec) {
+            Ok(filter) => (filter, None),
+            Err(err) => (LogFilter::default(), Some(err)),
+        },
+        Err(_) => (LogFilter::default(), None),
+    };
+    let log = create_root_logger(verbose_level, filter, mode, format);
+    if let Some(err) = filter_error {
+        slog::warn!(log, "{} Ignoring DFX_LOG.", err);
+    }
+
+    (verbose_level, format, log)
 }
 
 fn print_error_and_diagnosis(err: Error, error_diagnosis: Diagnosis) {
@@ -181,6 +230,17 @@ fn get_args_altered_for_extension_run() -> DfxResult<Vec<OsString>> {
     Ok(args)
 }
 
+/// The names of the subcommands that were invoked, e.g. `canister install`, without any arguments.
+fn get_command_path(matches: &ArgMatches) -> String {
+    let mut path = vec![];
+    let mut matches = matches;
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        path.push(name);
+        matches = sub_matches;
+    }
+    path.join(" ")
+}
+
 fn main() {
     let args = get_args_altered_for_extension_run().unwrap_or_else(|err| {
         print_error_and_diagnosis(err, NULL_DIAGNOSIS);
@@ -189,19 +249,31 @@ fn main() {
 
     let mut error_diagnosis: Diagnosis = NULL_DIAGNOSIS;
 
-    let cli_opts = CliOpts::parse_from(args);
-    let (verbose_level, log) = setup_logging(&cli_opts);
+    let matches = CliOpts::command().get_matches_from(args);
+    let command_path = get_command_path(&matches);
+    let cli_opts = CliOpts::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let start = Instant::now();
+    if let Some(state_dir) = &cli_opts.state_dir {
+        // dfx-core and the processes dfx starts read the state directory from the environment.
+        std::env::set_var("DFX_STATE_DIR", state_dir);
+    }
+    let (verbose_level, log_format, log) = setup_logging(&cli_opts);
     let identity = cli_opts.identity;
+    let overlay = cli_opts.env;
     let effective_canister_id = cli_opts.provisional_create_canister_effective_canister_id;
     let command = cli_opts.command;
-    let result = match EnvironmentImpl::new() {
+    let result = match EnvironmentImpl::new(overlay.as_deref()) {
         Ok(env) => {
-            #[allow(clippy::let_unit_value)]
-            let _ = maybe_redirect_dfx(env.get_version()).map_or((), |_| unreachable!());
-            match EnvironmentImpl::new().map(|env| {
+            // The toolchain commands manage the pinned version, so they always run as this version.
+            if !matches!(command, commands::DfxCommand::Toolchain(_)) {
+                #[allow(clippy::let_unit_value)]
+                let _ = maybe_redirect_dfx(env.get_version()).map_or((), |_| unreachable!());
+            }
+            match EnvironmentImpl::new(overlay.as_deref()).map(|env| {
                 env.with_logger(log)
                     .with_identity_override(identity)
                     .with_verbose_level(verbose_level)
+                    .with_log_format(log_format)
                     .with_effective_canister_id(effective_canister_id)
             }) {
                 Ok(env) => {
@@ -225,6 +297,7 @@ fn main() {
             _ => Err(e),
         },
     };
+    lib::telemetry::record_event(&command_path, start.elapsed(), &result);
     if let Err(err) = result {
         print_error_and_diagnosis(err, error_diagnosis);
         std::process::exit(255);
@@ -108,6 +108,28 @@ pub fn bitcoin_wasm(logger: &slog::Logger) -> DfxResult<Vec<u8>> {
     }
 }
 
+pub fn ui_canister_wasm() -> DfxResult<Vec<u8>> {
+    let mut canister_assets = ui_canister().context("Failed to get ui canister assets.")?;
+    for file in canister_assets
+        .entries()
+        .context("Failed to get ui canister asset entries.")?
+    {
+        let mut file = file.context("Failed to examine archive entry.")?;
+        if file
+            .header()
+            .path()
+            .context("Failed to get archive entry path.")?
+            .ends_with("ui.wasm")
+        {
+            let mut wasm = vec![];
+            file.read_to_end(&mut wasm)
+                .context("Failed to read wasm.")?;
+            return Ok(wasm);
+        }
+    }
+    bail!("Failed to find ui canister archive entry");
+}
+
 pub fn management_idl() -> DfxResult<String> {
     // FIXME get idl from replica when it's available
     // Pulled from https://github.com/dfinity/interface-spec/blob/master/spec/_attachments/ic.did
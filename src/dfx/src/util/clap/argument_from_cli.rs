@@ -19,13 +19,19 @@
 //! Beyond that, the name of the field for the argument type is also different:
 //!   - In [ArgumentFromCliLongOpt], it is [argument_type](ArgumentFromCliLongOpt::argument_type).
 //!   - In [ArgumentFromCliPositionalOpt], it is [type](ArgumentFromCliPositionalOpt::type).
+//!
+//! [ArgumentFromCliPositionalOpt] can also splice the contents of files into the argument,
+//! with [arg_file_as_blob](ArgumentFromCliPositionalOpt::arg_file_as_blob) and
+//! [arg_file_as_candid](ArgumentFromCliPositionalOpt::arg_file_as_candid).
+use std::fmt::Write;
 use std::path::PathBuf;
 
-use clap::Args;
+use anyhow::bail;
+use clap::{ArgAction, Args};
 
 use crate::lib::error::DfxResult;
 use crate::util::arguments_from_file;
-use crate::util::clap::parsers::file_or_stdin_parser;
+use crate::util::clap::parsers::{file_or_stdin_parser, file_placeholder_parser};
 
 /// CLI options for specifying an argument to pass to a method.
 /// In which, [argument](Self::argument) is a "long" option.
@@ -69,11 +75,30 @@ pub struct ArgumentFromCliPositionalOpt {
     /// Specifies the file from which to read the argument to pass to the method.
     #[arg(long, value_parser = file_or_stdin_parser, conflicts_with("argument"))]
     argument_file: Option<PathBuf>,
+
+    /// Replaces the placeholder ${NAME} in the argument with the bytes of the file at PATH, as a Candid blob.
+    /// Can be specified more than once. Without an argument, the argument is the blob of the only file given.
+    #[arg(long, value_name = "NAME=PATH", value_parser = file_placeholder_parser, action = ArgAction::Append)]
+    arg_file_as_blob: Vec<(String, PathBuf)>,
+
+    /// Replaces the placeholder ${NAME} in the argument with the contents of the file at PATH, which must be Candid text.
+    /// Can be specified more than once.
+    #[arg(long, value_name = "NAME=PATH", value_parser = file_placeholder_parser, action = ArgAction::Append)]
+    arg_file_as_candid: Vec<(String, PathBuf)>,
 }
 
 impl ArgumentFromCliPositionalOpt {
     pub fn get_argument_and_type(&self) -> DfxResult<(Option<String>, Option<String>)> {
-        get_argument_from_cli(&self.argument, &self.r#type, &self.argument_file)
+        let (argument, argument_type) =
+            get_argument_from_cli(&self.argument, &self.r#type, &self.argument_file)?;
+        if self.arg_file_as_blob.is_empty() && self.arg_file_as_candid.is_empty() {
+            return Ok((argument, argument_type));
+        }
+        if argument_type.as_deref() == Some("raw") {
+            bail!("Files can only be spliced into arguments of type idl.");
+        }
+        let argument = splice_files(argument, &self.arg_file_as_blob, &self.arg_file_as_candid)?;
+        Ok((Some(argument), argument_type))
     }
 }
 
@@ -90,3 +115,60 @@ fn get_argument_from_cli(
     let argument_from_cli = arguments_from_file.or(arguments);
     Ok((argument_from_cli, argument_type.clone()))
 }
+
+/// Replaces the `${NAME}` placeholders of the argument with the contents of files.
+fn splice_files(
+    argument: Option<String>,
+    blobs: &[(String, PathBuf)],
+    candid: &[(String, PathBuf)],
+) -> DfxResult<String> {
+    let mut argument = match argument {
+        Some(argument) => argument,
+        None => match (blobs, candid) {
+            ([(name, _)], []) | ([], [(name, _)]) => format!("(${{{name}}})"),
+            _ => bail!("Specify the argument, with a ${{NAME}} placeholder for each file."),
+        },
+    };
+    for (name, path, as_blob) in blobs
+        .iter()
+        .map(|(name, path)| (name, path, true))
+        .chain(candid.iter().map(|(name, path)| (name, path, false)))
+    {
+        let placeholder = format!("${{{name}}}");
+        if !argument.contains(&placeholder) {
+            bail!(
+                "The argument has no placeholder {placeholder} for {}.",
+                path.display()
+            );
+        }
+        let value = if as_blob {
+            candid_blob(&dfx_core::fs::read(path)?)
+        } else {
+            dfx_core::fs::read_to_string(path)?
+        };
+        argument = argument.replace(&placeholder, &value);
+    }
+    Ok(argument)
+}
+
+/// Renders bytes as a Candid `blob "..."` literal.
+fn candid_blob(bytes: &[u8]) -> String {
+    let mut blob = String::with_capacity(bytes.len() * 3 + 7);
+    blob.push_str("blob \"");
+    for byte in bytes {
+        write!(blob, "\\{byte:02x}").unwrap();
+    }
+    blob.push('"');
+    blob
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_blobs() {
+        assert_eq!(candid_blob(&[0, 0x41, 0xff]), r#"blob "\00\41\ff""#);
+        assert_eq!(candid_blob(&[]), r#"blob """#);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::lib::environment::Environment;
+use crate::lib::error::DfxResult;
+use crate::lib::init_presets::{init_preset_argument, InitPreset};
+use anyhow::anyhow;
+use clap::Args;
+
+/// CLI options for generating the install argument of a well-known canister.
+#[derive(Args, Clone, Debug, Default)]
+pub struct InitPresetOpt {
+    /// Generates the install argument of a well-known canister, instead of passing it with --argument.
+    #[arg(long, conflicts_with_all(["argument", "argument_file"]))]
+    init_preset: Option<InitPreset>,
+
+    /// A parameter of the preset, as <KEY>=<VALUE>. Can be repeated.
+    #[arg(long, requires("init_preset"), value_name = "KEY=VALUE", value_parser = preset_arg_parser)]
+    preset_arg: Vec<(String, String)>,
+}
+
+impl InitPresetOpt {
+    /// Returns the argument generated by the preset, in Candid text format, if one was selected.
+    pub fn get_argument(&self, env: &dyn Environment) -> DfxResult<Option<String>> {
+        let Some(preset) = self.init_preset else {
+            return Ok(None);
+        };
+        let caller = env
+            .get_selected_identity_principal()
+            .ok_or_else(|| anyhow!("The presets require an identity."))?;
+        init_preset_argument(preset, &self.preset_arg, caller).map(Some)
+    }
+}
+
+fn preset_arg_parser(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Expected <KEY>=<VALUE>, got '{arg}'."))
+}
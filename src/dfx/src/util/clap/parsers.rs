@@ -1,7 +1,7 @@
 use byte_unit::{Byte, ByteUnit};
 use icrc_ledger_types::icrc1::account::Subaccount;
 use rust_decimal::Decimal;
-use std::{path::PathBuf, str::FromStr};
+use std::{path::PathBuf, str::FromStr, time::Duration};
 
 /// Removes `_`, interprets `k`, `m`, `b`, `t` suffix (case-insensitive)
 fn decimal_with_suffix_parser(input: &str) -> Result<Decimal, String> {
@@ -86,6 +86,23 @@ pub fn file_or_stdin_parser(path: &str) -> Result<PathBuf, String> {
     }
 }
 
+/// Parses `NAME=PATH`, where NAME names a `${NAME}` placeholder in a Candid argument.
+pub fn file_placeholder_parser(input: &str) -> Result<(String, PathBuf), String> {
+    let (name, path) = input
+        .split_once('=')
+        .ok_or_else(|| "Expected NAME=PATH.".to_string())?;
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Invalid placeholder name '{name}': use letters, digits, '_' and '-' only."
+        ));
+    }
+    Ok((name.to_string(), file_parser(path)?))
+}
+
 pub fn trillion_cycle_amount_parser(input: &str) -> Result<u128, String> {
     if let Ok(cycles) = format!("{}000000000000", input.replace('_', "")).parse::<u128>() {
         Ok(cycles)
@@ -124,12 +141,27 @@ pub fn freezing_threshold_parser(freezing_threshold: &str) -> Result<u64, String
         .map_err(|_| "Must be a value between 0 and 2^64-1 inclusive".to_string())
 }
 
+pub fn wasm_memory_limit_parser(wasm_memory_limit: &str) -> Result<Byte, String> {
+    let limit = Byte::from_bytes(1 << 48);
+    if let Ok(bytes) = wasm_memory_limit.parse::<Byte>() {
+        if bytes <= limit {
+            return Ok(bytes);
+        }
+    }
+    Err("Must be a value between 0..256 TiB inclusive.".to_string())
+}
+
 pub fn reserved_cycles_limit_parser(reserved_cycles_limit: &str) -> Result<u128, String> {
     reserved_cycles_limit
         .parse::<u128>()
         .map_err(|_| "Must be a value between 0 and 2^128-1 inclusive".to_string())
 }
 
+/// Parses durations like `500ms`, `2s` or `1m 30s`.
+pub fn duration_parser(duration: &str) -> Result<Duration, String> {
+    humantime::parse_duration(duration).map_err(|err| err.to_string())
+}
+
 /// Validate a String can be a valid project name.
 /// A project name is valid if it starts with a letter, and is alphanumeric (with hyphens).
 /// It cannot end with a dash.
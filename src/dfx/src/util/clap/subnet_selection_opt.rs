@@ -5,6 +5,7 @@ use crate::lib::{
     cycles_ledger_types::create_canister::{SubnetFilter, SubnetSelection},
     environment::Environment,
     error::DfxResult,
+    retryable::retry_policy,
     subnet::get_subnet_for_canister,
 };
 
@@ -36,7 +37,8 @@ impl SubnetSelectionOpt {
         if let Some(sibling) = self.next_to {
             let next_to = Principal::from_text(&sibling)
                 .or_else(|_| env.get_canister_id_store()?.get(&sibling))?;
-            let subnet = get_subnet_for_canister(env.get_agent(), next_to).await?;
+            let subnet =
+                get_subnet_for_canister(env.get_agent(), retry_policy(env), next_to).await?;
             Ok(Some(SubnetSelection::Subnet { subnet }))
         } else {
             Ok(self
@@ -1,14 +1,19 @@
 use crate::lib::{
-    error::DfxResult, nns_types::icpts::ICPTs, operations::ledger::xdr_permyriad_per_icp,
+    error::DfxResult,
+    nns_types::icpts::{ICPTs, ICP_SUBDIVIDABLE_BY},
+    operations::ledger::xdr_permyriad_per_icp,
 };
+use crate::util::format_as_trillions;
 use anyhow::Context;
 use dfx_core::config::model::dfinity::DEFAULT_IC_GATEWAY;
 use fn_error_context::context;
 use ic_agent::{agent::http_transport::ReqwestTransport, Agent};
+use num_traits::FromPrimitive;
 use rust_decimal::Decimal;
+use slog::{warn, Logger};
 
 /// How many cycles you get per XDR when converting ICP to cycles
-const CYCLES_PER_XDR: u128 = 1_000_000_000_000;
+pub const CYCLES_PER_XDR: u128 = 1_000_000_000_000;
 
 /// This returns how many cycles the amount of ICP/e8s is currently worth.
 /// Fetches the exchange rate from the (hardcoded) IC network.
@@ -22,8 +27,73 @@ pub async fn as_cycles_with_current_exchange_rate(icpts: &ICPTs) -> DfxResult<u1
         .build()
         .context("Cannot create mainnet agent.")?;
     let xdr_permyriad_per_icp = xdr_permyriad_per_icp(&agent).await?;
-    let xdr_per_icp = Decimal::from_i128_with_scale(xdr_permyriad_per_icp as i128, 4);
-    let xdr = xdr_per_icp * icpts.to_decimal();
-    let cycles = xdr * Decimal::from(CYCLES_PER_XDR);
-    Ok(u128::try_from(cycles)?)
+    icp_to_cycles(xdr_permyriad_per_icp, icpts)
+}
+
+/// Converts the rate returned by the cycles minting canister into XDR per ICP.
+pub fn xdr_per_icp(xdr_permyriad_per_icp: u64) -> Decimal {
+    Decimal::from_i128_with_scale(xdr_permyriad_per_icp as i128, 4)
+}
+
+/// Returns how many cycles an amount of ICP is worth at the given rate, rounded down.
+pub fn icp_to_cycles(xdr_permyriad_per_icp: u64, icpts: &ICPTs) -> DfxResult<u128> {
+    let cycles =
+        xdr_per_icp(xdr_permyriad_per_icp) * icpts.to_decimal() * Decimal::from(CYCLES_PER_XDR);
+    Ok(u128::try_from(cycles.trunc())?)
+}
+
+/// Returns how much ICP is needed to get an amount of cycles at the given rate, rounded up to the next e8.
+pub fn cycles_to_icp(xdr_permyriad_per_icp: u64, cycles: u128) -> DfxResult<ICPTs> {
+    if xdr_permyriad_per_icp == 0 {
+        anyhow::bail!("The conversion rate of the cycles minting canister is zero.");
+    }
+    let cycles = Decimal::from_u128(cycles).context("The amount of cycles is too large.")?;
+    let icp = cycles / Decimal::from(CYCLES_PER_XDR) / xdr_per_icp(xdr_permyriad_per_icp);
+    let e8s = (icp * Decimal::from(ICP_SUBDIVIDABLE_BY)).ceil();
+    Ok(ICPTs::from_e8s(u64::try_from(e8s)?))
+}
+
+/// Prints the conversion rate of the cycles minting canister of the network, and how many cycles
+/// an amount of ICP is about to be converted into.
+/// Failing to fetch the rate only logs a warning, since it does not prevent the conversion.
+pub async fn print_cycles_estimate(agent: &Agent, logger: &Logger, amount: &ICPTs) {
+    let estimate = async {
+        let xdr_permyriad_per_icp = xdr_permyriad_per_icp(agent).await?;
+        let cycles = icp_to_cycles(xdr_permyriad_per_icp, amount)?;
+        DfxResult::Ok((xdr_per_icp(xdr_permyriad_per_icp), cycles))
+    };
+    match estimate.await {
+        Ok((xdr_per_icp, cycles)) => {
+            eprintln!("Conversion rate: 1 ICP <> {xdr_per_icp} XDR");
+            eprintln!(
+                "Converting {amount} into about {}T cycles ({cycles} cycles).",
+                format_as_trillions(cycles)
+            );
+        }
+        Err(err) => warn!(
+            logger,
+            "Failed to fetch the ICP <> XDR conversion rate: {err:#}"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_icp_and_cycles() {
+        // 1 ICP <> 4.5 XDR
+        let rate = 45_000;
+        assert_eq!(
+            icp_to_cycles(rate, &ICPTs::from_e8s(200_000_000)).unwrap(),
+            9_000_000_000_000
+        );
+        assert_eq!(
+            cycles_to_icp(rate, 9_000_000_000_000).unwrap(),
+            ICPTs::from_e8s(200_000_000)
+        );
+        // rounds up, so that the ICP is always enough
+        assert_eq!(cycles_to_icp(rate, 1).unwrap(), ICPTs::from_e8s(1));
+    }
 }
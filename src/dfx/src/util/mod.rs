@@ -10,6 +10,7 @@ use candid::{Decode, Encode, IDLArgs, Principal};
 use candid_parser::error::pretty_diagnose;
 use candid_parser::utils::CandidSource;
 use dfx_core::fs::create_dir_all;
+use flate2::write::GzEncoder;
 use fn_error_context::context;
 use num_traits::FromPrimitive;
 use reqwest::{Client, StatusCode, Url};
@@ -204,11 +205,7 @@ pub fn blob_from_arguments(
                     } else if func.args.is_empty() {
                         use candid::Encode;
                         Encode!()
-                    } else if func
-                        .args
-                        .iter()
-                        .all(|t| matches!(t.as_ref(), TypeInner::Opt(_)))
-                    {
+                    } else if !requires_arguments(func) {
                         // If the user provided no arguments, and if all the expected arguments are
                         // optional, then use null values.
                         let nulls = vec![IDLValue::Null; func.args.len()];
@@ -248,11 +245,18 @@ pub fn blob_from_arguments(
                         }
                         let args = input_args(&ctx, &func.args)?;
                         eprintln!("Sending the following argument:\n{}\n", args);
+                        let reusable = shell_quote(&single_line_candid(&args));
                         if is_init_arg {
+                            eprintln!(
+                                "To skip these prompts next time, pass --argument {reusable}\n"
+                            );
                             eprintln!(
                                 "Do you want to initialize the canister with this argument? [y/N]"
                             );
                         } else {
+                            eprintln!(
+                                "To skip these prompts next time, pass the argument {reusable}\n"
+                            );
                             eprintln!("Do you want to send this message? [y/N]");
                         }
                         let mut input = String::new();
@@ -261,6 +265,8 @@ pub fn blob_from_arguments(
                             return Err(error_invalid_data!("User cancelled."));
                         }
                         args.to_bytes_with_types(env, &func.args)
+                    } else if is_init_arg {
+                        return Err(error_invalid_data!("{}", missing_init_arg_message(func)));
                     } else {
                         return Err(error_invalid_data!("Expected arguments but found none."));
                     }
@@ -273,6 +279,54 @@ pub fn blob_from_arguments(
     }
 }
 
+/// Whether a call to the function needs arguments, because some of them are not optional.
+pub fn requires_arguments(func: &Function) -> bool {
+    !func
+        .args
+        .iter()
+        .all(|t| matches!(t.as_ref(), TypeInner::Opt(_)))
+}
+
+/// Explains which initialization argument a canister expects, and how to provide it.
+pub fn missing_init_arg_message(func: &Function) -> String {
+    let types = func.args.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+    format!(
+        "This canister requires an initialization argument of type ({}).\nSpecify it with --argument, or with init_arg in dfx.json.",
+        types.join(", ")
+    )
+}
+
+/// Renders Candid values on a single line, e.g. to paste them on the command line.
+pub fn single_line_candid(args: &IDLArgs) -> String {
+    // Line breaks of the pretty-printed values are only between tokens, since text escapes them.
+    args.to_string()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Adds a file with the given content to a gzipped tarball.
+pub fn append_file(
+    builder: &mut tar::Builder<GzEncoder<Vec<u8>>>,
+    path: &str,
+    content: &[u8],
+) -> DfxResult {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, path, content)
+        .with_context(|| format!("Failed to add {path} to the archive."))
+}
+
+/// Quotes a string for POSIX shells.
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
 pub fn gather_principals_from_env(env: &dyn Environment) -> BTreeMap<String, String> {
     let mut res: BTreeMap<String, String> = BTreeMap::new();
     if let Ok(mgr) = env.new_identity_manager() {
@@ -287,6 +341,15 @@ pub fn gather_principals_from_env(env: &dyn Environment) -> BTreeMap<String, Str
     res
 }
 
+/// Renders principals as a Candid `vec { principal "..."; ... }` literal.
+pub fn candid_principal_vec(principals: &[Principal]) -> String {
+    let principals: Vec<_> = principals
+        .iter()
+        .map(|principal| format!("principal \"{principal}\""))
+        .collect();
+    format!("vec {{ {} }}", principals.join("; "))
+}
+
 pub fn fuzzy_parse_argument(
     arg_str: &str,
     env: &TypeEnv,
@@ -433,7 +496,7 @@ async fn attempt_download(client: &Client, url: &Url) -> DfxResult<Option<Bytes>
 
 #[cfg(test)]
 mod tests {
-    use super::{format_as_trillions, pretty_thousand_separators};
+    use super::{format_as_trillions, pretty_thousand_separators, shell_quote, single_line_candid};
 
     #[test]
     fn prettify_balance_amount() {
@@ -499,4 +562,21 @@ mod tests {
             pretty_thousand_separators(format_as_trillions(u128::MAX))
         );
     }
+
+    #[test]
+    fn reusable_candid_literal() {
+        let args = candid_parser::parse_idl_args(
+            r#"(record { name = "it's a long name that does not fit"; tags = vec { "a"; "b"; "c"; "d"; "e" }; owner = opt principal "aaaaa-aa" }, variant { Ok = 1 : nat })"#,
+        )
+        .unwrap();
+        let line = single_line_candid(&args);
+        assert!(!line.contains('\n'));
+        assert_eq!(
+            candid_parser::parse_idl_args(&line).unwrap().args,
+            args.args
+        );
+
+        assert_eq!(shell_quote("(42 : nat)"), "'(42 : nat)'");
+        assert_eq!(shell_quote(r#"("it's")"#), r#"'("it'\''s")'"#);
+    }
 }